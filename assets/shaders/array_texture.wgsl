@@ -15,6 +15,10 @@ fn fragment(
     @builtin(front_facing) is_front: bool,
     mesh: VertexOutput,
 ) -> @location(0) vec4<f32> {
+#ifdef MULTIVIEW
+    bevy_pbr::mesh_view_bindings::select_view(mesh.view_index);
+#endif
+
     let layer = i32(mesh.world_position.x) & 0x3;
 
     // Prepare a 'processed' StandardMaterial by sampling all textures to resolve
@@ -11,6 +11,10 @@
 fn fragment(
     mesh: VertexOutput,
 ) -> @location(0) vec4<f32> {
+#ifdef MULTIVIEW
+    bevy_pbr::mesh_view_bindings::select_view(mesh.view_index);
+#endif
+
     let viewport_uv = coords_to_viewport_uv(mesh.position.xy, view.viewport);
     let color = textureSample(texture, texture_sampler, viewport_uv);
     return color;
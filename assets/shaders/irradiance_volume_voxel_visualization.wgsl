@@ -17,6 +17,10 @@ var<uniform> irradiance_volume_info: VoxelVisualizationIrradianceVolumeInfo;
 
 @fragment
 fn fragment(mesh: VertexOutput) -> @location(0) vec4<f32> {
+#ifdef MULTIVIEW
+    mesh_view_bindings::select_view(mesh.view_index);
+#endif
+
     // Snap the world position we provide to `irradiance_volume_light()` to the
     // middle of the nearest texel.
     var unit_pos = (irradiance_volume_info.voxel_from_world *
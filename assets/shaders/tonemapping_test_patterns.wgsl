@@ -47,6 +47,10 @@ fn continuous_hue(uv: vec2<f32>) -> vec3<f32> {
 fn fragment(
     in: VertexOutput,
 ) -> @location(0) vec4<f32> {
+#ifdef MULTIVIEW
+    mesh_view_bindings::select_view(in.view_index);
+#endif
+
     var uv = in.uv;
     var out = vec3(0.0);
     if uv.y > 0.5 {
@@ -10,6 +10,9 @@ use bevy_ecs::{
     world::{Mut, World},
 };
 
+#[cfg(feature = "std")]
+use bevy_ecs::schedule::ExecutorTrace;
+
 /// The schedule that contains the app logic that is evaluated each tick of [`App::update()`].
 ///
 /// By default, it will run the following schedules in the given order:
@@ -281,6 +284,11 @@ impl Main {
                 let _ = world.try_run_schedule(label);
             }
         });
+
+        #[cfg(feature = "std")]
+        if let Some(trace) = world.get_resource::<ExecutorTrace>() {
+            trace.advance_frame();
+        }
     }
 }
 
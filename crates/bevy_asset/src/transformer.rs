@@ -57,6 +57,15 @@ impl<A: Asset> DerefMut for TransformedAsset<A> {
     }
 }
 
+impl<A: Asset> From<A> for TransformedAsset<A> {
+    fn from(value: A) -> Self {
+        TransformedAsset {
+            value,
+            labeled_assets: HashMap::default(),
+        }
+    }
+}
+
 impl<A: Asset> TransformedAsset<A> {
     /// Creates a new [`TransformedAsset`] from `asset` if its internal value matches `A`.
     pub fn from_loaded(complete_asset: CompleteErasedLoadedAsset) -> Option<Self> {
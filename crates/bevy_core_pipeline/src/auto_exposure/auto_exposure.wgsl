@@ -15,7 +15,11 @@
 // is useful to avoid overexposure when you have a lot of shadows, or underexposure when you
 // have a lot of bright specular reflections.
 //
-// The final target_exposure is finally used to smoothly adjust the exposure value over time.
+// The final target_exposure is finally used to smoothly adjust the exposure value over time,
+// unless the exposure is locked or overridden.
+//
+// The histogram and the exposure values are also written to a readback buffer, from where they
+// can be read back to the CPU.
 
 #import bevy_render::view::View
 #import bevy_render::globals::Globals
@@ -23,6 +27,22 @@
 // Constant to convert RGB to luminance, taken from Real Time Rendering, Vol 4 pg. 278, 4th edition
 const RGB_TO_LUM = vec3<f32>(0.2125, 0.7154, 0.0721);
 
+const METERING_AVERAGE: u32 = 0u;
+const METERING_CENTER_WEIGHTED: u32 = 1u;
+const METERING_SPOT: u32 = 2u;
+const METERING_MATRIX: u32 = 3u;
+
+const CONTROL_AUTOMATIC: u32 = 0u;
+const CONTROL_LOCKED: u32 = 1u;
+const CONTROL_OVERRIDE: u32 = 2u;
+
+// The weights of the 3x3 zones of matrix metering, from the top-left to the bottom-right.
+const MATRIX_ZONE_WEIGHTS = array<f32, 9>(
+    0.5, 0.5, 0.5,
+    0.75, 1.0, 0.75,
+    0.75, 0.75, 0.75,
+);
+
 struct AutoExposure {
     min_log_lum: f32,
     inv_log_lum_range: f32,
@@ -32,6 +52,16 @@ struct AutoExposure {
     speed_up: f32,
     speed_down: f32,
     exponential_transition_distance: f32,
+    metering_mode: u32,
+    metering_radius: f32,
+    control: u32,
+    exposure_override: f32,
+}
+
+struct Readback {
+    histogram: array<u32, 64>,
+    target_exposure: f32,
+    exposure: f32,
 }
 
 struct CompensationCurve {
@@ -59,6 +89,8 @@ struct CompensationCurve {
 
 @group(0) @binding(8) var<storage, read_write> view: View;
 
+@group(0) @binding(9) var<storage, read_write> readback: Readback;
+
 var<workgroup> histogram_shared: array<atomic<u32>, 64>;
 
 // For a given color, return the histogram bin index
@@ -78,15 +110,38 @@ fn color_to_bin(hdr: vec3<f32>) -> u32 {
     return u32(log_lum * 62.0 + 1.0);
 }
 
+// The weight of the pixel at the given UV coordinates according to the metering mode.
+//
+// Distances are measured from the center of the view, in fractions of its height.
+fn metering_mode_weight(coords: vec2<f32>, aspect_ratio: f32) -> f32 {
+    let distance = length((coords - 0.5) * vec2<f32>(aspect_ratio, 1.0));
+
+    switch settings.metering_mode {
+        case METERING_CENTER_WEIGHTED: {
+            return 1.0 - 0.75 * smoothstep(0.0, settings.metering_radius, distance);
+        }
+        case METERING_SPOT: {
+            return select(0.0, 1.0, distance <= settings.metering_radius);
+        }
+        case METERING_MATRIX: {
+            let zone = min(vec2<u32>(coords * 3.0), vec2<u32>(2u));
+            return MATRIX_ZONE_WEIGHTS[zone.y * 3u + zone.x];
+        }
+        default: {
+            return 1.0;
+        }
+    }
+}
+
 // Read the metering mask at the given UV coordinates, returning a weight for the histogram.
 //
 // Since the histogram is summed in the compute_average step, there is a limit to the amount of
 // distinct values that can be represented. When using the chosen value of 16, the maximum
 // amount of pixels that can be weighted and summed is 2^32 / 16 = 16384^2.
-fn metering_weight(coords: vec2<f32>) -> u32 {
+fn metering_weight(coords: vec2<f32>, aspect_ratio: f32) -> u32 {
     let pos = vec2<i32>(coords * vec2<f32>(textureDimensions(tex_mask)));
     let mask = textureLoad(tex_mask, pos, 0).r;
-    return u32(mask * 16.0);
+    return u32(mask * metering_mode_weight(coords, aspect_ratio) * 16.0);
 }
 
 @compute @workgroup_size(16, 16, 1)
@@ -108,7 +163,7 @@ fn compute_histogram(
     if global_invocation_id.x < dim.x && global_invocation_id.y < dim.y {
         let col = textureLoad(tex_color, vec2<i32>(global_invocation_id.xy), 0).rgb;
         let index = color_to_bin(col);
-        let weight = metering_weight(uv);
+        let weight = metering_weight(uv, f32(dim.x) / f32(dim.y));
 
         // Increment the shared histogram bin by the weight obtained from the metering mask
         atomicAdd(&histogram_shared[index], weight);
@@ -134,6 +189,7 @@ fn compute_average(@builtin(local_invocation_index) local_index: u32) {
     for (var i=0u; i<64u; i+=1u) {
         histogram_sum += histogram[i];
         histogram_shared[i] = histogram_sum;
+        readback.histogram[i] = histogram[i];
 
         // Clear the histogram bin for the next frame
         histogram[i] = 0u;
@@ -175,18 +231,25 @@ fn compute_average(@builtin(local_invocation_index) local_index: u32) {
         + compensation_curve.min_compensation
         - avg_lum;
 
-    // Smoothly adjust the `exposure` towards the `target_exposure`
-    let delta = target_exposure - exposure;
-    if target_exposure > exposure {
-        let speed_down = settings.speed_down * globals.delta_time;
-        let exp_down = speed_down / settings.exponential_transition_distance;
-        exposure = exposure + min(speed_down, delta * exp_down);
-    } else {
-        let speed_up = settings.speed_up * globals.delta_time;
-        let exp_up = speed_up / settings.exponential_transition_distance;
-        exposure = exposure + max(-speed_up, delta * exp_up);
+    if settings.control == CONTROL_OVERRIDE {
+        exposure = settings.exposure_override;
+    } else if settings.control == CONTROL_AUTOMATIC {
+        // Smoothly adjust the `exposure` towards the `target_exposure`
+        let delta = target_exposure - exposure;
+        if target_exposure > exposure {
+            let speed_down = settings.speed_down * globals.delta_time;
+            let exp_down = speed_down / settings.exponential_transition_distance;
+            exposure = exposure + min(speed_down, delta * exp_down);
+        } else {
+            let speed_up = settings.speed_up * globals.delta_time;
+            let exp_up = speed_up / settings.exponential_transition_distance;
+            exposure = exposure + max(-speed_up, delta * exp_up);
+        }
     }
 
+    readback.target_exposure = target_exposure;
+    readback.exposure = exposure;
+
     // Apply the exposure to the color grading settings, from where it will be used for the color
     // grading pass.
     view.color_grading.exposure += exposure;
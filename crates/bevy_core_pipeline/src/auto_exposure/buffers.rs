@@ -59,6 +59,10 @@ pub(super) fn prepare_buffers(
             speed_up: settings.speed_brighten,
             speed_down: settings.speed_darken,
             exponential_transition_distance: settings.exponential_transition_distance,
+            metering_mode: settings.metering.mode(),
+            metering_radius: settings.metering.radius(),
+            control: settings.control.mode(),
+            exposure_override: settings.control.exposure_override(),
         };
 
         match buffers.buffers.entry(entity) {
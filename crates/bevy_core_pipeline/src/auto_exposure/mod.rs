@@ -6,7 +6,8 @@ use bevy_render::{
     render_asset::RenderAssetPlugin,
     render_graph::RenderGraphApp,
     render_resource::{
-        Buffer, BufferDescriptor, BufferUsages, PipelineCache, Shader, SpecializedComputePipelines,
+        Buffer, BufferDescriptor, BufferUsages, PipelineCache, Shader, ShaderType as _,
+        SpecializedComputePipelines,
     },
     renderer::RenderDevice,
     ExtractSchedule, Render, RenderApp, RenderSet,
@@ -16,6 +17,7 @@ mod buffers;
 mod compensation_curve;
 mod node;
 mod pipeline;
+mod readback;
 mod settings;
 
 use buffers::{extract_buffers, prepare_buffers, AutoExposureBuffers};
@@ -24,7 +26,12 @@ use node::AutoExposureNode;
 use pipeline::{
     AutoExposurePass, AutoExposurePipeline, ViewAutoExposurePipeline, METERING_SHADER_HANDLE,
 };
-pub use settings::AutoExposure;
+pub use readback::AutoExposureReadback;
+use readback::{
+    prepare_readback_buffers, read_back_metering, AutoExposureReadbackBuffer,
+    AutoExposureReadbackData,
+};
+pub use settings::{AutoExposure, AutoExposureControl, AutoExposureMetering};
 
 use crate::{
     auto_exposure::compensation_curve::GpuAutoExposureCompensationCurve,
@@ -39,6 +46,9 @@ pub struct AutoExposurePlugin;
 #[derive(Resource)]
 struct AutoExposureResources {
     histogram: Buffer,
+    /// The buffer that views without an [`AutoExposureReadback`] write their metering results
+    /// into.
+    readback_fallback: Buffer,
 }
 
 impl Plugin for AutoExposurePlugin {
@@ -58,8 +68,14 @@ impl Plugin for AutoExposurePlugin {
             .resource_mut::<Assets<AutoExposureCompensationCurve>>()
             .insert(&Handle::default(), AutoExposureCompensationCurve::default());
 
-        app.register_type::<AutoExposure>();
-        app.add_plugins(ExtractComponentPlugin::<AutoExposure>::default());
+        app.register_type::<AutoExposure>()
+            .register_type::<AutoExposureReadback>();
+        app.add_plugins((
+            ExtractComponentPlugin::<AutoExposure>::default(),
+            ExtractComponentPlugin::<AutoExposureReadbackBuffer>::default(),
+        ))
+        .add_systems(PostUpdate, prepare_readback_buffers)
+        .add_observer(read_back_metering);
 
         let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
             return;
@@ -95,15 +111,21 @@ impl Plugin for AutoExposurePlugin {
 
 impl FromWorld for AutoExposureResources {
     fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+
         Self {
-            histogram: world
-                .resource::<RenderDevice>()
-                .create_buffer(&BufferDescriptor {
-                    label: Some("histogram buffer"),
-                    size: pipeline::HISTOGRAM_BIN_COUNT * 4,
-                    usage: BufferUsages::STORAGE,
-                    mapped_at_creation: false,
-                }),
+            histogram: render_device.create_buffer(&BufferDescriptor {
+                label: Some("histogram buffer"),
+                size: pipeline::HISTOGRAM_BIN_COUNT * 4,
+                usage: BufferUsages::STORAGE,
+                mapped_at_creation: false,
+            }),
+            readback_fallback: render_device.create_buffer(&BufferDescriptor {
+                label: Some("auto exposure readback fallback buffer"),
+                size: AutoExposureReadbackData::min_size().get(),
+                usage: BufferUsages::STORAGE,
+                mapped_at_creation: false,
+            }),
         }
     }
 }
@@ -113,9 +135,9 @@ fn queue_view_auto_exposure_pipelines(
     pipeline_cache: Res<PipelineCache>,
     mut compute_pipelines: ResMut<SpecializedComputePipelines<AutoExposurePipeline>>,
     pipeline: Res<AutoExposurePipeline>,
-    view_targets: Query<(Entity, &AutoExposure)>,
+    view_targets: Query<(Entity, &AutoExposure, Option<&AutoExposureReadbackBuffer>)>,
 ) {
-    for (entity, auto_exposure) in view_targets.iter() {
+    for (entity, auto_exposure, readback_buffer) in view_targets.iter() {
         let histogram_pipeline =
             compute_pipelines.specialize(&pipeline_cache, &pipeline, AutoExposurePass::Histogram);
         let average_pipeline =
@@ -126,6 +148,7 @@ fn queue_view_auto_exposure_pipelines(
             mean_luminance_pipeline: average_pipeline,
             compensation_curve: auto_exposure.compensation_curve.clone(),
             metering_mask: auto_exposure.metering_mask.clone(),
+            readback: readback_buffer.map(|readback_buffer| readback_buffer.0.clone()),
         });
     }
 }
@@ -15,6 +15,7 @@ use bevy_render::{
     render_graph::*,
     render_resource::*,
     renderer::RenderContext,
+    storage::GpuShaderStorageBuffer,
     texture::{FallbackImage, GpuImage},
     view::{ExtractedView, ViewTarget, ViewUniform, ViewUniformOffset, ViewUniforms},
 };
@@ -91,6 +92,16 @@ impl Node for AutoExposureNode {
             .map(|i| &i.texture_view)
             .unwrap_or(&fallback.d2.texture_view);
 
+        let readback = auto_exposure
+            .readback
+            .as_ref()
+            .and_then(|readback| {
+                world
+                    .resource::<RenderAssets<GpuShaderStorageBuffer>>()
+                    .get(readback)
+            })
+            .map_or(&resources.readback_fallback, |readback| &readback.buffer);
+
         let Some(compensation_curve) = world
             .resource::<RenderAssets<GpuAutoExposureCompensationCurve>>()
             .get(&auto_exposure.compensation_curve)
@@ -115,6 +126,7 @@ impl Node for AutoExposureNode {
                     size: Some(ViewUniform::min_size()),
                     offset: 0,
                 },
+                readback.as_entire_buffer_binding(),
             )),
         );
 
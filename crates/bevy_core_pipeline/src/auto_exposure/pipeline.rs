@@ -1,5 +1,6 @@
-use super::compensation_curve::{
-    AutoExposureCompensationCurve, AutoExposureCompensationCurveUniform,
+use super::{
+    compensation_curve::{AutoExposureCompensationCurve, AutoExposureCompensationCurveUniform},
+    readback::AutoExposureReadbackData,
 };
 use bevy_asset::{prelude::*, weak_handle};
 use bevy_ecs::prelude::*;
@@ -8,6 +9,7 @@ use bevy_render::{
     globals::GlobalsUniform,
     render_resource::{binding_types::*, *},
     renderer::RenderDevice,
+    storage::ShaderStorageBuffer,
     view::ViewUniform,
 };
 use core::num::NonZero;
@@ -24,6 +26,7 @@ pub struct ViewAutoExposurePipeline {
     pub mean_luminance_pipeline: CachedComputePipelineId,
     pub compensation_curve: Handle<AutoExposureCompensationCurve>,
     pub metering_mask: Handle<Image>,
+    pub readback: Option<Handle<ShaderStorageBuffer>>,
 }
 
 #[derive(ShaderType, Clone, Copy)]
@@ -36,6 +39,10 @@ pub struct AutoExposureUniform {
     pub(super) speed_up: f32,
     pub(super) speed_down: f32,
     pub(super) exponential_transition_distance: f32,
+    pub(super) metering_mode: u32,
+    pub(super) metering_radius: f32,
+    pub(super) control: u32,
+    pub(super) exposure_override: f32,
 }
 
 #[derive(PartialEq, Eq, Hash, Clone)]
@@ -68,6 +75,7 @@ impl FromWorld for AutoExposurePipeline {
                         storage_buffer_sized(false, NonZero::<u64>::new(HISTOGRAM_BIN_COUNT * 4)),
                         storage_buffer_sized(false, NonZero::<u64>::new(4)),
                         storage_buffer::<ViewUniform>(true),
+                        storage_buffer::<AutoExposureReadbackData>(false),
                     ),
                 ),
             ),
@@ -0,0 +1,206 @@
+use bevy_asset::{Assets, Handle};
+use bevy_ecs::prelude::*;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    extract_component::ExtractComponent,
+    gpu_readback::{Readback, ReadbackComplete},
+    render_asset::RenderAssetUsages,
+    render_resource::{BufferUsages, ShaderType},
+    storage::ShaderStorageBuffer,
+};
+
+use super::{pipeline::HISTOGRAM_BIN_COUNT, AutoExposure};
+
+/// The results of [`AutoExposure`] metering, read back from the GPU.
+///
+/// Add this component to a camera with [`AutoExposure`] to read back its histogram and exposure
+/// every frame. The values arrive a few frames after they are computed, and stay at their
+/// defaults until the first readback completes.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub struct AutoExposureReadback {
+    /// The weighted luminance histogram of the view.
+    ///
+    /// The first bin counts the pixels below [`AutoExposure::range`], and the other bins split
+    /// the range evenly, in log2 luminance.
+    pub histogram: [u32; HISTOGRAM_BIN_COUNT as usize],
+    /// The exposure compensation that the exposure adapts towards, in F-stops.
+    pub target_exposure: f32,
+    /// The exposure compensation applied to the view, in F-stops.
+    pub exposure: f32,
+}
+
+impl Default for AutoExposureReadback {
+    fn default() -> Self {
+        Self {
+            histogram: [0; HISTOGRAM_BIN_COUNT as usize],
+            target_exposure: 0.0,
+            exposure: 0.0,
+        }
+    }
+}
+
+/// The layout of the buffer that the metering results are written into on the GPU.
+#[derive(ShaderType, Clone, Copy)]
+pub(super) struct AutoExposureReadbackData {
+    histogram: [u32; HISTOGRAM_BIN_COUNT as usize],
+    target_exposure: f32,
+    exposure: f32,
+}
+
+impl Default for AutoExposureReadbackData {
+    fn default() -> Self {
+        Self {
+            histogram: [0; HISTOGRAM_BIN_COUNT as usize],
+            target_exposure: 0.0,
+            exposure: 0.0,
+        }
+    }
+}
+
+/// The buffer that the metering results of a camera with an [`AutoExposureReadback`] are
+/// written into.
+#[derive(Component, Clone, ExtractComponent)]
+pub(super) struct AutoExposureReadbackBuffer(pub(super) Handle<ShaderStorageBuffer>);
+
+/// Allocates readback buffers for the cameras that request one, and frees those that aren't
+/// requested anymore.
+pub(super) fn prepare_readback_buffers(
+    mut commands: Commands,
+    mut buffers: ResMut<Assets<ShaderStorageBuffer>>,
+    added: Query<
+        Entity,
+        (
+            With<AutoExposure>,
+            With<AutoExposureReadback>,
+            Without<AutoExposureReadbackBuffer>,
+        ),
+    >,
+    removed: Query<
+        Entity,
+        (
+            With<AutoExposureReadbackBuffer>,
+            Or<(Without<AutoExposure>, Without<AutoExposureReadback>)>,
+        ),
+    >,
+) {
+    for entity in &added {
+        let mut buffer = ShaderStorageBuffer::from(AutoExposureReadbackData::default());
+        buffer.asset_usage = RenderAssetUsages::RENDER_WORLD;
+        buffer.buffer_description.usage |= BufferUsages::COPY_SRC;
+        let buffer = buffers.add(buffer);
+
+        commands.entity(entity).insert((
+            AutoExposureReadbackBuffer(buffer.clone()),
+            Readback::buffer(buffer),
+        ));
+    }
+
+    for entity in &removed {
+        commands
+            .entity(entity)
+            .remove::<(AutoExposureReadbackBuffer, Readback)>();
+    }
+}
+
+/// Copies the metering results of a completed readback into the [`AutoExposureReadback`] of the
+/// camera.
+pub(super) fn read_back_metering(
+    trigger: Trigger<ReadbackComplete>,
+    mut readbacks: Query<&mut AutoExposureReadback, With<AutoExposureReadbackBuffer>>,
+) {
+    let Ok(mut readback) = readbacks.get_mut(trigger.target()) else {
+        return;
+    };
+
+    let data: AutoExposureReadbackData = trigger.event().to_shader_type();
+    readback.histogram = data.histogram;
+    readback.target_exposure = data.target_exposure;
+    readback.exposure = data.exposure;
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_ecs::system::RunSystemOnce;
+    use bevy_render::render_resource::encase::StorageBuffer;
+
+    use super::*;
+
+    fn run_prepare(world: &mut World) {
+        world.run_system_once(prepare_readback_buffers).unwrap();
+    }
+
+    fn has_buffer(world: &World, entity: Entity) -> bool {
+        let entity = world.entity(entity);
+        entity.contains::<AutoExposureReadbackBuffer>() && entity.contains::<Readback>()
+    }
+
+    #[test]
+    fn readback_buffers_follow_components() {
+        let mut world = World::new();
+        world.init_resource::<Assets<ShaderStorageBuffer>>();
+        let without_readback = world.spawn(AutoExposure::default()).id();
+        let loses_readback = world
+            .spawn((AutoExposure::default(), AutoExposureReadback::default()))
+            .id();
+        let loses_auto_exposure = world
+            .spawn((AutoExposure::default(), AutoExposureReadback::default()))
+            .id();
+
+        run_prepare(&mut world);
+        assert!(!has_buffer(&world, without_readback));
+        assert!(has_buffer(&world, loses_readback));
+        assert!(has_buffer(&world, loses_auto_exposure));
+        assert_eq!(world.resource::<Assets<ShaderStorageBuffer>>().len(), 2);
+
+        world
+            .entity_mut(loses_readback)
+            .remove::<AutoExposureReadback>();
+        world
+            .entity_mut(loses_auto_exposure)
+            .remove::<AutoExposure>();
+        run_prepare(&mut world);
+        for entity in [loses_readback, loses_auto_exposure] {
+            let entity = world.entity(entity);
+            assert!(!entity.contains::<AutoExposureReadbackBuffer>());
+            assert!(!entity.contains::<Readback>());
+        }
+    }
+
+    #[test]
+    fn read_back_metering_copies_results() {
+        let mut world = World::new();
+        world.add_observer(read_back_metering);
+        let camera = world
+            .spawn((
+                AutoExposureReadback::default(),
+                AutoExposureReadbackBuffer(Handle::default()),
+            ))
+            .id();
+        let unrequested = world.spawn(AutoExposureReadback::default()).id();
+
+        let mut histogram = [0; HISTOGRAM_BIN_COUNT as usize];
+        histogram[3] = 42;
+        histogram[HISTOGRAM_BIN_COUNT as usize - 1] = 7;
+        let mut bytes = StorageBuffer::new(Vec::new());
+        bytes
+            .write(&AutoExposureReadbackData {
+                histogram,
+                target_exposure: 1.5,
+                exposure: -0.5,
+            })
+            .unwrap();
+        let bytes = bytes.into_inner();
+        world.trigger_targets(ReadbackComplete(bytes.clone()), camera);
+        world.trigger_targets(ReadbackComplete(bytes), unrequested);
+
+        let readback = world.get::<AutoExposureReadback>(camera).unwrap();
+        assert_eq!(readback.histogram, histogram);
+        assert_eq!(readback.target_exposure, 1.5);
+        assert_eq!(readback.exposure, -0.5);
+
+        let readback = world.get::<AutoExposureReadback>(unrequested).unwrap();
+        assert_eq!(readback.histogram, [0; HISTOGRAM_BIN_COUNT as usize]);
+        assert_eq!(readback.exposure, 0.0);
+    }
+}
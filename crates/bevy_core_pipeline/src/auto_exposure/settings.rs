@@ -82,6 +82,17 @@ pub struct AutoExposure {
     /// implementation.
     pub metering_mask: Handle<Image>,
 
+    /// How the pixels of the view are weighted by their position when metering, on top of the
+    /// [`metering_mask`](Self::metering_mask).
+    ///
+    /// The default value is [`AutoExposureMetering::Average`].
+    pub metering: AutoExposureMetering,
+
+    /// Whether the exposure adapts to the scene, stays where it is or is set by gameplay code.
+    ///
+    /// The default value is [`AutoExposureControl::Automatic`].
+    pub control: AutoExposureControl,
+
     /// Exposure compensation curve to apply after metering.
     /// The default value is a flat line at 0.0.
     /// For more information, see [`AutoExposureCompensationCurve`].
@@ -97,7 +108,141 @@ impl Default for AutoExposure {
             speed_darken: 1.0,
             exponential_transition_distance: 1.5,
             metering_mask: default(),
+            metering: default(),
+            control: default(),
             compensation_curve: default(),
         }
     }
 }
+
+/// How [`AutoExposure`] weights the pixels of the view by their position when metering.
+///
+/// Distances are fractions of the height of the view, measured from its center.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[reflect(Default, Debug, PartialEq)]
+pub enum AutoExposureMetering {
+    /// All pixels contribute equally.
+    #[default]
+    Average,
+    /// Pixels contribute fully near the center of the view, and a quarter as much past `radius`.
+    CenterWeighted {
+        /// The distance at which the contribution of pixels stops decreasing.
+        radius: f32,
+    },
+    /// Only the pixels within `radius` of the center of the view contribute.
+    Spot {
+        /// The radius of the metered spot.
+        radius: f32,
+    },
+    /// The view is split into a 3×3 grid of zones with fixed weights, favoring the center and
+    /// the bottom of the view over the top, where the sky usually is.
+    Matrix,
+}
+
+impl AutoExposureMetering {
+    /// The value of the `metering_mode` field of the uniform.
+    pub(super) fn mode(&self) -> u32 {
+        match self {
+            AutoExposureMetering::Average => 0,
+            AutoExposureMetering::CenterWeighted { .. } => 1,
+            AutoExposureMetering::Spot { .. } => 2,
+            AutoExposureMetering::Matrix => 3,
+        }
+    }
+
+    /// The value of the `metering_radius` field of the uniform.
+    pub(super) fn radius(&self) -> f32 {
+        match self {
+            AutoExposureMetering::CenterWeighted { radius }
+            | AutoExposureMetering::Spot { radius } => *radius,
+            AutoExposureMetering::Average | AutoExposureMetering::Matrix => 0.0,
+        }
+    }
+}
+
+/// Whether [`AutoExposure`] adapts the exposure to the scene.
+///
+/// Metering keeps running whatever the control, so that
+/// [`AutoExposureReadback`](super::AutoExposureReadback) stays up to date.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[reflect(Default, Debug, PartialEq)]
+pub enum AutoExposureControl {
+    /// The exposure adapts to the scene.
+    #[default]
+    Automatic,
+    /// The exposure stays where it is.
+    Locked,
+    /// The exposure is set to the given compensation, in F-stops, and adapts from there once
+    /// the control is back to [`AutoExposureControl::Automatic`].
+    Override(f32),
+}
+
+impl AutoExposureControl {
+    /// The value of the `control` field of the uniform.
+    pub(super) fn mode(&self) -> u32 {
+        match self {
+            AutoExposureControl::Automatic => 0,
+            AutoExposureControl::Locked => 1,
+            AutoExposureControl::Override(_) => 2,
+        }
+    }
+
+    /// The value of the `exposure_override` field of the uniform.
+    pub(super) fn exposure_override(&self) -> f32 {
+        match self {
+            AutoExposureControl::Override(exposure) => *exposure,
+            AutoExposureControl::Automatic | AutoExposureControl::Locked => 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHADER: &str = include_str!("auto_exposure.wgsl");
+
+    fn assert_shader_constant(name: &str, value: u32) {
+        assert!(
+            SHADER.contains(&format!("const {name}: u32 = {value}u;")),
+            "{name} should be {value} in auto_exposure.wgsl"
+        );
+    }
+
+    #[test]
+    fn metering_matches_shader() {
+        for (metering, name, radius) in [
+            (AutoExposureMetering::Average, "METERING_AVERAGE", 0.0),
+            (
+                AutoExposureMetering::CenterWeighted { radius: 0.25 },
+                "METERING_CENTER_WEIGHTED",
+                0.25,
+            ),
+            (
+                AutoExposureMetering::Spot { radius: 0.1 },
+                "METERING_SPOT",
+                0.1,
+            ),
+            (AutoExposureMetering::Matrix, "METERING_MATRIX", 0.0),
+        ] {
+            assert_shader_constant(name, metering.mode());
+            assert_eq!(metering.radius(), radius);
+        }
+    }
+
+    #[test]
+    fn control_matches_shader() {
+        for (control, name, exposure_override) in [
+            (AutoExposureControl::Automatic, "CONTROL_AUTOMATIC", 0.0),
+            (AutoExposureControl::Locked, "CONTROL_LOCKED", 0.0),
+            (
+                AutoExposureControl::Override(-1.5),
+                "CONTROL_OVERRIDE",
+                -1.5,
+            ),
+        ] {
+            assert_shader_constant(name, control.mode());
+            assert_eq!(control.exposure_override(), exposure_override);
+        }
+    }
+}
@@ -1,9 +1,22 @@
 #import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
 
+#ifdef MULTIVIEW
+@group(0) @binding(0) var in_texture: texture_2d_array<f32>;
+#else
 @group(0) @binding(0) var in_texture: texture_2d<f32>;
+#endif
 @group(0) @binding(1) var in_sampler: sampler;
 
 @fragment
-fn fs_main(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+fn fs_main(
+    in: FullscreenVertexOutput,
+#ifdef MULTIVIEW
+    @builtin(view_index) view_index: i32,
+#endif
+) -> @location(0) vec4<f32> {
+#ifdef MULTIVIEW
+    return textureSample(in_texture, in_sampler, in.uv, view_index);
+#else
     return textureSample(in_texture, in_sampler, in.uv);
+#endif
 }
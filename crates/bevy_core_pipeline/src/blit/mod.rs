@@ -2,14 +2,17 @@ use bevy_app::{App, Plugin};
 use bevy_asset::{load_internal_asset, weak_handle, Handle};
 use bevy_ecs::prelude::*;
 use bevy_render::{
+    camera::Multiview,
     render_resource::{
-        binding_types::{sampler, texture_2d},
+        binding_types::{sampler, texture_2d, texture_2d_array},
         *,
     },
     renderer::RenderDevice,
     RenderApp,
 };
 
+use core::num::NonZeroU32;
+
 use crate::fullscreen_vertex_shader::fullscreen_shader_vertex_state;
 
 pub const BLIT_SHADER_HANDLE: Handle<Shader> = weak_handle!("59be3075-c34e-43e7-bf24-c8fe21a0192e");
@@ -39,6 +42,8 @@ impl Plugin for BlitPlugin {
 #[derive(Resource)]
 pub struct BlitPipeline {
     pub texture_bind_group: BindGroupLayout,
+    /// The layout used to blit both layers of a [`Multiview`] texture at once.
+    pub multiview_texture_bind_group: BindGroupLayout,
     pub sampler: Sampler,
 }
 
@@ -57,10 +62,22 @@ impl FromWorld for BlitPipeline {
             ),
         );
 
+        let multiview_texture_bind_group = render_device.create_bind_group_layout(
+            "blit_multiview_bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    texture_2d_array(TextureSampleType::Float { filterable: false }),
+                    sampler(SamplerBindingType::NonFiltering),
+                ),
+            ),
+        );
+
         let sampler = render_device.create_sampler(&SamplerDescriptor::default());
 
         BlitPipeline {
             texture_bind_group,
+            multiview_texture_bind_group,
             sampler,
         }
     }
@@ -71,6 +88,8 @@ pub struct BlitPipelineKey {
     pub texture_format: TextureFormat,
     pub blend_state: Option<BlendState>,
     pub samples: u32,
+    /// Whether both layers of a [`Multiview`] texture are blitted at once.
+    pub multiview: bool,
 }
 
 impl SpecializedRenderPipeline for BlitPipeline {
@@ -79,11 +98,19 @@ impl SpecializedRenderPipeline for BlitPipeline {
     fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
         RenderPipelineDescriptor {
             label: Some("blit pipeline".into()),
-            layout: vec![self.texture_bind_group.clone()],
+            layout: vec![if key.multiview {
+                self.multiview_texture_bind_group.clone()
+            } else {
+                self.texture_bind_group.clone()
+            }],
             vertex: fullscreen_shader_vertex_state(),
             fragment: Some(FragmentState {
                 shader: BLIT_SHADER_HANDLE,
-                shader_defs: vec![],
+                shader_defs: if key.multiview {
+                    vec!["MULTIVIEW".into()]
+                } else {
+                    vec![]
+                },
                 entry_point: "fs_main".into(),
                 targets: vec![Some(ColorTargetState {
                     format: key.texture_format,
@@ -99,6 +126,10 @@ impl SpecializedRenderPipeline for BlitPipeline {
             },
             push_constant_ranges: Vec::new(),
             zero_initialize_workgroup_memory: false,
+            multiview: key
+                .multiview
+                .then_some(Multiview::VIEW_COUNT)
+                .and_then(NonZeroU32::new),
         }
     }
 }
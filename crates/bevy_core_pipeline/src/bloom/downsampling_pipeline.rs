@@ -134,6 +134,7 @@ impl SpecializedRenderPipeline for BloomDownsamplingPipeline {
             multisample: MultisampleState::default(),
             push_constant_ranges: Vec::new(),
             zero_initialize_workgroup_memory: false,
+            multiview: None,
         }
     }
 }
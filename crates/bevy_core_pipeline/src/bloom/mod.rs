@@ -14,7 +14,7 @@ use bevy_asset::{load_internal_asset, weak_handle, Handle};
 use bevy_ecs::{prelude::*, query::QueryItem};
 use bevy_math::{ops, UVec2};
 use bevy_render::{
-    camera::ExtractedCamera,
+    camera::{ExtractedCamera, Multiview},
     diagnostic::RecordDiagnostics,
     extract_component::{
         ComponentUniforms, DynamicUniformIndex, ExtractComponentPlugin, UniformComponentPlugin,
@@ -26,10 +26,12 @@ use bevy_render::{
     view::ViewTarget,
     Render, RenderApp, RenderSet,
 };
+use bevy_utils::once;
 use downsampling_pipeline::{
     prepare_downsampling_pipeline, BloomDownsamplingPipeline, BloomDownsamplingPipelineIds,
     BloomUniforms,
 };
+use tracing::warn;
 use upsampling_pipeline::{
     prepare_upsampling_pipeline, BloomUpsamplingPipeline, UpsamplingPipelineIds,
 };
@@ -103,6 +105,7 @@ impl ViewNode for BloomNode {
         &'static Bloom,
         &'static UpsamplingPipelineIds,
         &'static BloomDownsamplingPipelineIds,
+        Has<Multiview>,
     );
 
     // Atypically for a post-processing effect, we do not need to
@@ -121,9 +124,17 @@ impl ViewNode for BloomNode {
             bloom_settings,
             upsampling_pipeline_ids,
             downsampling_pipeline_ids,
+            multiview,
         ): QueryItem<Self::ViewQuery>,
         world: &World,
     ) -> Result<(), NodeRunError> {
+        if multiview {
+            once!(warn!(
+                "Bloom isn't supported on `Multiview` cameras and is skipped"
+            ));
+            return Ok(());
+        }
+
         if bloom_settings.intensity == 0.0 {
             return Ok(());
         }
@@ -281,7 +292,7 @@ impl ViewNode for BloomNode {
 }
 
 #[derive(Component)]
-struct BloomTexture {
+pub(crate) struct BloomTexture {
     // First mip is half the screen resolution, successive mips are half the previous
     #[cfg(any(
         not(feature = "webgl"),
@@ -301,7 +312,7 @@ impl BloomTexture {
         not(target_arch = "wasm32"),
         feature = "webgpu"
     ))]
-    fn view(&self, base_mip_level: u32) -> TextureView {
+    pub(crate) fn view(&self, base_mip_level: u32) -> TextureView {
         self.texture.texture.create_view(&TextureViewDescriptor {
             base_mip_level,
             mip_level_count: Some(1u32),
@@ -309,7 +320,7 @@ impl BloomTexture {
         })
     }
     #[cfg(all(feature = "webgl", target_arch = "wasm32", not(feature = "webgpu")))]
-    fn view(&self, base_mip_level: u32) -> TextureView {
+    pub(crate) fn view(&self, base_mip_level: u32) -> TextureView {
         self.texture[base_mip_level as usize]
             .texture
             .create_view(&TextureViewDescriptor {
@@ -126,6 +126,7 @@ impl SpecializedRenderPipeline for BloomUpsamplingPipeline {
             multisample: MultisampleState::default(),
             push_constant_ranges: Vec::new(),
             zero_initialize_workgroup_memory: false,
+            multiview: None,
         }
     }
 }
@@ -231,6 +231,7 @@ impl SpecializedRenderPipeline for CasPipeline {
             multisample: MultisampleState::default(),
             push_constant_ranges: Vec::new(),
             zero_initialize_workgroup_memory: false,
+            multiview: None,
         }
     }
 }
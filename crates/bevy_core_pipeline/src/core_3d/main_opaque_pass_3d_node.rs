@@ -10,7 +10,9 @@ use bevy_render::{
     render_phase::{TrackedRenderPass, ViewBinnedRenderPhases},
     render_resource::{CommandEncoderDescriptor, PipelineCache, RenderPassDescriptor, StoreOp},
     renderer::RenderContext,
-    view::{ExtractedView, ViewDepthTexture, ViewTarget, ViewUniformOffset},
+    view::{
+        ExtractedView, MultiviewUniformOffset, ViewDepthTexture, ViewTarget, ViewUniformOffset,
+    },
 };
 use tracing::error;
 #[cfg(feature = "trace")]
@@ -31,6 +33,7 @@ impl ViewNode for MainOpaquePass3dNode {
         Option<&'static SkyboxPipelineId>,
         Option<&'static SkyboxBindGroup>,
         &'static ViewUniformOffset,
+        Option<&'static MultiviewUniformOffset>,
     );
 
     fn run<'w>(
@@ -45,6 +48,7 @@ impl ViewNode for MainOpaquePass3dNode {
             skybox_pipeline,
             skybox_bind_group,
             view_uniform_offset,
+            multiview_uniform_offset,
         ): QueryItem<'w, Self::ViewQuery>,
         world: &'w World,
     ) -> Result<(), NodeRunError> {
@@ -117,11 +121,14 @@ impl ViewNode for MainOpaquePass3dNode {
             {
                 let pipeline_cache = world.resource::<PipelineCache>();
                 if let Some(pipeline) = pipeline_cache.get_render_pipeline(skybox_pipeline.0) {
+                    // Multiview views bind the uniforms of both eyes in place of their own.
+                    let view_offset = multiview_uniform_offset
+                        .map_or(view_uniform_offset.offset, |offset| offset.offset);
                     render_pass.set_render_pipeline(pipeline);
                     render_pass.set_bind_group(
                         0,
                         &skybox_bind_group.0,
-                        &[view_uniform_offset.offset, skybox_bind_group.1],
+                        &[view_offset, skybox_bind_group.1],
                     );
                     render_pass.draw(0..3, 0..1);
                 }
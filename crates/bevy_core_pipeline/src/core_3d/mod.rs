@@ -35,6 +35,7 @@ pub mod graph {
         DepthOfField,
         PostProcessing,
         Tonemapping,
+        Outline,
         Fxaa,
         Smaa,
         Upscaling,
@@ -87,7 +88,7 @@ use bevy_image::BevyDefault;
 use bevy_math::FloatOrd;
 use bevy_platform_support::collections::{HashMap, HashSet};
 use bevy_render::{
-    camera::{Camera, ExtractedCamera},
+    camera::{Camera, ExtractedCamera, Multiview},
     extract_component::ExtractComponentPlugin,
     prelude::Msaa,
     render_graph::{EmptyNode, RenderGraphApp, ViewNodeRunner},
@@ -120,8 +121,9 @@ use crate::{
     prepass::{
         node::{EarlyPrepassNode, LatePrepassNode},
         AlphaMask3dPrepass, DeferredPrepass, DepthPrepass, MotionVectorPrepass, NormalPrepass,
-        Opaque3dPrepass, OpaqueNoLightmap3dBatchSetKey, OpaqueNoLightmap3dBinKey,
+        Opaque3dPrepass, OpaqueNoLightmap3dBatchSetKey, OpaqueNoLightmap3dBinKey, OutlineIdPrepass,
         ViewPrepassTextures, MOTION_VECTOR_PREPASS_FORMAT, NORMAL_PREPASS_FORMAT,
+        OUTLINE_ID_PREPASS_FORMAT,
     },
     skybox::SkyboxPlugin,
     tonemapping::TonemappingNode,
@@ -662,6 +664,7 @@ pub fn extract_camera_prepass_phase(
                 Has<NormalPrepass>,
                 Has<MotionVectorPrepass>,
                 Has<DeferredPrepass>,
+                Has<OutlineIdPrepass>,
             ),
             With<Camera3d>,
         >,
@@ -680,6 +683,7 @@ pub fn extract_camera_prepass_phase(
         normal_prepass,
         motion_vector_prepass,
         deferred_prepass,
+        outline_id_prepass,
     ) in cameras_3d.iter()
     {
         if !camera.is_active {
@@ -697,7 +701,7 @@ pub fn extract_camera_prepass_phase(
         // This is the main 3D camera, so we use the first subview index (0).
         let retained_view_entity = RetainedViewEntity::new(main_entity.into(), None, 0);
 
-        if depth_prepass || normal_prepass || motion_vector_prepass {
+        if depth_prepass || normal_prepass || motion_vector_prepass || outline_id_prepass {
             opaque_3d_prepass_phases
                 .prepare_for_new_frame(retained_view_entity, gpu_preprocessing_mode);
             alpha_mask_3d_prepass_phases
@@ -724,7 +728,8 @@ pub fn extract_camera_prepass_phase(
             .insert_if(DepthPrepass, || depth_prepass)
             .insert_if(NormalPrepass, || normal_prepass)
             .insert_if(MotionVectorPrepass, || motion_vector_prepass)
-            .insert_if(DeferredPrepass, || deferred_prepass);
+            .insert_if(DeferredPrepass, || deferred_prepass)
+            .insert_if(OutlineIdPrepass, || outline_id_prepass);
     }
 
     opaque_3d_prepass_phases.retain(|view_entity, _| live_entities.contains(view_entity));
@@ -748,10 +753,11 @@ pub fn prepare_core_3d_depth_textures(
         Option<&DepthPrepass>,
         &Camera3d,
         &Msaa,
+        Has<Multiview>,
     )>,
 ) {
     let mut render_target_usage = <HashMap<_, _>>::default();
-    for (_, camera, extracted_view, depth_prepass, camera_3d, _msaa) in &views_3d {
+    for (_, camera, extracted_view, depth_prepass, camera_3d, _msaa, _multiview) in &views_3d {
         if !opaque_3d_phases.contains_key(&extracted_view.retained_view_entity)
             || !alpha_mask_3d_phases.contains_key(&extracted_view.retained_view_entity)
             || !transmissive_3d_phases.contains_key(&extracted_view.retained_view_entity)
@@ -773,17 +779,17 @@ pub fn prepare_core_3d_depth_textures(
     }
 
     let mut textures = <HashMap<_, _>>::default();
-    for (entity, camera, _, _, camera_3d, msaa) in &views_3d {
+    for (entity, camera, _, _, camera_3d, msaa, multiview) in &views_3d {
         let Some(physical_target_size) = camera.physical_target_size else {
             continue;
         };
 
         let cached_texture = textures
-            .entry((camera.target.clone(), msaa))
+            .entry((camera.target.clone(), msaa, multiview))
             .or_insert_with(|| {
                 // The size of the depth texture
                 let size = Extent3d {
-                    depth_or_array_layers: 1,
+                    depth_or_array_layers: if multiview { Multiview::VIEW_COUNT } else { 1 },
                     width: physical_target_size.x,
                     height: physical_target_size.y,
                 };
@@ -963,6 +969,8 @@ pub fn prepare_prepass_textures(
         Has<NormalPrepass>,
         Has<MotionVectorPrepass>,
         Has<DeferredPrepass>,
+        Has<OutlineIdPrepass>,
+        Has<Multiview>,
     )>,
 ) {
     let mut depth_textures = <HashMap<_, _>>::default();
@@ -970,6 +978,7 @@ pub fn prepare_prepass_textures(
     let mut deferred_textures = <HashMap<_, _>>::default();
     let mut deferred_lighting_id_textures = <HashMap<_, _>>::default();
     let mut motion_vectors_textures = <HashMap<_, _>>::default();
+    let mut outline_id_textures = <HashMap<_, _>>::default();
     for (
         entity,
         camera,
@@ -979,6 +988,8 @@ pub fn prepare_prepass_textures(
         normal_prepass,
         motion_vector_prepass,
         deferred_prepass,
+        outline_id_prepass,
+        multiview,
     ) in &views_3d
     {
         if !opaque_3d_prepass_phases.contains_key(&view.retained_view_entity)
@@ -994,7 +1005,7 @@ pub fn prepare_prepass_textures(
         };
 
         let size = Extent3d {
-            depth_or_array_layers: 1,
+            depth_or_array_layers: if multiview { Multiview::VIEW_COUNT } else { 1 },
             width: physical_target_size.x,
             height: physical_target_size.y,
         };
@@ -1108,6 +1119,28 @@ pub fn prepare_prepass_textures(
                 .clone()
         });
 
+        let cached_outline_id_texture = outline_id_prepass.then(|| {
+            outline_id_textures
+                .entry(camera.target.clone())
+                .or_insert_with(|| {
+                    texture_cache.get(
+                        &render_device,
+                        TextureDescriptor {
+                            label: Some("prepass_outline_id_texture"),
+                            size,
+                            mip_level_count: 1,
+                            sample_count: msaa.samples(),
+                            dimension: TextureDimension::D2,
+                            format: OUTLINE_ID_PREPASS_FORMAT,
+                            usage: TextureUsages::RENDER_ATTACHMENT
+                                | TextureUsages::TEXTURE_BINDING,
+                            view_formats: &[],
+                        },
+                    )
+                })
+                .clone()
+        });
+
         commands.entity(entity).insert(ViewPrepassTextures {
             depth: cached_depth_texture
                 .map(|t| ColorAttachment::new(t, None, Some(LinearRgba::BLACK))),
@@ -1122,6 +1155,8 @@ pub fn prepare_prepass_textures(
                 .map(|t| ColorAttachment::new(t, None, Some(LinearRgba::BLACK))),
             deferred_lighting_pass_id: cached_deferred_lighting_pass_id_texture
                 .map(|t| ColorAttachment::new(t, None, Some(LinearRgba::BLACK))),
+            outline_id: cached_outline_id_texture
+                .map(|t| ColorAttachment::new(t, None, Some(LinearRgba::BLACK))),
             size,
         });
     }
@@ -161,6 +161,7 @@ impl FromWorld for CopyDeferredLightingIdPipeline {
                     multisample: MultisampleState::default(),
                     push_constant_ranges: vec![],
                     zero_initialize_workgroup_memory: false,
+                    multiview: None,
                 });
 
         Self {
@@ -14,6 +14,18 @@
 // passes down to two.
 //
 // [1]: https://colinbarrebrisebois.com/2017/04/18/hexagonal-bokeh-blur-revisited-part-2-improved-2-pass-version/
+//
+// The aperture bokeh uses a scatter-as-gather technique, as described in Jorge
+// Jimenez, "Next Generation Post Processing in Call of Duty: Advanced
+// Warfare" [2]. The first pass stores the signed circle of confusion of each
+// pixel in the alpha channel. The second pass gathers samples in a disc
+// around each pixel and weights each one by whether the pixel falls within
+// the aperture shape that the sample would scatter to. Background samples
+// can't spread further than the blur of the pixel they land on, so they don't
+// bleed over sharper foreground objects, while foreground samples are
+// gathered separately and blended over everything with their coverage.
+//
+// [2]: https://www.iryoku.com/next-generation-post-processing-in-call-of-duty-advanced-warfare
 
 #import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
 #import bevy_pbr::mesh_view_bindings::view
@@ -89,6 +101,11 @@ struct DualOutput {
 @group(0) @binding(3) var color_texture_b: texture_2d<f32>;
 #endif  // DUAL_INPUT
 
+// The shape of the aperture, used by the aperture bokeh gather pass.
+#ifdef APERTURE
+@group(0) @binding(3) var aperture_texture: texture_2d<f32>;
+#endif  // APERTURE
+
 // The global uniforms, representing data backed by buffers shared among all
 // views in the scene.
 
@@ -107,11 +124,26 @@ const COS_NEG_FRAC_PI_5_6: f32 = -0.8660254037844387;
 // sin(-150°), used for the bokeh blur.
 const SIN_NEG_FRAC_PI_5_6: f32 = -0.5;
 
+// The number of samples that the aperture bokeh gathers for the background
+// and in-focus layer.
+const APERTURE_FAR_SAMPLE_COUNT: u32 = 48u;
+// The number of samples that the aperture bokeh gathers for the foreground
+// layer.
+const APERTURE_NEAR_SAMPLE_COUNT: u32 = 32u;
+// The golden angle, used to lay out aperture bokeh samples in a spiral.
+const GOLDEN_ANGLE: f32 = 2.399963229728653;
+
 // Calculates and returns the diameter (not the radius) of the [circle of
 // confusion].
 //
 // [circle of confusion]: https://en.wikipedia.org/wiki/Circle_of_confusion
 fn calculate_circle_of_confusion(in_frag_coord: vec4<f32>) -> f32 {
+    return abs(calculate_signed_circle_of_confusion(in_frag_coord));
+}
+
+// Calculates and returns the diameter of the circle of confusion, negated for
+// objects nearer than the focal distance.
+fn calculate_signed_circle_of_confusion(in_frag_coord: vec4<f32>) -> f32 {
     // Unpack the depth of field parameters.
     let focus = dof_params.focal_distance;
     let f = dof_params.focal_length;
@@ -128,10 +160,10 @@ fn calculate_circle_of_confusion(in_frag_coord: vec4<f32>) -> f32 {
     // This is just the formula from Wikipedia [1].
     //
     // [1]: https://en.wikipedia.org/wiki/Circle_of_confusion#Determining_a_circle_of_confusion_diameter_from_the_object_field
-    let candidate_coc = scale * abs(depth - focus) / (depth * (focus - f));
+    let candidate_coc = scale * (depth - focus) / (depth * (focus - f));
 
     let framebuffer_size = vec2<f32>(textureDimensions(color_texture_a));
-    return clamp(candidate_coc * framebuffer_size.y, 0.0, max_coc_diameter);
+    return clamp(candidate_coc * framebuffer_size.y, -max_coc_diameter, max_coc_diameter);
 }
 
 // Performs a single direction of the separable Gaussian blur kernel.
@@ -299,3 +331,103 @@ fn bokeh_pass_1(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
     return mix(output_0, output_1, 0.5);
 }
 #endif
+
+// Stores the signed circle of confusion of each pixel alongside its color, for
+// the aperture bokeh gather pass.
+//
+// The circle of confusion is remapped from [-max, max] to [0, 1], so that it
+// fits in the alpha channel of LDR textures too.
+@fragment
+fn aperture_coc(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let coc = calculate_signed_circle_of_confusion(in.position);
+    let color = textureLoad(color_texture_a, vec2<i32>(floor(in.position.xy)), 0).rgb;
+    let max_coc = max(dof_params.max_circle_of_confusion_diameter, 1e-4);
+    return vec4(color, coc / max_coc * 0.5 + 0.5);
+}
+
+#ifdef APERTURE
+
+// Loads the color and signed circle of confusion that `aperture_coc` stored
+// for a pixel.
+fn load_color_and_coc(frag_coord: vec2<i32>) -> vec4<f32> {
+    let size = vec2<i32>(textureDimensions(color_texture_a));
+    let texel = textureLoad(color_texture_a, clamp(frag_coord, vec2(0), size - 1), 0);
+    let coc = (texel.a * 2.0 - 1.0) * dof_params.max_circle_of_confusion_diameter;
+    return vec4(texel.rgb, coc);
+}
+
+// Returns the offset, in pixels, of a sample in a spiral filling a disc of the
+// given radius.
+fn spiral_offset(index: u32, count: u32, radius: f32) -> vec2<f32> {
+    let distance = radius * sqrt((f32(index) + 0.5) / f32(count));
+    let angle = f32(index) * GOLDEN_ANGLE;
+    return distance * vec2(cos(angle), sin(angle));
+}
+
+// Returns how much of a sample whose bokeh has diameter `coc` covers a pixel
+// `offset` pixels away from the center of the bokeh.
+fn aperture_coverage(offset: vec2<f32>, coc: f32) -> f32 {
+    let uv = offset / coc + 0.5;
+    if (any(uv < vec2(0.0)) || any(uv > vec2(1.0))) {
+        return 0.0;
+    }
+    return textureSampleLevel(aperture_texture, color_texture_sampler, uv, 0.0).r;
+}
+
+// Gathers the bokeh of the surrounding pixels.
+@fragment
+fn aperture_gather(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let frag_coord = vec2<i32>(floor(in.position.xy));
+    let center = load_color_and_coc(frag_coord);
+
+    // Gather the background and in-focus layer. Samples can only spread as
+    // far as the blur of this pixel, so that blurry backgrounds don't bleed
+    // over sharper objects in front of them.
+    var color = center.rgb;
+    if (center.a > 1.0) {
+        var far_sum = vec3(0.0);
+        var far_weight = 0.0;
+        for (var i = 0u; i < APERTURE_FAR_SAMPLE_COUNT; i += 1u) {
+            let offset = round(spiral_offset(i, APERTURE_FAR_SAMPLE_COUNT, center.a * 0.5));
+            let sample = load_color_and_coc(frag_coord + vec2<i32>(offset));
+            let coc = clamp(sample.a, 1.0, center.a);
+            // Larger bokeh spread the same energy over a larger area.
+            let weight = aperture_coverage(-offset, coc) / (coc * coc);
+            far_sum += sample.rgb * weight;
+            far_weight += weight;
+        }
+        if (far_weight > 0.0) {
+            color = far_sum / far_weight;
+        }
+    }
+
+    // Gather the foreground layer, which spreads over everything behind it.
+    // Each sample stands for an equal part of the search disc, so the
+    // coverage of the foreground is estimated by comparing that area to the
+    // area of the sample's bokeh, assuming a round aperture.
+    let near_radius = dof_params.max_circle_of_confusion_diameter * 0.5;
+    let sample_area_ratio = 4.0 * near_radius * near_radius / f32(APERTURE_NEAR_SAMPLE_COUNT);
+    var near_sum = vec3(0.0);
+    var near_weight = 0.0;
+    var near_coverage = 0.0;
+    for (var i = 0u; i < APERTURE_NEAR_SAMPLE_COUNT; i += 1u) {
+        let offset = round(spiral_offset(i, APERTURE_NEAR_SAMPLE_COUNT, near_radius));
+        let sample = load_color_and_coc(frag_coord + vec2<i32>(offset));
+        let coc = -sample.a;
+        if (coc <= 1.0) {
+            continue;
+        }
+        let coverage = aperture_coverage(-offset, coc);
+        let weight = coverage / (coc * coc);
+        near_sum += sample.rgb * weight;
+        near_weight += weight;
+        near_coverage += coverage * sample_area_ratio / (coc * coc);
+    }
+    if (near_weight > 0.0) {
+        color = mix(color, near_sum / near_weight, saturate(near_coverage));
+    }
+
+    return vec4(color, 1.0);
+}
+
+#endif  // APERTURE
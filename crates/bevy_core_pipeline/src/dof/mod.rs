@@ -15,24 +15,25 @@
 //! [Depth of field]: https://en.wikipedia.org/wiki/Depth_of_field
 
 use bevy_app::{App, Plugin};
-use bevy_asset::{load_internal_asset, weak_handle, Handle};
+use bevy_asset::{load_internal_asset, weak_handle, Assets, Handle};
 use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{
     component::Component,
     entity::Entity,
-    query::{QueryItem, With},
+    query::{Has, QueryItem, With},
     reflect::ReflectComponent,
     resource::Resource,
     schedule::IntoSystemConfigs as _,
     system::{lifetimeless::Read, Commands, Query, Res, ResMut},
     world::{FromWorld, World},
 };
-use bevy_image::BevyDefault as _;
-use bevy_math::ops;
+use bevy_image::{BevyDefault as _, Image};
+use bevy_math::{ops, Vec2};
 use bevy_reflect::{prelude::ReflectDefault, Reflect};
 use bevy_render::{
-    camera::{PhysicalCameraParameters, Projection},
+    camera::{Multiview, PhysicalCameraParameters, Projection},
     extract_component::{ComponentUniforms, DynamicUniformIndex, UniformComponentPlugin},
+    render_asset::{RenderAssetUsages, RenderAssets},
     render_graph::{
         NodeRunError, RenderGraphApp as _, RenderGraphContext, ViewNode, ViewNodeRunner,
     },
@@ -41,8 +42,8 @@ use bevy_render::{
             sampler, texture_2d, texture_depth_2d, texture_depth_2d_multisampled, uniform_buffer,
         },
         BindGroup, BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries,
-        CachedRenderPipelineId, ColorTargetState, ColorWrites, FilterMode, FragmentState, LoadOp,
-        Operations, PipelineCache, RenderPassColorAttachment, RenderPassDescriptor,
+        CachedRenderPipelineId, ColorTargetState, ColorWrites, Extent3d, FilterMode, FragmentState,
+        LoadOp, Operations, PipelineCache, RenderPassColorAttachment, RenderPassDescriptor,
         RenderPipelineDescriptor, Sampler, SamplerBindingType, SamplerDescriptor, Shader,
         ShaderStages, ShaderType, SpecializedRenderPipeline, SpecializedRenderPipelines, StoreOp,
         TextureDescriptor, TextureDimension, TextureFormat, TextureSampleType, TextureUsages,
@@ -50,7 +51,7 @@ use bevy_render::{
     renderer::{RenderContext, RenderDevice},
     sync_component::SyncComponentPlugin,
     sync_world::RenderEntity,
-    texture::{CachedTexture, TextureCache},
+    texture::{CachedTexture, GpuImage, TextureCache},
     view::{
         prepare_view_targets, ExtractedView, Msaa, ViewDepthTexture, ViewTarget, ViewUniform,
         ViewUniformOffset, ViewUniforms,
@@ -71,6 +72,15 @@ use crate::{
 
 const DOF_SHADER_HANDLE: Handle<Shader> = weak_handle!("c3580ddc-2cbc-4535-a02b-9a2959066b52");
 
+/// The handle to the default aperture shape of [`BokehAperture`], an octagon.
+const DEFAULT_APERTURE_SHAPE_HANDLE: Handle<Image> =
+    weak_handle!("4b7d2e91-6c3a-4f58-a0e4-9d1c5b8f2a63");
+
+/// The width and height, in texels, of the aperture shapes that
+/// [`BokehAperture::polygon_image`] and [`BokehAperture::ellipse_image`]
+/// create.
+const APERTURE_SHAPE_IMAGE_SIZE: u32 = 64;
+
 /// A plugin that adds support for the depth of field effect to Bevy.
 pub struct DepthOfFieldPlugin;
 
@@ -146,6 +156,119 @@ pub enum DepthOfFieldMode {
     /// If targeting native platforms, consider using [`DepthOfFieldMode::Bokeh`] instead.
     #[default]
     Gaussian,
+
+    /// A physically based simulation, in which every out-of-focus point of
+    /// light spreads into the shape of the lens aperture.
+    ///
+    /// The shape is taken from the camera's [`BokehAperture`], or is an
+    /// octagon if the camera has none. Objects in front of the focal plane
+    /// blur over the sharper objects behind them, as they do with real
+    /// lenses.
+    ///
+    /// This is the slowest mode. Its cost grows with
+    /// [`DepthOfField::max_circle_of_confusion_diameter`]. It works on native
+    /// and WebGPU.
+    Aperture,
+}
+
+/// The shape of the aperture of the lens, which out-of-focus points of light
+/// take on when a camera uses [`DepthOfFieldMode::Aperture`].
+///
+/// Real apertures are usually polygons with as many sides as the lens has
+/// blades, or ellipses on anamorphic lenses. See
+/// [`BokehAperture::polygon_image`] and [`BokehAperture::ellipse_image`] to
+/// create those shapes.
+#[derive(Component, Clone, Reflect)]
+#[reflect(Component, Default)]
+pub struct BokehAperture {
+    /// A texture containing the shape of the aperture, centered and touching
+    /// the edges of the texture.
+    ///
+    /// Only the red channel is used: 1.0 inside the aperture and 0.0 outside.
+    /// Intermediate values can be used for soft edges or to simulate uneven
+    /// brightness across the aperture.
+    pub shape: Handle<Image>,
+}
+
+impl Default for BokehAperture {
+    fn default() -> Self {
+        Self {
+            shape: DEFAULT_APERTURE_SHAPE_HANDLE,
+        }
+    }
+}
+
+impl BokehAperture {
+    /// Creates an image of a regular polygon with `blade_count` sides, such as
+    /// the aperture of a lens with that many blades, for use as a
+    /// [`BokehAperture::shape`].
+    ///
+    /// Blade counts below 3 are treated as 3.
+    ///
+    /// `rotation` is the angle of the polygon in radians. At 0.0, one of its
+    /// corners points up.
+    pub fn polygon_image(blade_count: u32, rotation: f32) -> Image {
+        let blade_count = blade_count.max(3) as f32;
+        let blade_angle = core::f32::consts::TAU / blade_count;
+        // The distance from the center to the middle of each side, relative to
+        // the distance to the corners.
+        let apothem = ops::cos(0.5 * blade_angle);
+        aperture_shape_image(|position| {
+            let angle = ops::atan2(position.x, -position.y) - rotation;
+            let side_angle = (angle.rem_euclid(blade_angle)) - 0.5 * blade_angle;
+            position.length() * ops::cos(side_angle) / apothem
+        })
+    }
+
+    /// Creates an image of an ellipse, such as the aperture of an anamorphic
+    /// lens, for use as a [`BokehAperture::shape`].
+    ///
+    /// `aspect_ratio` is the width of the ellipse divided by its height.
+    /// Anamorphic lenses produce tall ellipses, with aspect ratios below 1.0.
+    /// The aspect ratio is clamped so that the ellipse is at least two texels
+    /// wide and tall, and a NaN aspect ratio produces a circle.
+    pub fn ellipse_image(aspect_ratio: f32) -> Image {
+        let min_aspect_ratio = 2.0 / APERTURE_SHAPE_IMAGE_SIZE as f32;
+        let aspect_ratio = if aspect_ratio.is_nan() {
+            1.0
+        } else {
+            aspect_ratio.clamp(min_aspect_ratio, 1.0 / min_aspect_ratio)
+        };
+        let scale = if aspect_ratio >= 1.0 {
+            Vec2::new(1.0, aspect_ratio)
+        } else {
+            Vec2::new(1.0 / aspect_ratio, 1.0)
+        };
+        aperture_shape_image(|position| (position * scale).length())
+    }
+}
+
+/// Rasterizes an aperture shape, given a function that returns, for a position
+/// relative to the center of the image with the edges at ±1, a distance that
+/// is less than 1 inside the shape.
+fn aperture_shape_image(distance: impl Fn(Vec2) -> f32) -> Image {
+    let size = APERTURE_SHAPE_IMAGE_SIZE;
+    let mut data = Vec::with_capacity((size * size) as usize);
+    for y in 0..size {
+        for x in 0..size {
+            let position = (Vec2::new(x as f32, y as f32) + 0.5) / size as f32 * 2.0 - 1.0;
+            // Antialias the edge over about one texel.
+            let coverage = ((1.0 - distance(position)) * size as f32 * 0.5 + 0.5).clamp(0.0, 1.0);
+            data.push((coverage * 255.0).round() as u8);
+        }
+    }
+
+    Image::new(
+        Extent3d {
+            width: size,
+            height: size,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::R8Unorm,
+        RenderAssetUsages::RENDER_WORLD,
+    )
 }
 
 /// Data about the depth of field effect that's uploaded to the GPU.
@@ -202,14 +325,24 @@ enum DofPass {
     BokehPass0,
     /// The second bokeh pass: two diagonals.
     BokehPass1,
+    /// The first aperture bokeh pass, which stores the circle of confusion.
+    ApertureCoc,
+    /// The second aperture bokeh pass, which gathers the bokeh.
+    ApertureGather,
 }
 
 impl Plugin for DepthOfFieldPlugin {
     fn build(&self, app: &mut App) {
         load_internal_asset!(app, DOF_SHADER_HANDLE, "dof.wgsl", Shader::from_wgsl);
 
+        app.world_mut().resource_mut::<Assets<Image>>().insert(
+            DEFAULT_APERTURE_SHAPE_HANDLE.id(),
+            BokehAperture::polygon_image(8, 0.0),
+        );
+
         app.register_type::<DepthOfField>();
         app.register_type::<DepthOfFieldMode>();
+        app.register_type::<BokehAperture>();
         app.add_plugins(UniformComponentPlugin::<DepthOfFieldUniform>::default());
 
         app.add_plugins(SyncComponentPlugin::<DepthOfField>::default());
@@ -289,6 +422,10 @@ pub enum DepthOfFieldPipelines {
         pass_0: CachedRenderPipelineId,
         pass_1: CachedRenderPipelineId,
     },
+    Aperture {
+        coc: CachedRenderPipelineId,
+        gather: CachedRenderPipelineId,
+    },
 }
 
 struct DepthOfFieldPipelineRenderInfo {
@@ -297,6 +434,7 @@ struct DepthOfFieldPipelineRenderInfo {
     pipeline: CachedRenderPipelineId,
     is_dual_input: bool,
     is_dual_output: bool,
+    is_aperture_input: bool,
 }
 
 /// The extra texture used as the second render target for the hexagonal bokeh
@@ -317,6 +455,12 @@ pub struct ViewDepthOfFieldBindGroupLayouts {
     ///
     /// This will only be present if bokeh is in use.
     dual_input: Option<BindGroupLayout>,
+
+    /// The bind group layout for the aperture bokeh gather pass, which takes
+    /// the aperture shape alongside its input.
+    ///
+    /// This will only be present if aperture bokeh is in use.
+    aperture_input: Option<BindGroupLayout>,
 }
 
 /// Information needed to specialize the pipeline corresponding to a pass of the
@@ -338,6 +482,8 @@ impl ViewNode for DepthOfFieldNode {
         Read<ViewDepthOfFieldBindGroupLayouts>,
         Read<DynamicUniformIndex<DepthOfFieldUniform>>,
         Option<Read<AuxiliaryDepthOfFieldTexture>>,
+        Option<Read<BokehAperture>>,
+        Has<Multiview>,
     );
 
     fn run<'w>(
@@ -352,9 +498,18 @@ impl ViewNode for DepthOfFieldNode {
             view_bind_group_layouts,
             depth_of_field_uniform_index,
             auxiliary_dof_texture,
+            bokeh_aperture,
+            multiview,
         ): QueryItem<'w, Self::ViewQuery>,
         world: &'w World,
     ) -> Result<(), NodeRunError> {
+        if multiview {
+            once!(warn!(
+                "Depth of field isn't supported on `Multiview` cameras and is skipped"
+            ));
+            return Ok(());
+        }
+
         let pipeline_cache = world.resource::<PipelineCache>();
         let view_uniforms = world.resource::<ViewUniforms>();
         let global_bind_group = world.resource::<DepthOfFieldGlobalBindGroup>();
@@ -398,6 +553,29 @@ impl ViewNode for DepthOfFieldNode {
                         &auxiliary_dof_texture.default_view,
                     )),
                 )
+            } else if pipeline_render_info.is_aperture_input {
+                let gpu_images = world.resource::<RenderAssets<GpuImage>>();
+                let shape = bokeh_aperture
+                    .map_or(&DEFAULT_APERTURE_SHAPE_HANDLE, |bokeh_aperture| {
+                        &bokeh_aperture.shape
+                    });
+                let (Some(aperture_shape), Some(aperture_input_bind_group_layout)) = (
+                    gpu_images.get(shape),
+                    view_bind_group_layouts.aperture_input.as_ref(),
+                ) else {
+                    // Wait for the aperture shape to load.
+                    return Ok(());
+                };
+                render_context.render_device().create_bind_group(
+                    Some(pipeline_render_info.view_bind_group_label),
+                    aperture_input_bind_group_layout,
+                    &BindGroupEntries::sequential((
+                        view_uniforms_binding,
+                        view_depth_texture.view(),
+                        postprocess.source,
+                        &aperture_shape.texture_view,
+                    )),
+                )
             } else {
                 render_context.render_device().create_bind_group(
                     Some(pipeline_render_info.view_bind_group_label),
@@ -492,7 +670,10 @@ impl DepthOfField {
     /// obtained.
     ///
     /// All fields of the returned [`DepthOfField`] other than
-    /// `focal_length` and `aperture_f_stops` are set to their default values.
+    /// `sensor_height` and `aperture_f_stops` are set to their default values.
+    /// The size of the blur in every [`DepthOfFieldMode`], including the
+    /// bokeh of [`DepthOfFieldMode::Aperture`], follows from these and the
+    /// field of view of the camera, as it would for a real lens.
     pub fn from_physical_camera(camera: &PhysicalCameraParameters) -> DepthOfField {
         DepthOfField {
             sensor_height: camera.sensor_height,
@@ -564,7 +745,7 @@ pub fn prepare_depth_of_field_view_bind_group_layouts(
         // If needed, create the bind group layout for the second bokeh pass,
         // which takes two inputs. We only need to do this if bokeh is in use.
         let dual_input = match depth_of_field.mode {
-            DepthOfFieldMode::Gaussian => None,
+            DepthOfFieldMode::Gaussian | DepthOfFieldMode::Aperture => None,
             DepthOfFieldMode::Bokeh => Some(render_device.create_bind_group_layout(
                 Some("depth of field bind group layout (dual input)"),
                 &BindGroupLayoutEntries::sequential(
@@ -583,11 +764,34 @@ pub fn prepare_depth_of_field_view_bind_group_layouts(
             )),
         };
 
+        // If needed, create the bind group layout for the aperture bokeh gather
+        // pass, which takes the aperture shape too.
+        let aperture_input = match depth_of_field.mode {
+            DepthOfFieldMode::Gaussian | DepthOfFieldMode::Bokeh => None,
+            DepthOfFieldMode::Aperture => Some(render_device.create_bind_group_layout(
+                Some("depth of field bind group layout (aperture input)"),
+                &BindGroupLayoutEntries::sequential(
+                    ShaderStages::FRAGMENT,
+                    (
+                        uniform_buffer::<ViewUniform>(true),
+                        if *msaa != Msaa::Off {
+                            texture_depth_2d_multisampled()
+                        } else {
+                            texture_depth_2d()
+                        },
+                        texture_2d(TextureSampleType::Float { filterable: true }),
+                        texture_2d(TextureSampleType::Float { filterable: true }),
+                    ),
+                ),
+            )),
+        };
+
         commands
             .entity(view)
             .insert(ViewDepthOfFieldBindGroupLayouts {
                 single_input,
                 dual_input,
+                aperture_input,
             });
     }
 }
@@ -739,6 +943,31 @@ pub fn prepare_depth_of_field_pipelines(
                         ),
                     });
             }
+
+            DepthOfFieldMode::Aperture => {
+                commands
+                    .entity(entity)
+                    .insert(DepthOfFieldPipelines::Aperture {
+                        coc: pipelines.specialize(
+                            &pipeline_cache,
+                            &dof_pipeline,
+                            DepthOfFieldPipelineKey {
+                                hdr,
+                                multisample,
+                                pass: DofPass::ApertureCoc,
+                            },
+                        ),
+                        gather: pipelines.specialize(
+                            &pipeline_cache,
+                            &dof_pipeline,
+                            DepthOfFieldPipelineKey {
+                                hdr,
+                                multisample,
+                                pass: DofPass::ApertureGather,
+                            },
+                        ),
+                    });
+            }
         }
     }
 }
@@ -761,8 +990,9 @@ impl SpecializedRenderPipeline for DepthOfFieldPipeline {
 
         // Select bind group 0, the view-specific bind group.
         match key.pass {
-            DofPass::GaussianHorizontal | DofPass::GaussianVertical => {
-                // Gaussian blurs take only a single input and output.
+            DofPass::GaussianHorizontal | DofPass::GaussianVertical | DofPass::ApertureCoc => {
+                // Gaussian blurs and the circle of confusion pass take only a
+                // single input and output.
                 layout.push(self.view_bind_group_layouts.single_input.clone());
             }
             DofPass::BokehPass0 => {
@@ -782,6 +1012,18 @@ impl SpecializedRenderPipeline for DepthOfFieldPipeline {
                 layout.push(dual_input_bind_group_layout);
                 shader_defs.push("DUAL_INPUT".into());
             }
+            DofPass::ApertureGather => {
+                // The aperture gather pass takes the output of the circle of
+                // confusion pass and the aperture shape.
+                let aperture_input_bind_group_layout = self
+                    .view_bind_group_layouts
+                    .aperture_input
+                    .as_ref()
+                    .expect("Aperture depth of field bind group should have been created by now")
+                    .clone();
+                layout.push(aperture_input_bind_group_layout);
+                shader_defs.push("APERTURE".into());
+            }
         }
 
         // Add bind group 1, the global bind group.
@@ -807,10 +1049,13 @@ impl SpecializedRenderPipeline for DepthOfFieldPipeline {
                     DofPass::GaussianVertical => "gaussian_vertical".into(),
                     DofPass::BokehPass0 => "bokeh_pass_0".into(),
                     DofPass::BokehPass1 => "bokeh_pass_1".into(),
+                    DofPass::ApertureCoc => "aperture_coc".into(),
+                    DofPass::ApertureGather => "aperture_gather".into(),
                 },
                 targets,
             }),
             zero_initialize_workgroup_memory: false,
+            multiview: None,
         }
     }
 }
@@ -818,7 +1063,14 @@ impl SpecializedRenderPipeline for DepthOfFieldPipeline {
 /// Extracts all [`DepthOfField`] components into the render world.
 fn extract_depth_of_field_settings(
     mut commands: Commands,
-    mut query: Extract<Query<(RenderEntity, &DepthOfField, &Projection)>>,
+    mut query: Extract<
+        Query<(
+            RenderEntity,
+            &DepthOfField,
+            &Projection,
+            Option<&BokehAperture>,
+        )>,
+    >,
 ) {
     if !DEPTH_TEXTURE_SAMPLING_SUPPORTED {
         once!(info!(
@@ -827,7 +1079,7 @@ fn extract_depth_of_field_settings(
         return;
     }
 
-    for (entity, depth_of_field, projection) in query.iter_mut() {
+    for (entity, depth_of_field, projection, bokeh_aperture) in query.iter_mut() {
         let mut entity_commands = commands
             .get_entity(entity)
             .expect("Depth of field entity wasn't synced.");
@@ -842,6 +1094,7 @@ fn extract_depth_of_field_settings(
                 DepthOfFieldPipelines,
                 AuxiliaryDepthOfFieldTexture,
                 ViewDepthOfFieldBindGroupLayouts,
+                BokehAperture,
             )>();
             continue;
         };
@@ -864,6 +1117,11 @@ fn extract_depth_of_field_settings(
                 pad_c: 0,
             },
         ));
+
+        match bokeh_aperture {
+            Some(bokeh_aperture) => entity_commands.insert(bokeh_aperture.clone()),
+            None => entity_commands.remove::<BokehAperture>(),
+        };
     }
 }
 
@@ -889,6 +1147,7 @@ impl DepthOfFieldPipelines {
                     pipeline: horizontal_pipeline,
                     is_dual_input: false,
                     is_dual_output: false,
+                    is_aperture_input: false,
                 },
                 DepthOfFieldPipelineRenderInfo {
                     pass_label: "depth of field pass (vertical Gaussian)",
@@ -896,6 +1155,7 @@ impl DepthOfFieldPipelines {
                     pipeline: vertical_pipeline,
                     is_dual_input: false,
                     is_dual_output: false,
+                    is_aperture_input: false,
                 },
             ],
 
@@ -909,6 +1169,7 @@ impl DepthOfFieldPipelines {
                     pipeline: pass_0_pipeline,
                     is_dual_input: false,
                     is_dual_output: true,
+                    is_aperture_input: false,
                 },
                 DepthOfFieldPipelineRenderInfo {
                     pass_label: "depth of field pass (bokeh pass 1)",
@@ -916,8 +1177,162 @@ impl DepthOfFieldPipelines {
                     pipeline: pass_1_pipeline,
                     is_dual_input: true,
                     is_dual_output: false,
+                    is_aperture_input: false,
                 },
             ],
+
+            DepthOfFieldPipelines::Aperture {
+                coc: coc_pipeline,
+                gather: gather_pipeline,
+            } => [
+                DepthOfFieldPipelineRenderInfo {
+                    pass_label: "depth of field pass (aperture circle of confusion)",
+                    view_bind_group_label:
+                        "depth of field view bind group (aperture circle of confusion)",
+                    pipeline: coc_pipeline,
+                    is_dual_input: false,
+                    is_dual_output: false,
+                    is_aperture_input: false,
+                },
+                DepthOfFieldPipelineRenderInfo {
+                    pass_label: "depth of field pass (aperture gather)",
+                    view_bind_group_label: "depth of field view bind group (aperture gather)",
+                    pipeline: gather_pipeline,
+                    is_dual_input: false,
+                    is_dual_output: false,
+                    is_aperture_input: true,
+                },
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BokehAperture, APERTURE_SHAPE_IMAGE_SIZE};
+    use bevy_image::Image;
+    use bevy_math::ops;
+
+    const SIZE: usize = APERTURE_SHAPE_IMAGE_SIZE as usize;
+
+    fn texel(image: &Image, x: usize, y: usize) -> u8 {
+        image.data.as_ref().unwrap()[y * SIZE + x]
+    }
+
+    /// Returns the fraction of the image that the shape covers.
+    fn coverage(image: &Image) -> f32 {
+        let data = image.data.as_ref().unwrap();
+        data.iter().map(|&value| value as f32 / 255.0).sum::<f32>() / data.len() as f32
+    }
+
+    /// Returns whether the image is the same when mirrored horizontally, or
+    /// vertically, allowing for rounding.
+    fn is_mirrored(image: &Image, horizontally: bool) -> bool {
+        (0..SIZE).all(|y| {
+            (0..SIZE).all(|x| {
+                let (mirrored_x, mirrored_y) = if horizontally {
+                    (SIZE - 1 - x, y)
+                } else {
+                    (x, SIZE - 1 - y)
+                };
+                texel(image, x, y).abs_diff(texel(image, mirrored_x, mirrored_y)) <= 1
+            })
+        })
+    }
+
+    #[test]
+    fn polygon_covers_its_area() {
+        for blade_count in [3, 5, 6, 8, 100] {
+            let image = BokehAperture::polygon_image(blade_count, 0.0);
+            assert_eq!(image.data.as_ref().unwrap().len(), SIZE * SIZE);
+
+            // A regular polygon with a circumradius of 1 in a 2x2 image.
+            let angle = core::f32::consts::TAU / blade_count as f32;
+            let area = 0.5 * blade_count as f32 * ops::sin(angle) / 4.0;
+            assert!(
+                (coverage(&image) - area).abs() < 0.01,
+                "{blade_count} blades cover {} instead of {area}",
+                coverage(&image)
+            );
+            assert_eq!(texel(&image, SIZE / 2, SIZE / 2), 255);
+            assert_eq!(texel(&image, 0, 0), 0);
+            // One corner points up and touches the edge.
+            assert!(texel(&image, SIZE / 2, 0) > 0);
+        }
+    }
+
+    #[test]
+    fn polygon_is_symmetric() {
+        for blade_count in [3, 5, 8] {
+            let image = BokehAperture::polygon_image(blade_count, 0.0);
+            assert!(is_mirrored(&image, true));
+        }
+        // Polygons with an even number of sides are also symmetric vertically.
+        assert!(is_mirrored(&BokehAperture::polygon_image(6, 0.0), false));
+
+        // Rotating by the angle between two blades doesn't change the shape.
+        let rotated = BokehAperture::polygon_image(6, core::f32::consts::TAU / 6.0);
+        let image = BokehAperture::polygon_image(6, 0.0);
+        let (rotated, image) = (rotated.data.unwrap(), image.data.unwrap());
+        assert!(rotated.iter().zip(&image).all(|(a, b)| a.abs_diff(*b) <= 1));
+    }
+
+    #[test]
+    fn polygon_has_at_least_three_sides() {
+        let triangle = BokehAperture::polygon_image(3, 0.0).data;
+        for blade_count in [0, 1, 2] {
+            assert_eq!(
+                BokehAperture::polygon_image(blade_count, 0.0).data,
+                triangle
+            );
         }
     }
+
+    #[test]
+    fn ellipse_covers_its_area() {
+        for aspect_ratio in [0.25, 0.5, 1.0, 2.0, 4.0] {
+            let image = BokehAperture::ellipse_image(aspect_ratio);
+            let area = core::f32::consts::FRAC_PI_4 * aspect_ratio.min(1.0 / aspect_ratio);
+            assert!(
+                (coverage(&image) - area).abs() < 0.01,
+                "aspect ratio {aspect_ratio} covers {} instead of {area}",
+                coverage(&image)
+            );
+            assert!(is_mirrored(&image, true));
+            assert!(is_mirrored(&image, false));
+        }
+
+        // Tall ellipses are wide ones turned sideways.
+        let tall = BokehAperture::ellipse_image(0.5);
+        let wide = BokehAperture::ellipse_image(2.0);
+        assert!(texel(&tall, SIZE / 2, 0) > 0);
+        assert_eq!(texel(&tall, 0, SIZE / 2), 0);
+        for y in 0..SIZE {
+            for x in 0..SIZE {
+                assert_eq!(texel(&tall, x, y), texel(&wide, y, x));
+            }
+        }
+    }
+
+    #[test]
+    fn degenerate_ellipses_are_visible() {
+        let thinnest = BokehAperture::ellipse_image(0.0);
+        for aspect_ratio in [-1.0, f32::MIN_POSITIVE, 1e-6] {
+            assert_eq!(
+                BokehAperture::ellipse_image(aspect_ratio).data,
+                thinnest.data
+            );
+        }
+        assert!(coverage(&thinnest) > 0.0);
+        assert!(is_mirrored(&thinnest, true));
+
+        let widest = BokehAperture::ellipse_image(f32::INFINITY);
+        assert!(coverage(&widest) > 0.0);
+        assert_eq!(coverage(&widest), coverage(&thinnest));
+
+        assert_eq!(
+            BokehAperture::ellipse_image(f32::NAN).data,
+            BokehAperture::ellipse_image(1.0).data
+        );
+    }
 }
@@ -323,7 +323,7 @@ fn load_mip_0(x: u32, y: u32) -> f32 {
     var result = textureLoad(mip_0, vec2(x, y), 0);
     let sample_count = i32(textureNumSamples(mip_0));
     for (var sample = 1; sample < sample_count; sample += 1) {
-        result = min(result, textureLoad(mip_0, vec2(x, y), sample));
+        result = reduce_2(result, textureLoad(mip_0, vec2(x, y), sample));
     }
     return result;
 #else   // MULTISAMPLE
@@ -333,6 +333,18 @@ fn load_mip_0(x: u32, y: u32) -> f32 {
 #endif  // MESHLET_VISIBILITY_BUFFER_RASTER_PASS_OUTPUT
 }
 
+// By default, each texel of the pyramid holds the farthest depth beneath it,
+// which is what occlusion culling needs. With `DOWNSAMPLE_NEAREST`, it instead
+// holds the nearest depth, which is what screen-space ray marching needs in
+// order to skip over empty space conservatively.
+fn reduce_2(a: f32, b: f32) -> f32 {
+#ifdef DOWNSAMPLE_NEAREST
+    return max(a, b);
+#else   // DOWNSAMPLE_NEAREST
+    return min(a, b);
+#endif  // DOWNSAMPLE_NEAREST
+}
+
 fn reduce_4(v: vec4f) -> f32 {
-    return min(min(v.x, v.y), min(v.z, v.w));
+    return reduce_2(reduce_2(v.x, v.y), reduce_2(v.z, v.w));
 }
@@ -1,7 +1,7 @@
 //! Downsampling of textures to produce mipmap levels.
 //!
-//! Currently, this module only supports generation of hierarchical Z buffers
-//! for occlusion culling. It's marked experimental because the shader is
+//! Currently, this module only supports generation of hierarchical Z buffers,
+//! which are used for occlusion culling and screen-space ray marching. It's marked experimental because the shader is
 //! designed only for power-of-two texture sizes and is slightly incorrect for
 //! non-power-of-two depth buffer sizes.
 
@@ -29,10 +29,10 @@ use bevy_render::{
         BindGroup, BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries,
         CachedComputePipelineId, ComputePassDescriptor, ComputePipeline, ComputePipelineDescriptor,
         DownlevelFlags, Extent3d, IntoBinding, PipelineCache, PushConstantRange, Sampler,
-        SamplerBindingType, SamplerDescriptor, Shader, ShaderStages, SpecializedComputePipeline,
-        SpecializedComputePipelines, StorageTextureAccess, TextureAspect, TextureDescriptor,
-        TextureDimension, TextureFormat, TextureSampleType, TextureUsages, TextureView,
-        TextureViewDescriptor, TextureViewDimension,
+        SamplerBindingType, SamplerDescriptor, Shader, ShaderDefVal, ShaderStages,
+        SpecializedComputePipeline, SpecializedComputePipelines, StorageTextureAccess,
+        TextureAspect, TextureDescriptor, TextureDimension, TextureFormat, TextureSampleType,
+        TextureUsages, TextureView, TextureViewDescriptor, TextureViewDimension,
     },
     renderer::{RenderAdapter, RenderContext, RenderDevice},
     texture::TextureCache,
@@ -255,11 +255,45 @@ pub struct DownsampleDepthPipelines {
     first_multisample: DownsampleDepthPipeline,
     /// The second pass of the pipeline, when the depth buffer is multisampled.
     second_multisample: DownsampleDepthPipeline,
+    /// The first pass of the pipeline that keeps the *nearest* depth, when the
+    /// depth buffer is *not* multisampled.
+    first_nearest: DownsampleDepthPipeline,
+    /// The second pass of the pipeline that keeps the *nearest* depth, when
+    /// the depth buffer is *not* multisampled.
+    second_nearest: DownsampleDepthPipeline,
     /// The sampler that the depth downsampling shader uses to sample the depth
     /// buffer.
     sampler: Sampler,
 }
 
+impl DownsampleDepthPipelines {
+    /// Returns the layout of the bind group that [`ViewDepthPyramid::create_bind_group`]
+    /// expects for a non-multisampled depth buffer.
+    pub fn bind_group_layout(&self) -> &BindGroupLayout {
+        &self.first.bind_group_layout
+    }
+
+    /// Returns the sampler that the depth downsampling shader expects.
+    pub fn sampler(&self) -> &Sampler {
+        &self.sampler
+    }
+
+    /// Returns the IDs of the first and second pass pipelines that build a
+    /// pyramid of the *nearest* depth from a non-multisampled depth buffer.
+    ///
+    /// Unlike the default pyramid, which conservatively stores the farthest
+    /// depth for occlusion culling, this one is suitable for hierarchical
+    /// screen-space ray marching.
+    pub fn nearest_pipeline_ids(
+        &self,
+    ) -> Option<(CachedComputePipelineId, CachedComputePipelineId)> {
+        Some((
+            self.first_nearest.pipeline_id?,
+            self.second_nearest.pipeline_id?,
+        ))
+    }
+}
+
 /// Creates the [`DownsampleDepthPipelines`] if downsampling is supported on the
 /// current platform.
 fn create_downsample_depth_pipelines(
@@ -310,6 +344,8 @@ fn create_downsample_depth_pipelines(
         second: DownsampleDepthPipeline::new(standard_bind_group_layout.clone()),
         first_multisample: DownsampleDepthPipeline::new(multisampled_bind_group_layout.clone()),
         second_multisample: DownsampleDepthPipeline::new(multisampled_bind_group_layout.clone()),
+        first_nearest: DownsampleDepthPipeline::new(standard_bind_group_layout.clone()),
+        second_nearest: DownsampleDepthPipeline::new(standard_bind_group_layout.clone()),
         sampler,
     };
 
@@ -337,6 +373,18 @@ fn create_downsample_depth_pipelines(
             &downsample_depth_pipelines.second_multisample,
             DownsampleDepthPipelineKey::SECOND_PHASE | DownsampleDepthPipelineKey::MULTISAMPLE,
         ));
+    downsample_depth_pipelines.first_nearest.pipeline_id =
+        Some(specialized_compute_pipelines.specialize(
+            &pipeline_cache,
+            &downsample_depth_pipelines.first_nearest,
+            DownsampleDepthPipelineKey::NEAREST,
+        ));
+    downsample_depth_pipelines.second_nearest.pipeline_id =
+        Some(specialized_compute_pipelines.specialize(
+            &pipeline_cache,
+            &downsample_depth_pipelines.second_nearest,
+            DownsampleDepthPipelineKey::SECOND_PHASE | DownsampleDepthPipelineKey::NEAREST,
+        ));
 
     commands.insert_resource(downsample_depth_pipelines);
 }
@@ -395,25 +443,52 @@ bitflags! {
         /// True if this shader is the second phase of the downsample depth
         /// process; false if this shader is the first phase.
         const SECOND_PHASE = 2;
+        /// True if each texel of the pyramid should hold the nearest depth
+        /// beneath it instead of the farthest.
+        const NEAREST = 4;
     }
 }
 
-impl SpecializedComputePipeline for DownsampleDepthPipeline {
-    type Key = DownsampleDepthPipelineKey;
-
-    fn specialize(&self, key: Self::Key) -> ComputePipelineDescriptor {
+impl DownsampleDepthPipelineKey {
+    /// Returns the shader defs of the pipeline with this key.
+    pub fn shader_defs(self) -> Vec<ShaderDefVal> {
         let mut shader_defs = vec![];
-        if key.contains(DownsampleDepthPipelineKey::MULTISAMPLE) {
+        if self.contains(DownsampleDepthPipelineKey::MULTISAMPLE) {
             shader_defs.push("MULTISAMPLE".into());
         }
+        if self.contains(DownsampleDepthPipelineKey::NEAREST) {
+            shader_defs.push("DOWNSAMPLE_NEAREST".into());
+        }
+        shader_defs
+    }
+
+    /// Returns the name of the shader entry point of the pipeline with this
+    /// key.
+    pub fn entry_point(self) -> &'static str {
+        if self.contains(DownsampleDepthPipelineKey::SECOND_PHASE) {
+            "downsample_depth_second"
+        } else {
+            "downsample_depth_first"
+        }
+    }
+}
 
+impl SpecializedComputePipeline for DownsampleDepthPipeline {
+    type Key = DownsampleDepthPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> ComputePipelineDescriptor {
         let label = format!(
-            "downsample depth{}{} pipeline",
+            "downsample depth{}{}{} pipeline",
             if key.contains(DownsampleDepthPipelineKey::MULTISAMPLE) {
                 " multisample"
             } else {
                 ""
             },
+            if key.contains(DownsampleDepthPipelineKey::NEAREST) {
+                " nearest"
+            } else {
+                ""
+            },
             if key.contains(DownsampleDepthPipelineKey::SECOND_PHASE) {
                 " second phase"
             } else {
@@ -430,12 +505,8 @@ impl SpecializedComputePipeline for DownsampleDepthPipeline {
                 range: 0..8,
             }],
             shader: DOWNSAMPLE_DEPTH_SHADER_HANDLE,
-            shader_defs,
-            entry_point: if key.contains(DownsampleDepthPipelineKey::SECOND_PHASE) {
-                "downsample_depth_second".into()
-            } else {
-                "downsample_depth_first".into()
-            },
+            shader_defs: key.shader_defs(),
+            entry_point: key.entry_point().into(),
             zero_initialize_workgroup_memory: false,
         }
     }
@@ -708,3 +779,70 @@ fn prepare_downsample_depth_view_bind_groups(
             ));
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::DownsampleDepthPipelineKey;
+    use bevy_render::render_resource::ShaderDefVal;
+
+    fn has_def(key: DownsampleDepthPipelineKey, name: &str) -> bool {
+        key.shader_defs()
+            .iter()
+            .any(|def| matches!(def, ShaderDefVal::Bool(def, true) if def == name))
+    }
+
+    #[test]
+    fn only_nearest_pipelines_keep_nearest_depth() {
+        // The pyramids for occlusion culling keep the farthest depth.
+        for key in [
+            DownsampleDepthPipelineKey::empty(),
+            DownsampleDepthPipelineKey::SECOND_PHASE,
+            DownsampleDepthPipelineKey::MULTISAMPLE,
+            DownsampleDepthPipelineKey::MULTISAMPLE | DownsampleDepthPipelineKey::SECOND_PHASE,
+        ] {
+            assert!(!has_def(key, "DOWNSAMPLE_NEAREST"), "{}", key.bits());
+        }
+        for key in [
+            DownsampleDepthPipelineKey::NEAREST,
+            DownsampleDepthPipelineKey::NEAREST | DownsampleDepthPipelineKey::SECOND_PHASE,
+        ] {
+            assert!(has_def(key, "DOWNSAMPLE_NEAREST"), "{}", key.bits());
+            assert!(!has_def(key, "MULTISAMPLE"), "{}", key.bits());
+        }
+        assert!(has_def(
+            DownsampleDepthPipelineKey::MULTISAMPLE,
+            "MULTISAMPLE"
+        ));
+    }
+
+    #[test]
+    fn second_phase_selects_entry_point() {
+        for key in [
+            DownsampleDepthPipelineKey::empty(),
+            DownsampleDepthPipelineKey::NEAREST,
+        ] {
+            assert_eq!(key.entry_point(), "downsample_depth_first");
+            assert_eq!(
+                (key | DownsampleDepthPipelineKey::SECOND_PHASE).entry_point(),
+                "downsample_depth_second"
+            );
+        }
+    }
+
+    #[test]
+    fn shader_handles_every_def() {
+        let shader = include_str!("downsample_depth.wgsl");
+        let all = DownsampleDepthPipelineKey::all();
+        for def in all.shader_defs() {
+            let ShaderDefVal::Bool(name, _) = def else {
+                panic!("unexpected shader def {def:?}");
+            };
+            assert!(shader.contains(&format!("#ifdef {name}")), "{name}");
+        }
+        assert!(shader.contains(&format!("fn {}(", all.entry_point())));
+        assert!(shader.contains(&format!(
+            "fn {}(",
+            DownsampleDepthPipelineKey::empty().entry_point()
+        )));
+    }
+}
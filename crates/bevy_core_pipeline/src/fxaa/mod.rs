@@ -197,6 +197,7 @@ impl SpecializedRenderPipeline for FxaaPipeline {
             multisample: MultisampleState::default(),
             push_constant_ranges: Vec::new(),
             zero_initialize_workgroup_memory: false,
+            multiview: None,
         }
     }
 }
@@ -3,6 +3,7 @@ use std::sync::Mutex;
 use crate::fxaa::{CameraFxaaPipeline, Fxaa, FxaaPipeline};
 use bevy_ecs::{prelude::*, query::QueryItem};
 use bevy_render::{
+    camera::Multiview,
     render_graph::{NodeRunError, RenderGraphContext, ViewNode},
     render_resource::{
         BindGroup, BindGroupEntries, Operations, PipelineCache, RenderPassColorAttachment,
@@ -11,6 +12,8 @@ use bevy_render::{
     renderer::RenderContext,
     view::ViewTarget,
 };
+use bevy_utils::once;
+use tracing::warn;
 
 #[derive(Default)]
 pub struct FxaaNode {
@@ -22,15 +25,23 @@ impl ViewNode for FxaaNode {
         &'static ViewTarget,
         &'static CameraFxaaPipeline,
         &'static Fxaa,
+        Has<Multiview>,
     );
 
     fn run(
         &self,
         _graph: &mut RenderGraphContext,
         render_context: &mut RenderContext,
-        (target, pipeline, fxaa): QueryItem<Self::ViewQuery>,
+        (target, pipeline, fxaa, multiview): QueryItem<Self::ViewQuery>,
         world: &World,
     ) -> Result<(), NodeRunError> {
+        if multiview {
+            once!(warn!(
+                "FXAA isn't supported on `Multiview` cameras and is skipped"
+            ));
+            return Ok(());
+        }
+
         let pipeline_cache = world.resource::<PipelineCache>();
         let fxaa_pipeline = world.resource::<FxaaPipeline>();
 
@@ -20,6 +20,7 @@ pub mod fxaa;
 pub mod motion_blur;
 pub mod msaa_writeback;
 pub mod oit;
+pub mod outline;
 pub mod post_process;
 pub mod prepass;
 mod skybox;
@@ -51,8 +52,11 @@ use crate::{
     fxaa::FxaaPlugin,
     motion_blur::MotionBlurPlugin,
     msaa_writeback::MsaaWritebackPlugin,
+    outline::OutlinePlugin,
     post_process::PostProcessingPlugin,
-    prepass::{DeferredPrepass, DepthPrepass, MotionVectorPrepass, NormalPrepass},
+    prepass::{
+        DeferredPrepass, DepthPrepass, MotionVectorPrepass, NormalPrepass, OutlineIdPrepass,
+    },
     smaa::SmaaPlugin,
     tonemapping::TonemappingPlugin,
     upscaling::UpscalingPlugin,
@@ -78,6 +82,7 @@ impl Plugin for CorePipelinePlugin {
             .register_type::<NormalPrepass>()
             .register_type::<MotionVectorPrepass>()
             .register_type::<DeferredPrepass>()
+            .register_type::<OutlineIdPrepass>()
             .add_plugins((Core2dPlugin, Core3dPlugin, CopyDeferredLightingIdPlugin))
             .add_plugins((
                 BlitPlugin,
@@ -90,6 +95,7 @@ impl Plugin for CorePipelinePlugin {
                 MotionBlurPlugin,
                 DepthOfFieldPlugin,
                 SmaaPlugin,
+                OutlinePlugin,
                 PostProcessingPlugin,
                 OrderIndependentTransparencyPlugin,
                 MipGenerationPlugin,
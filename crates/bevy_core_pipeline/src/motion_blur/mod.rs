@@ -1,6 +1,9 @@
 //! Per-object, per-pixel motion blur.
 //!
 //! Add the [`MotionBlur`] component to a camera to enable motion blur.
+//!
+//! Meshes can opt out of motion blur, or limit how far they're smeared, with
+//! the `NoMotionBlur` and `MaxMotionVelocity` components of `bevy_pbr`.
 
 use crate::{
     core_3d::graph::{Core3d, Node3d},
@@ -10,18 +13,25 @@ use bevy_app::{App, Plugin};
 use bevy_asset::{load_internal_asset, weak_handle, Handle};
 use bevy_ecs::{
     component::{require, Component},
+    entity::{hash_map::EntityHashMap, Entity},
     query::With,
     reflect::ReflectComponent,
+    resource::Resource,
     schedule::IntoSystemConfigs,
+    system::{Commands, Local, Query, Res, ResMut},
 };
+use bevy_math::{Mat4, Quat};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_render::{
     camera::Camera,
-    extract_component::{ExtractComponent, ExtractComponentPlugin, UniformComponentPlugin},
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
     render_graph::{RenderGraphApp, ViewNodeRunner},
-    render_resource::{Shader, ShaderType, SpecializedRenderPipelines},
+    render_resource::{DynamicUniformBuffer, Shader, ShaderType, SpecializedRenderPipelines},
+    renderer::{RenderDevice, RenderQueue},
+    view::ExtractedView,
     Render, RenderApp, RenderSet,
 };
+use bevy_transform::components::GlobalTransform;
 
 pub mod node;
 pub mod pipeline;
@@ -56,7 +66,7 @@ pub mod pipeline;
 /// ));
 /// # }
 /// ````
-#[derive(Reflect, Component, Clone, ExtractComponent, ShaderType)]
+#[derive(Reflect, Component, Clone, ExtractComponent)]
 #[reflect(Component, Default)]
 #[extract_component_filter(With<Camera>)]
 #[require(DepthPrepass, MotionVectorPrepass)]
@@ -91,9 +101,10 @@ pub struct MotionBlur {
     /// Setting this to `3` will result in `3 * 2 + 1 = 7` samples. Setting this to `0` is
     /// equivalent to disabling motion blur.
     pub samples: u32,
-    #[cfg(all(feature = "webgl", target_arch = "wasm32", not(feature = "webgpu")))]
-    // WebGL2 structs must be 16 byte aligned.
-    pub _webgl2_padding: bevy_math::Vec2,
+    /// Which motion is blurred.
+    ///
+    /// The default is [`MotionBlurMode::PerObject`].
+    pub mode: MotionBlurMode,
 }
 
 impl Default for MotionBlur {
@@ -101,12 +112,52 @@ impl Default for MotionBlur {
         Self {
             shutter_angle: 0.5,
             samples: 1,
-            #[cfg(all(feature = "webgl", target_arch = "wasm32", not(feature = "webgpu")))]
-            _webgl2_padding: Default::default(),
+            mode: MotionBlurMode::PerObject,
         }
     }
 }
 
+/// Which motion a [`MotionBlur`] camera blurs.
+#[derive(Reflect, Clone, Copy, Default, PartialEq, Eq, Hash, Debug)]
+#[reflect(Default, PartialEq, Hash, Debug)]
+pub enum MotionBlurMode {
+    /// Blurs everything that moves on screen, using the motion vectors of the
+    /// motion vector prepass.
+    #[default]
+    PerObject,
+    /// Only blurs the motion caused by the rotation of the camera.
+    ///
+    /// Moving objects and the movement of the camera itself aren't blurred.
+    /// Meshes that write zero velocity into the motion vector prepass, such as
+    /// those with the `NoMotionBlur` component of `bevy_pbr`, stay sharp.
+    CameraRotation,
+}
+
+/// The on-GPU version of the [`MotionBlur`] settings.
+#[derive(ShaderType)]
+pub struct MotionBlurUniform {
+    /// Maps the clip space positions of this frame to those of the previous
+    /// frame, taking only the rotation of the camera into account.
+    previous_clip_from_clip: Mat4,
+    shutter_angle: f32,
+    samples: u32,
+    /// Padding data.
+    unused_1: u32,
+    /// Padding data.
+    unused_2: u32,
+}
+
+/// A resource, part of the render world, that stores the [`MotionBlurUniform`]
+/// of each view.
+#[derive(Resource, Default)]
+pub struct MotionBlurUniformBuffer(DynamicUniformBuffer<MotionBlurUniform>);
+
+/// A component, part of the render world, that stores the offset of the
+/// [`MotionBlurUniform`] of the camera it's attached to within the
+/// [`MotionBlurUniformBuffer`].
+#[derive(Component)]
+pub struct MotionBlurUniformOffset(pub u32);
+
 pub const MOTION_BLUR_SHADER_HANDLE: Handle<Shader> =
     weak_handle!("d9ca74af-fa0a-4f11-b0f2-19613b618b93");
 
@@ -120,10 +171,7 @@ impl Plugin for MotionBlurPlugin {
             "motion_blur.wgsl",
             Shader::from_wgsl
         );
-        app.add_plugins((
-            ExtractComponentPlugin::<MotionBlur>::default(),
-            UniformComponentPlugin::<MotionBlur>::default(),
-        ));
+        app.add_plugins(ExtractComponentPlugin::<MotionBlur>::default());
 
         let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
             return;
@@ -131,9 +179,14 @@ impl Plugin for MotionBlurPlugin {
 
         render_app
             .init_resource::<SpecializedRenderPipelines<pipeline::MotionBlurPipeline>>()
+            .init_resource::<MotionBlurUniformBuffer>()
             .add_systems(
                 Render,
-                pipeline::prepare_motion_blur_pipelines.in_set(RenderSet::Prepare),
+                (
+                    pipeline::prepare_motion_blur_pipelines,
+                    prepare_motion_blur_uniforms,
+                )
+                    .in_set(RenderSet::Prepare),
             );
 
         render_app
@@ -159,3 +212,120 @@ impl Plugin for MotionBlurPlugin {
         render_app.init_resource::<pipeline::MotionBlurPipeline>();
     }
 }
+
+/// The rotation and projection of a view, remembered from one frame to the
+/// next for [`MotionBlurMode::CameraRotation`].
+#[derive(Clone, Copy)]
+pub struct MotionBlurViewRotation {
+    rotation: Quat,
+    clip_from_view: Mat4,
+}
+
+impl MotionBlurViewRotation {
+    fn new(world_from_view: &GlobalTransform, clip_from_view: Mat4) -> Self {
+        Self {
+            rotation: world_from_view.rotation(),
+            clip_from_view,
+        }
+    }
+
+    /// Returns the matrix that maps the clip space positions of this view to
+    /// those of `previous`, the same view on the previous frame, taking only
+    /// the rotation into account.
+    ///
+    /// On the first frame of a view there's no previous view, so positions
+    /// don't move.
+    fn previous_clip_from_clip(&self, previous: Option<&Self>) -> Mat4 {
+        let previous = previous.unwrap_or(self);
+        previous.clip_from_view
+            * Mat4::from_quat(previous.rotation.inverse() * self.rotation)
+            * self.clip_from_view.inverse()
+    }
+}
+
+/// Uploads the [`MotionBlurUniform`] of every view to the GPU.
+///
+/// The rotation of each view is remembered from one frame to the next, for
+/// [`MotionBlurMode::CameraRotation`].
+pub fn prepare_motion_blur_uniforms(
+    mut commands: Commands,
+    mut motion_blur_uniform_buffer: ResMut<MotionBlurUniformBuffer>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    views: Query<(Entity, &ExtractedView, &MotionBlur)>,
+    mut previous_views: Local<EntityHashMap<MotionBlurViewRotation>>,
+) {
+    let buffer = &mut motion_blur_uniform_buffer.0;
+    buffer.clear();
+
+    let mut current_views = EntityHashMap::default();
+    for (entity, view, motion_blur) in &views {
+        let view_rotation = MotionBlurViewRotation::new(&view.world_from_view, view.clip_from_view);
+        current_views.insert(entity, view_rotation);
+
+        let offset = buffer.push(&MotionBlurUniform {
+            previous_clip_from_clip: view_rotation
+                .previous_clip_from_clip(previous_views.get(&entity)),
+            shutter_angle: motion_blur.shutter_angle,
+            samples: motion_blur.samples,
+            unused_1: 0,
+            unused_2: 0,
+        });
+        commands
+            .entity(entity)
+            .insert(MotionBlurUniformOffset(offset));
+    }
+    *previous_views = current_views;
+
+    buffer.write_buffer(&render_device, &render_queue);
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_math::{ops, Vec3, Vec4};
+    use bevy_transform::components::Transform;
+
+    use super::*;
+
+    fn view(transform: Transform) -> MotionBlurViewRotation {
+        MotionBlurViewRotation::new(
+            &GlobalTransform::from(transform),
+            Mat4::perspective_infinite_reverse_rh(core::f32::consts::FRAC_PI_2, 1.0, 0.1),
+        )
+    }
+
+    #[test]
+    fn first_frame_is_still() {
+        let current = view(Transform::from_rotation(Quat::from_rotation_y(0.5)));
+        assert!(current
+            .previous_clip_from_clip(None)
+            .abs_diff_eq(Mat4::IDENTITY, 1e-5));
+    }
+
+    #[test]
+    fn translation_is_ignored() {
+        let rotation = Quat::from_rotation_x(0.3);
+        let previous = view(Transform::from_xyz(1.0, 2.0, 3.0).with_rotation(rotation));
+        let current = view(Transform::from_xyz(-4.0, 0.0, 8.0).with_rotation(rotation));
+        assert!(current
+            .previous_clip_from_clip(Some(&previous))
+            .abs_diff_eq(Mat4::IDENTITY, 1e-5));
+    }
+
+    #[test]
+    fn yaw_moves_center_sideways() {
+        // The camera turns left, so what's now at the center of the view was
+        // to the left of it on the previous frame.
+        let yaw = 0.5;
+        let previous = view(Transform::default());
+        let current = view(Transform::from_rotation(Quat::from_rotation_y(yaw)));
+
+        let center = current.clip_from_view * Vec4::new(0.0, 0.0, -10.0, 1.0);
+        let previous_center = current.previous_clip_from_clip(Some(&previous)) * center;
+        let previous_center = previous_center.truncate() / previous_center.w;
+        assert!(
+            previous_center.abs_diff_eq(Vec3::new(-ops::tan(yaw), 0.0, previous_center.z), 1e-5),
+            "{previous_center}"
+        );
+    }
+}
@@ -14,16 +14,36 @@
 #endif
 @group(0) @binding(3) var texture_sampler: sampler;
 struct MotionBlur {
+    previous_clip_from_clip: mat4x4<f32>,
     shutter_angle: f32,
     samples: u32,
-#ifdef SIXTEEN_BYTE_ALIGNMENT
-    // WebGL2 structs must be 16 byte aligned.
-    _webgl2_padding: vec2<f32>
-#endif
+    unused_a: u32,
+    unused_b: u32,
 }
 @group(0) @binding(4) var<uniform> settings: MotionBlur;
 @group(0) @binding(5) var<uniform> globals: Globals;
 
+#ifdef CAMERA_ROTATION_ONLY
+// Replaces a motion vector from the prepass with the motion caused by the rotation of the camera
+// alone. Meshes that write zero velocity into the prepass are masked out and stay sharp, but the
+// background, which has no depth, is not.
+fn camera_rotation_motion_vector(
+    uv: vec2<f32>,
+    prepass_motion_vector: vec2<f32>,
+    fragment_depth: f32,
+) -> vec2<f32> {
+    if fragment_depth != 0.0 && all(prepass_motion_vector == vec2(0.0)) {
+        return vec2(0.0);
+    }
+    // A rotation moves every point along the same view ray in the same way, whatever its depth,
+    // so any depth works here.
+    let clip_position = vec4((uv * 2.0 - 1.0) * vec2(1.0, -1.0), 1.0, 1.0);
+    let previous_clip_position = settings.previous_clip_from_clip * clip_position;
+    let previous_uv = previous_clip_position.xy / previous_clip_position.w * vec2(0.5, -0.5) + 0.5;
+    return uv - previous_uv;
+}
+#endif
+
 @fragment
 fn fragment(
     #ifdef MULTISAMPLED
@@ -43,9 +63,9 @@ fn fragment(
     let shutter_angle = settings.shutter_angle;
 
 #ifdef MULTISAMPLED
-    let this_motion_vector = textureLoad(motion_vectors, frag_coords, i32(sample_index)).rg;
+    var this_motion_vector = textureLoad(motion_vectors, frag_coords, i32(sample_index)).rg;
 #else
-    let this_motion_vector = textureSample(motion_vectors, texture_sampler, in.uv).rg;
+    var this_motion_vector = textureSample(motion_vectors, texture_sampler, in.uv).rg;
 #endif
 
 #ifdef NO_DEPTH_TEXTURE_SUPPORT
@@ -59,6 +79,10 @@ fn fragment(
     let this_depth = textureSample(depth, texture_sampler, in.uv);
 #endif
 #endif
+
+#ifdef CAMERA_ROTATION_ONLY
+    this_motion_vector = camera_rotation_motion_vector(in.uv, this_motion_vector, this_depth);
+#endif
     
     // The exposure vector is the distance that this fragment moved while the camera shutter was
     // open. This is the motion vector (total distance traveled) multiplied by the shutter angle (a
@@ -93,9 +117,9 @@ fn fragment(
         let sample_color = textureSample(screen_texture, texture_sampler, sample_uv);
     #endif
     #ifdef MULTISAMPLED
-        let sample_motion = textureLoad(motion_vectors, sample_coords, i32(sample_index)).rg;
+        var sample_motion = textureLoad(motion_vectors, sample_coords, i32(sample_index)).rg;
     #else
-        let sample_motion = textureSample(motion_vectors, texture_sampler, sample_uv).rg;
+        var sample_motion = textureSample(motion_vectors, texture_sampler, sample_uv).rg;
     #endif
     #ifdef NO_DEPTH_TEXTURE_SUPPORT
         let sample_depth = 0.0;
@@ -105,6 +129,9 @@ fn fragment(
     #else
         let sample_depth = textureSample(depth, texture_sampler, sample_uv);
     #endif
+    #endif
+    #ifdef CAMERA_ROTATION_ONLY
+        sample_motion = camera_rotation_motion_vector(sample_uv, sample_motion, sample_depth);
     #endif
 
         var weight = 1.0;
@@ -1,6 +1,9 @@
-use bevy_ecs::{query::QueryItem, world::World};
+use bevy_ecs::{
+    query::{Has, QueryItem},
+    world::World,
+};
 use bevy_render::{
-    extract_component::ComponentUniforms,
+    camera::Multiview,
     globals::GlobalsBuffer,
     render_graph::{NodeRunError, RenderGraphContext, ViewNode},
     render_resource::{
@@ -10,12 +13,14 @@ use bevy_render::{
     renderer::RenderContext,
     view::{Msaa, ViewTarget},
 };
+use bevy_utils::once;
+use tracing::warn;
 
 use crate::prepass::ViewPrepassTextures;
 
 use super::{
     pipeline::{MotionBlurPipeline, MotionBlurPipelineId},
-    MotionBlur,
+    MotionBlur, MotionBlurUniformBuffer, MotionBlurUniformOffset,
 };
 
 #[derive(Default)]
@@ -27,27 +32,44 @@ impl ViewNode for MotionBlurNode {
         &'static MotionBlurPipelineId,
         &'static ViewPrepassTextures,
         &'static MotionBlur,
+        &'static MotionBlurUniformOffset,
         &'static Msaa,
+        Has<Multiview>,
     );
     fn run(
         &self,
         _graph: &mut RenderGraphContext,
         render_context: &mut RenderContext,
-        (view_target, pipeline_id, prepass_textures, motion_blur, msaa): QueryItem<Self::ViewQuery>,
+        (
+            view_target,
+            pipeline_id,
+            prepass_textures,
+            motion_blur,
+            uniform_offset,
+            msaa,
+            multiview,
+        ): QueryItem<Self::ViewQuery>,
         world: &World,
     ) -> Result<(), NodeRunError> {
+        if multiview {
+            once!(warn!(
+                "Motion blur isn't supported on `Multiview` cameras and is skipped"
+            ));
+            return Ok(());
+        }
+
         if motion_blur.samples == 0 || motion_blur.shutter_angle <= 0.0 {
             return Ok(()); // We can skip running motion blur in these cases.
         }
 
         let motion_blur_pipeline = world.resource::<MotionBlurPipeline>();
         let pipeline_cache = world.resource::<PipelineCache>();
-        let settings_uniforms = world.resource::<ComponentUniforms<MotionBlur>>();
+        let settings_uniforms = world.resource::<MotionBlurUniformBuffer>();
         let Some(pipeline) = pipeline_cache.get_render_pipeline(pipeline_id.0) else {
             return Ok(());
         };
 
-        let Some(settings_binding) = settings_uniforms.uniforms().binding() else {
+        let Some(settings_binding) = settings_uniforms.0.binding() else {
             return Ok(());
         };
         let (Some(prepass_motion_vectors_texture), Some(prepass_depth_texture)) =
@@ -93,7 +115,7 @@ impl ViewNode for MotionBlurNode {
         });
 
         render_pass.set_render_pipeline(pipeline);
-        render_pass.set_bind_group(0, &bind_group, &[]);
+        render_pass.set_bind_group(0, &bind_group, &[uniform_offset.0]);
         render_pass.draw(0..3, 0..1);
 
         Ok(())
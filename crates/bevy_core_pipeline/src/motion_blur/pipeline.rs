@@ -1,7 +1,6 @@
 use bevy_ecs::{
     component::Component,
     entity::Entity,
-    query::With,
     resource::Resource,
     system::{Commands, Query, Res, ResMut},
     world::FromWorld,
@@ -12,7 +11,7 @@ use bevy_render::{
     render_resource::{
         binding_types::{
             sampler, texture_2d, texture_2d_multisampled, texture_depth_2d,
-            texture_depth_2d_multisampled, uniform_buffer_sized,
+            texture_depth_2d_multisampled, uniform_buffer, uniform_buffer_sized,
         },
         BindGroupLayout, BindGroupLayoutEntries, CachedRenderPipelineId, ColorTargetState,
         ColorWrites, FragmentState, MultisampleState, PipelineCache, PrimitiveState,
@@ -26,7 +25,7 @@ use bevy_render::{
 
 use crate::fullscreen_vertex_shader::fullscreen_shader_vertex_state;
 
-use super::{MotionBlur, MOTION_BLUR_SHADER_HANDLE};
+use super::{MotionBlur, MotionBlurMode, MotionBlurUniform, MOTION_BLUR_SHADER_HANDLE};
 
 #[derive(Resource)]
 pub struct MotionBlurPipeline {
@@ -49,7 +48,7 @@ impl MotionBlurPipeline {
                 // Linear Sampler
                 sampler(SamplerBindingType::Filtering),
                 // Motion blur settings uniform input
-                uniform_buffer_sized(false, Some(MotionBlur::min_size())),
+                uniform_buffer::<MotionBlurUniform>(true),
                 // Globals uniform input
                 uniform_buffer_sized(false, Some(GlobalsUniform::min_size())),
             ),
@@ -67,7 +66,7 @@ impl MotionBlurPipeline {
                 // Linear Sampler
                 sampler(SamplerBindingType::Filtering),
                 // Motion blur settings uniform input
-                uniform_buffer_sized(false, Some(MotionBlur::min_size())),
+                uniform_buffer::<MotionBlurUniform>(true),
                 // Globals uniform input
                 uniform_buffer_sized(false, Some(GlobalsUniform::min_size())),
             ),
@@ -97,6 +96,7 @@ impl FromWorld for MotionBlurPipeline {
 pub struct MotionBlurPipelineKey {
     hdr: bool,
     samples: u32,
+    mode: MotionBlurMode,
 }
 
 impl SpecializedRenderPipeline for MotionBlurPipeline {
@@ -114,12 +114,13 @@ impl SpecializedRenderPipeline for MotionBlurPipeline {
             shader_defs.push(ShaderDefVal::from("MULTISAMPLED"));
         }
 
-        #[cfg(all(feature = "webgl", target_arch = "wasm32", not(feature = "webgpu")))]
-        {
-            shader_defs.push("NO_DEPTH_TEXTURE_SUPPORT".into());
-            shader_defs.push("SIXTEEN_BYTE_ALIGNMENT".into());
+        if key.mode == MotionBlurMode::CameraRotation {
+            shader_defs.push("CAMERA_ROTATION_ONLY".into());
         }
 
+        #[cfg(all(feature = "webgl", target_arch = "wasm32", not(feature = "webgpu")))]
+        shader_defs.push("NO_DEPTH_TEXTURE_SUPPORT".into());
+
         RenderPipelineDescriptor {
             label: Some("motion_blur_pipeline".into()),
             layout,
@@ -143,6 +144,7 @@ impl SpecializedRenderPipeline for MotionBlurPipeline {
             multisample: MultisampleState::default(),
             push_constant_ranges: vec![],
             zero_initialize_workgroup_memory: false,
+            multiview: None,
         }
     }
 }
@@ -155,15 +157,16 @@ pub(crate) fn prepare_motion_blur_pipelines(
     pipeline_cache: Res<PipelineCache>,
     mut pipelines: ResMut<SpecializedRenderPipelines<MotionBlurPipeline>>,
     pipeline: Res<MotionBlurPipeline>,
-    views: Query<(Entity, &ExtractedView, &Msaa), With<MotionBlur>>,
+    views: Query<(Entity, &ExtractedView, &Msaa, &MotionBlur)>,
 ) {
-    for (entity, view, msaa) in &views {
+    for (entity, view, msaa, motion_blur) in &views {
         let pipeline_id = pipelines.specialize(
             &pipeline_cache,
             &pipeline,
             MotionBlurPipelineKey {
                 hdr: view.hdr,
                 samples: msaa.samples(),
+                mode: motion_blur.mode,
             },
         );
 
@@ -7,7 +7,7 @@ use bevy_app::{App, Plugin};
 use bevy_color::LinearRgba;
 use bevy_ecs::{prelude::*, query::QueryItem};
 use bevy_render::{
-    camera::ExtractedCamera,
+    camera::{ExtractedCamera, Multiview},
     render_graph::{NodeRunError, RenderGraphApp, RenderGraphContext, ViewNode, ViewNodeRunner},
     render_resource::*,
     renderer::RenderContext,
@@ -55,13 +55,14 @@ impl ViewNode for MsaaWritebackNode {
         &'static ViewTarget,
         &'static MsaaWritebackBlitPipeline,
         &'static Msaa,
+        Has<Multiview>,
     );
 
     fn run<'w>(
         &self,
         _graph: &mut RenderGraphContext,
         render_context: &mut RenderContext<'w>,
-        (target, blit_pipeline_id, msaa): QueryItem<'w, Self::ViewQuery>,
+        (target, blit_pipeline_id, msaa, multiview): QueryItem<'w, Self::ViewQuery>,
         world: &'w World,
     ) -> Result<(), NodeRunError> {
         if *msaa == Msaa::Off {
@@ -100,7 +101,11 @@ impl ViewNode for MsaaWritebackNode {
 
         let bind_group = render_context.render_device().create_bind_group(
             None,
-            &blit_pipeline.texture_bind_group,
+            if multiview {
+                &blit_pipeline.multiview_texture_bind_group
+            } else {
+                &blit_pipeline.texture_bind_group
+            },
             &BindGroupEntries::sequential((post_process.source, &blit_pipeline.sampler)),
         );
 
@@ -124,9 +129,9 @@ fn prepare_msaa_writeback_pipelines(
     pipeline_cache: Res<PipelineCache>,
     mut pipelines: ResMut<SpecializedRenderPipelines<BlitPipeline>>,
     blit_pipeline: Res<BlitPipeline>,
-    view_targets: Query<(Entity, &ViewTarget, &ExtractedCamera, &Msaa)>,
+    view_targets: Query<(Entity, &ViewTarget, &ExtractedCamera, &Msaa, Has<Multiview>)>,
 ) {
-    for (entity, view_target, camera, msaa) in view_targets.iter() {
+    for (entity, view_target, camera, msaa, multiview) in view_targets.iter() {
         // only do writeback if writeback is enabled for the camera and this isn't the first camera in the target,
         // as there is nothing to write back for the first camera.
         if msaa.samples() > 1 && camera.msaa_writeback && camera.sorted_camera_index_for_target > 0
@@ -135,6 +140,7 @@ fn prepare_msaa_writeback_pipelines(
                 texture_format: view_target.main_texture_format(),
                 samples: msaa.samples(),
                 blend_state: None,
+                multiview,
             };
 
             let pipeline = pipelines.specialize(&pipeline_cache, &blit_pipeline, key);
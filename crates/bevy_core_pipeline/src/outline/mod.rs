@@ -0,0 +1,609 @@
+//! Screen-space outlines around meshes.
+//!
+//! Add the [`ScreenSpaceOutlines`] component to a 3D camera, and the [`Outline`] component to
+//! the meshes that should be outlined.
+
+use crate::{
+    core_3d::{
+        graph::{Core3d, Node3d},
+        DEPTH_TEXTURE_SAMPLING_SUPPORTED,
+    },
+    prepass::{DepthPrepass, NormalPrepass, OutlineIdPrepass},
+};
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::{load_internal_asset, weak_handle, Handle};
+use bevy_color::{Color, ColorToComponents as _, LinearRgba};
+use bevy_ecs::{
+    change_detection::{DetectChanges, DetectChangesMut},
+    component::{require, Component},
+    entity::Entity,
+    observer::Trigger,
+    query::{QueryItem, With},
+    reflect::ReflectComponent,
+    removal_detection::RemovedComponents,
+    resource::Resource,
+    schedule::IntoSystemConfigs,
+    system::{Commands, Query, Res, ResMut},
+    world::{FromWorld, OnRemove, World},
+};
+use bevy_math::{ops, Vec4};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    camera::Camera,
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    extract_resource::{ExtractResource, ExtractResourcePlugin},
+    mesh::Mesh3d,
+    render_graph::{RenderGraphApp, ViewNodeRunner},
+    render_resource::{
+        DynamicUniformBuffer, Shader, ShaderType, SpecializedRenderPipelines, TextureDescriptor,
+        TextureDimension, TextureFormat, TextureUsages,
+    },
+    renderer::{RenderDevice, RenderQueue},
+    texture::{CachedTexture, TextureCache},
+    view::{prepare_view_targets, ViewTarget},
+    Render, RenderApp, RenderSet,
+};
+use bevy_utils::{default, once};
+use tracing::{info, warn};
+
+pub mod node;
+pub mod pipeline;
+
+/// A component that draws an outline around a mesh in every camera with
+/// [`ScreenSpaceOutlines`].
+///
+/// Meshes with the same color and width share an outline style. At most
+/// [`OutlineStyles::MAX_STYLES`] different styles can be drawn at the same time.
+///
+/// Outlines are found in screen space, so they're drawn around the visible silhouette of the mesh
+/// and are hidden by anything in front of it. Transparent meshes and meshlets can't be outlined.
+/// Materials with custom prepass shaders need to copy the `outline_id` vertex output to the
+/// fragment output of the same name.
+#[derive(Component, Reflect, Clone, Copy, PartialEq, Debug)]
+#[reflect(Component, Default, PartialEq, Debug)]
+pub struct Outline {
+    /// The color of the outline.
+    ///
+    /// The alpha of the color controls how much of the scene shows through the outline.
+    pub color: Color,
+    /// The width of the outline, in physical pixels.
+    ///
+    /// A width of zero only draws the inner edges of the mesh, if
+    /// [`ScreenSpaceOutlines::crease_angle`] is set.
+    pub width: f32,
+}
+
+impl Default for Outline {
+    fn default() -> Self {
+        Self {
+            color: Color::WHITE,
+            width: 2.0,
+        }
+    }
+}
+
+/// A component that enables and configures screen-space outlines when added to a 3D camera.
+///
+/// Meshes are outlined according to their [`Outline`] component. The outline ID of each mesh is
+/// written to a prepass texture, and a jump flood pass finds the nearest outlined pixel of every
+/// pixel on screen, so wide outlines cost the same as thin ones.
+///
+/// With MSAA, the outline covers each sample separately, so its edges are antialiased along
+/// with the rest of the scene.
+///
+/// # Usage
+///
+/// ```
+/// # use bevy_core_pipeline::{core_3d::Camera3d, outline::ScreenSpaceOutlines};
+/// # use bevy_ecs::prelude::*;
+/// # fn test(mut commands: Commands) {
+/// commands.spawn((
+///     Camera3d::default(),
+///     ScreenSpaceOutlines::default(),
+/// ));
+/// # }
+/// ```
+///
+/// This isn't supported on WebGL 2, because depth textures can't be sampled there.
+#[derive(Component, Reflect, Clone, Copy, Default, Debug)]
+#[reflect(Component, Default, Debug)]
+#[require(DepthPrepass, NormalPrepass, OutlineIdPrepass)]
+pub struct ScreenSpaceOutlines {
+    /// If set, edges inside outlined meshes where the surface normal bends by more than this
+    /// angle, in radians, are drawn as well.
+    ///
+    /// The default is `None`, which only draws the outer silhouettes.
+    pub crease_angle: Option<f32>,
+}
+
+impl ExtractComponent for ScreenSpaceOutlines {
+    type QueryData = &'static Self;
+    type QueryFilter = With<Camera>;
+    type Out = Self;
+
+    fn extract_component(outlines: QueryItem<'_, Self::QueryData>) -> Option<Self::Out> {
+        if !DEPTH_TEXTURE_SAMPLING_SUPPORTED {
+            once!(info!(
+                "Disabling screen-space outlines on this platform because depth textures aren't supported correctly"
+            ));
+            return None;
+        }
+
+        Some(*outlines)
+    }
+}
+
+/// A resource that assigns each distinct [`Outline`] a slot in the outline ID prepass.
+///
+/// Slots are numbered from 1, as 0 means that a pixel isn't outlined. A style keeps its slot for
+/// as long as any mesh uses it.
+#[derive(Resource, Clone, Default, ExtractResource)]
+pub struct OutlineStyles {
+    styles: [Option<Outline>; OutlineStyles::MAX_STYLES],
+}
+
+impl OutlineStyles {
+    /// The maximum number of different outline styles that can be drawn at the same time.
+    pub const MAX_STYLES: usize = 15;
+
+    /// Returns the slot of the given style, if it has one.
+    pub fn slot(&self, outline: &Outline) -> Option<u8> {
+        self.styles
+            .iter()
+            .position(|style| style.as_ref() == Some(outline))
+            .map(|index| index as u8 + 1)
+    }
+
+    /// Returns the style in the given slot, if it's in use.
+    pub fn get(&self, slot: u8) -> Option<&Outline> {
+        self.styles.get((slot as usize).checked_sub(1)?)?.as_ref()
+    }
+
+    /// Iterates over the slots in use and their styles.
+    pub fn iter(&self) -> impl Iterator<Item = (u8, &Outline)> {
+        self.styles
+            .iter()
+            .enumerate()
+            .filter_map(|(index, style)| Some((index as u8 + 1, style.as_ref()?)))
+    }
+}
+
+/// The on-GPU version of an [`Outline`].
+#[derive(ShaderType, Clone, Copy, Default)]
+pub struct OutlineStyleUniform {
+    color: Vec4,
+    width: f32,
+    /// Padding data.
+    unused_1: u32,
+    /// Padding data.
+    unused_2: u32,
+    /// Padding data.
+    unused_3: u32,
+}
+
+/// The on-GPU version of the [`ScreenSpaceOutlines`] settings, along with every
+/// outline style, indexed by slot.
+#[derive(ShaderType)]
+pub struct OutlineUniform {
+    styles: [OutlineStyleUniform; OutlineStyles::MAX_STYLES + 1],
+    /// The cosine of [`ScreenSpaceOutlines::crease_angle`].
+    crease_threshold: f32,
+    /// Padding data.
+    unused_1: u32,
+    /// Padding data.
+    unused_2: u32,
+    /// Padding data.
+    unused_3: u32,
+}
+
+/// A resource, part of the render world, that stores the [`OutlineUniform`] of
+/// each view.
+#[derive(Resource, Default)]
+pub struct OutlineUniformBuffer(DynamicUniformBuffer<OutlineUniform>);
+
+/// A component, part of the render world, that stores the offset of the
+/// [`OutlineUniform`] of the camera it's attached to within the
+/// [`OutlineUniformBuffer`].
+///
+/// It also stores the number of jump flood passes the view needs to reach the
+/// edge of its widest outline.
+#[derive(Component)]
+pub struct OutlineUniformOffset {
+    pub offset: u32,
+    pub jump_flood_passes: u32,
+}
+
+/// The step size of a single jump flood pass.
+#[derive(ShaderType)]
+pub struct OutlineJumpFloodUniform {
+    step: u32,
+    /// Padding data.
+    unused_1: u32,
+    /// Padding data.
+    unused_2: u32,
+    /// Padding data.
+    unused_3: u32,
+}
+
+/// A resource, part of the render world, that stores the step size of every
+/// jump flood pass.
+///
+/// The step of pass `i` is `2^i` pixels and lives at `offsets[i]`.
+#[derive(Resource)]
+pub struct OutlineJumpFloodSteps {
+    buffer: DynamicUniformBuffer<OutlineJumpFloodUniform>,
+    offsets: Vec<u32>,
+}
+
+impl OutlineJumpFloodSteps {
+    /// The largest number of jump flood passes, which reach 2^16 - 1 pixels.
+    pub const MAX_PASSES: u32 = 16;
+}
+
+impl FromWorld for OutlineJumpFloodSteps {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let render_queue = world.resource::<RenderQueue>();
+
+        let mut buffer = DynamicUniformBuffer::default();
+        let offsets = (0..Self::MAX_PASSES)
+            .map(|pass| {
+                buffer.push(&OutlineJumpFloodUniform {
+                    step: 1 << pass,
+                    unused_1: 0,
+                    unused_2: 0,
+                    unused_3: 0,
+                })
+            })
+            .collect();
+        buffer.write_buffer(render_device, render_queue);
+
+        Self { buffer, offsets }
+    }
+}
+
+/// The textures that the jump flood passes of a view ping-pong between.
+///
+/// Each texel stores the coordinates, outline ID and depth of the nearest
+/// outlined pixel found so far.
+#[derive(Component)]
+pub struct OutlineJumpFloodTextures([CachedTexture; 2]);
+
+/// The format of the [`OutlineJumpFloodTextures`].
+pub const OUTLINE_JUMP_FLOOD_FORMAT: TextureFormat = TextureFormat::Rgba32Uint;
+
+pub const OUTLINE_SHADER_HANDLE: Handle<Shader> =
+    weak_handle!("1d9a4d48-edd3-4169-8867-e8f6465c3829");
+
+/// Adds support for screen-space outlines to the app. See [`ScreenSpaceOutlines`] for details.
+pub struct OutlinePlugin;
+
+impl Plugin for OutlinePlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            OUTLINE_SHADER_HANDLE,
+            "outline.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<Outline>()
+            .register_type::<ScreenSpaceOutlines>()
+            .init_resource::<OutlineStyles>()
+            .add_plugins((
+                ExtractComponentPlugin::<ScreenSpaceOutlines>::default(),
+                ExtractResourcePlugin::<OutlineStyles>::default(),
+            ))
+            .add_systems(PostUpdate, update_outline_styles)
+            .add_observer(mark_unoutlined_meshes_changed);
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .init_resource::<SpecializedRenderPipelines<pipeline::OutlinePipeline>>()
+            .init_resource::<OutlineUniformBuffer>()
+            .add_systems(
+                Render,
+                prepare_outline_textures
+                    .after(prepare_view_targets)
+                    .in_set(RenderSet::ManageViews),
+            )
+            .add_systems(
+                Render,
+                (
+                    pipeline::prepare_outline_pipelines,
+                    prepare_outline_uniforms,
+                )
+                    .in_set(RenderSet::Prepare),
+            );
+
+        render_app
+            .add_render_graph_node::<ViewNodeRunner<node::OutlineNode>>(Core3d, Node3d::Outline)
+            .add_render_graph_edges(Core3d, (Node3d::Tonemapping, Node3d::Outline, Node3d::Fxaa))
+            .add_render_graph_edge(Core3d, Node3d::Outline, Node3d::Smaa);
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .init_resource::<pipeline::OutlinePipeline>()
+            .init_resource::<OutlineJumpFloodSteps>();
+    }
+}
+
+/// Assigns a slot in [`OutlineStyles`] to every [`Outline`] style in use, and
+/// frees the slots of styles that aren't used anymore.
+///
+/// Meshes whose style only got a slot now, because all slots were taken
+/// before, are marked as changed so that they're extracted again.
+pub fn update_outline_styles(
+    mut outline_styles: ResMut<OutlineStyles>,
+    mut outlines: Query<&mut Outline>,
+    mut removed_outlines: RemovedComponents<Outline>,
+) {
+    let any_removed = removed_outlines.read().count() > 0;
+    if !any_removed && !outlines.iter_mut().any(|outline| outline.is_changed()) {
+        return;
+    }
+
+    let mut used = [false; OutlineStyles::MAX_STYLES];
+    let mut unassigned = vec![];
+    for outline in &outlines {
+        match outline_styles.slot(outline) {
+            Some(slot) => used[slot as usize - 1] = true,
+            None if !unassigned.contains(outline) => unassigned.push(*outline),
+            None => {}
+        }
+    }
+
+    for (style, used) in outline_styles.styles.iter_mut().zip(used) {
+        if !used {
+            *style = None;
+        }
+    }
+
+    let mut assigned = vec![];
+    for outline in unassigned {
+        let Some(free_style) = outline_styles
+            .styles
+            .iter_mut()
+            .find(|style| style.is_none())
+        else {
+            once!(warn!(
+                "More than {} different outline styles are in use; some meshes won't be outlined",
+                OutlineStyles::MAX_STYLES
+            ));
+            break;
+        };
+        *free_style = Some(outline);
+        assigned.push(outline);
+    }
+
+    if assigned.is_empty() {
+        return;
+    }
+    for mut outline in &mut outlines {
+        if assigned.contains(&*outline) {
+            outline.set_changed();
+        }
+    }
+}
+
+/// Marks meshes that lose their [`Outline`] as changed, so that their outline
+/// ID is cleared when they're extracted again.
+fn mark_unoutlined_meshes_changed(
+    trigger: Trigger<OnRemove, Outline>,
+    mut meshes: Query<&mut Mesh3d>,
+) {
+    if let Ok(mut mesh) = meshes.get_mut(trigger.target()) {
+        mesh.set_changed();
+    }
+}
+
+/// Creates the jump flood textures of each view with [`ScreenSpaceOutlines`].
+pub fn prepare_outline_textures(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    mut texture_cache: ResMut<TextureCache>,
+    views: Query<(Entity, &ViewTarget), With<ScreenSpaceOutlines>>,
+) {
+    for (entity, view_target) in &views {
+        let mut texture_descriptor = TextureDescriptor {
+            label: Some("outline_jump_flood_texture_a"),
+            size: view_target.main_texture().size(),
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: OUTLINE_JUMP_FLOOD_FORMAT,
+            usage: TextureUsages::RENDER_ATTACHMENT | TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        };
+        let texture_a = texture_cache.get(&render_device, texture_descriptor.clone());
+        texture_descriptor.label = Some("outline_jump_flood_texture_b");
+        let texture_b = texture_cache.get(&render_device, texture_descriptor);
+
+        commands
+            .entity(entity)
+            .insert(OutlineJumpFloodTextures([texture_a, texture_b]));
+    }
+}
+
+/// Returns the number of jump flood passes needed to draw outlines up to
+/// `max_width` pixels wide.
+fn jump_flood_passes(max_width: f32) -> u32 {
+    // The jump flood has to reach every pixel that the widest outline touches.
+    // Passes with steps of 2^(n - 1), ..., 2, 1 pixels reach 2^n - 1 pixels.
+    let reach = ((max_width + 1.0).ceil() as u32).min(1 << OutlineJumpFloodSteps::MAX_PASSES);
+    (reach + 1)
+        .next_power_of_two()
+        .trailing_zeros()
+        .min(OutlineJumpFloodSteps::MAX_PASSES)
+}
+
+/// Uploads the [`OutlineUniform`] of every view to the GPU.
+pub fn prepare_outline_uniforms(
+    mut commands: Commands,
+    mut outline_uniform_buffer: ResMut<OutlineUniformBuffer>,
+    outline_styles: Res<OutlineStyles>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    views: Query<(Entity, &ScreenSpaceOutlines)>,
+) {
+    let buffer = &mut outline_uniform_buffer.0;
+    buffer.clear();
+
+    let mut styles = [OutlineStyleUniform::default(); OutlineStyles::MAX_STYLES + 1];
+    let mut max_width = 0.0f32;
+    for (slot, outline) in outline_styles.iter() {
+        styles[slot as usize] = OutlineStyleUniform {
+            color: LinearRgba::from(outline.color).to_vec4(),
+            width: outline.width.max(0.0),
+            ..default()
+        };
+        max_width = max_width.max(outline.width);
+    }
+
+    let jump_flood_passes = jump_flood_passes(max_width);
+
+    for (entity, screen_space_outlines) in &views {
+        let offset = buffer.push(&OutlineUniform {
+            styles,
+            crease_threshold: screen_space_outlines.crease_angle.map_or(-1.0, ops::cos),
+            unused_1: 0,
+            unused_2: 0,
+            unused_3: 0,
+        });
+        commands.entity(entity).insert(OutlineUniformOffset {
+            offset,
+            jump_flood_passes,
+        });
+    }
+
+    buffer.write_buffer(&render_device, &render_queue);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{jump_flood_passes, update_outline_styles, Outline, OutlineStyles};
+    use bevy_color::Color;
+    use bevy_ecs::{entity::Entity, schedule::Schedule, world::World};
+
+    fn outline(width: f32) -> Outline {
+        Outline {
+            color: Color::WHITE,
+            width,
+        }
+    }
+
+    fn setup() -> (World, Schedule) {
+        let mut world = World::new();
+        world.init_resource::<OutlineStyles>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(update_outline_styles);
+        (world, schedule)
+    }
+
+    fn slot(world: &World, outline: &Outline) -> Option<u8> {
+        world.resource::<OutlineStyles>().slot(outline)
+    }
+
+    #[test]
+    fn styles_get_one_slot_each() {
+        let (mut world, mut schedule) = setup();
+        world.spawn(outline(1.0));
+        world.spawn(outline(2.0));
+        world.spawn(outline(1.0));
+        schedule.run(&mut world);
+
+        let styles = world.resource::<OutlineStyles>();
+        assert_eq!(styles.iter().count(), 2);
+        let first = styles.slot(&outline(1.0)).unwrap();
+        let second = styles.slot(&outline(2.0)).unwrap();
+        assert_ne!(first, second);
+        assert!(first >= 1 && second >= 1);
+        assert_eq!(styles.get(first), Some(&outline(1.0)));
+        assert_eq!(styles.get(0), None);
+    }
+
+    #[test]
+    fn unused_styles_free_their_slot() {
+        let (mut world, mut schedule) = setup();
+        let first = world.spawn(outline(1.0)).id();
+        let second = world.spawn(outline(1.0)).id();
+        world.spawn(outline(2.0));
+        schedule.run(&mut world);
+        let slot_1 = slot(&world, &outline(1.0)).unwrap();
+
+        // The style keeps its slot while any mesh uses it.
+        world.entity_mut(first).remove::<Outline>();
+        schedule.run(&mut world);
+        assert_eq!(slot(&world, &outline(1.0)), Some(slot_1));
+
+        world.despawn(second);
+        schedule.run(&mut world);
+        assert_eq!(slot(&world, &outline(1.0)), None);
+        assert_eq!(world.resource::<OutlineStyles>().get(slot_1), None);
+
+        // The freed slot is reused by the next new style.
+        world.spawn(outline(3.0));
+        schedule.run(&mut world);
+        assert_eq!(slot(&world, &outline(3.0)), Some(slot_1));
+    }
+
+    #[test]
+    fn styles_past_the_maximum_wait_for_a_free_slot() {
+        let (mut world, mut schedule) = setup();
+        let entities: Vec<Entity> = (0..=OutlineStyles::MAX_STYLES)
+            .map(|width| world.spawn(outline(width as f32)).id())
+            .collect();
+        schedule.run(&mut world);
+
+        let unassigned: Vec<_> = (0..=OutlineStyles::MAX_STYLES)
+            .filter(|&width| slot(&world, &outline(width as f32)).is_none())
+            .collect();
+        assert_eq!(
+            world.resource::<OutlineStyles>().iter().count(),
+            OutlineStyles::MAX_STYLES
+        );
+        assert_eq!(unassigned.len(), 1);
+
+        // Once another style is freed, the waiting one gets its slot.
+        let waiting = outline(unassigned[0] as f32);
+        let freed = entities
+            .into_iter()
+            .find(|&entity| world.get::<Outline>(entity) != Some(&waiting))
+            .unwrap();
+        world.despawn(freed);
+        schedule.run(&mut world);
+        assert!(slot(&world, &waiting).is_some());
+        assert_eq!(
+            world.resource::<OutlineStyles>().iter().count(),
+            OutlineStyles::MAX_STYLES
+        );
+    }
+
+    #[test]
+    fn jump_flood_reaches_widest_outline() {
+        assert_eq!(jump_flood_passes(0.0), 1);
+        assert_eq!(jump_flood_passes(1.0), 2);
+        assert_eq!(jump_flood_passes(2.0), 2);
+        assert_eq!(jump_flood_passes(2.5), 3);
+        assert_eq!(jump_flood_passes(6.0), 3);
+        assert_eq!(jump_flood_passes(100.0), 7);
+        for width in 0..1000 {
+            let passes = jump_flood_passes(width as f32);
+            let reach = (1u32 << passes) - 1;
+            assert!(reach > width, "{passes} passes don't reach {width} pixels");
+            assert!(reach / 2 <= width, "{passes} passes for {width} pixels");
+        }
+        assert_eq!(
+            jump_flood_passes(f32::MAX),
+            super::OutlineJumpFloodSteps::MAX_PASSES
+        );
+    }
+}
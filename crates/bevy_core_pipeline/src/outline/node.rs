@@ -0,0 +1,187 @@
+use bevy_ecs::{
+    query::{Has, QueryItem},
+    world::World,
+};
+use bevy_render::{
+    camera::Multiview,
+    render_graph::{NodeRunError, RenderGraphContext, ViewNode},
+    render_resource::{
+        BindGroup, BindGroupEntries, Operations, PipelineCache, RenderPassColorAttachment,
+        RenderPassDescriptor, RenderPipeline, TextureView,
+    },
+    renderer::RenderContext,
+    view::{Msaa, ViewTarget},
+};
+use bevy_utils::once;
+use tracing::warn;
+
+use crate::prepass::ViewPrepassTextures;
+
+use super::{
+    pipeline::{OutlinePipeline, OutlinePipelineIds},
+    OutlineJumpFloodSteps, OutlineJumpFloodTextures, OutlineStyles, OutlineUniformBuffer,
+    OutlineUniformOffset, ScreenSpaceOutlines,
+};
+
+#[derive(Default)]
+pub struct OutlineNode;
+
+impl ViewNode for OutlineNode {
+    type ViewQuery = (
+        &'static ViewTarget,
+        &'static OutlinePipelineIds,
+        &'static ViewPrepassTextures,
+        &'static OutlineJumpFloodTextures,
+        &'static OutlineUniformOffset,
+        &'static Msaa,
+        Has<ScreenSpaceOutlines>,
+        Has<Multiview>,
+    );
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (
+            view_target,
+            pipeline_ids,
+            prepass_textures,
+            jump_flood_textures,
+            uniform_offset,
+            msaa,
+            screen_space_outlines,
+            multiview,
+        ): QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        if multiview {
+            once!(warn!(
+                "Screen space outlining isn't supported on `Multiview` cameras and is skipped"
+            ));
+            return Ok(());
+        }
+
+        if !screen_space_outlines || world.resource::<OutlineStyles>().iter().next().is_none() {
+            return Ok(()); // Nothing is outlined.
+        }
+
+        let outline_pipeline = world.resource::<OutlinePipeline>();
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let (Some(seed_pipeline), Some(jump_flood_pipeline), Some(composite_pipeline)) = (
+            pipeline_cache.get_render_pipeline(pipeline_ids.seed),
+            pipeline_cache.get_render_pipeline(pipeline_ids.jump_flood),
+            pipeline_cache.get_render_pipeline(pipeline_ids.composite),
+        ) else {
+            return Ok(());
+        };
+
+        let jump_flood_steps = world.resource::<OutlineJumpFloodSteps>();
+        let (Some(settings_binding), Some(steps_binding)) = (
+            world.resource::<OutlineUniformBuffer>().0.binding(),
+            jump_flood_steps.buffer.binding(),
+        ) else {
+            return Ok(());
+        };
+        let (Some(outline_id_texture), Some(depth_texture), Some(normal_texture)) = (
+            prepass_textures.outline_id_view(),
+            prepass_textures.depth_view(),
+            prepass_textures.normal_view(),
+        ) else {
+            return Ok(());
+        };
+
+        let multisampled = msaa.samples() > 1;
+        let [texture_a, texture_b] = &jump_flood_textures.0;
+        let (view_a, view_b) = (&texture_a.default_view, &texture_b.default_view);
+
+        // Seed the jump flood with the outlined pixels.
+        let seed_bind_group = render_context.render_device().create_bind_group(
+            Some("outline_seed_bind_group"),
+            if multisampled {
+                &outline_pipeline.seed_layout_msaa
+            } else {
+                &outline_pipeline.seed_layout
+            },
+            &BindGroupEntries::sequential((outline_id_texture, depth_texture)),
+        );
+        run_fullscreen_pass(
+            render_context,
+            "outline_seed_pass",
+            seed_pipeline,
+            &seed_bind_group,
+            &[],
+            view_a,
+        );
+
+        // Halve the step size every pass, ending with a step of a single pixel.
+        let (mut source, mut destination) = (view_a, view_b);
+        for pass in (0..uniform_offset.jump_flood_passes).rev() {
+            let jump_flood_bind_group = render_context.render_device().create_bind_group(
+                Some("outline_jump_flood_bind_group"),
+                &outline_pipeline.jump_flood_layout,
+                &BindGroupEntries::sequential((source, steps_binding.clone())),
+            );
+            run_fullscreen_pass(
+                render_context,
+                "outline_jump_flood_pass",
+                jump_flood_pipeline,
+                &jump_flood_bind_group,
+                &[jump_flood_steps.offsets[pass as usize]],
+                destination,
+            );
+            (source, destination) = (destination, source);
+        }
+
+        let post_process = view_target.post_process_write();
+        let composite_bind_group = render_context.render_device().create_bind_group(
+            Some("outline_composite_bind_group"),
+            if multisampled {
+                &outline_pipeline.composite_layout_msaa
+            } else {
+                &outline_pipeline.composite_layout
+            },
+            &BindGroupEntries::sequential((
+                outline_id_texture,
+                depth_texture,
+                normal_texture,
+                post_process.source,
+                source,
+                settings_binding.clone(),
+            )),
+        );
+        run_fullscreen_pass(
+            render_context,
+            "outline_composite_pass",
+            composite_pipeline,
+            &composite_bind_group,
+            &[uniform_offset.offset],
+            post_process.destination,
+        );
+
+        Ok(())
+    }
+}
+
+fn run_fullscreen_pass(
+    render_context: &mut RenderContext,
+    label: &'static str,
+    pipeline: &RenderPipeline,
+    bind_group: &BindGroup,
+    dynamic_offsets: &[u32],
+    destination: &TextureView,
+) {
+    let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+        label: Some(label),
+        color_attachments: &[Some(RenderPassColorAttachment {
+            view: destination,
+            resolve_target: None,
+            ops: Operations::default(),
+        })],
+        depth_stencil_attachment: None,
+        timestamp_writes: None,
+        occlusion_query_set: None,
+    });
+
+    render_pass.set_render_pipeline(pipeline);
+    render_pass.set_bind_group(0, bind_group, dynamic_offsets);
+    render_pass.draw(0..3, 0..1);
+}
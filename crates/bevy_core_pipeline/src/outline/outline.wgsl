@@ -0,0 +1,212 @@
+// Screen-space outlines.
+//
+// The seed pass marks every pixel covered by an outlined mesh with its coordinates, outline ID and
+// depth. The jump flood passes then spread the nearest seed to every pixel on screen, halving the
+// step size each time, and the composite pass draws the outline wherever a sample lies within the
+// outline width of a seed with a different outline ID, and behind it.
+
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+struct OutlineStyle {
+    color: vec4<f32>,
+    width: f32,
+    unused_a: u32,
+    unused_b: u32,
+    unused_c: u32,
+}
+
+struct Outlines {
+    // Indexed by outline ID. The style at index 0 is unused.
+    styles: array<OutlineStyle, 16>,
+    crease_threshold: f32,
+    unused_a: u32,
+    unused_b: u32,
+    unused_c: u32,
+}
+
+struct JumpFlood {
+    step: u32,
+    unused_a: u32,
+    unused_b: u32,
+    unused_c: u32,
+}
+
+#ifdef JUMP_FLOOD_PASS
+@group(0) @binding(0) var seed_texture: texture_2d<u32>;
+@group(0) @binding(1) var<uniform> jump_flood_settings: JumpFlood;
+#else   // JUMP_FLOOD_PASS
+#ifdef MULTISAMPLED
+@group(0) @binding(0) var outline_id_texture: texture_multisampled_2d<u32>;
+@group(0) @binding(1) var depth_texture: texture_depth_multisampled_2d;
+#else   // MULTISAMPLED
+@group(0) @binding(0) var outline_id_texture: texture_2d<u32>;
+@group(0) @binding(1) var depth_texture: texture_depth_2d;
+#endif  // MULTISAMPLED
+#endif  // JUMP_FLOOD_PASS
+
+#ifdef COMPOSITE_PASS
+#ifdef MULTISAMPLED
+@group(0) @binding(2) var normal_texture: texture_multisampled_2d<f32>;
+#else   // MULTISAMPLED
+@group(0) @binding(2) var normal_texture: texture_2d<f32>;
+#endif  // MULTISAMPLED
+@group(0) @binding(3) var screen_texture: texture_2d<f32>;
+@group(0) @binding(4) var seed_texture: texture_2d<u32>;
+@group(0) @binding(5) var<uniform> outlines: Outlines;
+#endif  // COMPOSITE_PASS
+
+#ifndef JUMP_FLOOD_PASS
+
+fn sample_count() -> i32 {
+#ifdef MULTISAMPLED
+    return i32(textureNumSamples(depth_texture));
+#else
+    return 1;
+#endif
+}
+
+fn load_outline_id(coords: vec2<i32>, sample_index: i32) -> u32 {
+#ifdef MULTISAMPLED
+    return textureLoad(outline_id_texture, coords, sample_index).r;
+#else
+    return textureLoad(outline_id_texture, coords, 0).r;
+#endif
+}
+
+fn load_depth(coords: vec2<i32>, sample_index: i32) -> f32 {
+#ifdef MULTISAMPLED
+    return textureLoad(depth_texture, coords, sample_index);
+#else
+    return textureLoad(depth_texture, coords, 0);
+#endif
+}
+
+#endif  // JUMP_FLOOD_PASS
+
+#ifdef SEED_PASS
+
+// A seed is the coordinates of an outlined pixel, its outline ID and the bits of its depth. An
+// outline ID of 0 means that no seed has been found.
+@fragment
+fn seed(in: FullscreenVertexOutput) -> @location(0) vec4<u32> {
+    let coords = vec2<i32>(in.position.xy);
+
+    // With MSAA, the nearest outlined sample of the pixel becomes the seed.
+    var seed = vec4(0u);
+    var seed_depth = -1.0;
+    for (var sample_index = 0; sample_index < sample_count(); sample_index += 1) {
+        let outline_id = load_outline_id(coords, sample_index);
+        let depth = load_depth(coords, sample_index);
+        if outline_id != 0u && depth > seed_depth {
+            seed = vec4(vec2<u32>(coords), outline_id, bitcast<u32>(depth));
+            seed_depth = depth;
+        }
+    }
+    return seed;
+}
+
+#endif  // SEED_PASS
+
+#ifdef JUMP_FLOOD_PASS
+
+@fragment
+fn jump_flood(in: FullscreenVertexOutput) -> @location(0) vec4<u32> {
+    let coords = vec2<i32>(in.position.xy);
+    let size = vec2<i32>(textureDimensions(seed_texture));
+    let step = i32(jump_flood_settings.step);
+
+    var nearest_seed = vec4(0u);
+    var nearest_distance_squared = 3.40282347e38;
+    for (var y = -1; y <= 1; y += 1) {
+        for (var x = -1; x <= 1; x += 1) {
+            let neighbor_coords = coords + vec2(x, y) * step;
+            if any(neighbor_coords < vec2(0)) || any(neighbor_coords >= size) {
+                continue;
+            }
+
+            let seed = textureLoad(seed_texture, neighbor_coords, 0);
+            if seed.z == 0u {
+                continue;
+            }
+
+            let offset = vec2<f32>(vec2<i32>(seed.xy) - coords);
+            let distance_squared = dot(offset, offset);
+            if distance_squared < nearest_distance_squared {
+                nearest_seed = seed;
+                nearest_distance_squared = distance_squared;
+            }
+        }
+    }
+    return nearest_seed;
+}
+
+#endif  // JUMP_FLOOD_PASS
+
+#ifdef COMPOSITE_PASS
+
+fn load_normal(coords: vec2<i32>, sample_index: i32) -> vec3<f32> {
+#ifdef MULTISAMPLED
+    let normal = textureLoad(normal_texture, coords, sample_index).xyz;
+#else
+    let normal = textureLoad(normal_texture, coords, 0).xyz;
+#endif
+    return normalize(normal * 2.0 - 1.0);
+}
+
+#ifdef CREASES
+// Returns true if the surface bends sharply between this sample and the same sample of the pixel
+// to the right or below, within the same outlined mesh.
+fn is_crease(coords: vec2<i32>, sample_index: i32, outline_id: u32) -> bool {
+    let size = vec2<i32>(textureDimensions(screen_texture));
+    let normal = load_normal(coords, sample_index);
+    for (var i = 0; i < 2; i += 1) {
+        let neighbor_coords = coords + select(vec2(0, 1), vec2(1, 0), i == 0);
+        if any(neighbor_coords >= size) ||
+                load_outline_id(neighbor_coords, sample_index) != outline_id {
+            continue;
+        }
+        if dot(normal, load_normal(neighbor_coords, sample_index)) < outlines.crease_threshold {
+            return true;
+        }
+    }
+    return false;
+}
+#endif  // CREASES
+
+@fragment
+fn composite(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let coords = vec2<i32>(in.position.xy);
+    let color = textureLoad(screen_texture, coords, 0);
+    let seed = textureLoad(seed_texture, coords, 0);
+
+    // Fade the outer edge of the outline out over a pixel to antialias it.
+    let style = outlines.styles[seed.z];
+    let seed_depth = bitcast<f32>(seed.w);
+    let seed_distance = length(vec2<f32>(vec2<i32>(seed.xy) - coords));
+    let coverage = saturate(style.width + 0.5 - seed_distance);
+
+    // Each sample is outlined separately, so MSAA antialiases the inner edge of the outline.
+    var outline = vec4(0.0);
+    for (var sample_index = 0; sample_index < sample_count(); sample_index += 1) {
+        let outline_id = load_outline_id(coords, sample_index);
+
+        var sample_outline = vec4(0.0);
+        // Reversed-Z: samples behind the seed have a smaller depth.
+        if seed.z != 0u && outline_id != seed.z &&
+                load_depth(coords, sample_index) <= seed_depth {
+            sample_outline = vec4(style.color.rgb, style.color.a * coverage);
+        }
+#ifdef CREASES
+        else if outline_id != 0u && is_crease(coords, sample_index, outline_id) {
+            sample_outline = outlines.styles[outline_id].color;
+        }
+#endif  // CREASES
+
+        outline += vec4(sample_outline.rgb * sample_outline.a, sample_outline.a);
+    }
+    outline /= f32(sample_count());
+
+    return vec4(color.rgb * (1.0 - outline.a) + outline.rgb, color.a);
+}
+
+#endif  // COMPOSITE_PASS
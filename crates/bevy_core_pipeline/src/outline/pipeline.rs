@@ -0,0 +1,259 @@
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    resource::Resource,
+    system::{Commands, Query, Res, ResMut},
+    world::FromWorld,
+};
+use bevy_image::BevyDefault as _;
+use bevy_render::{
+    render_resource::{
+        binding_types::{
+            texture_2d, texture_2d_multisampled, texture_depth_2d, texture_depth_2d_multisampled,
+            uniform_buffer,
+        },
+        BindGroupLayout, BindGroupLayoutEntries, CachedRenderPipelineId, ColorTargetState,
+        ColorWrites, FragmentState, MultisampleState, PipelineCache, PrimitiveState,
+        RenderPipelineDescriptor, ShaderDefVal, ShaderStages, SpecializedRenderPipeline,
+        SpecializedRenderPipelines, TextureFormat, TextureSampleType,
+    },
+    renderer::RenderDevice,
+    view::{ExtractedView, Msaa, ViewTarget},
+};
+
+use crate::fullscreen_vertex_shader::fullscreen_shader_vertex_state;
+
+use super::{
+    OutlineJumpFloodUniform, OutlineUniform, ScreenSpaceOutlines, OUTLINE_JUMP_FLOOD_FORMAT,
+    OUTLINE_SHADER_HANDLE,
+};
+
+#[derive(Resource)]
+pub struct OutlinePipeline {
+    pub(crate) seed_layout: BindGroupLayout,
+    pub(crate) seed_layout_msaa: BindGroupLayout,
+    pub(crate) jump_flood_layout: BindGroupLayout,
+    pub(crate) composite_layout: BindGroupLayout,
+    pub(crate) composite_layout_msaa: BindGroupLayout,
+}
+
+impl OutlinePipeline {
+    pub(crate) fn new(render_device: &RenderDevice) -> Self {
+        let seed_layout = &BindGroupLayoutEntries::sequential(
+            ShaderStages::FRAGMENT,
+            (
+                // Outline IDs
+                texture_2d(TextureSampleType::Uint),
+                // Depth
+                texture_depth_2d(),
+            ),
+        );
+
+        let seed_layout_msaa = &BindGroupLayoutEntries::sequential(
+            ShaderStages::FRAGMENT,
+            (
+                // Outline IDs
+                texture_2d_multisampled(TextureSampleType::Uint),
+                // Depth
+                texture_depth_2d_multisampled(),
+            ),
+        );
+
+        let jump_flood_layout = &BindGroupLayoutEntries::sequential(
+            ShaderStages::FRAGMENT,
+            (
+                // Seeds of the previous pass
+                texture_2d(TextureSampleType::Uint),
+                // Step size uniform input
+                uniform_buffer::<OutlineJumpFloodUniform>(true),
+            ),
+        );
+
+        let composite_layout = &BindGroupLayoutEntries::sequential(
+            ShaderStages::FRAGMENT,
+            (
+                // Outline IDs
+                texture_2d(TextureSampleType::Uint),
+                // Depth
+                texture_depth_2d(),
+                // Normals
+                texture_2d(TextureSampleType::Float { filterable: false }),
+                // View target (read)
+                texture_2d(TextureSampleType::Float { filterable: false }),
+                // Seeds of the last jump flood pass
+                texture_2d(TextureSampleType::Uint),
+                // Outline settings uniform input
+                uniform_buffer::<OutlineUniform>(true),
+            ),
+        );
+
+        let composite_layout_msaa = &BindGroupLayoutEntries::sequential(
+            ShaderStages::FRAGMENT,
+            (
+                // Outline IDs
+                texture_2d_multisampled(TextureSampleType::Uint),
+                // Depth
+                texture_depth_2d_multisampled(),
+                // Normals
+                texture_2d_multisampled(TextureSampleType::Float { filterable: false }),
+                // View target (read)
+                texture_2d(TextureSampleType::Float { filterable: false }),
+                // Seeds of the last jump flood pass
+                texture_2d(TextureSampleType::Uint),
+                // Outline settings uniform input
+                uniform_buffer::<OutlineUniform>(true),
+            ),
+        );
+
+        Self {
+            seed_layout: render_device.create_bind_group_layout("outline_seed_layout", seed_layout),
+            seed_layout_msaa: render_device
+                .create_bind_group_layout("outline_seed_layout_msaa", seed_layout_msaa),
+            jump_flood_layout: render_device
+                .create_bind_group_layout("outline_jump_flood_layout", jump_flood_layout),
+            composite_layout: render_device
+                .create_bind_group_layout("outline_composite_layout", composite_layout),
+            composite_layout_msaa: render_device
+                .create_bind_group_layout("outline_composite_layout_msaa", composite_layout_msaa),
+        }
+    }
+}
+
+impl FromWorld for OutlinePipeline {
+    fn from_world(render_world: &mut bevy_ecs::world::World) -> Self {
+        let render_device = render_world.resource::<RenderDevice>().clone();
+        OutlinePipeline::new(&render_device)
+    }
+}
+
+/// The passes that draw screen-space outlines, in order.
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+pub enum OutlinePass {
+    /// Marks every outlined pixel as a seed of the jump flood.
+    Seed,
+    /// Spreads the nearest seed to each pixel, one step size at a time.
+    JumpFlood,
+    /// Draws the outlines over the view target.
+    Composite,
+}
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+pub struct OutlinePipelineKey {
+    pass: OutlinePass,
+    hdr: bool,
+    samples: u32,
+    creases: bool,
+}
+
+impl SpecializedRenderPipeline for OutlinePipeline {
+    type Key = OutlinePipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let multisampled = key.samples > 1;
+        let mut shader_defs = vec![];
+
+        if multisampled {
+            shader_defs.push(ShaderDefVal::from("MULTISAMPLED"));
+        }
+
+        if key.creases {
+            shader_defs.push("CREASES".into());
+        }
+
+        let (label, layout, pass_def, entry_point, format) = match key.pass {
+            OutlinePass::Seed => (
+                "outline_seed_pipeline",
+                if multisampled {
+                    &self.seed_layout_msaa
+                } else {
+                    &self.seed_layout
+                },
+                "SEED_PASS",
+                "seed",
+                OUTLINE_JUMP_FLOOD_FORMAT,
+            ),
+            OutlinePass::JumpFlood => (
+                "outline_jump_flood_pipeline",
+                &self.jump_flood_layout,
+                "JUMP_FLOOD_PASS",
+                "jump_flood",
+                OUTLINE_JUMP_FLOOD_FORMAT,
+            ),
+            OutlinePass::Composite => (
+                "outline_composite_pipeline",
+                if multisampled {
+                    &self.composite_layout_msaa
+                } else {
+                    &self.composite_layout
+                },
+                "COMPOSITE_PASS",
+                "composite",
+                if key.hdr {
+                    ViewTarget::TEXTURE_FORMAT_HDR
+                } else {
+                    TextureFormat::bevy_default()
+                },
+            ),
+        };
+        shader_defs.push(pass_def.into());
+
+        RenderPipelineDescriptor {
+            label: Some(label.into()),
+            layout: vec![layout.clone()],
+            vertex: fullscreen_shader_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: OUTLINE_SHADER_HANDLE,
+                shader_defs,
+                entry_point: entry_point.into(),
+                targets: vec![Some(ColorTargetState {
+                    format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            push_constant_ranges: vec![],
+            zero_initialize_workgroup_memory: false,
+            multiview: None,
+        }
+    }
+}
+
+/// The pipelines of each [`OutlinePass`] of a view.
+#[derive(Component)]
+pub struct OutlinePipelineIds {
+    pub seed: CachedRenderPipelineId,
+    pub jump_flood: CachedRenderPipelineId,
+    pub composite: CachedRenderPipelineId,
+}
+
+pub(crate) fn prepare_outline_pipelines(
+    mut commands: Commands,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<OutlinePipeline>>,
+    pipeline: Res<OutlinePipeline>,
+    views: Query<(Entity, &ExtractedView, &Msaa, &ScreenSpaceOutlines)>,
+) {
+    for (entity, view, msaa, screen_space_outlines) in &views {
+        let mut specialize = |pass| {
+            pipelines.specialize(
+                &pipeline_cache,
+                &pipeline,
+                OutlinePipelineKey {
+                    pass,
+                    hdr: view.hdr,
+                    samples: msaa.samples(),
+                    creases: screen_space_outlines.crease_angle.is_some(),
+                },
+            )
+        };
+
+        commands.entity(entity).insert(OutlinePipelineIds {
+            seed: specialize(OutlinePass::Seed),
+            jump_flood: specialize(OutlinePass::JumpFlood),
+            composite: specialize(OutlinePass::Composite),
+        });
+    }
+}
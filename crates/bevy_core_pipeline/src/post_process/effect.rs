@@ -0,0 +1,523 @@
+//! A high-level way to write custom full-screen postprocessing effects.
+
+use core::{any::TypeId, marker::PhantomData};
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{AssetServer, Handle};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::{QueryItem, With, Without},
+    resource::Resource,
+    schedule::IntoSystemConfigs as _,
+    system::{lifetimeless::Read, Commands, Query, Res, ResMut},
+    world::{FromWorld, World},
+};
+use bevy_render::{
+    extract_component::{
+        ComponentUniforms, DynamicUniformIndex, ExtractComponent, ExtractComponentPlugin,
+        UniformComponentPlugin,
+    },
+    render_graph::{
+        NodeRunError, RenderGraphApp as _, RenderGraphContext, RenderLabel, ViewNode,
+        ViewNodeRunner,
+    },
+    render_resource::{
+        binding_types::{sampler, texture_2d, uniform_buffer},
+        encase::internal::WriteInto,
+        BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries, CachedRenderPipelineId,
+        ColorTargetState, ColorWrites, FilterMode, FragmentState, Operations, PipelineCache,
+        RenderPassColorAttachment, RenderPassDescriptor, RenderPipelineDescriptor, Sampler,
+        SamplerBindingType, SamplerDescriptor, Shader, ShaderRef, ShaderStages, ShaderType,
+        SpecializedRenderPipeline, SpecializedRenderPipelines, TextureFormat, TextureSampleType,
+    },
+    renderer::{RenderContext, RenderDevice},
+    view::ViewTarget,
+    Render, RenderApp, RenderSet,
+};
+use bevy_utils::prelude::default;
+use tracing::error;
+
+use crate::{
+    core_2d::graph::{Core2d, Node2d},
+    core_3d::graph::{Core3d, Node3d},
+    fullscreen_vertex_shader,
+};
+
+/// A custom full-screen postprocessing effect, drawn by a
+/// [`PostProcessEffectPlugin`] for every camera that has it.
+///
+/// The effect is a component, added to cameras, that doubles as the uniform
+/// passed to its fragment shader. The fragment shader reads the output of the
+/// passes before it and writes the output of the effect, with these bindings:
+///
+/// ```wgsl
+/// @group(0) @binding(0) var screen_texture: texture_2d<f32>;
+/// @group(0) @binding(1) var screen_sampler: sampler;
+/// @group(0) @binding(2) var<uniform> settings: MyEffect;
+/// ```
+///
+/// The vertex stage is the [full-screen triangle], so the fragment shader can
+/// import `FullscreenVertexOutput` from
+/// `bevy_core_pipeline::fullscreen_vertex_shader` as its input.
+///
+/// ```
+/// # use bevy_core_pipeline::post_process::PostProcessEffect;
+/// # use bevy_ecs::component::Component;
+/// # use bevy_render::{extract_component::ExtractComponent, render_resource::{ShaderRef, ShaderType}};
+/// #[derive(Component, Clone, ExtractComponent, ShaderType)]
+/// struct Pixelate {
+///     cell_size: f32,
+/// }
+///
+/// impl PostProcessEffect for Pixelate {
+///     fn fragment_shader() -> ShaderRef {
+///         "shaders/pixelate.wgsl".into()
+///     }
+/// }
+/// ```
+///
+/// [full-screen triangle]: crate::fullscreen_vertex_shader::fullscreen_shader_vertex_state
+pub trait PostProcessEffect: ExtractComponent<Out = Self> + ShaderType + WriteInto + Clone {
+    /// The fragment shader of the effect.
+    ///
+    /// Effects have no default shader, so [`ShaderRef::Default`] disables
+    /// the effect with an error.
+    fn fragment_shader() -> ShaderRef;
+
+    /// The entry point of [`PostProcessEffect::fragment_shader`].
+    fn fragment_entry_point() -> &'static str {
+        "fragment"
+    }
+
+    /// Where the effect runs in the [`Core3d`] graph.
+    ///
+    /// By default, this is after tonemapping, on the final colors of the view.
+    fn placement_3d() -> PostProcessEffectPlacement<Node3d> {
+        PostProcessEffectPlacement {
+            after: Node3d::Tonemapping,
+            before: Node3d::EndMainPassPostProcessing,
+        }
+    }
+
+    /// Where the effect runs in the [`Core2d`] graph.
+    ///
+    /// By default, this is after tonemapping, on the final colors of the view.
+    fn placement_2d() -> PostProcessEffectPlacement<Node2d> {
+        PostProcessEffectPlacement {
+            after: Node2d::Tonemapping,
+            before: Node2d::EndMainPassPostProcessing,
+        }
+    }
+
+    /// Customizes the pipeline of the effect, for example to add shader defs.
+    fn specialize(_descriptor: &mut RenderPipelineDescriptor, _key: PostProcessEffectPipelineKey) {}
+}
+
+/// Where a [`PostProcessEffect`] runs in a render graph, between two of its
+/// nodes.
+#[derive(Clone, Copy, Debug)]
+pub struct PostProcessEffectPlacement<L> {
+    /// The node that the effect runs after.
+    pub after: L,
+    /// The node that the effect runs before.
+    pub before: L,
+}
+
+/// Draws the [`PostProcessEffect`] `E` for every camera that has it, in both
+/// the [`Core2d`] and the [`Core3d`] graphs.
+///
+/// The node of the effect is labeled with [`PostProcessEffectLabel::of`], so
+/// that other nodes can be ordered relative to it.
+pub struct PostProcessEffectPlugin<E: PostProcessEffect>(PhantomData<fn() -> E>);
+
+impl<E: PostProcessEffect> Default for PostProcessEffectPlugin<E> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// The label of the render graph node of the [`PostProcessEffect`] with the
+/// given type.
+#[derive(RenderLabel, Debug, Clone, Hash, PartialEq, Eq)]
+pub struct PostProcessEffectLabel(TypeId);
+
+impl PostProcessEffectLabel {
+    /// The label of the node of the effect `E`.
+    pub fn of<E: PostProcessEffect>() -> Self {
+        Self(TypeId::of::<E>())
+    }
+}
+
+/// GPU pipeline data for a [`PostProcessEffect`].
+///
+/// This is stored in the render world.
+#[derive(Resource)]
+pub struct PostProcessEffectPipeline<E: PostProcessEffect> {
+    /// The layout of bind group 0, containing the source texture, its sampler
+    /// and the settings of the effect.
+    pub bind_group_layout: BindGroupLayout,
+    /// Specifies how to sample the source texture.
+    pub source_sampler: Sampler,
+    /// The fragment shader of the effect, or [`None`] if it has none.
+    pub fragment_shader: Option<Handle<Shader>>,
+    marker: PhantomData<fn() -> E>,
+}
+
+/// A key that uniquely identifies a [`PostProcessEffectPipeline`].
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct PostProcessEffectPipelineKey {
+    /// The format of the source and destination textures, which depends on
+    /// whether the view is HDR.
+    pub texture_format: TextureFormat,
+}
+
+/// A component attached to cameras in the render world that stores the
+/// specialized pipeline ID for the [`PostProcessEffect`] `E`.
+#[derive(Component)]
+pub struct ViewPostProcessEffectPipeline<E: PostProcessEffect> {
+    /// The ID of the [`PostProcessEffectPipeline`] specialized for the view.
+    pub pipeline_id: CachedRenderPipelineId,
+    marker: PhantomData<fn() -> E>,
+}
+
+/// The render node that draws the [`PostProcessEffect`] `E`.
+pub struct PostProcessEffectNode<E: PostProcessEffect>(PhantomData<fn() -> E>);
+
+impl<E: PostProcessEffect> Default for PostProcessEffectNode<E> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<E: PostProcessEffect> Plugin for PostProcessEffectPlugin<E> {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            ExtractComponentPlugin::<E>::default(),
+            UniformComponentPlugin::<E>::default(),
+        ));
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        let placement_3d = E::placement_3d();
+        let placement_2d = E::placement_2d();
+        render_app
+            .init_resource::<SpecializedRenderPipelines<PostProcessEffectPipeline<E>>>()
+            .add_systems(
+                Render,
+                prepare_post_process_effect_pipelines::<E>.in_set(RenderSet::Prepare),
+            )
+            .add_render_graph_node::<ViewNodeRunner<PostProcessEffectNode<E>>>(
+                Core3d,
+                PostProcessEffectLabel::of::<E>(),
+            )
+            .add_render_graph_edges(
+                Core3d,
+                (
+                    placement_3d.after,
+                    PostProcessEffectLabel::of::<E>(),
+                    placement_3d.before,
+                ),
+            )
+            .add_render_graph_node::<ViewNodeRunner<PostProcessEffectNode<E>>>(
+                Core2d,
+                PostProcessEffectLabel::of::<E>(),
+            )
+            .add_render_graph_edges(
+                Core2d,
+                (
+                    placement_2d.after,
+                    PostProcessEffectLabel::of::<E>(),
+                    placement_2d.before,
+                ),
+            );
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app.init_resource::<PostProcessEffectPipeline<E>>();
+    }
+}
+
+impl<E: PostProcessEffect> FromWorld for PostProcessEffectPipeline<E> {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let asset_server = world.resource::<AssetServer>();
+
+        let bind_group_layout = render_device.create_bind_group_layout(
+            Some("post process effect bind group layout"),
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    // Source:
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    // Source sampler:
+                    sampler(SamplerBindingType::Filtering),
+                    // Settings:
+                    uniform_buffer::<E>(true),
+                ),
+            ),
+        );
+
+        let source_sampler = render_device.create_sampler(&SamplerDescriptor {
+            mipmap_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            mag_filter: FilterMode::Linear,
+            ..default()
+        });
+
+        PostProcessEffectPipeline {
+            bind_group_layout,
+            source_sampler,
+            fragment_shader: load_fragment_shader::<E>(asset_server),
+            marker: PhantomData,
+        }
+    }
+}
+
+/// Returns the fragment shader of the [`PostProcessEffect`] `E`, or [`None`]
+/// if it has none, which disables the effect.
+fn load_fragment_shader<E: PostProcessEffect>(
+    asset_server: &AssetServer,
+) -> Option<Handle<Shader>> {
+    match E::fragment_shader() {
+        ShaderRef::Default => {
+            error!(
+                "The post process effect `{}` has no fragment shader",
+                core::any::type_name::<E>()
+            );
+            None
+        }
+        ShaderRef::Handle(handle) => Some(handle),
+        ShaderRef::Path(path) => Some(asset_server.load(path)),
+    }
+}
+
+impl<E: PostProcessEffect> SpecializedRenderPipeline for PostProcessEffectPipeline<E> {
+    type Key = PostProcessEffectPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let mut descriptor = RenderPipelineDescriptor {
+            label: Some("post process effect".into()),
+            layout: vec![self.bind_group_layout.clone()],
+            vertex: fullscreen_vertex_shader::fullscreen_shader_vertex_state(),
+            fragment: Some(FragmentState {
+                // Views only specialize the pipeline if there's a shader.
+                shader: self.fragment_shader.clone().unwrap_or_default(),
+                shader_defs: vec![],
+                entry_point: E::fragment_entry_point().into(),
+                targets: vec![Some(ColorTargetState {
+                    format: key.texture_format,
+                    blend: None,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: default(),
+            depth_stencil: None,
+            multisample: default(),
+            push_constant_ranges: vec![],
+            zero_initialize_workgroup_memory: false,
+            multiview: None,
+        };
+        E::specialize(&mut descriptor, key);
+        descriptor
+    }
+}
+
+/// Specializes the pipeline of the [`PostProcessEffect`] `E` for every view
+/// that has it, and removes it from the views that no longer do.
+pub fn prepare_post_process_effect_pipelines<E: PostProcessEffect>(
+    mut commands: Commands,
+    pipeline_cache: Res<PipelineCache>,
+    mut pipelines: ResMut<SpecializedRenderPipelines<PostProcessEffectPipeline<E>>>,
+    post_process_effect_pipeline: Res<PostProcessEffectPipeline<E>>,
+    views: Query<(Entity, &ViewTarget), With<E>>,
+    removed_views: Query<Entity, (With<ViewPostProcessEffectPipeline<E>>, Without<E>)>,
+) {
+    for entity in removed_views.iter() {
+        commands
+            .entity(entity)
+            .remove::<ViewPostProcessEffectPipeline<E>>();
+    }
+
+    // Effects without a shader never get a pipeline.
+    if post_process_effect_pipeline.fragment_shader.is_none() {
+        return;
+    }
+
+    for (entity, view_target) in views.iter() {
+        let pipeline_id = pipelines.specialize(
+            &pipeline_cache,
+            &post_process_effect_pipeline,
+            PostProcessEffectPipelineKey {
+                texture_format: view_target.main_texture_format(),
+            },
+        );
+
+        commands
+            .entity(entity)
+            .insert(ViewPostProcessEffectPipeline::<E> {
+                pipeline_id,
+                marker: PhantomData,
+            });
+    }
+}
+
+impl<E: PostProcessEffect> ViewNode for PostProcessEffectNode<E> {
+    type ViewQuery = (
+        Read<ViewTarget>,
+        Read<ViewPostProcessEffectPipeline<E>>,
+        Read<DynamicUniformIndex<E>>,
+    );
+
+    fn run<'w>(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext<'w>,
+        (view_target, view_pipeline, uniform_index): QueryItem<'w, Self::ViewQuery>,
+        world: &'w World,
+    ) -> Result<(), NodeRunError> {
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let post_process_effect_pipeline = world.resource::<PostProcessEffectPipeline<E>>();
+        let uniforms = world.resource::<ComponentUniforms<E>>();
+
+        let Some(pipeline) = pipeline_cache.get_render_pipeline(view_pipeline.pipeline_id) else {
+            return Ok(());
+        };
+        let Some(uniforms_binding) = uniforms.uniforms().binding() else {
+            return Ok(());
+        };
+
+        // The bind group has to be created here, because every post process
+        // write swaps the source and the destination.
+        let post_process = view_target.post_process_write();
+
+        let bind_group = render_context.render_device().create_bind_group(
+            Some("post process effect bind group"),
+            &post_process_effect_pipeline.bind_group_layout,
+            &BindGroupEntries::sequential((
+                post_process.source,
+                &post_process_effect_pipeline.source_sampler,
+                uniforms_binding,
+            )),
+        );
+
+        let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
+            label: Some("post process effect pass"),
+            color_attachments: &[Some(RenderPassColorAttachment {
+                view: post_process.destination,
+                resolve_target: None,
+                ops: Operations::default(),
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+
+        render_pass.set_render_pipeline(pipeline);
+        render_pass.set_bind_group(0, &bind_group, &[uniform_index.index()]);
+        render_pass.draw(0..3, 0..1);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_app::SubApp;
+    use bevy_asset::{io::AssetSourceBuilders, AssetServerMode};
+    use bevy_render::{
+        extract_component::ExtractComponent,
+        render_graph::{Edge, EmptyNode, RenderGraph, RenderSubGraph},
+    };
+
+    use super::*;
+
+    #[derive(Component, Clone, ExtractComponent, ShaderType)]
+    struct TestEffect {
+        intensity: f32,
+    }
+
+    impl PostProcessEffect for TestEffect {
+        fn fragment_shader() -> ShaderRef {
+            "shaders/test_effect.wgsl".into()
+        }
+
+        fn placement_3d() -> PostProcessEffectPlacement<Node3d> {
+            PostProcessEffectPlacement {
+                after: Node3d::Bloom,
+                before: Node3d::Tonemapping,
+            }
+        }
+    }
+
+    #[derive(Component, Clone, ExtractComponent, ShaderType)]
+    struct EffectWithoutShader {
+        intensity: f32,
+    }
+
+    impl PostProcessEffect for EffectWithoutShader {
+        fn fragment_shader() -> ShaderRef {
+            ShaderRef::Default
+        }
+    }
+
+    fn assert_placed<L: RenderLabel + Clone>(
+        render_graph: &RenderGraph,
+        sub_graph: impl RenderSubGraph,
+        placement: PostProcessEffectPlacement<L>,
+    ) {
+        let label = PostProcessEffectLabel::of::<TestEffect>();
+        let node = render_graph
+            .sub_graph(sub_graph)
+            .get_node_state(label.clone())
+            .expect("the effect should have a node");
+        assert!(node.edges.has_input_edge(&Edge::NodeEdge {
+            input_node: label.clone().intern(),
+            output_node: placement.after.intern(),
+        }));
+        assert!(node.edges.has_output_edge(&Edge::NodeEdge {
+            input_node: placement.before.intern(),
+            output_node: label.intern(),
+        }));
+    }
+
+    #[test]
+    fn plugin_adds_placed_nodes() {
+        let mut render_graph = RenderGraph::default();
+        let mut core_2d = RenderGraph::default();
+        core_2d.add_node(Node2d::Tonemapping, EmptyNode);
+        core_2d.add_node(Node2d::EndMainPassPostProcessing, EmptyNode);
+        render_graph.add_sub_graph(Core2d, core_2d);
+        let mut core_3d = RenderGraph::default();
+        core_3d.add_node(Node3d::Bloom, EmptyNode);
+        core_3d.add_node(Node3d::Tonemapping, EmptyNode);
+        render_graph.add_sub_graph(Core3d, core_3d);
+
+        let mut render_app = SubApp::new();
+        render_app.insert_resource(render_graph);
+        let mut app = App::new();
+        app.insert_sub_app(RenderApp, render_app);
+        app.add_plugins(PostProcessEffectPlugin::<TestEffect>::default());
+
+        let render_graph = app.sub_app(RenderApp).world().resource::<RenderGraph>();
+        assert_placed(render_graph, Core2d, TestEffect::placement_2d());
+        assert_placed(render_graph, Core3d, TestEffect::placement_3d());
+    }
+
+    #[test]
+    fn default_shader_disables_effect() {
+        let mut sources = AssetSourceBuilders::default();
+        sources.init_default_source("assets", None);
+        let asset_server = AssetServer::new(
+            sources.build_sources(false, false),
+            AssetServerMode::Unprocessed,
+            false,
+        );
+        assert!(load_fragment_shader::<EffectWithoutShader>(&asset_server).is_none());
+    }
+}
@@ -0,0 +1,89 @@
+use bevy_ecs::{
+    component::Component,
+    query::{QueryItem, With},
+    reflect::ReflectComponent,
+    system::lifetimeless::Read,
+};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    camera::Camera, extract_component::ExtractComponent, render_resource::ShaderType,
+};
+
+/// Adds animated noise to the image, simulating the grain of photographic
+/// film.
+///
+/// The grain changes every frame. As on film, it's most visible in the
+/// shadows and midtones and fades out in bright areas, to an extent set by
+/// [`FilmGrain::luminance_response`].
+#[derive(Reflect, Component, Clone)]
+#[reflect(Component, Default)]
+pub struct FilmGrain {
+    /// How strong the grain is.
+    ///
+    /// The default value is 0.05.
+    pub intensity: f32,
+
+    /// The size of each grain, in pixels.
+    ///
+    /// The default value is 1.5.
+    pub grain_size: f32,
+
+    /// How much the grain fades out in bright areas.
+    ///
+    /// At 0.0, the grain is equally strong everywhere. At 1.0, it disappears
+    /// entirely from pixels with a luminance of 1.0 or more.
+    ///
+    /// The default value is 0.75.
+    pub luminance_response: f32,
+}
+
+impl Default for FilmGrain {
+    fn default() -> Self {
+        Self {
+            intensity: 0.05,
+            grain_size: 1.5,
+            luminance_response: 0.75,
+        }
+    }
+}
+
+/// The on-GPU version of the [`FilmGrain`] settings.
+///
+/// See the documentation for [`FilmGrain`] for more information on each of
+/// these fields.
+#[derive(ShaderType, Default)]
+pub struct FilmGrainUniform {
+    pub(super) intensity: f32,
+    pub(super) grain_size: f32,
+    pub(super) luminance_response: f32,
+    /// The number of the frame, which animates the grain.
+    pub(super) frame: u32,
+}
+
+impl FilmGrainUniform {
+    pub(super) fn new(film_grain: &FilmGrain, frame: u32) -> Self {
+        Self {
+            intensity: film_grain.intensity,
+            grain_size: film_grain.grain_size.max(1.0),
+            luminance_response: film_grain.luminance_response.clamp(0.0, 1.0),
+            frame,
+        }
+    }
+}
+
+impl ExtractComponent for FilmGrain {
+    type QueryData = Read<FilmGrain>;
+
+    type QueryFilter = With<Camera>;
+
+    type Out = FilmGrain;
+
+    fn extract_component(film_grain: QueryItem<'_, Self::QueryData>) -> Option<Self::Out> {
+        // Skip the effect entirely if the intensity is zero.
+        if film_grain.intensity > 0.0 {
+            Some(film_grain.clone())
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,37 @@
+// The film grain postprocessing effect.
+//
+// This adds animated noise that is strongest in the shadows and midtones.
+
+#define_import_path bevy_core_pipeline::post_processing::film_grain
+
+// See `bevy_core_pipeline::post_process::FilmGrain` for more information on
+// these fields.
+struct FilmGrainSettings {
+    intensity: f32,
+    grain_size: f32,
+    luminance_response: f32,
+    frame: u32,
+}
+
+// The settings supplied by the developer.
+@group(0) @binding(8) var<uniform> film_grain_settings: FilmGrainSettings;
+
+// A PCG hash, from "Hash Functions for GPU Rendering" (Jarzynski & Olano 2020).
+fn pcg_hash(input: u32) -> u32 {
+    let state = input * 747796405u + 2891336453u;
+    let word = ((state >> ((state >> 28u) + 4u)) ^ state) * 277803737u;
+    return (word >> 22u) ^ word;
+}
+
+fn film_grain(color: vec3<f32>, frag_coord: vec2<f32>) -> vec3<f32> {
+    // Pick a random value in [-0.5, 0.5] for each grain, changing every frame.
+    let grain = vec2<u32>(frag_coord / film_grain_settings.grain_size);
+    let hash = pcg_hash(grain.x ^ pcg_hash(grain.y ^ pcg_hash(film_grain_settings.frame)));
+    let noise = f32(hash) / 4294967295.0 - 0.5;
+
+    // Fade the grain out in bright areas.
+    let luminance = dot(color, vec3(0.2126, 0.7152, 0.0722));
+    let response = 1.0 - film_grain_settings.luminance_response * saturate(luminance);
+
+    return max(color + vec3(noise * film_grain_settings.intensity * response), vec3(0.0));
+}
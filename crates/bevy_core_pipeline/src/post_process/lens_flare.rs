@@ -0,0 +1,119 @@
+use bevy_ecs::{
+    component::Component,
+    query::{QueryItem, With},
+    reflect::ReflectComponent,
+    system::lifetimeless::Read,
+};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    camera::Camera, extract_component::ExtractComponent, render_resource::ShaderType,
+};
+
+/// Adds ghosts and a halo of the brightest parts of the image, mirrored
+/// around the center of the screen, as a camera lens would.
+///
+/// This is a screen-space effect based on John Chapman's [pseudo lens
+/// flare]. It reads the blurred bright areas from the mip chain of the
+/// camera's [`Bloom`](crate::bloom::Bloom), so it has no effect on cameras
+/// without bloom.
+///
+/// [pseudo lens flare]: https://john-chapman-graphics.blogspot.com/2013/02/pseudo-lens-flare.html
+#[derive(Reflect, Component, Clone)]
+#[reflect(Component, Default)]
+pub struct LensFlare {
+    /// How bright the flare is, relative to the bright areas it comes from.
+    ///
+    /// The default value is 0.05.
+    pub intensity: f32,
+
+    /// The brightness below which parts of the image don't create a flare.
+    ///
+    /// The default value is 1.0, so that only colors that are brighter than
+    /// white create flares on HDR cameras.
+    pub threshold: f32,
+
+    /// The number of ghosts.
+    ///
+    /// The default value is 4.
+    pub ghost_count: u32,
+
+    /// The distance between successive ghosts, as a fraction of the distance
+    /// from a bright area to the center of the screen.
+    ///
+    /// The default value is 0.35.
+    pub ghost_spacing: f32,
+
+    /// The radius of the halo, as a fraction of the screen size.
+    ///
+    /// The default value is 0.45.
+    pub halo_radius: f32,
+
+    /// How far the red and blue channels of the flare are pulled apart, as a
+    /// fraction of the screen size.
+    ///
+    /// The default value is 0.005.
+    pub chromatic_distortion: f32,
+}
+
+impl Default for LensFlare {
+    fn default() -> Self {
+        Self {
+            intensity: 0.05,
+            threshold: 1.0,
+            ghost_count: 4,
+            ghost_spacing: 0.35,
+            halo_radius: 0.45,
+            chromatic_distortion: 0.005,
+        }
+    }
+}
+
+/// The on-GPU version of the [`LensFlare`] settings.
+///
+/// See the documentation for [`LensFlare`] for more information on each of
+/// these fields.
+#[derive(ShaderType, Default)]
+pub struct LensFlareUniform {
+    pub(super) intensity: f32,
+    pub(super) threshold: f32,
+    pub(super) ghost_count: u32,
+    pub(super) ghost_spacing: f32,
+    pub(super) halo_radius: f32,
+    pub(super) chromatic_distortion: f32,
+    /// Padding data.
+    pub(super) unused_1: u32,
+    /// Padding data.
+    pub(super) unused_2: u32,
+}
+
+impl From<&LensFlare> for LensFlareUniform {
+    fn from(lens_flare: &LensFlare) -> Self {
+        Self {
+            intensity: lens_flare.intensity,
+            threshold: lens_flare.threshold,
+            ghost_count: lens_flare.ghost_count,
+            ghost_spacing: lens_flare.ghost_spacing,
+            halo_radius: lens_flare.halo_radius,
+            chromatic_distortion: lens_flare.chromatic_distortion,
+            unused_1: 0,
+            unused_2: 0,
+        }
+    }
+}
+
+impl ExtractComponent for LensFlare {
+    type QueryData = Read<LensFlare>;
+
+    type QueryFilter = With<Camera>;
+
+    type Out = LensFlare;
+
+    fn extract_component(lens_flare: QueryItem<'_, Self::QueryData>) -> Option<Self::Out> {
+        // Skip the effect entirely if the intensity is zero.
+        if lens_flare.intensity > 0.0 {
+            Some(lens_flare.clone())
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,70 @@
+// The lens flare postprocessing effect.
+//
+// This mirrors the brightest parts of the image around the center of the
+// screen as ghosts and a halo, following John Chapman's pseudo lens flare:
+//
+// <https://john-chapman-graphics.blogspot.com/2013/02/pseudo-lens-flare.html>
+
+#define_import_path bevy_core_pipeline::post_processing::lens_flare
+
+// See `bevy_core_pipeline::post_process::LensFlare` for more information on
+// these fields.
+struct LensFlareSettings {
+    intensity: f32,
+    threshold: f32,
+    ghost_count: u32,
+    ghost_spacing: f32,
+    halo_radius: f32,
+    chromatic_distortion: f32,
+    unused_a: u32,
+    unused_b: u32,
+}
+
+// The first mip of the bloom texture, which holds the blurred image.
+@group(0) @binding(5) var lens_flare_bloom_texture: texture_2d<f32>;
+// The sampler used to sample the bloom texture.
+@group(0) @binding(6) var lens_flare_bloom_sampler: sampler;
+// The settings supplied by the developer.
+@group(0) @binding(7) var<uniform> lens_flare_settings: LensFlareSettings;
+
+// The length of the vector from the center of the screen to a corner.
+const HALF_DIAGONAL: f32 = 0.70710678;
+
+// Samples the parts of the bloom texture above the threshold, splitting the
+// red and blue channels apart along `direction`.
+fn sample_bright(uv: vec2<f32>, direction: vec2<f32>) -> vec3<f32> {
+    let offset = direction * lens_flare_settings.chromatic_distortion;
+    let color = vec3(
+        textureSampleLevel(lens_flare_bloom_texture, lens_flare_bloom_sampler, uv + offset, 0.0).r,
+        textureSampleLevel(lens_flare_bloom_texture, lens_flare_bloom_sampler, uv, 0.0).g,
+        textureSampleLevel(lens_flare_bloom_texture, lens_flare_bloom_sampler, uv - offset, 0.0).b,
+    );
+    return max(color - vec3(lens_flare_settings.threshold), vec3(0.0));
+}
+
+// Fades samples out towards the edges of the screen, so that ghosts don't
+// appear to be cut off.
+fn edge_weight(uv: vec2<f32>, exponent: f32) -> f32 {
+    return pow(saturate(1.0 - length(vec2(0.5) - uv) / HALF_DIAGONAL), exponent);
+}
+
+fn lens_flare(uv: vec2<f32>) -> vec3<f32> {
+    // Ghosts are images of the bright areas mirrored through the center.
+    let flipped_uv = vec2(1.0) - uv;
+    let ghost_step = (vec2(0.5) - flipped_uv) * lens_flare_settings.ghost_spacing;
+    let ghost_length = length(ghost_step);
+    let direction = select(vec2(0.0), ghost_step / ghost_length, ghost_length > 0.0);
+
+    var flare = vec3(0.0);
+    for (var ghost_index = 0u; ghost_index < lens_flare_settings.ghost_count; ghost_index += 1u) {
+        let ghost_uv = fract(flipped_uv + ghost_step * f32(ghost_index));
+        flare += sample_bright(ghost_uv, direction) * edge_weight(ghost_uv, 10.0);
+    }
+
+    // The halo is a ring of the bright areas at a fixed distance from the
+    // center.
+    let halo_uv = fract(flipped_uv + direction * lens_flare_settings.halo_radius);
+    flare += sample_bright(halo_uv, direction) * edge_weight(halo_uv, 5.0);
+
+    return flare * lens_flare_settings.intensity;
+}
@@ -1,14 +1,16 @@
 //! Miscellaneous built-in postprocessing effects.
 //!
-//! Currently, this consists only of chromatic aberration.
+//! Currently, this consists of chromatic aberration, lens flare, vignette and
+//! film grain. Custom effects can be added with [`PostProcessEffectPlugin`].
 
 use bevy_app::{App, Plugin};
 use bevy_asset::{load_internal_asset, weak_handle, Assets, Handle};
 use bevy_derive::{Deref, DerefMut};
+use bevy_diagnostic::FrameCount;
 use bevy_ecs::{
     component::Component,
     entity::Entity,
-    query::{QueryItem, With},
+    query::{Has, Or, QueryItem, With},
     reflect::ReflectComponent,
     resource::Resource,
     schedule::IntoSystemConfigs as _,
@@ -18,7 +20,7 @@ use bevy_ecs::{
 use bevy_image::{BevyDefault, Image};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_render::{
-    camera::Camera,
+    camera::{Camera, Multiview},
     extract_component::{ExtractComponent, ExtractComponentPlugin},
     render_asset::{RenderAssetUsages, RenderAssets},
     render_graph::{
@@ -30,28 +32,53 @@ use bevy_render::{
         ColorTargetState, ColorWrites, DynamicUniformBuffer, Extent3d, FilterMode, FragmentState,
         Operations, PipelineCache, RenderPassColorAttachment, RenderPassDescriptor,
         RenderPipelineDescriptor, Sampler, SamplerBindingType, SamplerDescriptor, Shader,
-        ShaderStages, ShaderType, SpecializedRenderPipeline, SpecializedRenderPipelines,
-        TextureDimension, TextureFormat, TextureSampleType,
+        ShaderDefVal, ShaderStages, ShaderType, SpecializedRenderPipeline,
+        SpecializedRenderPipelines, TextureDimension, TextureFormat, TextureSampleType,
     },
     renderer::{RenderContext, RenderDevice, RenderQueue},
-    texture::GpuImage,
+    texture::{FallbackImageZero, GpuImage},
     view::{ExtractedView, ViewTarget},
     Render, RenderApp, RenderSet,
 };
-use bevy_utils::prelude::default;
+use bevy_utils::{once, prelude::default};
+use bitflags::bitflags;
+use tracing::warn;
 
 use crate::{
+    bloom::BloomTexture,
     core_2d::graph::{Core2d, Node2d},
     core_3d::graph::{Core3d, Node3d},
     fullscreen_vertex_shader,
 };
 
+mod effect;
+mod film_grain;
+mod lens_flare;
+mod vignette;
+
+pub use effect::{
+    prepare_post_process_effect_pipelines, PostProcessEffect, PostProcessEffectLabel,
+    PostProcessEffectNode, PostProcessEffectPipeline, PostProcessEffectPipelineKey,
+    PostProcessEffectPlacement, PostProcessEffectPlugin, ViewPostProcessEffectPipeline,
+};
+pub use film_grain::{FilmGrain, FilmGrainUniform};
+pub use lens_flare::{LensFlare, LensFlareUniform};
+pub use vignette::{Vignette, VignetteUniform};
+
 /// The handle to the built-in postprocessing shader `post_process.wgsl`.
 const POST_PROCESSING_SHADER_HANDLE: Handle<Shader> =
     weak_handle!("5e8e627a-7531-484d-a988-9a38acb34e52");
 /// The handle to the chromatic aberration shader `chromatic_aberration.wgsl`.
 const CHROMATIC_ABERRATION_SHADER_HANDLE: Handle<Shader> =
     weak_handle!("e598550e-71c3-4f5a-ba29-aebc3f88c7b5");
+/// The handle to the lens flare shader `lens_flare.wgsl`.
+const LENS_FLARE_SHADER_HANDLE: Handle<Shader> =
+    weak_handle!("0c4f5d27-9b3e-4a61-8e7d-2f6a1b9c3e58");
+/// The handle to the film grain shader `film_grain.wgsl`.
+const FILM_GRAIN_SHADER_HANDLE: Handle<Shader> =
+    weak_handle!("a7e2c9d4-56b1-4f3a-9c08-e1d4b7f26a93");
+/// The handle to the vignette shader `vignette.wgsl`.
+const VIGNETTE_SHADER_HANDLE: Handle<Shader> = weak_handle!("3f81b6e0-d27c-4c95-b4a3-6e0f9d2c71b8");
 
 /// The handle to the default chromatic aberration lookup texture.
 ///
@@ -77,7 +104,8 @@ static DEFAULT_CHROMATIC_ABERRATION_LUT_DATA: [u8; 12] =
 /// A plugin that implements a built-in postprocessing stack with some common
 /// effects.
 ///
-/// Currently, this only consists of chromatic aberration.
+/// Currently, this consists of [`ChromaticAberration`], [`LensFlare`],
+/// [`Vignette`] and [`FilmGrain`], which are all drawn in a single pass.
 pub struct PostProcessingPlugin;
 
 /// Adds colored fringes to the edges of objects in the scene.
@@ -130,7 +158,8 @@ pub struct ChromaticAberration {
 /// This is stored in the render world.
 #[derive(Resource)]
 pub struct PostProcessingPipeline {
-    /// The layout of bind group 0, containing the source, LUT, and settings.
+    /// The layout of bind group 0, containing the source, the LUT, the bloom
+    /// texture, and the settings of every effect.
     bind_group_layout: BindGroupLayout,
     /// Specifies how to sample the source framebuffer texture.
     source_sampler: Sampler,
@@ -143,6 +172,63 @@ pub struct PostProcessingPipeline {
 pub struct PostProcessingPipelineKey {
     /// The format of the source and destination textures.
     texture_format: TextureFormat,
+    /// The effects that the view uses.
+    effects: PostProcessingEffects,
+}
+
+bitflags! {
+    /// The effects of the built-in postprocessing stack that a view uses.
+    ///
+    /// This allows the shader to skip the others.
+    #[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+    pub struct PostProcessingEffects: u8 {
+        /// The view has [`ChromaticAberration`].
+        const CHROMATIC_ABERRATION = 0x01;
+        /// The view has a [`LensFlare`].
+        const LENS_FLARE           = 0x02;
+        /// The view has [`FilmGrain`].
+        const FILM_GRAIN           = 0x04;
+        /// The view has a [`Vignette`].
+        const VIGNETTE             = 0x08;
+    }
+}
+
+impl PostProcessingEffects {
+    /// Returns the effects of a view from whether it has each of their
+    /// components.
+    fn from_view(
+        (chromatic_aberration, lens_flare, film_grain, vignette): QueryItem<
+            '_,
+            PostProcessingEffectComponents,
+        >,
+    ) -> Self {
+        let mut effects = PostProcessingEffects::empty();
+        effects.set(
+            PostProcessingEffects::CHROMATIC_ABERRATION,
+            chromatic_aberration,
+        );
+        effects.set(PostProcessingEffects::LENS_FLARE, lens_flare);
+        effects.set(PostProcessingEffects::FILM_GRAIN, film_grain);
+        effects.set(PostProcessingEffects::VIGNETTE, vignette);
+        effects
+    }
+
+    /// Returns the shader defs that enable these effects in the shader.
+    fn shader_defs(self) -> Vec<ShaderDefVal> {
+        [
+            (
+                PostProcessingEffects::CHROMATIC_ABERRATION,
+                "CHROMATIC_ABERRATION",
+            ),
+            (PostProcessingEffects::LENS_FLARE, "LENS_FLARE"),
+            (PostProcessingEffects::FILM_GRAIN, "FILM_GRAIN"),
+            (PostProcessingEffects::VIGNETTE, "VIGNETTE"),
+        ]
+        .into_iter()
+        .filter(|(effect, _)| self.contains(*effect))
+        .map(|(_, shader_def)| shader_def.into())
+        .collect()
+    }
 }
 
 /// A component attached to cameras in the render world that stores the
@@ -154,7 +240,7 @@ pub struct PostProcessingPipelineId(CachedRenderPipelineId);
 ///
 /// See the documentation for [`ChromaticAberration`] for more information on
 /// each of these fields.
-#[derive(ShaderType)]
+#[derive(ShaderType, Default)]
 pub struct ChromaticAberrationUniform {
     /// The intensity of the effect, in a fraction of the screen.
     intensity: f32,
@@ -167,19 +253,40 @@ pub struct ChromaticAberrationUniform {
     unused_2: u32,
 }
 
-/// A resource, part of the render world, that stores the
-/// [`ChromaticAberrationUniform`]s for each view.
-#[derive(Resource, Deref, DerefMut, Default)]
+impl From<&ChromaticAberration> for ChromaticAberrationUniform {
+    fn from(chromatic_aberration: &ChromaticAberration) -> Self {
+        Self {
+            intensity: chromatic_aberration.intensity,
+            max_samples: chromatic_aberration.max_samples,
+            unused_1: 0,
+            unused_2: 0,
+        }
+    }
+}
+
+/// A resource, part of the render world, that stores the uniforms of every
+/// postprocessing effect for each view.
+///
+/// Every view that uses any of the effects has a uniform in each buffer, so
+/// that all of the bindings are valid. The uniforms of the effects that a view
+/// doesn't use are zeroed.
+#[derive(Resource, Default)]
 pub struct PostProcessingUniformBuffers {
     chromatic_aberration: DynamicUniformBuffer<ChromaticAberrationUniform>,
+    lens_flare: DynamicUniformBuffer<LensFlareUniform>,
+    film_grain: DynamicUniformBuffer<FilmGrainUniform>,
+    vignette: DynamicUniformBuffer<VignetteUniform>,
 }
 
 /// A component, part of the render world, that stores the appropriate byte
-/// offset within the [`PostProcessingUniformBuffers`] for the camera it's
+/// offsets within the [`PostProcessingUniformBuffers`] for the camera it's
 /// attached to.
-#[derive(Component, Deref, DerefMut)]
+#[derive(Component)]
 pub struct PostProcessingUniformBufferOffsets {
     chromatic_aberration: u32,
+    lens_flare: u32,
+    film_grain: u32,
+    vignette: u32,
 }
 
 /// The render node that runs the built-in postprocessing stack.
@@ -200,6 +307,24 @@ impl Plugin for PostProcessingPlugin {
             "chromatic_aberration.wgsl",
             Shader::from_wgsl
         );
+        load_internal_asset!(
+            app,
+            LENS_FLARE_SHADER_HANDLE,
+            "lens_flare.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            FILM_GRAIN_SHADER_HANDLE,
+            "film_grain.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            VIGNETTE_SHADER_HANDLE,
+            "vignette.wgsl",
+            Shader::from_wgsl
+        );
 
         // Load the default chromatic aberration LUT.
         let mut assets = app.world_mut().resource_mut::<Assets<_>>();
@@ -218,8 +343,16 @@ impl Plugin for PostProcessingPlugin {
             ),
         );
 
-        app.register_type::<ChromaticAberration>();
-        app.add_plugins(ExtractComponentPlugin::<ChromaticAberration>::default());
+        app.register_type::<ChromaticAberration>()
+            .register_type::<LensFlare>()
+            .register_type::<FilmGrain>()
+            .register_type::<Vignette>();
+        app.add_plugins((
+            ExtractComponentPlugin::<ChromaticAberration>::default(),
+            ExtractComponentPlugin::<LensFlare>::default(),
+            ExtractComponentPlugin::<FilmGrain>::default(),
+            ExtractComponentPlugin::<Vignette>::default(),
+        ));
 
         let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
             return;
@@ -296,6 +429,16 @@ impl FromWorld for PostProcessingPipeline {
                     sampler(SamplerBindingType::Filtering),
                     // Chromatic aberration settings:
                     uniform_buffer::<ChromaticAberrationUniform>(true),
+                    // Lens flare bloom texture:
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    // Lens flare bloom texture sampler:
+                    sampler(SamplerBindingType::Filtering),
+                    // Lens flare settings:
+                    uniform_buffer::<LensFlareUniform>(true),
+                    // Film grain settings:
+                    uniform_buffer::<FilmGrainUniform>(true),
+                    // Vignette settings:
+                    uniform_buffer::<VignetteUniform>(true),
                 ),
             ),
         );
@@ -335,7 +478,7 @@ impl SpecializedRenderPipeline for PostProcessingPipeline {
             vertex: fullscreen_vertex_shader::fullscreen_shader_vertex_state(),
             fragment: Some(FragmentState {
                 shader: POST_PROCESSING_SHADER_HANDLE,
-                shader_defs: vec![],
+                shader_defs: key.effects.shader_defs(),
                 entry_point: "fragment_main".into(),
                 targets: vec![Some(ColorTargetState {
                     format: key.texture_format,
@@ -348,6 +491,7 @@ impl SpecializedRenderPipeline for PostProcessingPipeline {
             multisample: default(),
             push_constant_ranges: vec![],
             zero_initialize_workgroup_memory: false,
+            multiview: None,
         }
     }
 }
@@ -356,21 +500,36 @@ impl ViewNode for PostProcessingNode {
     type ViewQuery = (
         Read<ViewTarget>,
         Read<PostProcessingPipelineId>,
-        Read<ChromaticAberration>,
+        Option<Read<ChromaticAberration>>,
         Read<PostProcessingUniformBufferOffsets>,
+        Has<Multiview>,
     );
 
     fn run<'w>(
         &self,
-        _: &mut RenderGraphContext,
+        graph: &mut RenderGraphContext,
         render_context: &mut RenderContext<'w>,
-        (view_target, pipeline_id, chromatic_aberration, post_processing_uniform_buffer_offsets): QueryItem<'w, Self::ViewQuery>,
+        (
+            view_target,
+            pipeline_id,
+            chromatic_aberration,
+            post_processing_uniform_buffer_offsets,
+            multiview,
+        ): QueryItem<'w, Self::ViewQuery>,
         world: &'w World,
     ) -> Result<(), NodeRunError> {
+        if multiview {
+            once!(warn!(
+                "Post-processing isn't supported on `Multiview` cameras and is skipped"
+            ));
+            return Ok(());
+        }
+
         let pipeline_cache = world.resource::<PipelineCache>();
         let post_processing_pipeline = world.resource::<PostProcessingPipeline>();
         let post_processing_uniform_buffers = world.resource::<PostProcessingUniformBuffers>();
         let gpu_image_assets = world.resource::<RenderAssets<GpuImage>>();
+        let fallback_image = world.resource::<FallbackImageZero>();
 
         // We need a render pipeline to be prepared.
         let Some(pipeline) = pipeline_cache.get_render_pipeline(**pipeline_id) else {
@@ -378,15 +537,33 @@ impl ViewNode for PostProcessingNode {
         };
 
         // We need the chromatic aberration LUT to be present.
-        let Some(chromatic_aberration_lut) = gpu_image_assets.get(&chromatic_aberration.color_lut)
+        let Some(chromatic_aberration_lut) =
+            gpu_image_assets.get(chromatic_aberration_lut(chromatic_aberration))
         else {
             return Ok(());
         };
 
+        // Lens flares are drawn from the first mip of the bloom texture. Views
+        // without bloom get no flare.
+        let bloom_view = world
+            .get::<BloomTexture>(graph.view_entity())
+            .map(|bloom_texture| bloom_texture.view(0));
+        let bloom_view = bloom_view.as_ref().unwrap_or(&fallback_image.texture_view);
+
         // We need the postprocessing settings to be uploaded to the GPU.
-        let Some(chromatic_aberration_uniform_buffer_binding) = post_processing_uniform_buffers
-            .chromatic_aberration
-            .binding()
+        let (
+            Some(chromatic_aberration_uniform_buffer_binding),
+            Some(lens_flare_uniform_buffer_binding),
+            Some(film_grain_uniform_buffer_binding),
+            Some(vignette_uniform_buffer_binding),
+        ) = (
+            post_processing_uniform_buffers
+                .chromatic_aberration
+                .binding(),
+            post_processing_uniform_buffers.lens_flare.binding(),
+            post_processing_uniform_buffers.film_grain.binding(),
+            post_processing_uniform_buffers.vignette.binding(),
+        )
         else {
             return Ok(());
         };
@@ -416,6 +593,11 @@ impl ViewNode for PostProcessingNode {
                 &chromatic_aberration_lut.texture_view,
                 &post_processing_pipeline.chromatic_aberration_lut_sampler,
                 chromatic_aberration_uniform_buffer_binding,
+                bloom_view,
+                &post_processing_pipeline.source_sampler,
+                lens_flare_uniform_buffer_binding,
+                film_grain_uniform_buffer_binding,
+                vignette_uniform_buffer_binding,
             )),
         );
 
@@ -424,22 +606,62 @@ impl ViewNode for PostProcessingNode {
             .begin_render_pass(&pass_descriptor);
 
         render_pass.set_pipeline(pipeline);
-        render_pass.set_bind_group(0, &bind_group, &[**post_processing_uniform_buffer_offsets]);
+        render_pass.set_bind_group(
+            0,
+            &bind_group,
+            &[
+                post_processing_uniform_buffer_offsets.chromatic_aberration,
+                post_processing_uniform_buffer_offsets.lens_flare,
+                post_processing_uniform_buffer_offsets.film_grain,
+                post_processing_uniform_buffer_offsets.vignette,
+            ],
+        );
         render_pass.draw(0..3, 0..1);
 
         Ok(())
     }
 }
 
+/// A query filter that matches views that use any of the built-in
+/// postprocessing effects.
+type WithPostProcessing = Or<(
+    With<ChromaticAberration>,
+    With<LensFlare>,
+    With<FilmGrain>,
+    With<Vignette>,
+)>;
+
+/// Whether a view has each of the built-in postprocessing effects, in the order
+/// of [`PostProcessingEffects::from_view`].
+type PostProcessingEffectComponents = (
+    Has<ChromaticAberration>,
+    Has<LensFlare>,
+    Has<FilmGrain>,
+    Has<Vignette>,
+);
+
+/// Returns the chromatic aberration LUT that a view binds.
+///
+/// Views without chromatic aberration still bind a LUT, so they use the
+/// default one.
+fn chromatic_aberration_lut(chromatic_aberration: Option<&ChromaticAberration>) -> &Handle<Image> {
+    chromatic_aberration.map_or(
+        &DEFAULT_CHROMATIC_ABERRATION_LUT_HANDLE,
+        |chromatic_aberration| &chromatic_aberration.color_lut,
+    )
+}
+
 /// Specializes the built-in postprocessing pipeline for each applicable view.
 pub fn prepare_post_processing_pipelines(
     mut commands: Commands,
     pipeline_cache: Res<PipelineCache>,
     mut pipelines: ResMut<SpecializedRenderPipelines<PostProcessingPipeline>>,
     post_processing_pipeline: Res<PostProcessingPipeline>,
-    views: Query<(Entity, &ExtractedView), With<ChromaticAberration>>,
+    views: Query<(Entity, &ExtractedView, PostProcessingEffectComponents), WithPostProcessing>,
 ) {
-    for (entity, view) in views.iter() {
+    for (entity, view, effects) in views.iter() {
+        let effects = PostProcessingEffects::from_view(effects);
+
         let pipeline_id = pipelines.specialize(
             &pipeline_cache,
             &post_processing_pipeline,
@@ -449,6 +671,7 @@ pub fn prepare_post_processing_pipelines(
                 } else {
                     TextureFormat::bevy_default()
                 },
+                effects,
             },
         );
 
@@ -465,28 +688,56 @@ pub fn prepare_post_processing_uniforms(
     mut post_processing_uniform_buffers: ResMut<PostProcessingUniformBuffers>,
     render_device: Res<RenderDevice>,
     render_queue: Res<RenderQueue>,
-    mut views: Query<(Entity, &ChromaticAberration)>,
+    frame_count: Res<FrameCount>,
+    mut views: Query<
+        (
+            Entity,
+            Option<&ChromaticAberration>,
+            Option<&LensFlare>,
+            Option<&FilmGrain>,
+            Option<&Vignette>,
+        ),
+        WithPostProcessing,
+    >,
 ) {
-    post_processing_uniform_buffers.clear();
+    let buffers = &mut *post_processing_uniform_buffers;
+    buffers.chromatic_aberration.clear();
+    buffers.lens_flare.clear();
+    buffers.film_grain.clear();
+    buffers.vignette.clear();
 
     // Gather up all the postprocessing settings.
-    for (view_entity, chromatic_aberration) in views.iter_mut() {
-        let chromatic_aberration_uniform_buffer_offset =
-            post_processing_uniform_buffers.push(&ChromaticAberrationUniform {
-                intensity: chromatic_aberration.intensity,
-                max_samples: chromatic_aberration.max_samples,
-                unused_1: 0,
-                unused_2: 0,
-            });
-        commands
-            .entity(view_entity)
-            .insert(PostProcessingUniformBufferOffsets {
-                chromatic_aberration: chromatic_aberration_uniform_buffer_offset,
-            });
+    for (view_entity, chromatic_aberration, lens_flare, film_grain, vignette) in views.iter_mut() {
+        let offsets = PostProcessingUniformBufferOffsets {
+            chromatic_aberration: buffers
+                .chromatic_aberration
+                .push(&chromatic_aberration.map(Into::into).unwrap_or_default()),
+            lens_flare: buffers
+                .lens_flare
+                .push(&lens_flare.map(Into::into).unwrap_or_default()),
+            film_grain: buffers.film_grain.push(
+                &film_grain
+                    .map(|film_grain| FilmGrainUniform::new(film_grain, frame_count.0))
+                    .unwrap_or_default(),
+            ),
+            vignette: buffers
+                .vignette
+                .push(&vignette.map(Into::into).unwrap_or_default()),
+        };
+        commands.entity(view_entity).insert(offsets);
     }
 
     // Upload to the GPU.
-    post_processing_uniform_buffers.write_buffer(&render_device, &render_queue);
+    buffers
+        .chromatic_aberration
+        .write_buffer(&render_device, &render_queue);
+    buffers
+        .lens_flare
+        .write_buffer(&render_device, &render_queue);
+    buffers
+        .film_grain
+        .write_buffer(&render_device, &render_queue);
+    buffers.vignette.write_buffer(&render_device, &render_queue);
 }
 
 impl ExtractComponent for ChromaticAberration {
@@ -507,3 +758,164 @@ impl ExtractComponent for ChromaticAberration {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy_color::{palettes::css::RED, Color, ColorToComponents as _, LinearRgba};
+    use bevy_math::Vec2;
+
+    use super::*;
+
+    fn effects_of(world: &mut World, entity: Entity) -> Option<PostProcessingEffects> {
+        world
+            .query_filtered::<PostProcessingEffectComponents, WithPostProcessing>()
+            .get(world, entity)
+            .ok()
+            .map(PostProcessingEffects::from_view)
+    }
+
+    #[test]
+    fn effects_follow_view_components() {
+        let mut world = World::new();
+        let none = world.spawn_empty().id();
+        let vignette = world.spawn(Vignette::default()).id();
+        let all = world
+            .spawn((
+                ChromaticAberration::default(),
+                LensFlare::default(),
+                FilmGrain::default(),
+                Vignette::default(),
+            ))
+            .id();
+
+        assert_eq!(effects_of(&mut world, none), None);
+        assert_eq!(
+            effects_of(&mut world, vignette),
+            Some(PostProcessingEffects::VIGNETTE)
+        );
+        assert_eq!(
+            effects_of(&mut world, all),
+            Some(PostProcessingEffects::all())
+        );
+    }
+
+    #[test]
+    fn views_without_chromatic_aberration_bind_default_lut() {
+        assert_eq!(
+            chromatic_aberration_lut(None),
+            &DEFAULT_CHROMATIC_ABERRATION_LUT_HANDLE
+        );
+
+        let chromatic_aberration = ChromaticAberration {
+            color_lut: weak_handle!("7b1f0c2e-5d64-4a8b-9e3f-0a2c4d6e8f10"),
+            ..default()
+        };
+        assert_eq!(
+            chromatic_aberration_lut(Some(&chromatic_aberration)),
+            &chromatic_aberration.color_lut
+        );
+    }
+
+    #[test]
+    fn shader_defs_follow_effects() {
+        let names = |effects: PostProcessingEffects| {
+            effects
+                .shader_defs()
+                .into_iter()
+                .map(|shader_def| match shader_def {
+                    ShaderDefVal::Bool(name, true) => name,
+                    shader_def => panic!("unexpected shader def {shader_def:?}"),
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert!(names(PostProcessingEffects::empty()).is_empty());
+        assert_eq!(
+            names(PostProcessingEffects::VIGNETTE | PostProcessingEffects::LENS_FLARE),
+            ["LENS_FLARE", "VIGNETTE"]
+        );
+
+        let shader = include_str!("post_process.wgsl");
+        let all = names(PostProcessingEffects::all());
+        assert_eq!(all.len(), 4);
+        for name in all {
+            assert!(
+                shader.contains(&format!("#ifdef {name}")),
+                "post_process.wgsl doesn't handle {name}"
+            );
+        }
+    }
+
+    #[test]
+    fn chromatic_aberration_uniform() {
+        let uniform = ChromaticAberrationUniform::from(&ChromaticAberration {
+            intensity: 0.25,
+            max_samples: 12,
+            ..default()
+        });
+        assert_eq!(uniform.intensity, 0.25);
+        assert_eq!(uniform.max_samples, 12);
+    }
+
+    #[test]
+    fn lens_flare_uniform() {
+        let lens_flare = LensFlare {
+            intensity: 0.5,
+            threshold: 2.0,
+            ghost_count: 6,
+            ghost_spacing: 0.3,
+            halo_radius: 0.4,
+            chromatic_distortion: 0.01,
+        };
+        let uniform = LensFlareUniform::from(&lens_flare);
+        assert_eq!(uniform.intensity, 0.5);
+        assert_eq!(uniform.threshold, 2.0);
+        assert_eq!(uniform.ghost_count, 6);
+        assert_eq!(uniform.ghost_spacing, 0.3);
+        assert_eq!(uniform.halo_radius, 0.4);
+        assert_eq!(uniform.chromatic_distortion, 0.01);
+    }
+
+    #[test]
+    fn film_grain_uniform_clamps_settings() {
+        let uniform = FilmGrainUniform::new(
+            &FilmGrain {
+                intensity: 0.5,
+                grain_size: 0.25,
+                luminance_response: 2.0,
+            },
+            7,
+        );
+        assert_eq!(uniform.intensity, 0.5);
+        assert_eq!(uniform.grain_size, 1.0);
+        assert_eq!(uniform.luminance_response, 1.0);
+        assert_eq!(uniform.frame, 7);
+
+        let uniform = FilmGrainUniform::new(
+            &FilmGrain {
+                luminance_response: -1.0,
+                ..default()
+            },
+            0,
+        );
+        assert_eq!(uniform.luminance_response, 0.0);
+    }
+
+    #[test]
+    fn vignette_uniform_clamps_settings() {
+        let uniform = VignetteUniform::from(&Vignette {
+            intensity: 2.0,
+            radius: 0.75,
+            smoothness: 0.0,
+            roundness: -1.0,
+            center: Vec2::new(0.25, 0.5),
+            color: Color::from(RED),
+        });
+        assert_eq!(uniform.color, LinearRgba::from(RED).to_vec4());
+        assert_eq!(uniform.center, Vec2::new(0.25, 0.5));
+        assert_eq!(uniform.intensity, 1.0);
+        assert_eq!(uniform.radius, 0.75);
+        assert_eq!(uniform.smoothness, f32::EPSILON);
+        assert_eq!(uniform.roundness, 0.0);
+    }
+}
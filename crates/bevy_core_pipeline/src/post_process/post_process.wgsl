@@ -1,9 +1,47 @@
-// Miscellaneous postprocessing effects, currently just chromatic aberration.
+// Miscellaneous postprocessing effects: chromatic aberration, lens flare,
+// vignette and film grain, applied in that order.
 
 #import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
-#import bevy_core_pipeline::post_processing::chromatic_aberration::chromatic_aberration
+#import bevy_core_pipeline::post_processing::chromatic_aberration::{
+    chromatic_aberration, chromatic_aberration_source_texture, chromatic_aberration_source_sampler
+}
+
+#ifdef LENS_FLARE
+#import bevy_core_pipeline::post_processing::lens_flare::lens_flare
+#endif
+#ifdef FILM_GRAIN
+#import bevy_core_pipeline::post_processing::film_grain::film_grain
+#endif
+#ifdef VIGNETTE
+#import bevy_core_pipeline::post_processing::vignette::vignette
+#endif
 
 @fragment
 fn fragment_main(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
-    return vec4(chromatic_aberration(in.uv), 1.0);
+#ifdef CHROMATIC_ABERRATION
+    var color = chromatic_aberration(in.uv);
+#endif
+#ifndef CHROMATIC_ABERRATION
+    var color = textureSampleLevel(
+        chromatic_aberration_source_texture,
+        chromatic_aberration_source_sampler,
+        in.uv,
+        0.0,
+    ).rgb;
+#endif
+
+#ifdef LENS_FLARE
+    color += lens_flare(in.uv);
+#endif
+
+#ifdef VIGNETTE
+    let screen_size = vec2<f32>(textureDimensions(chromatic_aberration_source_texture));
+    color = vignette(color, in.uv, screen_size);
+#endif
+
+#ifdef FILM_GRAIN
+    color = film_grain(color, in.position.xy);
+#endif
+
+    return vec4(color, 1.0);
 }
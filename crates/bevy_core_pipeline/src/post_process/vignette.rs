@@ -0,0 +1,123 @@
+use bevy_color::{Color, ColorToComponents as _, LinearRgba};
+use bevy_ecs::{
+    component::Component,
+    query::{QueryItem, With},
+    reflect::ReflectComponent,
+    system::lifetimeless::Read,
+};
+use bevy_math::{Vec2, Vec4};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    camera::Camera, extract_component::ExtractComponent, render_resource::ShaderType,
+};
+
+/// Darkens, or tints, the edges of the image, as the housing of a camera lens
+/// would.
+///
+/// The vignette is shaped by a circle or an ellipse around
+/// [`Vignette::center`]: pixels inside [`Vignette::radius`] are unaffected,
+/// and the effect ramps up over [`Vignette::smoothness`] beyond it.
+#[derive(Reflect, Component, Clone)]
+#[reflect(Component, Default)]
+pub struct Vignette {
+    /// How much of [`Vignette::color`] is blended into the edges of the image.
+    ///
+    /// The default value is 0.5.
+    pub intensity: f32,
+
+    /// The distance from the center, as a fraction of the screen height, at
+    /// which the vignette starts.
+    ///
+    /// The default value is 0.4.
+    pub radius: f32,
+
+    /// The distance, as a fraction of the screen height, over which the
+    /// vignette ramps up to its full intensity.
+    ///
+    /// The default value is 0.45.
+    pub smoothness: f32,
+
+    /// The shape of the vignette.
+    ///
+    /// At 1.0, the vignette is a circle. At 0.0, it's an ellipse stretched to
+    /// the aspect ratio of the screen.
+    ///
+    /// The default value is 1.0.
+    pub roundness: f32,
+
+    /// The center of the vignette, in normalized screen coordinates, where
+    /// (0.0, 0.0) is the top left of the screen and (1.0, 1.0) the bottom
+    /// right.
+    ///
+    /// The default value is (0.5, 0.5).
+    pub center: Vec2,
+
+    /// The color that the edges of the image fade to.
+    ///
+    /// The default value is black.
+    pub color: Color,
+}
+
+impl Default for Vignette {
+    fn default() -> Self {
+        Self {
+            intensity: 0.5,
+            radius: 0.4,
+            smoothness: 0.45,
+            roundness: 1.0,
+            center: Vec2::splat(0.5),
+            color: Color::BLACK,
+        }
+    }
+}
+
+/// The on-GPU version of the [`Vignette`] settings.
+///
+/// See the documentation for [`Vignette`] for more information on each of
+/// these fields.
+#[derive(ShaderType, Default)]
+pub struct VignetteUniform {
+    /// The color, in linear RGBA.
+    pub(super) color: Vec4,
+    pub(super) center: Vec2,
+    pub(super) intensity: f32,
+    pub(super) radius: f32,
+    pub(super) smoothness: f32,
+    pub(super) roundness: f32,
+    /// Padding data.
+    pub(super) unused_1: u32,
+    /// Padding data.
+    pub(super) unused_2: u32,
+}
+
+impl From<&Vignette> for VignetteUniform {
+    fn from(vignette: &Vignette) -> Self {
+        Self {
+            color: LinearRgba::from(vignette.color).to_vec4(),
+            center: vignette.center,
+            intensity: vignette.intensity.clamp(0.0, 1.0),
+            radius: vignette.radius,
+            smoothness: vignette.smoothness.max(f32::EPSILON),
+            roundness: vignette.roundness.clamp(0.0, 1.0),
+            unused_1: 0,
+            unused_2: 0,
+        }
+    }
+}
+
+impl ExtractComponent for Vignette {
+    type QueryData = Read<Vignette>;
+
+    type QueryFilter = With<Camera>;
+
+    type Out = Vignette;
+
+    fn extract_component(vignette: QueryItem<'_, Self::QueryData>) -> Option<Self::Out> {
+        // Skip the effect entirely if the intensity is zero.
+        if vignette.intensity > 0.0 {
+            Some(vignette.clone())
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,36 @@
+// The vignette postprocessing effect.
+//
+// This fades the edges of the image to a color.
+
+#define_import_path bevy_core_pipeline::post_processing::vignette
+
+// See `bevy_core_pipeline::post_process::Vignette` for more information on
+// these fields.
+struct VignetteSettings {
+    color: vec4<f32>,
+    center: vec2<f32>,
+    intensity: f32,
+    radius: f32,
+    smoothness: f32,
+    roundness: f32,
+    unused_a: u32,
+    unused_b: u32,
+}
+
+// The settings supplied by the developer.
+@group(0) @binding(9) var<uniform> vignette_settings: VignetteSettings;
+
+fn vignette(color: vec3<f32>, uv: vec2<f32>, screen_size: vec2<f32>) -> vec3<f32> {
+    // Measure distances in units of the screen height. A roundness of 0
+    // stretches the vignette to the aspect ratio of the screen instead.
+    let aspect_ratio = screen_size.x / screen_size.y;
+    var offset = uv - vignette_settings.center;
+    offset.x *= mix(1.0, aspect_ratio, vignette_settings.roundness);
+
+    let amount = smoothstep(
+        vignette_settings.radius,
+        vignette_settings.radius + vignette_settings.smoothness,
+        length(offset),
+    );
+    return mix(color, vignette_settings.color.rgb, amount * vignette_settings.intensity);
+}
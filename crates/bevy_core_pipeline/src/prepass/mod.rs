@@ -51,6 +51,7 @@ use bevy_render::{
 
 pub const NORMAL_PREPASS_FORMAT: TextureFormat = TextureFormat::Rgb10a2Unorm;
 pub const MOTION_VECTOR_PREPASS_FORMAT: TextureFormat = TextureFormat::Rg16Float;
+pub const OUTLINE_ID_PREPASS_FORMAT: TextureFormat = TextureFormat::R8Uint;
 
 /// If added to a [`crate::prelude::Camera3d`] then depth values will be copied to a separate texture available to the main pass.
 #[derive(Component, Default, Reflect, Clone)]
@@ -74,6 +75,13 @@ pub struct MotionVectorPrepass;
 #[reflect(Component, Default)]
 pub struct DeferredPrepass;
 
+/// If added to a [`crate::prelude::Camera3d`] then the outline style of each mesh with an
+/// [`Outline`](crate::outline::Outline) will be copied to a separate texture available to the
+/// outline pass.
+#[derive(Component, Default, Reflect, Clone)]
+#[reflect(Component, Default)]
+pub struct OutlineIdPrepass;
+
 #[derive(Component, ShaderType, Clone)]
 pub struct PreviousViewData {
     pub view_from_world: Mat4,
@@ -111,6 +119,9 @@ pub struct ViewPrepassTextures {
     /// A texture that specifies the deferred lighting pass id for a material.
     /// Exists only if [`DeferredPrepass`] is added to the `ViewTarget`
     pub deferred_lighting_pass_id: Option<ColorAttachment>,
+    /// The outline style of each pixel, or 0 for none.
+    /// Exists only if [`OutlineIdPrepass`] is added to the `ViewTarget`
+    pub outline_id: Option<ColorAttachment>,
     /// The size of the textures.
     pub size: Extent3d,
 }
@@ -133,6 +144,10 @@ impl ViewPrepassTextures {
     pub fn deferred_view(&self) -> Option<&TextureView> {
         self.deferred.as_ref().map(|t| &t.texture.default_view)
     }
+
+    pub fn outline_id_view(&self) -> Option<&TextureView> {
+        self.outline_id.as_ref().map(|t| &t.texture.default_view)
+    }
 }
 
 /// Opaque phase of the 3D prepass.
@@ -352,6 +367,7 @@ pub fn prepass_target_descriptors(
     normal_prepass: bool,
     motion_vector_prepass: bool,
     deferred_prepass: bool,
+    outline_id_prepass: bool,
 ) -> Vec<Option<ColorTargetState>> {
     vec![
         normal_prepass.then_some(ColorTargetState {
@@ -374,5 +390,10 @@ pub fn prepass_target_descriptors(
             blend: None,
             write_mask: ColorWrites::ALL,
         }),
+        outline_id_prepass.then_some(ColorTargetState {
+            format: OUTLINE_ID_PREPASS_FORMAT,
+            blend: None,
+            write_mask: ColorWrites::ALL,
+        }),
     ]
 }
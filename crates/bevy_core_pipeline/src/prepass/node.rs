@@ -142,6 +142,10 @@ fn run_prepass<'w>(
         // Use None in place of deferred attachments
         None,
         None,
+        view_prepass_textures
+            .outline_id
+            .as_ref()
+            .map(|outline_id_texture| outline_id_texture.get_attachment()),
     ];
 
     // If all color attachments are none: clear the color attachment list so that no fragment shader is required
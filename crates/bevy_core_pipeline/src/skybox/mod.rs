@@ -2,7 +2,7 @@ use bevy_app::{App, Plugin};
 use bevy_asset::{load_internal_asset, weak_handle, Handle};
 use bevy_ecs::{
     prelude::{Component, Entity},
-    query::{QueryItem, With},
+    query::{Has, QueryItem, With},
     reflect::ReflectComponent,
     resource::Resource,
     schedule::IntoSystemConfigs,
@@ -12,7 +12,7 @@ use bevy_image::{BevyDefault, Image};
 use bevy_math::{Mat4, Quat};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_render::{
-    camera::Exposure,
+    camera::{Exposure, Multiview},
     extract_component::{
         ComponentUniforms, DynamicUniformIndex, ExtractComponent, ExtractComponentPlugin,
         UniformComponentPlugin,
@@ -24,10 +24,14 @@ use bevy_render::{
     },
     renderer::RenderDevice,
     texture::GpuImage,
-    view::{ExtractedView, Msaa, ViewTarget, ViewUniform, ViewUniforms},
+    view::{
+        ExtractedView, Msaa, MultiviewUniform, MultiviewUniforms, ViewTarget, ViewUniform,
+        ViewUniforms,
+    },
     Render, RenderApp, RenderSet,
 };
 use bevy_transform::components::Transform;
+use core::num::NonZeroU32;
 use prepass::{SkyboxPrepassPipeline, SKYBOX_PREPASS_SHADER_HANDLE};
 
 use crate::{core_3d::CORE_3D_DEPTH_FORMAT, prepass::PreviousViewUniforms};
@@ -158,6 +162,9 @@ pub struct SkyboxUniforms {
 #[derive(Resource)]
 struct SkyboxPipeline {
     bind_group_layout: BindGroupLayout,
+    /// The layout for [`Multiview`] views, which binds the uniforms of both eyes instead of the
+    /// [`ViewUniform`].
+    multiview_bind_group_layout: BindGroupLayout,
 }
 
 impl SkyboxPipeline {
@@ -176,6 +183,18 @@ impl SkyboxPipeline {
                     ),
                 ),
             ),
+            multiview_bind_group_layout: render_device.create_bind_group_layout(
+                "skybox_multiview_bind_group_layout",
+                &BindGroupLayoutEntries::sequential(
+                    ShaderStages::FRAGMENT,
+                    (
+                        texture_cube(TextureSampleType::Float { filterable: true }),
+                        sampler(SamplerBindingType::Filtering),
+                        uniform_buffer::<MultiviewUniform>(true),
+                        uniform_buffer::<SkyboxUniforms>(true),
+                    ),
+                ),
+            ),
         }
     }
 }
@@ -185,19 +204,29 @@ struct SkyboxPipelineKey {
     hdr: bool,
     samples: u32,
     depth_format: TextureFormat,
+    /// The number of views rendered at once, for [`Multiview`] views.
+    multiview: Option<NonZeroU32>,
 }
 
 impl SpecializedRenderPipeline for SkyboxPipeline {
     type Key = SkyboxPipelineKey;
 
     fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let (layout, shader_defs) = match key.multiview {
+            Some(_) => (
+                self.multiview_bind_group_layout.clone(),
+                vec!["MULTIVIEW".into()],
+            ),
+            None => (self.bind_group_layout.clone(), Vec::new()),
+        };
+
         RenderPipelineDescriptor {
             label: Some("skybox_pipeline".into()),
-            layout: vec![self.bind_group_layout.clone()],
+            layout: vec![layout],
             push_constant_ranges: Vec::new(),
             vertex: VertexState {
                 shader: SKYBOX_SHADER_HANDLE,
-                shader_defs: Vec::new(),
+                shader_defs: shader_defs.clone(),
                 entry_point: "skybox_vertex".into(),
                 buffers: Vec::new(),
             },
@@ -225,7 +254,7 @@ impl SpecializedRenderPipeline for SkyboxPipeline {
             },
             fragment: Some(FragmentState {
                 shader: SKYBOX_SHADER_HANDLE,
-                shader_defs: Vec::new(),
+                shader_defs,
                 entry_point: "skybox_fragment".into(),
                 targets: vec![Some(ColorTargetState {
                     format: if key.hdr {
@@ -239,6 +268,7 @@ impl SpecializedRenderPipeline for SkyboxPipeline {
                 })],
             }),
             zero_initialize_workgroup_memory: false,
+            multiview: key.multiview,
         }
     }
 }
@@ -251,9 +281,9 @@ fn prepare_skybox_pipelines(
     pipeline_cache: Res<PipelineCache>,
     mut pipelines: ResMut<SpecializedRenderPipelines<SkyboxPipeline>>,
     pipeline: Res<SkyboxPipeline>,
-    views: Query<(Entity, &ExtractedView, &Msaa), With<Skybox>>,
+    views: Query<(Entity, &ExtractedView, &Msaa, Has<Multiview>), With<Skybox>>,
 ) {
-    for (entity, view, msaa) in &views {
+    for (entity, view, msaa, multiview) in &views {
         let pipeline_id = pipelines.specialize(
             &pipeline_cache,
             &pipeline,
@@ -261,6 +291,9 @@ fn prepare_skybox_pipelines(
                 hdr: view.hdr,
                 samples: msaa.samples(),
                 depth_format: CORE_3D_DEPTH_FORMAT,
+                multiview: multiview
+                    .then_some(Multiview::VIEW_COUNT)
+                    .and_then(NonZeroU32::new),
             },
         );
 
@@ -277,20 +310,39 @@ fn prepare_skybox_bind_groups(
     mut commands: Commands,
     pipeline: Res<SkyboxPipeline>,
     view_uniforms: Res<ViewUniforms>,
+    multiview_uniforms: Res<MultiviewUniforms>,
     skybox_uniforms: Res<ComponentUniforms<SkyboxUniforms>>,
     images: Res<RenderAssets<GpuImage>>,
     render_device: Res<RenderDevice>,
-    views: Query<(Entity, &Skybox, &DynamicUniformIndex<SkyboxUniforms>)>,
+    views: Query<(
+        Entity,
+        &Skybox,
+        &DynamicUniformIndex<SkyboxUniforms>,
+        Has<Multiview>,
+    )>,
 ) {
-    for (entity, skybox, skybox_uniform_index) in &views {
+    for (entity, skybox, skybox_uniform_index, multiview) in &views {
+        // Multiview views bind the uniforms of both eyes in place of their own view uniform.
+        let (layout, view_uniforms) = if multiview {
+            (
+                &pipeline.multiview_bind_group_layout,
+                multiview_uniforms.uniforms.binding(),
+            )
+        } else {
+            (
+                &pipeline.bind_group_layout,
+                view_uniforms.uniforms.binding(),
+            )
+        };
+
         if let (Some(skybox), Some(view_uniforms), Some(skybox_uniforms)) = (
             images.get(&skybox.image),
-            view_uniforms.uniforms.binding(),
+            view_uniforms,
             skybox_uniforms.binding(),
         ) {
             let bind_group = render_device.create_bind_group(
                 "skybox_bind_group",
-                &pipeline.bind_group_layout,
+                layout,
                 &BindGroupEntries::sequential((
                     &skybox.texture_view,
                     &skybox.sampler,
@@ -12,9 +12,10 @@ use bevy_ecs::{
 use bevy_render::{
     render_resource::{
         binding_types::uniform_buffer, BindGroup, BindGroupEntries, BindGroupLayout,
-        BindGroupLayoutEntries, CachedRenderPipelineId, CompareFunction, DepthStencilState,
-        FragmentState, MultisampleState, PipelineCache, RenderPipelineDescriptor, Shader,
-        ShaderStages, SpecializedRenderPipeline, SpecializedRenderPipelines,
+        BindGroupLayoutEntries, CachedRenderPipelineId, ColorWrites, CompareFunction,
+        DepthStencilState, FragmentState, MultisampleState, PipelineCache,
+        RenderPipelineDescriptor, Shader, ShaderStages, SpecializedRenderPipeline,
+        SpecializedRenderPipelines,
     },
     renderer::RenderDevice,
     view::{Msaa, ViewUniform, ViewUniforms},
@@ -24,8 +25,8 @@ use bevy_utils::prelude::default;
 use crate::{
     core_3d::CORE_3D_DEPTH_FORMAT,
     prepass::{
-        prepass_target_descriptors, MotionVectorPrepass, NormalPrepass, PreviousViewData,
-        PreviousViewUniforms,
+        prepass_target_descriptors, MotionVectorPrepass, NormalPrepass, OutlineIdPrepass,
+        PreviousViewData, PreviousViewUniforms,
     },
     Skybox,
 };
@@ -48,6 +49,7 @@ pub struct SkyboxPrepassPipeline {
 pub struct SkyboxPrepassPipelineKey {
     samples: u32,
     normal_prepass: bool,
+    outline_id_prepass: bool,
 }
 
 /// Stores the ID for a camera's specialized pipeline, so it can be retrieved from the
@@ -83,6 +85,13 @@ impl SpecializedRenderPipeline for SkyboxPrepassPipeline {
     type Key = SkyboxPrepassPipelineKey;
 
     fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let mut targets =
+            prepass_target_descriptors(key.normal_prepass, true, false, key.outline_id_prepass);
+        // The skybox has no outline, so leave the outline IDs cleared.
+        if let Some(Some(outline_id_target)) = targets.get_mut(4) {
+            outline_id_target.write_mask = ColorWrites::empty();
+        }
+
         RenderPipelineDescriptor {
             label: Some("skybox_prepass_pipeline".into()),
             layout: vec![self.bind_group_layout.clone()],
@@ -105,9 +114,10 @@ impl SpecializedRenderPipeline for SkyboxPrepassPipeline {
                 shader: SKYBOX_PREPASS_SHADER_HANDLE,
                 shader_defs: vec![],
                 entry_point: "fragment".into(),
-                targets: prepass_target_descriptors(key.normal_prepass, true, false),
+                targets,
             }),
             zero_initialize_workgroup_memory: false,
+            multiview: None,
         }
     }
 }
@@ -118,12 +128,16 @@ pub fn prepare_skybox_prepass_pipelines(
     pipeline_cache: Res<PipelineCache>,
     mut pipelines: ResMut<SpecializedRenderPipelines<SkyboxPrepassPipeline>>,
     pipeline: Res<SkyboxPrepassPipeline>,
-    views: Query<(Entity, Has<NormalPrepass>, &Msaa), (With<Skybox>, With<MotionVectorPrepass>)>,
+    views: Query<
+        (Entity, Has<NormalPrepass>, Has<OutlineIdPrepass>, &Msaa),
+        (With<Skybox>, With<MotionVectorPrepass>),
+    >,
 ) {
-    for (entity, normal_prepass, msaa) in &views {
+    for (entity, normal_prepass, outline_id_prepass, msaa) in &views {
         let pipeline_key = SkyboxPrepassPipelineKey {
             samples: msaa.samples(),
             normal_prepass,
+            outline_id_prepass,
         };
 
         let render_skybox_prepass_pipeline =
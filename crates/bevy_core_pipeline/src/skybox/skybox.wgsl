@@ -1,4 +1,4 @@
-#import bevy_render::view::View
+#import bevy_render::view::{View, Multiview}
 #import bevy_pbr::utils::coords_to_viewport_uv
 
 struct SkyboxUniforms {
@@ -13,7 +13,13 @@ struct SkyboxUniforms {
 
 @group(0) @binding(0) var skybox: texture_cube<f32>;
 @group(0) @binding(1) var skybox_sampler: sampler;
+#ifdef MULTIVIEW
+// The view of the eye being rendered, set by `skybox_fragment`.
+var<private> view: View;
+@group(0) @binding(2) var<uniform> multiview: Multiview;
+#else   // MULTIVIEW
 @group(0) @binding(2) var<uniform> view: View;
+#endif  // MULTIVIEW
 @group(0) @binding(3) var<uniform> uniforms: SkyboxUniforms;
 
 fn coords_to_ray_direction(position: vec2<f32>, viewport: vec4<f32>) -> vec3<f32> {
@@ -72,7 +78,16 @@ fn skybox_vertex(@builtin(vertex_index) vertex_index: u32) -> VertexOutput {
 }
 
 @fragment
-fn skybox_fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+fn skybox_fragment(
+    in: VertexOutput,
+#ifdef MULTIVIEW
+    @builtin(view_index) view_index: i32,
+#endif
+) -> @location(0) vec4<f32> {
+#ifdef MULTIVIEW
+    view = multiview.views[view_index];
+#endif
+
     let ray_direction = coords_to_ray_direction(in.position.xy, view.viewport);
 
     // Cube maps are left-handed so we negate the z coordinate.
@@ -43,7 +43,7 @@ use bevy_derive::{Deref, DerefMut};
 use bevy_ecs::{
     component::Component,
     entity::Entity,
-    query::{QueryItem, With},
+    query::{Has, QueryItem, With},
     reflect::ReflectComponent,
     resource::Resource,
     schedule::IntoSystemConfigs as _,
@@ -54,7 +54,7 @@ use bevy_image::{BevyDefault, Image};
 use bevy_math::{vec4, Vec4};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_render::{
-    camera::ExtractedCamera,
+    camera::{ExtractedCamera, Multiview},
     extract_component::{ExtractComponent, ExtractComponentPlugin},
     render_asset::RenderAssets,
     render_graph::{
@@ -78,7 +78,8 @@ use bevy_render::{
     view::{ExtractedView, ViewTarget},
     Render, RenderApp, RenderSet,
 };
-use bevy_utils::prelude::default;
+use bevy_utils::{once, prelude::default};
+use tracing::warn;
 
 /// The handle of the `smaa.wgsl` shader.
 const SMAA_SHADER_HANDLE: Handle<Shader> = weak_handle!("fdd9839f-1ab4-4e0d-88a0-240b67da2ddf");
@@ -507,6 +508,7 @@ impl SpecializedRenderPipeline for SmaaEdgeDetectionPipeline {
             }),
             multisample: MultisampleState::default(),
             zero_initialize_workgroup_memory: false,
+            multiview: None,
         }
     }
 }
@@ -567,6 +569,7 @@ impl SpecializedRenderPipeline for SmaaBlendingWeightCalculationPipeline {
             }),
             multisample: MultisampleState::default(),
             zero_initialize_workgroup_memory: false,
+            multiview: None,
         }
     }
 }
@@ -604,6 +607,7 @@ impl SpecializedRenderPipeline for SmaaNeighborhoodBlendingPipeline {
             depth_stencil: None,
             multisample: MultisampleState::default(),
             zero_initialize_workgroup_memory: false,
+            multiview: None,
         }
     }
 }
@@ -830,6 +834,7 @@ impl ViewNode for SmaaNode {
         Read<SmaaInfoUniformOffset>,
         Read<SmaaTextures>,
         Read<SmaaBindGroups>,
+        Has<Multiview>,
     );
 
     fn run<'w>(
@@ -842,9 +847,17 @@ impl ViewNode for SmaaNode {
             view_smaa_uniform_offset,
             smaa_textures,
             view_smaa_bind_groups,
+            multiview,
         ): QueryItem<'w, Self::ViewQuery>,
         world: &'w World,
     ) -> Result<(), NodeRunError> {
+        if multiview {
+            once!(warn!(
+                "SMAA isn't supported on `Multiview` cameras and is skipped"
+            ));
+            return Ok(());
+        }
+
         let pipeline_cache = world.resource::<PipelineCache>();
         let smaa_pipelines = world.resource::<SmaaPipelines>();
         let smaa_info_uniform_buffer = world.resource::<SmaaInfoUniformBuffer>();
@@ -9,7 +9,7 @@ use bevy_asset::{load_internal_asset, weak_handle, Handle};
 use bevy_diagnostic::FrameCount;
 use bevy_ecs::{
     prelude::{require, Component, Entity, ReflectComponent},
-    query::{QueryItem, With},
+    query::{Has, QueryItem, With},
     resource::Resource,
     schedule::IntoSystemConfigs,
     system::{Commands, Query, Res, ResMut},
@@ -19,7 +19,7 @@ use bevy_image::BevyDefault as _;
 use bevy_math::vec2;
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_render::{
-    camera::{ExtractedCamera, MipBias, TemporalJitter},
+    camera::{ExtractedCamera, MipBias, Multiview, TemporalJitter},
     prelude::{Camera, Projection},
     render_graph::{NodeRunError, RenderGraphApp, RenderGraphContext, ViewNode, ViewNodeRunner},
     render_resource::{
@@ -38,6 +38,7 @@ use bevy_render::{
     view::{ExtractedView, Msaa, ViewTarget},
     ExtractSchedule, MainWorld, Render, RenderApp, RenderSet,
 };
+use bevy_utils::once;
 use tracing::warn;
 
 const TAA_SHADER_HANDLE: Handle<Shader> = weak_handle!("fea20d50-86b6-4069-aa32-374346aec00c");
@@ -163,17 +164,31 @@ impl ViewNode for TemporalAntiAliasNode {
         &'static ViewPrepassTextures,
         &'static TemporalAntiAliasPipelineId,
         &'static Msaa,
+        Has<Multiview>,
     );
 
     fn run(
         &self,
         _graph: &mut RenderGraphContext,
         render_context: &mut RenderContext,
-        (camera, view_target, taa_history_textures, prepass_textures, taa_pipeline_id, msaa): QueryItem<
-            Self::ViewQuery,
-        >,
+        (
+            camera,
+            view_target,
+            taa_history_textures,
+            prepass_textures,
+            taa_pipeline_id,
+            msaa,
+            multiview,
+        ): QueryItem<Self::ViewQuery>,
         world: &World,
     ) -> Result<(), NodeRunError> {
+        if multiview {
+            once!(warn!(
+                "Temporal anti-aliasing isn't supported on `Multiview` cameras and is skipped"
+            ));
+            return Ok(());
+        }
+
         if *msaa != Msaa::Off {
             warn!("Temporal anti-aliasing requires MSAA to be disabled");
             return Ok(());
@@ -340,6 +355,7 @@ impl SpecializedRenderPipeline for TaaPipeline {
             multisample: MultisampleState::default(),
             push_constant_ranges: Vec::new(),
             zero_initialize_workgroup_memory: false,
+            multiview: None,
         }
     }
 }
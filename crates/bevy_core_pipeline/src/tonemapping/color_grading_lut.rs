@@ -0,0 +1,196 @@
+use bevy_asset::Handle;
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::prelude::*;
+use bevy_image::Image;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    camera::Camera,
+    extract_component::ExtractComponent,
+    render_asset::RenderAssets,
+    render_resource::{
+        binding_types::{sampler, texture_3d, uniform_buffer},
+        *,
+    },
+    renderer::{RenderDevice, RenderQueue},
+    texture::GpuImage,
+    view::ViewTarget,
+};
+
+use super::TonemappingPipeline;
+
+/// Applies a 3D color lookup table, such as one loaded from a `.cube` file, to
+/// the output of a [`Camera`] in the tonemapping pass.
+///
+/// Two lookup tables can be blended together, for example to transition
+/// between the looks of two zones of a level.
+///
+/// The tonemapping pass only runs on cameras with [`Camera::hdr`] enabled, so
+/// the lookup table has no effect on other cameras. It's applied even when
+/// [`Tonemapping::None`](super::Tonemapping::None) is used.
+#[derive(Component, Clone, Debug, Reflect, ExtractComponent)]
+#[extract_component_filter(With<Camera>)]
+#[reflect(Component, Default, Debug)]
+pub struct ColorGradingLut {
+    /// The lookup table to apply.
+    pub lut: Handle<Image>,
+    /// A second lookup table to blend towards, if any.
+    pub blend_lut: Option<Handle<Image>>,
+    /// How much of [`Self::blend_lut`] to use, from 0.0 for none of it to 1.0
+    /// for only it.
+    pub blend: f32,
+    /// Where the lookup table is applied in the tonemapping pass, which
+    /// determines the colors it expects.
+    pub placement: ColorGradingLutPlacement,
+}
+
+impl Default for ColorGradingLut {
+    fn default() -> Self {
+        Self {
+            lut: Handle::default(),
+            blend_lut: None,
+            blend: 0.0,
+            placement: ColorGradingLutPlacement::default(),
+        }
+    }
+}
+
+/// Where a [`ColorGradingLut`] is applied relative to the tonemapper.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Default, Debug, PartialEq, Hash)]
+pub enum ColorGradingLutPlacement {
+    /// The lookup table is applied after [`ColorGrading`] and before the
+    /// tonemapper, to scene colors.
+    ///
+    /// The lookup table is indexed by the log2 encoding of the color, from 10
+    /// stops below to 6.5 stops above middle grey (0.18), and its output is
+    /// decoded the same way.
+    ///
+    /// [`ColorGrading`]: bevy_render::view::ColorGrading
+    BeforeTonemapping,
+    /// The lookup table is applied to the output of the tonemapper.
+    ///
+    /// The lookup table is indexed by the sRGB encoding of the color, and its
+    /// output is decoded the same way. This matches the display-referred
+    /// lookup tables usually exported by grading software.
+    #[default]
+    AfterTonemapping,
+}
+
+/// The on-GPU settings of a [`ColorGradingLut`].
+#[derive(ShaderType)]
+pub struct ColorGradingLutUniform {
+    /// How much of the second lookup table to use.
+    pub blend: f32,
+    /// Padding data.
+    pub unused_1: u32,
+    /// Padding data.
+    pub unused_2: u32,
+    /// Padding data.
+    pub unused_3: u32,
+}
+
+/// A resource, part of the render world, that stores the
+/// [`ColorGradingLutUniform`]s of each view.
+#[derive(Resource, Deref, DerefMut, Default)]
+pub struct ColorGradingLutUniformBuffer(DynamicUniformBuffer<ColorGradingLutUniform>);
+
+/// A component, part of the render world, on views whose [`ColorGradingLut`]
+/// is loaded and ready to be applied.
+#[derive(Component)]
+pub struct ViewColorGradingLut {
+    pub bind_group: BindGroup,
+    pub uniform_offset: u32,
+}
+
+pub(super) fn color_grading_lut_bind_group_layout(render_device: &RenderDevice) -> BindGroupLayout {
+    render_device.create_bind_group_layout(
+        "color_grading_lut_bind_group_layout",
+        &BindGroupLayoutEntries::sequential(
+            ShaderStages::FRAGMENT,
+            (
+                texture_3d(TextureSampleType::Float { filterable: true }),
+                texture_3d(TextureSampleType::Float { filterable: true }),
+                sampler(SamplerBindingType::Filtering),
+                uniform_buffer::<ColorGradingLutUniform>(true),
+            ),
+        ),
+    )
+}
+
+/// Returns the images of a [`ColorGradingLut`] once all of them are loaded.
+///
+/// Without a [`ColorGradingLut::blend_lut`], the first image is returned
+/// twice.
+pub(super) fn color_grading_lut_images<'a>(
+    color_grading_lut: &ColorGradingLut,
+    images: &'a RenderAssets<GpuImage>,
+) -> Option<(&'a GpuImage, &'a GpuImage)> {
+    let lut = images.get(&color_grading_lut.lut)?;
+    let blend_lut = match &color_grading_lut.blend_lut {
+        Some(blend_lut) => images.get(blend_lut)?,
+        None => lut,
+    };
+    Some((lut, blend_lut))
+}
+
+/// Uploads the settings of every view's [`ColorGradingLut`] and creates the
+/// bind groups of those whose lookup tables are loaded.
+pub fn prepare_color_grading_luts(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    images: Res<RenderAssets<GpuImage>>,
+    tonemapping_pipeline: Res<TonemappingPipeline>,
+    mut uniform_buffer: ResMut<ColorGradingLutUniformBuffer>,
+    views: Query<(Entity, Option<&ColorGradingLut>, Has<ViewColorGradingLut>), With<ViewTarget>>,
+) {
+    uniform_buffer.clear();
+
+    let mut ready_views = vec![];
+    for (view_entity, color_grading_lut, has_view_color_grading_lut) in &views {
+        let Some((color_grading_lut, (lut, blend_lut))) =
+            color_grading_lut.and_then(|color_grading_lut| {
+                Some((
+                    color_grading_lut,
+                    color_grading_lut_images(color_grading_lut, &images)?,
+                ))
+            })
+        else {
+            if has_view_color_grading_lut {
+                commands.entity(view_entity).remove::<ViewColorGradingLut>();
+            }
+            continue;
+        };
+
+        let uniform_offset = uniform_buffer.push(&ColorGradingLutUniform {
+            blend: color_grading_lut.blend.clamp(0.0, 1.0),
+            unused_1: 0,
+            unused_2: 0,
+            unused_3: 0,
+        });
+        ready_views.push((view_entity, lut, blend_lut, uniform_offset));
+    }
+
+    // Upload to the GPU.
+    uniform_buffer.write_buffer(&render_device, &render_queue);
+
+    let Some(uniform_binding) = uniform_buffer.binding() else {
+        return;
+    };
+    for (view_entity, lut, blend_lut, uniform_offset) in ready_views {
+        let bind_group = render_device.create_bind_group(
+            "color_grading_lut_bind_group",
+            &tonemapping_pipeline.color_grading_lut_bind_group,
+            &BindGroupEntries::sequential((
+                &lut.texture_view,
+                &blend_lut.texture_view,
+                &lut.sampler,
+                uniform_binding.clone(),
+            )),
+        );
+        commands.entity(view_entity).insert(ViewColorGradingLut {
+            bind_group,
+            uniform_offset,
+        });
+    }
+}
@@ -0,0 +1,63 @@
+#define_import_path bevy_core_pipeline::color_grading_lut
+
+struct ColorGradingLutUniform {
+    blend: f32,
+    unused_a: u32,
+    unused_b: u32,
+    unused_c: u32,
+}
+
+@group(1) @binding(0) var color_grading_lut_texture: texture_3d<f32>;
+@group(1) @binding(1) var color_grading_blend_lut_texture: texture_3d<f32>;
+@group(1) @binding(2) var color_grading_lut_sampler: sampler;
+@group(1) @binding(3) var<uniform> color_grading_lut_settings: ColorGradingLutUniform;
+
+// The range, in stops around middle grey, of the log2 encoding used to look up
+// scene colors before tonemapping.
+const LOG2_MIN_EV: f32 = -10.0;
+const LOG2_MAX_EV: f32 = 6.5;
+const MIDDLE_GREY: f32 = 0.18;
+
+fn sample_lut(lut: texture_3d<f32>, coords: vec3<f32>) -> vec3<f32> {
+    // Sample at the centers of the first and last texels for inputs of 0 and 1.
+    let size = vec3<f32>(textureDimensions(lut));
+    let uvw = saturate(coords) * ((size - 1.0) / size) + 0.5 / size;
+    return textureSampleLevel(lut, color_grading_lut_sampler, uvw, 0.0).rgb;
+}
+
+// Looks up the (possibly blended) lookup tables with coordinates in 0 to 1.
+fn lookup_color_grading_lut(coords: vec3<f32>) -> vec3<f32> {
+    let graded = sample_lut(color_grading_lut_texture, coords);
+#ifdef COLOR_GRADING_LUT_BLEND
+    let blend_graded = sample_lut(color_grading_blend_lut_texture, coords);
+    return mix(graded, blend_graded, color_grading_lut_settings.blend);
+#endif
+#ifndef COLOR_GRADING_LUT_BLEND
+    return graded;
+#endif
+}
+
+// Applies the lookup table to a scene-referred linear color, through a log2
+// encoding.
+fn apply_color_grading_lut_scene(color: vec3<f32>) -> vec3<f32> {
+    let log_color = log2(max(color, vec3(1e-6)) / MIDDLE_GREY);
+    let coords = (log_color - LOG2_MIN_EV) / (LOG2_MAX_EV - LOG2_MIN_EV);
+    let graded = lookup_color_grading_lut(coords);
+    return exp2(graded * (LOG2_MAX_EV - LOG2_MIN_EV) + LOG2_MIN_EV) * MIDDLE_GREY;
+}
+
+fn srgb_encode(color: vec3<f32>) -> vec3<f32> {
+    let c = saturate(color);
+    return select(1.055 * pow(c, vec3(1.0 / 2.4)) - 0.055, c * 12.92, c <= vec3(0.0031308));
+}
+
+fn srgb_decode(color: vec3<f32>) -> vec3<f32> {
+    let c = saturate(color);
+    return select(pow((c + 0.055) / 1.055, vec3(2.4)), c / 12.92, c <= vec3(0.04045));
+}
+
+// Applies the lookup table to a display-referred linear color, through the
+// sRGB transfer function.
+fn apply_color_grading_lut_display(color: vec3<f32>) -> vec3<f32> {
+    return srgb_decode(lookup_color_grading_lut(srgb_encode(color)));
+}
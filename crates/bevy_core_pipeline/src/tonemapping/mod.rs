@@ -5,12 +5,12 @@ use bevy_ecs::prelude::*;
 use bevy_image::{CompressedImageFormats, Image, ImageSampler, ImageType};
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_render::{
-    camera::Camera,
+    camera::{Camera, Multiview},
     extract_component::{ExtractComponent, ExtractComponentPlugin},
     extract_resource::{ExtractResource, ExtractResourcePlugin},
     render_asset::{RenderAssetUsages, RenderAssets},
     render_resource::{
-        binding_types::{sampler, texture_2d, texture_3d, uniform_buffer},
+        binding_types::{sampler, texture_2d, texture_2d_array, texture_3d, uniform_buffer},
         *,
     },
     renderer::RenderDevice,
@@ -19,12 +19,15 @@ use bevy_render::{
     Render, RenderApp, RenderSet,
 };
 use bitflags::bitflags;
+use core::num::NonZeroU32;
 #[cfg(not(feature = "tonemapping_luts"))]
 use tracing::error;
 
+mod color_grading_lut;
 mod node;
 
 use bevy_utils::default;
+pub use color_grading_lut::*;
 pub use node::TonemappingNode;
 
 const TONEMAPPING_SHADER_HANDLE: Handle<Shader> =
@@ -36,6 +39,9 @@ const TONEMAPPING_SHARED_SHADER_HANDLE: Handle<Shader> =
 const TONEMAPPING_LUT_BINDINGS_SHADER_HANDLE: Handle<Shader> =
     weak_handle!("d50e3a70-c85e-4725-a81e-72fc83281145");
 
+const COLOR_GRADING_LUT_SHADER_HANDLE: Handle<Shader> =
+    weak_handle!("7b1e4c92-3a6d-4f08-b5c3-9e2a8d61f0c4");
+
 /// 3D LUT (look up table) textures used for tonemapping
 #[derive(Resource, Clone, ExtractResource)]
 pub struct TonemappingLuts {
@@ -66,6 +72,12 @@ impl Plugin for TonemappingPlugin {
             "lut_bindings.wgsl",
             Shader::from_wgsl
         );
+        load_internal_asset!(
+            app,
+            COLOR_GRADING_LUT_SHADER_HANDLE,
+            "color_grading_lut.wgsl",
+            Shader::from_wgsl
+        );
 
         if !app.world().is_resource_added::<TonemappingLuts>() {
             let mut images = app.world_mut().resource_mut::<Assets<Image>>();
@@ -105,10 +117,12 @@ impl Plugin for TonemappingPlugin {
 
         app.register_type::<Tonemapping>();
         app.register_type::<DebandDither>();
+        app.register_type::<ColorGradingLut>();
 
         app.add_plugins((
             ExtractComponentPlugin::<Tonemapping>::default(),
             ExtractComponentPlugin::<DebandDither>::default(),
+            ExtractComponentPlugin::<ColorGradingLut>::default(),
         ));
 
         let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
@@ -116,9 +130,13 @@ impl Plugin for TonemappingPlugin {
         };
         render_app
             .init_resource::<SpecializedRenderPipelines<TonemappingPipeline>>()
+            .init_resource::<ColorGradingLutUniformBuffer>()
             .add_systems(
                 Render,
-                prepare_view_tonemapping_pipelines.in_set(RenderSet::Prepare),
+                (
+                    prepare_view_tonemapping_pipelines.in_set(RenderSet::Prepare),
+                    prepare_color_grading_luts.in_set(RenderSet::PrepareBindGroups),
+                ),
             );
     }
 
@@ -133,6 +151,8 @@ impl Plugin for TonemappingPlugin {
 #[derive(Resource)]
 pub struct TonemappingPipeline {
     texture_bind_group: BindGroupLayout,
+    multiview_texture_bind_group: BindGroupLayout,
+    color_grading_lut_bind_group: BindGroupLayout,
     sampler: Sampler,
 }
 
@@ -207,6 +227,14 @@ bitflags! {
         /// Saturation/contrast/gamma/gain/lift for one or more sections
         /// (shadows, midtones, highlights) need to be adjusted.
         const SECTIONAL_COLOR_GRADING   = 0x04;
+        /// A [`ColorGradingLut`] is applied before the tonemapper.
+        const COLOR_GRADING_LUT_BEFORE_TONEMAPPING = 0x08;
+        /// A [`ColorGradingLut`] is applied after the tonemapper.
+        const COLOR_GRADING_LUT_AFTER_TONEMAPPING = 0x10;
+        /// The [`ColorGradingLut`] blends between two lookup tables.
+        const COLOR_GRADING_LUT_BLEND = 0x20;
+        /// The view renders both eyes of a [`Multiview`] camera at once.
+        const MULTIVIEW = 0x40;
     }
 }
 
@@ -253,6 +281,41 @@ impl SpecializedRenderPipeline for TonemappingPipeline {
             shader_defs.push("SECTIONAL_COLOR_GRADING".into());
         }
 
+        let multiview = key.flags.contains(TonemappingPipelineKeyFlags::MULTIVIEW);
+        if multiview {
+            shader_defs.push("MULTIVIEW".into());
+        }
+
+        let mut layout = vec![if multiview {
+            self.multiview_texture_bind_group.clone()
+        } else {
+            self.texture_bind_group.clone()
+        }];
+        if key
+            .flags
+            .contains(TonemappingPipelineKeyFlags::COLOR_GRADING_LUT_BEFORE_TONEMAPPING)
+        {
+            shader_defs.push("COLOR_GRADING_LUT_BEFORE_TONEMAPPING".into());
+        }
+        if key
+            .flags
+            .contains(TonemappingPipelineKeyFlags::COLOR_GRADING_LUT_AFTER_TONEMAPPING)
+        {
+            shader_defs.push("COLOR_GRADING_LUT_AFTER_TONEMAPPING".into());
+        }
+        if key.flags.intersects(
+            TonemappingPipelineKeyFlags::COLOR_GRADING_LUT_BEFORE_TONEMAPPING
+                | TonemappingPipelineKeyFlags::COLOR_GRADING_LUT_AFTER_TONEMAPPING,
+        ) {
+            layout.push(self.color_grading_lut_bind_group.clone());
+        }
+        if key
+            .flags
+            .contains(TonemappingPipelineKeyFlags::COLOR_GRADING_LUT_BLEND)
+        {
+            shader_defs.push("COLOR_GRADING_LUT_BLEND".into());
+        }
+
         match key.tonemapping {
             Tonemapping::None => shader_defs.push("TONEMAP_METHOD_NONE".into()),
             Tonemapping::Reinhard => shader_defs.push("TONEMAP_METHOD_REINHARD".into()),
@@ -293,7 +356,7 @@ impl SpecializedRenderPipeline for TonemappingPipeline {
         }
         RenderPipelineDescriptor {
             label: Some("tonemapping pipeline".into()),
-            layout: vec![self.texture_bind_group.clone()],
+            layout,
             vertex: fullscreen_shader_vertex_state(),
             fragment: Some(FragmentState {
                 shader: TONEMAPPING_SHADER_HANDLE,
@@ -310,35 +373,48 @@ impl SpecializedRenderPipeline for TonemappingPipeline {
             multisample: MultisampleState::default(),
             push_constant_ranges: Vec::new(),
             zero_initialize_workgroup_memory: false,
+            multiview: multiview
+                .then_some(Multiview::VIEW_COUNT)
+                .and_then(NonZeroU32::new),
         }
     }
 }
 
 impl FromWorld for TonemappingPipeline {
     fn from_world(render_world: &mut World) -> Self {
-        let mut entries = DynamicBindGroupLayoutEntries::new_with_indices(
-            ShaderStages::FRAGMENT,
-            (
-                (0, uniform_buffer::<ViewUniform>(true)),
+        let lut_layout_entries = get_lut_bind_group_layout_entries();
+        let entries = |hdr_texture| {
+            DynamicBindGroupLayoutEntries::new_with_indices(
+                ShaderStages::FRAGMENT,
                 (
-                    1,
-                    texture_2d(TextureSampleType::Float { filterable: false }),
+                    (0, uniform_buffer::<ViewUniform>(true)),
+                    (1, hdr_texture),
+                    (2, sampler(SamplerBindingType::NonFiltering)),
                 ),
-                (2, sampler(SamplerBindingType::NonFiltering)),
-            ),
-        );
-        let lut_layout_entries = get_lut_bind_group_layout_entries();
-        entries =
-            entries.extend_with_indices(((3, lut_layout_entries[0]), (4, lut_layout_entries[1])));
+            )
+            .extend_with_indices(((3, lut_layout_entries[0]), (4, lut_layout_entries[1])))
+        };
 
         let render_device = render_world.resource::<RenderDevice>();
-        let tonemap_texture_bind_group = render_device
-            .create_bind_group_layout("tonemapping_hdr_texture_bind_group_layout", &entries);
+        let tonemap_texture_bind_group = render_device.create_bind_group_layout(
+            "tonemapping_hdr_texture_bind_group_layout",
+            &entries(texture_2d(TextureSampleType::Float { filterable: false })),
+        );
+        let multiview_texture_bind_group = render_device.create_bind_group_layout(
+            "tonemapping_multiview_hdr_texture_bind_group_layout",
+            &entries(texture_2d_array(TextureSampleType::Float {
+                filterable: false,
+            })),
+        );
+
+        let color_grading_lut_bind_group = color_grading_lut_bind_group_layout(render_device);
 
         let sampler = render_device.create_sampler(&SamplerDescriptor::default());
 
         TonemappingPipeline {
             texture_bind_group: tonemap_texture_bind_group,
+            multiview_texture_bind_group,
+            color_grading_lut_bind_group,
             sampler,
         }
     }
@@ -352,17 +428,20 @@ pub fn prepare_view_tonemapping_pipelines(
     pipeline_cache: Res<PipelineCache>,
     mut pipelines: ResMut<SpecializedRenderPipelines<TonemappingPipeline>>,
     upscaling_pipeline: Res<TonemappingPipeline>,
+    images: Res<RenderAssets<GpuImage>>,
     view_targets: Query<
         (
             Entity,
             &ExtractedView,
             Option<&Tonemapping>,
             Option<&DebandDither>,
+            Option<&ColorGradingLut>,
+            Has<Multiview>,
         ),
         With<ViewTarget>,
     >,
 ) {
-    for (entity, view, tonemapping, dither) in view_targets.iter() {
+    for (entity, view, tonemapping, dither, color_grading_lut, multiview) in view_targets.iter() {
         // As an optimization, we omit parts of the shader that are unneeded.
         let mut flags = TonemappingPipelineKeyFlags::empty();
         flags.set(
@@ -379,6 +458,25 @@ pub fn prepare_view_tonemapping_pipelines(
                 .all_sections()
                 .any(|section| *section != default()),
         );
+        flags.set(TonemappingPipelineKeyFlags::MULTIVIEW, multiview);
+
+        // Lookup tables are only applied once they're loaded.
+        if let Some(color_grading_lut) = color_grading_lut.filter(|color_grading_lut| {
+            color_grading_lut_images(color_grading_lut, &images).is_some()
+        }) {
+            flags.insert(match color_grading_lut.placement {
+                ColorGradingLutPlacement::BeforeTonemapping => {
+                    TonemappingPipelineKeyFlags::COLOR_GRADING_LUT_BEFORE_TONEMAPPING
+                }
+                ColorGradingLutPlacement::AfterTonemapping => {
+                    TonemappingPipelineKeyFlags::COLOR_GRADING_LUT_AFTER_TONEMAPPING
+                }
+            });
+            flags.set(
+                TonemappingPipelineKeyFlags::COLOR_GRADING_LUT_BLEND,
+                color_grading_lut.blend_lut.is_some() && color_grading_lut.blend > 0.0,
+            );
+        }
 
         let key = TonemappingPipelineKey {
             deband_dither: *dither.unwrap_or(&DebandDither::Disabled),
@@ -4,6 +4,7 @@ use crate::tonemapping::{TonemappingLuts, TonemappingPipeline, ViewTonemappingPi
 
 use bevy_ecs::{prelude::*, query::QueryItem};
 use bevy_render::{
+    camera::Multiview,
     render_asset::RenderAssets,
     render_graph::{NodeRunError, RenderGraphContext, ViewNode},
     render_resource::{
@@ -15,7 +16,7 @@ use bevy_render::{
     view::{ViewTarget, ViewUniformOffset, ViewUniforms},
 };
 
-use super::{get_lut_bindings, Tonemapping};
+use super::{get_lut_bindings, Tonemapping, ViewColorGradingLut};
 
 #[derive(Default)]
 pub struct TonemappingNode {
@@ -29,15 +30,22 @@ impl ViewNode for TonemappingNode {
         &'static ViewTarget,
         &'static ViewTonemappingPipeline,
         &'static Tonemapping,
+        Option<&'static ViewColorGradingLut>,
+        Has<Multiview>,
     );
 
     fn run(
         &self,
         _graph: &mut RenderGraphContext,
         render_context: &mut RenderContext,
-        (view_uniform_offset, target, view_tonemapping_pipeline, tonemapping): QueryItem<
-            Self::ViewQuery,
-        >,
+        (
+            view_uniform_offset,
+            target,
+            view_tonemapping_pipeline,
+            tonemapping,
+            color_grading_lut,
+            multiview,
+        ): QueryItem<Self::ViewQuery>,
         world: &World,
     ) -> Result<(), NodeRunError> {
         let pipeline_cache = world.resource::<PipelineCache>();
@@ -48,7 +56,7 @@ impl ViewNode for TonemappingNode {
         let view_uniforms = &view_uniforms_resource.uniforms;
         let view_uniforms_id = view_uniforms.buffer().unwrap().id();
 
-        if *tonemapping == Tonemapping::None {
+        if *tonemapping == Tonemapping::None && color_grading_lut.is_none() {
             return Ok(());
         }
 
@@ -93,7 +101,11 @@ impl ViewNode for TonemappingNode {
 
                 let bind_group = render_context.render_device().create_bind_group(
                     None,
-                    &tonemapping_pipeline.texture_bind_group,
+                    if multiview {
+                        &tonemapping_pipeline.multiview_texture_bind_group
+                    } else {
+                        &tonemapping_pipeline.texture_bind_group
+                    },
                     &BindGroupEntries::sequential((
                         view_uniforms,
                         source,
@@ -134,6 +146,13 @@ impl ViewNode for TonemappingNode {
 
         render_pass.set_pipeline(pipeline);
         render_pass.set_bind_group(0, bind_group, &[view_uniform_offset.offset]);
+        if let Some(color_grading_lut) = color_grading_lut {
+            render_pass.set_bind_group(
+                1,
+                &color_grading_lut.bind_group,
+                &[color_grading_lut.uniform_offset],
+            );
+        }
         render_pass.draw(0..3, 0..1);
 
         Ok(())
@@ -9,19 +9,40 @@
     tonemapping::{tone_mapping, screen_space_dither},
 }
 
+#ifdef COLOR_GRADING_LUT_AFTER_TONEMAPPING
+#import bevy_core_pipeline::color_grading_lut::apply_color_grading_lut_display
+#endif
+
 @group(0) @binding(0) var<uniform> view: View;
 
+#ifdef MULTIVIEW
+@group(0) @binding(1) var hdr_texture: texture_2d_array<f32>;
+#else
 @group(0) @binding(1) var hdr_texture: texture_2d<f32>;
+#endif
 @group(0) @binding(2) var hdr_sampler: sampler;
 @group(0) @binding(3) var dt_lut_texture: texture_3d<f32>;
 @group(0) @binding(4) var dt_lut_sampler: sampler;
 
 @fragment
-fn fragment(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+fn fragment(
+    in: FullscreenVertexOutput,
+#ifdef MULTIVIEW
+    @builtin(view_index) view_index: i32,
+#endif
+) -> @location(0) vec4<f32> {
+#ifdef MULTIVIEW
+    let hdr_color = textureSample(hdr_texture, hdr_sampler, in.uv, view_index);
+#else
     let hdr_color = textureSample(hdr_texture, hdr_sampler, in.uv);
+#endif
 
     var output_rgb = tone_mapping(hdr_color, view.color_grading).rgb;
 
+#ifdef COLOR_GRADING_LUT_AFTER_TONEMAPPING
+    output_rgb = apply_color_grading_lut_display(output_rgb);
+#endif
+
 #ifdef DEBAND_DITHER
     output_rgb = powsafe(output_rgb.rgb, 1.0 / 2.2);
     output_rgb = output_rgb + screen_space_dither(in.position.xy);
@@ -11,6 +11,10 @@
     dt_lut_sampler,
 }
 
+#ifdef COLOR_GRADING_LUT_BEFORE_TONEMAPPING
+#import bevy_core_pipeline::color_grading_lut::apply_color_grading_lut_scene
+#endif
+
 // Half the size of the crossfade region between shadows and midtones and
 // between midtones and highlights. This value, 0.1, corresponds to 10% of the
 // gamut on either side of the cutoff point.
@@ -368,6 +372,11 @@ fn tone_mapping(in: vec4<f32>, in_color_grading: ColorGrading) -> vec4<f32> {
     color = color * powsafe(vec3(2.0), color_grading.exposure);
 #endif
 
+    // Apply a user-supplied lookup table to the scene colors.
+#ifdef COLOR_GRADING_LUT_BEFORE_TONEMAPPING
+    color = apply_color_grading_lut_scene(color);
+#endif
+
     // tone_mapping
 #ifdef TONEMAP_METHOD_NONE
     color = color;
@@ -0,0 +1,119 @@
+use bevy_asset::{weak_handle, Handle};
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::prelude::*;
+use bevy_render::{
+    render_resource::{
+        binding_types::{sampler, texture_2d, uniform_buffer},
+        *,
+    },
+    renderer::RenderDevice,
+    view::HdrEncoding,
+};
+
+use crate::fullscreen_vertex_shader::fullscreen_shader_vertex_state;
+
+pub const DISPLAY_MAPPING_SHADER_HANDLE: Handle<Shader> =
+    weak_handle!("3c8e5b7a-1f42-4d6e-9a0b-7e2d5c4f8a91");
+
+/// Writes the output of a camera to a window presented in HDR, mapping it to
+/// the luminance range of the display and encoding it for the swap chain.
+///
+/// This takes the place of the [`BlitPipeline`](crate::blit::BlitPipeline)
+/// in the upscaling pass of views whose target window has an
+/// [`HdrOutput`](bevy_render::view::HdrOutput).
+#[derive(Resource)]
+pub struct DisplayMappingPipeline {
+    pub texture_bind_group: BindGroupLayout,
+    pub sampler: Sampler,
+}
+
+impl FromWorld for DisplayMappingPipeline {
+    fn from_world(render_world: &mut World) -> Self {
+        let render_device = render_world.resource::<RenderDevice>();
+
+        let texture_bind_group = render_device.create_bind_group_layout(
+            "display_mapping_bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::FRAGMENT,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: false }),
+                    sampler(SamplerBindingType::NonFiltering),
+                    uniform_buffer::<DisplayMappingUniform>(true),
+                ),
+            ),
+        );
+
+        let sampler = render_device.create_sampler(&SamplerDescriptor::default());
+
+        DisplayMappingPipeline {
+            texture_bind_group,
+            sampler,
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+pub struct DisplayMappingPipelineKey {
+    pub encoding: HdrEncoding,
+    pub blend_state: Option<BlendState>,
+}
+
+impl SpecializedRenderPipeline for DisplayMappingPipeline {
+    type Key = DisplayMappingPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        let mut shader_defs = vec![];
+        if key.encoding == HdrEncoding::Pq {
+            shader_defs.push("DISPLAY_MAPPING_PQ".into());
+        }
+
+        RenderPipelineDescriptor {
+            label: Some("display mapping pipeline".into()),
+            layout: vec![self.texture_bind_group.clone()],
+            vertex: fullscreen_shader_vertex_state(),
+            fragment: Some(FragmentState {
+                shader: DISPLAY_MAPPING_SHADER_HANDLE,
+                shader_defs,
+                entry_point: "fs_main".into(),
+                targets: vec![Some(ColorTargetState {
+                    format: key.encoding.texture_format(),
+                    blend: key.blend_state,
+                    write_mask: ColorWrites::ALL,
+                })],
+            }),
+            primitive: PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: MultisampleState::default(),
+            push_constant_ranges: Vec::new(),
+            zero_initialize_workgroup_memory: false,
+            multiview: None,
+        }
+    }
+}
+
+/// The on-GPU version of the luminances of an
+/// [`HdrOutput`](bevy_render::view::HdrOutput).
+#[derive(ShaderType)]
+pub struct DisplayMappingUniform {
+    /// The luminance, in nits, that a value of 1.0 is shown at.
+    pub paper_white: f32,
+    /// The luminance, in nits, of the brightest highlights.
+    pub peak_luminance: f32,
+    /// Padding data.
+    pub unused_1: u32,
+    /// Padding data.
+    pub unused_2: u32,
+}
+
+/// A resource, part of the render world, that stores the
+/// [`DisplayMappingUniform`]s of each view.
+#[derive(Resource, Deref, DerefMut, Default)]
+pub struct DisplayMappingUniformBuffer(DynamicUniformBuffer<DisplayMappingUniform>);
+
+/// A component, part of the render world, on views that are mapped to an HDR
+/// display. It stores the offset of the view's [`DisplayMappingUniform`]
+/// within the [`DisplayMappingUniformBuffer`].
+#[derive(Component)]
+pub struct ViewDisplayMapping {
+    pub uniform_offset: u32,
+}
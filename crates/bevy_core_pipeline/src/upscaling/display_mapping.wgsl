@@ -0,0 +1,73 @@
+// Maps the output of a camera to an HDR display
+//
+// The input is linear Rec. 709, where 1.0 is paper white. Colors brighter
+// than paper white are rolled off towards the peak luminance of the display,
+// then encoded for the swap chain.
+
+#import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
+
+struct DisplayMapping {
+    paper_white: f32,
+    peak_luminance: f32,
+    unused_a: u32,
+    unused_b: u32,
+}
+
+@group(0) @binding(0) var in_texture: texture_2d<f32>;
+@group(0) @binding(1) var in_sampler: sampler;
+@group(0) @binding(2) var<uniform> display_mapping: DisplayMapping;
+
+// The luminance, in nits, of 1.0 in scRGB.
+const SCRGB_WHITE_LUMINANCE: f32 = 80.0;
+
+// The luminance, in nits, of 1.0 in PQ.
+const PQ_MAX_LUMINANCE: f32 = 10000.0;
+
+// Rec. 709 to Rec. 2020 primaries, from ITU-R BT.2087.
+const REC_709_TO_REC_2020: mat3x3<f32> = mat3x3<f32>(
+    vec3(0.6274, 0.0691, 0.0164),
+    vec3(0.3293, 0.9195, 0.0880),
+    vec3(0.0433, 0.0114, 0.8956),
+);
+
+// Compresses luminances above paper white so that they reach the peak
+// luminance asymptotically, with a continuous slope at paper white. Scaling
+// all channels by the same factor preserves hue.
+fn roll_off(luminance: vec3<f32>) -> vec3<f32> {
+    let knee = display_mapping.paper_white;
+    let headroom = display_mapping.peak_luminance - knee;
+    let brightest = max(luminance.r, max(luminance.g, luminance.b));
+    if brightest <= knee {
+        return luminance;
+    }
+    if headroom <= 0.0 {
+        return luminance * (knee / brightest);
+    }
+    let rolled_off = knee + headroom * (1.0 - exp((knee - brightest) / headroom));
+    return luminance * (rolled_off / brightest);
+}
+
+// SMPTE ST 2084 inverse EOTF
+fn pq_encode(luminance: vec3<f32>) -> vec3<f32> {
+    let m1 = 0.1593017578125;
+    let m2 = 78.84375;
+    let c1 = 0.8359375;
+    let c2 = 18.8515625;
+    let c3 = 18.6875;
+    let y = pow(saturate(luminance / PQ_MAX_LUMINANCE), vec3(m1));
+    return pow((c1 + c2 * y) / (1.0 + c3 * y), vec3(m2));
+}
+
+@fragment
+fn fs_main(in: FullscreenVertexOutput) -> @location(0) vec4<f32> {
+    let color = textureSample(in_texture, in_sampler, in.uv);
+    let luminance = roll_off(max(color.rgb, vec3(0.0)) * display_mapping.paper_white);
+
+#ifdef DISPLAY_MAPPING_PQ
+    let encoded = pq_encode(REC_709_TO_REC_2020 * luminance);
+#else
+    let encoded = luminance / SCRGB_WHITE_LUMINANCE;
+#endif
+
+    return vec4(encoded, color.a);
+}
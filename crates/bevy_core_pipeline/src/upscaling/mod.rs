@@ -1,36 +1,59 @@
 use crate::blit::{BlitPipeline, BlitPipelineKey};
 use bevy_app::prelude::*;
+use bevy_asset::load_internal_asset;
 use bevy_ecs::prelude::*;
 use bevy_platform_support::collections::HashSet;
 use bevy_render::{
-    camera::{CameraOutputMode, ExtractedCamera},
+    camera::{CameraOutputMode, ExtractedCamera, Multiview, NormalizedRenderTarget},
     render_resource::*,
-    view::ViewTarget,
+    renderer::{RenderDevice, RenderQueue},
+    view::{ExtractedWindows, ViewTarget},
     Render, RenderApp, RenderSet,
 };
 
+mod display_mapping;
 mod node;
 
+pub use display_mapping::*;
 pub use node::UpscalingNode;
 
 pub struct UpscalingPlugin;
 
 impl Plugin for UpscalingPlugin {
     fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            DISPLAY_MAPPING_SHADER_HANDLE,
+            "display_mapping.wgsl",
+            Shader::from_wgsl
+        );
+
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
-            render_app.add_systems(
-                Render,
-                // This system should probably technically be run *after* all of the other systems
-                // that might modify `PipelineCache` via interior mutability, but for now,
-                // we've chosen to simply ignore the ambiguities out of a desire for a better refactor
-                // and aversion to extensive and intrusive system ordering.
-                // See https://github.com/bevyengine/bevy/issues/14770 for more context.
-                prepare_view_upscaling_pipelines
-                    .in_set(RenderSet::Prepare)
-                    .ambiguous_with_all(),
-            );
+            render_app
+                .init_resource::<DisplayMappingUniformBuffer>()
+                .allow_ambiguous_resource::<SpecializedRenderPipelines<DisplayMappingPipeline>>()
+                .add_systems(
+                    Render,
+                    // This system should probably technically be run *after* all of the other systems
+                    // that might modify `PipelineCache` via interior mutability, but for now,
+                    // we've chosen to simply ignore the ambiguities out of a desire for a better refactor
+                    // and aversion to extensive and intrusive system ordering.
+                    // See https://github.com/bevyengine/bevy/issues/14770 for more context.
+                    prepare_view_upscaling_pipelines
+                        .in_set(RenderSet::Prepare)
+                        .ambiguous_with_all(),
+                );
         }
     }
+
+    fn finish(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+        render_app
+            .init_resource::<DisplayMappingPipeline>()
+            .init_resource::<SpecializedRenderPipelines<DisplayMappingPipeline>>();
+    }
 }
 
 #[derive(Component)]
@@ -41,10 +64,23 @@ fn prepare_view_upscaling_pipelines(
     mut pipeline_cache: ResMut<PipelineCache>,
     mut pipelines: ResMut<SpecializedRenderPipelines<BlitPipeline>>,
     blit_pipeline: Res<BlitPipeline>,
-    view_targets: Query<(Entity, &ViewTarget, Option<&ExtractedCamera>)>,
+    mut display_mapping_pipelines: ResMut<SpecializedRenderPipelines<DisplayMappingPipeline>>,
+    display_mapping_pipeline: Res<DisplayMappingPipeline>,
+    mut display_mapping_uniforms: ResMut<DisplayMappingUniformBuffer>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    windows: Res<ExtractedWindows>,
+    view_targets: Query<(
+        Entity,
+        &ViewTarget,
+        Option<&ExtractedCamera>,
+        Has<Multiview>,
+    )>,
 ) {
+    display_mapping_uniforms.clear();
+
     let mut output_textures = <HashSet<_>>::default();
-    for (entity, view_target, camera) in view_targets.iter() {
+    for (entity, view_target, camera, multiview) in view_targets.iter() {
         let out_texture_id = view_target.out_texture().id();
         let blend_state = if let Some(extracted_camera) = camera {
             match extracted_camera.output_mode {
@@ -73,12 +109,40 @@ fn prepare_view_upscaling_pipelines(
             None
         };
 
-        let key = BlitPipelineKey {
-            texture_format: view_target.out_texture_format(),
-            blend_state,
-            samples: 1,
+        // Views that render to a window presented in HDR are mapped to the
+        // display instead of being copied as they are.
+        let hdr_window = camera
+            .and_then(|camera| match camera.target.as_ref()? {
+                NormalizedRenderTarget::Window(window_ref) => windows.get(&window_ref.entity()),
+                _ => None,
+            })
+            .and_then(|window| Some((window.hdr_output?, window.hdr_encoding()?)));
+
+        let pipeline = if let Some((hdr_output, encoding)) = hdr_window {
+            let key = DisplayMappingPipelineKey {
+                encoding,
+                blend_state,
+            };
+            let uniform_offset = display_mapping_uniforms.push(&DisplayMappingUniform {
+                paper_white: hdr_output.paper_white,
+                peak_luminance: hdr_output.peak_luminance,
+                unused_1: 0,
+                unused_2: 0,
+            });
+            commands
+                .entity(entity)
+                .insert(ViewDisplayMapping { uniform_offset });
+            display_mapping_pipelines.specialize(&pipeline_cache, &display_mapping_pipeline, key)
+        } else {
+            let key = BlitPipelineKey {
+                texture_format: view_target.out_texture_format(),
+                blend_state,
+                samples: 1,
+                multiview,
+            };
+            commands.entity(entity).remove::<ViewDisplayMapping>();
+            pipelines.specialize(&pipeline_cache, &blit_pipeline, key)
         };
-        let pipeline = pipelines.specialize(&pipeline_cache, &blit_pipeline, key);
 
         // Ensure the pipeline is loaded before continuing the frame to prevent frames without any GPU work submitted
         pipeline_cache.block_on_render_pipeline(pipeline);
@@ -87,4 +151,6 @@ fn prepare_view_upscaling_pipelines(
             .entity(entity)
             .insert(ViewUpscalingPipeline(pipeline));
     }
+
+    display_mapping_uniforms.write_buffer(&render_device, &render_queue);
 }
@@ -1,19 +1,27 @@
-use crate::{blit::BlitPipeline, upscaling::ViewUpscalingPipeline};
+use crate::{
+    blit::BlitPipeline,
+    upscaling::{
+        DisplayMappingPipeline, DisplayMappingUniformBuffer, ViewDisplayMapping,
+        ViewUpscalingPipeline,
+    },
+};
 use bevy_ecs::{prelude::*, query::QueryItem};
 use bevy_render::{
-    camera::{CameraOutputMode, ClearColor, ClearColorConfig, ExtractedCamera},
+    camera::{CameraOutputMode, ClearColor, ClearColorConfig, ExtractedCamera, Multiview},
     render_graph::{NodeRunError, RenderGraphContext, ViewNode},
     render_resource::{
-        BindGroup, BindGroupEntries, PipelineCache, RenderPassDescriptor, TextureViewId,
+        BindGroup, BindGroupEntries, BufferId, PipelineCache, RenderPassDescriptor, TextureViewId,
     },
     renderer::RenderContext,
     view::ViewTarget,
 };
+use core::slice;
 use std::sync::Mutex;
 
 #[derive(Default)]
 pub struct UpscalingNode {
     cached_texture_bind_group: Mutex<Option<(TextureViewId, BindGroup)>>,
+    cached_display_mapping_bind_group: Mutex<Option<(TextureViewId, BufferId, BindGroup)>>,
 }
 
 impl ViewNode for UpscalingNode {
@@ -21,13 +29,15 @@ impl ViewNode for UpscalingNode {
         &'static ViewTarget,
         &'static ViewUpscalingPipeline,
         Option<&'static ExtractedCamera>,
+        Option<&'static ViewDisplayMapping>,
+        Has<Multiview>,
     );
 
     fn run(
         &self,
         _graph: &mut RenderGraphContext,
         render_context: &mut RenderContext,
-        (target, upscaling_target, camera): QueryItem<Self::ViewQuery>,
+        (target, upscaling_target, camera, display_mapping, multiview): QueryItem<Self::ViewQuery>,
         world: &World,
     ) -> Result<(), NodeRunError> {
         let pipeline_cache = world.get_resource::<PipelineCache>().unwrap();
@@ -51,18 +61,62 @@ impl ViewNode for UpscalingNode {
         let upscaled_texture = target.main_texture_view();
 
         let mut cached_bind_group = self.cached_texture_bind_group.lock().unwrap();
-        let bind_group = match &mut *cached_bind_group {
-            Some((id, bind_group)) if upscaled_texture.id() == *id => bind_group,
-            cached_bind_group => {
-                let bind_group = render_context.render_device().create_bind_group(
-                    None,
-                    &blit_pipeline.texture_bind_group,
-                    &BindGroupEntries::sequential((upscaled_texture, &blit_pipeline.sampler)),
-                );
+        let mut cached_display_mapping_bind_group =
+            self.cached_display_mapping_bind_group.lock().unwrap();
+        let (bind_group, dynamic_offsets) = if let Some(display_mapping) = display_mapping {
+            let display_mapping_pipeline = world.resource::<DisplayMappingPipeline>();
+            let uniforms = world.resource::<DisplayMappingUniformBuffer>();
+            let Some(uniforms_binding) = uniforms.binding() else {
+                return Ok(());
+            };
+            let uniforms_id = uniforms.buffer().unwrap().id();
 
-                let (_, bind_group) = cached_bind_group.insert((upscaled_texture.id(), bind_group));
-                bind_group
-            }
+            let bind_group = match &mut *cached_display_mapping_bind_group {
+                Some((texture_id, buffer_id, bind_group))
+                    if upscaled_texture.id() == *texture_id && uniforms_id == *buffer_id =>
+                {
+                    bind_group
+                }
+                cached_bind_group => {
+                    let bind_group = render_context.render_device().create_bind_group(
+                        None,
+                        &display_mapping_pipeline.texture_bind_group,
+                        &BindGroupEntries::sequential((
+                            upscaled_texture,
+                            &display_mapping_pipeline.sampler,
+                            uniforms_binding,
+                        )),
+                    );
+
+                    let (_, _, bind_group) =
+                        cached_bind_group.insert((upscaled_texture.id(), uniforms_id, bind_group));
+                    bind_group
+                }
+            };
+            (
+                &*bind_group,
+                slice::from_ref(&display_mapping.uniform_offset),
+            )
+        } else {
+            let bind_group = match &mut *cached_bind_group {
+                Some((id, bind_group)) if upscaled_texture.id() == *id => bind_group,
+                cached_bind_group => {
+                    let bind_group = render_context.render_device().create_bind_group(
+                        None,
+                        if multiview {
+                            &blit_pipeline.multiview_texture_bind_group
+                        } else {
+                            &blit_pipeline.texture_bind_group
+                        },
+                        &BindGroupEntries::sequential((upscaled_texture, &blit_pipeline.sampler)),
+                    );
+
+                    let (_, bind_group) =
+                        cached_bind_group.insert((upscaled_texture.id(), bind_group));
+                    bind_group
+                }
+            };
+            (&*bind_group, &[][..])
         };
 
         let Some(pipeline) = pipeline_cache.get_render_pipeline(upscaling_target.0) else {
@@ -92,7 +146,7 @@ impl ViewNode for UpscalingNode {
         }
 
         render_pass.set_pipeline(pipeline);
-        render_pass.set_bind_group(0, bind_group, &[]);
+        render_pass.set_bind_group(0, bind_group, dynamic_offsets);
         render_pass.draw(0..3, 0..1);
 
         Ok(())
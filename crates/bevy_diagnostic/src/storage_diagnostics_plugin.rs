@@ -0,0 +1,63 @@
+use bevy_app::prelude::*;
+use bevy_ecs::{system::Local, world::World};
+
+use crate::{Diagnostic, DiagnosticPath, Diagnostics, RegisterDiagnostic};
+
+/// Adds diagnostics about how entities are stored in archetypes and tables to an App.
+///
+/// These are measured from the [`World::storage_summary`]. The [`World::storage_report`] can be
+/// inspected directly for per-archetype and per-column details.
+///
+/// # See also
+///
+/// [`LogDiagnosticsPlugin`](crate::LogDiagnosticsPlugin) to output diagnostics to the console.
+#[derive(Default)]
+pub struct StorageDiagnosticsPlugin;
+
+impl Plugin for StorageDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_diagnostic(Diagnostic::new(Self::ARCHETYPE_COUNT))
+            .register_diagnostic(Diagnostic::new(Self::EMPTY_ARCHETYPE_COUNT))
+            .register_diagnostic(Diagnostic::new(Self::TABLE_COUNT))
+            .register_diagnostic(Diagnostic::new(Self::COMPONENT_BYTES).with_suffix(" B"))
+            .register_diagnostic(Diagnostic::new(Self::ALLOCATED_TABLE_BYTES).with_suffix(" B"))
+            .register_diagnostic(Diagnostic::new(Self::ARCHETYPE_MOVES))
+            .add_systems(Update, Self::diagnostic_system);
+    }
+}
+
+impl StorageDiagnosticsPlugin {
+    pub const ARCHETYPE_COUNT: DiagnosticPath =
+        DiagnosticPath::const_new("storage/archetype_count");
+    pub const EMPTY_ARCHETYPE_COUNT: DiagnosticPath =
+        DiagnosticPath::const_new("storage/empty_archetype_count");
+    pub const TABLE_COUNT: DiagnosticPath = DiagnosticPath::const_new("storage/table_count");
+    pub const COMPONENT_BYTES: DiagnosticPath =
+        DiagnosticPath::const_new("storage/component_bytes");
+    pub const ALLOCATED_TABLE_BYTES: DiagnosticPath =
+        DiagnosticPath::const_new("storage/allocated_table_bytes");
+    /// The number of times entities moved between archetypes since the previous update.
+    pub const ARCHETYPE_MOVES: DiagnosticPath =
+        DiagnosticPath::const_new("storage/archetype_moves");
+
+    pub fn diagnostic_system(
+        mut diagnostics: Diagnostics,
+        world: &World,
+        mut last_entity_moves: Local<u64>,
+    ) {
+        let summary = world.storage_summary();
+        diagnostics.add_measurement(&Self::ARCHETYPE_COUNT, || summary.archetype_count as f64);
+        diagnostics.add_measurement(&Self::EMPTY_ARCHETYPE_COUNT, || {
+            summary.empty_archetype_count as f64
+        });
+        diagnostics.add_measurement(&Self::TABLE_COUNT, || summary.table_count as f64);
+        diagnostics.add_measurement(&Self::COMPONENT_BYTES, || summary.component_bytes as f64);
+        diagnostics.add_measurement(&Self::ALLOCATED_TABLE_BYTES, || {
+            summary.allocated_table_bytes as f64
+        });
+        diagnostics.add_measurement(&Self::ARCHETYPE_MOVES, || {
+            (summary.entity_moves - *last_entity_moves) as f64
+        });
+        *last_entity_moves = summary.entity_moves;
+    }
+}
@@ -9,8 +9,8 @@ use syn::{
     punctuated::Punctuated,
     spanned::Spanned,
     token::{Comma, Paren},
-    Data, DataStruct, DeriveInput, ExprClosure, ExprPath, Fields, Ident, Index, LitStr, Member,
-    Path, Result, Token, Visibility,
+    Data, DataStruct, DeriveInput, ExprClosure, ExprPath, Fields, GenericArgument, Ident, Index,
+    LitStr, Member, Path, PathArguments, Result, Token, Type, TypePath, Visibility,
 };
 
 pub fn derive_event(input: TokenStream) -> TokenStream {
@@ -22,13 +22,32 @@ pub fn derive_event(input: TokenStream) -> TokenStream {
         .predicates
         .push(parse_quote! { Self: Send + Sync + 'static });
 
+    let mut traversal: Type = parse_quote!(());
+    let mut auto_propagate = false;
+    for attr in ast.attrs.iter().filter(|attr| attr.path().is_ident(EVENT)) {
+        let result = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident(TRAVERSAL) {
+                traversal = meta.value()?.parse()?;
+                Ok(())
+            } else if meta.path.is_ident(AUTO_PROPAGATE) {
+                auto_propagate = true;
+                Ok(())
+            } else {
+                Err(meta.error("Unsupported attribute"))
+            }
+        });
+        if let Err(error) = result {
+            return error.into_compile_error().into();
+        }
+    }
+
     let struct_name = &ast.ident;
     let (impl_generics, type_generics, where_clause) = &ast.generics.split_for_impl();
 
     TokenStream::from(quote! {
         impl #impl_generics #bevy_ecs_path::event::Event for #struct_name #type_generics #where_clause {
-            type Traversal = ();
-            const AUTO_PROPAGATE: bool = false;
+            type Traversal = #traversal;
+            const AUTO_PROPAGATE: bool = #auto_propagate;
         }
     })
 }
@@ -70,15 +89,51 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
         Ok(value) => value,
         Err(err) => err.into_compile_error().into(),
     };
+    let many_relationship = match derive_many_relationship(&ast, &attrs, &bevy_ecs_path) {
+        Ok(value) => value,
+        Err(err) => err.into_compile_error().into(),
+    };
+    let many_relationship_target =
+        match derive_many_relationship_target(&ast, &attrs, &bevy_ecs_path) {
+            Ok(value) => value,
+            Err(err) => err.into_compile_error().into(),
+        };
+
+    let relationship_trait = if relationship.is_some() {
+        Some(quote!(#bevy_ecs_path::relationship::Relationship))
+    } else if many_relationship.is_some() {
+        Some(quote!(#bevy_ecs_path::relationship::ManyRelationship))
+    } else {
+        None
+    };
+    let relationship_target_trait = if attrs.relationship_target.is_some() {
+        Some(quote!(#bevy_ecs_path::relationship::RelationshipTarget))
+    } else if attrs.many_relationship_target.is_some() {
+        Some(quote!(#bevy_ecs_path::relationship::ManyRelationshipTarget))
+    } else {
+        None
+    };
 
-    let visit_entities = visit_entities(&ast.data, &bevy_ecs_path, relationship.is_some());
+    if attrs.index && (relationship_trait.is_some() || relationship_target_trait.is_some()) {
+        return syn::Error::new(
+            ast.span(),
+            "Indexed components cannot be relationships or relationship targets",
+        )
+        .into_compile_error()
+        .into();
+    }
+    let index_trait = attrs
+        .index
+        .then(|| quote!(#bevy_ecs_path::index::IndexedComponent));
+
+    let visit_entities = visit_entities(&ast.data, &bevy_ecs_path, relationship_trait.is_some());
 
     let storage = storage_path(&bevy_ecs_path, attrs.storage);
 
     let on_add_path = attrs.on_add.map(|path| path.to_token_stream());
     let on_remove_path = attrs.on_remove.map(|path| path.to_token_stream());
 
-    let on_insert_path = if relationship.is_some() {
+    let on_insert_path = if let Some(relationship_trait) = &relationship_trait {
         if attrs.on_insert.is_some() {
             return syn::Error::new(
                 ast.span(),
@@ -88,12 +143,23 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
             .into();
         }
 
-        Some(quote!(<Self as #bevy_ecs_path::relationship::Relationship>::on_insert))
+        Some(quote!(<Self as #relationship_trait>::on_insert))
+    } else if let Some(index_trait) = &index_trait {
+        if attrs.on_insert.is_some() {
+            return syn::Error::new(
+                ast.span(),
+                "Custom on_insert hooks are not supported as indexed components already define an on_insert hook",
+            )
+            .into_compile_error()
+            .into();
+        }
+
+        Some(quote!(<Self as #index_trait>::on_insert))
     } else {
         attrs.on_insert.map(|path| path.to_token_stream())
     };
 
-    let on_replace_path = if relationship.is_some() {
+    let on_replace_path = if let Some(relationship_trait) = &relationship_trait {
         if attrs.on_replace.is_some() {
             return syn::Error::new(
                 ast.span(),
@@ -103,8 +169,8 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
             .into();
         }
 
-        Some(quote!(<Self as #bevy_ecs_path::relationship::Relationship>::on_replace))
-    } else if attrs.relationship_target.is_some() {
+        Some(quote!(<Self as #relationship_trait>::on_replace))
+    } else if let Some(relationship_target_trait) = &relationship_target_trait {
         if attrs.on_replace.is_some() {
             return syn::Error::new(
                 ast.span(),
@@ -114,14 +180,30 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
             .into();
         }
 
-        Some(quote!(<Self as #bevy_ecs_path::relationship::RelationshipTarget>::on_replace))
+        Some(quote!(<Self as #relationship_target_trait>::on_replace))
+    } else if let Some(index_trait) = &index_trait {
+        if attrs.on_replace.is_some() {
+            return syn::Error::new(
+                ast.span(),
+                "Custom on_replace hooks are not supported as indexed components already define an on_replace hook",
+            )
+            .into_compile_error()
+            .into();
+        }
+
+        Some(quote!(<Self as #index_trait>::on_replace))
     } else {
         attrs.on_replace.map(|path| path.to_token_stream())
     };
 
     let on_despawn_path = if attrs
         .relationship_target
+        .as_ref()
         .is_some_and(|target| target.linked_spawn)
+        || attrs
+            .many_relationship_target
+            .as_ref()
+            .is_some_and(|target| target.linked_despawn.is_some())
     {
         if attrs.on_despawn.is_some() {
             return syn::Error::new(
@@ -132,7 +214,7 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
             .into();
         }
 
-        Some(quote!(<Self as #bevy_ecs_path::relationship::RelationshipTarget>::on_despawn))
+        Some(quote!(<Self as #relationship_target_trait>::on_despawn))
     } else {
         attrs.on_despawn.map(|path| path.to_token_stream())
     };
@@ -202,12 +284,14 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
     let struct_name = &ast.ident;
     let (impl_generics, type_generics, where_clause) = &ast.generics.split_for_impl();
 
-    let mutable_type = (attrs.immutable || relationship.is_some())
+    let mutable_type = (attrs.immutable || attrs.index || relationship_trait.is_some())
         .then_some(quote! { #bevy_ecs_path::component::Immutable })
         .unwrap_or(quote! { #bevy_ecs_path::component::Mutable });
 
     let clone_behavior = if relationship_target.is_some() {
         quote!(#bevy_ecs_path::component::ComponentCloneBehavior::RelationshipTarget(#bevy_ecs_path::relationship::clone_relationship_target::<Self>))
+    } else if many_relationship_target.is_some() {
+        quote!(#bevy_ecs_path::component::ComponentCloneBehavior::RelationshipTarget(#bevy_ecs_path::relationship::clone_many_relationship_target::<Self>))
     } else {
         quote!(
             use #bevy_ecs_path::component::{DefaultCloneBehaviorBase, DefaultCloneBehaviorViaClone};
@@ -215,6 +299,12 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
         )
     };
 
+    let index = index_trait.map(|index_trait| {
+        quote! {
+            impl #impl_generics #index_trait for #struct_name #type_generics #where_clause {}
+        }
+    });
+
     // This puts `register_required` before `register_recursive_requires` to ensure that the constructors of _all_ top
     // level components are initialized first, giving them precedence over recursively defined constructors for the same component type
     TokenStream::from(quote! {
@@ -252,6 +342,12 @@ pub fn derive_component(input: TokenStream) -> TokenStream {
         #relationship
 
         #relationship_target
+
+        #many_relationship
+
+        #many_relationship_target
+
+        #index
     })
 }
 
@@ -421,11 +517,17 @@ pub fn document_required_components(attr: TokenStream, item: TokenStream) -> Tok
     out
 }
 
+pub const EVENT: &str = "event";
+pub const TRAVERSAL: &str = "traversal";
+pub const AUTO_PROPAGATE: &str = "auto_propagate";
+
 pub const COMPONENT: &str = "component";
 pub const STORAGE: &str = "storage";
 pub const REQUIRE: &str = "require";
 pub const RELATIONSHIP: &str = "relationship";
 pub const RELATIONSHIP_TARGET: &str = "relationship_target";
+pub const MANY_RELATIONSHIP: &str = "many_relationship";
+pub const MANY_RELATIONSHIP_TARGET: &str = "many_relationship_target";
 
 pub const ON_ADD: &str = "on_add";
 pub const ON_INSERT: &str = "on_insert";
@@ -434,6 +536,7 @@ pub const ON_REMOVE: &str = "on_remove";
 pub const ON_DESPAWN: &str = "on_despawn";
 
 pub const IMMUTABLE: &str = "immutable";
+pub const INDEX: &str = "index";
 
 struct Attrs {
     storage: StorageTy,
@@ -445,7 +548,10 @@ struct Attrs {
     on_despawn: Option<ExprPath>,
     relationship: Option<Relationship>,
     relationship_target: Option<RelationshipTarget>,
+    many_relationship: Option<Relationship>,
+    many_relationship_target: Option<ManyRelationshipTarget>,
     immutable: bool,
+    index: bool,
 }
 
 #[derive(Clone, Copy)]
@@ -473,6 +579,11 @@ struct RelationshipTarget {
     linked_spawn: bool,
 }
 
+struct ManyRelationshipTarget {
+    relationship: Ident,
+    linked_despawn: Option<Ident>,
+}
+
 // values for `storage` attribute
 const TABLE: &str = "Table";
 const SPARSE_SET: &str = "SparseSet";
@@ -488,7 +599,10 @@ fn parse_component_attr(ast: &DeriveInput) -> Result<Attrs> {
         requires: None,
         relationship: None,
         relationship_target: None,
+        many_relationship: None,
+        many_relationship_target: None,
         immutable: false,
+        index: false,
     };
 
     let mut require_paths = HashSet::new();
@@ -524,6 +638,9 @@ fn parse_component_attr(ast: &DeriveInput) -> Result<Attrs> {
                 } else if nested.path.is_ident(IMMUTABLE) {
                     attrs.immutable = true;
                     Ok(())
+                } else if nested.path.is_ident(INDEX) {
+                    attrs.index = true;
+                    Ok(())
                 } else {
                     Err(nested.error("Unsupported attribute"))
                 }
@@ -550,6 +667,12 @@ fn parse_component_attr(ast: &DeriveInput) -> Result<Attrs> {
         } else if attr.path().is_ident(RELATIONSHIP_TARGET) {
             let relationship_target = attr.parse_args::<RelationshipTarget>()?;
             attrs.relationship_target = Some(relationship_target);
+        } else if attr.path().is_ident(MANY_RELATIONSHIP) {
+            let relationship = attr.parse_args::<Relationship>()?;
+            attrs.many_relationship = Some(relationship);
+        } else if attr.path().is_ident(MANY_RELATIONSHIP_TARGET) {
+            let relationship_target = attr.parse_args::<ManyRelationshipTarget>()?;
+            attrs.many_relationship_target = Some(relationship_target);
         }
     }
 
@@ -643,6 +766,51 @@ impl Parse for RelationshipTarget {
     }
 }
 
+impl Parse for ManyRelationshipTarget {
+    fn parse(input: syn::parse::ParseStream) -> Result<Self> {
+        let mut relationship_ident = None;
+        let mut linked_despawn_policy = None;
+        syn::custom_keyword!(relationship);
+        syn::custom_keyword!(linked_despawn);
+        loop {
+            if input.peek(relationship) {
+                input.parse::<relationship>()?;
+                input.parse::<Token![=]>()?;
+                relationship_ident = Some(input.parse::<Ident>()?);
+            } else if input.peek(linked_despawn) {
+                let keyword = input.parse::<linked_despawn>()?;
+                linked_despawn_policy = Some(if input.peek(Token![=]) {
+                    input.parse::<Token![=]>()?;
+                    let policy = input.parse::<Ident>()?;
+                    match policy.to_string().as_str() {
+                        "all" => Ident::new("All", policy.span()),
+                        "orphans" => Ident::new("Orphans", policy.span()),
+                        _ => {
+                            return Err(syn::Error::new(
+                                policy.span(),
+                                "Invalid linked_despawn policy, expected 'all' or 'orphans'",
+                            ))
+                        }
+                    }
+                } else {
+                    Ident::new("All", keyword.span)
+                });
+            } else {
+                break;
+            }
+            if input.peek(Token![,]) {
+                input.parse::<Token![,]>()?;
+            }
+        }
+
+        let relationship = relationship_ident.ok_or_else(|| syn::Error::new(input.span(), "ManyRelationshipTarget derive must specify a relationship via #[many_relationship_target(relationship = X)"))?;
+        Ok(ManyRelationshipTarget {
+            relationship,
+            linked_despawn: linked_despawn_policy,
+        })
+    }
+}
+
 fn derive_relationship(
     ast: &DeriveInput,
     attrs: &Attrs,
@@ -754,3 +922,155 @@ fn derive_relationship_target(
         }
     }))
 }
+
+fn derive_many_relationship(
+    ast: &DeriveInput,
+    attrs: &Attrs,
+    bevy_ecs_path: &Path,
+) -> Result<Option<TokenStream2>> {
+    let Some(relationship) = &attrs.many_relationship else {
+        return Ok(None);
+    };
+    const MANY_RELATIONSHIP_FORMAT_MESSAGE: &str = "ManyRelationship derives must be a tuple struct with the only element being a RelationshipEdges type (ex: Likes(RelationshipEdges<f32>))";
+    let Data::Struct(DataStruct {
+        fields: Fields::Unnamed(unnamed_fields),
+        ..
+    }) = &ast.data
+    else {
+        return Err(syn::Error::new(
+            ast.span(),
+            MANY_RELATIONSHIP_FORMAT_MESSAGE,
+        ));
+    };
+    if unnamed_fields.unnamed.len() != 1 {
+        return Err(syn::Error::new(
+            ast.span(),
+            MANY_RELATIONSHIP_FORMAT_MESSAGE,
+        ));
+    }
+
+    // The edge data is the generic argument of `RelationshipEdges<D>`, which defaults to `()`.
+    let field = unnamed_fields.unnamed.first().unwrap();
+    let Type::Path(TypePath { path, .. }) = &field.ty else {
+        return Err(syn::Error::new(
+            field.ty.span(),
+            MANY_RELATIONSHIP_FORMAT_MESSAGE,
+        ));
+    };
+    let Some(segment) = path.segments.last() else {
+        return Err(syn::Error::new(
+            field.ty.span(),
+            MANY_RELATIONSHIP_FORMAT_MESSAGE,
+        ));
+    };
+    let data = match &segment.arguments {
+        PathArguments::None => quote!(()),
+        PathArguments::AngleBracketed(arguments) => match arguments.args.first() {
+            Some(GenericArgument::Type(data)) if arguments.args.len() == 1 => quote!(#data),
+            _ => {
+                return Err(syn::Error::new(
+                    field.ty.span(),
+                    MANY_RELATIONSHIP_FORMAT_MESSAGE,
+                ))
+            }
+        },
+        PathArguments::Parenthesized(_) => {
+            return Err(syn::Error::new(
+                field.ty.span(),
+                MANY_RELATIONSHIP_FORMAT_MESSAGE,
+            ))
+        }
+    };
+
+    let struct_name = &ast.ident;
+    let (impl_generics, type_generics, where_clause) = &ast.generics.split_for_impl();
+
+    let relationship_target = &relationship.relationship_target;
+
+    Ok(Some(quote! {
+        impl #impl_generics #bevy_ecs_path::relationship::ManyRelationship for #struct_name #type_generics #where_clause {
+            type RelationshipTarget = #relationship_target;
+            type Data = #data;
+
+            #[inline(always)]
+            fn edges(&self) -> &#bevy_ecs_path::relationship::RelationshipEdges<Self::Data> {
+                &self.0
+            }
+
+            #[inline]
+            fn from_edges(edges: #bevy_ecs_path::relationship::RelationshipEdges<Self::Data>) -> Self {
+                Self(edges)
+            }
+
+            #[inline]
+            fn into_edges(self) -> #bevy_ecs_path::relationship::RelationshipEdges<Self::Data> {
+                self.0
+            }
+        }
+    }))
+}
+
+fn derive_many_relationship_target(
+    ast: &DeriveInput,
+    attrs: &Attrs,
+    bevy_ecs_path: &Path,
+) -> Result<Option<TokenStream2>> {
+    let Some(relationship_target) = &attrs.many_relationship_target else {
+        return Ok(None);
+    };
+
+    const MANY_RELATIONSHIP_TARGET_FORMAT_MESSAGE: &str = "ManyRelationshipTarget derives must be a tuple struct with the first element being a private RelationshipSourceCollection (ex: LikedBy(Vec<Entity>))";
+    let collection = if let Data::Struct(DataStruct {
+        fields: Fields::Unnamed(unnamed_fields),
+        struct_token,
+        ..
+    }) = &ast.data
+    {
+        if let Some(first) = unnamed_fields.unnamed.first() {
+            if first.vis != Visibility::Inherited {
+                return Err(syn::Error::new(first.span(), "The collection in ManyRelationshipTarget must be private to prevent users from directly mutating it, which could invalidate the correctness of relationships."));
+            }
+            first.ty.clone()
+        } else {
+            return Err(syn::Error::new(
+                struct_token.span(),
+                MANY_RELATIONSHIP_TARGET_FORMAT_MESSAGE,
+            ));
+        }
+    } else {
+        return Err(syn::Error::new(
+            ast.span(),
+            MANY_RELATIONSHIP_TARGET_FORMAT_MESSAGE,
+        ));
+    };
+
+    let relationship = &relationship_target.relationship;
+    let struct_name = &ast.ident;
+    let (impl_generics, type_generics, where_clause) = &ast.generics.split_for_impl();
+    let linked_despawn = relationship_target
+        .linked_despawn
+        .clone()
+        .unwrap_or_else(|| Ident::new("None", Span::call_site()));
+    Ok(Some(quote! {
+        impl #impl_generics #bevy_ecs_path::relationship::ManyRelationshipTarget for #struct_name #type_generics #where_clause {
+            const LINKED_DESPAWN: #bevy_ecs_path::relationship::LinkedDespawn = #bevy_ecs_path::relationship::LinkedDespawn::#linked_despawn;
+            type Relationship = #relationship;
+            type Collection = #collection;
+
+            #[inline]
+            fn collection(&self) -> &Self::Collection {
+                &self.0
+            }
+
+            #[inline]
+            fn collection_mut_risky(&mut self) -> &mut Self::Collection {
+                &mut self.0
+            }
+
+            #[inline]
+            fn from_collection_risky(collection: Self::Collection) -> Self {
+                Self(collection)
+            }
+        }
+    }))
+}
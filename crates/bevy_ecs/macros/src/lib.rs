@@ -582,7 +582,7 @@ pub(crate) fn bevy_ecs_path() -> syn::Path {
     BevyManifest::shared().get_path("bevy_ecs")
 }
 
-#[proc_macro_derive(Event)]
+#[proc_macro_derive(Event, attributes(event))]
 pub fn derive_event(input: TokenStream) -> TokenStream {
     component::derive_event(input)
 }
@@ -594,7 +594,14 @@ pub fn derive_resource(input: TokenStream) -> TokenStream {
 
 #[proc_macro_derive(
     Component,
-    attributes(component, relationship, relationship_target, entities)
+    attributes(
+        component,
+        relationship,
+        relationship_target,
+        many_relationship,
+        many_relationship_target,
+        entities
+    )
 )]
 pub fn derive_component(input: TokenStream) -> TokenStream {
     component::derive_component(input)
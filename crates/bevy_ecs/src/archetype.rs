@@ -799,6 +799,8 @@ pub struct Archetypes {
     by_components: HashMap<ArchetypeComponents, ArchetypeId>,
     /// find all the archetypes that contain a component
     pub(crate) by_component: ComponentIndex,
+    /// the number of times an entity moved between archetypes
+    pub(crate) entity_moves: u64,
 }
 
 /// Metadata about how a component is stored in an [`Archetype`].
@@ -819,6 +821,7 @@ impl Archetypes {
             by_components: Default::default(),
             by_component: Default::default(),
             archetype_component_count: 0,
+            entity_moves: 0,
         };
         // SAFETY: Empty archetype has no components
         unsafe {
@@ -854,6 +857,15 @@ impl Archetypes {
         self.archetypes.len()
     }
 
+    /// Returns the number of times an entity moved from one [`Archetype`] to another, because
+    /// components were inserted into or removed from it.
+    ///
+    /// This is a running total, which can be sampled periodically to measure archetype churn.
+    #[inline]
+    pub fn entity_moves(&self) -> u64 {
+        self.entity_moves
+    }
+
     /// Fetches an immutable reference to the archetype without any components.
     ///
     /// Shorthand for `archetypes.get(ArchetypeId::EMPTY).unwrap()`
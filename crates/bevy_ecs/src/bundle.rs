@@ -1164,6 +1164,7 @@ impl<'w> BundleInserter<'w> {
                 // SAFETY: Mutable references do not alias and will be dropped after this block
                 let (sparse_sets, entities) = {
                     let world = self.world.world_mut();
+                    world.archetypes.entity_moves += 1;
                     (&mut world.storages.sparse_sets, &mut world.entities)
                 };
 
@@ -1209,6 +1210,7 @@ impl<'w> BundleInserter<'w> {
                 // SAFETY: Mutable references do not alias and will be dropped after this block
                 let (archetypes_ptr, sparse_sets, entities) = {
                     let world = self.world.world_mut();
+                    world.archetypes.entity_moves += 1;
                     let archetype_ptr: *mut Archetype = world.archetypes.archetypes.as_mut_ptr();
                     (
                         archetype_ptr,
@@ -83,11 +83,16 @@ pub use bevy_ecs_macros::require;
 /// See the documentation for [`ComponentMutability`] for more details around this
 /// feature.
 ///
+/// Immutable components that are looked up by value can also be indexed by adding the
+/// `#[component(index)]` attribute, which implies `immutable`.
+/// See the [`index`] module level documentation for more details.
+///
 /// See the [`entity`] module level documentation to learn how to add or remove components from an entity.
 ///
 /// See the documentation for [`Query`] to learn how to access component data from a system.
 ///
 /// [`entity`]: crate::entity#usage
+/// [`index`]: crate::index
 /// [`Query`]: crate::system::Query
 /// [`ComponentMutability`]: crate::component::ComponentMutability
 ///
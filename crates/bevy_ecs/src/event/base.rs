@@ -19,6 +19,16 @@ use core::{
 /// Events can also be "triggered" on a [`World`], which will then cause any [`Observer`] of that trigger to run.
 ///
 /// This trait can be derived.
+/// When triggered for entities, derived events don't [propagate] by default. The path they
+/// propagate along and whether they propagate automatically can be set with the `#[event]`
+/// attribute:
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Event)]
+/// #[event(traversal = &'static ChildOf, auto_propagate)]
+/// struct Damage(u32);
+/// ```
 ///
 /// Events must be thread-safe.
 ///
@@ -28,6 +38,7 @@ use core::{
 /// [`Events<E>`]: super::Events
 /// [`EventReader`]: super::EventReader
 /// [`EventWriter`]: super::EventWriter
+/// [propagate]: crate::observer::Trigger::propagate
 #[diagnostic::on_unimplemented(
     message = "`{Self}` is not an `Event`",
     label = "invalid `Event`",
@@ -0,0 +1,310 @@
+//! Lookups of entities by the value of one of their components.
+//!
+//! Finding the entity with a given component value normally requires iterating over every entity
+//! with that component. Components that are looked up by value often can instead opt into being
+//! indexed with `#[component(index)]`, which keeps a [`ComponentValueIndex`] resource mapping each
+//! value to the entities that have it. [`IndexedQuery`] uses this index to find matching entities
+//! without a linear scan.
+//!
+//! ```
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_ecs::index::IndexedQuery;
+//! #[derive(Component, PartialEq, Eq, Hash, Clone)]
+//! #[component(index)]
+//! struct PlayerId(u32);
+//!
+//! #[derive(Component)]
+//! struct Score(u32);
+//!
+//! fn reward_player(mut players: IndexedQuery<PlayerId, &mut Score>) {
+//!     if let Some(mut score) = players.single_mut(&PlayerId(7)) {
+//!         score.0 += 10;
+//!     }
+//! }
+//! # bevy_ecs::system::assert_is_system(reward_player);
+//! ```
+//!
+//! Indexed components are always [`Immutable`], as the index could not observe in-place changes to
+//! their value. To change the value of an indexed component, insert a new one instead.
+
+use crate::{
+    component::{Component, HookContext, Immutable},
+    entity::{hash_set::EntityHashSet, Entity},
+    query::{QueryData, QueryFilter, ROQueryItem},
+    resource::Resource,
+    system::{Query, Res, SystemParam},
+    world::{DeferredWorld, FromWorld, World},
+};
+use bevy_platform_support::collections::HashMap;
+use core::hash::Hash;
+
+/// An [`Immutable`] [`Component`] whose values are indexed in a [`ComponentValueIndex`] resource.
+///
+/// This should be implemented by adding the `#[component(index)]` attribute when deriving
+/// [`Component`], which also makes the component immutable and registers the
+/// [`on_insert`](IndexedComponent::on_insert) and [`on_replace`](IndexedComponent::on_replace)
+/// hooks that keep the index current. Indexed components cannot define their own `on_insert` or
+/// `on_replace` hooks.
+///
+/// See the [module documentation](crate::index) for an example.
+pub trait IndexedComponent: Component<Mutability = Immutable> + Eq + Hash + Clone {
+    /// The `on_insert` component hook that adds the entity to the index under its new value.
+    ///
+    /// If the [`ComponentValueIndex`] resource doesn't exist yet, it is instead initialized from
+    /// all entities in the world once the hook's commands are applied.
+    fn on_insert(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+        let value = world.entity(entity).get::<Self>().unwrap().clone();
+        if let Some(mut index) = world.get_resource_mut::<ComponentValueIndex<Self>>() {
+            index.insert(value, entity);
+        } else {
+            world
+                .commands()
+                .init_resource::<ComponentValueIndex<Self>>();
+        }
+    }
+
+    /// The `on_replace` component hook that removes the entity from the index under its old value.
+    fn on_replace(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+        let value = world.entity(entity).get::<Self>().unwrap().clone();
+        if let Some(mut index) = world.get_resource_mut::<ComponentValueIndex<Self>>() {
+            index.remove(&value, entity);
+        }
+    }
+}
+
+/// A [`Resource`] mapping each value of the [`IndexedComponent`] `C` to the entities that have it.
+///
+/// This is created when the first `C` is inserted and kept current by the component's hooks.
+#[derive(Resource)]
+pub struct ComponentValueIndex<C: IndexedComponent> {
+    entities: HashMap<C, EntityHashSet>,
+}
+
+impl<C: IndexedComponent> FromWorld for ComponentValueIndex<C> {
+    fn from_world(world: &mut World) -> Self {
+        let mut index = Self {
+            entities: HashMap::default(),
+        };
+        for (entity, value) in world.query::<(Entity, &C)>().iter(world) {
+            index.insert(value.clone(), entity);
+        }
+        index
+    }
+}
+
+impl<C: IndexedComponent> ComponentValueIndex<C> {
+    /// Returns the entities whose `C` is equal to `value`, in arbitrary order.
+    pub fn entities(&self, value: &C) -> impl Iterator<Item = Entity> + '_ {
+        self.entities.get(value).into_iter().flatten().copied()
+    }
+
+    /// Returns `true` if any entity has a `C` equal to `value`.
+    pub fn contains(&self, value: &C) -> bool {
+        self.entities.contains_key(value)
+    }
+
+    /// Returns the distinct values of `C` and the entities with each of them.
+    pub fn iter(&self) -> impl Iterator<Item = (&C, &EntityHashSet)> {
+        self.entities.iter()
+    }
+
+    /// Returns the number of distinct values of `C`.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns `true` if no entity has a `C`.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    fn insert(&mut self, value: C, entity: Entity) {
+        self.entities.entry(value).or_default().insert(entity);
+    }
+
+    fn remove(&mut self, value: &C, entity: Entity) {
+        if let Some(entities) = self.entities.get_mut(value) {
+            entities.remove(&entity);
+            if entities.is_empty() {
+                self.entities.remove(value);
+            }
+        }
+    }
+}
+
+/// A [`SystemParam`] that looks up the entities matching a [`Query`] by the value of their
+/// [`IndexedComponent`] `C`, using its [`ComponentValueIndex`].
+///
+/// Lookups take constant time, rather than iterating over every entity with a `C`. The query
+/// doesn't need to access `C` itself, and entities with a matching `C` that don't match the query
+/// are skipped.
+///
+/// See the [module documentation](crate::index) for an example.
+#[derive(SystemParam)]
+pub struct IndexedQuery<
+    'w,
+    's,
+    C: IndexedComponent,
+    D: QueryData + 'static = Entity,
+    F: QueryFilter + 'static = (),
+> {
+    index: Option<Res<'w, ComponentValueIndex<C>>>,
+    query: Query<'w, 's, D, F>,
+}
+
+impl<'w, 's, C: IndexedComponent, D: QueryData, F: QueryFilter> IndexedQuery<'w, 's, C, D, F> {
+    /// Returns the entities matching the query whose `C` is equal to `value`, in arbitrary order.
+    pub fn entities<'a>(&'a self, value: &'a C) -> impl Iterator<Item = Entity> + 'a {
+        self.index_entities(value)
+            .filter(|entity| self.query.contains(*entity))
+    }
+
+    /// Iterates over the read-only query items of the entities whose `C` is equal to `value`,
+    /// in arbitrary order.
+    ///
+    /// Use [`single`](Self::single) to look up one item instead.
+    pub fn iter_by_value<'a>(
+        &'a self,
+        value: &'a C,
+    ) -> impl Iterator<Item = ROQueryItem<'a, D>> + 'a {
+        self.index_entities(value)
+            .filter_map(|entity| self.query.get(entity).ok())
+    }
+
+    /// Returns the read-only query item of an entity whose `C` is equal to `value`.
+    ///
+    /// If several entities match, which one is returned is unspecified.
+    pub fn single(&self, value: &C) -> Option<ROQueryItem<'_, D>> {
+        let entity = self
+            .index_entities(value)
+            .find(|entity| self.query.contains(*entity))?;
+        self.query.get(entity).ok()
+    }
+
+    /// Returns the query item of an entity whose `C` is equal to `value`.
+    ///
+    /// If several entities match, which one is returned is unspecified.
+    pub fn single_mut(&mut self, value: &C) -> Option<D::Item<'_>> {
+        let entity = self
+            .index
+            .as_ref()?
+            .entities(value)
+            .find(|entity| self.query.contains(*entity))?;
+        self.query.get_mut(entity).ok()
+    }
+
+    /// Returns the underlying [`Query`].
+    pub fn query(&self) -> &Query<'w, 's, D, F> {
+        &self.query
+    }
+
+    /// Returns the underlying [`Query`] mutably.
+    pub fn query_mut(&mut self) -> &mut Query<'w, 's, D, F> {
+        &mut self.query
+    }
+
+    /// Returns the [`ComponentValueIndex`] of `C`, or `None` if no `C` has been inserted yet.
+    pub fn index(&self) -> Option<&ComponentValueIndex<C>> {
+        self.index.as_deref()
+    }
+
+    fn index_entities<'a>(&'a self, value: &'a C) -> impl Iterator<Item = Entity> + 'a {
+        self.index
+            .as_deref()
+            .into_iter()
+            .flat_map(move |index| index.entities(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        prelude::{Component, With},
+        system::RunSystemOnce,
+    };
+    use alloc::vec::Vec;
+
+    #[derive(Component, PartialEq, Eq, Hash, Clone, Debug)]
+    #[component(index)]
+    struct PlayerId(u32);
+
+    #[derive(Component)]
+    struct Active;
+
+    fn lookup(world: &World, id: u32) -> Vec<Entity> {
+        let mut entities: Vec<_> = world
+            .resource::<ComponentValueIndex<PlayerId>>()
+            .entities(&PlayerId(id))
+            .collect();
+        entities.sort();
+        entities
+    }
+
+    #[test]
+    fn index_follows_inserts_replacements_and_removals() {
+        let mut world = World::new();
+        let a = world.spawn(PlayerId(1)).id();
+        let b = world.spawn(PlayerId(1)).id();
+        let c = world.spawn(PlayerId(2)).id();
+        assert_eq!(lookup(&world, 1), [a, b]);
+        assert_eq!(lookup(&world, 2), [c]);
+
+        world.entity_mut(b).insert(PlayerId(2));
+        assert_eq!(lookup(&world, 1), [a]);
+        assert_eq!(lookup(&world, 2), [b, c]);
+
+        world.entity_mut(a).remove::<PlayerId>();
+        world.despawn(c);
+        assert_eq!(lookup(&world, 1), []);
+        assert_eq!(lookup(&world, 2), [b]);
+
+        let index = world.resource::<ComponentValueIndex<PlayerId>>();
+        assert!(!index.contains(&PlayerId(1)));
+        assert_eq!(index.len(), 1);
+    }
+
+    #[test]
+    fn index_is_built_from_existing_entities() {
+        let mut world = World::new();
+        let a = world.spawn(PlayerId(1)).id();
+        world.remove_resource::<ComponentValueIndex<PlayerId>>();
+        let b = world.spawn(PlayerId(1)).id();
+        assert_eq!(lookup(&world, 1), [a, b]);
+    }
+
+    #[test]
+    fn indexed_query_lookups() {
+        let mut world = World::new();
+        world.spawn(PlayerId(1));
+        let active = world.spawn((PlayerId(1), Active)).id();
+        world.spawn((PlayerId(2), Active));
+
+        let (entities, items, found, missing) = world
+            .run_system_once(|query: IndexedQuery<PlayerId, Entity, With<Active>>| {
+                (
+                    query.entities(&PlayerId(1)).collect::<Vec<_>>(),
+                    query.iter_by_value(&PlayerId(1)).collect::<Vec<_>>(),
+                    query.single(&PlayerId(1)),
+                    query.single(&PlayerId(3)),
+                )
+            })
+            .unwrap();
+        assert_eq!(entities, [active]);
+        assert_eq!(items, [active]);
+        assert_eq!(found, Some(active));
+        assert_eq!(missing, None);
+    }
+
+    #[test]
+    fn indexed_query_without_index() {
+        let mut world = World::new();
+        let found = world
+            .run_system_once(|mut query: IndexedQuery<PlayerId>| {
+                assert!(query.index().is_none());
+                query.single_mut(&PlayerId(1))
+            })
+            .unwrap();
+        assert_eq!(found, None);
+    }
+}
@@ -15,7 +15,7 @@ use crate::{
     system::IntoObserverSystem,
     world::{DeferredWorld, *},
 };
-use alloc::vec::Vec;
+use alloc::{boxed::Box, vec::Vec};
 use bevy_platform_support::collections::HashMap;
 use bevy_ptr::Ptr;
 use core::{
@@ -128,6 +128,12 @@ impl<'w, E, B: Bundle> Trigger<'w, E, B> {
     ///
     /// You can prevent an event from propagating further using `propagate(false)`.
     ///
+    /// Events that propagate automatically also have a capture phase, which runs
+    /// [capture observers](Observer::capture) on every entity of the path, starting with the entity
+    /// furthest from the target and ending with the target, before any other observer runs. Calling
+    /// `propagate(false)` in a capture observer stops the event before it reaches the target's
+    /// regular observers. See [`PropagationPhase`] for the full order.
+    ///
     /// [`Traversal`]: crate::traversal::Traversal
     pub fn propagate(&mut self, should_propagate: bool) {
         *self.propagate = should_propagate;
@@ -140,6 +146,11 @@ impl<'w, E, B: Bundle> Trigger<'w, E, B> {
         *self.propagate
     }
 
+    /// Returns the [`PropagationPhase`] the event is in.
+    pub fn phase(&self) -> PropagationPhase {
+        self.trigger.phase
+    }
+
     /// Returns the source code location that triggered this observer.
     pub fn caller(&self) -> MaybeLocation {
         self.trigger.caller
@@ -274,6 +285,9 @@ pub struct ObserverDescriptor {
 
     /// The entities the observer is watching.
     entities: Vec<Entity>,
+
+    /// Whether the observer runs during the capture phase.
+    capture: bool,
 }
 
 impl ObserverDescriptor {
@@ -298,11 +312,19 @@ impl ObserverDescriptor {
         self
     }
 
+    /// Run the observer during the [capture phase](PropagationPhase::Capture) instead of the
+    /// target and bubble phases.
+    pub fn with_capture(mut self) -> Self {
+        self.capture = true;
+        self
+    }
+
     pub(crate) fn merge(&mut self, descriptor: &ObserverDescriptor) {
         self.events.extend(descriptor.events.iter().copied());
         self.components
             .extend(descriptor.components.iter().copied());
         self.entities.extend(descriptor.entities.iter().copied());
+        self.capture |= descriptor.capture;
     }
 
     /// Returns the `events` that the observer is watching.
@@ -319,6 +341,11 @@ impl ObserverDescriptor {
     pub fn entities(&self) -> &[Entity] {
         &self.entities
     }
+
+    /// Returns `true` if the observer runs during the [capture phase](PropagationPhase::Capture).
+    pub fn capture(&self) -> bool {
+        self.capture
+    }
 }
 
 /// Event trigger metadata for a given [`Observer`],
@@ -332,10 +359,41 @@ pub struct ObserverTrigger {
     components: SmallVec<[ComponentId; 2]>,
     /// The entity the trigger targeted.
     pub target: Entity,
+    /// The propagation phase the event is in.
+    pub phase: PropagationPhase,
     /// The location of the source code that triggered the obserer.
     pub caller: MaybeLocation,
 }
 
+/// The phase of event propagation during which an [`Observer`] runs, similar to the phases of DOM
+/// events.
+///
+/// When an event that [propagates](Trigger::propagate) is triggered for an entity, its
+/// [`Traversal`](crate::traversal::Traversal) defines a path from the target to, for example, the
+/// root of its hierarchy. The event then goes through these phases:
+///
+/// 1. [`Capture`](Self::Capture): [capture observers](Observer::capture) run for each entity of
+///    the path, from the end of the path to the target.
+/// 2. [`Target`](Self::Target): the other observers of the target run.
+/// 3. [`Bubble`](Self::Bubble): the other observers run for each entity of the path, from the
+///    target's next entity to the end of the path.
+///
+/// Calling [`Trigger::propagate`] with `false` during any phase stops the event after the current
+/// entity. The capture phase only happens for events that [propagate automatically], as the path
+/// must be known before any observer runs.
+///
+/// [propagate automatically]: crate::event::Event::AUTO_PROPAGATE
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PropagationPhase {
+    /// The event travels from the end of its path towards its target, running capture observers.
+    Capture,
+    /// The event is at its target. Events that don't propagate only have this phase.
+    #[default]
+    Target,
+    /// The event travels from its target towards the end of its path.
+    Bubble,
+}
+
 impl ObserverTrigger {
     /// Returns the components that the trigger targeted.
     pub fn components(&self) -> &[ComponentId] {
@@ -364,6 +422,22 @@ pub struct CachedObservers {
     component_observers: HashMap<ComponentId, CachedComponentObservers>,
     // Observers listening for this trigger fired at a specific entity
     entity_observers: EntityHashMap<ObserverMap>,
+    // Observers listening for this trigger during the capture phase
+    capture: Option<Box<CachedObservers>>,
+}
+
+impl CachedObservers {
+    fn is_empty(&self) -> bool {
+        self.map.is_empty()
+            && self.component_observers.is_empty()
+            && self.entity_observers.is_empty()
+            && self.capture.is_none()
+    }
+
+    /// Returns `true` if there are observers for the capture phase of this trigger.
+    pub(crate) fn has_capture_observers(&self) -> bool {
+        self.capture.is_some()
+    }
 }
 
 /// Metadata for observers. Stores a cache mapping trigger ids to the registered observers.
@@ -410,6 +484,7 @@ impl Observers {
         components: impl Iterator<Item = ComponentId> + Clone,
         data: &mut T,
         propagate: &mut bool,
+        phase: PropagationPhase,
         caller: MaybeLocation,
     ) {
         // SAFETY: You cannot get a mutable reference to `observers` from `DeferredWorld`
@@ -418,9 +493,15 @@ impl Observers {
             // SAFETY: There are no outstanding world references
             world.increment_trigger_id();
             let observers = world.observers();
-            let Some(observers) = observers.try_get_observers(event_type) else {
+            let Some(mut observers) = observers.try_get_observers(event_type) else {
                 return;
             };
+            if phase == PropagationPhase::Capture {
+                let Some(capture) = &observers.capture else {
+                    return;
+                };
+                observers = capture;
+            }
             // SAFETY: The only outstanding reference to world is `observers`
             (world.into_deferred(), observers)
         };
@@ -435,6 +516,7 @@ impl Observers {
                     event_type,
                     components: components.clone().collect(),
                     target,
+                    phase,
                     caller,
                 },
                 data.into(),
@@ -719,7 +801,10 @@ impl World {
         let descriptor = &observer_state.descriptor;
 
         for &event_type in &descriptor.events {
-            let cache = observers.get_observers(event_type);
+            let mut cache = observers.get_observers(event_type);
+            if descriptor.capture {
+                cache = cache.capture.get_or_insert_with(Default::default);
+            }
 
             if descriptor.components.is_empty() && descriptor.entities.is_empty() {
                 cache.map.insert(observer_entity, observer_state.runner);
@@ -737,7 +822,9 @@ impl World {
                             .component_observers
                             .entry(component)
                             .or_insert_with(|| {
-                                if let Some(flag) = Observers::is_archetype_cached(event_type) {
+                                if let Some(flag) = Observers::is_archetype_cached(event_type)
+                                    .filter(|_| !descriptor.capture)
+                                {
                                     archetypes.update_flags(component, flag, true);
                                 }
                                 CachedComponentObservers::default()
@@ -763,7 +850,15 @@ impl World {
         let observers = &mut self.observers;
 
         for &event_type in &descriptor.events {
-            let cache = observers.get_observers(event_type);
+            let parent_cache = observers.get_observers(event_type);
+            let cache = if descriptor.capture {
+                let Some(capture) = parent_cache.capture.as_deref_mut() else {
+                    continue;
+                };
+                capture
+            } else {
+                parent_cache
+            };
             if descriptor.components.is_empty() && descriptor.entities.is_empty() {
                 cache.map.remove(&entity);
             } else if descriptor.components.is_empty() {
@@ -798,7 +893,9 @@ impl World {
 
                     if observers.map.is_empty() && observers.entity_map.is_empty() {
                         cache.component_observers.remove(component);
-                        if let Some(flag) = Observers::is_archetype_cached(event_type) {
+                        if let Some(flag) = Observers::is_archetype_cached(event_type)
+                            .filter(|_| !descriptor.capture)
+                        {
                             if let Some(by_component) = archetypes.by_component.get(component) {
                                 for archetype in by_component.keys() {
                                     let archetype = &mut archetypes.archetypes[archetype.index()];
@@ -817,6 +914,15 @@ impl World {
                     }
                 }
             }
+
+            let cache = observers.get_observers(event_type);
+            if cache
+                .capture
+                .as_ref()
+                .is_some_and(|capture| capture.is_empty())
+            {
+                cache.capture = None;
+            }
         }
     }
 }
@@ -831,7 +937,10 @@ mod tests {
     use crate::component::ComponentId;
     use crate::{
         change_detection::MaybeLocation,
-        observer::{Observer, ObserverDescriptor, ObserverState, OnReplace},
+        observer::{
+            CachedObservers, Observer, ObserverDescriptor, ObserverState, OnReplace,
+            PropagationPhase,
+        },
         prelude::*,
         traversal::Traversal,
     };
@@ -1370,6 +1479,143 @@ mod tests {
         assert_eq!(vec!["child"], world.resource::<Order>().0);
     }
 
+    #[test]
+    fn observer_propagating_capture() {
+        let mut world = World::new();
+        world.init_resource::<Order>();
+
+        let parent = world
+            .spawn_empty()
+            .observe(|_: Trigger<EventPropagating>, mut res: ResMut<Order>| {
+                res.observed("parent");
+            })
+            .observe_capture(
+                |trigger: Trigger<EventPropagating>, mut res: ResMut<Order>| {
+                    assert_eq!(trigger.phase(), PropagationPhase::Capture);
+                    res.observed("parent_capture");
+                },
+            )
+            .id();
+
+        let child = world
+            .spawn(ChildOf(parent))
+            .observe(
+                |trigger: Trigger<EventPropagating>, mut res: ResMut<Order>| {
+                    assert_eq!(trigger.phase(), PropagationPhase::Target);
+                    res.observed("child");
+                },
+            )
+            .observe_capture(|_: Trigger<EventPropagating>, mut res: ResMut<Order>| {
+                res.observed("child_capture");
+            })
+            .id();
+
+        world.add_observer(
+            |trigger: Trigger<EventPropagating>, mut res: ResMut<Order>| {
+                if trigger.phase() == PropagationPhase::Bubble {
+                    res.observed("global_bubble");
+                }
+            },
+        );
+
+        world.flush();
+        world.trigger_targets(EventPropagating, child);
+        world.flush();
+        assert_eq!(
+            vec![
+                "parent_capture",
+                "child_capture",
+                "child",
+                "global_bubble",
+                "parent"
+            ],
+            world.resource::<Order>().0
+        );
+    }
+
+    #[test]
+    fn observer_propagating_capture_halt() {
+        let mut world = World::new();
+        world.init_resource::<Order>();
+
+        let parent = world
+            .spawn_empty()
+            .observe(|_: Trigger<EventPropagating>, mut res: ResMut<Order>| {
+                res.observed("parent");
+            })
+            .observe_capture(
+                |mut trigger: Trigger<EventPropagating>, mut res: ResMut<Order>| {
+                    res.observed("parent_capture");
+                    trigger.propagate(false);
+                },
+            )
+            .id();
+
+        let child = world
+            .spawn(ChildOf(parent))
+            .observe(|_: Trigger<EventPropagating>, mut res: ResMut<Order>| {
+                res.observed("child");
+            })
+            .id();
+
+        world.flush();
+        world.trigger_targets(EventPropagating, child);
+        world.flush();
+        assert_eq!(vec!["parent_capture"], world.resource::<Order>().0);
+    }
+
+    #[test]
+    fn observer_capture_despawn() {
+        let mut world = World::new();
+        world.init_resource::<Order>();
+
+        let entity = world.spawn_empty().id();
+        let observer = world
+            .spawn(
+                Observer::new(|_: Trigger<EventPropagating>, mut res: ResMut<Order>| {
+                    res.observed("capture");
+                })
+                .with_entity(entity)
+                .capture(),
+            )
+            .id();
+        world.flush();
+        world.trigger_targets(EventPropagating, entity);
+        assert_eq!(vec!["capture"], world.resource::<Order>().0);
+
+        world.despawn(observer);
+        let event_type = world.register_component::<EventPropagating>();
+        assert!(!world
+            .observers
+            .try_get_observers(event_type)
+            .is_some_and(CachedObservers::has_capture_observers));
+        world.trigger_targets(EventPropagating, entity);
+        assert_eq!(vec!["capture"], world.resource::<Order>().0);
+    }
+
+    #[test]
+    fn observer_propagating_derived_event() {
+        #[derive(Event)]
+        #[event(traversal = &'static ChildOf, auto_propagate)]
+        struct DerivedPropagating;
+
+        let mut world = World::new();
+        world.init_resource::<Order>();
+
+        let parent = world
+            .spawn_empty()
+            .observe(|_: Trigger<DerivedPropagating>, mut res: ResMut<Order>| {
+                res.observed("parent");
+            })
+            .id();
+        let child = world.spawn(ChildOf(parent)).id();
+
+        world.flush();
+        world.trigger_targets(DerivedPropagating, child);
+        world.flush();
+        assert_eq!(vec!["parent"], world.resource::<Order>().0);
+    }
+
     #[test]
     fn observer_propagating_join() {
         let mut world = World::new();
@@ -306,6 +306,34 @@ impl Observer {
         self
     }
 
+    /// Run this [`Observer`] during the [capture phase](crate::observer::PropagationPhase::Capture)
+    /// of propagating events, before the observers of their target run, instead of at the target
+    /// and while bubbling.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Event)]
+    /// #[event(traversal = &'static ChildOf, auto_propagate)]
+    /// struct Click;
+    ///
+    /// # let mut world = World::default();
+    /// let panel = world.spawn_empty().id();
+    /// let button = world.spawn(ChildOf(panel)).id();
+    ///
+    /// // Runs before any observer of `button`, and keeps the click from reaching it.
+    /// world.spawn(
+    ///     Observer::new(|mut trigger: Trigger<Click>| trigger.propagate(false))
+    ///         .with_entity(panel)
+    ///         .capture(),
+    /// );
+    /// world.flush();
+    /// world.trigger_targets(Click, button);
+    /// ```
+    pub fn capture(mut self) -> Self {
+        self.descriptor.capture = true;
+        self
+    }
+
     /// Observe the given `event`. This will cause the [`Observer`] to run whenever an event with the given [`ComponentId`]
     /// is triggered.
     /// # Safety
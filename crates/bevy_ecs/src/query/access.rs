@@ -835,6 +835,9 @@ pub struct FilteredAccess<T: SparseSetIndex> {
     // An array of filter sets to express `With` or `Without` clauses in disjunctive normal form, for example: `Or<(With<A>, With<B>)>`.
     // Filters like `(With<A>, Or<(With<B>, Without<C>)>` are expanded into `Or<((With<A>, With<B>), (With<A>, Without<C>))>`.
     pub(crate) filter_sets: Vec<AccessFilters<T>>,
+    // Accesses to components of entities other than the ones matched by the filters, for example
+    // entities reached through a relationship. These are also part of `access`.
+    pub(crate) unfiltered_access: Access<T>,
 }
 
 // This is needed since `#[derive(Clone)]` does not generate optimized `clone_from`.
@@ -844,6 +847,7 @@ impl<T: SparseSetIndex> Clone for FilteredAccess<T> {
             access: self.access.clone(),
             required: self.required.clone(),
             filter_sets: self.filter_sets.clone(),
+            unfiltered_access: self.unfiltered_access.clone(),
         }
     }
 
@@ -851,6 +855,7 @@ impl<T: SparseSetIndex> Clone for FilteredAccess<T> {
         self.access.clone_from(&source.access);
         self.required.clone_from(&source.required);
         self.filter_sets.clone_from(&source.filter_sets);
+        self.unfiltered_access.clone_from(&source.unfiltered_access);
     }
 }
 
@@ -939,6 +944,7 @@ impl<T: SparseSetIndex> FilteredAccess<T> {
             access: Access::default(),
             required: FixedBitSet::default(),
             filter_sets: vec![AccessFilters::default()],
+            unfiltered_access: Access::default(),
         }
     }
 
@@ -949,6 +955,7 @@ impl<T: SparseSetIndex> FilteredAccess<T> {
             access: Access::default(),
             required: FixedBitSet::default(),
             filter_sets: Vec::new(),
+            unfiltered_access: Access::default(),
         }
     }
 
@@ -978,6 +985,21 @@ impl<T: SparseSetIndex> FilteredAccess<T> {
         self.and_with(index);
     }
 
+    /// Returns a reference to the accesses that aren't limited to the entities matched by the filters.
+    #[inline]
+    pub fn unfiltered_access(&self) -> &Access<T> {
+        &self.unfiltered_access
+    }
+
+    /// Adds all of the accesses from `other` to `self`, applying to any entity rather than only to the
+    /// entities matched by the filters.
+    ///
+    /// This neither requires nor filters for the accessed components.
+    pub fn extend_unfiltered_access(&mut self, other: &Access<T>) {
+        self.access.extend(other);
+        self.unfiltered_access.extend(other);
+    }
+
     /// Adds access to the resource given by `index`.
     pub fn add_resource_read(&mut self, index: T) {
         self.access.add_resource_read(index.clone());
@@ -1024,6 +1046,7 @@ impl<T: SparseSetIndex> FilteredAccess<T> {
     /// Adds all of the accesses from `other` to `self`.
     pub fn extend_access(&mut self, other: &FilteredAccess<T>) {
         self.access.extend(&other.access);
+        self.unfiltered_access.extend(&other.unfiltered_access);
     }
 
     /// Returns `true` if this and `other` can be active at the same time.
@@ -1034,6 +1057,17 @@ impl<T: SparseSetIndex> FilteredAccess<T> {
             return false;
         }
 
+        // Likewise, unfiltered accesses may touch entities outside of the filtered archetypes.
+        if !self
+            .unfiltered_access
+            .is_components_compatible(&other.access)
+            || !other
+                .unfiltered_access
+                .is_components_compatible(&self.access)
+        {
+            return false;
+        }
+
         if self.access.is_components_compatible(&other.access) {
             return true;
         }
@@ -1071,6 +1105,7 @@ impl<T: SparseSetIndex> FilteredAccess<T> {
     pub fn extend(&mut self, other: &FilteredAccess<T>) {
         self.access.extend(&other.access);
         self.required.union_with(&other.required);
+        self.unfiltered_access.extend(&other.unfiltered_access);
 
         // We can avoid allocating a new array of bitsets if `other` contains just a single set of filters:
         // in this case we can short-circuit by performing an in-place union for each bitset.
@@ -0,0 +1,454 @@
+use crate::{
+    archetype::Archetype,
+    component::{ComponentId, Components, Tick},
+    entity::Entity,
+    query::{FilteredAccess, OptionFetch, QueryData, QueryFilter, ReadOnlyQueryData, WorldQuery},
+    storage::{Table, TableRow},
+    traversal::Traversal,
+    world::{unsafe_world_cell::UnsafeWorldCell, World},
+};
+use core::marker::PhantomData;
+
+/// Fetches or filters on the components of the entity that the queried entity points to
+/// through the [`Traversal`] `T`, such as the parent given by [`ChildOf`](crate::hierarchy::ChildOf).
+///
+/// As [`QueryData`], `Related<T, D>` returns `Some` with the `D` item of the related entity, or `None`
+/// if the queried entity has no `T`, or if the related entity doesn't match `D`.
+/// As a [`QueryFilter`], `Related<T, F>` only matches entities whose related entity matches `F`.
+///
+/// The related entity may lie outside of the archetypes matched by the query, so the components
+/// accessed through `D` or `F` conflict with mutable access to them on any entity.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::query::Related;
+/// #
+/// # #[derive(Component)]
+/// # struct Health(f32);
+/// # #[derive(Component)]
+/// # struct Vehicle;
+/// #
+/// // Passengers are the children of vehicles.
+/// fn passenger_system(passengers: Query<(Entity, Related<&ChildOf, &Health>), Related<&ChildOf, With<Vehicle>>>) {
+///     for (passenger, vehicle_health) in &passengers {
+///         if vehicle_health.is_some_and(|health| health.0 <= 0.0) {
+///             // The vehicle has been destroyed.
+///         }
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(passenger_system);
+/// ```
+pub struct Related<T, Q>(PhantomData<(T, Q)>);
+
+/// Fetches or filters on the components of the nearest ancestor of the queried entity,
+/// walking up through the [`Traversal`] `T` until an entity matches.
+///
+/// As [`QueryData`], `Ancestor<T, D>` returns `Some` with the `D` item of the nearest ancestor that
+/// matches `D`, or `None` if there is no such ancestor.
+/// As a [`QueryFilter`], `Ancestor<T, F>` only matches entities with an ancestor that matches `F`.
+///
+/// Like [`Related`], the components accessed through `D` or `F` conflict with mutable access to
+/// them on any entity.
+///
+/// # Warning
+///
+/// For relationship graphs that contain loops, this could loop infinitely.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::query::Ancestor;
+/// #
+/// # #[derive(Component)]
+/// # struct Visible(bool);
+/// #
+/// fn visibility_system(query: Query<(Entity, Ancestor<&ChildOf, &Visible>)>) {
+///     for (entity, inherited) in &query {
+///         let visible = inherited.is_none_or(|visible| visible.0);
+///     }
+/// }
+/// # bevy_ecs::system::assert_is_system(visibility_system);
+/// ```
+pub struct Ancestor<T, Q>(PhantomData<(T, Q)>);
+
+#[doc(hidden)]
+pub struct RelatedFetch<'w, T: WorldQuery, Q: WorldQuery> {
+    traversal: OptionFetch<'w, T>,
+    // The fetch of a query item doesn't have access to the state, but it is needed to look up the
+    // related entities.
+    state: (T::State, Q::State),
+    world: UnsafeWorldCell<'w>,
+    last_run: Tick,
+    this_run: Tick,
+}
+
+impl<T: WorldQuery, Q: WorldQuery> Clone for RelatedFetch<'_, T, Q>
+where
+    T::State: Clone,
+    Q::State: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            traversal: self.traversal.clone(),
+            state: self.state.clone(),
+            world: self.world,
+            last_run: self.last_run,
+            this_run: self.this_run,
+        }
+    }
+}
+
+impl<'w, T: Traversal<()>, Q: WorldQuery> RelatedFetch<'w, T, Q> {
+    /// Follows `T` from `entity`, and calls `f` with a fetch of `Q` set to each entity reached that
+    /// matches the component set of `Q`, until it returns `Some`.
+    /// If `ancestors` is `false`, only the first entity reached is visited.
+    ///
+    /// # Safety
+    ///
+    /// Must uphold the invariants of [`QueryData::fetch`], and the query must have registered read
+    /// access to the components of `T` and `Q` on every entity.
+    unsafe fn find<O>(
+        &mut self,
+        entity: Entity,
+        table_row: TableRow,
+        ancestors: bool,
+        mut f: impl FnMut(&mut Q::Fetch<'w>, Entity, TableRow) -> Option<O>,
+    ) -> Option<O> {
+        // SAFETY: The invariants are upheld by the caller.
+        let item =
+            unsafe { <Option<T> as QueryData>::fetch(&mut self.traversal, entity, table_row) };
+        let mut next = T::traverse(item?, &());
+        while let Some(target) = next {
+            let (archetype, table, table_row) = self.locate(target)?;
+            if Q::matches_component_set(&self.state.1, &|id| archetype.contains(id)) {
+                // SAFETY: The caller ensures that `Q` has read access to every entity, `target`
+                // belongs to `archetype` and `table`, and the state was initialized for this world.
+                let found = unsafe {
+                    let mut fetch =
+                        Q::init_fetch(self.world, &self.state.1, self.last_run, self.this_run);
+                    Q::set_archetype(&mut fetch, &self.state.1, archetype, table);
+                    f(&mut fetch, target, table_row)
+                };
+                if found.is_some() {
+                    return found;
+                }
+            }
+            if !ancestors || !T::matches_component_set(&self.state.0, &|id| archetype.contains(id))
+            {
+                return None;
+            }
+            // SAFETY: The caller ensures that `T` has read access to every entity, `target`
+            // belongs to `archetype` and `table`, and the state was initialized for this world.
+            let item = unsafe {
+                let mut fetch =
+                    T::init_fetch(self.world, &self.state.0, self.last_run, self.this_run);
+                T::set_archetype(&mut fetch, &self.state.0, archetype, table);
+                T::fetch(&mut fetch, target, table_row)
+            };
+            next = T::traverse(item, &());
+        }
+        None
+    }
+
+    /// Returns the archetype, table and table row of `entity`, if it exists.
+    fn locate(&self, entity: Entity) -> Option<(&'w Archetype, &'w Table, TableRow)> {
+        let location = self.world.entities().get(entity)?;
+        let archetype = self.world.archetypes().get(location.archetype_id)?;
+        // SAFETY: Only the metadata of the table is accessed here, and its columns are only read
+        // through `Q` and `T`, whose access has been registered.
+        let table = unsafe { self.world.storages() }
+            .tables
+            .get(location.table_id)?;
+        Some((archetype, table, location.table_row))
+    }
+}
+
+macro_rules! impl_related_query {
+    ($name:ident, $ancestors:literal) => {
+        // SAFETY:
+        // - The traversal component of the queried entity is accessed like `Option<T>`.
+        // - `T` and `Q` are only read on other entities, and this is registered as unfiltered access
+        //   in `update_component_access`.
+        unsafe impl<T: Traversal<()>, Q: WorldQuery> WorldQuery for $name<T, Q>
+        where
+            T::State: Clone,
+            Q::State: Clone,
+        {
+            type Fetch<'w> = RelatedFetch<'w, T, Q>;
+            type State = (T::State, Q::State);
+
+            fn shrink_fetch<'wlong: 'wshort, 'wshort>(
+                fetch: Self::Fetch<'wlong>,
+            ) -> Self::Fetch<'wshort> {
+                RelatedFetch {
+                    traversal: <Option<T> as WorldQuery>::shrink_fetch(fetch.traversal),
+                    state: fetch.state,
+                    world: fetch.world,
+                    last_run: fetch.last_run,
+                    this_run: fetch.this_run,
+                }
+            }
+
+            #[inline]
+            unsafe fn init_fetch<'w>(
+                world: UnsafeWorldCell<'w>,
+                state: &Self::State,
+                last_run: Tick,
+                this_run: Tick,
+            ) -> Self::Fetch<'w> {
+                RelatedFetch {
+                    // SAFETY: The invariants are upheld by the caller.
+                    traversal: unsafe {
+                        <Option<T> as WorldQuery>::init_fetch(world, &state.0, last_run, this_run)
+                    },
+                    state: state.clone(),
+                    world,
+                    last_run,
+                    this_run,
+                }
+            }
+
+            const IS_DENSE: bool = T::IS_DENSE;
+
+            #[inline]
+            unsafe fn set_archetype<'w>(
+                fetch: &mut Self::Fetch<'w>,
+                state: &Self::State,
+                archetype: &'w Archetype,
+                table: &'w Table,
+            ) {
+                // SAFETY: The invariants are upheld by the caller.
+                unsafe {
+                    <Option<T> as WorldQuery>::set_archetype(
+                        &mut fetch.traversal,
+                        &state.0,
+                        archetype,
+                        table,
+                    );
+                }
+            }
+
+            #[inline]
+            unsafe fn set_table<'w>(
+                fetch: &mut Self::Fetch<'w>,
+                state: &Self::State,
+                table: &'w Table,
+            ) {
+                // SAFETY: The invariants are upheld by the caller.
+                unsafe { <Option<T> as WorldQuery>::set_table(&mut fetch.traversal, &state.0, table) };
+            }
+
+            fn update_component_access(
+                state: &Self::State,
+                access: &mut FilteredAccess<ComponentId>,
+            ) {
+                <Option<T> as WorldQuery>::update_component_access(&state.0, access);
+
+                // The related entities may lie outside of the archetypes matched by the filters,
+                // so their access must be compatible with accesses to any entity.
+                let mut related = FilteredAccess::matches_everything();
+                Q::update_component_access(&state.1, &mut related);
+                if $ancestors {
+                    T::update_component_access(&state.0, &mut related);
+                }
+                assert!(
+                    !related.access().has_any_component_write()
+                        && related.access().is_components_compatible(access.access()),
+                    "{} conflicts with a previous access in this query. Shared access cannot coincide with exclusive access.",
+                    core::any::type_name::<Self>(),
+                );
+                access.extend_unfiltered_access(related.access());
+            }
+
+            fn init_state(world: &mut World) -> Self::State {
+                (T::init_state(world), Q::init_state(world))
+            }
+
+            fn get_state(components: &Components) -> Option<Self::State> {
+                Some((T::get_state(components)?, Q::get_state(components)?))
+            }
+
+            fn matches_component_set(
+                _state: &Self::State,
+                _set_contains_id: &impl Fn(ComponentId) -> bool,
+            ) -> bool {
+                true
+            }
+        }
+
+        // SAFETY: `Self::ReadOnly` is `Self`, which only reads components.
+        unsafe impl<T: Traversal<()>, D: ReadOnlyQueryData> QueryData for $name<T, D>
+        where
+            T::State: Clone,
+            D::State: Clone,
+        {
+            type ReadOnly = Self;
+            type Item<'w> = Option<D::Item<'w>>;
+
+            fn shrink<'wlong: 'wshort, 'wshort>(item: Self::Item<'wlong>) -> Self::Item<'wshort> {
+                item.map(D::shrink)
+            }
+
+            #[inline]
+            unsafe fn fetch<'w>(
+                fetch: &mut Self::Fetch<'w>,
+                entity: Entity,
+                table_row: TableRow,
+            ) -> Self::Item<'w> {
+                // SAFETY: The invariants are upheld by the caller, and the read access to `D` and `T`
+                // on every entity is registered in `update_component_access`.
+                unsafe {
+                    fetch.find(entity, table_row, $ancestors, |fetch, entity, table_row| {
+                        Some(D::fetch(fetch, entity, table_row))
+                    })
+                }
+            }
+        }
+
+        // SAFETY: `D` is read-only, and `T` is read-only since it is a `Traversal`.
+        unsafe impl<T: Traversal<()>, D: ReadOnlyQueryData> ReadOnlyQueryData for $name<T, D>
+        where
+            T::State: Clone,
+            D::State: Clone,
+        {
+        }
+
+        // SAFETY: `filter_fetch` only reads the components of `T` and `F`, which is registered in
+        // `update_component_access`.
+        unsafe impl<T: Traversal<()>, F: QueryFilter> QueryFilter for $name<T, F>
+        where
+            T::State: Clone,
+            F::State: Clone,
+        {
+            const IS_ARCHETYPAL: bool = false;
+
+            #[inline]
+            unsafe fn filter_fetch(
+                fetch: &mut Self::Fetch<'_>,
+                entity: Entity,
+                table_row: TableRow,
+            ) -> bool {
+                // SAFETY: The invariants are upheld by the caller, and the read access to `F` and `T`
+                // on every entity is registered in `update_component_access`.
+                unsafe {
+                    fetch.find(entity, table_row, $ancestors, |fetch, entity, table_row| {
+                        F::filter_fetch(fetch, entity, table_row).then_some(())
+                    })
+                }
+                .is_some()
+            }
+        }
+    };
+}
+
+impl_related_query!(Related, false);
+impl_related_query!(Ancestor, true);
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        component::Component,
+        hierarchy::ChildOf,
+        prelude::*,
+        query::{Ancestor, Related},
+        system::{assert_is_system, RunSystemOnce},
+    };
+    use alloc::{vec, vec::Vec};
+
+    #[derive(Component, PartialEq, Debug)]
+    struct A(u32);
+
+    #[derive(Component)]
+    struct B;
+
+    #[test]
+    fn related_query_data() {
+        let mut world = World::new();
+        let root = world.spawn(A(0)).id();
+        let parent = world.spawn((A(1), ChildOf(root))).id();
+        let child = world.spawn(ChildOf(parent)).id();
+        let grandchild = world.spawn(ChildOf(child)).id();
+
+        let mut query = world.query::<(Entity, Related<&ChildOf, &A>)>();
+        let mut related: Vec<_> = query.iter(&world).collect();
+        related.sort_by_key(|(entity, _)| *entity);
+        assert_eq!(
+            related,
+            vec![
+                (root, None),
+                (parent, Some(&A(0))),
+                (child, Some(&A(1))),
+                (grandchild, None)
+            ]
+        );
+
+        let mut query = world.query::<Ancestor<&ChildOf, &A>>();
+        assert_eq!(query.get(&world, grandchild).unwrap(), Some(&A(1)));
+        assert_eq!(query.get(&world, parent).unwrap(), Some(&A(0)));
+        assert_eq!(query.get(&world, root).unwrap(), None);
+    }
+
+    #[test]
+    fn related_query_filter() {
+        let mut world = World::new();
+        let root = world.spawn(B).id();
+        let parent = world.spawn(ChildOf(root)).id();
+        let child = world.spawn(ChildOf(parent)).id();
+
+        let mut query = world.query_filtered::<Entity, Related<&ChildOf, With<B>>>();
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), vec![parent]);
+
+        let mut query = world.query_filtered::<Entity, Ancestor<&ChildOf, With<B>>>();
+        let mut entities: Vec<_> = query.iter(&world).collect();
+        entities.sort();
+        assert_eq!(entities, vec![parent, child]);
+    }
+
+    #[test]
+    fn related_query_filter_change_detection() {
+        let mut world = World::new();
+        let parent = world.spawn(A(0)).id();
+        let child = world.spawn(ChildOf(parent)).id();
+
+        let mut query = world.query_filtered::<Entity, Related<&ChildOf, Changed<A>>>();
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), vec![child]);
+
+        world.clear_trackers();
+        assert_eq!(query.iter(&world).count(), 0);
+
+        world.get_mut::<A>(parent).unwrap().0 = 1;
+        assert_eq!(query.iter(&world).collect::<Vec<_>>(), vec![child]);
+    }
+
+    #[test]
+    #[should_panic = "conflicts with a previous access in this query"]
+    fn related_conflicts_with_write_in_same_query() {
+        let mut world = World::new();
+        world.query::<(&mut A, Related<&ChildOf, &A>)>();
+    }
+
+    #[test]
+    #[should_panic = "error[B0001]"]
+    fn related_conflicts_with_disjoint_write() {
+        fn system(
+            _related: Query<Related<&ChildOf, &A>, With<B>>,
+            _write: Query<&mut A, Without<B>>,
+        ) {
+        }
+
+        let mut world = World::new();
+        world.run_system_once(system).unwrap();
+    }
+
+    #[test]
+    fn related_read_is_compatible_with_reads() {
+        fn system(_related: Query<Ancestor<&ChildOf, &A>>, _read: Query<&A>) {}
+        assert_is_system(system);
+
+        let mut world = World::new();
+        world.run_system_once(system).unwrap();
+    }
+}
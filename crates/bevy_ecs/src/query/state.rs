@@ -186,6 +186,7 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
                 if state.new_archetype_internal(archetype) {
                     state.update_archetype_component_access(archetype, access);
                 }
+                state.update_unfiltered_archetype_component_access(archetype, access);
             }
         }
         state.archetype_generation = world.archetypes.generation();
@@ -630,6 +631,8 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
             // SAFETY: The caller ensures that `archetype` is from the World the state was initialized from.
             unsafe { self.update_archetype_component_access(archetype, access) };
         }
+        // SAFETY: The caller ensures that `archetype` is from the World the state was initialized from.
+        unsafe { self.update_unfiltered_archetype_component_access(archetype, access) };
     }
 
     /// Process the given [`Archetype`] to update internal metadata about the [`Table`](crate::storage::Table)s
@@ -734,6 +737,40 @@ impl<D: QueryData, F: QueryFilter> QueryState<D, F> {
         }
     }
 
+    /// For the given `archetype`, adds the accesses of this query that aren't limited to the matched
+    /// archetypes, such as reads of related entities, to `access`.
+    ///
+    /// Unlike [`QueryState::update_archetype_component_access`], this must be called for every archetype,
+    /// whether or not it is matched by the query.
+    ///
+    /// # Safety
+    /// `archetype` must be from the `World` this state was initialized from.
+    pub unsafe fn update_unfiltered_archetype_component_access(
+        &self,
+        archetype: &Archetype,
+        access: &mut Access<ArchetypeComponentId>,
+    ) {
+        let unfiltered_access = self.component_access.unfiltered_access();
+        let (component_reads, component_reads_inverted) =
+            unfiltered_access.component_reads_and_writes();
+        if !component_reads_inverted {
+            component_reads.for_each(|id| {
+                if let Some(id) = archetype.get_archetype_component_id(id) {
+                    access.add_component_read(id);
+                }
+            });
+            return;
+        }
+
+        for (component_id, archetype_component_id) in
+            archetype.components_with_archetype_component_id()
+        {
+            if unfiltered_access.has_component_read(component_id) {
+                access.add_component_read(archetype_component_id);
+            }
+        }
+    }
+
     /// Use this to transform a [`QueryState`] into a more generic [`QueryState`].
     /// This can be useful for passing to another function that might take the more general form.
     /// See [`Query::transmute_lens`](crate::system::Query::transmute_lens) for more details.
@@ -0,0 +1,645 @@
+use alloc::{format, vec::Vec};
+use core::slice;
+
+use crate::{
+    component::{Component, HookContext, Mutable},
+    entity::{ComponentCloneCtx, Entity, VisitEntities, VisitEntitiesMut},
+    system::{
+        command::HandleError,
+        entity_command::{self, CommandWithEntity},
+        error_handler, Commands,
+    },
+    world::{DeferredWorld, EntityWorldMut},
+};
+use log::warn;
+
+use super::RelationshipSourceCollection;
+
+/// The iterator type for the source entities in a [`ManyRelationshipTarget`] collection,
+/// as defined in the [`RelationshipSourceCollection`] trait.
+pub type ManySourceIter<'w, R> =
+    <<R as ManyRelationshipTarget>::Collection as RelationshipSourceCollection>::SourceIter<'w>;
+
+/// A [`Component`] on a "source" [`Entity`] that references any number of "target" entities, with some data `D` on
+/// each of these "edges", creating a many-to-many relationship between them.
+///
+/// This is the many-to-many counterpart of [`Relationship`](super::Relationship): every [`ManyRelationship`] has a
+/// corresponding [`ManyRelationshipTarget`] type (and vice-versa), which exists on each of the "target" entities and
+/// contains the list of all "source" entities that relate to the given "target". The edges, and their data, are only
+/// stored on the source, in a [`RelationshipEdges`] collection.
+///
+/// Like [`Relationship`](super::Relationship), the [`ManyRelationship`] component is the "source of truth" and is
+/// kept in sync with the [`ManyRelationshipTarget`] components via component hooks. [`ManyRelationship`] components
+/// are immutable: to add or remove a single edge, use [`EntityWorldMut::relate`] and [`EntityWorldMut::unrelate`]
+/// (or their [`EntityCommands`](crate::system::EntityCommands) equivalents), or insert a new component with the
+/// updated [`RelationshipEdges`].
+///
+/// [`ManyRelationship`] and [`ManyRelationshipTarget`] should always be derived via the [`Component`] trait to ensure
+/// the hooks are set up properly.
+///
+/// ```
+/// # use bevy_ecs::component::Component;
+/// # use bevy_ecs::entity::Entity;
+/// # use bevy_ecs::relationship::RelationshipEdges;
+/// #[derive(Component)]
+/// #[many_relationship(relationship_target = LikedBy)]
+/// pub struct Likes(RelationshipEdges<f32>);
+///
+/// #[derive(Component)]
+/// #[many_relationship_target(relationship = Likes)]
+/// pub struct LikedBy(Vec<Entity>);
+/// ```
+///
+/// When deriving [`ManyRelationshipTarget`] you can specify a [`LinkedDespawn`] policy, to automatically despawn
+/// some of the entities stored in an entity's [`ManyRelationshipTarget`] when that entity is despawned:
+/// `#[many_relationship_target(relationship = Likes, linked_despawn)]` despawns all of them, and
+/// `#[many_relationship_target(relationship = Likes, linked_despawn = orphans)]` only despawns those that are left
+/// without any target.
+pub trait ManyRelationship: Component + Sized {
+    /// The [`Component`] added to the "target" entities of this [`ManyRelationship`], which contains the list of all
+    /// "source" entities that relate to the "target".
+    type RelationshipTarget: ManyRelationshipTarget<Relationship = Self>;
+
+    /// The data stored on each edge of the relationship.
+    type Data: Send + Sync + 'static;
+
+    /// Gets the target entities of this [`ManyRelationship`], and the data of each edge.
+    fn edges(&self) -> &RelationshipEdges<Self::Data>;
+
+    /// Creates this [`ManyRelationship`] from the given `edges`.
+    fn from_edges(edges: RelationshipEdges<Self::Data>) -> Self;
+
+    /// Converts this [`ManyRelationship`] into its edges.
+    fn into_edges(self) -> RelationshipEdges<Self::Data>;
+
+    /// The `on_insert` component hook that maintains the [`ManyRelationship`] / [`ManyRelationshipTarget`]
+    /// connection.
+    fn on_insert(mut world: DeferredWorld, HookContext { entity, caller, .. }: HookContext) {
+        let targets: Vec<Entity> = world
+            .entity(entity)
+            .get::<Self>()
+            .unwrap()
+            .edges()
+            .targets()
+            .collect();
+
+        let mut has_invalid_edges = false;
+        for target_entity in targets {
+            if target_entity == entity {
+                warn!(
+                    "{}The {} relationship on entity {entity:?} points to itself. The invalid edge has been removed.",
+                    caller.map(|location|format!("{location}: ")).unwrap_or_default(),
+                    core::any::type_name::<Self>(),
+                );
+                has_invalid_edges = true;
+                continue;
+            }
+            if let Ok(mut target_entity_mut) = world.get_entity_mut(target_entity) {
+                if let Some(mut relationship_target) =
+                    target_entity_mut.get_mut::<Self::RelationshipTarget>()
+                {
+                    relationship_target.collection_mut_risky().add(entity);
+                } else {
+                    let mut target =
+                        <Self::RelationshipTarget as ManyRelationshipTarget>::with_capacity(1);
+                    target.collection_mut_risky().add(entity);
+                    world.commands().entity(target_entity).insert(target);
+                }
+            } else {
+                warn!(
+                    "{}The {} relationship on entity {entity:?} relates to {target_entity:?}, which does not exist. The invalid edge has been removed.",
+                    caller.map(|location|format!("{location}: ")).unwrap_or_default(),
+                    core::any::type_name::<Self>(),
+                );
+                has_invalid_edges = true;
+            }
+        }
+
+        if has_invalid_edges {
+            world
+                .commands()
+                .entity(entity)
+                .queue(|mut entity: EntityWorldMut| {
+                    let id = entity.id();
+                    let Some(relationship) = entity.take::<Self>() else {
+                        return;
+                    };
+                    let edges: RelationshipEdges<Self::Data> = relationship
+                        .into_edges()
+                        .into_iter()
+                        .filter(|(target, _)| {
+                            *target != id && entity.world().get_entity(*target).is_ok()
+                        })
+                        .collect();
+                    if !edges.is_empty() {
+                        entity.insert(Self::from_edges(edges));
+                    }
+                });
+        }
+    }
+
+    /// The `on_replace` component hook that maintains the [`ManyRelationship`] / [`ManyRelationshipTarget`]
+    /// connection.
+    // note: think of this as "on_drop"
+    fn on_replace(mut world: DeferredWorld, HookContext { entity, .. }: HookContext) {
+        let targets: Vec<Entity> = world
+            .entity(entity)
+            .get::<Self>()
+            .unwrap()
+            .edges()
+            .targets()
+            .collect();
+
+        for target_entity in targets {
+            if let Ok(mut target_entity_mut) = world.get_entity_mut(target_entity) {
+                if let Some(mut relationship_target) =
+                    target_entity_mut.get_mut::<Self::RelationshipTarget>()
+                {
+                    relationship_target.collection_mut_risky().remove(entity);
+                    if relationship_target.len() == 0 {
+                        if let Some(mut entity) = world.commands().get_entity(target_entity) {
+                            // this "remove" operation must check emptiness because in the event that an identical
+                            // relationship is inserted on top, this despawn would result in the removal of that identical
+                            // relationship ... not what we want!
+                            entity.queue(|mut entity: EntityWorldMut| {
+                                if entity
+                                    .get::<Self::RelationshipTarget>()
+                                    .is_some_and(ManyRelationshipTarget::is_empty)
+                                {
+                                    entity.remove::<Self::RelationshipTarget>();
+                                }
+                            });
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// What happens to the "source" entities stored in an entity's [`ManyRelationshipTarget`] when that entity is
+/// despawned.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum LinkedDespawn {
+    /// The sources are kept, and only their edges to the despawned entity are removed.
+    #[default]
+    None,
+    /// All of the sources are despawned.
+    All,
+    /// The sources that have no other target left are despawned.
+    Orphans,
+}
+
+/// A [`Component`] containing the collection of entities that relate to this [`Entity`] via the associated
+/// [`ManyRelationship`] type. See the [`ManyRelationship`] documentation for more information.
+pub trait ManyRelationshipTarget: Component<Mutability = Mutable> + Sized {
+    /// What happens to the related entities targeting this entity when it's despawned, or when it's cloned
+    /// [recursively](crate::entity::EntityClonerBuilder::recursive).
+    ///
+    /// With [`LinkedDespawn::All`], the related entities are also despawned or cloned. With
+    /// [`LinkedDespawn::Orphans`], only the related entities that have no other target are despawned, and none are
+    /// cloned. This defaults to [`LinkedDespawn::None`] when derived.
+    const LINKED_DESPAWN: LinkedDespawn;
+    /// The [`ManyRelationship`] that populates this [`ManyRelationshipTarget`] collection.
+    type Relationship: ManyRelationship<RelationshipTarget = Self>;
+    /// The collection type that stores the "source" entities for this [`ManyRelationshipTarget`] component.
+    type Collection: RelationshipSourceCollection;
+
+    /// Returns a reference to the stored [`ManyRelationshipTarget::Collection`].
+    fn collection(&self) -> &Self::Collection;
+    /// Returns a mutable reference to the stored [`ManyRelationshipTarget::Collection`].
+    ///
+    /// # Warning
+    /// This should generally not be called by user code, as modifying the internal collection could invalidate the relationship.
+    fn collection_mut_risky(&mut self) -> &mut Self::Collection;
+
+    /// Creates a new [`ManyRelationshipTarget`] from the given [`ManyRelationshipTarget::Collection`].
+    ///
+    /// # Warning
+    /// This should generally not be called by user code, as constructing the internal collection could invalidate the relationship.
+    fn from_collection_risky(collection: Self::Collection) -> Self;
+
+    /// The `on_replace` component hook that maintains the [`ManyRelationship`] / [`ManyRelationshipTarget`]
+    /// connection, by removing the edges to this entity from its sources.
+    // note: think of this as "on_drop"
+    fn on_replace(mut world: DeferredWorld, HookContext { entity, caller, .. }: HookContext) {
+        // NOTE: this unsafe code is an optimization. We could make this safe, but it would require
+        // copying the ManyRelationshipTarget collection
+        // SAFETY: This only reads the Self component and queues commands
+        unsafe {
+            let world = world.as_unsafe_world_cell();
+            let relationship_target = world.get_entity(entity).unwrap().get::<Self>().unwrap();
+            let mut commands = world.get_raw_command_queue();
+            for source_entity in relationship_target.iter() {
+                if world.get_entity(source_entity).is_some() {
+                    commands.push(
+                        remove_edge::<Self::Relationship>(entity)
+                            .with_entity(source_entity)
+                            .handle_error_with(error_handler::silent()),
+                    );
+                } else {
+                    warn!(
+                        "{}Tried to remove a {} edge from non-existent entity {}",
+                        caller
+                            .map(|location| format!("{location}: "))
+                            .unwrap_or_default(),
+                        core::any::type_name::<Self::Relationship>(),
+                        source_entity
+                    );
+                }
+            }
+        }
+    }
+
+    /// The `on_despawn` component hook that despawns entities stored in an entity's [`ManyRelationshipTarget`] when
+    /// that entity is despawned, according to [`ManyRelationshipTarget::LINKED_DESPAWN`].
+    // note: think of this as "on_drop"
+    fn on_despawn(mut world: DeferredWorld, HookContext { entity, caller, .. }: HookContext) {
+        if Self::LINKED_DESPAWN == LinkedDespawn::None {
+            return;
+        }
+
+        // NOTE: this unsafe code is an optimization. We could make this safe, but it would require
+        // copying the ManyRelationshipTarget collection
+        // SAFETY: This only reads the Self component and queues despawn commands
+        unsafe {
+            let world = world.as_unsafe_world_cell();
+            let relationship_target = world.get_entity(entity).unwrap().get::<Self>().unwrap();
+            let mut commands = world.get_raw_command_queue();
+            for source_entity in relationship_target.iter() {
+                if world.get_entity(source_entity).is_none() {
+                    warn!(
+                        "{}Tried to despawn non-existent entity {}",
+                        caller
+                            .map(|location| format!("{location}: "))
+                            .unwrap_or_default(),
+                        source_entity
+                    );
+                } else if Self::LINKED_DESPAWN == LinkedDespawn::All {
+                    commands.push(
+                        entity_command::despawn()
+                            .with_entity(source_entity)
+                            .handle_error_with(error_handler::silent()),
+                    );
+                } else {
+                    commands.push(
+                        despawn_if_orphaned::<Self::Relationship>(entity)
+                            .with_entity(source_entity)
+                            .handle_error_with(error_handler::silent()),
+                    );
+                }
+            }
+        }
+    }
+
+    /// Creates this [`ManyRelationshipTarget`] with the given pre-allocated entity capacity.
+    fn with_capacity(capacity: usize) -> Self {
+        let collection =
+            <Self::Collection as RelationshipSourceCollection>::with_capacity(capacity);
+        Self::from_collection_risky(collection)
+    }
+
+    /// Iterates the entities stored in this collection.
+    #[inline]
+    fn iter(&self) -> ManySourceIter<'_, Self> {
+        self.collection().iter()
+    }
+
+    /// Returns the number of entities in this collection.
+    #[inline]
+    fn len(&self) -> usize {
+        self.collection().len()
+    }
+
+    /// Returns true if this entity collection is empty.
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.collection().is_empty()
+    }
+}
+
+/// An [`EntityCommand`](crate::system::EntityCommand) that removes the edge to `target` from the `R`
+/// [`ManyRelationship`] of an entity, and removes the component entirely if that was its last edge.
+fn remove_edge<R: ManyRelationship>(target: Entity) -> impl FnOnce(EntityWorldMut) {
+    move |mut entity: EntityWorldMut| {
+        entity.unrelate::<R>(target);
+    }
+}
+
+/// An [`EntityCommand`](crate::system::EntityCommand) that despawns an entity if `target` is the only entity it
+/// still relates to via the `R` [`ManyRelationship`].
+fn despawn_if_orphaned<R: ManyRelationship>(target: Entity) -> impl FnOnce(EntityWorldMut) {
+    move |entity: EntityWorldMut| {
+        let orphaned = entity.get::<R>().is_none_or(|relationship| {
+            relationship
+                .edges()
+                .targets()
+                .all(|other| other == target || entity.world().get_entity(other).is_err())
+        });
+        if orphaned {
+            entity.despawn();
+        }
+    }
+}
+
+/// The "clone behavior" for [`ManyRelationshipTarget`]. Like [`clone_relationship_target`](super::clone_relationship_target),
+/// this creates an empty [`ManyRelationshipTarget`] instance which is then populated when the corresponding
+/// [`ManyRelationship`] sources of truth are inserted.
+///
+/// This will also queue up clones of the relationship sources if the [`EntityCloner`](crate::entity::EntityCloner) is
+/// configured to spawn recursively and [`ManyRelationshipTarget::LINKED_DESPAWN`] is [`LinkedDespawn::All`].
+pub fn clone_many_relationship_target<T: ManyRelationshipTarget>(
+    _commands: &mut Commands,
+    context: &mut ComponentCloneCtx,
+) {
+    if let Some(component) = context.read_source_component::<T>() {
+        if context.is_recursive() && T::LINKED_DESPAWN == LinkedDespawn::All {
+            for entity in component.iter() {
+                context.queue_entity_clone(entity);
+            }
+        }
+        context.write_target_component(T::with_capacity(component.len()));
+    }
+}
+
+/// The target entities of a [`ManyRelationship`], each with the data `D` of its edge.
+///
+/// Each target appears at most once, and the edges are kept in insertion order.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RelationshipEdges<D = ()> {
+    edges: Vec<(Entity, D)>,
+}
+
+impl<D> Default for RelationshipEdges<D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<D> RelationshipEdges<D> {
+    /// Creates an empty collection of edges.
+    pub const fn new() -> Self {
+        Self { edges: Vec::new() }
+    }
+
+    /// Returns these edges with an edge to `target` carrying `data`, replacing any existing edge to `target`.
+    pub fn with(mut self, target: Entity, data: D) -> Self {
+        self.insert(target, data);
+        self
+    }
+
+    /// Adds an edge to `target` carrying `data`. If there already was an edge to `target`, its data is replaced and
+    /// the previous data is returned.
+    pub fn insert(&mut self, target: Entity, data: D) -> Option<D> {
+        match self.edges.iter_mut().find(|(other, _)| *other == target) {
+            Some((_, existing)) => Some(core::mem::replace(existing, data)),
+            None => {
+                self.edges.push((target, data));
+                None
+            }
+        }
+    }
+
+    /// Removes the edge to `target`, returning its data if there was one.
+    pub fn remove(&mut self, target: Entity) -> Option<D> {
+        let index = self.edges.iter().position(|(other, _)| *other == target)?;
+        Some(self.edges.remove(index).1)
+    }
+
+    /// Returns the data of the edge to `target`, if there is one.
+    pub fn get(&self, target: Entity) -> Option<&D> {
+        self.edges
+            .iter()
+            .find_map(|(other, data)| (*other == target).then_some(data))
+    }
+
+    /// Returns true if there is an edge to `target`.
+    pub fn contains(&self, target: Entity) -> bool {
+        self.edges.iter().any(|(other, _)| *other == target)
+    }
+
+    /// Iterates the target entities.
+    pub fn targets(&self) -> impl Iterator<Item = Entity> + '_ {
+        self.edges.iter().map(|(target, _)| *target)
+    }
+
+    /// Iterates the target entities, with the data of their edges.
+    pub fn iter(&self) -> RelationshipEdgesIter<'_, D> {
+        RelationshipEdgesIter {
+            inner: self.edges.iter(),
+        }
+    }
+
+    /// Returns the number of edges.
+    pub fn len(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Returns true if there are no edges.
+    pub fn is_empty(&self) -> bool {
+        self.edges.is_empty()
+    }
+}
+
+impl<D> FromIterator<(Entity, D)> for RelationshipEdges<D> {
+    fn from_iter<T: IntoIterator<Item = (Entity, D)>>(iter: T) -> Self {
+        let mut edges = Self::new();
+        for (target, data) in iter {
+            edges.insert(target, data);
+        }
+        edges
+    }
+}
+
+impl FromIterator<Entity> for RelationshipEdges {
+    fn from_iter<T: IntoIterator<Item = Entity>>(iter: T) -> Self {
+        iter.into_iter().map(|target| (target, ())).collect()
+    }
+}
+
+impl<D> IntoIterator for RelationshipEdges<D> {
+    type Item = (Entity, D);
+    type IntoIter = alloc::vec::IntoIter<(Entity, D)>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.edges.into_iter()
+    }
+}
+
+impl<'a, D> IntoIterator for &'a RelationshipEdges<D> {
+    type Item = (Entity, &'a D);
+    type IntoIter = RelationshipEdgesIter<'a, D>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<D> VisitEntities for RelationshipEdges<D> {
+    fn visit_entities<F: FnMut(Entity)>(&self, f: F) {
+        self.targets().for_each(f);
+    }
+}
+
+impl<D> VisitEntitiesMut for RelationshipEdges<D> {
+    fn visit_entities_mut<F: FnMut(&mut Entity)>(&mut self, mut f: F) {
+        for (target, _) in &mut self.edges {
+            f(target);
+        }
+    }
+}
+
+/// An iterator over the edges of a [`RelationshipEdges`] collection.
+pub struct RelationshipEdgesIter<'a, D> {
+    inner: slice::Iter<'a, (Entity, D)>,
+}
+
+impl<'a, D> Iterator for RelationshipEdgesIter<'a, D> {
+    type Item = (Entity, &'a D);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next().map(|(target, data)| (*target, data))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+impl<'a, D> DoubleEndedIterator for RelationshipEdgesIter<'a, D> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner.next_back().map(|(target, data)| (*target, data))
+    }
+}
+
+impl<'a, D> ExactSizeIterator for RelationshipEdgesIter<'a, D> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::world::World;
+
+    #[derive(Component)]
+    #[many_relationship(relationship_target = LikedBy)]
+    struct Likes(RelationshipEdges<u32>);
+
+    #[derive(Component)]
+    #[many_relationship_target(relationship = Likes)]
+    struct LikedBy(Vec<Entity>);
+
+    #[test]
+    fn many_relationship() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let b = world.spawn_empty().id();
+        let c = world
+            .spawn(Likes(RelationshipEdges::new().with(a, 1).with(b, 2)))
+            .id();
+        let d = world.spawn(Likes(RelationshipEdges::new().with(a, 3))).id();
+
+        assert_eq!(world.entity(a).get::<LikedBy>().unwrap().0, &[c, d]);
+        assert_eq!(world.entity(b).get::<LikedBy>().unwrap().0, &[c]);
+
+        world.entity_mut(d).relate::<Likes>(b, 4);
+        assert_eq!(world.entity(b).get::<LikedBy>().unwrap().0, &[c, d]);
+        assert_eq!(world.entity(d).get::<Likes>().unwrap().0.get(b), Some(&4));
+
+        world.entity_mut(c).unrelate::<Likes>(a);
+        assert_eq!(world.entity(a).get::<LikedBy>().unwrap().0, &[d]);
+        assert_eq!(
+            world
+                .entity(c)
+                .get::<Likes>()
+                .unwrap()
+                .0
+                .targets()
+                .collect::<Vec<_>>(),
+            [b]
+        );
+
+        // Removing the last edge removes the relationship, and the emptied targets.
+        world.entity_mut(c).unrelate::<Likes>(b);
+        assert!(!world.entity(c).contains::<Likes>());
+        assert_eq!(world.entity(b).get::<LikedBy>().unwrap().0, &[d]);
+
+        // Despawning a target only removes the edges to it.
+        world.despawn(b);
+        assert_eq!(
+            world
+                .entity(d)
+                .get::<Likes>()
+                .unwrap()
+                .0
+                .targets()
+                .collect::<Vec<_>>(),
+            [a]
+        );
+    }
+
+    #[test]
+    fn invalid_edges_are_removed() {
+        let mut world = World::new();
+        let a = world.spawn_empty().id();
+        let missing = world.spawn_empty().id();
+        world.despawn(missing);
+
+        let b = world.spawn_empty().id();
+        world
+            .entity_mut(b)
+            .insert(Likes([(a, 1), (b, 2), (missing, 3)].into_iter().collect()));
+        let likes = world.entity(b).get::<Likes>().unwrap();
+        assert_eq!(likes.0.iter().collect::<Vec<_>>(), [(a, &1)]);
+        assert!(!world.entity(b).contains::<LikedBy>());
+        assert_eq!(world.entity(a).get::<LikedBy>().unwrap().0, &[b]);
+    }
+
+    #[test]
+    fn linked_despawn_policies() {
+        #[derive(Component)]
+        #[many_relationship(relationship_target = MemberOf)]
+        struct Member(RelationshipEdges);
+
+        #[derive(Component)]
+        #[many_relationship_target(relationship = Member, linked_despawn = orphans)]
+        struct MemberOf(Vec<Entity>);
+
+        #[derive(Component)]
+        #[many_relationship(relationship_target = OwnedBy)]
+        struct Owns(RelationshipEdges);
+
+        #[derive(Component)]
+        #[many_relationship_target(relationship = Owns, linked_despawn)]
+        struct OwnedBy(Vec<Entity>);
+
+        let mut world = World::new();
+        let group_a = world.spawn_empty().id();
+        let group_b = world.spawn_empty().id();
+        let in_a = world.spawn(Member([group_a].into_iter().collect())).id();
+        let in_both = world
+            .spawn(Member([group_a, group_b].into_iter().collect()))
+            .id();
+
+        world.despawn(group_a);
+        assert!(world.get_entity(in_a).is_err());
+        assert!(world.get_entity(in_both).is_ok());
+        assert_eq!(
+            world
+                .entity(in_both)
+                .get::<Member>()
+                .unwrap()
+                .0
+                .targets()
+                .collect::<Vec<_>>(),
+            [group_b]
+        );
+
+        let owner = world.spawn_empty().id();
+        let other_owner = world.spawn_empty().id();
+        let owned = world
+            .spawn(Owns([owner, other_owner].into_iter().collect()))
+            .id();
+        world.despawn(owner);
+        assert!(world.get_entity(owned).is_err());
+        assert!(!world.entity(other_owner).contains::<OwnedBy>());
+    }
+}
@@ -1,11 +1,13 @@
 //! This module provides functionality to link entities to each other using specialized components called "relationships". See the [`Relationship`] trait for more info.
 
+mod many_to_many;
 mod related_methods;
 mod relationship_query;
 mod relationship_source_collection;
 
 use alloc::format;
 
+pub use many_to_many::*;
 pub use related_methods::*;
 pub use relationship_query::*;
 pub use relationship_source_collection::*;
@@ -1,7 +1,7 @@
 use crate::{
     bundle::Bundle,
     entity::Entity,
-    relationship::{Relationship, RelationshipTarget},
+    relationship::{ManyRelationship, Relationship, RelationshipTarget},
     system::{Commands, EntityCommands},
     world::{EntityWorldMut, World},
 };
@@ -32,6 +32,37 @@ impl<'w> EntityWorldMut<'w> {
         self
     }
 
+    /// Adds an edge of the [`ManyRelationship`] `R` from this entity to `target`, carrying `data`.
+    /// If the edge already exists, its data is replaced.
+    pub fn relate<R: ManyRelationship>(&mut self, target: Entity, data: R::Data) -> &mut Self {
+        let mut edges = self
+            .take::<R>()
+            .map(ManyRelationship::into_edges)
+            .unwrap_or_default();
+        edges.insert(target, data);
+        self.insert(R::from_edges(edges));
+        self
+    }
+
+    /// Removes the edge of the [`ManyRelationship`] `R` from this entity to `target`, if it exists.
+    /// The `R` component is removed once it has no edges left.
+    pub fn unrelate<R: ManyRelationship>(&mut self, target: Entity) -> &mut Self {
+        if !self
+            .get::<R>()
+            .is_some_and(|relationship| relationship.edges().contains(target))
+        {
+            return self;
+        }
+        if let Some(relationship) = self.take::<R>() {
+            let mut edges = relationship.into_edges();
+            edges.remove(target);
+            if !edges.is_empty() {
+                self.insert(R::from_edges(edges));
+            }
+        }
+        self
+    }
+
     /// Despawns entities that relate to this one via the given [`RelationshipTarget`].
     /// This entity will not be despawned.
     pub fn despawn_related<S: RelationshipTarget>(&mut self) -> &mut Self {
@@ -120,6 +151,25 @@ impl<'a> EntityCommands<'a> {
         self
     }
 
+    /// Adds an edge of the [`ManyRelationship`] `R` from this entity to `target`, carrying `data`.
+    /// If the edge already exists, its data is replaced.
+    pub fn relate<R: ManyRelationship>(&mut self, target: Entity, data: R::Data) -> &mut Self {
+        let id = self.id();
+        self.commands.queue(move |world: &mut World| {
+            world.entity_mut(id).relate::<R>(target, data);
+        });
+        self
+    }
+
+    /// Removes the edge of the [`ManyRelationship`] `R` from this entity to `target`, if it exists.
+    pub fn unrelate<R: ManyRelationship>(&mut self, target: Entity) -> &mut Self {
+        let id = self.id();
+        self.commands.queue(move |world: &mut World| {
+            world.entity_mut(id).unrelate::<R>(target);
+        });
+        self
+    }
+
     /// Despawns entities that relate to this one via the given [`RelationshipTarget`].
     /// This entity will not be despawned.
     pub fn despawn_related<S: RelationshipTarget>(&mut self) -> &mut Self {
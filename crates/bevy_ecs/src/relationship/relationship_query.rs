@@ -1,7 +1,7 @@
 use crate::{
     entity::Entity,
     query::{QueryData, QueryFilter},
-    relationship::{Relationship, RelationshipTarget},
+    relationship::{ManyRelationship, ManyRelationshipTarget, Relationship, RelationshipTarget},
     system::Query,
 };
 use alloc::collections::VecDeque;
@@ -33,6 +33,49 @@ impl<'w, 's, D: QueryData, F: QueryFilter> Query<'w, 's, D, F> {
             .flat_map(RelationshipTarget::iter)
     }
 
+    /// If the given `entity` contains the `R` [`ManyRelationship`] component, returns the
+    /// target entities of that relationship along with the data stored on each edge.
+    pub fn related_edges<R: ManyRelationship>(
+        &'w self,
+        entity: Entity,
+    ) -> impl Iterator<Item = (Entity, &'w R::Data)> + 'w
+    where
+        <D as QueryData>::ReadOnly: QueryData<Item<'w> = &'w R>,
+    {
+        self.get(entity)
+            .into_iter()
+            .flat_map(|relationship| relationship.edges().iter())
+    }
+
+    /// Returns the data stored on the `R` [`ManyRelationship`] edge from `source` to `target`,
+    /// if that edge exists.
+    pub fn edge<R: ManyRelationship>(
+        &'w self,
+        source: Entity,
+        target: Entity,
+    ) -> Option<&'w R::Data>
+    where
+        <D as QueryData>::ReadOnly: QueryData<Item<'w> = &'w R>,
+    {
+        self.get(source)
+            .ok()
+            .and_then(|relationship| relationship.edges().get(target))
+    }
+
+    /// If the given `entity` contains the `S` [`ManyRelationshipTarget`] component, returns the
+    /// source entities stored on that component.
+    pub fn many_relationship_sources<S: ManyRelationshipTarget>(
+        &'w self,
+        entity: Entity,
+    ) -> impl Iterator<Item = Entity> + 'w
+    where
+        <D as QueryData>::ReadOnly: QueryData<Item<'w> = &'w S>,
+    {
+        self.get(entity)
+            .into_iter()
+            .flat_map(ManyRelationshipTarget::iter)
+    }
+
     /// Recursively walks up the tree defined by the given `R` [`Relationship`] until
     /// there are no more related entities, returning the "root entity" of the relationship hierarchy.
     ///
@@ -0,0 +1,681 @@
+use alloc::{string::String, vec, vec::Vec};
+use bevy_platform_support::collections::HashMap;
+use core::fmt::{self, Write};
+use fixedbitset::FixedBitSet;
+
+use crate::{
+    component::{ComponentId, Components},
+    schedule::{
+        graph::Direction::{Incoming, Outgoing},
+        json::write_json_string,
+        NodeId, ScheduleGraph, ScheduleNotInitialized,
+    },
+    system::ScheduleSystem,
+    world::World,
+};
+
+/// A structured report of the system order ambiguities in a [`Schedule`], created with
+/// [`Schedule::ambiguity_report`].
+///
+/// Unlike the message logged by [`ScheduleBuildSettings::ambiguity_detection`], the conflicts are
+/// grouped by the component or resource they conflict on, each conflict is classified as
+/// read/write or write/write, and a small set of orderings that would resolve every ambiguity is
+/// suggested. The report can be printed with [`Display`](fmt::Display), or exported with
+/// [`AmbiguityReport::to_json`] to gate CI on it.
+///
+/// [`ScheduleBuildSettings::ambiguity_detection`]: crate::schedule::ScheduleBuildSettings::ambiguity_detection
+/// [`Schedule`]: crate::schedule::Schedule
+/// [`Schedule::ambiguity_report`]: crate::schedule::Schedule::ambiguity_report
+#[derive(Clone, Debug, Default)]
+pub struct AmbiguityReport {
+    /// The ambiguities, grouped by the data they conflict on.
+    ///
+    /// Groups with write/write conflicts come first, followed by the groups with the most
+    /// conflicting pairs.
+    pub conflicts: Vec<AmbiguityConflict>,
+    /// Orderings that together resolve every ambiguity in the report.
+    pub suggestions: Vec<OrderingSuggestion>,
+}
+
+/// The ambiguous system pairs of an [`AmbiguityReport`] that conflict on the same data.
+#[derive(Clone, Debug)]
+pub struct AmbiguityConflict {
+    /// The data the systems conflict on.
+    pub data: ConflictingData,
+    /// The name of that data.
+    pub name: String,
+    /// The pairs of systems with indeterminate order that conflict on this data, write/write
+    /// conflicts first.
+    pub pairs: Vec<AmbiguousSystemPair>,
+}
+
+/// The data an [`AmbiguityConflict`] is about.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ConflictingData {
+    /// A component.
+    Component(ComponentId),
+    /// A resource.
+    Resource(ComponentId),
+    /// The whole [`World`], because at least one of the systems is exclusive or the systems
+    /// conflict on all of their access.
+    World,
+}
+
+/// Two systems with indeterminate order in an [`AmbiguityConflict`].
+#[derive(Clone, Debug)]
+pub struct AmbiguousSystemPair {
+    /// The first system.
+    pub first: NodeId,
+    /// The second system.
+    pub second: NodeId,
+    /// The name of the first system.
+    pub first_name: String,
+    /// The name of the second system.
+    pub second_name: String,
+    /// How the systems access the conflicting data.
+    pub kind: AmbiguityKind,
+}
+
+/// How two ambiguous systems access the data they conflict on.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum AmbiguityKind {
+    /// Both systems write the data, so its final value depends on the order they run in.
+    WriteWrite,
+    /// One system writes the data and the other reads it, so the value that is read depends on
+    /// the order they run in.
+    ReadWrite,
+}
+
+/// An ordering constraint suggested by an [`AmbiguityReport`].
+#[derive(Clone, Debug)]
+pub struct OrderingSuggestion {
+    /// The system or system set to run first.
+    pub before: NodeId,
+    /// The system or system set to run second.
+    pub after: NodeId,
+    /// The name of [`before`](Self::before).
+    pub before_name: String,
+    /// The name of [`after`](Self::after).
+    pub after_name: String,
+    /// The number of ambiguous system pairs this ordering resolves, taking the suggestions before
+    /// it into account.
+    pub resolves: usize,
+}
+
+impl AmbiguityKind {
+    fn as_str(self) -> &'static str {
+        match self {
+            AmbiguityKind::WriteWrite => "write/write",
+            AmbiguityKind::ReadWrite => "read/write",
+        }
+    }
+}
+
+impl ConflictingData {
+    fn as_str(self) -> &'static str {
+        match self {
+            ConflictingData::Component(_) => "component",
+            ConflictingData::Resource(_) => "resource",
+            ConflictingData::World => "world",
+        }
+    }
+}
+
+impl AmbiguityReport {
+    /// Returns `true` if the schedule has no ambiguities.
+    pub fn is_empty(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+
+    /// Returns the number of ambiguous system pairs.
+    ///
+    /// A pair of systems that conflicts on several components or resources is counted once for
+    /// each of them.
+    pub fn pair_count(&self) -> usize {
+        self.conflicts
+            .iter()
+            .map(|conflict| conflict.pairs.len())
+            .sum()
+    }
+
+    /// Returns the number of ambiguous system pairs where both systems write the conflicting data.
+    pub fn write_write_count(&self) -> usize {
+        self.conflicts
+            .iter()
+            .flat_map(|conflict| &conflict.pairs)
+            .filter(|pair| pair.kind == AmbiguityKind::WriteWrite)
+            .count()
+    }
+
+    /// Exports the report as JSON.
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        let _ = write!(
+            json,
+            "{{\"pairs\":{},\"write_write\":{},\"conflicts\":[",
+            self.pair_count(),
+            self.write_write_count()
+        );
+        for (index, conflict) in self.conflicts.iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            let _ = write!(json, "{{\"data\":\"{}\",\"name\":", conflict.data.as_str());
+            write_json_string(&mut json, &conflict.name);
+            json.push_str(",\"pairs\":[");
+            for (index, pair) in conflict.pairs.iter().enumerate() {
+                if index > 0 {
+                    json.push(',');
+                }
+                json.push_str("{\"first\":");
+                write_json_string(&mut json, &pair.first_name);
+                json.push_str(",\"second\":");
+                write_json_string(&mut json, &pair.second_name);
+                let _ = write!(json, ",\"kind\":\"{}\"}}", pair.kind.as_str());
+            }
+            json.push_str("]}");
+        }
+        json.push_str("],\"suggestions\":[");
+        for (index, suggestion) in self.suggestions.iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            json.push_str("{\"before\":");
+            write_json_string(&mut json, &suggestion.before_name);
+            json.push_str(",\"after\":");
+            write_json_string(&mut json, &suggestion.after_name);
+            let _ = write!(json, ",\"resolves\":{}}}", suggestion.resolves);
+        }
+        json.push_str("]}");
+        json
+    }
+}
+
+impl fmt::Display for AmbiguityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return writeln!(f, "No system order ambiguities.");
+        }
+        writeln!(
+            f,
+            "{} ambiguous system pairs ({} write/write):",
+            self.pair_count(),
+            self.write_write_count()
+        )?;
+        for conflict in &self.conflicts {
+            writeln!(f, " -- {} `{}`", conflict.data.as_str(), conflict.name)?;
+            for pair in &conflict.pairs {
+                writeln!(
+                    f,
+                    "    [{}] {} and {}",
+                    pair.kind.as_str(),
+                    pair.first_name,
+                    pair.second_name
+                )?;
+            }
+        }
+        writeln!(f, "Suggested orderings:")?;
+        for suggestion in &self.suggestions {
+            writeln!(
+                f,
+                " -- {} before {} (resolves {})",
+                suggestion.before_name, suggestion.after_name, suggestion.resolves
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl ScheduleGraph {
+    /// Creates an [`AmbiguityReport`] of the system order ambiguities in this graph.
+    ///
+    /// The graph only holds its systems between [`ScheduleGraph::build_schedule`] and the moment
+    /// they're moved into the executable schedule, so outside of that window this returns
+    /// [`ScheduleNotInitialized`]. Prefer [`Schedule::ambiguity_report`], which initializes the
+    /// schedule first. Before the graph is first built, the report is empty.
+    ///
+    /// Orderings are suggested greedily: an ordering between two named system sets is preferred
+    /// when it resolves several ambiguities at once, otherwise the two systems are ordered
+    /// directly. Each suggestion is consistent with the existing ordering of the schedule and with
+    /// the suggestions before it, so applying all of them never introduces a cycle. The direction
+    /// of a suggestion is arbitrary when both directions are valid.
+    ///
+    /// [`Schedule::ambiguity_report`]: crate::schedule::Schedule::ambiguity_report
+    pub fn ambiguity_report(
+        &self,
+        components: &Components,
+    ) -> Result<AmbiguityReport, ScheduleNotInitialized> {
+        self.build_ambiguity_report(components, |id| self.get_system_at(id))
+    }
+
+    /// Creates an [`AmbiguityReport`], looking the systems of the graph up with `get_system`.
+    pub(super) fn build_ambiguity_report<'a>(
+        &'a self,
+        components: &Components,
+        get_system: impl Fn(NodeId) -> Option<&'a ScheduleSystem>,
+    ) -> Result<AmbiguityReport, ScheduleNotInitialized> {
+        let node_name = |id: NodeId| match id {
+            NodeId::System(_) => {
+                Ok(self.system_name(get_system(id).ok_or(ScheduleNotInitialized)?))
+            }
+            NodeId::Set(_) => Ok(self.get_node_name_inner(&id, false)),
+        };
+
+        let mut groups: HashMap<ConflictingData, Vec<AmbiguousSystemPair>> = HashMap::default();
+        for (a, b, conflicts) in self.conflicting_systems() {
+            let system_a = get_system(*a).ok_or(ScheduleNotInitialized)?;
+            let system_b = get_system(*b).ok_or(ScheduleNotInitialized)?;
+            let first_name = node_name(*a)?;
+            let second_name = node_name(*b)?;
+            let mut push = |data, kind| {
+                groups.entry(data).or_default().push(AmbiguousSystemPair {
+                    first: *a,
+                    second: *b,
+                    first_name: first_name.clone(),
+                    second_name: second_name.clone(),
+                    kind,
+                });
+            };
+
+            if conflicts.is_empty() {
+                let writes = |system: &ScheduleSystem| {
+                    system.is_exclusive() || system.component_access().has_any_write()
+                };
+                let kind = if writes(system_a) && writes(system_b) {
+                    AmbiguityKind::WriteWrite
+                } else {
+                    AmbiguityKind::ReadWrite
+                };
+                push(ConflictingData::World, kind);
+                continue;
+            }
+
+            let access_a = system_a.component_access();
+            let access_b = system_b.component_access();
+            for &id in conflicts {
+                let is_resource = access_a.has_resource_read(id) && access_b.has_resource_read(id);
+                let (data, writes_a, writes_b) = if is_resource {
+                    (
+                        ConflictingData::Resource(id),
+                        access_a.has_resource_write(id),
+                        access_b.has_resource_write(id),
+                    )
+                } else {
+                    (
+                        ConflictingData::Component(id),
+                        access_a.has_component_write(id),
+                        access_b.has_component_write(id),
+                    )
+                };
+                let kind = if writes_a && writes_b {
+                    AmbiguityKind::WriteWrite
+                } else {
+                    AmbiguityKind::ReadWrite
+                };
+                push(data, kind);
+            }
+        }
+
+        let mut conflicts: Vec<_> = groups
+            .into_iter()
+            .map(|(data, mut pairs)| {
+                pairs.sort_by_key(|pair| pair.kind);
+                let name = match data {
+                    ConflictingData::Component(id) | ConflictingData::Resource(id) => {
+                        components.get_name(id).unwrap_or("<unknown>").into()
+                    }
+                    ConflictingData::World => core::any::type_name::<World>().into(),
+                };
+                AmbiguityConflict { data, name, pairs }
+            })
+            .collect();
+        conflicts.sort_by(|a, b| {
+            let has_write_write = |conflict: &AmbiguityConflict| {
+                conflict
+                    .pairs
+                    .iter()
+                    .any(|pair| pair.kind == AmbiguityKind::WriteWrite)
+            };
+            has_write_write(b)
+                .cmp(&has_write_write(a))
+                .then(b.pairs.len().cmp(&a.pairs.len()))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+
+        let suggestions = self
+            .suggest_orderings()
+            .into_iter()
+            .map(|(before, after, resolves)| {
+                Ok(OrderingSuggestion {
+                    before,
+                    after,
+                    before_name: node_name(before)?,
+                    after_name: node_name(after)?,
+                    resolves,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(AmbiguityReport {
+            conflicts,
+            suggestions,
+        })
+    }
+
+    /// Greedily picks orderings that resolve every pair in [`ScheduleGraph::conflicting_systems`],
+    /// as `(before, after, resolves)`.
+    fn suggest_orderings(&self) -> Vec<(NodeId, NodeId, usize)> {
+        let mut pairs: Vec<(usize, usize)> = self
+            .conflicting_systems()
+            .iter()
+            .map(|(a, b, _)| (a.index(), b.index()))
+            .collect();
+        pairs.sort_unstable();
+        pairs.dedup();
+        if pairs.is_empty() {
+            return Vec::new();
+        }
+
+        let mut reachable = self.system_reachability();
+        let mut set_systems: HashMap<NodeId, FixedBitSet> = HashMap::default();
+        let mut suggestions = Vec::new();
+        loop {
+            pairs.retain(|&(a, b)| !reachable[a][b] && !reachable[b][a]);
+            let Some(&(first_a, first_b)) = pairs.first() else {
+                break;
+            };
+
+            // The best ordering between named sets: (resolved pairs, size of the sets, before, after).
+            let mut best: Option<(usize, usize, NodeId, NodeId)> = None;
+            for &(a, b) in &pairs {
+                let sets_a = self.named_sets_containing(NodeId::System(a));
+                let sets_b = self.named_sets_containing(NodeId::System(b));
+                for &set_a in &sets_a {
+                    for &set_b in &sets_b {
+                        let systems_a = self.systems_in_set(&mut set_systems, set_a);
+                        let systems_b = self.systems_in_set(&mut set_systems, set_b);
+                        if !systems_a.is_disjoint(&systems_b) {
+                            continue;
+                        }
+                        let (before, after, systems_before, systems_after) =
+                            if can_order(&reachable, &systems_a, &systems_b) {
+                                (set_a, set_b, &systems_a, &systems_b)
+                            } else if can_order(&reachable, &systems_b, &systems_a) {
+                                (set_b, set_a, &systems_b, &systems_a)
+                            } else {
+                                continue;
+                            };
+                        let resolves = pairs
+                            .iter()
+                            .filter(|&&(x, y)| {
+                                (systems_before.contains(x) && systems_after.contains(y))
+                                    || (systems_before.contains(y) && systems_after.contains(x))
+                            })
+                            .count();
+                        let size = systems_before.count_ones(..) + systems_after.count_ones(..);
+                        let better = match best {
+                            None => true,
+                            Some((best_resolves, best_size, ..)) => {
+                                resolves > best_resolves
+                                    || (resolves == best_resolves && size < best_size)
+                            }
+                        };
+                        if better {
+                            best = Some((resolves, size, before, after));
+                        }
+                    }
+                }
+            }
+
+            let (before, after, resolves) = match best {
+                Some((resolves, _, before, after)) if resolves > 1 => (before, after, resolves),
+                _ => (NodeId::System(first_a), NodeId::System(first_b), 1),
+            };
+            let systems_before = self.systems_in_set(&mut set_systems, before);
+            let systems_after = self.systems_in_set(&mut set_systems, after);
+            add_ordering(&mut reachable, &systems_before, &systems_after);
+            suggestions.push((before, after, resolves));
+        }
+        suggestions
+    }
+
+    /// Returns the systems that each system is ordered before, directly or transitively, indexed
+    /// by system index.
+    fn system_reachability(&self) -> Vec<FixedBitSet> {
+        let n = self.systems.len();
+        let mut set_systems: HashMap<NodeId, FixedBitSet> = HashMap::default();
+
+        // Systems that directly follow each system once sets are flattened.
+        let mut successors = Vec::with_capacity(n);
+        for index in 0..n {
+            let mut direct = FixedBitSet::with_capacity(n);
+            let mut nodes = self.sets_containing(NodeId::System(index));
+            nodes.push(NodeId::System(index));
+            let mut stack = Vec::new();
+            for node in nodes {
+                stack.extend(self.dependency().graph().neighbors_directed(node, Outgoing));
+            }
+            while let Some(next) = stack.pop() {
+                let systems = self.systems_in_set(&mut set_systems, next);
+                if next.is_set() && systems.is_clear() {
+                    // Empty sets pass their ordering on to what comes after them.
+                    stack.extend(self.dependency().graph().neighbors_directed(next, Outgoing));
+                }
+                direct.union_with(&systems);
+            }
+            successors.push(direct);
+        }
+
+        let mut reachable: Vec<FixedBitSet> = Vec::with_capacity(n);
+        for index in 0..n {
+            let mut visited = FixedBitSet::with_capacity(n);
+            let mut stack: Vec<usize> = successors[index].ones().collect();
+            while let Some(next) = stack.pop() {
+                if visited.put(next) {
+                    continue;
+                }
+                stack.extend(successors[next].ones());
+            }
+            reachable.push(visited);
+        }
+        reachable
+    }
+
+    /// Returns the sets containing `id`, directly or transitively.
+    fn sets_containing(&self, id: NodeId) -> Vec<NodeId> {
+        let mut sets = Vec::new();
+        let mut stack = vec![id];
+        while let Some(node) = stack.pop() {
+            for set in self.hierarchy().graph().neighbors_directed(node, Incoming) {
+                if !sets.contains(&set) {
+                    sets.push(set);
+                    stack.push(set);
+                }
+            }
+        }
+        sets.sort_unstable();
+        sets
+    }
+
+    /// Returns the sets containing `id` that can be named when adding an ordering, i.e. sets
+    /// that are neither anonymous nor the set of a single system type.
+    fn named_sets_containing(&self, id: NodeId) -> Vec<NodeId> {
+        let mut sets = self.sets_containing(id);
+        sets.retain(|&set| {
+            let set = self.set_at(set);
+            !set.is_anonymous() && set.system_type().is_none()
+        });
+        sets
+    }
+
+    /// Returns the systems in the system or set `id`, as a bitset of system indices.
+    fn systems_in_set(&self, cache: &mut HashMap<NodeId, FixedBitSet>, id: NodeId) -> FixedBitSet {
+        if let Some(systems) = cache.get(&id) {
+            return systems.clone();
+        }
+        let mut systems = FixedBitSet::with_capacity(self.systems.len());
+        let mut stack = vec![id];
+        while let Some(node) = stack.pop() {
+            match node {
+                NodeId::System(index) => systems.insert(index),
+                NodeId::Set(_) => {
+                    stack.extend(self.hierarchy().graph().neighbors_directed(node, Outgoing));
+                }
+            }
+        }
+        cache.insert(id, systems.clone());
+        systems
+    }
+}
+
+/// Returns `true` if ordering `before` ahead of `after` doesn't create a cycle.
+fn can_order(reachable: &[FixedBitSet], before: &FixedBitSet, after: &FixedBitSet) -> bool {
+    after
+        .ones()
+        .all(|system| reachable[system].is_disjoint(before))
+}
+
+/// Updates `reachable` as if every system in `before` was ordered ahead of every system in `after`.
+fn add_ordering(reachable: &mut [FixedBitSet], before: &FixedBitSet, after: &FixedBitSet) {
+    let mut successors = after.clone();
+    for system in after.ones() {
+        successors.union_with(&reachable[system]);
+    }
+    for (system, reachable) in reachable.iter_mut().enumerate() {
+        if before.contains(system) || !reachable.is_disjoint(before) {
+            reachable.union_with(&successors);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        prelude::{Component, Query, Res, ResMut, Resource, Schedule, SystemSet},
+        schedule::IntoSystemConfigs,
+    };
+
+    #[derive(Resource, Default)]
+    struct R;
+
+    #[derive(Component)]
+    struct A;
+
+    #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+    enum Sets {
+        Input,
+        Physics,
+    }
+
+    fn write_r(_: ResMut<R>) {}
+    fn read_r(_: Res<R>) {}
+    fn write_a(_: Query<&mut A>) {}
+    fn read_a(_: Query<&A>) {}
+
+    fn build_report(schedule: &mut Schedule) -> AmbiguityReport {
+        let mut world = World::new();
+        world.init_resource::<R>();
+        schedule.ambiguity_report(&mut world).unwrap()
+    }
+
+    #[test]
+    fn groups_and_classifies_conflicts() {
+        let mut schedule = Schedule::default();
+        schedule.add_systems((write_r, read_r, write_a, read_a));
+        let report = build_report(&mut schedule);
+
+        assert_eq!(report.pair_count(), 2);
+        assert_eq!(report.write_write_count(), 0);
+        assert_eq!(report.conflicts.len(), 2);
+        assert!(report
+            .conflicts
+            .iter()
+            .any(|conflict| matches!(conflict.data, ConflictingData::Resource(_))));
+        assert!(report
+            .conflicts
+            .iter()
+            .any(|conflict| matches!(conflict.data, ConflictingData::Component(_))));
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems((write_r, write_r, read_r));
+        let report = build_report(&mut schedule);
+        assert_eq!(report.conflicts.len(), 1);
+        let pairs = &report.conflicts[0].pairs;
+        assert_eq!(pairs.len(), 3);
+        assert_eq!(pairs[0].kind, AmbiguityKind::WriteWrite);
+        assert_eq!(pairs[1].kind, AmbiguityKind::ReadWrite);
+    }
+
+    #[test]
+    fn suggests_set_orderings() {
+        let mut schedule = Schedule::default();
+        schedule.add_systems((
+            (write_r, write_a).in_set(Sets::Input),
+            (read_r, read_a).in_set(Sets::Physics),
+        ));
+        let report = build_report(&mut schedule);
+
+        assert_eq!(report.pair_count(), 2);
+        assert_eq!(report.suggestions.len(), 1);
+        let suggestion = &report.suggestions[0];
+        assert_eq!(suggestion.resolves, 2);
+        assert!(suggestion.before.is_set() && suggestion.after.is_set());
+    }
+
+    #[test]
+    fn suggestions_respect_existing_order() {
+        let mut schedule = Schedule::default();
+        // `read_r` already runs before `read_a`, so ordering `Input` before `Physics` would
+        // introduce a cycle.
+        schedule.add_systems((
+            (write_r, read_a).in_set(Sets::Input),
+            (read_r.before(read_a), write_a).in_set(Sets::Physics),
+        ));
+        let report = build_report(&mut schedule);
+
+        assert_eq!(report.suggestions.len(), 1);
+        let suggestion = &report.suggestions[0];
+        assert_eq!(suggestion.before_name, "Physics");
+        assert_eq!(suggestion.after_name, "Input");
+    }
+
+    #[test]
+    fn json_export() {
+        let mut schedule = Schedule::default();
+        schedule.add_systems((write_r, write_r));
+        let json = build_report(&mut schedule).to_json();
+
+        assert!(json
+            .starts_with("{\"pairs\":1,\"write_write\":1,\"conflicts\":[{\"data\":\"resource\""));
+        assert!(json.contains("\"kind\":\"write/write\""));
+        assert!(json.contains("\"suggestions\":[{\"before\":"));
+        assert!(json.ends_with("\"resolves\":1}]}"));
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems((write_r, read_a));
+        assert_eq!(
+            build_report(&mut schedule).to_json(),
+            "{\"pairs\":0,\"write_write\":0,\"conflicts\":[],\"suggestions\":[]}"
+        );
+    }
+
+    #[test]
+    fn report_after_schedule_runs() {
+        let mut world = World::new();
+        world.init_resource::<R>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems((write_r, read_r));
+        schedule.run(&mut world);
+
+        // The systems have been moved out of the graph.
+        assert!(schedule
+            .graph()
+            .ambiguity_report(world.components())
+            .is_err());
+        assert_eq!(
+            schedule.ambiguity_report(&mut world).unwrap().pair_count(),
+            1
+        );
+    }
+}
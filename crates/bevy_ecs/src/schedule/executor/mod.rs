@@ -2,6 +2,8 @@
 mod multi_threaded;
 mod simple;
 mod single_threaded;
+#[cfg(feature = "std")]
+mod trace;
 
 use alloc::{borrow::Cow, vec, vec::Vec};
 use core::any::TypeId;
@@ -9,7 +11,10 @@ use core::any::TypeId;
 pub use self::{simple::SimpleExecutor, single_threaded::SingleThreadedExecutor};
 
 #[cfg(feature = "std")]
-pub use self::multi_threaded::{MainThreadExecutor, MultiThreadedExecutor};
+pub use self::{
+    multi_threaded::{MainThreadExecutor, MultiThreadedExecutor},
+    trace::{ExecutorTrace, ExecutorTraceEvent, ExecutorTraceEventKind},
+};
 
 use fixedbitset::FixedBitSet;
 
@@ -1,4 +1,4 @@
-use alloc::{boxed::Box, vec::Vec};
+use alloc::{borrow::Cow, boxed::Box, vec::Vec};
 use bevy_platform_support::sync::Arc;
 use bevy_tasks::{ComputeTaskPool, Scope, TaskPool, ThreadExecutor};
 use bevy_utils::{default, syncunsafecell::SyncUnsafeCell};
@@ -17,9 +17,12 @@ use crate::{
     archetype::ArchetypeComponentId,
     prelude::Resource,
     query::Access,
-    schedule::{is_apply_deferred, BoxedCondition, ExecutorKind, SystemExecutor, SystemSchedule},
+    schedule::{
+        is_apply_deferred, BoxedCondition, ExecutorKind, ExecutorTrace, ExecutorTraceEventKind,
+        SystemExecutor, SystemSchedule,
+    },
     system::ScheduleSystem,
-    world::{unsafe_world_cell::UnsafeWorldCell, World},
+    world::{unsafe_world_cell::UnsafeWorldCell, SystemChangeRecorder, World},
 };
 
 use super::__rust_begin_short_backtrace;
@@ -30,6 +33,8 @@ struct Environment<'env, 'sys> {
     systems: &'sys [SyncUnsafeCell<ScheduleSystem>],
     conditions: SyncUnsafeCell<Conditions<'sys>>,
     world_cell: UnsafeWorldCell<'env>,
+    trace: Option<ExecutorTrace>,
+    change_recorder: Option<&'env SystemChangeRecorder>,
 }
 
 struct Conditions<'a> {
@@ -44,6 +49,8 @@ impl<'env, 'sys> Environment<'env, 'sys> {
         executor: &'env MultiThreadedExecutor,
         schedule: &'sys mut SystemSchedule,
         world: &'env mut World,
+        trace: Option<ExecutorTrace>,
+        change_recorder: Option<&'env SystemChangeRecorder>,
     ) -> Self {
         Environment {
             executor,
@@ -55,6 +62,8 @@ impl<'env, 'sys> Environment<'env, 'sys> {
                 systems_in_sets_with_conditions: &schedule.systems_in_sets_with_conditions,
             }),
             world_cell: world.as_unsafe_world_cell(),
+            trace,
+            change_recorder,
         }
     }
 }
@@ -62,6 +71,8 @@ impl<'env, 'sys> Environment<'env, 'sys> {
 /// Per-system data used by the [`MultiThreadedExecutor`].
 // Copied here because it can't be read from the system when it's running.
 struct SystemTaskMetadata {
+    /// The name of the system, for the [`ExecutorTrace`].
+    name: Cow<'static, str>,
     /// The [`ArchetypeComponentId`] access of the system.
     archetype_component_access: Access<ArchetypeComponentId>,
     /// Indices of the systems that directly depend on the system.
@@ -121,6 +132,8 @@ pub struct ExecutorState {
     completed_systems: FixedBitSet,
     /// Systems that have run but have not had their buffers applied.
     unapplied_systems: FixedBitSet,
+    /// Systems that have been recorded as blocked in the [`ExecutorTrace`].
+    blocked_systems: FixedBitSet,
 }
 
 /// References to data required by the executor.
@@ -159,10 +172,12 @@ impl SystemExecutor for MultiThreadedExecutor {
         state.completed_systems = FixedBitSet::with_capacity(sys_count);
         state.skipped_systems = FixedBitSet::with_capacity(sys_count);
         state.unapplied_systems = FixedBitSet::with_capacity(sys_count);
+        state.blocked_systems = FixedBitSet::with_capacity(sys_count);
 
         state.system_task_metadata = Vec::with_capacity(sys_count);
         for index in 0..sys_count {
             state.system_task_metadata.push(SystemTaskMetadata {
+                name: schedule.systems[index].name(),
                 archetype_component_access: default(),
                 dependents: schedule.system_dependents[index].clone(),
                 is_send: schedule.systems[index].is_send(),
@@ -214,7 +229,15 @@ impl SystemExecutor for MultiThreadedExecutor {
             .map(|e| e.0.clone());
         let thread_executor = thread_executor.as_deref();
 
-        let environment = &Environment::new(self, schedule, world);
+        let trace = world.get_resource::<ExecutorTrace>().cloned();
+        let change_recorder = SystemChangeRecorder::new(world);
+        let environment = &Environment::new(
+            self,
+            schedule,
+            world,
+            trace.clone(),
+            change_recorder.as_ref(),
+        );
 
         ComputeTaskPool::get_or_init(TaskPool::default).scope_with_executor(
             false,
@@ -235,7 +258,13 @@ impl SystemExecutor for MultiThreadedExecutor {
         if self.apply_final_deferred {
             // Do one final apply buffers after all systems have completed
             // Commands should be applied while on the scope's thread, not the executor's thread
-            let res = apply_deferred(&state.unapplied_systems, systems, world);
+            let res = apply_deferred(
+                &state.unapplied_systems,
+                systems,
+                world,
+                trace.as_ref(),
+                change_recorder.as_ref(),
+            );
             if let Err(payload) = res {
                 let panic_payload = self.panic_payload.get_mut().unwrap();
                 *panic_payload = Some(payload);
@@ -243,6 +272,14 @@ impl SystemExecutor for MultiThreadedExecutor {
             state.unapplied_systems.clear();
         }
 
+        if let Some(change_recorder) = change_recorder {
+            change_recorder.finish(world);
+        }
+
+        if let Some(trace) = &trace {
+            trace.flush();
+        }
+
         // check to see if there was a panic
         let payload = self.panic_payload.get_mut().unwrap();
         if let Some(payload) = payload.take() {
@@ -255,6 +292,7 @@ impl SystemExecutor for MultiThreadedExecutor {
         state.evaluated_sets.clear();
         state.skipped_systems.clear();
         state.completed_systems.clear();
+        state.blocked_systems.clear();
     }
 
     fn set_apply_final_deferred(&mut self, value: bool) {
@@ -347,6 +385,7 @@ impl ExecutorState {
             skipped_systems: FixedBitSet::new(),
             completed_systems: FixedBitSet::new(),
             unapplied_systems: FixedBitSet::new(),
+            blocked_systems: FixedBitSet::new(),
         }
     }
 
@@ -400,6 +439,7 @@ impl ExecutorState {
                     system,
                     conditions,
                     context.environment.world_cell,
+                    context.environment.trace.as_ref(),
                 ) {
                     // NOTE: exclusive systems with ambiguities are susceptible to
                     // being significantly displaced here (compared to single-threaded order)
@@ -460,13 +500,19 @@ impl ExecutorState {
         system: &mut ScheduleSystem,
         conditions: &mut Conditions,
         world: UnsafeWorldCell,
+        trace: Option<&ExecutorTrace>,
     ) -> bool {
         let system_meta = &self.system_task_metadata[system_index];
-        if system_meta.is_exclusive && self.num_running_systems > 0 {
+        let (is_exclusive, is_send) = (system_meta.is_exclusive, system_meta.is_send);
+        if is_exclusive && self.num_running_systems > 0 {
+            self.record_blocked(trace, system_index, |_| true);
             return false;
         }
 
-        if !system_meta.is_send && self.local_thread_running {
+        if !is_send && self.local_thread_running {
+            self.record_blocked(trace, system_index, |running| {
+                !running.is_send || running.is_exclusive
+            });
             return false;
         }
 
@@ -480,6 +526,10 @@ impl ExecutorState {
                     .archetype_component_access()
                     .is_compatible(&self.active_access)
                 {
+                    let access = condition.archetype_component_access();
+                    self.record_blocked(trace, system_index, |running| {
+                        !running.archetype_component_access.is_compatible(access)
+                    });
                     return false;
                 }
             }
@@ -491,6 +541,10 @@ impl ExecutorState {
                 .archetype_component_access()
                 .is_compatible(&self.active_access)
             {
+                let access = condition.archetype_component_access();
+                self.record_blocked(trace, system_index, |running| {
+                    !running.archetype_component_access.is_compatible(access)
+                });
                 return false;
             }
         }
@@ -501,6 +555,10 @@ impl ExecutorState {
                 .archetype_component_access()
                 .is_compatible(&self.active_access)
             {
+                let access = system.archetype_component_access();
+                self.record_blocked(trace, system_index, |running| {
+                    !running.archetype_component_access.is_compatible(access)
+                });
                 return false;
             }
 
@@ -512,6 +570,34 @@ impl ExecutorState {
         true
     }
 
+    /// Records in `trace` that the system at `system_index` is blocked by the running systems for
+    /// which `blocks` returns `true`, if it hasn't been recorded already.
+    fn record_blocked(
+        &mut self,
+        trace: Option<&ExecutorTrace>,
+        system_index: usize,
+        blocks: impl Fn(&SystemTaskMetadata) -> bool,
+    ) {
+        let Some(trace) = trace else {
+            return;
+        };
+        if self.blocked_systems.contains(system_index) {
+            return;
+        }
+        self.blocked_systems.grow_and_insert(system_index);
+        let conflicts = self
+            .running_systems
+            .ones()
+            .map(|index| &self.system_task_metadata[index])
+            .filter(|running| blocks(running))
+            .map(|running| running.name.clone())
+            .collect();
+        trace.record(ExecutorTraceEventKind::SystemBlocked {
+            system: self.system_task_metadata[system_index].name.clone(),
+            conflicts,
+        });
+    }
+
     /// # Safety
     /// * `world` must have permission to read any world data required by
     ///   the system's conditions: this includes conditions for the system
@@ -595,6 +681,8 @@ impl ExecutorState {
         let system_meta = &self.system_task_metadata[system_index];
 
         let task = async move {
+            let trace = context.environment.trace.as_ref();
+            record_system(trace, ExecutorTraceEventKind::SystemStarted, system);
             let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
                 // SAFETY:
                 // - The caller ensures that we have permission to
@@ -614,6 +702,13 @@ impl ExecutorState {
                     };
                 };
             }));
+            record_system(trace, ExecutorTraceEventKind::SystemFinished, system);
+            if let (Ok(()), Some(change_recorder)) = (&res, context.environment.change_recorder) {
+                // SAFETY: The system is still counted as running, so no system with conflicting
+                // access can start before its changes are recorded, and the archetypes and
+                // storages can't be modified while non-exclusive systems run.
+                unsafe { change_recorder.record_parallel(context.environment.world_cell, system) };
+            }
             context.system_completed(system_index, res, system);
         };
 
@@ -644,7 +739,13 @@ impl ExecutorState {
                 // SAFETY: `can_run` returned true for this system, which means
                 // that no other systems currently have access to the world.
                 let world = unsafe { context.environment.world_cell.world_mut() };
-                let res = apply_deferred(&unapplied_systems, context.environment.systems, world);
+                let res = apply_deferred(
+                    &unapplied_systems,
+                    context.environment.systems,
+                    world,
+                    context.environment.trace.as_ref(),
+                    context.environment.change_recorder,
+                );
                 context.system_completed(system_index, res, system);
             };
 
@@ -654,6 +755,9 @@ impl ExecutorState {
                 // SAFETY: `can_run` returned true for this system, which means
                 // that no other systems currently have access to the world.
                 let world = unsafe { context.environment.world_cell.world_mut() };
+                let trace = context.environment.trace.as_ref();
+                record_system(trace, ExecutorTraceEventKind::SystemStarted, system);
+                let first_tick = world.change_tick();
                 let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
                     // TODO: implement an error-handling API instead of panicking.
                     if let Err(err) = __rust_begin_short_backtrace::run(system, world) {
@@ -664,6 +768,11 @@ impl ExecutorState {
                         );
                     };
                 }));
+                record_system(trace, ExecutorTraceEventKind::SystemFinished, system);
+                if let (Ok(()), Some(change_recorder)) = (&res, context.environment.change_recorder)
+                {
+                    change_recorder.record(world, system, first_tick);
+                }
                 context.system_completed(system_index, res, system);
             };
 
@@ -720,17 +829,49 @@ impl ExecutorState {
     }
 }
 
+fn record_system(
+    trace: Option<&ExecutorTrace>,
+    kind: fn(Cow<'static, str>) -> ExecutorTraceEventKind,
+    system: &ScheduleSystem,
+) {
+    if let Some(trace) = trace {
+        trace.record(kind(system.name()));
+    }
+}
+
 fn apply_deferred(
     unapplied_systems: &FixedBitSet,
     systems: &[SyncUnsafeCell<ScheduleSystem>],
     world: &mut World,
+    trace: Option<&ExecutorTrace>,
+    change_recorder: Option<&SystemChangeRecorder>,
+) -> Result<(), Box<dyn Any + Send>> {
+    if let Some(trace) = trace {
+        trace.record(ExecutorTraceEventKind::ApplyDeferredStarted);
+    }
+    let res = apply_deferred_buffers(unapplied_systems, systems, world, change_recorder);
+    if let Some(trace) = trace {
+        trace.record(ExecutorTraceEventKind::ApplyDeferredFinished);
+    }
+    res
+}
+
+fn apply_deferred_buffers(
+    unapplied_systems: &FixedBitSet,
+    systems: &[SyncUnsafeCell<ScheduleSystem>],
+    world: &mut World,
+    change_recorder: Option<&SystemChangeRecorder>,
 ) -> Result<(), Box<dyn Any + Send>> {
     for system_index in unapplied_systems.ones() {
         // SAFETY: none of these systems are running, no other references exist
         let system = unsafe { &mut *systems[system_index].get() };
+        let first_tick = world.change_tick();
         let res = std::panic::catch_unwind(AssertUnwindSafe(|| {
             system.apply_deferred(world);
         }));
+        if let (Ok(()), Some(change_recorder)) = (&res, change_recorder) {
+            change_recorder.record(world, system, first_tick);
+        }
         if let Err(payload) = res {
             eprintln!(
                 "Encountered a panic when applying buffers for system `{}`!",
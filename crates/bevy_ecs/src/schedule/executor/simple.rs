@@ -11,7 +11,7 @@ use crate::{
     schedule::{
         executor::is_apply_deferred, BoxedCondition, ExecutorKind, SystemExecutor, SystemSchedule,
     },
-    world::World,
+    world::{SystemChangeRecorder, World},
 };
 
 use super::__rust_begin_short_backtrace;
@@ -52,6 +52,8 @@ impl SystemExecutor for SimpleExecutor {
             self.completed_systems |= skipped_systems;
         }
 
+        let change_recorder = SystemChangeRecorder::new(world);
+
         for system_index in 0..schedule.systems.len() {
             #[cfg(feature = "trace")]
             let name = schedule.systems[system_index].name();
@@ -103,6 +105,7 @@ impl SystemExecutor for SimpleExecutor {
                 continue;
             }
 
+            let first_tick = world.change_tick();
             let f = AssertUnwindSafe(|| {
                 // TODO: implement an error-handling API instead of panicking.
                 if let Err(err) = __rust_begin_short_backtrace::run(system, world) {
@@ -126,6 +129,14 @@ impl SystemExecutor for SimpleExecutor {
             {
                 (f)();
             }
+
+            if let Some(change_recorder) = &change_recorder {
+                change_recorder.record(world, system, first_tick);
+            }
+        }
+
+        if let Some(change_recorder) = change_recorder {
+            change_recorder.finish(world);
         }
 
         self.evaluated_sets.clear();
@@ -9,7 +9,7 @@ use std::eprintln;
 
 use crate::{
     schedule::{is_apply_deferred, BoxedCondition, ExecutorKind, SystemExecutor, SystemSchedule},
-    world::World,
+    world::{SystemChangeRecorder, World},
 };
 
 use super::__rust_begin_short_backtrace;
@@ -58,6 +58,8 @@ impl SystemExecutor for SingleThreadedExecutor {
             self.completed_systems |= skipped_systems;
         }
 
+        let change_recorder = SystemChangeRecorder::new(world);
+
         for system_index in 0..schedule.systems.len() {
             #[cfg(feature = "trace")]
             let name = schedule.systems[system_index].name();
@@ -106,10 +108,11 @@ impl SystemExecutor for SingleThreadedExecutor {
             }
 
             if is_apply_deferred(system) {
-                self.apply_deferred(schedule, world);
+                self.apply_deferred(schedule, world, change_recorder.as_ref());
                 continue;
             }
 
+            let first_tick = world.change_tick();
             let f = AssertUnwindSafe(|| {
                 if system.is_exclusive() {
                     // TODO: implement an error-handling API instead of panicking.
@@ -152,11 +155,17 @@ impl SystemExecutor for SingleThreadedExecutor {
                 (f)();
             }
 
+            if let Some(change_recorder) = &change_recorder {
+                change_recorder.record(world, system, first_tick);
+            }
             self.unapplied_systems.insert(system_index);
         }
 
         if self.apply_final_deferred {
-            self.apply_deferred(schedule, world);
+            self.apply_deferred(schedule, world, change_recorder.as_ref());
+        }
+        if let Some(change_recorder) = change_recorder {
+            change_recorder.finish(world);
         }
         self.evaluated_sets.clear();
         self.completed_systems.clear();
@@ -180,10 +189,19 @@ impl SingleThreadedExecutor {
         }
     }
 
-    fn apply_deferred(&mut self, schedule: &mut SystemSchedule, world: &mut World) {
+    fn apply_deferred(
+        &mut self,
+        schedule: &mut SystemSchedule,
+        world: &mut World,
+        change_recorder: Option<&SystemChangeRecorder>,
+    ) {
         for system_index in self.unapplied_systems.ones() {
             let system = &mut schedule.systems[system_index];
+            let first_tick = world.change_tick();
             system.apply_deferred(world);
+            if let Some(change_recorder) = change_recorder {
+                change_recorder.record(world, system, first_tick);
+            }
         }
 
         self.unapplied_systems.clear();
@@ -0,0 +1,548 @@
+use alloc::{
+    borrow::Cow,
+    collections::VecDeque,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use bevy_platform_support::{
+    collections::HashMap,
+    sync::{Arc, Weak},
+    time::Instant,
+};
+use core::{
+    cell::RefCell,
+    fmt::Write,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Duration,
+};
+use std::{
+    sync::{Mutex, MutexGuard},
+    thread,
+};
+
+use crate::{resource::Resource, schedule::json::write_json_string};
+
+/// Records what the [`MultiThreadedExecutor`](super::MultiThreadedExecutor) does while running
+/// schedules, for profiling without an external profiler.
+///
+/// Tracing is opt-in: insert this resource into the [`World`](crate::world::World) to start
+/// recording. The executor then records when each system starts and finishes and on which thread,
+/// which running systems blocked a system from starting, and how long applying deferred commands
+/// took.
+///
+/// Each thread records into its own buffer, so that recording doesn't serialize the threads. The
+/// buffers are merged, ordered by time, into a ring buffer holding the most recent events at the
+/// end of each schedule run, and whenever the events are read. Events are grouped into frames,
+/// which are advanced with [`ExecutorTrace::advance_frame`]. Frames can be exported in the
+/// [Chrome trace event format] with [`ExecutorTrace::to_chrome_trace`], and viewed in
+/// `chrome://tracing` or [Perfetto](https://ui.perfetto.dev).
+///
+/// [Chrome trace event format]: https://docs.google.com/document/d/1CvAClvFfyA5R-PhYUmn5OOQtYMH4h6I0nSsKchNAySU
+#[derive(Resource, Clone)]
+pub struct ExecutorTrace {
+    shared: Arc<SharedTrace>,
+}
+
+struct SharedTrace {
+    /// Identifies the trace in the buffers cached by each thread.
+    id: u64,
+    start: Instant,
+    frame: AtomicU64,
+    buffer: Mutex<TraceBuffer>,
+    /// The buffers of the threads that recorded events.
+    threads: Mutex<Vec<Arc<ThreadBuffer>>>,
+}
+
+struct TraceBuffer {
+    capacity: usize,
+    events: VecDeque<ExecutorTraceEvent>,
+    thread_names: HashMap<u64, String>,
+}
+
+/// The events recorded by one thread, which only that thread locks until they're flushed.
+struct ThreadBuffer {
+    thread: u64,
+    name: String,
+    events: Mutex<Vec<ExecutorTraceEvent>>,
+}
+
+std::thread_local! {
+    /// The buffers of the current thread, by the ID of their trace.
+    static THREAD_BUFFERS: RefCell<Vec<(u64, Weak<ThreadBuffer>)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// An event recorded by an [`ExecutorTrace`].
+#[derive(Clone, Debug)]
+pub struct ExecutorTraceEvent {
+    /// The frame during which the event was recorded.
+    pub frame: u64,
+    /// The time of the event, since the [`ExecutorTrace`] was created.
+    pub timestamp: Duration,
+    /// The index of the thread the event was recorded on, unique for each thread of the process.
+    pub thread: u64,
+    /// What happened.
+    pub kind: ExecutorTraceEventKind,
+}
+
+/// What happened in an [`ExecutorTraceEvent`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ExecutorTraceEventKind {
+    /// The system with the given name started running.
+    SystemStarted(Cow<'static, str>),
+    /// The system with the given name finished running.
+    SystemFinished(Cow<'static, str>),
+    /// A system was ready to run, but couldn't start because of running systems: its access
+    /// conflicts with theirs, it's exclusive, or it's `!Send` while another `!Send` or exclusive
+    /// system runs on the main thread.
+    ///
+    /// This is only recorded the first time the system is blocked while running a schedule.
+    SystemBlocked {
+        /// The name of the blocked system.
+        system: Cow<'static, str>,
+        /// The names of the running systems it waits for.
+        conflicts: Vec<Cow<'static, str>>,
+    },
+    /// The executor started applying the deferred commands of systems.
+    ApplyDeferredStarted,
+    /// The executor finished applying the deferred commands of systems.
+    ApplyDeferredFinished,
+}
+
+impl Default for ExecutorTrace {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl ExecutorTrace {
+    /// The number of events kept by [`ExecutorTrace::default`].
+    pub const DEFAULT_CAPACITY: usize = 65536;
+
+    /// Creates a trace that keeps the `capacity` most recent events.
+    pub fn new(capacity: usize) -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        Self {
+            shared: Arc::new(SharedTrace {
+                id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+                start: Instant::now(),
+                frame: AtomicU64::new(0),
+                buffer: Mutex::new(TraceBuffer {
+                    capacity,
+                    events: VecDeque::with_capacity(capacity),
+                    thread_names: HashMap::default(),
+                }),
+                threads: Mutex::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Returns the current frame, to which new events are added.
+    pub fn frame(&self) -> u64 {
+        self.shared.frame.load(Ordering::Relaxed)
+    }
+
+    /// Starts a new frame.
+    ///
+    /// This is called at the end of each update of the `Main` schedule of `bevy_app`.
+    pub fn advance_frame(&self) {
+        self.flush();
+        self.shared.frame.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Records an event on the current thread.
+    pub fn record(&self, kind: ExecutorTraceEventKind) {
+        let timestamp = self.shared.start.elapsed();
+        let frame = self.frame();
+        let thread_buffer = self.thread_buffer();
+        thread_buffer
+            .events
+            .lock()
+            .unwrap()
+            .push(ExecutorTraceEvent {
+                frame,
+                timestamp,
+                thread: thread_buffer.thread,
+                kind,
+            });
+    }
+
+    /// Returns the buffer of the current thread, registering a new one on its first event.
+    fn thread_buffer(&self) -> Arc<ThreadBuffer> {
+        THREAD_BUFFERS.with_borrow_mut(|buffers| {
+            if let Some(buffer) = buffers
+                .iter()
+                .find(|(id, _)| *id == self.shared.id)
+                .and_then(|(_, buffer)| buffer.upgrade())
+            {
+                return buffer;
+            }
+
+            // Forget the buffers of dropped traces.
+            buffers.retain(|(_, buffer)| buffer.strong_count() > 0);
+            let thread = current_thread_index();
+            let buffer = Arc::new(ThreadBuffer {
+                thread,
+                name: thread::current()
+                    .name()
+                    .map_or_else(|| thread.to_string(), ToString::to_string),
+                events: Mutex::new(Vec::new()),
+            });
+            self.shared.threads.lock().unwrap().push(buffer.clone());
+            buffers.push((self.shared.id, Arc::downgrade(&buffer)));
+            buffer
+        })
+    }
+
+    /// Moves the events recorded by each thread into the ring buffer, ordered by time.
+    ///
+    /// The executor calls this at the end of each run of a schedule.
+    pub fn flush(&self) {
+        drop(self.flushed_buffer());
+    }
+
+    /// Flushes the events of each thread, and returns the locked ring buffer.
+    fn flushed_buffer(&self) -> MutexGuard<'_, TraceBuffer> {
+        let mut buffer = self.shared.buffer.lock().unwrap();
+        let mut events = Vec::new();
+        for thread_buffer in self.shared.threads.lock().unwrap().iter() {
+            events.append(&mut thread_buffer.events.lock().unwrap());
+            buffer
+                .thread_names
+                .entry(thread_buffer.thread)
+                .or_insert_with(|| thread_buffer.name.clone());
+        }
+        events.sort_by_key(|event| event.timestamp);
+
+        if buffer.capacity > 0 {
+            for event in events {
+                if buffer.events.len() == buffer.capacity {
+                    buffer.events.pop_front();
+                }
+                buffer.events.push_back(event);
+            }
+        }
+        buffer
+    }
+
+    /// Returns the recorded events of `frame`, oldest first.
+    ///
+    /// Events that no longer fit in the ring buffer have been dropped.
+    pub fn events(&self, frame: u64) -> Vec<ExecutorTraceEvent> {
+        self.flushed_buffer()
+            .events
+            .iter()
+            .filter(|event| event.frame == frame)
+            .cloned()
+            .collect()
+    }
+
+    /// Removes all recorded events.
+    pub fn clear(&self) {
+        self.flushed_buffer().events.clear();
+    }
+
+    /// Exports the recorded events of `frame` as Chrome trace event JSON.
+    ///
+    /// Systems and command application are exported as duration events on the thread they ran on,
+    /// and blocked systems as instant events listing the systems they wait for. Duration events
+    /// whose start or end is missing, because it was dropped from the ring buffer or recorded in
+    /// another frame, are left out.
+    pub fn to_chrome_trace(&self, frame: u64) -> String {
+        let buffer = self.flushed_buffer();
+        let events: Vec<_> = buffer
+            .events
+            .iter()
+            .filter(|event| event.frame == frame)
+            .collect();
+        let paired = paired_events(&events);
+
+        let mut json = String::from("{\"traceEvents\":[");
+        let mut first = true;
+        let mut separator = |json: &mut String| {
+            if !first {
+                json.push(',');
+            }
+            first = false;
+        };
+
+        let mut threads: Vec<_> = events.iter().map(|event| event.thread).collect();
+        threads.sort_unstable();
+        threads.dedup();
+        for thread in threads {
+            separator(&mut json);
+            let _ = write!(
+                json,
+                "{{\"name\":\"thread_name\",\"ph\":\"M\",\"pid\":0,\"tid\":{thread},\"args\":{{\"name\":"
+            );
+            write_json_string(&mut json, &buffer.thread_names[&thread]);
+            json.push_str("}}");
+        }
+
+        for (event, _) in events.iter().zip(&paired).filter(|(_, paired)| **paired) {
+            separator(&mut json);
+            let (name, category, phase) = match &event.kind {
+                ExecutorTraceEventKind::SystemStarted(system) => (system, "system", "B"),
+                ExecutorTraceEventKind::SystemFinished(system) => (system, "system", "E"),
+                ExecutorTraceEventKind::SystemBlocked { system, .. } => (system, "blocked", "i"),
+                ExecutorTraceEventKind::ApplyDeferredStarted => {
+                    (&Cow::Borrowed("apply_deferred"), "commands", "B")
+                }
+                ExecutorTraceEventKind::ApplyDeferredFinished => {
+                    (&Cow::Borrowed("apply_deferred"), "commands", "E")
+                }
+            };
+            json.push_str("{\"name\":");
+            write_json_string(&mut json, name);
+            let _ = write!(
+                json,
+                ",\"cat\":\"{category}\",\"ph\":\"{phase}\",\"ts\":{:.3},\"pid\":0,\"tid\":{}",
+                event.timestamp.as_secs_f64() * 1_000_000.0,
+                event.thread,
+            );
+            if let ExecutorTraceEventKind::SystemBlocked { conflicts, .. } = &event.kind {
+                json.push_str(",\"s\":\"t\",\"args\":{\"conflicts\":[");
+                for (index, conflict) in conflicts.iter().enumerate() {
+                    if index > 0 {
+                        json.push(',');
+                    }
+                    write_json_string(&mut json, conflict);
+                }
+                json.push_str("]}");
+            }
+            json.push('}');
+        }
+
+        json.push_str("]}");
+        json
+    }
+}
+
+/// Returns, for each event, whether it's exported: duration events are only exported when both
+/// their start and their end are in `events`, which holds the events of a single frame.
+fn paired_events(events: &[&ExecutorTraceEvent]) -> Vec<bool> {
+    let mut exported = vec![true; events.len()];
+    // The started duration events of each thread, innermost last.
+    let mut open: HashMap<u64, Vec<usize>> = HashMap::default();
+    for (index, event) in events.iter().enumerate() {
+        match &event.kind {
+            ExecutorTraceEventKind::SystemStarted(_)
+            | ExecutorTraceEventKind::ApplyDeferredStarted => {
+                open.entry(event.thread).or_default().push(index);
+            }
+            ExecutorTraceEventKind::SystemFinished(_)
+            | ExecutorTraceEventKind::ApplyDeferredFinished => {
+                let started = open.get_mut(&event.thread).and_then(|open| {
+                    let position = open
+                        .iter()
+                        .rposition(|&started| starts(&events[started].kind, &event.kind))?;
+                    // Durations started within this one never finished.
+                    for unfinished in open.drain(position + 1..) {
+                        exported[unfinished] = false;
+                    }
+                    open.pop()
+                });
+                if started.is_none() {
+                    exported[index] = false;
+                }
+            }
+            ExecutorTraceEventKind::SystemBlocked { .. } => {}
+        }
+    }
+    for unfinished in open.into_values().flatten() {
+        exported[unfinished] = false;
+    }
+    exported
+}
+
+/// Returns `true` if `finished` is the end of the duration that `started` starts.
+fn starts(started: &ExecutorTraceEventKind, finished: &ExecutorTraceEventKind) -> bool {
+    match (started, finished) {
+        (
+            ExecutorTraceEventKind::SystemStarted(started),
+            ExecutorTraceEventKind::SystemFinished(finished),
+        ) => started == finished,
+        (
+            ExecutorTraceEventKind::ApplyDeferredStarted,
+            ExecutorTraceEventKind::ApplyDeferredFinished,
+        ) => true,
+        _ => false,
+    }
+}
+
+/// Returns an index unique to the current thread, which is cheaper to record than its name.
+fn current_thread_index() -> u64 {
+    static NEXT_THREAD_INDEX: AtomicU64 = AtomicU64::new(0);
+    std::thread_local! {
+        static THREAD_INDEX: u64 = NEXT_THREAD_INDEX.fetch_add(1, Ordering::Relaxed);
+    }
+    THREAD_INDEX.with(|index| *index)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        prelude::{Resource, World},
+        schedule::{ExecutorKind, IntoSystemConfigs, Schedule},
+        system::{Commands, ResMut},
+    };
+
+    #[derive(Resource, Default)]
+    struct Counter(u32);
+
+    fn increment(mut counter: ResMut<Counter>) {
+        counter.0 += 1;
+    }
+
+    fn spawn(mut commands: Commands) {
+        commands.spawn_empty();
+    }
+
+    #[test]
+    fn records_executor_events() {
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let trace = ExecutorTrace::default();
+        world.insert_resource(trace.clone());
+
+        let mut schedule = Schedule::default();
+        schedule.set_executor_kind(ExecutorKind::MultiThreaded);
+        // Both systems write `Counter`, so whichever runs second is blocked by the first.
+        schedule.add_systems((increment, (increment, spawn).chain()));
+        schedule.run(&mut world);
+
+        let events = trace.events(0);
+        let started = events
+            .iter()
+            .filter(|event| matches!(event.kind, ExecutorTraceEventKind::SystemStarted(_)))
+            .count();
+        let finished = events
+            .iter()
+            .filter(|event| matches!(event.kind, ExecutorTraceEventKind::SystemFinished(_)))
+            .count();
+        assert_eq!(started, 3);
+        assert_eq!(finished, 3);
+        assert!(events.iter().any(|event| matches!(
+            &event.kind,
+            ExecutorTraceEventKind::SystemBlocked { conflicts, .. } if !conflicts.is_empty()
+        )));
+        assert!(events
+            .iter()
+            .any(|event| event.kind == ExecutorTraceEventKind::ApplyDeferredFinished));
+
+        trace.advance_frame();
+        schedule.run(&mut world);
+        assert!(!trace.events(1).is_empty());
+        assert_eq!(trace.events(0).len(), events.len());
+    }
+
+    #[test]
+    fn records_blocked_exclusive_systems() {
+        fn exclusive(_: &mut World) {}
+
+        let mut world = World::new();
+        world.init_resource::<Counter>();
+        let trace = ExecutorTrace::default();
+        world.insert_resource(trace.clone());
+
+        let mut schedule = Schedule::default();
+        schedule.set_executor_kind(ExecutorKind::MultiThreaded);
+        schedule.add_systems((exclusive, increment));
+        schedule.run(&mut world);
+
+        // The executor spawns `increment` first, so the exclusive system waits for it.
+        assert!(trace.events(0).iter().any(|event| matches!(
+            &event.kind,
+            ExecutorTraceEventKind::SystemBlocked { system, conflicts }
+                if system.ends_with("exclusive") && conflicts.len() == 1
+        )));
+    }
+
+    #[test]
+    fn merges_events_of_each_thread() {
+        let trace = ExecutorTrace::default();
+        thread::scope(|scope| {
+            for _ in 0..4 {
+                scope.spawn(|| {
+                    for _ in 0..10 {
+                        trace.record(ExecutorTraceEventKind::ApplyDeferredStarted);
+                        trace.record(ExecutorTraceEventKind::ApplyDeferredFinished);
+                    }
+                });
+            }
+        });
+
+        let events = trace.events(0);
+        assert_eq!(events.len(), 80);
+        assert!(events
+            .windows(2)
+            .all(|pair| pair[0].timestamp <= pair[1].timestamp));
+        let mut threads: Vec<_> = events.iter().map(|event| event.thread).collect();
+        threads.sort_unstable();
+        threads.dedup();
+        assert_eq!(threads.len(), 4);
+    }
+
+    #[test]
+    fn chrome_trace_drops_unpaired_events() {
+        let trace = ExecutorTrace::new(3);
+        trace.record(ExecutorTraceEventKind::SystemStarted("evicted".into()));
+        trace.record(ExecutorTraceEventKind::SystemFinished("evicted".into()));
+        trace.record(ExecutorTraceEventKind::SystemStarted("complete".into()));
+        trace.record(ExecutorTraceEventKind::SystemFinished("complete".into()));
+
+        // Only the end of `evicted` is left in the ring buffer.
+        let json = trace.to_chrome_trace(0);
+        assert!(!json.contains("evicted"));
+        assert_eq!(json.matches("\"name\":\"complete\"").count(), 2);
+
+        trace.record(ExecutorTraceEventKind::SystemStarted("unfinished".into()));
+        let json = trace.to_chrome_trace(0);
+        assert!(!json.contains("unfinished"));
+        assert_eq!(json.matches("\"name\":\"complete\"").count(), 2);
+    }
+
+    #[test]
+    fn ring_buffer_drops_oldest_events() {
+        let trace = ExecutorTrace::new(2);
+        trace.record(ExecutorTraceEventKind::ApplyDeferredStarted);
+        trace.record(ExecutorTraceEventKind::ApplyDeferredFinished);
+        trace.record(ExecutorTraceEventKind::SystemStarted("a".into()));
+        let kinds: Vec<_> = trace
+            .events(0)
+            .into_iter()
+            .map(|event| event.kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                ExecutorTraceEventKind::ApplyDeferredFinished,
+                ExecutorTraceEventKind::SystemStarted("a".into())
+            ]
+        );
+    }
+
+    #[test]
+    fn chrome_trace_export() {
+        let trace = ExecutorTrace::default();
+        trace.record(ExecutorTraceEventKind::SystemStarted(
+            "my_\"system\"".into(),
+        ));
+        trace.record(ExecutorTraceEventKind::SystemBlocked {
+            system: "other".into(),
+            conflicts: alloc::vec!["my_\"system\"".into()],
+        });
+        trace.record(ExecutorTraceEventKind::SystemFinished(
+            "my_\"system\"".into(),
+        ));
+
+        let json = trace.to_chrome_trace(0);
+        assert!(json.starts_with("{\"traceEvents\":[{\"name\":\"thread_name\",\"ph\":\"M\""));
+        assert!(json.contains("{\"name\":\"my_\\\"system\\\"\",\"cat\":\"system\",\"ph\":\"B\""));
+        assert!(json.contains("\"ph\":\"i\""));
+        assert!(json.contains("\"args\":{\"conflicts\":[\"my_\\\"system\\\"\"]}"));
+        assert!(json.ends_with("}]}"));
+        assert_eq!(trace.to_chrome_trace(1), "{\"traceEvents\":[]}");
+    }
+}
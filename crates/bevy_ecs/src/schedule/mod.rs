@@ -1,16 +1,18 @@
 //! Contains APIs for ordering systems and executing them on a [`World`](crate::world::World)
 
+mod ambiguity;
 mod auto_insert_apply_deferred;
 mod condition;
 mod config;
 mod executor;
+mod json;
 mod pass;
 mod schedule;
 mod set;
 mod stepping;
 
 use self::graph::*;
-pub use self::{condition::*, config::*, executor::*, schedule::*, set::*};
+pub use self::{ambiguity::*, condition::*, config::*, executor::*, schedule::*, set::*};
 pub use pass::ScheduleBuildPass;
 
 pub use self::graph::NodeId;
@@ -437,6 +437,9 @@ impl Schedule {
         self.initialize(world)
             .unwrap_or_else(|e| panic!("Error when initializing schedule {:?}: {e}", self.label));
 
+        // Record the changes made before this run, so they aren't attributed to its first system.
+        world.record_component_changes();
+
         #[cfg(not(feature = "bevy_debug_stepping"))]
         self.executor.run(&mut self.executable, world, None);
 
@@ -563,6 +566,49 @@ impl Schedule {
             self.executable.systems.len()
         }
     }
+
+    /// Creates an [`AmbiguityReport`] of the system order ambiguities in this schedule.
+    ///
+    /// The schedule is initialized first, like with [`Schedule::initialize`], so this works at any
+    /// time, and returns an error if the schedule can't be built. See
+    /// [`ScheduleGraph::ambiguity_report`] for how orderings are suggested.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Resource, Default)]
+    /// struct Score(u32);
+    ///
+    /// fn add_points(score: ResMut<Score>) {}
+    /// fn reset_score(score: ResMut<Score>) {}
+    ///
+    /// let mut world = World::new();
+    /// world.init_resource::<Score>();
+    /// let mut schedule = Schedule::default();
+    /// schedule.add_systems((add_points, reset_score));
+    ///
+    /// let report = schedule.ambiguity_report(&mut world).unwrap();
+    /// assert_eq!(report.write_write_count(), 1);
+    /// assert_eq!(report.suggestions.len(), 1);
+    /// ```
+    pub fn ambiguity_report(
+        &mut self,
+        world: &mut World,
+    ) -> Result<AmbiguityReport, ScheduleBuildError> {
+        self.initialize(world)?;
+
+        let systems: HashMap<NodeId, &ScheduleSystem> = self
+            .executable
+            .system_ids
+            .iter()
+            .copied()
+            .zip(&self.executable.systems)
+            .collect();
+        let report = self
+            .graph
+            .build_ambiguity_report(world.components(), |id| systems.get(&id).copied())
+            .expect("an initialized schedule holds all of its systems");
+        Ok(report)
+    }
 }
 
 /// A directed acyclic graph structure.
@@ -1549,8 +1595,18 @@ impl ScheduleGraph {
         self.get_node_name_inner(id, self.settings.report_sets)
     }
 
+    /// Returns the name of `system`, shortened if [`ScheduleBuildSettings::use_shortnames`] is set.
+    pub(super) fn system_name(&self, system: &ScheduleSystem) -> String {
+        let name = system.name();
+        if self.settings.use_shortnames {
+            ShortName(&name).to_string()
+        } else {
+            name.into_owned()
+        }
+    }
+
     #[inline]
-    fn get_node_name_inner(&self, id: &NodeId, report_sets: bool) -> String {
+    pub(super) fn get_node_name_inner(&self, id: &NodeId, report_sets: bool) -> String {
         let name = match id {
             NodeId::System(_) => {
                 let name = self.systems[id.index()].get().unwrap().name().to_string();
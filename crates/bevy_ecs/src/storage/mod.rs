@@ -13,20 +13,26 @@
 //!    lookup and regular insertion/removal of components.
 //!  - [`Resources`] - singleton storage for the resources in the world
 //!
+//! A summary of how entities are laid out in these stores, and how much memory their components
+//! use, can be built with [`World::storage_report`].
+//!
 //! # Safety
 //! To avoid trivially unsound use of the APIs in this module, it is explicitly impossible to get a mutable
 //! reference to [`Storages`] from [`World`], and none of the types publicly expose a mutable interface.
 //!
 //! [`World`]: crate::world::World
 //! [`World::storages`]: crate::world::World::storages
+//! [`World::storage_report`]: crate::world::World::storage_report
 
 mod blob_array;
 mod blob_vec;
+mod report;
 mod resource;
 mod sparse_set;
 mod table;
 mod thin_array_ptr;
 
+pub use report::*;
 pub use resource::*;
 pub use sparse_set::*;
 pub use table::*;
@@ -0,0 +1,364 @@
+use alloc::vec::Vec;
+use core::mem::size_of;
+
+use crate::{
+    archetype::ArchetypeId,
+    change_detection::MaybeLocation,
+    component::{ComponentId, Tick},
+    storage::TableId,
+    world::World,
+};
+
+/// A summary of the [`Archetype`]s, [`Table`]s and [`ComponentSparseSet`]s of a [`World`], and of
+/// the memory used by their components, built with [`World::storage_report`].
+///
+/// [`Archetype`]: crate::archetype::Archetype
+/// [`Table`]: super::Table
+/// [`ComponentSparseSet`]: super::ComponentSparseSet
+#[derive(Clone, Debug)]
+pub struct StorageReport {
+    /// The archetypes of the world, in [`ArchetypeId`] order.
+    pub archetypes: Vec<ArchetypeReport>,
+    /// The tables of the world, in [`TableId`] order.
+    pub tables: Vec<TableReport>,
+    /// The sparse sets of the world.
+    pub sparse_sets: Vec<SparseSetReport>,
+    /// The number of times an entity has moved between archetypes.
+    ///
+    /// See [`Archetypes::entity_moves`](crate::archetype::Archetypes::entity_moves).
+    pub entity_moves: u64,
+}
+
+/// The totals of a [`StorageReport`], built with [`World::storage_summary`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StorageSummary {
+    /// The number of archetypes.
+    pub archetype_count: usize,
+    /// The number of archetypes without any entities.
+    ///
+    /// See [`StorageReport::empty_archetype_count`].
+    pub empty_archetype_count: usize,
+    /// The number of tables.
+    pub table_count: usize,
+    /// The number of tables without any entities.
+    pub empty_table_count: usize,
+    /// The bytes used by the component values of all tables and sparse sets.
+    pub component_bytes: usize,
+    /// The bytes allocated for the columns of all tables.
+    pub allocated_table_bytes: usize,
+    /// The number of times an entity has moved between archetypes.
+    pub entity_moves: u64,
+}
+
+/// The part of a [`StorageReport`] describing an [`Archetype`](crate::archetype::Archetype).
+#[derive(Clone, Debug)]
+pub struct ArchetypeReport {
+    /// The id of the archetype.
+    pub id: ArchetypeId,
+    /// The id of the table storing the archetype's table components.
+    pub table: TableId,
+    /// The number of entities in the archetype.
+    pub entity_count: usize,
+    /// The components of the archetype.
+    pub components: Vec<ComponentId>,
+}
+
+/// The part of a [`StorageReport`] describing a [`Table`](super::Table).
+#[derive(Clone, Debug)]
+pub struct TableReport {
+    /// The id of the table.
+    pub id: TableId,
+    /// The number of entities in the table.
+    pub entity_count: usize,
+    /// The number of entities the table can store without reallocating.
+    pub capacity: usize,
+    /// The number of archetypes sharing the table.
+    ///
+    /// Archetypes that only differ by sparse set components share the same table.
+    pub archetype_count: usize,
+    /// The columns of the table.
+    pub columns: Vec<ColumnReport>,
+}
+
+/// The part of a [`TableReport`] describing a column of a [`Table`](super::Table).
+#[derive(Clone, Debug)]
+pub struct ColumnReport {
+    /// The component stored in the column.
+    pub component: ComponentId,
+    /// The size of a single component value, in bytes.
+    pub item_size: usize,
+    /// The bytes used by the component values stored in the column.
+    pub bytes: usize,
+    /// The bytes allocated for the column, including unused capacity and change detection ticks.
+    pub allocated_bytes: usize,
+}
+
+/// The part of a [`StorageReport`] describing a [`ComponentSparseSet`](super::ComponentSparseSet).
+#[derive(Clone, Debug)]
+pub struct SparseSetReport {
+    /// The component stored in the sparse set.
+    pub component: ComponentId,
+    /// The number of entities with the component.
+    pub entity_count: usize,
+    /// The size of a single component value, in bytes.
+    pub item_size: usize,
+    /// The bytes used by the component values stored in the sparse set.
+    pub bytes: usize,
+}
+
+impl StorageReport {
+    /// Returns the bytes used by the component values of all tables and sparse sets.
+    pub fn component_bytes(&self) -> usize {
+        let table_bytes: usize = self
+            .tables
+            .iter()
+            .flat_map(|table| &table.columns)
+            .map(|column| column.bytes)
+            .sum();
+        let sparse_set_bytes: usize = self.sparse_sets.iter().map(|set| set.bytes).sum();
+        table_bytes + sparse_set_bytes
+    }
+
+    /// Returns the bytes allocated for the columns of all tables.
+    pub fn allocated_table_bytes(&self) -> usize {
+        self.tables
+            .iter()
+            .flat_map(|table| &table.columns)
+            .map(|column| column.allocated_bytes)
+            .sum()
+    }
+
+    /// Returns the number of archetypes without any entities.
+    ///
+    /// A large number of empty archetypes is a sign of fragmentation, for example from
+    /// frequently adding and removing many different combinations of components.
+    pub fn empty_archetype_count(&self) -> usize {
+        self.archetypes
+            .iter()
+            .filter(|archetype| archetype.entity_count == 0)
+            .count()
+    }
+
+    /// Returns the number of tables without any entities.
+    pub fn empty_table_count(&self) -> usize {
+        self.tables
+            .iter()
+            .filter(|table| table.entity_count == 0)
+            .count()
+    }
+
+    /// Returns the totals of this report.
+    pub fn summary(&self) -> StorageSummary {
+        StorageSummary {
+            archetype_count: self.archetypes.len(),
+            empty_archetype_count: self.empty_archetype_count(),
+            table_count: self.tables.len(),
+            empty_table_count: self.empty_table_count(),
+            component_bytes: self.component_bytes(),
+            allocated_table_bytes: self.allocated_table_bytes(),
+            entity_moves: self.entity_moves,
+        }
+    }
+}
+
+/// The bytes allocated for each entity a column can store, on top of the component value.
+const CHANGE_DETECTION_SIZE: usize = 2 * size_of::<Tick>() + size_of::<MaybeLocation>();
+
+impl World {
+    /// Builds a [`StorageReport`] describing how the entities of this world are laid out in
+    /// archetypes, tables and sparse sets, and how much memory their components use.
+    ///
+    /// ```
+    /// # use bevy_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Position(f32, f32);
+    ///
+    /// let mut world = World::new();
+    /// world.spawn(Position(0.0, 0.0));
+    ///
+    /// let report = world.storage_report();
+    /// assert_eq!(report.component_bytes(), 8);
+    /// ```
+    pub fn storage_report(&self) -> StorageReport {
+        let archetypes = self.archetypes();
+        let tables = &self.storages().tables;
+        let item_size = |component| self.component_item_size(component);
+
+        let mut archetype_counts = alloc::vec![0; tables.len()];
+        for archetype in archetypes.iter() {
+            archetype_counts[archetype.table_id().as_usize()] += 1;
+        }
+
+        StorageReport {
+            archetypes: archetypes
+                .iter()
+                .map(|archetype| ArchetypeReport {
+                    id: archetype.id(),
+                    table: archetype.table_id(),
+                    entity_count: archetype.len(),
+                    components: archetype.components().collect(),
+                })
+                .collect(),
+            tables: tables
+                .iter()
+                .enumerate()
+                .map(|(index, table)| TableReport {
+                    id: TableId::from_usize(index),
+                    entity_count: table.entity_count(),
+                    capacity: table.capacity(),
+                    archetype_count: archetype_counts[index],
+                    columns: table
+                        .component_ids()
+                        .map(|component| {
+                            let item_size = item_size(component);
+                            ColumnReport {
+                                component,
+                                item_size,
+                                bytes: item_size * table.entity_count(),
+                                allocated_bytes: (item_size + CHANGE_DETECTION_SIZE)
+                                    * table.capacity(),
+                            }
+                        })
+                        .collect(),
+                })
+                .collect(),
+            sparse_sets: self
+                .storages()
+                .sparse_sets
+                .iter()
+                .map(|(component, sparse_set)| {
+                    let item_size = item_size(component);
+                    SparseSetReport {
+                        component,
+                        entity_count: sparse_set.len(),
+                        item_size,
+                        bytes: item_size * sparse_set.len(),
+                    }
+                })
+                .collect(),
+            entity_moves: archetypes.entity_moves(),
+        }
+    }
+
+    /// Computes the totals of the [`World::storage_report`] of this world, without building a
+    /// report of every archetype and table.
+    ///
+    /// This is cheap enough to call every frame.
+    pub fn storage_summary(&self) -> StorageSummary {
+        let archetypes = self.archetypes();
+        let storages = self.storages();
+        let mut summary = StorageSummary {
+            archetype_count: archetypes.len(),
+            empty_archetype_count: archetypes
+                .iter()
+                .filter(|archetype| archetype.is_empty())
+                .count(),
+            table_count: storages.tables.len(),
+            entity_moves: archetypes.entity_moves(),
+            ..StorageSummary::default()
+        };
+
+        for table in storages.tables.iter() {
+            if table.is_empty() {
+                summary.empty_table_count += 1;
+            }
+            for component in table.component_ids() {
+                let item_size = self.component_item_size(component);
+                summary.component_bytes += item_size * table.entity_count();
+                summary.allocated_table_bytes +=
+                    (item_size + CHANGE_DETECTION_SIZE) * table.capacity();
+            }
+        }
+        for (component, sparse_set) in storages.sparse_sets.iter() {
+            summary.component_bytes += self.component_item_size(component) * sparse_set.len();
+        }
+
+        summary
+    }
+
+    /// Returns the size of a single value of a component, in bytes.
+    fn component_item_size(&self, component: ComponentId) -> usize {
+        self.components()
+            .get_info(component)
+            .map_or(0, |info| info.layout().size())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{component::Component, world::World};
+
+    #[expect(
+        dead_code,
+        reason = "The field is never read, it only gives the component its size."
+    )]
+    #[derive(Component)]
+    struct A(u32);
+
+    #[expect(
+        dead_code,
+        reason = "The field is never read, it only gives the component its size."
+    )]
+    #[derive(Component)]
+    #[component(storage = "SparseSet")]
+    struct B(u64);
+
+    #[test]
+    fn storage_report() {
+        let mut world = World::new();
+        let a = world.register_component::<A>();
+        let b = world.register_component::<B>();
+        world.spawn(A(0));
+        world.spawn((A(1), B(1)));
+        world.spawn((A(2), B(2)));
+
+        let report = world.storage_report();
+        let with_a = report
+            .archetypes
+            .iter()
+            .find(|archetype| archetype.components == [a])
+            .unwrap();
+        let with_a_b = report
+            .archetypes
+            .iter()
+            .find(|archetype| archetype.components.len() == 2)
+            .unwrap();
+        assert_eq!(with_a.entity_count, 1);
+        assert_eq!(with_a_b.entity_count, 2);
+        assert_eq!(with_a.table, with_a_b.table);
+
+        let table = &report.tables[with_a.table.as_usize()];
+        assert_eq!(table.entity_count, 3);
+        assert_eq!(table.archetype_count, 2);
+        assert_eq!(table.columns.len(), 1);
+        assert_eq!(table.columns[0].component, a);
+        assert_eq!(table.columns[0].bytes, 12);
+        assert!(table.columns[0].allocated_bytes >= 12);
+
+        let sparse_set = report
+            .sparse_sets
+            .iter()
+            .find(|set| set.component == b)
+            .unwrap();
+        assert_eq!(sparse_set.entity_count, 2);
+        assert_eq!(sparse_set.bytes, 16);
+        assert_eq!(report.component_bytes(), 28);
+        assert_eq!(world.storage_summary(), report.summary());
+    }
+
+    #[test]
+    fn entity_moves() {
+        let mut world = World::new();
+        let entity = world.spawn(A(0)).id();
+        let moves = world.archetypes().entity_moves();
+
+        world.entity_mut(entity).insert(B(0));
+        world.entity_mut(entity).insert(A(1));
+        assert_eq!(world.archetypes().entity_moves(), moves + 1);
+
+        world.entity_mut(entity).remove::<B>();
+        world.entity_mut(entity).remove::<B>();
+        assert_eq!(world.archetypes().entity_moves(), moves + 2);
+        assert_eq!(world.storage_report().entity_moves, moves + 2);
+    }
+}
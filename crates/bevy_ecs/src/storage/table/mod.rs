@@ -497,6 +497,12 @@ impl Table {
         self.columns.contains(component_id)
     }
 
+    /// Iterates over the [`ComponentId`]s of the components stored in the table.
+    #[inline]
+    pub fn component_ids(&self) -> impl Iterator<Item = ComponentId> + '_ {
+        self.columns.indices()
+    }
+
     /// Reserves `additional` elements worth of capacity within the table.
     pub(crate) fn reserve(&mut self, additional: usize) {
         if self.capacity() - self.entity_count() < additional {
@@ -267,6 +267,18 @@ pub fn observe<E: Event, B: Bundle, M>(
     }
 }
 
+/// An [`EntityCommand`] that creates an [`Observer`](crate::observer::Observer)
+/// listening for events of type `E` during their capture phase on an entity
+#[track_caller]
+pub fn observe_capture<E: Event, B: Bundle, M>(
+    observer: impl IntoObserverSystem<E, B, M>,
+) -> impl EntityCommand {
+    let caller = MaybeLocation::caller();
+    move |mut entity: EntityWorldMut| {
+        entity.observe_capture_with_caller(observer, caller);
+    }
+}
+
 /// An [`EntityCommand`] that clones parts of an entity onto another entity,
 /// configured through [`EntityClonerBuilder`].
 pub fn clone_with(
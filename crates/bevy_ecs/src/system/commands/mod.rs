@@ -1910,6 +1910,15 @@ impl<'a> EntityCommands<'a> {
         self.queue(entity_command::observe(observer))
     }
 
+    /// Creates an [`Observer`] listening for events of type `E` during their capture phase on this
+    /// entity. See [`Observer::capture`] for more information.
+    pub fn observe_capture<E: Event, B: Bundle, M>(
+        &mut self,
+        observer: impl IntoObserverSystem<E, B, M>,
+    ) -> &mut Self {
+        self.queue(entity_command::observe_capture(observer))
+    }
+
     /// Clones parts of an entity (components, observers, etc.) onto another entity,
     /// configured through [`EntityClonerBuilder`].
     ///
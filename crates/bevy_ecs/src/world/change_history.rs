@@ -0,0 +1,704 @@
+//! Recording the recent mutations of selected components, for debugging where changes come from.
+
+use alloc::{borrow::Cow, collections::VecDeque, vec::Vec};
+use core::cmp::Reverse;
+
+use bevy_platform_support::{
+    collections::HashMap,
+    sync::{Mutex, PoisonError},
+};
+use bevy_ptr::UnsafeCellDeref;
+
+use crate::{
+    archetype::{Archetype, ArchetypeComponentId, ArchetypeEntity, Archetypes},
+    change_detection::{DetectChangesMut, MaybeLocation, Mut},
+    component::{Component, ComponentId, ComponentTicks, Components, StorageType, Tick},
+    entity::Entity,
+    query::Access,
+    resource::Resource,
+    storage::Storages,
+    system::ScheduleSystem,
+    world::World,
+};
+
+#[cfg(feature = "std")]
+use crate::world::unsafe_world_cell::UnsafeWorldCell;
+
+/// A [`Resource`] recording the last mutations of the components tracked with
+/// [`World::track_component_changes`].
+///
+/// While a [`Schedule`](crate::schedule::Schedule) runs, the changes made by each system are
+/// recorded right after it runs, and the changes made by its [`Commands`](crate::system::Commands)
+/// right after they are applied. Changes made outside of schedules are recorded when the next
+/// schedule starts, or when calling [`World::record_component_changes`].
+///
+/// Changes are found by comparing change ticks, so only the last mutation of a component by a
+/// single system run is recorded, and mutations that don't trigger change detection, such as those
+/// through [`bypass_change_detection`](DetectChangesMut::bypass_change_detection), are not recorded
+/// at all. Commands and changes made outside of systems use the change tick of the next system to
+/// run, so when they changed a tracked component the change tick is
+/// [incremented](World::increment_change_tick) after recording them, to tell them apart from the
+/// changes of that system.
+///
+/// Histories of despawned entities and removed components are discarded when changes are next
+/// recorded. Recording changes scans all entities with a tracked component after each system, so
+/// tracking is best limited to the components being debugged.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// #[derive(Component)]
+/// struct Health(u32);
+///
+/// fn take_damage(mut query: Query<&mut Health>) {
+///     for mut health in &mut query {
+///         health.0 -= 1;
+///     }
+/// }
+///
+/// fn regenerate(mut query: Query<&mut Health>) {
+///     for mut health in &mut query {
+///         health.0 += 2;
+///     }
+/// }
+///
+/// let mut world = World::new();
+/// world.track_component_changes::<Health>();
+/// let entity = world.spawn(Health(10)).id();
+///
+/// let mut schedule = Schedule::default();
+/// schedule.add_systems((take_damage, regenerate).chain());
+/// schedule.run(&mut world);
+///
+/// let changes: Vec<_> = world.component_changes::<Health>(entity).collect();
+/// assert_eq!(changes.len(), 3);
+/// assert!(changes[0].added);
+/// assert!(changes[1].system.as_deref().unwrap().ends_with("take_damage"));
+/// assert!(changes[2].system.as_deref().unwrap().ends_with("regenerate"));
+/// ```
+#[derive(Resource)]
+pub struct ComponentChangeHistory {
+    capacity: usize,
+    components: Vec<ComponentId>,
+    last_scan: Tick,
+    changes: HashMap<(Entity, ComponentId), VecDeque<ComponentChange>>,
+}
+
+/// A mutation of a component recorded in a [`ComponentChangeHistory`].
+#[derive(Clone, Debug)]
+pub struct ComponentChange {
+    /// The tick at which the component was changed.
+    pub tick: Tick,
+    /// Whether the component was inserted rather than mutated in place.
+    pub added: bool,
+    /// The name of the system that made the change, either directly or through its deferred
+    /// [`Commands`](crate::system::Commands).
+    ///
+    /// This is `None` for changes made outside of systems.
+    pub system: Option<Cow<'static, str>>,
+    /// The source location of the change, if the `track_location` feature is enabled.
+    pub caller: MaybeLocation,
+}
+
+impl Default for ComponentChangeHistory {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+impl ComponentChangeHistory {
+    /// The number of changes kept per entity and component by [`ComponentChangeHistory::default`].
+    pub const DEFAULT_CAPACITY: usize = 8;
+
+    /// Creates an empty history keeping the last `capacity` changes of each entity's components.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            components: Vec::new(),
+            last_scan: Tick::new(0),
+            changes: HashMap::default(),
+        }
+    }
+
+    /// Returns the number of changes kept per entity and component.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the tracked components.
+    pub fn components(&self) -> &[ComponentId] {
+        &self.components
+    }
+
+    /// Returns `true` if changes of the component are recorded.
+    pub fn is_tracked(&self, component_id: ComponentId) -> bool {
+        self.components.contains(&component_id)
+    }
+
+    /// Returns the recorded changes of the component of `entity`, from oldest to newest.
+    pub fn changes(
+        &self,
+        entity: Entity,
+        component_id: ComponentId,
+    ) -> impl DoubleEndedIterator<Item = &ComponentChange> {
+        self.changes
+            .get(&(entity, component_id))
+            .into_iter()
+            .flatten()
+    }
+
+    /// Adds a change to the history of the component of `entity`, unless a change at the same
+    /// tick has already been recorded.
+    fn push(&mut self, entity: Entity, component_id: ComponentId, change: ComponentChange) {
+        if self.capacity == 0 {
+            return;
+        }
+        let changes = self.changes.entry((entity, component_id)).or_default();
+        if changes.back().is_some_and(|last| last.tick == change.tick) {
+            return;
+        }
+        if changes.len() == self.capacity {
+            changes.pop_front();
+        }
+        changes.push_back(change);
+    }
+
+    /// Records the changes made to the tracked components since the last scan, without
+    /// attributing them to any system.
+    ///
+    /// Returns `true` if a component was changed at the current change tick. Otherwise, changes
+    /// made later at that tick are found by the next scan.
+    fn scan(&mut self, world: &World) -> bool {
+        let this_run = world.read_change_tick();
+        let last_scan = self.last_scan;
+        let mut changes = Vec::new();
+        // SAFETY: The world is borrowed immutably, so nothing can be writing to the components.
+        unsafe {
+            for_each_change(
+                &self.components,
+                world.components(),
+                world.archetypes(),
+                world.storages(),
+                None,
+                |entity, component_id, ticks, caller| {
+                    if ticks.changed.is_newer_than(last_scan, this_run) {
+                        changes.push((entity, component_id, ticks, caller));
+                    }
+                },
+            );
+        }
+        let changed_this_tick = changes
+            .iter()
+            .any(|(_, _, ticks, _)| ticks.changed == this_run);
+        for (entity, component_id, ticks, caller) in changes {
+            self.push(entity, component_id, change(ticks, None, caller));
+        }
+        self.discard_removed(world);
+        self.last_scan = if changed_this_tick {
+            this_run
+        } else {
+            Tick::new(this_run.get().wrapping_sub(1))
+        };
+        changed_this_tick
+    }
+
+    /// Discards the histories of despawned entities and removed components.
+    fn discard_removed(&mut self, world: &World) {
+        self.changes.retain(|(entity, component_id), _| {
+            world
+                .get_entity(*entity)
+                .is_ok_and(|entity| entity.contains_id(*component_id))
+        });
+    }
+}
+
+fn change(
+    ticks: ComponentTicks,
+    system: Option<&ScheduleSystem>,
+    caller: MaybeLocation,
+) -> ComponentChange {
+    ComponentChange {
+        tick: ticks.changed,
+        added: ticks.added == ticks.changed,
+        system: system.map(|system| system.name()),
+        caller,
+    }
+}
+
+/// Collects the changes that the systems of a running schedule make to the components tracked by
+/// the [`ComponentChangeHistory`], and adds them to the history once the schedule has run.
+pub(crate) struct SystemChangeRecorder {
+    components: Vec<ComponentId>,
+    changes: Mutex<Vec<(Entity, ComponentId, ComponentChange)>>,
+}
+
+impl SystemChangeRecorder {
+    /// Returns a recorder for the tracked components, or `None` if no changes are tracked.
+    pub(crate) fn new(world: &World) -> Option<Self> {
+        let history = world.get_resource::<ComponentChangeHistory>()?;
+        (history.capacity > 0 && !history.components.is_empty()).then(|| Self {
+            components: history.components.clone(),
+            changes: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Records the changes made by `system` from the tick `first` to the current change tick.
+    ///
+    /// This is used after running exclusive systems and applying deferred commands, and after
+    /// running systems that don't run in parallel with others. If a component was changed at the
+    /// current change tick, which the next system will use, the change tick is incremented.
+    pub(crate) fn record(&self, world: &mut World, system: &ScheduleSystem, first: Tick) {
+        let last = world.change_tick();
+        // SAFETY: The world is borrowed mutably, so nothing else can be writing to the components.
+        let changed_last_tick = unsafe {
+            self.record_ticks(
+                world.components(),
+                world.archetypes(),
+                world.storages(),
+                None,
+                system,
+                first,
+                last,
+            )
+        };
+        if changed_last_tick {
+            world.increment_change_tick();
+        }
+    }
+
+    /// Records the changes made by `system` in its last run, in the archetypes it can write to.
+    ///
+    /// # Safety
+    /// - `system` must have finished running, and no other system may be running with access to
+    ///   the components it can write to.
+    /// - The archetypes and storages of `world` must not be modified concurrently.
+    #[cfg(feature = "std")]
+    pub(crate) unsafe fn record_parallel(&self, world: UnsafeWorldCell, system: &ScheduleSystem) {
+        let tick = system.get_last_run();
+        // SAFETY: The caller ensures that no one can be writing to the components that `system`
+        // can write to, which are the only ones read.
+        unsafe {
+            self.record_ticks(
+                world.components(),
+                world.archetypes(),
+                world.storages(),
+                Some(system.archetype_component_access()),
+                system,
+                tick,
+                tick,
+            );
+        }
+    }
+
+    /// Adds the recorded changes to the [`ComponentChangeHistory`], from oldest to newest.
+    pub(crate) fn finish(self, world: &mut World) {
+        let mut changes = self
+            .changes
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner);
+        let this_run = world.change_tick();
+        changes
+            .sort_by_key(|(_, _, change)| Reverse(this_run.get().wrapping_sub(change.tick.get())));
+        world.try_resource_scope(|world, mut history: Mut<ComponentChangeHistory>| {
+            let history = history.bypass_change_detection();
+            for (entity, component_id, change) in changes {
+                history.push(entity, component_id, change);
+            }
+            history.discard_removed(world);
+        });
+    }
+
+    /// Records the changes made from the tick `first` to the tick `last`, returning `true` if a
+    /// component was changed at `last`.
+    ///
+    /// # Safety
+    /// Nothing may be writing to the tracked components of the archetypes allowed by `access`, or
+    /// of all archetypes if `access` is `None`.
+    unsafe fn record_ticks(
+        &self,
+        components: &Components,
+        archetypes: &Archetypes,
+        storages: &Storages,
+        access: Option<&Access<ArchetypeComponentId>>,
+        system: &ScheduleSystem,
+        first: Tick,
+        last: Tick,
+    ) -> bool {
+        let range = last.get().wrapping_sub(first.get());
+        let mut changes = Vec::new();
+        // SAFETY: Ensured by the caller.
+        unsafe {
+            for_each_change(
+                &self.components,
+                components,
+                archetypes,
+                storages,
+                access,
+                |entity, component_id, ticks, caller| {
+                    if ticks.changed.get().wrapping_sub(first.get()) <= range {
+                        changes.push((entity, component_id, change(ticks, Some(system), caller)));
+                    }
+                },
+            );
+        }
+        let changed_last_tick = changes.iter().any(|(_, _, change)| change.tick == last);
+        if !changes.is_empty() {
+            self.changes
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .append(&mut changes);
+        }
+        changed_last_tick
+    }
+}
+
+/// Calls `f` with the change ticks and last change location of each of `tracked` components,
+/// in the archetypes whose components can be written with `access`, or in all archetypes if
+/// `access` is `None`.
+///
+/// # Safety
+/// Nothing may be writing to the visited components.
+unsafe fn for_each_change(
+    tracked: &[ComponentId],
+    components: &Components,
+    archetypes: &Archetypes,
+    storages: &Storages,
+    access: Option<&Access<ArchetypeComponentId>>,
+    mut f: impl FnMut(Entity, ComponentId, ComponentTicks, MaybeLocation),
+) {
+    for &component_id in tracked {
+        let Some(info) = components.get_info(component_id) else {
+            continue;
+        };
+        let Some(component_archetypes) = archetypes.component_index().get(&component_id) else {
+            continue;
+        };
+        for archetype_id in component_archetypes.keys() {
+            let archetype = &archetypes[*archetype_id];
+            if let Some(access) = access {
+                let writable = archetype
+                    .get_archetype_component_id(component_id)
+                    .is_some_and(|id| access.has_component_write(id));
+                if !writable {
+                    continue;
+                }
+            }
+            for archetype_entity in archetype.entities() {
+                // SAFETY: Ensured by the caller.
+                let change = unsafe {
+                    read_change(
+                        storages,
+                        archetype,
+                        archetype_entity,
+                        component_id,
+                        info.storage_type(),
+                    )
+                };
+                if let Some((ticks, caller)) = change {
+                    f(archetype_entity.id(), component_id, ticks, caller);
+                }
+            }
+        }
+    }
+}
+
+/// Reads the change ticks of a component of an entity, and the location of its last change.
+///
+/// # Safety
+/// Nothing may be writing to the component.
+unsafe fn read_change(
+    storages: &Storages,
+    archetype: &Archetype,
+    archetype_entity: &ArchetypeEntity,
+    component_id: ComponentId,
+    storage_type: StorageType,
+) -> Option<(ComponentTicks, MaybeLocation)> {
+    let (added, changed, changed_by) = match storage_type {
+        StorageType::Table => {
+            let table = storages.tables.get(archetype.table_id())?;
+            let row = archetype_entity.table_row();
+            (
+                table.get_added_tick(component_id, row)?,
+                table.get_changed_tick(component_id, row)?,
+                table.get_changed_by(component_id, row),
+            )
+        }
+        StorageType::SparseSet => {
+            let sparse_set = storages.sparse_sets.get(component_id)?;
+            let entity = archetype_entity.id();
+            (
+                sparse_set.get_added_tick(entity)?,
+                sparse_set.get_changed_tick(entity)?,
+                sparse_set.get_changed_by(entity),
+            )
+        }
+    };
+    let changed_by = changed_by.transpose()?;
+    // SAFETY: The caller ensures that nothing is writing to the component.
+    unsafe {
+        Some((
+            ComponentTicks {
+                added: added.read(),
+                changed: changed.read(),
+            },
+            changed_by.map(|changed_by| changed_by.read()),
+        ))
+    }
+}
+
+impl World {
+    /// Starts recording the last mutations of the component `C` in the [`ComponentChangeHistory`]
+    /// resource, which is inserted with the [default](ComponentChangeHistory::DEFAULT_CAPACITY)
+    /// capacity if it doesn't exist.
+    ///
+    /// See [`ComponentChangeHistory`] for more details.
+    pub fn track_component_changes<C: Component>(&mut self) {
+        let component_id = self.register_component::<C>();
+        self.track_component_changes_by_id(component_id);
+    }
+
+    /// Starts recording the last mutations of the component with the given [`ComponentId`].
+    ///
+    /// See [`World::track_component_changes`] for more details.
+    pub fn track_component_changes_by_id(&mut self, component_id: ComponentId) {
+        let mut history = self.get_resource_or_init::<ComponentChangeHistory>();
+        if !history.is_tracked(component_id) {
+            history.components.push(component_id);
+        }
+    }
+
+    /// Returns the recorded changes of the component `C` of `entity`, from oldest to newest.
+    ///
+    /// This is empty if changes of `C` are not [tracked](World::track_component_changes).
+    pub fn component_changes<C: Component>(
+        &self,
+        entity: Entity,
+    ) -> impl DoubleEndedIterator<Item = &ComponentChange> {
+        self.component_id::<C>()
+            .into_iter()
+            .flat_map(move |component_id| self.component_changes_by_id(entity, component_id))
+    }
+
+    /// Returns the recorded changes of the component with the given [`ComponentId`] of `entity`,
+    /// from oldest to newest.
+    ///
+    /// See [`World::component_changes`] for more details.
+    pub fn component_changes_by_id(
+        &self,
+        entity: Entity,
+        component_id: ComponentId,
+    ) -> impl DoubleEndedIterator<Item = &ComponentChange> {
+        self.get_resource::<ComponentChangeHistory>()
+            .into_iter()
+            .flat_map(move |history| history.changes(entity, component_id))
+    }
+
+    /// Records the changes made to the tracked components outside of systems since changes were
+    /// last recorded.
+    ///
+    /// If a tracked component was changed at the current change tick, the change tick is
+    /// [incremented](World::increment_change_tick), so that later changes can be told apart.
+    ///
+    /// This is done automatically when a [`Schedule`](crate::schedule::Schedule) starts, and only
+    /// needs to be called to observe changes made outside of schedules right away. The changes
+    /// recorded by this method are not attributed to any system.
+    ///
+    /// Does nothing if no component changes are [tracked](World::track_component_changes).
+    pub fn record_component_changes(&mut self) {
+        let changed_this_tick = self
+            .try_resource_scope(|world, mut history: Mut<ComponentChangeHistory>| {
+                history.bypass_change_detection().scan(world)
+            })
+            .unwrap_or(false);
+        if changed_this_tick {
+            self.increment_change_tick();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        prelude::{Commands, Query},
+        schedule::{ExecutorKind, IntoSystemConfigs, Schedule},
+    };
+
+    #[derive(Component)]
+    struct Health(u32);
+
+    #[derive(Component)]
+    #[component(storage = "SparseSet")]
+    struct Shield;
+
+    fn damage(mut query: Query<&mut Health>) {
+        for mut health in &mut query {
+            health.0 -= 1;
+        }
+    }
+
+    fn heal(mut query: Query<&mut Health>) {
+        for mut health in &mut query {
+            health.0 += 2;
+        }
+    }
+
+    fn system_names(world: &World, entity: Entity) -> Vec<Option<&str>> {
+        world
+            .component_changes::<Health>(entity)
+            .map(|change| {
+                change
+                    .system
+                    .as_deref()
+                    .map(|name| name.rsplit("::").next().unwrap())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn records_changes_from_systems() {
+        let mut world = World::new();
+        world.track_component_changes::<Health>();
+        let entity = world.spawn(Health(10)).id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(damage);
+        schedule.run(&mut world);
+        let mut schedule = Schedule::default();
+        schedule.add_systems(heal);
+        schedule.run(&mut world);
+        schedule.run(&mut world);
+
+        assert_eq!(
+            system_names(&world, entity),
+            [None, Some("damage"), Some("heal"), Some("heal")]
+        );
+        let changes: Vec<_> = world.component_changes::<Health>(entity).collect();
+        assert!(changes[0].added);
+        assert!(!changes[1].added);
+        assert!(changes[2]
+            .tick
+            .is_newer_than(changes[1].tick, world.change_tick()));
+        assert_eq!(world.get::<Health>(entity).unwrap().0, 13);
+    }
+
+    #[test]
+    fn records_every_system_of_a_schedule_run() {
+        for executor in [
+            ExecutorKind::SingleThreaded,
+            ExecutorKind::Simple,
+            #[cfg(feature = "std")]
+            ExecutorKind::MultiThreaded,
+        ] {
+            let mut world = World::new();
+            world.insert_resource(ComponentChangeHistory::new(3));
+            world.track_component_changes::<Health>();
+            let entity = world.spawn(Health(10)).id();
+
+            let mut schedule = Schedule::default();
+            schedule.set_executor_kind(executor);
+            schedule.add_systems((damage, heal, damage).chain());
+            schedule.run(&mut world);
+            assert_eq!(
+                system_names(&world, entity),
+                [Some("damage"), Some("heal"), Some("damage")],
+                "{executor:?}"
+            );
+
+            // Older changes are dropped once the capacity is reached.
+            schedule.run(&mut world);
+            assert_eq!(
+                system_names(&world, entity),
+                [Some("damage"), Some("heal"), Some("damage")],
+                "{executor:?}"
+            );
+            let changes: Vec<_> = world.component_changes::<Health>(entity).collect();
+            assert!(changes[1]
+                .tick
+                .is_newer_than(changes[0].tick, world.change_tick()));
+        }
+    }
+
+    #[test]
+    fn records_exclusive_systems() {
+        let mut world = World::new();
+        world.track_component_changes::<Health>();
+        let entity = world.spawn(Health(10)).id();
+        world.record_component_changes();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(
+            (damage, move |world: &mut World| {
+                world.get_mut::<Health>(entity).unwrap().0 = 0;
+            })
+                .chain(),
+        );
+        schedule.run(&mut world);
+
+        let changes: Vec<_> = world.component_changes::<Health>(entity).collect();
+        assert_eq!(changes.len(), 3);
+        assert!(changes[2]
+            .system
+            .as_deref()
+            .unwrap()
+            .contains("records_exclusive_systems"));
+    }
+
+    #[test]
+    fn records_deferred_changes_for_the_system_that_queued_them() {
+        let mut world = World::new();
+        world.track_component_changes::<Shield>();
+        let entity = world.spawn_empty().id();
+
+        fn insert_shield(entity: Entity) -> impl FnMut(Commands) {
+            move |mut commands: Commands| {
+                commands.entity(entity).insert(Shield);
+            }
+        }
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems((insert_shield(entity), || {}).chain());
+        schedule.run(&mut world);
+
+        let changes: Vec<_> = world.component_changes::<Shield>(entity).collect();
+        assert_eq!(changes.len(), 1);
+        assert!(changes[0].added);
+        assert!(changes[0]
+            .system
+            .as_deref()
+            .unwrap()
+            .contains("insert_shield"));
+
+        world.entity_mut(entity).remove::<Shield>();
+        world.record_component_changes();
+        assert_eq!(world.component_changes::<Shield>(entity).count(), 0);
+    }
+
+    #[test]
+    fn records_changes_after_manual_recording() {
+        let mut world = World::new();
+        world.track_component_changes::<Health>();
+        let entity = world.spawn(Health(10)).id();
+        world.record_component_changes();
+        assert_eq!(world.component_changes::<Health>(entity).count(), 1);
+
+        world.get_mut::<Health>(entity).unwrap().0 = 5;
+        world.record_component_changes();
+        world.record_component_changes();
+        assert_eq!(system_names(&world, entity), [None, None]);
+    }
+
+    #[test]
+    fn untracked_components_are_not_recorded() {
+        let mut world = World::new();
+        world.track_component_changes::<Shield>();
+        let entity = world.spawn(Health(10)).id();
+
+        let mut schedule = Schedule::default();
+        schedule.add_systems(damage);
+        schedule.run(&mut world);
+
+        assert_eq!(world.component_changes::<Health>(entity).count(), 0);
+    }
+}
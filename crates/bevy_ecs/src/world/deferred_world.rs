@@ -1,3 +1,4 @@
+use alloc::vec;
 use core::ops::Deref;
 
 use crate::{
@@ -6,7 +7,7 @@ use crate::{
     component::{ComponentId, HookContext, Mutable},
     entity::Entity,
     event::{Event, EventId, Events, SendBatchIds},
-    observer::{Observers, TriggerTargets},
+    observer::{CachedObservers, Observers, PropagationPhase, TriggerTargets},
     prelude::{Component, QueryState},
     query::{QueryData, QueryFilter},
     resource::Resource,
@@ -672,6 +673,7 @@ impl<'w> DeferredWorld<'w> {
             components,
             &mut (),
             &mut false,
+            PropagationPhase::Target,
             caller,
         );
     }
@@ -692,6 +694,35 @@ impl<'w> DeferredWorld<'w> {
     ) where
         T: Traversal<E>,
     {
+        if propagate
+            && self
+                .world
+                .observers()
+                .try_get_observers(event)
+                .is_some_and(CachedObservers::has_capture_observers)
+        {
+            let mut path = vec![target];
+            while let Some(traverse_to) = self.traverse::<E, T>(*path.last().unwrap(), data) {
+                path.push(traverse_to);
+            }
+            for &entity in path.iter().rev() {
+                Observers::invoke::<_>(
+                    self.reborrow(),
+                    event,
+                    entity,
+                    components.iter().copied(),
+                    data,
+                    &mut propagate,
+                    PropagationPhase::Capture,
+                    caller,
+                );
+                if !propagate {
+                    return;
+                }
+            }
+        }
+
+        let mut phase = PropagationPhase::Target;
         loop {
             Observers::invoke::<_>(
                 self.reborrow(),
@@ -700,24 +731,29 @@ impl<'w> DeferredWorld<'w> {
                 components.iter().copied(),
                 data,
                 &mut propagate,
+                phase,
                 caller,
             );
             if !propagate {
                 break;
             }
-            if let Some(traverse_to) = self
-                .get_entity(target)
-                .ok()
-                .and_then(|entity| entity.get_components::<T>())
-                .and_then(|item| T::traverse(item, data))
-            {
+            if let Some(traverse_to) = self.traverse::<E, T>(target, data) {
                 target = traverse_to;
+                phase = PropagationPhase::Bubble;
             } else {
                 break;
             }
         }
     }
 
+    /// Returns the entity an event propagates to from `entity`, according to the traversal `T`.
+    fn traverse<E, T: Traversal<E>>(&self, entity: Entity, data: &E) -> Option<Entity> {
+        self.get_entity(entity)
+            .ok()
+            .and_then(|entity| entity.get_components::<T>())
+            .and_then(|item| T::traverse(item, data))
+    }
+
     /// Sends a "global" [`Trigger`](crate::observer::Trigger) without any targets.
     pub fn trigger(&mut self, trigger: impl Event) {
         self.commands().trigger(trigger);
@@ -1807,6 +1807,7 @@ impl<'w> EntityWorldMut<'w> {
         storages: &mut Storages,
         new_archetype_id: ArchetypeId,
     ) {
+        archetypes.entity_moves += 1;
         let old_archetype = &mut archetypes[old_archetype_id];
         let remove_result = old_archetype.swap_remove(old_location.archetype_row);
         // if an entity was moved into this entity's archetype row, update its archetype row
@@ -2484,6 +2485,36 @@ impl<'w> EntityWorldMut<'w> {
         self
     }
 
+    /// Creates an [`Observer`] listening for events of type `E` during their
+    /// [capture phase](crate::observer::PropagationPhase::Capture) on this entity.
+    /// See [`Observer::capture`] for more information.
+    ///
+    /// # Panics
+    ///
+    /// If the entity has been despawned while this `EntityWorldMut` is still alive.
+    #[track_caller]
+    pub fn observe_capture<E: Event, B: Bundle, M>(
+        &mut self,
+        observer: impl IntoObserverSystem<E, B, M>,
+    ) -> &mut Self {
+        self.observe_capture_with_caller(observer, MaybeLocation::caller())
+    }
+
+    pub(crate) fn observe_capture_with_caller<E: Event, B: Bundle, M>(
+        &mut self,
+        observer: impl IntoObserverSystem<E, B, M>,
+        caller: MaybeLocation,
+    ) -> &mut Self {
+        self.assert_not_despawned();
+        self.world.spawn_with_caller(
+            Observer::new(observer).with_entity(self.entity).capture(),
+            caller,
+        );
+        self.world.flush();
+        self.update_location();
+        self
+    }
+
     /// Clones parts of an entity (components, observers, etc.) onto another entity,
     /// configured through [`EntityClonerBuilder`].
     ///
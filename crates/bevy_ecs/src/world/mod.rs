@@ -1,5 +1,6 @@
 //! Defines the [`World`] and APIs for accessing it directly.
 
+mod change_history;
 pub(crate) mod command_queue;
 mod component_constants;
 mod deferred_world;
@@ -8,6 +9,7 @@ mod entity_ref;
 pub mod error;
 mod filtered_resource;
 mod identifier;
+mod snapshot;
 mod spawn_batch;
 pub mod unsafe_world_cell;
 
@@ -19,6 +21,7 @@ pub use crate::{
     world::command_queue::CommandQueue,
 };
 pub use bevy_ecs_macros::FromWorld;
+pub use change_history::*;
 pub use component_constants::*;
 pub use deferred_world::DeferredWorld;
 pub use entity_fetch::WorldEntityFetch;
@@ -28,6 +31,7 @@ pub use entity_ref::{
 };
 pub use filtered_resource::*;
 pub use identifier::WorldId;
+pub use snapshot::*;
 pub use spawn_batch::*;
 
 use crate::{
@@ -0,0 +1,798 @@
+//! Capturing and restoring the state of a [`World`], for example for rollback networking.
+
+use alloc::{boxed::Box, vec::Vec};
+use bevy_platform_support::sync::Arc;
+use core::{any::Any, marker::PhantomData};
+
+use fixedbitset::FixedBitSet;
+
+use crate::{
+    archetype::ArchetypeEntity,
+    component::{Component, ComponentId, ComponentTicks, Tick},
+    entity::{hash_map::EntityHashMap, hash_set::EntityHashSet, Entity, EntityMapper, MapEntities},
+    resource::Resource,
+    world::{EntityRef, EntityWorldMut, World},
+};
+
+#[cfg(feature = "bevy_reflect")]
+use {
+    crate::reflect::{ReflectComponent, ReflectMapEntities, ReflectResource},
+    bevy_reflect::{PartialReflect, TypeRegistryArc},
+    core::any::TypeId,
+};
+
+/// Selects which components and resources a [`Snapshot`] captures, and how they are copied.
+///
+/// Components and resources are either copied with [`Clone`], or with reflection when the
+/// `bevy_reflect` feature is enabled.
+///
+/// # Example
+///
+/// ```
+/// # use bevy_ecs::prelude::*;
+/// # use bevy_ecs::entity::hash_map::EntityHashMap;
+/// # use bevy_ecs::world::SnapshotConfig;
+/// #
+/// #[derive(Component, Clone)]
+/// struct Position(f32);
+///
+/// #[derive(Resource, Clone)]
+/// struct Frame(u32);
+///
+/// let config = SnapshotConfig::new()
+///     .with_component::<Position>()
+///     .with_resource::<Frame>();
+///
+/// let mut world = World::new();
+/// world.insert_resource(Frame(0));
+/// let entity = world.spawn(Position(0.0)).id();
+///
+/// let snapshot = world.snapshot(&config);
+/// world.entity_mut(entity).insert(Position(1.0));
+/// world.insert_resource(Frame(1));
+///
+/// world.restore_snapshot(&snapshot, &mut EntityHashMap::default());
+/// assert_eq!(world.get::<Position>(entity).unwrap().0, 0.0);
+/// assert_eq!(world.resource::<Frame>().0, 0);
+/// ```
+#[derive(Default, Clone)]
+pub struct SnapshotConfig {
+    components: Vec<Arc<dyn ComponentSnapshotter>>,
+    resources: Vec<Arc<dyn ResourceSnapshotter>>,
+}
+
+impl SnapshotConfig {
+    /// Creates a configuration that captures nothing.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Captures the component `C` by cloning it.
+    ///
+    /// When restored, the entities referenced by the component are mapped through
+    /// [`Component::visit_entities_mut`].
+    pub fn with_component<C: Component + Clone>(mut self) -> Self {
+        self.components
+            .push(into_arc(Box::new(CloneComponentSnapshotter::<C>(
+                PhantomData,
+            ))));
+        self
+    }
+
+    /// Captures the resource `R` by cloning it.
+    pub fn with_resource<R: Resource + Clone>(mut self) -> Self {
+        self.resources
+            .push(into_arc(Box::new(CloneResourceSnapshotter::<R> {
+                map_entities: |_, _| {},
+            })));
+        self
+    }
+
+    /// Captures the resource `R` by cloning it, mapping the entities it references through
+    /// [`MapEntities`] when restored.
+    pub fn with_mapped_resource<R: Resource + Clone + MapEntities>(mut self) -> Self {
+        self.resources
+            .push(into_arc(Box::new(CloneResourceSnapshotter::<R> {
+                map_entities: |resource, entity_map| resource.map_entities(entity_map),
+            })));
+        self
+    }
+
+    /// Captures the component with the given `type_id` using reflection.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type isn't registered in `type_registry` with [`ReflectComponent`].
+    #[cfg(feature = "bevy_reflect")]
+    pub fn with_reflect_component(
+        mut self,
+        type_registry: &TypeRegistryArc,
+        type_id: TypeId,
+    ) -> Self {
+        let reflect_component = type_registry
+            .read()
+            .get_type_data::<ReflectComponent>(type_id)
+            .unwrap_or_else(|| {
+                panic!("{type_id:?} must be registered with `ReflectComponent` to be captured")
+            })
+            .clone();
+        self.components
+            .push(into_arc(Box::new(ReflectComponentSnapshotter {
+                type_id,
+                reflect_component,
+                type_registry: type_registry.clone(),
+            })));
+        self
+    }
+
+    /// Captures the resource with the given `type_id` using reflection, mapping the entities it
+    /// references when restored if it is registered with [`ReflectMapEntities`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the type isn't registered in `type_registry` with [`ReflectResource`].
+    #[cfg(feature = "bevy_reflect")]
+    pub fn with_reflect_resource(
+        mut self,
+        type_registry: &TypeRegistryArc,
+        type_id: TypeId,
+    ) -> Self {
+        let registry = type_registry.read();
+        let reflect_resource = registry
+            .get_type_data::<ReflectResource>(type_id)
+            .unwrap_or_else(|| {
+                panic!("{type_id:?} must be registered with `ReflectResource` to be captured")
+            })
+            .clone();
+        let reflect_map_entities = registry
+            .get_type_data::<ReflectMapEntities>(type_id)
+            .cloned();
+        drop(registry);
+        self.resources
+            .push(into_arc(Box::new(ReflectResourceSnapshotter {
+                type_id,
+                reflect_resource,
+                reflect_map_entities,
+                type_registry: type_registry.clone(),
+            })));
+        self
+    }
+}
+
+/// The captured state of the components and resources selected by a [`SnapshotConfig`].
+///
+/// Created with [`World::snapshot`], or with [`World::snapshot_changes`] to only store the values
+/// that changed since a previous snapshot, and restored with [`World::restore_snapshot`].
+///
+/// The captured entities are the ones that had at least one of the selected components.
+pub struct Snapshot {
+    change_tick: Tick,
+    is_diff: bool,
+    entities: Vec<Entity>,
+    /// Every entity that existed when the snapshot was captured, indexed by [`Entity::index`].
+    alive: Vec<Option<Entity>>,
+    columns: Vec<SnapshotColumn>,
+    resources: Vec<SnapshotResource>,
+}
+
+/// The values of a single component type in a [`Snapshot`].
+struct SnapshotColumn {
+    snapshotter: Arc<dyn ComponentSnapshotter>,
+    /// The indices of the entities that had the component, into [`Snapshot::entities`].
+    present: FixedBitSet,
+    /// The indices of the entities whose values were captured, in the same order as `values`.
+    rows: Vec<u32>,
+    values: Box<dyn Any + Send + Sync>,
+}
+
+struct SnapshotResource {
+    snapshotter: Arc<dyn ResourceSnapshotter>,
+    state: ResourceState,
+}
+
+enum ResourceState {
+    Absent,
+    Unchanged,
+    Captured(Box<dyn Any + Send + Sync>),
+}
+
+impl Snapshot {
+    /// Returns the change tick of the [`World`] when this snapshot was captured.
+    ///
+    /// The values changed after this tick are the ones captured by [`World::snapshot_changes`].
+    pub fn change_tick(&self) -> Tick {
+        self.change_tick
+    }
+
+    /// Returns `true` if this snapshot only stores the values that changed since a previous snapshot.
+    pub fn is_diff(&self) -> bool {
+        self.is_diff
+    }
+
+    /// Returns the captured entities.
+    pub fn entities(&self) -> &[Entity] {
+        &self.entities
+    }
+
+    /// Returns `true` if `entity` existed when this snapshot was captured, whether or not it was
+    /// captured.
+    pub fn contains_entity(&self, entity: Entity) -> bool {
+        self.alive.get(entity.index() as usize) == Some(&Some(entity))
+    }
+
+    /// Returns the number of component values stored in this snapshot.
+    pub fn component_count(&self) -> usize {
+        self.columns.iter().map(|column| column.rows.len()).sum()
+    }
+
+    /// Returns the number of resource values stored in this snapshot.
+    pub fn resource_count(&self) -> usize {
+        self.resources
+            .iter()
+            .filter(|resource| matches!(resource.state, ResourceState::Captured(_)))
+            .count()
+    }
+}
+
+impl World {
+    /// Captures the components and resources selected by `config`.
+    ///
+    /// This increments the change tick of the world, so that changes made after the snapshot can be
+    /// captured by [`World::snapshot_changes`].
+    pub fn snapshot(&mut self, config: &SnapshotConfig) -> Snapshot {
+        self.capture_snapshot(config, None)
+    }
+
+    /// Captures the components and resources selected by `config` that were added or changed since
+    /// `since` was captured.
+    ///
+    /// The resulting snapshot still records which entities exist and which components and resources
+    /// they have, so restoring it removes anything that was added in between, but restoring it is
+    /// only correct if the world is in the state captured by `since`, for example right after
+    /// restoring it.
+    pub fn snapshot_changes(&mut self, config: &SnapshotConfig, since: &Snapshot) -> Snapshot {
+        self.capture_snapshot(config, Some(since.change_tick))
+    }
+
+    fn capture_snapshot(&mut self, config: &SnapshotConfig, since: Option<Tick>) -> Snapshot {
+        let change_tick = self.increment_change_tick();
+        let is_changed = |ticks: Option<ComponentTicks>| {
+            since
+                .is_none_or(|since| ticks.is_some_and(|ticks| ticks.is_changed(since, change_tick)))
+        };
+
+        let component_ids: Vec<_> = config
+            .components
+            .iter()
+            .map(|snapshotter| snapshotter.component_id(self))
+            .collect();
+
+        let mut entities = Vec::new();
+        let mut alive = Vec::new();
+        let mut columns: Vec<_> = config
+            .components
+            .iter()
+            .map(|snapshotter| SnapshotColumn {
+                snapshotter: snapshotter.clone(),
+                present: FixedBitSet::new(),
+                rows: Vec::new(),
+                values: snapshotter.new_column(),
+            })
+            .collect();
+        for archetype in self.archetypes.iter() {
+            for archetype_entity in archetype.entities() {
+                let entity = archetype_entity.id();
+                let index = entity.index() as usize;
+                if alive.len() <= index {
+                    alive.resize(index + 1, None);
+                }
+                alive[index] = Some(entity);
+            }
+
+            let archetype_columns: Vec<_> = component_ids
+                .iter()
+                .enumerate()
+                .filter_map(|(index, id)| Some((index, (*id)?)))
+                .filter(|(_, id)| archetype.contains(*id))
+                .collect();
+            if archetype_columns.is_empty() {
+                continue;
+            }
+            for archetype_entity in archetype.entities() {
+                let row = entities.len();
+                let entity = self.entity(archetype_entity.id());
+                entities.push(entity.id());
+                for &(index, component_id) in &archetype_columns {
+                    let column = &mut columns[index];
+                    column.present.grow_and_insert(row);
+                    if is_changed(entity.get_change_ticks_by_id(component_id))
+                        && column.snapshotter.capture(&mut *column.values, entity)
+                    {
+                        column.rows.push(row as u32);
+                    }
+                }
+            }
+        }
+
+        let resources = config
+            .resources
+            .iter()
+            .map(|snapshotter| {
+                let state = match snapshotter.component_id(self) {
+                    Some(id) if self.contains_resource_by_id(id) => {
+                        if is_changed(self.get_resource_change_ticks_by_id(id)) {
+                            snapshotter
+                                .capture(self)
+                                .map_or(ResourceState::Absent, ResourceState::Captured)
+                        } else {
+                            ResourceState::Unchanged
+                        }
+                    }
+                    _ => ResourceState::Absent,
+                };
+                SnapshotResource {
+                    snapshotter: snapshotter.clone(),
+                    state,
+                }
+            })
+            .collect();
+
+        Snapshot {
+            change_tick,
+            is_diff: since.is_some(),
+            entities,
+            alive,
+            columns,
+            resources,
+        }
+    }
+
+    /// Restores the components and resources captured in `snapshot`.
+    ///
+    /// - Captured entities that no longer exist are spawned again, and `entity_map` is updated to map
+    ///   their captured id to the new one. Entity references in the restored values are mapped through
+    ///   `entity_map`, so the same map should be reused across restores.
+    /// - Entities with any of the captured components that were spawned after the snapshot was
+    ///   captured are despawned.
+    /// - Captured components and resources that weren't present when the snapshot was captured are
+    ///   removed, including from entities that existed then but weren't captured.
+    pub fn restore_snapshot(
+        &mut self,
+        snapshot: &Snapshot,
+        entity_map: &mut EntityHashMap<Entity>,
+    ) {
+        let mut restored = EntityHashSet::default();
+        let targets: Vec<_> = snapshot
+            .entities
+            .iter()
+            .map(|&entity| {
+                let mapped = entity_map.get_mapped(entity);
+                let target = if self.entities.contains(mapped) {
+                    mapped
+                } else {
+                    let target = self.spawn_empty().id();
+                    entity_map.set_mapped(entity, target);
+                    target
+                };
+                restored.insert(target);
+                target
+            })
+            .collect();
+
+        let component_ids: Vec<_> = snapshot
+            .columns
+            .iter()
+            .filter_map(|column| column.snapshotter.component_id(self))
+            .collect();
+        let stale: Vec<_> = self
+            .archetypes
+            .iter()
+            .filter(|archetype| component_ids.iter().any(|&id| archetype.contains(id)))
+            .flat_map(|archetype| archetype.entities().iter().map(ArchetypeEntity::id))
+            .filter(|entity| !restored.contains(entity))
+            .collect();
+        for entity in stale {
+            if !snapshot.contains_entity(entity) {
+                self.despawn(entity);
+                continue;
+            }
+            let Ok(mut entity) = self.get_entity_mut(entity) else {
+                continue;
+            };
+            for column in &snapshot.columns {
+                column.snapshotter.remove(&mut entity);
+            }
+        }
+
+        for column in &snapshot.columns {
+            let mut values = column.rows.iter().enumerate().peekable();
+            for (row, &target) in targets.iter().enumerate() {
+                let Ok(mut entity) = self.get_entity_mut(target) else {
+                    continue;
+                };
+                if !column.present.contains(row) {
+                    column.snapshotter.remove(&mut entity);
+                } else if let Some((index, _)) =
+                    values.next_if(|(_, &value_row)| value_row as usize == row)
+                {
+                    column
+                        .snapshotter
+                        .restore(&*column.values, index, &mut entity, entity_map);
+                }
+            }
+        }
+
+        for resource in &snapshot.resources {
+            match &resource.state {
+                ResourceState::Absent => resource.snapshotter.remove(self),
+                ResourceState::Unchanged => {}
+                ResourceState::Captured(value) => {
+                    resource.snapshotter.restore(self, &**value, entity_map);
+                }
+            }
+        }
+    }
+}
+
+// `portable-atomic-util`'s `Arc` can't coerce unsized types like `std::sync::Arc` can,
+// so the snapshotters are coerced through a `Box` first.
+fn into_arc<T: ?Sized>(boxed: Box<T>) -> Arc<T> {
+    Arc::from(boxed)
+}
+
+/// Captures and restores a single component type.
+trait ComponentSnapshotter: Send + Sync {
+    fn component_id(&self, world: &World) -> Option<ComponentId>;
+
+    /// Creates an empty column of captured values.
+    fn new_column(&self) -> Box<dyn Any + Send + Sync>;
+
+    /// Pushes the value of the component on `entity` to `column`, returning `false` if there is none.
+    fn capture(&self, column: &mut dyn Any, entity: EntityRef) -> bool;
+
+    /// Inserts the `index`th value of `column` on `entity`.
+    fn restore(
+        &self,
+        column: &dyn Any,
+        index: usize,
+        entity: &mut EntityWorldMut,
+        entity_map: &mut EntityHashMap<Entity>,
+    );
+
+    fn remove(&self, entity: &mut EntityWorldMut);
+}
+
+/// Captures and restores a single resource type.
+trait ResourceSnapshotter: Send + Sync {
+    fn component_id(&self, world: &World) -> Option<ComponentId>;
+
+    fn capture(&self, world: &World) -> Option<Box<dyn Any + Send + Sync>>;
+
+    fn restore(&self, world: &mut World, value: &dyn Any, entity_map: &mut EntityHashMap<Entity>);
+
+    fn remove(&self, world: &mut World);
+}
+
+struct CloneComponentSnapshotter<C>(PhantomData<fn() -> C>);
+
+impl<C: Component + Clone> ComponentSnapshotter for CloneComponentSnapshotter<C> {
+    fn component_id(&self, world: &World) -> Option<ComponentId> {
+        world.component_id::<C>()
+    }
+
+    fn new_column(&self) -> Box<dyn Any + Send + Sync> {
+        Box::new(Vec::<C>::new())
+    }
+
+    fn capture(&self, column: &mut dyn Any, entity: EntityRef) -> bool {
+        let (Some(column), Some(component)) = (column.downcast_mut::<Vec<C>>(), entity.get::<C>())
+        else {
+            return false;
+        };
+        column.push(component.clone());
+        true
+    }
+
+    fn restore(
+        &self,
+        column: &dyn Any,
+        index: usize,
+        entity: &mut EntityWorldMut,
+        entity_map: &mut EntityHashMap<Entity>,
+    ) {
+        let Some(component) = column
+            .downcast_ref::<Vec<C>>()
+            .and_then(|column| column.get(index))
+        else {
+            return;
+        };
+        let mut component = component.clone();
+        C::visit_entities_mut(&mut component, |entity| {
+            *entity = entity_map.get_mapped(*entity);
+        });
+        entity.insert(component);
+    }
+
+    fn remove(&self, entity: &mut EntityWorldMut) {
+        entity.remove::<C>();
+    }
+}
+
+struct CloneResourceSnapshotter<R> {
+    map_entities: fn(&mut R, &mut EntityHashMap<Entity>),
+}
+
+impl<R: Resource + Clone> ResourceSnapshotter for CloneResourceSnapshotter<R> {
+    fn component_id(&self, world: &World) -> Option<ComponentId> {
+        world.components().resource_id::<R>()
+    }
+
+    fn capture(&self, world: &World) -> Option<Box<dyn Any + Send + Sync>> {
+        world
+            .get_resource::<R>()
+            .map(|resource| Box::new(resource.clone()) as Box<dyn Any + Send + Sync>)
+    }
+
+    fn restore(&self, world: &mut World, value: &dyn Any, entity_map: &mut EntityHashMap<Entity>) {
+        if let Some(resource) = value.downcast_ref::<R>() {
+            let mut resource = resource.clone();
+            (self.map_entities)(&mut resource, entity_map);
+            world.insert_resource(resource);
+        }
+    }
+
+    fn remove(&self, world: &mut World) {
+        world.remove_resource::<R>();
+    }
+}
+
+#[cfg(feature = "bevy_reflect")]
+struct ReflectComponentSnapshotter {
+    type_id: TypeId,
+    reflect_component: ReflectComponent,
+    type_registry: TypeRegistryArc,
+}
+
+#[cfg(feature = "bevy_reflect")]
+impl ComponentSnapshotter for ReflectComponentSnapshotter {
+    fn component_id(&self, world: &World) -> Option<ComponentId> {
+        world.components().get_id(self.type_id)
+    }
+
+    fn new_column(&self) -> Box<dyn Any + Send + Sync> {
+        Box::new(Vec::<Box<dyn PartialReflect>>::new())
+    }
+
+    fn capture(&self, column: &mut dyn Any, entity: EntityRef) -> bool {
+        let (Some(column), Some(component)) = (
+            column.downcast_mut::<Vec<Box<dyn PartialReflect>>>(),
+            self.reflect_component.reflect(entity),
+        ) else {
+            return false;
+        };
+        column.push(component.clone_value());
+        true
+    }
+
+    fn restore(
+        &self,
+        column: &dyn Any,
+        index: usize,
+        entity: &mut EntityWorldMut,
+        entity_map: &mut EntityHashMap<Entity>,
+    ) {
+        if let Some(component) = column
+            .downcast_ref::<Vec<Box<dyn PartialReflect>>>()
+            .and_then(|column| column.get(index))
+        {
+            self.reflect_component.apply_or_insert_mapped(
+                entity,
+                &**component,
+                &self.type_registry.read(),
+                entity_map,
+            );
+        }
+    }
+
+    fn remove(&self, entity: &mut EntityWorldMut) {
+        self.reflect_component.remove(entity);
+    }
+}
+
+#[cfg(feature = "bevy_reflect")]
+struct ReflectResourceSnapshotter {
+    type_id: TypeId,
+    reflect_resource: ReflectResource,
+    reflect_map_entities: Option<ReflectMapEntities>,
+    type_registry: TypeRegistryArc,
+}
+
+#[cfg(feature = "bevy_reflect")]
+impl ResourceSnapshotter for ReflectResourceSnapshotter {
+    fn component_id(&self, world: &World) -> Option<ComponentId> {
+        world.components().get_resource_id(self.type_id)
+    }
+
+    fn capture(&self, world: &World) -> Option<Box<dyn Any + Send + Sync>> {
+        self.reflect_resource
+            .reflect(world)
+            .map(|resource| Box::new(resource.clone_value()) as Box<dyn Any + Send + Sync>)
+    }
+
+    fn restore(&self, world: &mut World, value: &dyn Any, entity_map: &mut EntityHashMap<Entity>) {
+        let Some(resource) = value.downcast_ref::<Box<dyn PartialReflect>>() else {
+            return;
+        };
+        let mut resource = resource.clone_value();
+        if let Some(reflect_map_entities) = &self.reflect_map_entities {
+            reflect_map_entities.map_entities(&mut *resource, entity_map);
+        }
+        self.reflect_resource
+            .apply_or_insert(world, &*resource, &self.type_registry.read());
+    }
+
+    fn remove(&self, world: &mut World) {
+        self.reflect_resource.remove(world);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::entity::hash_map::EntityHashMap;
+
+    #[derive(Component, Clone, PartialEq, Debug)]
+    struct Position(i32);
+
+    #[derive(Component, Clone, PartialEq, Debug)]
+    struct Target(#[entities] Entity);
+
+    #[derive(Component, Clone)]
+    struct Untracked;
+
+    #[derive(Resource, Clone, PartialEq, Debug)]
+    struct Frame(u32);
+
+    fn config() -> SnapshotConfig {
+        SnapshotConfig::new()
+            .with_component::<Position>()
+            .with_component::<Target>()
+            .with_resource::<Frame>()
+    }
+
+    #[test]
+    fn restore_snapshot() {
+        let mut world = World::new();
+        world.insert_resource(Frame(0));
+        let a = world.spawn(Position(0)).id();
+        let b = world.spawn((Position(1), Target(a), Untracked)).id();
+
+        let config = config();
+        let snapshot = world.snapshot(&config);
+        assert_eq!(snapshot.entities().len(), 2);
+        assert_eq!(snapshot.component_count(), 3);
+
+        world.entity_mut(a).insert(Position(10));
+        world.entity_mut(b).remove::<Target>();
+        let c = world.spawn(Position(2)).id();
+        world.insert_resource(Frame(1));
+
+        let mut entity_map = EntityHashMap::default();
+        world.restore_snapshot(&snapshot, &mut entity_map);
+        assert!(entity_map.is_empty());
+        assert_eq!(world.get::<Position>(a), Some(&Position(0)));
+        assert_eq!(world.get::<Target>(b), Some(&Target(a)));
+        assert!(world.get::<Untracked>(b).is_some());
+        assert!(world.get_entity(c).is_err());
+        assert_eq!(world.resource::<Frame>(), &Frame(0));
+    }
+
+    #[test]
+    fn restore_keeps_entities_that_gained_captured_components() {
+        let mut world = World::new();
+        let a = world.spawn(Position(0)).id();
+        let camera = world.spawn(Untracked).id();
+
+        let snapshot = world.snapshot(&config());
+        assert!(snapshot.contains_entity(camera));
+        assert!(!snapshot.entities().contains(&camera));
+
+        world.entity_mut(camera).insert((Position(3), Target(a)));
+        world.despawn(a);
+        let reused = world.spawn(Position(4)).id();
+        assert!(!snapshot.contains_entity(reused));
+
+        world.restore_snapshot(&snapshot, &mut EntityHashMap::default());
+        assert!(world.get::<Untracked>(camera).is_some());
+        assert!(world.get::<Position>(camera).is_none());
+        assert!(world.get::<Target>(camera).is_none());
+        assert!(world.get_entity(reused).is_err());
+    }
+
+    #[test]
+    fn restore_despawned_entities() {
+        let mut world = World::new();
+        let a = world.spawn(Position(0)).id();
+        let b = world.spawn(Target(a)).id();
+
+        let snapshot = world.snapshot(&config());
+        world.despawn(a);
+        world.remove_resource::<Frame>();
+
+        let mut entity_map = EntityHashMap::default();
+        world.restore_snapshot(&snapshot, &mut entity_map);
+        let new_a = entity_map[&a];
+        assert_ne!(new_a, a);
+        assert_eq!(world.get::<Position>(new_a), Some(&Position(0)));
+        assert_eq!(world.get::<Target>(b), Some(&Target(new_a)));
+        assert!(!world.contains_resource::<Frame>());
+    }
+
+    #[test]
+    fn restore_snapshot_changes() {
+        let mut world = World::new();
+        world.insert_resource(Frame(0));
+        let a = world.spawn(Position(0)).id();
+        let b = world.spawn(Position(1)).id();
+
+        let config = config();
+        let base = world.snapshot(&config);
+        world.entity_mut(a).insert(Position(5));
+        world.entity_mut(b).insert(Target(a));
+
+        let diff = world.snapshot_changes(&config, &base);
+        assert!(diff.is_diff());
+        assert_eq!(diff.entities().len(), 2);
+        assert_eq!(diff.component_count(), 2);
+        assert_eq!(diff.resource_count(), 0);
+
+        world.entity_mut(a).insert(Position(7));
+        world.entity_mut(b).insert(Position(8));
+        world.insert_resource(Frame(2));
+
+        let mut entity_map = EntityHashMap::default();
+        world.restore_snapshot(&base, &mut entity_map);
+        assert_eq!(world.get::<Position>(a), Some(&Position(0)));
+        assert!(world.get::<Target>(b).is_none());
+
+        world.restore_snapshot(&diff, &mut entity_map);
+        assert_eq!(world.get::<Position>(a), Some(&Position(5)));
+        assert_eq!(world.get::<Position>(b), Some(&Position(1)));
+        assert_eq!(world.get::<Target>(b), Some(&Target(a)));
+        assert_eq!(world.resource::<Frame>(), &Frame(0));
+    }
+
+    #[cfg(feature = "bevy_reflect")]
+    #[test]
+    fn restore_reflected_snapshot() {
+        use crate::reflect::{AppTypeRegistry, ReflectComponent, ReflectResource};
+        use bevy_reflect::Reflect;
+        use core::any::TypeId;
+
+        #[derive(Component, Reflect, PartialEq, Debug)]
+        #[reflect(Component)]
+        struct Health(u32);
+
+        #[derive(Resource, Reflect, PartialEq, Debug)]
+        #[reflect(Resource)]
+        struct Seed(u64);
+
+        let type_registry = AppTypeRegistry::default();
+        type_registry.write().register::<Health>();
+        type_registry.write().register::<Seed>();
+        let config = SnapshotConfig::new()
+            .with_reflect_component(&type_registry, TypeId::of::<Health>())
+            .with_reflect_resource(&type_registry, TypeId::of::<Seed>());
+
+        let mut world = World::new();
+        world.insert_resource(Seed(1));
+        let entity = world.spawn(Health(10)).id();
+        let snapshot = world.snapshot(&config);
+
+        world.entity_mut(entity).insert(Health(3));
+        world.insert_resource(Seed(2));
+        world.restore_snapshot(&snapshot, &mut EntityHashMap::default());
+        assert_eq!(world.get::<Health>(entity), Some(&Health(10)));
+        assert_eq!(world.resource::<Seed>(), &Seed(1));
+    }
+}
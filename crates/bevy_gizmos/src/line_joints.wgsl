@@ -1,6 +1,17 @@
-#import bevy_render::{view::View, maths::affine3_to_square}
+#import bevy_render::{view::{View, Multiview}, maths::affine3_to_square}
 
+#ifdef MULTIVIEW
+// The view of the eye being rendered, set by `select_view`.
+var<private> view: View;
+@group(0) @binding(40) var<uniform> multiview: Multiview;
+
+// Selects the view of the eye being rendered.
+fn select_view(view_index: i32) {
+    view = multiview.views[view_index];
+}
+#else   // MULTIVIEW
 @group(0) @binding(0) var<uniform> view: View;
+#endif  // MULTIVIEW
 
 
 struct LineGizmoUniform {
@@ -22,6 +33,9 @@ struct VertexInput {
     @location(2) position_c: vec3<f32>,
     @location(3) color: vec4<f32>,
     @builtin(vertex_index) index: u32,
+#ifdef MULTIVIEW
+    @builtin(view_index) view_index: i32,
+#endif
 };
 
 struct VertexOutput {
@@ -33,6 +47,10 @@ const EPSILON: f32 = 4.88e-04;
 
 @vertex
 fn vertex_bevel(vertex: VertexInput) -> VertexOutput {
+#ifdef MULTIVIEW
+    select_view(vertex.view_index);
+#endif
+
     var positions = array<vec2<f32>, 3>(
         vec2(0, 0),
         vec2(0, 0.5),
@@ -91,6 +109,10 @@ fn vertex_bevel(vertex: VertexInput) -> VertexOutput {
 
 @vertex
 fn vertex_miter(vertex: VertexInput) -> VertexOutput {
+#ifdef MULTIVIEW
+    select_view(vertex.view_index);
+#endif
+
     var positions = array<vec3<f32>, 6>(
         vec3(0, 0, 0),
         vec3(0.5, 0, 0),
@@ -153,6 +175,10 @@ fn vertex_miter(vertex: VertexInput) -> VertexOutput {
 
 @vertex
 fn vertex_round(vertex: VertexInput) -> VertexOutput {
+#ifdef MULTIVIEW
+    select_view(vertex.view_index);
+#endif
+
     let world_from_local = affine3_to_square(joints_gizmo.world_from_local);
 
     var clip_a = view.clip_from_world * world_from_local * vec4(vertex.position_a, 1.);
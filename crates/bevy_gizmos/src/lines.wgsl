@@ -1,7 +1,18 @@
 // TODO use common view binding
-#import bevy_render::{view::View, maths::affine3_to_square}
+#import bevy_render::{view::{View, Multiview}, maths::affine3_to_square}
 
+#ifdef MULTIVIEW
+// The view of the eye being rendered, set by `select_view`.
+var<private> view: View;
+@group(0) @binding(40) var<uniform> multiview: Multiview;
+
+// Selects the view of the eye being rendered.
+fn select_view(view_index: i32) {
+    view = multiview.views[view_index];
+}
+#else   // MULTIVIEW
 @group(0) @binding(0) var<uniform> view: View;
+#endif  // MULTIVIEW
 
 
 struct LineGizmoUniform {
@@ -25,6 +36,9 @@ struct VertexInput {
     @location(2) color_a: vec4<f32>,
     @location(3) color_b: vec4<f32>,
     @builtin(vertex_index) index: u32,
+#ifdef MULTIVIEW
+    @builtin(view_index) view_index: i32,
+#endif
 };
 
 struct VertexOutput {
@@ -38,6 +52,10 @@ const EPSILON: f32 = 4.88e-04;
 
 @vertex
 fn vertex(vertex: VertexInput) -> VertexOutput {
+#ifdef MULTIVIEW
+    select_view(vertex.view_index);
+#endif
+
     var positions = array<vec2<f32>, 6>(
         vec2(-0.5, 0.),
         vec2(-0.5, 1.),
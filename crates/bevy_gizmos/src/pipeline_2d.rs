@@ -165,6 +165,7 @@ impl SpecializedRenderPipeline for LineGizmoPipeline {
             label: Some("LineGizmo Pipeline 2D".into()),
             push_constant_ranges: vec![],
             zero_initialize_workgroup_memory: false,
+            multiview: None,
         }
     }
 }
@@ -266,6 +267,7 @@ impl SpecializedRenderPipeline for LineJointGizmoPipeline {
             label: Some("LineJointGizmo Pipeline 2D".into()),
             push_constant_ranges: vec![],
             zero_initialize_workgroup_memory: false,
+            multiview: None,
         }
     }
 }
@@ -22,6 +22,7 @@ use bevy_image::BevyDefault as _;
 use bevy_pbr::{MeshPipeline, MeshPipelineKey, SetMeshViewBindGroup};
 use bevy_render::sync_world::MainEntity;
 use bevy_render::{
+    camera::Multiview,
     render_asset::{prepare_assets, RenderAssets},
     render_phase::{
         AddRenderCommand, DrawFunctions, PhaseItemExtraIndex, SetItemPipeline,
@@ -109,6 +110,10 @@ impl SpecializedRenderPipeline for LineGizmoPipeline {
             shader_defs.push("PERSPECTIVE".into());
         }
 
+        if key.view_key.contains(MeshPipelineKey::MULTIVIEW) {
+            shader_defs.push("MULTIVIEW".into());
+        }
+
         let format = if key.view_key.contains(MeshPipelineKey::HDR) {
             ViewTarget::TEXTURE_FORMAT_HDR
         } else {
@@ -162,6 +167,7 @@ impl SpecializedRenderPipeline for LineGizmoPipeline {
             label: Some("LineGizmo 3d Pipeline".into()),
             push_constant_ranges: vec![],
             zero_initialize_workgroup_memory: false,
+            multiview: key.view_key.multiview(),
         }
     }
 }
@@ -204,6 +210,10 @@ impl SpecializedRenderPipeline for LineJointGizmoPipeline {
             shader_defs.push("PERSPECTIVE".into());
         }
 
+        if key.view_key.contains(MeshPipelineKey::MULTIVIEW) {
+            shader_defs.push("MULTIVIEW".into());
+        }
+
         let format = if key.view_key.contains(MeshPipelineKey::HDR) {
             ViewTarget::TEXTURE_FORMAT_HDR
         } else {
@@ -261,6 +271,7 @@ impl SpecializedRenderPipeline for LineJointGizmoPipeline {
             label: Some("LineJointGizmo 3d Pipeline".into()),
             push_constant_ranges: vec![],
             zero_initialize_workgroup_memory: false,
+            multiview: key.view_key.multiview(),
         }
     }
 }
@@ -301,6 +312,7 @@ fn queue_line_gizmos_3d(
             Has<DepthPrepass>,
             Has<MotionVectorPrepass>,
             Has<DeferredPrepass>,
+            Has<Multiview>,
         ),
     )>,
 ) {
@@ -314,7 +326,7 @@ fn queue_line_gizmos_3d(
         view,
         msaa,
         render_layers,
-        (normal_prepass, depth_prepass, motion_vector_prepass, deferred_prepass),
+        (normal_prepass, depth_prepass, motion_vector_prepass, deferred_prepass, multiview),
     ) in &views
     {
         let Some(transparent_phase) = transparent_render_phases.get_mut(&view.retained_view_entity)
@@ -327,20 +339,25 @@ fn queue_line_gizmos_3d(
         let mut view_key = MeshPipelineKey::from_msaa_samples(msaa.samples())
             | MeshPipelineKey::from_hdr(view.hdr);
 
-        if normal_prepass {
-            view_key |= MeshPipelineKey::NORMAL_PREPASS;
-        }
+        // The main passes of multiview views don't read the layered prepass textures.
+        if multiview {
+            view_key |= MeshPipelineKey::MULTIVIEW;
+        } else {
+            if normal_prepass {
+                view_key |= MeshPipelineKey::NORMAL_PREPASS;
+            }
 
-        if depth_prepass {
-            view_key |= MeshPipelineKey::DEPTH_PREPASS;
-        }
+            if depth_prepass {
+                view_key |= MeshPipelineKey::DEPTH_PREPASS;
+            }
 
-        if motion_vector_prepass {
-            view_key |= MeshPipelineKey::MOTION_VECTOR_PREPASS;
-        }
+            if motion_vector_prepass {
+                view_key |= MeshPipelineKey::MOTION_VECTOR_PREPASS;
+            }
 
-        if deferred_prepass {
-            view_key |= MeshPipelineKey::DEFERRED_PREPASS;
+            if deferred_prepass {
+                view_key |= MeshPipelineKey::DEFERRED_PREPASS;
+            }
         }
 
         for (entity, main_entity, config) in &line_gizmos {
@@ -416,6 +433,7 @@ fn queue_line_joint_gizmos_3d(
             Has<DepthPrepass>,
             Has<MotionVectorPrepass>,
             Has<DeferredPrepass>,
+            Has<Multiview>,
         ),
     )>,
 ) {
@@ -428,7 +446,7 @@ fn queue_line_joint_gizmos_3d(
         view,
         msaa,
         render_layers,
-        (normal_prepass, depth_prepass, motion_vector_prepass, deferred_prepass),
+        (normal_prepass, depth_prepass, motion_vector_prepass, deferred_prepass, multiview),
     ) in &views
     {
         let Some(transparent_phase) = transparent_render_phases.get_mut(&view.retained_view_entity)
@@ -441,20 +459,25 @@ fn queue_line_joint_gizmos_3d(
         let mut view_key = MeshPipelineKey::from_msaa_samples(msaa.samples())
             | MeshPipelineKey::from_hdr(view.hdr);
 
-        if normal_prepass {
-            view_key |= MeshPipelineKey::NORMAL_PREPASS;
-        }
+        // The main passes of multiview views don't read the layered prepass textures.
+        if multiview {
+            view_key |= MeshPipelineKey::MULTIVIEW;
+        } else {
+            if normal_prepass {
+                view_key |= MeshPipelineKey::NORMAL_PREPASS;
+            }
 
-        if depth_prepass {
-            view_key |= MeshPipelineKey::DEPTH_PREPASS;
-        }
+            if depth_prepass {
+                view_key |= MeshPipelineKey::DEPTH_PREPASS;
+            }
 
-        if motion_vector_prepass {
-            view_key |= MeshPipelineKey::MOTION_VECTOR_PREPASS;
-        }
+            if motion_vector_prepass {
+                view_key |= MeshPipelineKey::MOTION_VECTOR_PREPASS;
+            }
 
-        if deferred_prepass {
-            view_key |= MeshPipelineKey::DEFERRED_PREPASS;
+            if deferred_prepass {
+                view_key |= MeshPipelineKey::DEFERRED_PREPASS;
+            }
         }
 
         for (entity, main_entity, config) in &line_gizmos {
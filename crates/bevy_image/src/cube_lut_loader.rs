@@ -0,0 +1,261 @@
+use crate::{Image, ImageAddressMode, ImageFilterMode, ImageSampler, ImageSamplerDescriptor};
+use bevy_asset::RenderAssetUsages;
+use bevy_asset::{io::Reader, AssetLoader, LoadContext};
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::warn;
+use wgpu_types::{Extent3d, TextureDimension, TextureFormat};
+
+/// Loads 3D color lookup tables from the `.cube` files of Adobe and Resolve
+/// as 3D [`Image`]s.
+///
+/// The loaded image stores the table in [`TextureFormat::Rgba16Float`], with
+/// the red input along X, green along Y and blue along Z, and a linearly
+/// filtered, edge clamped sampler. 1D lookup tables and tables with an input
+/// domain other than `0.0..=1.0` aren't supported. Other keywords, such as
+/// `LUT_IN_VIDEO_RANGE`, are ignored with a warning.
+#[derive(Clone, Default)]
+pub struct CubeLutLoader;
+
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct CubeLutLoaderSettings {
+    pub asset_usage: RenderAssetUsages,
+}
+
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum CubeLutLoaderError {
+    #[error("Could not load LUT: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("LUT is not valid UTF-8")]
+    InvalidUtf8,
+    #[error("Invalid line {line} in LUT: {reason}")]
+    InvalidLine { line: usize, reason: &'static str },
+    #[error("1D LUTs are not supported")]
+    Unsupported1d,
+    #[error("LUT domains other than 0 to 1 are not supported")]
+    UnsupportedDomain,
+    #[error("LUT is missing its LUT_3D_SIZE")]
+    MissingSize,
+    #[error("LUT has {found} entries but its size requires {expected}")]
+    WrongEntryCount { expected: usize, found: usize },
+}
+
+impl AssetLoader for CubeLutLoader {
+    type Asset = Image;
+    type Settings = CubeLutLoaderSettings;
+    type Error = CubeLutLoaderError;
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        settings: &Self::Settings,
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Image, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let text = core::str::from_utf8(&bytes).map_err(|_| CubeLutLoaderError::InvalidUtf8)?;
+        let (size, entries) = parse_cube_lut(text)?;
+
+        let mut data = Vec::with_capacity(entries.len() * 8);
+        for rgb in entries {
+            for channel in [rgb[0], rgb[1], rgb[2], 1.0] {
+                data.extend_from_slice(&half::f16::from_f32(channel).to_le_bytes());
+            }
+        }
+
+        let mut image = Image::new(
+            Extent3d {
+                width: size,
+                height: size,
+                depth_or_array_layers: size,
+            },
+            TextureDimension::D3,
+            data,
+            TextureFormat::Rgba16Float,
+            settings.asset_usage,
+        );
+        image.sampler = ImageSampler::Descriptor(ImageSamplerDescriptor {
+            label: Some("cube LUT sampler".to_string()),
+            address_mode_u: ImageAddressMode::ClampToEdge,
+            address_mode_v: ImageAddressMode::ClampToEdge,
+            address_mode_w: ImageAddressMode::ClampToEdge,
+            mag_filter: ImageFilterMode::Linear,
+            min_filter: ImageFilterMode::Linear,
+            mipmap_filter: ImageFilterMode::Linear,
+            ..Default::default()
+        });
+        Ok(image)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["cube"]
+    }
+}
+
+/// Parses the text of a `.cube` file into the size of its 3D table and its
+/// entries, in file order: red changes fastest, then green, then blue.
+fn parse_cube_lut(text: &str) -> Result<(u32, Vec<[f32; 3]>), CubeLutLoaderError> {
+    let mut size = None;
+    let mut entries = Vec::new();
+
+    for (index, line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        let invalid = |reason| CubeLutLoaderError::InvalidLine {
+            line: line_number,
+            reason,
+        };
+
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let mut words = line.split_whitespace();
+        let Some(first) = words.next() else {
+            continue;
+        };
+        let numbers = |words: core::str::SplitWhitespace| {
+            words
+                .map(str::parse::<f32>)
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|_| invalid("expected numbers"))
+        };
+
+        match first {
+            // Titles may be quoted and contain spaces, so skip the rest of the
+            // line.
+            "TITLE" => {}
+            "LUT_1D_SIZE" => return Err(CubeLutLoaderError::Unsupported1d),
+            "LUT_3D_SIZE" => {
+                let value = words
+                    .next()
+                    .and_then(|word| word.parse::<u32>().ok())
+                    .filter(|value| (2..=256).contains(value))
+                    .ok_or_else(|| invalid("expected a size from 2 to 256"))?;
+                size = Some(value);
+            }
+            "DOMAIN_MIN" | "DOMAIN_MAX" => {
+                let expected = if first == "DOMAIN_MIN" { 0.0 } else { 1.0 };
+                let values = numbers(words)?;
+                if values.len() != 3 {
+                    return Err(invalid("expected three numbers"));
+                }
+                if values.iter().any(|&value| value != expected) {
+                    return Err(CubeLutLoaderError::UnsupportedDomain);
+                }
+            }
+            "LUT_3D_INPUT_RANGE" => {
+                if numbers(words)? != [0.0, 1.0] {
+                    return Err(CubeLutLoaderError::UnsupportedDomain);
+                }
+            }
+            // Keywords of other applications, and the input range of a 1D
+            // shaper, which is only used along with a `LUT_1D_SIZE`.
+            _ if first.starts_with(|c: char| c.is_ascii_alphabetic()) => {
+                warn!("Ignoring unsupported keyword {first} on line {line_number} of LUT");
+            }
+            _ => {
+                let values = numbers(line.split_whitespace())?;
+                let [r, g, b] = values[..] else {
+                    return Err(invalid("expected three numbers"));
+                };
+                entries.push([r, g, b]);
+            }
+        }
+    }
+
+    let size = size.ok_or(CubeLutLoaderError::MissingSize)?;
+    let expected = (size as usize).pow(3);
+    if entries.len() != expected {
+        return Err(CubeLutLoaderError::WrongEntryCount {
+            expected,
+            found: entries.len(),
+        });
+    }
+    Ok((size, entries))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_cube_lut, CubeLutLoaderError};
+
+    #[test]
+    fn parse_identity_lut() {
+        let text = "\
+# Created by hand
+TITLE \"Identity LUT\"
+LUT_3D_SIZE 2
+DOMAIN_MIN 0.0 0.0 0.0
+DOMAIN_MAX 1.0 1.0 1.0
+
+0.0 0.0 0.0
+1.0 0.0 0.0
+0.0 1.0 0.0
+1.0 1.0 0.0
+0.0 0.0 1.0
+1.0 0.0 1.0
+0.0 1.0 1.0
+1.0 1.0 1.0
+";
+        let (size, entries) = parse_cube_lut(text).unwrap();
+        assert_eq!(size, 2);
+        assert_eq!(entries.len(), 8);
+        assert_eq!(entries[1], [1.0, 0.0, 0.0]);
+        assert_eq!(entries[2], [0.0, 1.0, 0.0]);
+        assert_eq!(entries[4], [0.0, 0.0, 1.0]);
+    }
+
+    #[test]
+    fn skip_unknown_keywords() {
+        let text = "\
+LUT_3D_SIZE 2
+LUT_IN_VIDEO_RANGE
+LUT_1D_INPUT_RANGE 0.0 1.0
+LUT_OUT_VIDEO_RANGE
+0 0 0
+1 0 0
+0 1 0
+1 1 0
+0 0 1
+1 0 1
+0 1 1
+1 1 1
+";
+        let (size, entries) = parse_cube_lut(text).unwrap();
+        assert_eq!(size, 2);
+        assert_eq!(entries.len(), 8);
+
+        // Malformed data rows are still errors.
+        assert!(matches!(
+            parse_cube_lut("LUT_3D_SIZE 2\nLUT_IN_VIDEO_RANGE\n0 0 x\n"),
+            Err(CubeLutLoaderError::InvalidLine { line: 3, .. })
+        ));
+    }
+
+    #[test]
+    fn reject_invalid_luts() {
+        assert!(matches!(
+            parse_cube_lut("LUT_1D_SIZE 2\n0 0 0\n1 1 1\n"),
+            Err(CubeLutLoaderError::Unsupported1d)
+        ));
+        assert!(matches!(
+            parse_cube_lut("LUT_3D_SIZE 2\nDOMAIN_MAX 2 2 2\n"),
+            Err(CubeLutLoaderError::UnsupportedDomain)
+        ));
+        assert!(matches!(
+            parse_cube_lut("0 0 0\n"),
+            Err(CubeLutLoaderError::MissingSize)
+        ));
+        assert!(matches!(
+            parse_cube_lut("LUT_3D_SIZE 2\n0 0 0\n"),
+            Err(CubeLutLoaderError::WrongEntryCount {
+                expected: 8,
+                found: 1
+            })
+        ));
+        assert!(matches!(
+            parse_cube_lut("LUT_3D_SIZE 2\n0 0\n"),
+            Err(CubeLutLoaderError::InvalidLine { line: 2, .. })
+        ));
+    }
+}
@@ -0,0 +1,936 @@
+use crate::{Image, ImageSampler, TextureAccessError};
+use bevy_asset::{
+    io::{Reader, Writer},
+    saver::{AssetSaver, SavedAsset},
+    transformer::{AssetTransformer, TransformedAsset},
+    AssetLoader, Handle, LoadContext, LoadedAsset, RenderAssetUsages,
+};
+use bevy_color::LinearRgba;
+use bevy_math::{ops, Vec2, Vec3};
+#[cfg(feature = "bevy_reflect")]
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use core::f32::consts::{PI, TAU};
+use futures_lite::AsyncWriteExt;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use wgpu_types::{
+    Extent3d, TextureDimension, TextureFormat, TextureViewDescriptor, TextureViewDimension,
+};
+
+/// The label of the diffuse map that [`EquirectangularToCubemapTransformer`] adds to the skybox.
+pub const EQUIRECTANGULAR_DIFFUSE_MAP_LABEL: &str = "diffuse";
+/// The label of the specular map that [`EquirectangularToCubemapTransformer`] adds to the skybox.
+pub const EQUIRECTANGULAR_SPECULAR_MAP_LABEL: &str = "specular";
+
+/// The magic number at the start of the files written by [`EnvironmentMapSaver`].
+const ENVIRONMENT_MAP_MAGIC: &[u8; 8] = b"BEVYENVM";
+/// The version of the file format of [`EnvironmentMapSaver`].
+const ENVIRONMENT_MAP_VERSION: u32 = 1;
+
+/// How to convert an equirectangular panorama into cubemaps.
+///
+/// Used by [`generate_environment_map`], [`EquirectangularToCubemapTransformer`] and the GPU
+/// conversion of `bevy_pbr`.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(
+    feature = "bevy_reflect",
+    derive(Reflect),
+    reflect(Default, Debug, PartialEq)
+)]
+pub struct EquirectangularToCubemapSettings {
+    /// The width and height of the faces of the skybox cubemap, in pixels.
+    pub face_size: u32,
+    /// The width and height of the faces of the diffuse cubemap, in pixels.
+    ///
+    /// Diffuse light varies slowly with the direction, so a few dozen pixels are plenty.
+    pub diffuse_face_size: u32,
+    /// The width and height of the faces of the first mip level of the specular cubemap, in
+    /// pixels.
+    ///
+    /// The specular cubemap has a full mip chain, with a roughness that increases linearly
+    /// from 0 at the first level to 1 at the last.
+    pub specular_face_size: u32,
+    /// The number of samples taken per pixel of the filtered cubemaps.
+    pub sample_count: u32,
+}
+
+impl Default for EquirectangularToCubemapSettings {
+    fn default() -> Self {
+        Self {
+            face_size: 1024,
+            diffuse_face_size: 32,
+            specular_face_size: 256,
+            sample_count: 256,
+        }
+    }
+}
+
+impl EquirectangularToCubemapSettings {
+    /// The number of mip levels of the skybox cubemap.
+    pub fn mip_level_count(&self) -> u32 {
+        mip_level_count(self.face_size)
+    }
+
+    /// The number of mip levels of the specular cubemap.
+    pub fn specular_mip_level_count(&self) -> u32 {
+        mip_level_count(self.specular_face_size)
+    }
+}
+
+fn mip_level_count(face_size: u32) -> u32 {
+    face_size.max(1).ilog2() + 1
+}
+
+/// The cubemaps generated from an equirectangular panorama.
+#[derive(Clone, Debug)]
+pub struct EquirectangularEnvironmentMapImages {
+    /// The panorama itself, with a full mip chain, for use as a skybox.
+    pub skybox: Image,
+    /// The diffuse map of an environment map light.
+    pub diffuse_map: Image,
+    /// The specular map of an environment map light.
+    pub specular_map: Image,
+}
+
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum EquirectangularToCubemapError {
+    #[error("Equirectangular image must be a single 2D image")]
+    WrongDimension,
+    #[error("Equirectangular image has no data")]
+    MissingData,
+    #[error("Cubemap face size must not be zero")]
+    ZeroFaceSize,
+    #[error("Could not read equirectangular image: {0}")]
+    TextureAccess(#[from] TextureAccessError),
+}
+
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum EnvironmentMapSaverError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error("Environment map has no {0} map")]
+    MissingMap(&'static str),
+    #[error("The {0} map of the environment map isn't an Rgba16Float cubemap with data")]
+    InvalidMap(&'static str),
+}
+
+#[non_exhaustive]
+#[derive(Debug, Error)]
+pub enum EnvironmentMapLoaderError {
+    #[error("Could not read environment map: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("File isn't an environment map")]
+    InvalidMagic,
+    #[error("Unsupported environment map version {0}")]
+    UnsupportedVersion(u32),
+    #[error("Environment map is truncated or has a malformed {0} map")]
+    InvalidMap(&'static str),
+}
+
+/// Converts an equirectangular panorama, such as one loaded from an `.hdr` or `.exr` file, into a
+/// cubemap with faces of `face_size` pixels and a full mip chain, on the CPU.
+///
+/// The center of the panorama faces forward, towards -Z. The cubemap is laid out the way
+/// `Skybox` and `EnvironmentMapLight` expect.
+pub fn equirectangular_to_cubemap(
+    image: &Image,
+    face_size: u32,
+) -> Result<Image, EquirectangularToCubemapError> {
+    let panorama = Panorama::from_image(image)?;
+    let radiance = Cubemap::from_panorama(&panorama, face_size)?;
+    Ok(radiance.to_image(radiance.mips.len() as u32))
+}
+
+/// Converts an equirectangular panorama into a skybox cubemap and the diffuse and specular maps
+/// of an environment map light, on the CPU.
+///
+/// The maps are filtered the way the glTF IBL Sampler filters them: the diffuse map with the
+/// Lambertian distribution and the specular map with the GGX distribution, importance sampled.
+/// This is slow for large maps and sample counts, so prefer the GPU conversion of `bevy_pbr` at
+/// runtime, or [`EquirectangularToCubemapTransformer`] to convert the maps ahead of time.
+pub fn generate_environment_map(
+    image: &Image,
+    settings: &EquirectangularToCubemapSettings,
+) -> Result<EquirectangularEnvironmentMapImages, EquirectangularToCubemapError> {
+    if settings.diffuse_face_size == 0 || settings.specular_face_size == 0 {
+        return Err(EquirectangularToCubemapError::ZeroFaceSize);
+    }
+
+    let panorama = Panorama::from_image(image)?;
+    let radiance = Cubemap::from_panorama(&panorama, settings.face_size)?;
+    let sample_count = settings.sample_count.max(1);
+
+    let diffuse = Cubemap::from_fn(settings.diffuse_face_size, 1, |_, normal| {
+        radiance.filter_diffuse(normal, settings.diffuse_face_size, sample_count)
+    });
+
+    let specular_mip_level_count = settings.specular_mip_level_count();
+    let specular = Cubemap::from_fn(
+        settings.specular_face_size,
+        specular_mip_level_count,
+        |mip, normal| {
+            let perceptual_roughness = if specular_mip_level_count > 1 {
+                mip as f32 / (specular_mip_level_count - 1) as f32
+            } else {
+                0.0
+            };
+            radiance.filter_specular(
+                normal,
+                perceptual_roughness,
+                settings.specular_face_size >> mip,
+                sample_count,
+            )
+        },
+    );
+
+    Ok(EquirectangularEnvironmentMapImages {
+        skybox: radiance.to_image(radiance.mips.len() as u32),
+        diffuse_map: diffuse.to_image(1),
+        specular_map: specular.to_image(specular_mip_level_count),
+    })
+}
+
+/// Converts an equirectangular panorama into a skybox cubemap, as an asset processing step.
+///
+/// The diffuse and specular maps of the panorama are added to the skybox as the
+/// [`EQUIRECTANGULAR_DIFFUSE_MAP_LABEL`] and [`EQUIRECTANGULAR_SPECULAR_MAP_LABEL`] labeled
+/// assets. See [`generate_environment_map`].
+///
+/// Use it with [`EnvironmentMapSaver`] in a
+/// [`LoadTransformAndSave`](bevy_asset::processor::LoadTransformAndSave) processor to keep the
+/// labeled maps in the processed asset.
+#[derive(Clone, Default)]
+pub struct EquirectangularToCubemapTransformer;
+
+impl AssetTransformer for EquirectangularToCubemapTransformer {
+    type AssetInput = Image;
+    type AssetOutput = Image;
+    type Settings = EquirectangularToCubemapSettings;
+    type Error = EquirectangularToCubemapError;
+
+    async fn transform<'a>(
+        &'a self,
+        mut asset: TransformedAsset<Image>,
+        settings: &'a Self::Settings,
+    ) -> Result<TransformedAsset<Image>, Self::Error> {
+        let images = generate_environment_map(asset.get(), settings)?;
+        insert_labeled_map(
+            &mut asset,
+            EQUIRECTANGULAR_DIFFUSE_MAP_LABEL,
+            images.diffuse_map,
+        );
+        insert_labeled_map(
+            &mut asset,
+            EQUIRECTANGULAR_SPECULAR_MAP_LABEL,
+            images.specular_map,
+        );
+        *asset.get_mut() = images.skybox;
+        Ok(asset)
+    }
+}
+
+/// Adds a map to a transformed skybox as a labeled asset.
+///
+/// [`EnvironmentMapSaver`] only looks labeled maps up by their label, and [`EnvironmentMapLoader`]
+/// gives them handles of their own when the processed skybox is loaded, so the handle stored with
+/// the map is never used.
+fn insert_labeled_map(asset: &mut TransformedAsset<Image>, label: &'static str, map: Image) {
+    asset.insert_labeled(label, Handle::<Image>::default(), LoadedAsset::from(map));
+}
+
+/// Saves a skybox cubemap and its diffuse and specular maps, as converted by
+/// [`EquirectangularToCubemapTransformer`], to be loaded by [`EnvironmentMapLoader`].
+///
+/// The file holds, after a header, the face size, the mip level count and the `Rgba16Float` data
+/// of the skybox, the diffuse map and the specular map, in that order.
+#[derive(Clone, Default)]
+pub struct EnvironmentMapSaver;
+
+impl AssetSaver for EnvironmentMapSaver {
+    type Asset = Image;
+    type Settings = ();
+    type OutputLoader = EnvironmentMapLoader;
+    type Error = EnvironmentMapSaverError;
+
+    async fn save(
+        &self,
+        writer: &mut Writer,
+        asset: SavedAsset<'_, Self::Asset>,
+        _settings: &Self::Settings,
+    ) -> Result<(), Self::Error> {
+        let diffuse_map = asset
+            .get_labeled::<Image, _>(EQUIRECTANGULAR_DIFFUSE_MAP_LABEL)
+            .ok_or(EnvironmentMapSaverError::MissingMap("diffuse"))?;
+        let specular_map = asset
+            .get_labeled::<Image, _>(EQUIRECTANGULAR_SPECULAR_MAP_LABEL)
+            .ok_or(EnvironmentMapSaverError::MissingMap("specular"))?;
+
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(ENVIRONMENT_MAP_MAGIC);
+        bytes.extend_from_slice(&ENVIRONMENT_MAP_VERSION.to_le_bytes());
+        for (name, image) in [
+            ("skybox", asset.get()),
+            ("diffuse", diffuse_map),
+            ("specular", specular_map),
+        ] {
+            let descriptor = &image.texture_descriptor;
+            let data = image
+                .data
+                .as_ref()
+                .filter(|data| {
+                    descriptor.format == TextureFormat::Rgba16Float
+                        && descriptor.size.width == descriptor.size.height
+                        && descriptor.size.depth_or_array_layers == 6
+                        && data.len()
+                            == cubemap_data_len(descriptor.size.width, descriptor.mip_level_count)
+                })
+                .ok_or(EnvironmentMapSaverError::InvalidMap(name))?;
+            bytes.extend_from_slice(&descriptor.size.width.to_le_bytes());
+            bytes.extend_from_slice(&descriptor.mip_level_count.to_le_bytes());
+            bytes.extend_from_slice(data);
+        }
+
+        writer.write_all(&bytes).await?;
+        Ok(())
+    }
+}
+
+/// Loads the skybox cubemaps saved by [`EnvironmentMapSaver`], with their diffuse and specular
+/// maps as the [`EQUIRECTANGULAR_DIFFUSE_MAP_LABEL`] and [`EQUIRECTANGULAR_SPECULAR_MAP_LABEL`]
+/// labeled assets.
+#[derive(Clone, Default)]
+pub struct EnvironmentMapLoader;
+
+impl AssetLoader for EnvironmentMapLoader {
+    type Asset = Image;
+    type Settings = ();
+    type Error = EnvironmentMapLoaderError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Image, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+
+        let Some(rest) = bytes.strip_prefix(ENVIRONMENT_MAP_MAGIC) else {
+            return Err(EnvironmentMapLoaderError::InvalidMagic);
+        };
+        let mut rest = rest;
+        let version = read_u32(&mut rest).ok_or(EnvironmentMapLoaderError::InvalidMagic)?;
+        if version != ENVIRONMENT_MAP_VERSION {
+            return Err(EnvironmentMapLoaderError::UnsupportedVersion(version));
+        }
+
+        let mut read_map = |name| {
+            let (Some(face_size), Some(mips)) = (read_u32(&mut rest), read_u32(&mut rest)) else {
+                return Err(EnvironmentMapLoaderError::InvalidMap(name));
+            };
+            if face_size == 0 || mips == 0 || mips > mip_level_count(face_size) {
+                return Err(EnvironmentMapLoaderError::InvalidMap(name));
+            }
+            let len = cubemap_data_len(face_size, mips);
+            if rest.len() < len {
+                return Err(EnvironmentMapLoaderError::InvalidMap(name));
+            }
+            let (data, remaining) = rest.split_at(len);
+            rest = remaining;
+            Ok(cubemap_image(face_size, mips, data.to_vec()))
+        };
+        let skybox = read_map("skybox")?;
+        let diffuse_map = read_map("diffuse")?;
+        let specular_map = read_map("specular")?;
+
+        load_context.add_labeled_asset(EQUIRECTANGULAR_DIFFUSE_MAP_LABEL.into(), diffuse_map);
+        load_context.add_labeled_asset(EQUIRECTANGULAR_SPECULAR_MAP_LABEL.into(), specular_map);
+        Ok(skybox)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["envmap"]
+    }
+}
+
+/// Reads a little endian `u32` from the start of `bytes`, and advances past it.
+fn read_u32(bytes: &mut &[u8]) -> Option<u32> {
+    let (value, rest) = bytes.split_first_chunk()?;
+    *bytes = rest;
+    Some(u32::from_le_bytes(*value))
+}
+
+/// The length of the data of an `Rgba16Float` cubemap with faces of `face_size` pixels.
+fn cubemap_data_len(face_size: u32, mip_level_count: u32) -> usize {
+    (0..mip_level_count)
+        .map(|mip| {
+            let size = (face_size >> mip).max(1) as usize;
+            6 * size * size * 8
+        })
+        .sum()
+}
+
+/// Creates an `Rgba16Float` cubemap [`Image`] from `data`, laid out face by face.
+fn cubemap_image(face_size: u32, mip_level_count: u32, data: Vec<u8>) -> Image {
+    let mut image = Image::new_uninit(
+        Extent3d {
+            width: face_size,
+            height: face_size,
+            depth_or_array_layers: 6,
+        },
+        TextureDimension::D2,
+        TextureFormat::Rgba16Float,
+        RenderAssetUsages::default(),
+    );
+    image.data = Some(data);
+    image.texture_descriptor.mip_level_count = mip_level_count;
+    image.texture_view_descriptor = Some(TextureViewDescriptor {
+        dimension: Some(TextureViewDimension::Cube),
+        ..Default::default()
+    });
+    image.sampler = ImageSampler::linear();
+    image
+}
+
+/// Returns the direction, in cubemap space, through the point `uv` of the face `face`, where
+/// `uv` goes from -1 to 1, left to right and top to bottom.
+///
+/// The faces are in the order +X, -X, +Y, -Y, +Z, -Z. This must match
+/// `equirectangular.wgsl` in `bevy_pbr`.
+fn face_direction(face: usize, uv: Vec2) -> Vec3 {
+    let Vec2 { x: u, y: v } = uv;
+    let direction = match face {
+        0 => Vec3::new(1.0, -v, -u),
+        1 => Vec3::new(-1.0, -v, u),
+        2 => Vec3::new(u, 1.0, v),
+        3 => Vec3::new(u, -1.0, -v),
+        4 => Vec3::new(u, -v, 1.0),
+        _ => Vec3::new(-u, -v, -1.0),
+    };
+    direction.normalize()
+}
+
+/// The inverse of [`face_direction`].
+fn direction_face(direction: Vec3) -> (usize, Vec2) {
+    let abs = direction.abs();
+    let (face, u, v, major) = if abs.x >= abs.y && abs.x >= abs.z {
+        if direction.x > 0.0 {
+            (0, -direction.z, -direction.y, abs.x)
+        } else {
+            (1, direction.z, -direction.y, abs.x)
+        }
+    } else if abs.y >= abs.z {
+        if direction.y > 0.0 {
+            (2, direction.x, direction.z, abs.y)
+        } else {
+            (3, direction.x, -direction.z, abs.y)
+        }
+    } else if direction.z > 0.0 {
+        (4, direction.x, -direction.y, abs.z)
+    } else {
+        (5, -direction.x, -direction.y, abs.z)
+    };
+    (face, Vec2::new(u, v) / major)
+}
+
+/// Returns an orthonormal basis around `normal` that maps the tangent space `sample` into it.
+fn tangent_to_world(normal: Vec3, sample: Vec3) -> Vec3 {
+    let up = if normal.z.abs() < 0.999 {
+        Vec3::Z
+    } else {
+        Vec3::X
+    };
+    let tangent = up.cross(normal).normalize();
+    let bitangent = normal.cross(tangent);
+    tangent * sample.x + bitangent * sample.y + normal * sample.z
+}
+
+/// The `i`th of `n` points of the Hammersley sequence.
+fn hammersley(i: u32, n: u32) -> Vec2 {
+    Vec2::new(
+        i as f32 / n as f32,
+        i.reverse_bits() as f32 / 4_294_967_296.0,
+    )
+}
+
+/// The mip level to sample a cubemap with faces of `face_size` pixels at, for a sample that
+/// covers the solid angle `1 / (sample_count * pdf)`.
+fn sample_mip_level(pdf: f32, sample_count: u32, face_size: u32) -> f32 {
+    let sample_solid_angle = 1.0 / (sample_count as f32 * pdf + 1.0e-4);
+    let texel_solid_angle = 4.0 * PI / (6.0 * (face_size * face_size) as f32);
+    0.5 * ops::log2(sample_solid_angle / texel_solid_angle) + 1.0
+}
+
+/// An equirectangular panorama in linear color.
+struct Panorama {
+    width: u32,
+    height: u32,
+    texels: Vec<Vec3>,
+}
+
+impl Panorama {
+    fn from_image(image: &Image) -> Result<Self, EquirectangularToCubemapError> {
+        let size = image.texture_descriptor.size;
+        if image.texture_descriptor.dimension != TextureDimension::D2
+            || size.depth_or_array_layers != 1
+        {
+            return Err(EquirectangularToCubemapError::WrongDimension);
+        }
+        if image.data.is_none() {
+            return Err(EquirectangularToCubemapError::MissingData);
+        }
+
+        let mut texels = Vec::with_capacity((size.width * size.height) as usize);
+        for y in 0..size.height {
+            for x in 0..size.width {
+                let color = LinearRgba::from(image.get_color_at(x, y)?);
+                texels.push(Vec3::new(color.red, color.green, color.blue));
+            }
+        }
+
+        Ok(Self {
+            width: size.width,
+            height: size.height,
+            texels,
+        })
+    }
+
+    /// Samples the panorama in the world space `direction`, with bilinear filtering.
+    fn sample(&self, direction: Vec3) -> Vec3 {
+        let u = 0.5 + ops::atan2(direction.x, -direction.z) / TAU;
+        let v = ops::acos(direction.y.clamp(-1.0, 1.0)) / PI;
+
+        let x = u * self.width as f32 - 0.5;
+        let y = (v * self.height as f32 - 0.5).clamp(0.0, (self.height - 1) as f32);
+        let (x0, y0) = (x.floor(), y.floor());
+        let (fx, fy) = (x - x0, y - y0);
+
+        // The panorama wraps around horizontally.
+        let texel = |x: f32, y: f32| {
+            let x = (x as i32).rem_euclid(self.width as i32) as u32;
+            let y = (y as u32).min(self.height - 1);
+            self.texels[(y * self.width + x) as usize]
+        };
+        let top = texel(x0, y0).lerp(texel(x0 + 1.0, y0), fx);
+        let bottom = texel(x0, y0 + 1.0).lerp(texel(x0 + 1.0, y0 + 1.0), fx);
+        top.lerp(bottom, fy)
+    }
+}
+
+/// A cubemap in linear color, with the texels of each mip level in face, row, column order.
+struct Cubemap {
+    face_size: u32,
+    mips: Vec<Vec<Vec3>>,
+}
+
+impl Cubemap {
+    /// Creates a cubemap by calling `f` with the mip level and the cubemap space direction of
+    /// each texel.
+    fn from_fn(face_size: u32, mip_level_count: u32, mut f: impl FnMut(u32, Vec3) -> Vec3) -> Self {
+        let mips = (0..mip_level_count)
+            .map(|mip| {
+                let size = (face_size >> mip).max(1);
+                let mut texels = Vec::with_capacity((6 * size * size) as usize);
+                for face in 0..6 {
+                    for y in 0..size {
+                        for x in 0..size {
+                            let uv =
+                                (Vec2::new(x as f32, y as f32) + 0.5) / size as f32 * 2.0 - 1.0;
+                            texels.push(f(mip, face_direction(face, uv)));
+                        }
+                    }
+                }
+                texels
+            })
+            .collect();
+        Self { face_size, mips }
+    }
+
+    /// Projects the panorama onto a cubemap, and downsamples it into a full mip chain.
+    fn from_panorama(
+        panorama: &Panorama,
+        face_size: u32,
+    ) -> Result<Self, EquirectangularToCubemapError> {
+        if face_size == 0 {
+            return Err(EquirectangularToCubemapError::ZeroFaceSize);
+        }
+
+        let mut cubemap = Self::from_fn(face_size, 1, |_, direction| {
+            // Cubemaps are sampled with Z flipped.
+            panorama.sample(direction * Vec3::new(1.0, 1.0, -1.0))
+        });
+
+        for mip in 1..mip_level_count(face_size) {
+            let previous_size = face_size >> (mip - 1);
+            let size = face_size >> mip;
+            let previous = &cubemap.mips[mip as usize - 1];
+            let mut texels = Vec::with_capacity((6 * size * size) as usize);
+            for face in 0..6 {
+                for y in 0..size {
+                    for x in 0..size {
+                        let texel = |dx, dy| {
+                            let index =
+                                (face * previous_size + y * 2 + dy) * previous_size + x * 2 + dx;
+                            previous[index as usize]
+                        };
+                        texels.push((texel(0, 0) + texel(1, 0) + texel(0, 1) + texel(1, 1)) / 4.0);
+                    }
+                }
+            }
+            cubemap.mips.push(texels);
+        }
+
+        Ok(cubemap)
+    }
+
+    /// Samples the cubemap in the cubemap space `direction` at `mip`, with bilinear filtering
+    /// within the face.
+    fn sample_mip(&self, direction: Vec3, mip: usize) -> Vec3 {
+        let size = (self.face_size >> mip).max(1);
+        let (face, uv) = direction_face(direction);
+        let position = ((uv + 1.0) * 0.5 * size as f32 - 0.5)
+            .clamp(Vec2::ZERO, Vec2::splat((size - 1) as f32));
+        let (x0, y0) = (position.x.floor() as u32, position.y.floor() as u32);
+        let (x1, y1) = ((x0 + 1).min(size - 1), (y0 + 1).min(size - 1));
+        let fraction = position - position.floor();
+
+        let texels = &self.mips[mip];
+        let texel = |x: u32, y: u32| texels[((face as u32 * size + y) * size + x) as usize];
+        let top = texel(x0, y0).lerp(texel(x1, y0), fraction.x);
+        let bottom = texel(x0, y1).lerp(texel(x1, y1), fraction.x);
+        top.lerp(bottom, fraction.y)
+    }
+
+    /// Samples the cubemap in the cubemap space `direction` with trilinear filtering.
+    fn sample(&self, direction: Vec3, mip: f32) -> Vec3 {
+        let mip = mip.clamp(0.0, (self.mips.len() - 1) as f32);
+        let (mip0, fraction) = (mip.floor() as usize, mip.fract());
+        let mip1 = (mip0 + 1).min(self.mips.len() - 1);
+        self.sample_mip(direction, mip0)
+            .lerp(self.sample_mip(direction, mip1), fraction)
+    }
+
+    /// The cosine weighted average of the radiance around `normal`, for a diffuse map with faces
+    /// of `face_size` pixels.
+    fn filter_diffuse(&self, normal: Vec3, face_size: u32, sample_count: u32) -> Vec3 {
+        let min_mip = ops::log2(self.face_size as f32 / face_size as f32).max(0.0);
+        let mut sum = Vec3::ZERO;
+        for i in 0..sample_count {
+            let xi = hammersley(i, sample_count);
+            let (sin_phi, cos_phi) = ops::sin_cos(TAU * xi.x);
+            let cos_theta = (1.0 - xi.y).sqrt();
+            let sin_theta = xi.y.sqrt();
+            let light = tangent_to_world(
+                normal,
+                Vec3::new(sin_theta * cos_phi, sin_theta * sin_phi, cos_theta),
+            );
+            let pdf = cos_theta / PI;
+            let mip = sample_mip_level(pdf, sample_count, self.face_size).max(min_mip);
+            sum += self.sample(light, mip);
+        }
+        sum / sample_count as f32
+    }
+
+    /// The GGX filtered radiance around `normal`, for the mip level of a specular map with faces of
+    /// `face_size` pixels.
+    fn filter_specular(
+        &self,
+        normal: Vec3,
+        perceptual_roughness: f32,
+        face_size: u32,
+        sample_count: u32,
+    ) -> Vec3 {
+        let min_mip = ops::log2(self.face_size as f32 / face_size.max(1) as f32).max(0.0);
+        if perceptual_roughness == 0.0 {
+            return self.sample(normal, min_mip);
+        }
+
+        let alpha = perceptual_roughness * perceptual_roughness;
+        let alpha_squared = alpha * alpha;
+        let mut sum = Vec3::ZERO;
+        let mut weight = 0.0;
+        for i in 0..sample_count {
+            let xi = hammersley(i, sample_count);
+            let (sin_phi, cos_phi) = ops::sin_cos(TAU * xi.x);
+            let cos_theta = ((1.0 - xi.y) / (1.0 + (alpha_squared - 1.0) * xi.y)).sqrt();
+            let sin_theta = (1.0 - cos_theta * cos_theta).sqrt();
+            let half_vector = tangent_to_world(
+                normal,
+                Vec3::new(sin_theta * cos_phi, sin_theta * sin_phi, cos_theta),
+            );
+
+            // The view direction is the normal.
+            let n_dot_h = cos_theta;
+            let light = 2.0 * n_dot_h * half_vector - normal;
+            let n_dot_l = normal.dot(light);
+            if n_dot_l <= 0.0 {
+                continue;
+            }
+
+            let d = n_dot_h * n_dot_h * (alpha_squared - 1.0) + 1.0;
+            let pdf = alpha_squared / (PI * d * d) / 4.0;
+            let mip = sample_mip_level(pdf, sample_count, self.face_size).max(min_mip);
+            sum += self.sample(light, mip) * n_dot_l;
+            weight += n_dot_l;
+        }
+
+        if weight > 0.0 {
+            sum / weight
+        } else {
+            self.sample(normal, min_mip)
+        }
+    }
+
+    /// Converts the first `mip_level_count` mip levels into a cubemap [`Image`].
+    fn to_image(&self, mip_level_count: u32) -> Image {
+        let mut data = Vec::new();
+        for face in 0..6 {
+            for (mip, texels) in self.mips.iter().take(mip_level_count as usize).enumerate() {
+                let size =
+                    ((self.face_size >> mip).max(1) * (self.face_size >> mip).max(1)) as usize;
+                for texel in &texels[face * size..(face + 1) * size] {
+                    for channel in [texel.x, texel.y, texel.z, 1.0] {
+                        data.extend_from_slice(&half::f16::from_f32(channel).to_le_bytes());
+                    }
+                }
+            }
+        }
+
+        cubemap_image(self.face_size, mip_level_count, data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy_color::{Color, ColorToComponents};
+
+    fn panorama(width: u32, height: u32, color: impl Fn(u32, u32) -> LinearRgba) -> Image {
+        let mut image = Image::new_fill(
+            Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            &[0; 16],
+            TextureFormat::Rgba32Float,
+            RenderAssetUsages::default(),
+        );
+        for y in 0..height {
+            for x in 0..width {
+                image.set_color_at(x, y, Color::from(color(x, y))).unwrap();
+            }
+        }
+        image
+    }
+
+    /// Reads a texel of the first mip level of a cubemap face.
+    fn texel(image: &Image, face: u32, x: u32, y: u32) -> Vec3 {
+        let size = image.width();
+        let face_len: u32 = (0..image.texture_descriptor.mip_level_count)
+            .map(|mip| (size >> mip) * (size >> mip) * 8)
+            .sum();
+        let start = (face * face_len + (y * size + x) * 8) as usize;
+        let data = &image.data.as_ref().unwrap()[start..start + 6];
+        Vec3::from_array(core::array::from_fn(|channel| {
+            half::f16::from_le_bytes([data[channel * 2], data[channel * 2 + 1]]).to_f32()
+        }))
+    }
+
+    #[test]
+    fn face_direction_roundtrip() {
+        for face in 0..6 {
+            let uv = Vec2::new(0.25, -0.5);
+            let (roundtrip_face, roundtrip_uv) = direction_face(face_direction(face, uv));
+            assert_eq!(face, roundtrip_face);
+            assert!(uv.abs_diff_eq(roundtrip_uv, 1.0e-5));
+        }
+    }
+
+    #[test]
+    fn constant_panorama_stays_constant() {
+        let color = LinearRgba::rgb(2.0, 0.5, 0.25);
+        let image = panorama(16, 8, |_, _| color);
+        let settings = EquirectangularToCubemapSettings {
+            face_size: 8,
+            diffuse_face_size: 2,
+            specular_face_size: 4,
+            sample_count: 16,
+        };
+        let images = generate_environment_map(&image, &settings).unwrap();
+
+        assert_eq!(images.skybox.texture_descriptor.mip_level_count, 4);
+        assert_eq!(images.specular_map.texture_descriptor.mip_level_count, 3);
+        for image in [&images.skybox, &images.diffuse_map, &images.specular_map] {
+            assert_eq!(image.texture_descriptor.size.depth_or_array_layers, 6);
+            for face in 0..6 {
+                let texel = texel(image, face, 1, 1);
+                assert!(
+                    texel.abs_diff_eq(Vec3::from_array(color.to_f32_array_no_alpha()), 1.0e-2),
+                    "{texel:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn panorama_center_faces_forward() {
+        // The top half of the panorama is red and the bottom half blue, and its center columns
+        // are green.
+        let image = panorama(16, 8, |x, y| match (x, y) {
+            (6..=9, _) => LinearRgba::GREEN,
+            (_, 0..4) => LinearRgba::RED,
+            _ => LinearRgba::BLUE,
+        });
+        let skybox = equirectangular_to_cubemap(&image, 4).unwrap();
+
+        // Forward, -Z in world space, is +Z in cubemap space.
+        assert_eq!(texel(&skybox, 4, 1, 1), Vec3::Y);
+        assert_eq!(texel(&skybox, 4, 2, 2), Vec3::Y);
+        assert_eq!(texel(&skybox, 2, 0, 0), Vec3::X);
+        assert_eq!(texel(&skybox, 3, 3, 3), Vec3::Z);
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        let image = panorama(4, 2, |_, _| LinearRgba::WHITE);
+        assert!(matches!(
+            equirectangular_to_cubemap(&image, 0),
+            Err(EquirectangularToCubemapError::ZeroFaceSize)
+        ));
+
+        let mut image = image;
+        image.data = None;
+        assert!(matches!(
+            equirectangular_to_cubemap(&image, 4),
+            Err(EquirectangularToCubemapError::MissingData)
+        ));
+    }
+
+    #[test]
+    fn processed_environment_map_loads_back() {
+        use bevy_app::{App, TaskPoolPlugin};
+        use bevy_asset::{
+            io::{
+                memory::{Dir, MemoryAssetReader},
+                AssetSource, AssetSourceId,
+            },
+            AssetApp, AssetPlugin, AssetServer, Assets, LoadState,
+        };
+        use futures_lite::future::block_on;
+        use std::path::Path;
+
+        let settings = EquirectangularToCubemapSettings {
+            face_size: 8,
+            diffuse_face_size: 2,
+            specular_face_size: 4,
+            sample_count: 4,
+        };
+        let image = panorama(16, 8, |x, _| LinearRgba::rgb(x as f32, 1.0, 0.5));
+        let expected = generate_environment_map(&image, &settings).unwrap();
+
+        // Process the panorama as `LoadTransformAndSave` does.
+        let transformed = block_on(
+            EquirectangularToCubemapTransformer.transform(TransformedAsset::from(image), &settings),
+        )
+        .unwrap();
+        let mut bytes = Vec::new();
+        block_on(EnvironmentMapSaver.save(
+            &mut bytes,
+            SavedAsset::from_transformed(&transformed),
+            &(),
+        ))
+        .unwrap();
+
+        let dir = Dir::default();
+        dir.insert_asset(Path::new("sky.envmap"), bytes);
+        let mut app = App::new();
+        app.register_asset_source(
+            AssetSourceId::Default,
+            AssetSource::build()
+                .with_reader(move || Box::new(MemoryAssetReader { root: dir.clone() })),
+        )
+        .add_plugins((TaskPoolPlugin::default(), AssetPlugin::default()))
+        .init_asset::<Image>()
+        .register_asset_loader(EnvironmentMapLoader);
+
+        let server = app.world().resource::<AssetServer>().clone();
+        let skybox = server.load::<Image>("sky.envmap");
+        for _ in 0..100 {
+            app.update();
+            if server.is_loaded_with_dependencies(&skybox) {
+                break;
+            }
+            assert!(!matches!(server.load_state(&skybox), LoadState::Failed(_)));
+        }
+        let diffuse_map =
+            server.load::<Image>(format!("sky.envmap#{EQUIRECTANGULAR_DIFFUSE_MAP_LABEL}"));
+        let specular_map =
+            server.load::<Image>(format!("sky.envmap#{EQUIRECTANGULAR_SPECULAR_MAP_LABEL}"));
+        app.update();
+
+        let images = app.world().resource::<Assets<Image>>();
+        for (handle, expected) in [
+            (&skybox, &expected.skybox),
+            (&diffuse_map, &expected.diffuse_map),
+            (&specular_map, &expected.specular_map),
+        ] {
+            let image = images.get(handle).expect("environment map wasn't loaded");
+            assert_eq!(image.texture_descriptor, expected.texture_descriptor);
+            assert_eq!(
+                image.texture_view_descriptor,
+                expected.texture_view_descriptor
+            );
+            assert_eq!(image.data, expected.data);
+        }
+    }
+
+    #[test]
+    fn environment_map_saver_writes_every_map() {
+        let settings = EquirectangularToCubemapSettings {
+            face_size: 4,
+            diffuse_face_size: 1,
+            specular_face_size: 2,
+            sample_count: 1,
+        };
+        let transformed =
+            futures_lite::future::block_on(EquirectangularToCubemapTransformer.transform(
+                TransformedAsset::from(panorama(8, 4, |_, _| LinearRgba::WHITE)),
+                &settings,
+            ))
+            .unwrap();
+        let mut bytes = Vec::new();
+        futures_lite::future::block_on(EnvironmentMapSaver.save(
+            &mut bytes,
+            SavedAsset::from_transformed(&transformed),
+            &(),
+        ))
+        .unwrap();
+
+        // The saved maps must exactly fill the file.
+        assert_eq!(
+            bytes.len(),
+            ENVIRONMENT_MAP_MAGIC.len()
+                + 4
+                + [(4, 3), (1, 1), (2, 2)]
+                    .into_iter()
+                    .map(|(size, mips)| 8 + cubemap_data_len(size, mips))
+                    .sum::<usize>()
+        );
+
+        // Saving fails without the labeled maps.
+        let mut unlabeled = Vec::new();
+        let skybox = TransformedAsset::from(transformed.get().clone());
+        assert!(matches!(
+            futures_lite::future::block_on(EnvironmentMapSaver.save(
+                &mut unlabeled,
+                SavedAsset::from_transformed(&skybox),
+                &(),
+            )),
+            Err(EnvironmentMapSaverError::MissingMap("diffuse"))
+        ));
+    }
+}
@@ -16,9 +16,11 @@ pub use self::image::*;
 mod basis;
 #[cfg(feature = "basis-universal")]
 mod compressed_image_saver;
+mod cube_lut_loader;
 #[cfg(feature = "dds")]
 mod dds;
 mod dynamic_texture_atlas_builder;
+mod equirectangular;
 #[cfg(feature = "exr")]
 mod exr_texture_loader;
 #[cfg(feature = "hdr")]
@@ -31,9 +33,11 @@ mod texture_atlas_builder;
 
 #[cfg(feature = "basis-universal")]
 pub use compressed_image_saver::*;
+pub use cube_lut_loader::*;
 #[cfg(feature = "dds")]
 pub use dds::*;
 pub use dynamic_texture_atlas_builder::*;
+pub use equirectangular::*;
 #[cfg(feature = "exr")]
 pub use exr_texture_loader::*;
 #[cfg(feature = "hdr")]
@@ -0,0 +1,40 @@
+#import bevy_pbr::atmosphere::{
+    bindings::{settings, lights},
+    clouds::{clouds, sample_cloud_density},
+}
+
+@group(1) @binding(6) var cloud_shadow_map_out: texture_storage_2d<rgba16float, write>;
+
+// Each texel stores the opacity of the cloud layer along the direction to the
+// first directional light, starting from the bottom of the layer.
+@compute
+@workgroup_size(16, 16, 1)
+fn main(@builtin(global_invocation_id) idx: vec3<u32>) {
+    let size = textureDimensions(cloud_shadow_map_out);
+    if any(idx.xy >= size) { return; }
+
+    let direction_to_light = lights.directional_lights[0].direction_to_light;
+    if lights.n_directional_lights == 0u || direction_to_light.y <= 0.0 {
+        textureStore(cloud_shadow_map_out, idx.xy, vec4(0.0));
+        return;
+    }
+
+    let uv = (vec2<f32>(idx.xy) + 0.5) / vec2<f32>(size);
+    let params = clouds.shadow_map_params;
+    let position_xz = (params.xy + (uv - 0.5) / params.z) * settings.scene_units_to_m;
+
+    let thickness = clouds.top_altitude - clouds.bottom_altitude;
+    let steps = clouds.light_steps * 2u;
+    let dt = min(thickness / direction_to_light.y, 4.0 * thickness) / f32(steps);
+
+    var optical_depth = 0.0;
+    for (var step_i: u32 = 0u; step_i < steps; step_i++) {
+        let t = (f32(step_i) + 0.5) * dt;
+        optical_depth += sample_cloud_density(
+            position_xz + direction_to_light.xz * t,
+            clouds.bottom_altitude + direction_to_light.y * t
+        ) * dt;
+    }
+
+    textureStore(cloud_shadow_map_out, idx.xy, vec4(0.0, 0.0, 0.0, 1.0 - exp(-optical_depth)));
+}
@@ -0,0 +1,947 @@
+//! Ray-marched volumetric clouds.
+//!
+//! Clouds are rendered as a layer of participating media between two altitudes
+//! of the [`Atmosphere`]. Each frame, a half-resolution compute pass marches
+//! view rays through the layer, lighting each sample with the directional
+//! lights attenuated by the transmittance LUT, and adding ambient light from
+//! the multiscattering LUT. The result is temporally reprojected and blended
+//! with the previous frame's result to hide the noise from the low sample
+//! count, and is then composited in the render sky pass.
+//!
+//! A second compute pass renders a top-down cloud shadow map for the first
+//! directional light, which the PBR shaders sample so that the clouds cast
+//! shadows onto the scene.
+
+use bevy_asset::Handle;
+use bevy_core_pipeline::core_3d::Camera3d;
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    query::{Or, QueryItem, With, Without},
+    reflect::ReflectComponent,
+    resource::Resource,
+    system::{lifetimeless::Read, Commands, Query, Res, ResMut},
+    world::{FromWorld, World},
+};
+use bevy_image::Image;
+use bevy_math::{Mat4, UVec2, Vec2, Vec3, Vec3Swizzles, Vec4, Vec4Swizzles};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    extract_component::{ComponentUniforms, ExtractComponent},
+    globals::{GlobalsBuffer, GlobalsUniform},
+    render_asset::RenderAssets,
+    render_resource::{binding_types::*, *},
+    renderer::{RenderDevice, RenderQueue},
+    texture::{CachedTexture, FallbackImageZero, GpuImage, TextureCache},
+    view::{ExtractedView, ViewUniform, ViewUniforms},
+};
+
+use crate::{GpuLights, LightMeta};
+
+use super::{
+    resources::{AtmosphereSamplers, AtmosphereTextures, RenderSkyBindGroupLayouts},
+    shaders, Atmosphere, AtmosphereSettings,
+};
+
+/// Add this component to a camera with an [`Atmosphere`] to render a layer of
+/// ray-marched volumetric clouds.
+///
+/// The clouds are lit by every directional light in the scene, and the first
+/// directional light also casts cloud shadows onto the scene. Like the
+/// atmosphere itself, clouds assume that the world is locally flat around the
+/// camera, and that the ground is at y=0.
+///
+/// Cloud shapes are made by eroding a low-frequency noise by a high-frequency
+/// one, masked by [`coverage`](Self::coverage) and, optionally, a
+/// [`weather_map`](Self::weather_map).
+///
+/// The camera is assumed to be below the cloud layer. Clouds are composited
+/// over opaque geometry only when the geometry is farther away than the bottom
+/// of the cloud layer.
+///
+/// Because the ray march uses few samples and relies on temporal accumulation
+/// to converge, fast camera motion may show some ghosting.
+#[derive(Clone, Component, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub struct VolumetricClouds {
+    /// The altitude at which the cloud layer begins.
+    ///
+    /// units: m
+    pub bottom_altitude: f32,
+
+    /// The altitude at which the cloud layer ends.
+    ///
+    /// units: m
+    pub top_altitude: f32,
+
+    /// How much of the sky is covered by clouds.
+    ///
+    /// domain: [0, 1]
+    /// units: N/A
+    pub coverage: f32,
+
+    /// The extinction coefficient of the thickest part of a cloud.
+    ///
+    /// units: m^-1
+    pub density: f32,
+
+    /// An optional texture whose red channel scales the cloud coverage. It is
+    /// tiled horizontally over the world every
+    /// [`weather_map_size`](Self::weather_map_size) meters.
+    ///
+    /// Use this to art-direct where clouds appear. Without a weather map,
+    /// coverage is uniform.
+    pub weather_map: Option<Handle<Image>>,
+
+    /// The horizontal size of a single tile of the weather map.
+    ///
+    /// units: m
+    pub weather_map_size: f32,
+
+    /// The approximate size of the large features of the clouds.
+    ///
+    /// units: m
+    pub shape_noise_size: f32,
+
+    /// The approximate size of the small features eroded from the edges of
+    /// the clouds.
+    ///
+    /// units: m
+    pub detail_noise_size: f32,
+
+    /// How strongly the detail noise erodes the cloud shapes.
+    ///
+    /// domain: [0, 1]
+    /// units: N/A
+    pub detail_strength: f32,
+
+    /// The horizontal velocity at which the clouds drift, along the world x
+    /// and z axes.
+    ///
+    /// units: m/s
+    pub wind: Vec2,
+
+    /// The number of samples taken along each view ray through the cloud
+    /// layer.
+    pub steps: u32,
+
+    /// The number of samples taken towards each light, at each view ray
+    /// sample, to compute the self-shadowing of the clouds.
+    pub light_steps: u32,
+
+    /// The maximum distance along a view ray that the cloud layer is
+    /// marched.
+    ///
+    /// units: m
+    pub max_distance: f32,
+
+    /// How much of the previous frame's result is kept each frame. Higher
+    /// values reduce noise at the cost of more ghosting.
+    ///
+    /// domain: [0, 1)
+    /// units: N/A
+    pub temporal_blend: f32,
+
+    /// The resolution of the cloud shadow map, in texels along each side.
+    pub shadow_map_size: u32,
+
+    /// The horizontal size of the area around the camera covered by the cloud
+    /// shadow map. Outside of this area clouds don't cast shadows.
+    ///
+    /// units: m
+    pub shadow_map_extent: f32,
+}
+
+impl Default for VolumetricClouds {
+    fn default() -> Self {
+        Self {
+            bottom_altitude: 1_500.0,
+            top_altitude: 4_000.0,
+            coverage: 0.5,
+            density: 0.02,
+            weather_map: None,
+            weather_map_size: 40_000.0,
+            shape_noise_size: 6_000.0,
+            detail_noise_size: 600.0,
+            detail_strength: 0.35,
+            wind: Vec2::new(10.0, 0.0),
+            steps: 64,
+            light_steps: 6,
+            max_distance: 40_000.0,
+            temporal_blend: 0.9,
+            shadow_map_size: 512,
+            shadow_map_extent: 20_000.0,
+        }
+    }
+}
+
+impl VolumetricClouds {
+    /// Returns the parameters used to sample the cloud shadow map: the world
+    /// space x and z of its center, the reciprocal of its extent in world
+    /// units, and the world space height of the bottom of the cloud layer.
+    ///
+    /// The center is snapped to the shadow map texel grid so that shadows
+    /// don't shimmer as the camera moves.
+    pub(crate) fn shadow_map_params(&self, view_translation: Vec3, scene_units_to_m: f32) -> Vec4 {
+        let extent = self.shadow_map_extent / scene_units_to_m;
+        let texel_size = extent / self.shadow_map_size.max(1) as f32;
+        let center = (view_translation.xz() / texel_size).floor() * texel_size;
+        Vec4::new(
+            center.x,
+            center.y,
+            1.0 / extent,
+            self.bottom_altitude / scene_units_to_m,
+        )
+    }
+}
+
+impl ExtractComponent for VolumetricClouds {
+    type QueryData = Read<VolumetricClouds>;
+
+    type QueryFilter = (With<Camera3d>, With<Atmosphere>);
+
+    type Out = VolumetricClouds;
+
+    fn extract_component(item: QueryItem<'_, Self::QueryData>) -> Option<Self::Out> {
+        Some(item.clone())
+    }
+}
+
+/// The GPU representation of [`VolumetricClouds`], along with the per-view
+/// state needed for temporal reprojection.
+#[derive(ShaderType)]
+pub struct GpuVolumetricClouds {
+    previous_clip_from_world: Mat4,
+    shadow_map_params: Vec4,
+    wind: Vec2,
+    bottom_altitude: f32,
+    top_altitude: f32,
+    coverage: f32,
+    density: f32,
+    // zero if there's no weather map
+    inv_weather_map_size: f32,
+    inv_shape_noise_size: f32,
+    inv_detail_noise_size: f32,
+    detail_strength: f32,
+    max_distance: f32,
+    // zero if there's no valid history
+    temporal_blend: f32,
+    steps: u32,
+    light_steps: u32,
+}
+
+impl GpuVolumetricClouds {
+    /// Packs `clouds` for a view with the given transforms.
+    ///
+    /// Without `history`, the clouds are reprojected onto the current view and
+    /// nothing is blended with.
+    fn new(
+        clouds: &VolumetricClouds,
+        scene_units_to_m: f32,
+        view_translation: Vec3,
+        clip_from_world: Mat4,
+        history: Option<&VolumetricCloudsHistory>,
+        has_weather_map: bool,
+    ) -> Self {
+        Self {
+            previous_clip_from_world: history
+                .map_or(clip_from_world, |history| history.previous_clip_from_world),
+            shadow_map_params: clouds.shadow_map_params(view_translation, scene_units_to_m),
+            wind: clouds.wind,
+            bottom_altitude: clouds.bottom_altitude,
+            top_altitude: clouds.top_altitude.max(clouds.bottom_altitude + 1.0),
+            coverage: clouds.coverage,
+            density: clouds.density,
+            inv_weather_map_size: if has_weather_map {
+                1.0 / clouds.weather_map_size
+            } else {
+                0.0
+            },
+            inv_shape_noise_size: 1.0 / clouds.shape_noise_size,
+            inv_detail_noise_size: 1.0 / clouds.detail_noise_size,
+            detail_strength: clouds.detail_strength,
+            max_distance: clouds.max_distance,
+            temporal_blend: if history.is_some() {
+                clouds.temporal_blend.clamp(0.0, 0.99)
+            } else {
+                0.0
+            },
+            steps: clouds.steps.max(1),
+            light_steps: clouds.light_steps.max(1),
+        }
+    }
+}
+
+/// Per-view state carried from one frame to the next.
+#[derive(Component)]
+pub(super) struct VolumetricCloudsHistory {
+    previous_clip_from_world: Mat4,
+    frame: u32,
+}
+
+impl VolumetricCloudsHistory {
+    fn new(clip_from_world: Mat4) -> Self {
+        Self {
+            previous_clip_from_world: clip_from_world,
+            frame: 1,
+        }
+    }
+
+    /// Returns the index of the history texture that this frame writes to.
+    fn current(&self) -> usize {
+        self.frame as usize % 2
+    }
+
+    /// Records the view of this frame, to reproject from in the next one, and
+    /// swaps the history textures.
+    fn advance(&mut self, clip_from_world: Mat4) {
+        self.previous_clip_from_world = clip_from_world;
+        self.frame = self.frame.wrapping_add(1);
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct VolumetricCloudsUniforms {
+    uniforms: DynamicUniformBuffer<GpuVolumetricClouds>,
+}
+
+impl VolumetricCloudsUniforms {
+    #[inline]
+    pub fn uniforms(&self) -> &DynamicUniformBuffer<GpuVolumetricClouds> {
+        &self.uniforms
+    }
+}
+
+#[derive(Component)]
+pub struct VolumetricCloudsUniformOffset {
+    index: u32,
+}
+
+impl VolumetricCloudsUniformOffset {
+    #[inline]
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+}
+
+#[derive(Component)]
+pub struct VolumetricCloudsTextures {
+    /// Ping-pong textures holding the accumulated clouds of this frame and
+    /// the previous one. RGB is the inscattered light and A is the opacity.
+    pub history: [CachedTexture; 2],
+    /// The index into `history` written this frame.
+    pub current: usize,
+    /// The opacity of the clouds along the direction to the first
+    /// directional light, in the A channel.
+    pub shadow_map: CachedTexture,
+    pub shadow_map_sampler: Sampler,
+}
+
+impl VolumetricCloudsTextures {
+    #[inline]
+    pub fn current(&self) -> &CachedTexture {
+        &self.history[self.current]
+    }
+
+    #[inline]
+    pub fn previous(&self) -> &CachedTexture {
+        &self.history[1 - self.current]
+    }
+}
+
+#[derive(Resource)]
+pub(crate) struct VolumetricCloudsBindGroupLayouts {
+    pub atmosphere: BindGroupLayout,
+    pub shadow_map: BindGroupLayout,
+    pub ray_march: BindGroupLayout,
+}
+
+impl FromWorld for VolumetricCloudsBindGroupLayouts {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let atmosphere = render_device.create_bind_group_layout(
+            "volumetric_clouds_atmosphere_bind_group_layout",
+            &BindGroupLayoutEntries::with_indices(
+                ShaderStages::COMPUTE,
+                (
+                    (0, uniform_buffer::<Atmosphere>(true)),
+                    (1, uniform_buffer::<AtmosphereSettings>(true)),
+                    (3, uniform_buffer::<ViewUniform>(true)),
+                    (4, uniform_buffer::<GpuLights>(true)),
+                    (5, texture_2d(TextureSampleType::Float { filterable: true })), //transmittance lut and sampler
+                    (6, sampler(SamplerBindingType::Filtering)),
+                    (7, texture_2d(TextureSampleType::Float { filterable: true })), //multiscattering lut and sampler
+                    (8, sampler(SamplerBindingType::Filtering)),
+                ),
+            ),
+        );
+
+        let shadow_map = render_device.create_bind_group_layout(
+            "volumetric_clouds_shadow_map_bind_group_layout",
+            &BindGroupLayoutEntries::with_indices(
+                ShaderStages::COMPUTE,
+                (
+                    (0, uniform_buffer::<GpuVolumetricClouds>(true)),
+                    (1, uniform_buffer::<GlobalsUniform>(false)),
+                    (2, texture_2d(TextureSampleType::Float { filterable: true })), //weather map and sampler
+                    (3, sampler(SamplerBindingType::Filtering)),
+                    (
+                        //cloud shadow map storage texture
+                        6,
+                        texture_storage_2d(
+                            TextureFormat::Rgba16Float,
+                            StorageTextureAccess::WriteOnly,
+                        ),
+                    ),
+                ),
+            ),
+        );
+
+        let ray_march = render_device.create_bind_group_layout(
+            "volumetric_clouds_ray_march_bind_group_layout",
+            &BindGroupLayoutEntries::with_indices(
+                ShaderStages::COMPUTE,
+                (
+                    (0, uniform_buffer::<GpuVolumetricClouds>(true)),
+                    (1, uniform_buffer::<GlobalsUniform>(false)),
+                    (2, texture_2d(TextureSampleType::Float { filterable: true })), //weather map and sampler
+                    (3, sampler(SamplerBindingType::Filtering)),
+                    (4, texture_2d(TextureSampleType::Float { filterable: true })), //previous frame's clouds and sampler
+                    (5, sampler(SamplerBindingType::Filtering)),
+                    (
+                        //current frame's clouds storage texture
+                        6,
+                        texture_storage_2d(
+                            TextureFormat::Rgba16Float,
+                            StorageTextureAccess::WriteOnly,
+                        ),
+                    ),
+                ),
+            ),
+        );
+
+        Self {
+            atmosphere,
+            shadow_map,
+            ray_march,
+        }
+    }
+}
+
+/// Creates the layout of the bind group used to composite the clouds in the
+/// render sky pass.
+pub(super) fn render_sky_clouds_bind_group_layout(render_device: &RenderDevice) -> BindGroupLayout {
+    render_device.create_bind_group_layout(
+        "render_sky_clouds_bind_group_layout",
+        &BindGroupLayoutEntries::with_indices(
+            ShaderStages::FRAGMENT,
+            (
+                (0, uniform_buffer::<GpuVolumetricClouds>(true)),
+                (4, texture_2d(TextureSampleType::Float { filterable: true })), //current frame's clouds and sampler
+                (5, sampler(SamplerBindingType::Filtering)),
+            ),
+        ),
+    )
+}
+
+#[derive(Resource)]
+pub struct VolumetricCloudsSamplers {
+    pub weather_map: Sampler,
+    pub clouds: Sampler,
+    pub shadow_map: Sampler,
+}
+
+impl FromWorld for VolumetricCloudsSamplers {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+
+        let base_sampler = SamplerDescriptor {
+            mag_filter: FilterMode::Linear,
+            min_filter: FilterMode::Linear,
+            mipmap_filter: FilterMode::Nearest,
+            ..Default::default()
+        };
+
+        let weather_map = render_device.create_sampler(&SamplerDescriptor {
+            label: Some("cloud_weather_map_sampler"),
+            address_mode_u: AddressMode::Repeat,
+            address_mode_v: AddressMode::Repeat,
+            ..base_sampler
+        });
+
+        let clouds = render_device.create_sampler(&SamplerDescriptor {
+            label: Some("clouds_sampler"),
+            ..base_sampler
+        });
+
+        let shadow_map = render_device.create_sampler(&SamplerDescriptor {
+            label: Some("cloud_shadow_map_sampler"),
+            ..base_sampler
+        });
+
+        Self {
+            weather_map,
+            clouds,
+            shadow_map,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub(crate) struct VolumetricCloudsPipelines {
+    pub shadow_map: CachedComputePipelineId,
+    pub ray_march: CachedComputePipelineId,
+}
+
+impl FromWorld for VolumetricCloudsPipelines {
+    fn from_world(world: &mut World) -> Self {
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let layouts = world.resource::<VolumetricCloudsBindGroupLayouts>();
+
+        let shadow_map = pipeline_cache.queue_compute_pipeline(ComputePipelineDescriptor {
+            label: Some("cloud_shadow_map_pipeline".into()),
+            layout: vec![layouts.atmosphere.clone(), layouts.shadow_map.clone()],
+            push_constant_ranges: vec![],
+            shader: shaders::CLOUD_SHADOW_MAP,
+            shader_defs: vec![],
+            entry_point: "main".into(),
+            zero_initialize_workgroup_memory: false,
+        });
+
+        let ray_march = pipeline_cache.queue_compute_pipeline(ComputePipelineDescriptor {
+            label: Some("volumetric_clouds_pipeline".into()),
+            layout: vec![layouts.atmosphere.clone(), layouts.ray_march.clone()],
+            push_constant_ranges: vec![],
+            shader: shaders::VOLUMETRIC_CLOUDS,
+            shader_defs: vec![],
+            entry_point: "main".into(),
+            zero_initialize_workgroup_memory: false,
+        });
+
+        Self {
+            shadow_map,
+            ray_march,
+        }
+    }
+}
+
+/// Returns the size of the texture that the clouds are ray marched into for a
+/// view with the given viewport size.
+pub(super) fn clouds_texture_size(viewport_size: UVec2) -> UVec2 {
+    (viewport_size / 2).max(UVec2::ONE)
+}
+
+/// Frees the textures and history of views that no longer have clouds, so
+/// that the history isn't reprojected if clouds are enabled again.
+pub(super) fn remove_volumetric_clouds_history(
+    views: Query<
+        Entity,
+        (
+            With<VolumetricCloudsHistory>,
+            Or<(Without<VolumetricClouds>, Without<Atmosphere>)>,
+        ),
+    >,
+    mut commands: Commands,
+) {
+    for entity in &views {
+        commands.entity(entity).remove::<(
+            VolumetricCloudsHistory,
+            VolumetricCloudsTextures,
+            VolumetricCloudsUniformOffset,
+        )>();
+    }
+}
+
+pub(super) fn prepare_volumetric_clouds_textures(
+    views: Query<
+        (
+            Entity,
+            &ExtractedView,
+            &VolumetricClouds,
+            Option<&VolumetricCloudsHistory>,
+        ),
+        With<Atmosphere>,
+    >,
+    render_device: Res<RenderDevice>,
+    samplers: Res<VolumetricCloudsSamplers>,
+    mut texture_cache: ResMut<TextureCache>,
+    mut commands: Commands,
+) {
+    for (entity, view, clouds, history) in &views {
+        let size = clouds_texture_size(view.viewport.zw());
+        let descriptor = |label| TextureDescriptor {
+            label: Some(label),
+            size: Extent3d {
+                width: size.x,
+                height: size.y,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Rgba16Float,
+            usage: TextureUsages::STORAGE_BINDING | TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        };
+
+        let history_textures = [
+            texture_cache.get(&render_device, descriptor("volumetric_clouds_history_1")),
+            texture_cache.get(&render_device, descriptor("volumetric_clouds_history_2")),
+        ];
+
+        let shadow_map = texture_cache.get(
+            &render_device,
+            TextureDescriptor {
+                label: Some("cloud_shadow_map"),
+                size: Extent3d {
+                    width: clouds.shadow_map_size.max(1),
+                    height: clouds.shadow_map_size.max(1),
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: TextureFormat::Rgba16Float,
+                usage: TextureUsages::STORAGE_BINDING | TextureUsages::TEXTURE_BINDING,
+                view_formats: &[],
+            },
+        );
+
+        commands.entity(entity).insert(VolumetricCloudsTextures {
+            history: history_textures,
+            current: history.map_or(0, VolumetricCloudsHistory::current),
+            shadow_map,
+            shadow_map_sampler: samplers.shadow_map.clone(),
+        });
+    }
+}
+
+pub(super) fn prepare_volumetric_clouds_uniforms(
+    mut views: Query<
+        (
+            Entity,
+            &ExtractedView,
+            &VolumetricClouds,
+            &AtmosphereSettings,
+            Option<&mut VolumetricCloudsHistory>,
+        ),
+        With<Atmosphere>,
+    >,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    images: Res<RenderAssets<GpuImage>>,
+    mut clouds_uniforms: ResMut<VolumetricCloudsUniforms>,
+    mut commands: Commands,
+) {
+    let clouds_count = views.iter().len();
+    let Some(mut writer) =
+        clouds_uniforms
+            .uniforms
+            .get_writer(clouds_count, &render_device, &render_queue)
+    else {
+        return;
+    };
+
+    for (entity, view, clouds, settings, history) in &mut views {
+        let clip_from_world = view.clip_from_world.unwrap_or_else(|| {
+            view.clip_from_view * view.world_from_view.compute_matrix().inverse()
+        });
+
+        let has_weather_map = clouds
+            .weather_map
+            .as_ref()
+            .is_some_and(|weather_map| images.get(weather_map).is_some());
+
+        let uniform = GpuVolumetricClouds::new(
+            clouds,
+            settings.scene_units_to_m,
+            view.world_from_view.translation(),
+            clip_from_world,
+            history.as_deref(),
+            has_weather_map,
+        );
+
+        commands
+            .entity(entity)
+            .insert(VolumetricCloudsUniformOffset {
+                index: writer.write(&uniform),
+            });
+
+        match history {
+            Some(mut history) => history.advance(clip_from_world),
+            None => {
+                commands
+                    .entity(entity)
+                    .insert(VolumetricCloudsHistory::new(clip_from_world));
+            }
+        }
+    }
+}
+
+#[derive(Component)]
+pub(crate) struct VolumetricCloudsBindGroups {
+    pub atmosphere: BindGroup,
+    pub shadow_map: BindGroup,
+    pub ray_march: BindGroup,
+    pub render_sky: BindGroup,
+}
+
+pub(super) fn prepare_volumetric_clouds_bind_groups(
+    views: Query<
+        (
+            Entity,
+            &VolumetricClouds,
+            &AtmosphereTextures,
+            &VolumetricCloudsTextures,
+        ),
+        With<Atmosphere>,
+    >,
+    render_device: Res<RenderDevice>,
+    layouts: Res<VolumetricCloudsBindGroupLayouts>,
+    render_sky_layouts: Res<RenderSkyBindGroupLayouts>,
+    samplers: Res<VolumetricCloudsSamplers>,
+    atmosphere_samplers: Res<AtmosphereSamplers>,
+    images: Res<RenderAssets<GpuImage>>,
+    fallback_image_zero: Res<FallbackImageZero>,
+    view_uniforms: Res<ViewUniforms>,
+    lights_uniforms: Res<LightMeta>,
+    globals_buffer: Res<GlobalsBuffer>,
+    atmosphere_uniforms: Res<ComponentUniforms<Atmosphere>>,
+    settings_uniforms: Res<ComponentUniforms<AtmosphereSettings>>,
+    clouds_uniforms: Res<VolumetricCloudsUniforms>,
+    mut commands: Commands,
+) {
+    if views.iter().len() == 0 {
+        return;
+    }
+
+    let (
+        Some(atmosphere_binding),
+        Some(settings_binding),
+        Some(view_binding),
+        Some(lights_binding),
+        Some(globals_binding),
+        Some(clouds_binding),
+    ) = (
+        atmosphere_uniforms.binding(),
+        settings_uniforms.binding(),
+        view_uniforms.uniforms.binding(),
+        lights_uniforms.view_gpu_lights.binding(),
+        globals_buffer.buffer.binding(),
+        clouds_uniforms.uniforms().binding(),
+    )
+    else {
+        return;
+    };
+
+    for (entity, clouds, atmosphere_textures, clouds_textures) in &views {
+        // Without a weather map the shader never samples this texture, so any
+        // 2D texture will do.
+        let weather_map = clouds
+            .weather_map
+            .as_ref()
+            .and_then(|weather_map| images.get(weather_map))
+            .map(|weather_map| &weather_map.texture_view)
+            .unwrap_or(&fallback_image_zero.texture_view);
+
+        let atmosphere = render_device.create_bind_group(
+            "volumetric_clouds_atmosphere_bind_group",
+            &layouts.atmosphere,
+            &BindGroupEntries::with_indices((
+                (0, atmosphere_binding.clone()),
+                (1, settings_binding.clone()),
+                (3, view_binding.clone()),
+                (4, lights_binding.clone()),
+                (5, &atmosphere_textures.transmittance_lut.default_view),
+                (6, &atmosphere_samplers.transmittance_lut),
+                (7, &atmosphere_textures.multiscattering_lut.default_view),
+                (8, &atmosphere_samplers.multiscattering_lut),
+            )),
+        );
+
+        let shadow_map = render_device.create_bind_group(
+            "cloud_shadow_map_bind_group",
+            &layouts.shadow_map,
+            &BindGroupEntries::with_indices((
+                (0, clouds_binding.clone()),
+                (1, globals_binding.clone()),
+                (2, weather_map),
+                (3, &samplers.weather_map),
+                (6, &clouds_textures.shadow_map.default_view),
+            )),
+        );
+
+        let ray_march = render_device.create_bind_group(
+            "volumetric_clouds_bind_group",
+            &layouts.ray_march,
+            &BindGroupEntries::with_indices((
+                (0, clouds_binding.clone()),
+                (1, globals_binding.clone()),
+                (2, weather_map),
+                (3, &samplers.weather_map),
+                (4, &clouds_textures.previous().default_view),
+                (5, &samplers.clouds),
+                (6, &clouds_textures.current().default_view),
+            )),
+        );
+
+        let render_sky = render_device.create_bind_group(
+            "render_sky_clouds_bind_group",
+            &render_sky_layouts.volumetric_clouds,
+            &BindGroupEntries::with_indices((
+                (0, clouds_binding.clone()),
+                (4, &clouds_textures.current().default_view),
+                (5, &samplers.clouds),
+            )),
+        );
+
+        commands.entity(entity).insert(VolumetricCloudsBindGroups {
+            atmosphere,
+            shadow_map,
+            ray_march,
+            render_sky,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        remove_volumetric_clouds_history, GpuVolumetricClouds, VolumetricClouds,
+        VolumetricCloudsHistory,
+    };
+    use crate::Atmosphere;
+    use bevy_core_pipeline::core_3d::Camera3d;
+    use bevy_ecs::{entity::Entity, system::RunSystemOnce, world::World};
+    use bevy_math::{Mat4, Vec2, Vec3, Vec4};
+    use bevy_render::extract_component::ExtractComponent;
+
+    #[test]
+    fn uniform_packs_clouds() {
+        let clouds = VolumetricClouds {
+            bottom_altitude: 2_000.0,
+            top_altitude: 1_000.0,
+            coverage: 0.75,
+            wind: Vec2::new(-5.0, 20.0),
+            weather_map_size: 10_000.0,
+            shape_noise_size: 4_000.0,
+            detail_noise_size: 500.0,
+            steps: 0,
+            light_steps: 0,
+            temporal_blend: 1.0,
+            shadow_map_size: 100,
+            shadow_map_extent: 1_000.0,
+            ..VolumetricClouds::default()
+        };
+        let clip_from_world = Mat4::from_translation(Vec3::X);
+        let history = VolumetricCloudsHistory::new(clip_from_world);
+
+        let uniform = GpuVolumetricClouds::new(
+            &clouds,
+            1.0,
+            Vec3::new(25.0, 100.0, -15.0),
+            clip_from_world,
+            Some(&history),
+            true,
+        );
+        assert_eq!(uniform.wind, Vec2::new(-5.0, 20.0));
+        assert_eq!(uniform.coverage, 0.75);
+        assert_eq!(uniform.inv_weather_map_size, 1.0 / 10_000.0);
+        assert_eq!(uniform.inv_shape_noise_size, 1.0 / 4_000.0);
+        assert_eq!(uniform.inv_detail_noise_size, 1.0 / 500.0);
+        // The layer is at least a meter thick, and at least one sample is
+        // taken.
+        assert_eq!(uniform.top_altitude, 2_001.0);
+        assert_eq!((uniform.steps, uniform.light_steps), (1, 1));
+        assert_eq!(uniform.temporal_blend, 0.99);
+        // The shadow map is centered on the camera, snapped to its 10 m
+        // texels.
+        assert_eq!(
+            uniform.shadow_map_params,
+            Vec4::new(20.0, -20.0, 1.0 / 1_000.0, 2_000.0)
+        );
+
+        // Without a weather map or history, coverage is uniform and nothing
+        // is blended.
+        let uniform =
+            GpuVolumetricClouds::new(&clouds, 1.0, Vec3::ZERO, clip_from_world, None, false);
+        assert_eq!(uniform.inv_weather_map_size, 0.0);
+        assert_eq!(uniform.temporal_blend, 0.0);
+    }
+
+    #[test]
+    fn history_tracks_previous_view() {
+        let frames = [
+            Mat4::from_translation(Vec3::X),
+            Mat4::from_translation(Vec3::Y),
+            Mat4::from_rotation_y(0.5),
+        ];
+        let clouds = VolumetricClouds::default();
+
+        // The first frame reprojects onto itself.
+        let uniform = GpuVolumetricClouds::new(&clouds, 1.0, Vec3::ZERO, frames[0], None, false);
+        assert_eq!(uniform.previous_clip_from_world, frames[0]);
+        let mut history = VolumetricCloudsHistory::new(frames[0]);
+        assert_eq!(history.current(), 1);
+
+        // Later frames reproject onto the frame before them.
+        for (previous, &current) in frames.iter().zip(&frames[1..]) {
+            let current_index = history.current();
+            let uniform =
+                GpuVolumetricClouds::new(&clouds, 1.0, Vec3::ZERO, current, Some(&history), false);
+            assert_eq!(uniform.previous_clip_from_world, *previous);
+            history.advance(current);
+            assert_ne!(history.current(), current_index);
+        }
+        assert_eq!(history.previous_clip_from_world, frames[2]);
+    }
+
+    #[test]
+    fn extract_clouds_of_atmosphere_cameras() {
+        let mut world = World::new();
+        let clouds = VolumetricClouds {
+            coverage: 0.25,
+            ..VolumetricClouds::default()
+        };
+        let camera = world
+            .spawn((Camera3d::default(), Atmosphere::EARTH, clouds.clone()))
+            .id();
+        world.spawn((Camera3d::default(), clouds.clone()));
+        world.spawn((Atmosphere::EARTH, clouds));
+
+        let mut query = world.query_filtered::<(
+            Entity,
+            <VolumetricClouds as ExtractComponent>::QueryData,
+        ), <VolumetricClouds as ExtractComponent>::QueryFilter>();
+        let extracted: Vec<_> = query
+            .iter(&world)
+            .map(|(entity, item)| (entity, VolumetricClouds::extract_component(item)))
+            .collect();
+        assert_eq!(extracted.len(), 1);
+        assert_eq!(extracted[0].0, camera);
+        assert_eq!(
+            extracted[0].1.as_ref().map(|clouds| clouds.coverage),
+            Some(0.25)
+        );
+    }
+
+    #[test]
+    fn history_is_removed_with_clouds() {
+        let mut world = World::new();
+        let history = || VolumetricCloudsHistory::new(Mat4::IDENTITY);
+        let enabled = world
+            .spawn((VolumetricClouds::default(), Atmosphere::EARTH, history()))
+            .id();
+        let no_clouds = world.spawn((Atmosphere::EARTH, history())).id();
+        let no_atmosphere = world.spawn((VolumetricClouds::default(), history())).id();
+
+        world
+            .run_system_once(remove_volumetric_clouds_history)
+            .unwrap();
+        assert!(world.get::<VolumetricCloudsHistory>(enabled).is_some());
+        assert!(world.get::<VolumetricCloudsHistory>(no_clouds).is_none());
+        assert!(world
+            .get::<VolumetricCloudsHistory>(no_atmosphere)
+            .is_none());
+    }
+}
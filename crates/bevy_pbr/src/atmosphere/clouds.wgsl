@@ -0,0 +1,189 @@
+#define_import_path bevy_pbr::atmosphere::clouds
+
+#import bevy_render::globals::Globals
+
+#import bevy_pbr::atmosphere::{
+    types::VolumetricClouds,
+    bindings::atmosphere,
+    functions::FRAC_4_PI,
+    bruneton_functions::ray_intersects_ground,
+}
+
+@group(1) @binding(0) var<uniform> clouds: VolumetricClouds;
+@group(1) @binding(1) var<uniform> globals: Globals;
+@group(1) @binding(2) var weather_map: texture_2d<f32>;
+@group(1) @binding(3) var weather_map_sampler: sampler;
+// The previous frame's clouds during the ray march, and the current frame's
+// clouds when compositing.
+@group(1) @binding(4) var clouds_texture: texture_2d<f32>;
+@group(1) @binding(5) var clouds_sampler: sampler;
+
+// The fraction of the cloud layer, from the bottom and top respectively, over
+// which density fades in and out.
+const BOTTOM_FADE: f32 = 0.1;
+const TOP_FADE: f32 = 0.4;
+
+// Cloud scattering is approximated with a blend of a strong forward lobe and a
+// weaker backward lobe, which gives both the silver lining around clouds seen
+// towards the sun and some brightness when looking away from it.
+const FORWARD_ASYMMETRY: f32 = 0.8;
+const BACKWARD_ASYMMETRY: f32 = -0.3;
+const BACKWARD_WEIGHT: f32 = 0.3;
+
+// NOISE
+
+fn hash(p: vec3<i32>) -> f32 {
+    let h = bitcast<vec3<u32>>(p) * vec3(73856093u, 19349663u, 83492791u);
+    var n = h.x ^ h.y ^ h.z;
+    n = (n ^ (n >> 16u)) * 0x45d9f3bu;
+    n = (n ^ (n >> 16u)) * 0x45d9f3bu;
+    n = n ^ (n >> 16u);
+    return f32(n) / 4294967295.0;
+}
+
+// Value noise in [0, 1] with quintic interpolation
+fn value_noise(p: vec3<f32>) -> f32 {
+    let cell = vec3<i32>(floor(p));
+    let f = fract(p);
+    let u = f * f * f * (f * (f * 6.0 - 15.0) + 10.0);
+
+    let x00 = mix(hash(cell), hash(cell + vec3(1, 0, 0)), u.x);
+    let x10 = mix(hash(cell + vec3(0, 1, 0)), hash(cell + vec3(1, 1, 0)), u.x);
+    let x01 = mix(hash(cell + vec3(0, 0, 1)), hash(cell + vec3(1, 0, 1)), u.x);
+    let x11 = mix(hash(cell + vec3(0, 1, 1)), hash(cell + vec3(1, 1, 1)), u.x);
+    return mix(mix(x00, x10, u.y), mix(x01, x11, u.y), u.z);
+}
+
+// Fractal sum of value noise, normalized to [0, 1]
+fn fbm(p: vec3<f32>, octaves: u32) -> f32 {
+    var sum = 0.0;
+    var amplitude = 0.5;
+    var total_amplitude = 0.0;
+    var q = p;
+    for (var i: u32 = 0u; i < octaves; i++) {
+        sum += value_noise(q) * amplitude;
+        total_amplitude += amplitude;
+        amplitude *= 0.5;
+        q *= 2.03;
+    }
+    return sum / total_amplitude;
+}
+
+// CLOUD SAMPLING
+
+/// Samples the cloud extinction at a given horizontal position and altitude,
+/// both in meters. Since clouds are treated as purely scattering, this is also
+/// the scattering coefficient.
+///
+/// units: m^-1
+fn sample_cloud_density(position_xz: vec2<f32>, altitude: f32) -> f32 {
+    let h = (altitude - clouds.bottom_altitude) / (clouds.top_altitude - clouds.bottom_altitude);
+    if h <= 0.0 || h >= 1.0 {
+        return 0.0;
+    }
+
+    let p = position_xz - clouds.wind * globals.time;
+
+    var coverage = clouds.coverage;
+    if clouds.inv_weather_map_size > 0.0 {
+        let weather_uv = p * clouds.inv_weather_map_size;
+        coverage *= textureSampleLevel(weather_map, weather_map_sampler, weather_uv, 0.0).r;
+    }
+    if coverage <= 0.0 {
+        return 0.0;
+    }
+
+    let height_gradient = saturate(h / BOTTOM_FADE) * saturate((1.0 - h) / TOP_FADE);
+    let p_3d = vec3(p.x, altitude, p.y);
+
+    let shape = fbm(p_3d * clouds.inv_shape_noise_size, 4u);
+    let base_density = saturate((shape - (1.0 - coverage)) / coverage) * height_gradient;
+    if base_density <= 0.0 {
+        return 0.0;
+    }
+
+    // Erode the edges of the clouds, where the base density is lowest, with
+    // the higher frequency detail noise.
+    let erosion = fbm(p_3d * clouds.inv_detail_noise_size, 3u) * clouds.detail_strength;
+    let density = saturate((base_density - erosion) / (1.0 - erosion));
+
+    return density * clouds.density;
+}
+
+/// Marches from a point in the cloud layer towards a light to find how much of
+/// its light reaches that point through the clouds.
+fn sample_cloud_transmittance_to_light(position_xz: vec2<f32>, altitude: f32, direction_to_light: vec3<f32>) -> f32 {
+    let thickness = clouds.top_altitude - clouds.bottom_altitude;
+    let distance_to_top = (clouds.top_altitude - altitude) / max(direction_to_light.y, 0.05);
+    let dt = min(distance_to_top, 4.0 * thickness) / f32(clouds.light_steps);
+
+    var optical_depth = 0.0;
+    for (var step_i: u32 = 0u; step_i < clouds.light_steps; step_i++) {
+        let t = (f32(step_i) + 0.5) * dt;
+        optical_depth += sample_cloud_density(
+            position_xz + direction_to_light.xz * t,
+            altitude + direction_to_light.y * t
+        ) * dt;
+    }
+
+    // Approximates multiple scattering within the cloud by blending in a
+    // softer, dimmer extinction, which keeps the cores of thick clouds from
+    // going completely black.
+    return max(exp(-optical_depth), 0.7 * exp(-0.25 * optical_depth));
+}
+
+fn henyey_greenstein_with_asymmetry(neg_LdotV: f32, g: f32) -> f32 {
+    let denom = 1.0 + g * g - 2.0 * g * neg_LdotV;
+    return FRAC_4_PI * (1.0 - g * g) / (denom * sqrt(denom));
+}
+
+fn cloud_phase(neg_LdotV: f32) -> f32 {
+    return mix(
+        henyey_greenstein_with_asymmetry(neg_LdotV, FORWARD_ASYMMETRY),
+        henyey_greenstein_with_asymmetry(neg_LdotV, BACKWARD_ASYMMETRY),
+        BACKWARD_WEIGHT
+    );
+}
+
+// RAY INTERSECTION
+
+// Returns the near and far distances along a ray from radius r with
+// mu = cos(zenith angle) to a sphere of the given radius around the planet
+// center, or a negative far distance if the ray misses it.
+fn ray_sphere_distances(r: f32, mu: f32, radius: f32) -> vec2<f32> {
+    let discriminant = r * r * (mu * mu - 1.0) + radius * radius;
+    if discriminant < 0.0 {
+        return vec2(-1.0);
+    }
+    let root = sqrt(discriminant);
+    return vec2(-r * mu - root, -r * mu + root);
+}
+
+/// Returns the range of distances along a view ray, in meters, that lies
+/// within the cloud layer, clamped to the maximum march distance. The range is
+/// empty if the ray doesn't pass through the cloud layer.
+fn cloud_layer_bounds(r: f32, mu: f32) -> vec2<f32> {
+    let bottom_radius = atmosphere.bottom_radius + clouds.bottom_altitude;
+    let top_radius = atmosphere.bottom_radius + clouds.top_altitude;
+    let bottom = ray_sphere_distances(r, mu, bottom_radius);
+    let top = ray_sphere_distances(r, mu, top_radius);
+
+    var bounds = vec2(0.0);
+    if r < bottom_radius {
+        if ray_intersects_ground(r, mu) {
+            return vec2(0.0);
+        }
+        bounds = vec2(bottom.y, top.y);
+    } else {
+        if top.y < 0.0 {
+            return vec2(0.0);
+        }
+        bounds = vec2(max(top.x, 0.0), top.y);
+        if bottom.x > 0.0 {
+            bounds.y = bottom.x;
+        }
+    }
+
+    bounds.y = min(bounds.y, clouds.max_distance);
+    return bounds;
+}
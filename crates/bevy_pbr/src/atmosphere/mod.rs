@@ -25,13 +25,21 @@
 //! at once is untested, and might not be physically accurate. These may be
 //! integrated into a single module in the future.
 //!
+//! Adding the [`VolumetricClouds`] component alongside [`Atmosphere`] renders
+//! a ray-marched cloud layer that is lit by the atmosphere's LUTs and casts
+//! shadows onto the scene through the first directional light.
+//!
 //! [Shadertoy]: https://www.shadertoy.com/view/slSXRW
 //!
 //! [Unreal Engine Implementation]: https://github.com/sebh/UnrealEngineSkyAtmosphere
 
+mod clouds;
 mod node;
 pub mod resources;
 
+pub use clouds::VolumetricClouds;
+pub(crate) use clouds::VolumetricCloudsTextures;
+
 use bevy_app::{App, Plugin};
 use bevy_asset::load_internal_asset;
 use bevy_core_pipeline::core_3d::graph::Node3d;
@@ -65,6 +73,12 @@ use resources::{
 use tracing::warn;
 
 use self::{
+    clouds::{
+        prepare_volumetric_clouds_bind_groups, prepare_volumetric_clouds_textures,
+        prepare_volumetric_clouds_uniforms, remove_volumetric_clouds_history,
+        VolumetricCloudsBindGroupLayouts, VolumetricCloudsPipelines, VolumetricCloudsSamplers,
+        VolumetricCloudsUniforms,
+    },
     node::{AtmosphereLutsNode, AtmosphereNode, RenderSkyNode},
     resources::{
         prepare_atmosphere_bind_groups, prepare_atmosphere_textures, AtmosphereBindGroupLayouts,
@@ -90,6 +104,12 @@ mod shaders {
     pub const AERIAL_VIEW_LUT: Handle<Shader> =
         weak_handle!("a3daf030-4b64-49ae-a6a7-354489597cbe");
     pub const RENDER_SKY: Handle<Shader> = weak_handle!("09422f46-d0f7-41c1-be24-121c17d6e834");
+
+    pub const CLOUDS: Handle<Shader> = weak_handle!("5c0f7c3e-2a8d-4b51-9d6e-8f1b3a7e4c92");
+    pub const CLOUD_SHADOW_MAP: Handle<Shader> =
+        weak_handle!("b3e91f24-6d0a-4c7b-8e25-1a9f4d6c3b80");
+    pub const VOLUMETRIC_CLOUDS: Handle<Shader> =
+        weak_handle!("2f8a6d13-9c4e-4e0b-a7f1-6b3d5c8e9a24");
 }
 
 #[doc(hidden)]
@@ -143,11 +163,29 @@ impl Plugin for AtmospherePlugin {
             Shader::from_wgsl
         );
 
+        load_internal_asset!(app, shaders::CLOUDS, "clouds.wgsl", Shader::from_wgsl);
+
+        load_internal_asset!(
+            app,
+            shaders::CLOUD_SHADOW_MAP,
+            "cloud_shadow_map.wgsl",
+            Shader::from_wgsl
+        );
+
+        load_internal_asset!(
+            app,
+            shaders::VOLUMETRIC_CLOUDS,
+            "volumetric_clouds.wgsl",
+            Shader::from_wgsl
+        );
+
         app.register_type::<Atmosphere>()
             .register_type::<AtmosphereSettings>()
+            .register_type::<VolumetricClouds>()
             .add_plugins((
                 ExtractComponentPlugin::<Atmosphere>::default(),
                 ExtractComponentPlugin::<AtmosphereSettings>::default(),
+                ExtractComponentPlugin::<VolumetricClouds>::default(),
                 UniformComponentPlugin::<Atmosphere>::default(),
                 UniformComponentPlugin::<AtmosphereSettings>::default(),
             ));
@@ -194,6 +232,10 @@ impl Plugin for AtmospherePlugin {
             .init_resource::<AtmosphereLutPipelines>()
             .init_resource::<AtmosphereTransforms>()
             .init_resource::<SpecializedRenderPipelines<RenderSkyBindGroupLayouts>>()
+            .init_resource::<VolumetricCloudsBindGroupLayouts>()
+            .init_resource::<VolumetricCloudsSamplers>()
+            .init_resource::<VolumetricCloudsPipelines>()
+            .init_resource::<VolumetricCloudsUniforms>()
             .add_systems(
                 Render,
                 (
@@ -201,7 +243,15 @@ impl Plugin for AtmospherePlugin {
                     queue_render_sky_pipelines.in_set(RenderSet::Queue),
                     prepare_atmosphere_textures.in_set(RenderSet::PrepareResources),
                     prepare_atmosphere_transforms.in_set(RenderSet::PrepareResources),
+                    (
+                        remove_volumetric_clouds_history,
+                        prepare_volumetric_clouds_textures,
+                        prepare_volumetric_clouds_uniforms,
+                    )
+                        .chain()
+                        .in_set(RenderSet::PrepareResources),
                     prepare_atmosphere_bind_groups.in_set(RenderSet::PrepareBindGroups),
+                    prepare_volumetric_clouds_bind_groups.in_set(RenderSet::PrepareBindGroups),
                 ),
             )
             .add_render_graph_node::<ViewNodeRunner<AtmosphereLutsNode>>(
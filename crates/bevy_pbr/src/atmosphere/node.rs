@@ -1,4 +1,8 @@
-use bevy_ecs::{query::QueryItem, system::lifetimeless::Read, world::World};
+use bevy_ecs::{
+    query::{Has, QueryItem},
+    system::lifetimeless::Read,
+    world::World,
+};
 use bevy_math::{UVec2, Vec3Swizzles};
 use bevy_render::{
     extract_component::DynamicUniformIndex,
@@ -11,11 +15,15 @@ use bevy_render::{
 use crate::ViewLightsUniformOffset;
 
 use super::{
+    clouds::{
+        VolumetricCloudsBindGroups, VolumetricCloudsPipelines, VolumetricCloudsTextures,
+        VolumetricCloudsUniformOffset,
+    },
     resources::{
         AtmosphereBindGroups, AtmosphereLutPipelines, AtmosphereTransformsOffset,
         RenderSkyPipelineId,
     },
-    Atmosphere, AtmosphereSettings,
+    Atmosphere, AtmosphereSettings, VolumetricClouds,
 };
 
 #[derive(PartialEq, Eq, Debug, Copy, Clone, Hash, RenderLabel)]
@@ -36,6 +44,11 @@ impl ViewNode for AtmosphereLutsNode {
         Read<AtmosphereTransformsOffset>,
         Read<ViewUniformOffset>,
         Read<ViewLightsUniformOffset>,
+        Option<(
+            Read<VolumetricCloudsBindGroups>,
+            Read<VolumetricCloudsTextures>,
+            Read<VolumetricCloudsUniformOffset>,
+        )>,
     );
 
     fn run(
@@ -50,6 +63,7 @@ impl ViewNode for AtmosphereLutsNode {
             atmosphere_transforms_offset,
             view_uniforms_offset,
             lights_uniforms_offset,
+            volumetric_clouds,
         ): QueryItem<Self::ViewQuery>,
         world: &World,
     ) -> Result<(), NodeRunError> {
@@ -149,6 +163,62 @@ impl ViewNode for AtmosphereLutsNode {
 
         dispatch_2d(&mut luts_pass, settings.aerial_view_lut_size.xy());
 
+        // Volumetric clouds
+
+        let Some((clouds_bind_groups, clouds_textures, clouds_uniforms_offset)) = volumetric_clouds
+        else {
+            return Ok(());
+        };
+
+        let clouds_pipelines = world.resource::<VolumetricCloudsPipelines>();
+        let (Some(cloud_shadow_map_pipeline), Some(volumetric_clouds_pipeline)) = (
+            pipeline_cache.get_compute_pipeline(clouds_pipelines.shadow_map),
+            pipeline_cache.get_compute_pipeline(clouds_pipelines.ray_march),
+        ) else {
+            return Ok(());
+        };
+
+        luts_pass.set_bind_group(
+            0,
+            &clouds_bind_groups.atmosphere,
+            &[
+                atmosphere_uniforms_offset.index(),
+                settings_uniforms_offset.index(),
+                view_uniforms_offset.offset,
+                lights_uniforms_offset.offset,
+            ],
+        );
+
+        // Cloud Shadow Map
+
+        luts_pass.set_pipeline(cloud_shadow_map_pipeline);
+        luts_pass.set_bind_group(
+            1,
+            &clouds_bind_groups.shadow_map,
+            &[clouds_uniforms_offset.index()],
+        );
+
+        let shadow_map_size = clouds_textures.shadow_map.texture.size();
+        dispatch_2d(
+            &mut luts_pass,
+            UVec2::new(shadow_map_size.width, shadow_map_size.height),
+        );
+
+        // Clouds Ray March
+
+        luts_pass.set_pipeline(volumetric_clouds_pipeline);
+        luts_pass.set_bind_group(
+            1,
+            &clouds_bind_groups.ray_march,
+            &[clouds_uniforms_offset.index()],
+        );
+
+        let clouds_size = clouds_textures.current().texture.size();
+        dispatch_2d(
+            &mut luts_pass,
+            UVec2::new(clouds_size.width, clouds_size.height),
+        );
+
         Ok(())
     }
 }
@@ -166,6 +236,11 @@ impl ViewNode for RenderSkyNode {
         Read<ViewUniformOffset>,
         Read<ViewLightsUniformOffset>,
         Read<RenderSkyPipelineId>,
+        Has<VolumetricClouds>,
+        Option<(
+            Read<VolumetricCloudsBindGroups>,
+            Read<VolumetricCloudsUniformOffset>,
+        )>,
     );
 
     fn run<'w>(
@@ -181,6 +256,8 @@ impl ViewNode for RenderSkyNode {
             view_uniforms_offset,
             lights_uniforms_offset,
             render_sky_pipeline_id,
+            has_volumetric_clouds,
+            volumetric_clouds,
         ): QueryItem<'w, Self::ViewQuery>,
         world: &'w World,
     ) -> Result<(), NodeRunError> {
@@ -191,6 +268,11 @@ impl ViewNode for RenderSkyNode {
             return Ok(());
         }; //TODO: warning
 
+        // The pipeline expects the clouds bind group if the view has clouds
+        if has_volumetric_clouds && volumetric_clouds.is_none() {
+            return Ok(());
+        }
+
         let mut render_sky_pass =
             render_context
                 .command_encoder()
@@ -214,6 +296,13 @@ impl ViewNode for RenderSkyNode {
                 lights_uniforms_offset.offset,
             ],
         );
+        if let Some((clouds_bind_groups, clouds_uniforms_offset)) = volumetric_clouds {
+            render_sky_pass.set_bind_group(
+                1,
+                &clouds_bind_groups.render_sky,
+                &[clouds_uniforms_offset.index()],
+            );
+        }
         render_sky_pass.draw(0..3, 0..1);
 
         Ok(())
@@ -10,6 +10,10 @@
 };
 #import bevy_render::view::View;
 
+#ifdef VOLUMETRIC_CLOUDS
+#import bevy_pbr::atmosphere::clouds::{clouds_texture, clouds_sampler, cloud_layer_bounds}
+#endif
+
 #import bevy_core_pipeline::fullscreen_vertex_shader::FullscreenVertexOutput
 
 #ifdef MULTISAMPLED
@@ -33,15 +37,28 @@ fn main(in: FullscreenVertexOutput) -> RenderSkyOutput {
 
     var transmittance: vec3<f32>;
     var inscattering: vec3<f32>;
+    var t = 0.0;
     if depth == 0.0 {
         let ray_dir_as = direction_world_to_atmosphere(ray_dir_ws.xyz);
         transmittance = sample_transmittance_lut(r, mu);
         inscattering += sample_sky_view_lut(r, ray_dir_as);
         inscattering += sample_sun_illuminance(ray_dir_ws.xyz, transmittance);
     } else {
-        let t = ndc_to_camera_dist(vec3(uv_to_ndc(in.uv), depth));
+        t = ndc_to_camera_dist(vec3(uv_to_ndc(in.uv), depth));
         inscattering = sample_aerial_view_lut(in.uv, t);
         transmittance = sample_transmittance_lut_segment(r, mu, t);
     }
+
+#ifdef VOLUMETRIC_CLOUDS
+    // Clouds lie in front of the sky, and in front of any geometry farther
+    // away than the bottom of the cloud layer.
+    let cloud_bounds = cloud_layer_bounds(r, mu);
+    if depth == 0.0 || (cloud_bounds.y > cloud_bounds.x && t > cloud_bounds.x) {
+        let cloud = textureSampleLevel(clouds_texture, clouds_sampler, in.uv, 0.0);
+        inscattering = cloud.rgb + inscattering * (1.0 - cloud.a);
+        transmittance *= 1.0 - cloud.a;
+    }
+#endif
+
     return RenderSkyOutput(vec4(inscattering, 0.0), vec4(transmittance, 1.0));
 }
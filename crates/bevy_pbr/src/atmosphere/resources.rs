@@ -4,7 +4,7 @@ use bevy_core_pipeline::{
 use bevy_ecs::{
     component::Component,
     entity::Entity,
-    query::With,
+    query::{Has, With},
     resource::Resource,
     system::{Commands, Query, Res, ResMut},
     world::{FromWorld, World},
@@ -21,7 +21,10 @@ use bevy_render::{
 
 use crate::{GpuLights, LightMeta};
 
-use super::{shaders, Atmosphere, AtmosphereSettings};
+use super::{
+    clouds::render_sky_clouds_bind_group_layout, shaders, Atmosphere, AtmosphereSettings,
+    VolumetricClouds,
+};
 
 #[derive(Resource)]
 pub(crate) struct AtmosphereBindGroupLayouts {
@@ -35,6 +38,7 @@ pub(crate) struct AtmosphereBindGroupLayouts {
 pub(crate) struct RenderSkyBindGroupLayouts {
     pub render_sky: BindGroupLayout,
     pub render_sky_msaa: BindGroupLayout,
+    pub volumetric_clouds: BindGroupLayout,
 }
 
 impl FromWorld for AtmosphereBindGroupLayouts {
@@ -200,9 +204,12 @@ impl FromWorld for RenderSkyBindGroupLayouts {
             ),
         );
 
+        let volumetric_clouds = render_sky_clouds_bind_group_layout(render_device);
+
         Self {
             render_sky,
             render_sky_msaa,
+            volumetric_clouds,
         }
     }
 }
@@ -326,6 +333,7 @@ pub(crate) struct RenderSkyPipelineId(pub CachedRenderPipelineId);
 pub(crate) struct RenderSkyPipelineKey {
     pub msaa_samples: u32,
     pub hdr: bool,
+    pub volumetric_clouds: bool,
 }
 
 impl SpecializedRenderPipeline for RenderSkyBindGroupLayouts {
@@ -340,14 +348,22 @@ impl SpecializedRenderPipeline for RenderSkyBindGroupLayouts {
         if key.hdr {
             shader_defs.push("TONEMAP_IN_SHADER".into());
         }
+        if key.volumetric_clouds {
+            shader_defs.push("VOLUMETRIC_CLOUDS".into());
+        }
+
+        let mut layout = vec![if key.msaa_samples == 1 {
+            self.render_sky.clone()
+        } else {
+            self.render_sky_msaa.clone()
+        }];
+        if key.volumetric_clouds {
+            layout.push(self.volumetric_clouds.clone());
+        }
 
         RenderPipelineDescriptor {
             label: Some(format!("render_sky_pipeline_{}", key.msaa_samples).into()),
-            layout: vec![if key.msaa_samples == 1 {
-                self.render_sky.clone()
-            } else {
-                self.render_sky_msaa.clone()
-            }],
+            layout,
             push_constant_ranges: vec![],
             vertex: fullscreen_shader_vertex_state(),
             primitive: PrimitiveState::default(),
@@ -358,6 +374,7 @@ impl SpecializedRenderPipeline for RenderSkyBindGroupLayouts {
                 alpha_to_coverage_enabled: false,
             },
             zero_initialize_workgroup_memory: false,
+            multiview: None,
             fragment: Some(FragmentState {
                 shader: shaders::RENDER_SKY.clone(),
                 shader_defs,
@@ -384,19 +401,20 @@ impl SpecializedRenderPipeline for RenderSkyBindGroupLayouts {
 }
 
 pub(super) fn queue_render_sky_pipelines(
-    views: Query<(Entity, &Camera, &Msaa), With<Atmosphere>>,
+    views: Query<(Entity, &Camera, &Msaa, Has<VolumetricClouds>), With<Atmosphere>>,
     pipeline_cache: Res<PipelineCache>,
     layouts: Res<RenderSkyBindGroupLayouts>,
     mut specializer: ResMut<SpecializedRenderPipelines<RenderSkyBindGroupLayouts>>,
     mut commands: Commands,
 ) {
-    for (entity, camera, msaa) in &views {
+    for (entity, camera, msaa, volumetric_clouds) in &views {
         let id = specializer.specialize(
             &pipeline_cache,
             &layouts,
             RenderSkyPipelineKey {
                 msaa_samples: msaa.samples(),
                 hdr: camera.hdr,
+                volumetric_clouds,
             },
         );
         commands.entity(entity).insert(RenderSkyPipelineId(id));
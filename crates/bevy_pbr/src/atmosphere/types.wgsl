@@ -43,3 +43,24 @@ struct AtmosphereTransforms {
     world_from_atmosphere: mat4x4<f32>,
     atmosphere_from_world: mat4x4<f32>,
 }
+
+struct VolumetricClouds {
+    previous_clip_from_world: mat4x4<f32>,
+    // xy: world space center of the cloud shadow map
+    // z: 1 / world space extent of the cloud shadow map
+    // w: world space height of the bottom of the cloud layer
+    shadow_map_params: vec4<f32>,
+    wind: vec2<f32>, // units: m/s
+    bottom_altitude: f32, // units: m
+    top_altitude: f32, // units: m
+    coverage: f32,
+    density: f32, // units: m^-1
+    inv_weather_map_size: f32, // zero if there's no weather map. units: m^-1
+    inv_shape_noise_size: f32, // units: m^-1
+    inv_detail_noise_size: f32, // units: m^-1
+    detail_strength: f32,
+    max_distance: f32, // units: m
+    temporal_blend: f32, // zero if there's no valid history
+    steps: u32,
+    light_steps: u32,
+}
@@ -0,0 +1,118 @@
+#import bevy_pbr::{
+    utils::interleaved_gradient_noise,
+    atmosphere::{
+        bindings::{atmosphere, settings, view, lights},
+        functions::{
+            sample_transmittance_lut, sample_transmittance_lut_segment,
+            sample_multiscattering_lut, get_local_r, get_local_up, view_radius,
+            uv_to_ray_direction, ndc_to_uv
+        },
+        bruneton_functions::ray_intersects_ground,
+        clouds::{
+            clouds, globals, clouds_texture, clouds_sampler, sample_cloud_density,
+            sample_cloud_transmittance_to_light, cloud_phase, cloud_layer_bounds
+        },
+    }
+}
+
+@group(1) @binding(6) var clouds_out: texture_storage_2d<rgba16float, write>;
+
+@compute
+@workgroup_size(16, 16, 1)
+fn main(@builtin(global_invocation_id) idx: vec3<u32>) {
+    let size = textureDimensions(clouds_out);
+    if any(idx.xy >= size) { return; }
+
+    let uv = (vec2<f32>(idx.xy) + 0.5) / vec2<f32>(size);
+    let ray_dir = uv_to_ray_direction(uv).xyz;
+    let r = view_radius();
+    let mu = ray_dir.y;
+    let bounds = cloud_layer_bounds(r, mu);
+
+    var inscattering = vec3(0.0);
+    var transmittance = 1.0;
+    // The distance to the clouds, weighted by how much each sample contributes
+    // to the final opacity. Used for reprojection.
+    var weighted_t = 0.0;
+    var total_weight = 0.0;
+
+    if bounds.y > bounds.x {
+        let dt = (bounds.y - bounds.x) / f32(clouds.steps);
+        let jitter = interleaved_gradient_noise(vec2<f32>(idx.xy), globals.frame_count);
+        let origin_xz = view.world_position.xz * settings.scene_units_to_m;
+
+        for (var step_i: u32 = 0u; step_i < clouds.steps; step_i++) {
+            let t_i = bounds.x + (f32(step_i) + jitter) * dt;
+            let local_r = get_local_r(r, mu, t_i);
+            let local_up = get_local_up(r, t_i, ray_dir);
+            let altitude = local_r - atmosphere.bottom_radius;
+            let position_xz = origin_xz + ray_dir.xz * t_i;
+
+            let extinction = sample_cloud_density(position_xz, altitude);
+            if extinction <= 0.0 {
+                continue;
+            }
+
+            var luminance = vec3(0.0);
+            for (var light_i: u32 = 0u; light_i < lights.n_directional_lights; light_i++) {
+                let light = &lights.directional_lights[light_i];
+                let mu_light = dot((*light).direction_to_light, local_up);
+                let neg_LdotV = dot((*light).direction_to_light, ray_dir);
+
+                let atmosphere_transmittance = sample_transmittance_lut(local_r, mu_light)
+                    * f32(!ray_intersects_ground(local_r, mu_light));
+                let cloud_transmittance = sample_cloud_transmittance_to_light(
+                    position_xz,
+                    altitude,
+                    (*light).direction_to_light
+                );
+
+                // Ambient light from the sky, approximated with the same
+                // multiscattering term the atmosphere uses.
+                let psi_ms = sample_multiscattering_lut(local_r, mu_light);
+
+                luminance += (*light).color.rgb * (
+                    atmosphere_transmittance * cloud_transmittance * cloud_phase(neg_LdotV) + psi_ms
+                );
+            }
+
+            // Analytical integration of the scattering over the segment, as in
+            // the aerial view LUT. Clouds have an albedo of ~1, so the
+            // scattering coefficient cancels out with the extinction.
+            let sample_transmittance = exp(-extinction * dt);
+            let contribution = transmittance * (1.0 - sample_transmittance);
+            inscattering += luminance * contribution;
+            weighted_t += t_i * contribution;
+            total_weight += contribution;
+
+            transmittance *= sample_transmittance;
+            if transmittance < 0.01 {
+                break;
+            }
+        }
+
+        // Attenuate the clouds by the atmosphere between them and the camera
+        let cloud_t = select(bounds.x, weighted_t / total_weight, total_weight > 0.0);
+        inscattering *= sample_transmittance_lut_segment(r, mu, cloud_t) * view.exposure;
+        weighted_t = cloud_t;
+    }
+
+    var result = vec4(inscattering, 1.0 - transmittance);
+
+    if clouds.temporal_blend > 0.0 {
+        // Rays that miss the cloud layer are reprojected as directions, as if
+        // they hit something infinitely far away.
+        var reprojected = vec4(ray_dir, 0.0);
+        if bounds.y > bounds.x {
+            reprojected = vec4(view.world_position + ray_dir * (weighted_t / settings.scene_units_to_m), 1.0);
+        }
+        let previous_clip = clouds.previous_clip_from_world * reprojected;
+        let previous_uv = ndc_to_uv(previous_clip.xy / previous_clip.w);
+        if previous_clip.w > 0.0 && all(previous_uv >= vec2(0.0)) && all(previous_uv <= vec2(1.0)) {
+            let history = textureSampleLevel(clouds_texture, clouds_sampler, previous_uv, 0.0);
+            result = mix(result, history, clouds.temporal_blend);
+        }
+    }
+
+    textureStore(clouds_out, idx.xy, result);
+}
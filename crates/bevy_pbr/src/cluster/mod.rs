@@ -6,7 +6,7 @@ use bevy_core_pipeline::core_3d::Camera3d;
 use bevy_ecs::{
     component::Component,
     entity::{hash_map::EntityHashMap, Entity},
-    query::{With, Without},
+    query::{Has, With, Without},
     reflect::ReflectComponent,
     resource::Resource,
     system::{Commands, Query, Res},
@@ -16,7 +16,7 @@ use bevy_math::{uvec4, AspectRatio, UVec2, UVec3, UVec4, Vec3Swizzles as _, Vec4
 use bevy_platform_support::collections::HashSet;
 use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_render::{
-    camera::Camera,
+    camera::{Camera, Multiview},
     render_resource::{
         BindingResource, BufferBindingType, ShaderSize as _, ShaderType, StorageBuffer,
         UniformBuffer,
@@ -161,8 +161,8 @@ pub struct GpuClusterableObject {
     pub(crate) spot_light_tan_angle: f32,
     pub(crate) soft_shadow_size: f32,
     pub(crate) shadow_map_near_z: f32,
-    pub(crate) pad_a: f32,
-    pub(crate) pad_b: f32,
+    pub(crate) contact_shadow_length: f32,
+    pub(crate) contact_shadow_thickness: f32,
 }
 
 pub enum GpuClusterableObjects {
@@ -382,14 +382,23 @@ impl Clusters {
 
 pub fn add_clusters(
     mut commands: Commands,
-    cameras: Query<(Entity, Option<&ClusterConfig>, &Camera), (Without<Clusters>, With<Camera3d>)>,
+    cameras: Query<
+        (Entity, Option<&ClusterConfig>, &Camera, Has<Multiview>),
+        (Without<Clusters>, With<Camera3d>),
+    >,
 ) {
-    for (entity, config, camera) in &cameras {
+    for (entity, config, camera, multiview) in &cameras {
         if !camera.is_active {
             continue;
         }
 
-        let config = config.copied().unwrap_or_default();
+        // The clusters are computed for the camera, not for each eye of a multiview camera, so
+        // multiview cameras default to a single cluster that both eyes can share.
+        let config = config.copied().unwrap_or(if multiview {
+            ClusterConfig::Single
+        } else {
+            ClusterConfig::default()
+        });
         // actual settings here don't matter - they will be overwritten in
         // `assign_objects_to_clusters``
         commands
@@ -0,0 +1,59 @@
+#define_import_path bevy_pbr::debug_view
+
+// Debug views that replace the shaded color of a mesh
+//
+// `DEBUG_VIEW_OVERDRAW` and `DEBUG_VIEW_MESH_ID` are set by the mesh pipeline,
+// and `DEBUG_VIEW_SHADER_COMPLEXITY` and `DEBUG_VIEW_MATERIAL_TYPE` by the
+// material pipeline, which sets them to the number of shader defs and to a
+// hash of the material type respectively.
+
+#import bevy_pbr::{
+    mesh_bindings::mesh,
+    utils::rand_f,
+}
+#import bevy_render::{
+    color_operations::hsv_to_rgb,
+    maths::PI_2,
+}
+
+// The color that each fragment adds to the pixel in the overdraw view. Eight
+// layers of overdraw reach full red.
+const OVERDRAW_COLOR: vec3<f32> = vec3(0.125, 0.04, 0.01);
+
+// The number of shader defs at which the shader complexity view is white.
+const MAX_SHADER_COMPLEXITY: f32 = 48.0;
+
+// Returns a bright color that is unique, most of the time, to the given ID.
+fn id_color(id: u32) -> vec3<f32> {
+    var rng = id;
+    return hsv_to_rgb(vec3(rand_f(&rng) * PI_2, 0.8, 0.9));
+}
+
+// Maps 0 to green, 0.5 to red and 1 to white.
+fn heat_color(t: f32) -> vec3<f32> {
+    let heat = saturate(t) * 2.0;
+    if heat < 1.0 {
+        return mix(vec3(0.0, 1.0, 0.0), vec3(1.0, 0.0, 0.0), heat);
+    }
+    return mix(vec3(1.0, 0.0, 0.0), vec3(1.0), heat - 1.0);
+}
+
+// Returns the color that the current debug view draws for a fragment of the
+// given mesh instance in place of its shaded color.
+fn debug_view_color(instance_index: u32, color: vec4<f32>) -> vec4<f32> {
+    var debug_color = color;
+#ifdef DEBUG_VIEW_OVERDRAW
+    debug_color = vec4(OVERDRAW_COLOR, 1.0);
+#endif
+#ifdef DEBUG_VIEW_SHADER_COMPLEXITY
+    debug_color = vec4(heat_color(f32(#{DEBUG_VIEW_SHADER_COMPLEXITY}u) / MAX_SHADER_COMPLEXITY), 1.0);
+#endif
+#ifdef DEBUG_VIEW_MESH_ID
+    // Meshes share vertex buffers, but never their first vertex.
+    debug_color = vec4(id_color(mesh[instance_index].first_vertex_index), 1.0);
+#endif
+#ifdef DEBUG_VIEW_MATERIAL_TYPE
+    debug_color = vec4(id_color(#{DEBUG_VIEW_MATERIAL_TYPE}u), 1.0);
+#endif
+    return debug_color;
+}
@@ -0,0 +1,88 @@
+//! Debug views for diagnosing fill rate and batching problems.
+
+mod statistics;
+
+pub use statistics::*;
+
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, weak_handle, Handle};
+use bevy_ecs::{component::Component, query::With, reflect::ReflectComponent};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    camera::Camera,
+    extract_component::{ExtractComponent, ExtractComponentPlugin},
+    render_resource::Shader,
+};
+
+use crate::MeshPipelineKey;
+
+const DEBUG_VIEW_SHADER_HANDLE: Handle<Shader> =
+    weak_handle!("5d0f3a1e-8c47-4b9a-a2e6-1f7c9b3d4e85");
+
+/// Adds support for the [`DebugView`] camera component.
+pub struct DebugViewPlugin;
+
+impl Plugin for DebugViewPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            DEBUG_VIEW_SHADER_HANDLE,
+            "debug_view.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<DebugView>()
+            .add_plugins(ExtractComponentPlugin::<DebugView>::default());
+    }
+}
+
+/// Add this component to a 3D camera to draw a diagnostic visualization of
+/// each mesh in place of its shaded color.
+///
+/// Debug views are drawn by the forward main passes of [`Material`] pipelines.
+/// Meshes drawn by the deferred lighting pass or as meshlets keep their usual
+/// shading. On cameras with [`Camera::hdr`] enabled, the debug colors are
+/// tonemapped like any other color.
+///
+/// See [`MaterialStatisticsPlugin`] for the number of draws and instances of
+/// each material.
+///
+/// [`Material`]: crate::Material
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Component, Reflect, ExtractComponent,
+)]
+#[extract_component_filter(With<Camera>)]
+#[reflect(Component, Default, Debug, PartialEq)]
+pub enum DebugView {
+    /// Every fragment of every mesh, whether or not it ends up hidden behind
+    /// another, adds a little red to its pixel, so that areas that are shaded
+    /// many times over stand out.
+    #[default]
+    Overdraw,
+    /// Colors each mesh by the complexity of its pipeline, from green for the
+    /// simplest to red and then white.
+    ///
+    /// The complexity is estimated from the number of shader defs that the
+    /// pipeline was specialized with, which grows with the number of features
+    /// the shader enables.
+    ShaderComplexity,
+    /// Gives every mesh asset a distinct color.
+    MeshId,
+    /// Gives every [`Material`] type a distinct color.
+    ///
+    /// All materials of the same type have the same color, whatever their
+    /// settings.
+    ///
+    /// [`Material`]: crate::Material
+    MaterialType,
+}
+
+/// Returns the [`MeshPipelineKey`] bits of a [`DebugView`].
+pub const fn debug_view_pipeline_key(debug_view: DebugView) -> MeshPipelineKey {
+    match debug_view {
+        DebugView::Overdraw => MeshPipelineKey::DEBUG_VIEW_OVERDRAW,
+        DebugView::ShaderComplexity => MeshPipelineKey::DEBUG_VIEW_SHADER_COMPLEXITY,
+        DebugView::MeshId => MeshPipelineKey::DEBUG_VIEW_MESH_ID,
+        DebugView::MaterialType => MeshPipelineKey::DEBUG_VIEW_MATERIAL_TYPE,
+    }
+}
@@ -0,0 +1,297 @@
+use alloc::sync::Arc;
+use core::mem;
+use std::sync::Mutex;
+
+use bevy_app::{App, Plugin, PreUpdate};
+use bevy_asset::{AssetId, UntypedAssetId};
+use bevy_core_pipeline::core_3d::{AlphaMask3d, Opaque3d};
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::{
+    resource::Resource,
+    schedule::IntoSystemConfigs,
+    system::{Res, ResMut},
+};
+use bevy_platform_support::collections::{HashMap, HashSet};
+use bevy_render::{
+    render_phase::{BinnedPhaseItem, BinnedRenderPhase, ViewBinnedRenderPhases},
+    Render, RenderApp, RenderSet,
+};
+
+use crate::{Material, RenderMaterialInstances};
+
+/// Counts the draws and instances of every material rendered each frame, and
+/// makes them available in the [`MaterialRenderStatistics`] resource.
+///
+/// Counts are collected from the binned opaque and alpha mask phases of every
+/// view. Transparent meshes, shadows and prepasses aren't counted.
+#[derive(Default)]
+pub struct MaterialStatisticsPlugin;
+
+impl Plugin for MaterialStatisticsPlugin {
+    fn build(&self, app: &mut App) {
+        let mutex = MaterialStatisticsMutex::default();
+        app.init_resource::<MaterialRenderStatistics>()
+            .insert_resource(mutex.clone())
+            .add_systems(PreUpdate, sync_material_render_statistics);
+
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .insert_resource(mutex)
+                .init_resource::<RenderMaterialStatistics>()
+                .add_systems(
+                    Render,
+                    publish_material_render_statistics.in_set(RenderSet::Cleanup),
+                );
+        }
+    }
+}
+
+/// The number of draws and instances of one material in a frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MaterialStatistics {
+    /// The number of times the material was drawn.
+    ///
+    /// Each bin of meshes that are drawn together counts as one draw of every
+    /// material used in it, however many of its meshes use that material.
+    /// Each mesh that can't be batched counts as one draw of its material.
+    pub draws: u32,
+    /// The number of mesh instances drawn with the material.
+    pub instances: u32,
+}
+
+/// The draws and instances of every material in the most recently rendered
+/// frame, summed over all views.
+///
+/// This is only updated when the [`MaterialStatisticsPlugin`] is added.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct MaterialRenderStatistics {
+    materials: HashMap<UntypedAssetId, MaterialStatistics>,
+}
+
+impl MaterialRenderStatistics {
+    /// Returns the statistics of a material, or `None` if it wasn't drawn.
+    pub fn get(&self, material: impl Into<UntypedAssetId>) -> Option<MaterialStatistics> {
+        self.materials.get(&material.into()).copied()
+    }
+
+    /// Returns the statistics of every material that was drawn.
+    pub fn iter(&self) -> impl Iterator<Item = (UntypedAssetId, MaterialStatistics)> + '_ {
+        self.materials
+            .iter()
+            .map(|(material, statistics)| (*material, *statistics))
+    }
+
+    /// Returns the sum of the statistics of every material.
+    pub fn total(&self) -> MaterialStatistics {
+        self.materials
+            .values()
+            .fold(MaterialStatistics::default(), |total, statistics| {
+                MaterialStatistics {
+                    draws: total.draws + statistics.draws,
+                    instances: total.instances + statistics.instances,
+                }
+            })
+    }
+}
+
+/// Hands material statistics from the render world to the main world.
+#[derive(Resource, Clone, Default)]
+struct MaterialStatisticsMutex(Arc<Mutex<Option<HashMap<UntypedAssetId, MaterialStatistics>>>>);
+
+/// The material statistics of the frame being rendered, which every
+/// [`MaterialPlugin`](crate::MaterialPlugin) adds its materials to.
+#[derive(Resource, Default, Deref, DerefMut)]
+pub(crate) struct RenderMaterialStatistics(HashMap<UntypedAssetId, MaterialStatistics>);
+
+/// Counts the draws and instances of materials of type `M` in every view.
+pub(crate) fn collect_material_render_statistics<M: Material>(
+    mut statistics: ResMut<RenderMaterialStatistics>,
+    render_material_instances: Res<RenderMaterialInstances<M>>,
+    opaque_phases: Res<ViewBinnedRenderPhases<Opaque3d>>,
+    alpha_mask_phases: Res<ViewBinnedRenderPhases<AlphaMask3d>>,
+) {
+    for phase in opaque_phases.values() {
+        count_binned_phase(phase, &render_material_instances, &mut statistics);
+    }
+    for phase in alpha_mask_phases.values() {
+        count_binned_phase(phase, &render_material_instances, &mut statistics);
+    }
+}
+
+fn count_binned_phase<BPI: BinnedPhaseItem, M: Material>(
+    phase: &BinnedRenderPhase<BPI>,
+    render_material_instances: &RenderMaterialInstances<M>,
+    statistics: &mut RenderMaterialStatistics,
+) {
+    // Entities in a bin share a mesh and are drawn together, but with bindless
+    // materials they may not share a material.
+    let mut bin_materials = HashSet::<AssetId<M>>::default();
+    let bins = phase
+        .multidrawable_mesh_values
+        .values()
+        .flat_map(HashMap::values)
+        .chain(phase.batchable_mesh_values.values());
+    for bin in bins {
+        bin_materials.clear();
+        for main_entity in bin.entities() {
+            let Some(material) = render_material_instances.get(main_entity) else {
+                continue;
+            };
+            let material_statistics = statistics.entry(material.untyped()).or_default();
+            material_statistics.instances += 1;
+            if bin_materials.insert(*material) {
+                material_statistics.draws += 1;
+            }
+        }
+    }
+
+    for unbatchables in phase.unbatchable_mesh_values.values() {
+        for main_entity in unbatchables.entities.keys() {
+            let Some(material) = render_material_instances.get(main_entity) else {
+                continue;
+            };
+            let material_statistics = statistics.entry(material.untyped()).or_default();
+            material_statistics.draws += 1;
+            material_statistics.instances += 1;
+        }
+    }
+}
+
+fn publish_material_render_statistics(
+    mut statistics: ResMut<RenderMaterialStatistics>,
+    mutex: Res<MaterialStatisticsMutex>,
+) {
+    let statistics = mem::take(&mut statistics.0);
+    if let Ok(mut published) = mutex.0.lock() {
+        *published = Some(statistics);
+    }
+}
+
+fn sync_material_render_statistics(
+    mutex: Res<MaterialStatisticsMutex>,
+    mut statistics: ResMut<MaterialRenderStatistics>,
+) {
+    if let Some(materials) = mutex.0.lock().ok().and_then(|mut v| v.take()) {
+        statistics.materials = materials;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_asset::uuid::Uuid;
+    use bevy_core_pipeline::core_3d::{Opaque3dBatchSetKey, Opaque3dBinKey};
+    use bevy_ecs::{component::Tick, entity::Entity, world::World};
+    use bevy_render::{
+        batching::gpu_preprocessing::GpuPreprocessingMode,
+        mesh::{allocator::SlabId, Mesh},
+        render_phase::{BinnedRenderPhaseType, Draw, DrawError, DrawFunctions, TrackedRenderPass},
+        render_resource::CachedRenderPipelineId,
+        sync_world::MainEntity,
+        view::RetainedViewEntity,
+    };
+    use nonmax::NonMaxU32;
+
+    use super::*;
+    use crate::StandardMaterial;
+
+    struct DrawNothing;
+
+    impl Draw<Opaque3d> for DrawNothing {
+        fn draw<'w>(
+            &mut self,
+            _: &'w World,
+            _: &mut TrackedRenderPass<'w>,
+            _: Entity,
+            _: &Opaque3d,
+        ) -> Result<(), DrawError> {
+            Ok(())
+        }
+    }
+
+    fn material(id: u128) -> AssetId<StandardMaterial> {
+        AssetId::Uuid {
+            uuid: Uuid::from_u128(id),
+        }
+    }
+
+    #[test]
+    fn count_draws_and_instances() {
+        let batch_set_key = Opaque3dBatchSetKey {
+            pipeline: CachedRenderPipelineId::INVALID,
+            draw_function: DrawFunctions::<Opaque3d>::default()
+                .write()
+                .add(DrawNothing),
+            material_bind_group_index: None,
+            vertex_slab: SlabId(NonMaxU32::ZERO),
+            index_slab: None,
+            lightmap_slab: None,
+        };
+        let bin_key = |mesh| Opaque3dBinKey {
+            asset_id: AssetId::<Mesh>::Uuid {
+                uuid: Uuid::from_u128(mesh),
+            }
+            .untyped(),
+        };
+
+        let view = RetainedViewEntity::new(MainEntity::from(Entity::from_raw(0)), None, 0);
+        let mut phases = ViewBinnedRenderPhases::<Opaque3d>::default();
+        phases.prepare_for_new_frame(view, GpuPreprocessingMode::None);
+        let phase = phases.get_mut(&view).unwrap();
+
+        let (a, b) = (material(1), material(2));
+        let mut render_material_instances = RenderMaterialInstances::<StandardMaterial>::default();
+        for (index, material, mesh, phase_type) in [
+            // One bin with both materials, which counts as a draw of each.
+            (1, Some(a), 1, BinnedRenderPhaseType::MultidrawableMesh),
+            (2, Some(a), 1, BinnedRenderPhaseType::MultidrawableMesh),
+            (3, Some(b), 1, BinnedRenderPhaseType::MultidrawableMesh),
+            (4, Some(a), 2, BinnedRenderPhaseType::BatchableMesh),
+            // Unbatchable meshes are drawn one at a time.
+            (5, Some(b), 3, BinnedRenderPhaseType::UnbatchableMesh),
+            (6, Some(b), 3, BinnedRenderPhaseType::UnbatchableMesh),
+            // Meshes with materials of other types aren't counted.
+            (7, None, 2, BinnedRenderPhaseType::BatchableMesh),
+        ] {
+            let main_entity = MainEntity::from(Entity::from_raw(index));
+            if let Some(material) = material {
+                render_material_instances.insert(main_entity, material);
+            }
+            phase.add(
+                batch_set_key.clone(),
+                bin_key(mesh),
+                (Entity::PLACEHOLDER, main_entity),
+                phase_type,
+                Tick::new(0),
+            );
+        }
+
+        let mut statistics = RenderMaterialStatistics::default();
+        count_binned_phase(phase, &render_material_instances, &mut statistics);
+        let statistics = MaterialRenderStatistics {
+            materials: statistics.0,
+        };
+
+        assert_eq!(
+            statistics.get(a),
+            Some(MaterialStatistics {
+                draws: 2,
+                instances: 3
+            })
+        );
+        assert_eq!(
+            statistics.get(b),
+            Some(MaterialStatistics {
+                draws: 3,
+                instances: 3
+            })
+        );
+        assert_eq!(statistics.get(material(3)), None);
+        assert_eq!(
+            statistics.total(),
+            MaterialStatistics {
+                draws: 5,
+                instances: 6
+            }
+        );
+    }
+}
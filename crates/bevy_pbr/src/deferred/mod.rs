@@ -1,9 +1,9 @@
 use crate::{
     graph::NodePbr, irradiance_volume::IrradianceVolume, prelude::EnvironmentMapLight,
     MeshPipeline, MeshViewBindGroup, RenderViewLightProbes, ScreenSpaceAmbientOcclusion,
-    ScreenSpaceReflectionsUniform, ViewEnvironmentMapUniformOffset, ViewLightProbesUniformOffset,
-    ViewScreenSpaceReflectionsUniformOffset, TONEMAPPING_LUT_SAMPLER_BINDING_INDEX,
-    TONEMAPPING_LUT_TEXTURE_BINDING_INDEX,
+    ScreenSpaceGlobalIllumination, ScreenSpaceReflectionsUniform, ViewEnvironmentMapUniformOffset,
+    ViewLightProbesUniformOffset, ViewScreenSpaceReflectionsUniformOffset,
+    TONEMAPPING_LUT_SAMPLER_BINDING_INDEX, TONEMAPPING_LUT_TEXTURE_BINDING_INDEX,
 };
 use crate::{
     DistanceFog, MeshPipelineKey, ShadowFilteringMethod, ViewFogUniformOffset,
@@ -296,6 +296,9 @@ impl SpecializedRenderPipeline for DeferredLightingLayout {
         if key.contains(MeshPipelineKey::SCREEN_SPACE_AMBIENT_OCCLUSION) {
             shader_defs.push("SCREEN_SPACE_AMBIENT_OCCLUSION".into());
         }
+        if key.contains(MeshPipelineKey::SCREEN_SPACE_GLOBAL_ILLUMINATION) {
+            shader_defs.push("SCREEN_SPACE_GLOBAL_ILLUMINATION".into());
+        }
 
         if key.contains(MeshPipelineKey::ENVIRONMENT_MAP) {
             shader_defs.push("ENVIRONMENT_MAP".into());
@@ -395,6 +398,7 @@ impl SpecializedRenderPipeline for DeferredLightingLayout {
             multisample: MultisampleState::default(),
             push_constant_ranges: vec![],
             zero_initialize_workgroup_memory: false,
+            multiview: None,
         }
     }
 }
@@ -441,6 +445,7 @@ pub fn prepare_deferred_lighting_pipelines(
             Option<&ShadowFilteringMethod>,
             (
                 Has<ScreenSpaceAmbientOcclusion>,
+                Has<ScreenSpaceGlobalIllumination>,
                 Has<ScreenSpaceReflectionsUniform>,
                 Has<DistanceFog>,
             ),
@@ -461,7 +466,7 @@ pub fn prepare_deferred_lighting_pipelines(
         tonemapping,
         dither,
         shadow_filter_method,
-        (ssao, ssr, distance_fog),
+        (ssao, ssgi, ssr, distance_fog),
         (normal_prepass, depth_prepass, motion_vector_prepass),
         has_environment_maps,
         has_irradiance_volumes,
@@ -510,6 +515,9 @@ pub fn prepare_deferred_lighting_pipelines(
         if ssao {
             view_key |= MeshPipelineKey::SCREEN_SPACE_AMBIENT_OCCLUSION;
         }
+        if ssgi {
+            view_key |= MeshPipelineKey::SCREEN_SPACE_GLOBAL_ILLUMINATION;
+        }
         if ssr {
             view_key |= MeshPipelineKey::SCREEN_SPACE_REFLECTIONS;
         }
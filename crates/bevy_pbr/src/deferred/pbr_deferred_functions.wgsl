@@ -18,7 +18,10 @@
 #endif
 
 #ifdef MOTION_VECTOR_PREPASS
-    #import bevy_pbr::pbr_prepass_functions::calculate_motion_vector
+    #import bevy_pbr::{
+        pbr_prepass_functions::calculate_motion_vector,
+        mesh_functions::limit_motion_vector,
+    }
 #endif
 
 // Creates the deferred gbuffer from a PbrInput.
@@ -144,8 +147,15 @@ fn deferred_output(in: VertexOutput, pbr_input: PbrInput) -> FragmentOutput {
 #ifdef MESHLET_MESH_MATERIAL_PASS
     out.motion_vector = in.motion_vector;
 #else
-    out.motion_vector = calculate_motion_vector(in.world_position, in.previous_world_position);
+    out.motion_vector = limit_motion_vector(
+        calculate_motion_vector(in.world_position, in.previous_world_position),
+        in.motion_vector_limit
+    );
+#endif
 #endif
+    // outline id if required
+#ifdef OUTLINE_ID_PREPASS
+    out.outline_id = in.outline_id;
 #endif
 
     return out;
@@ -27,6 +27,7 @@ pub mod experimental {
 mod atmosphere;
 mod cluster;
 mod components;
+mod debug_view;
 pub mod decal;
 pub mod deferred;
 mod extended_material;
@@ -42,7 +43,9 @@ mod pbr_material;
 mod prepass;
 mod render;
 mod ssao;
+mod ssgi;
 mod ssr;
+mod terrain;
 mod volumetric_fog;
 
 use crate::material_bind_groups::FallbackBindlessResources;
@@ -52,6 +55,7 @@ use bevy_color::{Color, LinearRgba};
 pub use atmosphere::*;
 pub use cluster::*;
 pub use components::*;
+pub use debug_view::*;
 pub use decal::clustered::ClusteredDecalPlugin;
 pub use extended_material::*;
 pub use fog::*;
@@ -65,7 +69,9 @@ pub use pbr_material::*;
 pub use prepass::*;
 pub use render::*;
 pub use ssao::*;
+pub use ssgi::*;
 pub use ssr::*;
+pub use terrain::*;
 pub use volumetric_fog::{FogVolume, VolumetricFog, VolumetricFogPlugin, VolumetricLight};
 
 /// The PBR prelude.
@@ -94,6 +100,11 @@ pub mod graph {
         ShadowPass,
         /// Label for the screen space ambient occlusion render node.
         ScreenSpaceAmbientOcclusion,
+        /// Label for the screen space global illumination render node.
+        ScreenSpaceGlobalIllumination,
+        /// Label for the node that copies the lit color for the next frame's
+        /// screen space global illumination.
+        ScreenSpaceGlobalIlluminationCaptureColor,
         DeferredLightingPass,
         /// Label for the volumetric lighting pass.
         VolumetricFog,
@@ -313,6 +324,7 @@ impl Plugin for PbrPlugin {
             .register_type::<CascadeShadowConfig>()
             .register_type::<Cascades>()
             .register_type::<CascadesVisibleEntities>()
+            .register_type::<ContactShadows>()
             .register_type::<VisibleMeshEntities>()
             .register_type::<ClusterConfig>()
             .register_type::<CubemapVisibleEntities>()
@@ -320,6 +332,8 @@ impl Plugin for PbrPlugin {
             .register_type::<DirectionalLightShadowMap>()
             .register_type::<NotShadowCaster>()
             .register_type::<NotShadowReceiver>()
+            .register_type::<NoMotionBlur>()
+            .register_type::<MaxMotionVelocity>()
             .register_type::<PointLight>()
             .register_type::<PointLightShadowMap>()
             .register_type::<SpotLight>()
@@ -351,6 +365,7 @@ impl Plugin for PbrPlugin {
                 },
                 VolumetricFogPlugin,
                 ScreenSpaceReflectionsPlugin,
+                ScreenSpaceGlobalIlluminationPlugin,
                 ClusteredDecalPlugin,
             ))
             .add_plugins((
@@ -359,6 +374,8 @@ impl Plugin for PbrPlugin {
                 SyncComponentPlugin::<PointLight>::default(),
                 SyncComponentPlugin::<SpotLight>::default(),
                 ExtractComponentPlugin::<AmbientLight>::default(),
+                TerrainPlugin,
+                DebugViewPlugin,
             ))
             .add_plugins(AtmospherePlugin)
             .configure_sets(
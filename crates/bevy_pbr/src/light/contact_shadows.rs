@@ -0,0 +1,44 @@
+use super::*;
+
+/// Add this component to a [`PointLight`], [`SpotLight`], or
+/// [`DirectionalLight`] to enable screen-space contact shadows for it.
+///
+/// Contact shadows recover the small-scale shadowing detail that shadow maps
+/// lose at typical resolutions, such as the shadows where an object meets the
+/// ground. They're computed by ray marching the depth prepass from each
+/// fragment toward the light and are combined with the regular shadow map
+/// result, if any.
+///
+/// Because contact shadows are computed in screen space, they require a
+/// [`DepthPrepass`](bevy_core_pipeline::prepass::DepthPrepass) on the camera
+/// (or deferred rendering). Without one, this component has no effect. Only
+/// geometry that's visible on screen can cast contact shadows.
+///
+/// Contact shadows are somewhat noisy; consider pairing them with temporal
+/// antialiasing (TAA).
+#[derive(Clone, Copy, Component, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub struct ContactShadows {
+    /// The length of the ray marched from each fragment toward the light, in
+    /// world units.
+    ///
+    /// Longer rays find more occluders but make the steps coarser, since the
+    /// number of steps is fixed.
+    pub length: f32,
+
+    /// The assumed thickness of every depth buffer sample, in world units.
+    ///
+    /// The ray is considered occluded only if it passes behind the depth
+    /// buffer by less than this amount. This prevents thin objects in front
+    /// of the fragment from casting infinitely long shadows.
+    pub thickness: f32,
+}
+
+impl Default for ContactShadows {
+    fn default() -> Self {
+        Self {
+            length: 0.3,
+            thickness: 0.05,
+        }
+    }
+}
@@ -41,6 +41,9 @@ use super::*;
 /// To modify the cascade setup, such as the number of cascades or the maximum shadow distance,
 /// change the [`CascadeShadowConfig`] component of the entity with the [`DirectionalLight`].
 ///
+/// To recover small-scale shadow detail that the shadow maps miss, add the
+/// [`ContactShadows`] component to the entity with the [`DirectionalLight`].
+///
 /// To control the resolution of the shadow maps, use the [`DirectionalLightShadowMap`] resource:
 ///
 /// ```
@@ -80,6 +83,9 @@ pub struct DirectionalLight {
     /// Note that shadows are rather expensive and become more so with every
     /// light that casts them. In general, it's best to aggressively limit the
     /// number of lights with shadows enabled to one or two at most.
+    ///
+    /// To recover small-scale shadow detail that the shadow maps miss, add the
+    /// [`ContactShadows`] component to the entity with the light.
     pub shadows_enabled: bool,
 
     /// Whether soft shadows are enabled, and if so, the size of the light.
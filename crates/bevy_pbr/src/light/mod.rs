@@ -24,6 +24,8 @@ use crate::*;
 
 mod ambient_light;
 pub use ambient_light::AmbientLight;
+mod contact_shadows;
+pub use contact_shadows::ContactShadows;
 
 mod point_light;
 pub use point_light::PointLight;
@@ -51,6 +51,9 @@ pub struct PointLight {
     pub radius: f32,
 
     /// Whether this light casts shadows.
+    ///
+    /// To recover small-scale shadow detail that the shadow maps miss, add the
+    /// [`ContactShadows`] component to the entity with the light.
     pub shadows_enabled: bool,
 
     /// Whether soft shadows are enabled.
@@ -41,6 +41,9 @@ pub struct SpotLight {
     /// Note that shadows are rather expensive and become more so with every
     /// light that casts them. In general, it's best to aggressively limit the
     /// number of lights with shadows enabled to one or two at most.
+    ///
+    /// To recover small-scale shadow detail that the shadow maps miss, add the
+    /// [`ContactShadows`] component to the entity with the light.
     pub shadows_enabled: bool,
 
     /// Whether soft shadows are enabled.
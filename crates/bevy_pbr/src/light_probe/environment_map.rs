@@ -25,7 +25,9 @@
 //! one for the specular component, according to the [split-sum approximation].
 //! To pre-filter your environment map, you can use the [glTF IBL Sampler] or
 //! its [artist-friendly UI]. The diffuse map uses the Lambertian distribution,
-//! while the specular map uses the GGX distribution.
+//! while the specular map uses the GGX distribution. Equirectangular HDR
+//! panoramas can also be pre-filtered at runtime, with an
+//! [`EquirectangularEnvironmentMap`](crate::equirectangular::EquirectangularEnvironmentMap).
 //!
 //! The Khronos Group has [several pre-filtered environment maps] available for
 //! you to use.
@@ -0,0 +1,652 @@
+//! Environment maps generated from equirectangular panoramas on the GPU.
+//!
+//! Skyboxes and environment maps are cubemaps, while HDR panoramas, such as those loaded from
+//! `.hdr` and `.exr` files, are usually equirectangular. Adding an
+//! [`EquirectangularEnvironmentMap`] to a camera or a [`crate::LightProbe`] converts the panorama
+//! into a skybox cubemap and the pre-filtered diffuse and specular maps of an
+//! [`EnvironmentMapLight`] once it's loaded, with a few compute passes.
+//!
+//! To convert panoramas ahead of time instead, see
+//! [`EquirectangularToCubemapTransformer`](bevy_image::EquirectangularToCubemapTransformer),
+//! which does the same conversion on the CPU as an asset processing step.
+
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::{load_internal_asset, weak_handle, AssetEvent, AssetId, Assets, Handle};
+use bevy_core_pipeline::{core_3d::Camera3d, Skybox};
+use bevy_ecs::{
+    component::Component,
+    entity::Entity,
+    event::EventReader,
+    observer::Trigger,
+    query::Has,
+    reflect::ReflectComponent,
+    resource::Resource,
+    schedule::IntoSystemConfigs,
+    system::{Commands, Query, Res, ResMut},
+    world::{FromWorld, OnRemove, World},
+};
+use bevy_image::{EquirectangularToCubemapSettings, Image};
+use bevy_platform_support::collections::HashSet;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    render_asset::{RenderAssetUsages, RenderAssets},
+    render_graph::{self, RenderGraph, RenderLabel},
+    render_resource::{
+        binding_types::{
+            sampler, texture_2d, texture_2d_array, texture_cube, texture_storage_2d_array,
+            uniform_buffer,
+        },
+        BindGroup, BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries,
+        CachedComputePipelineId, ComputePassDescriptor, ComputePipelineDescriptor,
+        DynamicUniformBuffer, Extent3d, FilterMode, PipelineCache, Sampler, SamplerBindingType,
+        SamplerDescriptor, Shader, ShaderStages, ShaderType, SpecializedComputePipeline,
+        SpecializedComputePipelines, StorageTextureAccess, TextureDimension, TextureFormat,
+        TextureSampleType, TextureUsages, TextureView, TextureViewDescriptor, TextureViewDimension,
+    },
+    renderer::{RenderContext, RenderDevice, RenderQueue},
+    texture::GpuImage,
+    Extract, ExtractSchedule, Render, RenderApp, RenderSet,
+};
+
+use super::environment_map::EnvironmentMapLight;
+
+/// A handle to the shader that converts equirectangular panoramas into cubemaps.
+pub const EQUIRECTANGULAR_SHADER_HANDLE: Handle<Shader> =
+    weak_handle!("3e1b7c52-9a4d-4f0e-8c61-d2a5f7b90e34");
+
+/// The size of the workgroups of `equirectangular.wgsl`, in both dimensions.
+const WORKGROUP_SIZE: u32 = 8;
+
+/// Generates the [`Skybox`] and the [`EnvironmentMapLight`] of the entity from an
+/// equirectangular panorama, on the GPU.
+///
+/// Once [`EquirectangularEnvironmentMap::image`] is loaded, a
+/// [`GeneratedEquirectangularEnvironmentMap`] holding the generated cubemaps is added to the
+/// entity, along with an [`EnvironmentMapLight`] that uses them and, for 3D cameras, a [`Skybox`].
+/// The cubemaps are filled in by the GPU during the next frame, and regenerated whenever the
+/// image or the settings change, or the image is modified, for instance when it's hot reloaded.
+///
+/// Removing the component also removes the generated components.
+///
+/// See [`crate::light_probe::equirectangular`] for more information.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub struct EquirectangularEnvironmentMap {
+    /// The equirectangular panorama, with its center facing forward, towards -Z.
+    pub image: Handle<Image>,
+
+    /// The sizes of the generated cubemaps, and the number of samples to filter them with.
+    pub settings: EquirectangularToCubemapSettings,
+
+    /// The intensity of the generated [`EnvironmentMapLight`], and the brightness of the
+    /// generated [`Skybox`], in [cd/m^2](https://en.wikipedia.org/wiki/Candela_per_square_metre).
+    pub intensity: f32,
+}
+
+impl Default for EquirectangularEnvironmentMap {
+    fn default() -> Self {
+        Self {
+            image: Handle::default(),
+            settings: EquirectangularToCubemapSettings::default(),
+            intensity: 0.0,
+        }
+    }
+}
+
+/// The cubemaps generated from an [`EquirectangularEnvironmentMap`].
+///
+/// Remove this component to regenerate them. The generated [`EnvironmentMapLight`] and [`Skybox`]
+/// are only updated when the cubemaps are regenerated, so change
+/// [`EquirectangularEnvironmentMap::intensity`] on them directly afterwards.
+#[derive(Component, Clone, Debug)]
+pub struct GeneratedEquirectangularEnvironmentMap {
+    /// The panorama that the cubemaps were generated from.
+    pub source: AssetId<Image>,
+    /// The settings that the cubemaps were generated with.
+    pub settings: EquirectangularToCubemapSettings,
+    /// The skybox cubemap, with a full mip chain.
+    pub skybox: Handle<Image>,
+    /// The diffuse map of the [`EnvironmentMapLight`].
+    pub diffuse_map: Handle<Image>,
+    /// The specular map of the [`EnvironmentMapLight`].
+    pub specular_map: Handle<Image>,
+}
+
+/// A render graph label for the node that generates the cubemaps of
+/// [`EquirectangularEnvironmentMap`]s.
+#[derive(Debug, Hash, PartialEq, Eq, Clone, RenderLabel)]
+pub struct EquirectangularEnvironmentMapLabel;
+
+/// Adds support for [`EquirectangularEnvironmentMap`]s.
+pub struct EquirectangularEnvironmentMapPlugin;
+
+impl Plugin for EquirectangularEnvironmentMapPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            EQUIRECTANGULAR_SHADER_HANDLE,
+            "equirectangular.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<EquirectangularEnvironmentMap>()
+            .add_systems(PostUpdate, generate_equirectangular_environment_maps)
+            .add_observer(remove_generated_environment_map);
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app
+            .init_resource::<SpecializedComputePipelines<EquirectangularPipeline>>()
+            .init_resource::<EquirectangularJobs>()
+            .add_systems(ExtractSchedule, extract_equirectangular_environment_maps)
+            .add_systems(
+                Render,
+                prepare_equirectangular_jobs.in_set(RenderSet::PrepareBindGroups),
+            );
+
+        let mut render_graph = render_app.world_mut().resource_mut::<RenderGraph>();
+        render_graph.add_node(EquirectangularEnvironmentMapLabel, EquirectangularNode);
+        render_graph.add_node_edge(
+            EquirectangularEnvironmentMapLabel,
+            bevy_render::graph::CameraDriverLabel,
+        );
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        render_app.init_resource::<EquirectangularPipeline>();
+    }
+}
+
+/// Creates the cubemaps of [`EquirectangularEnvironmentMap`]s whose image has loaded, and the
+/// [`EnvironmentMapLight`]s and [`Skybox`]es that use them.
+pub fn generate_equirectangular_environment_maps(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    environment_maps: Query<(
+        Entity,
+        &EquirectangularEnvironmentMap,
+        Option<&GeneratedEquirectangularEnvironmentMap>,
+        Has<Camera3d>,
+    )>,
+) {
+    for (entity, environment_map, generated, is_camera) in &environment_maps {
+        let up_to_date = generated.is_some_and(|generated| {
+            generated.source == environment_map.image.id()
+                && generated.settings == environment_map.settings
+        });
+        if up_to_date || !images.contains(&environment_map.image) {
+            continue;
+        }
+
+        let settings = environment_map.settings;
+        let mut cubemap = |face_size: u32, mip_level_count: u32| {
+            let mut image = Image::new_uninit(
+                Extent3d {
+                    width: face_size.max(1),
+                    height: face_size.max(1),
+                    depth_or_array_layers: 6,
+                },
+                TextureDimension::D2,
+                TextureFormat::Rgba16Float,
+                RenderAssetUsages::default(),
+            );
+            image.texture_descriptor.mip_level_count = mip_level_count;
+            image.texture_descriptor.usage |= TextureUsages::STORAGE_BINDING;
+            image.texture_view_descriptor = Some(TextureViewDescriptor {
+                dimension: Some(TextureViewDimension::Cube),
+                ..Default::default()
+            });
+            image.sampler = bevy_image::ImageSampler::linear();
+            images.add(image)
+        };
+        let generated = GeneratedEquirectangularEnvironmentMap {
+            source: environment_map.image.id(),
+            settings,
+            skybox: cubemap(settings.face_size, settings.mip_level_count()),
+            diffuse_map: cubemap(settings.diffuse_face_size, 1),
+            specular_map: cubemap(
+                settings.specular_face_size,
+                settings.specular_mip_level_count(),
+            ),
+        };
+
+        let mut entity_commands = commands.entity(entity);
+        entity_commands.insert(EnvironmentMapLight {
+            diffuse_map: generated.diffuse_map.clone(),
+            specular_map: generated.specular_map.clone(),
+            intensity: environment_map.intensity,
+            ..Default::default()
+        });
+        if is_camera {
+            entity_commands.insert(Skybox {
+                image: generated.skybox.clone(),
+                brightness: environment_map.intensity,
+                ..Default::default()
+            });
+        }
+        entity_commands.insert(generated);
+    }
+}
+
+/// Removes the [`GeneratedEquirectangularEnvironmentMap`], [`EnvironmentMapLight`] and [`Skybox`]
+/// generated from an [`EquirectangularEnvironmentMap`] when it's removed.
+fn remove_generated_environment_map(
+    trigger: Trigger<OnRemove, EquirectangularEnvironmentMap>,
+    generated_environment_maps: Query<(
+        &GeneratedEquirectangularEnvironmentMap,
+        Option<&EnvironmentMapLight>,
+        Option<&Skybox>,
+    )>,
+    mut commands: Commands,
+) {
+    let Ok((generated, environment_map_light, skybox)) =
+        generated_environment_maps.get(trigger.target())
+    else {
+        return;
+    };
+    let Some(mut entity_commands) = commands.get_entity(trigger.target()) else {
+        return;
+    };
+
+    // Keep the components that were replaced by others since.
+    entity_commands.try_remove::<GeneratedEquirectangularEnvironmentMap>();
+    if environment_map_light.is_some_and(|light| light.specular_map == generated.specular_map) {
+        entity_commands.try_remove::<EnvironmentMapLight>();
+    }
+    if skybox.is_some_and(|skybox| skybox.image == generated.skybox) {
+        entity_commands.try_remove::<Skybox>();
+    }
+}
+
+/// The passes of the conversion, which each have their own entry point in
+/// `equirectangular.wgsl`.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum EquirectangularPass {
+    /// Projects the panorama onto the first mip level of the skybox.
+    Convert,
+    /// Downsamples a mip level of the skybox into the next one.
+    Downsample,
+    /// Filters the skybox into the diffuse map.
+    Diffuse,
+    /// Filters the skybox into a mip level of the specular map.
+    Specular,
+}
+
+/// The settings of a diffuse or specular filtering pass.
+#[derive(ShaderType, Clone, Copy)]
+struct EquirectangularFilterUniform {
+    perceptual_roughness: f32,
+    sample_count: u32,
+    radiance_face_size: u32,
+    face_size: u32,
+}
+
+/// The bind group layouts of the passes of the conversion.
+#[derive(Resource)]
+pub struct EquirectangularPipeline {
+    convert_layout: BindGroupLayout,
+    downsample_layout: BindGroupLayout,
+    filter_layout: BindGroupLayout,
+    sampler: Sampler,
+}
+
+impl FromWorld for EquirectangularPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let output =
+            texture_storage_2d_array(TextureFormat::Rgba16Float, StorageTextureAccess::WriteOnly);
+
+        Self {
+            convert_layout: render_device.create_bind_group_layout(
+                "equirectangular convert bind group layout",
+                &BindGroupLayoutEntries::sequential(
+                    ShaderStages::COMPUTE,
+                    (
+                        texture_2d(TextureSampleType::Float { filterable: false }),
+                        output,
+                    ),
+                ),
+            ),
+            downsample_layout: render_device.create_bind_group_layout(
+                "equirectangular downsample bind group layout",
+                &BindGroupLayoutEntries::sequential(
+                    ShaderStages::COMPUTE,
+                    (
+                        texture_2d_array(TextureSampleType::Float { filterable: false }),
+                        output,
+                    ),
+                ),
+            ),
+            filter_layout: render_device.create_bind_group_layout(
+                "equirectangular filter bind group layout",
+                &BindGroupLayoutEntries::sequential(
+                    ShaderStages::COMPUTE,
+                    (
+                        texture_cube(TextureSampleType::Float { filterable: true }),
+                        output,
+                        sampler(SamplerBindingType::Filtering),
+                        uniform_buffer::<EquirectangularFilterUniform>(true),
+                    ),
+                ),
+            ),
+            sampler: render_device.create_sampler(&SamplerDescriptor {
+                label: Some("equirectangular radiance sampler"),
+                mag_filter: FilterMode::Linear,
+                min_filter: FilterMode::Linear,
+                mipmap_filter: FilterMode::Linear,
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+impl SpecializedComputePipeline for EquirectangularPipeline {
+    type Key = EquirectangularPass;
+
+    fn specialize(&self, pass: Self::Key) -> ComputePipelineDescriptor {
+        let (label, layout, shader_defs, entry_point) = match pass {
+            EquirectangularPass::Convert => (
+                "equirectangular convert pipeline",
+                &self.convert_layout,
+                vec!["CONVERT".into()],
+                "convert",
+            ),
+            EquirectangularPass::Downsample => (
+                "equirectangular downsample pipeline",
+                &self.downsample_layout,
+                vec!["DOWNSAMPLE".into()],
+                "downsample",
+            ),
+            EquirectangularPass::Diffuse => (
+                "equirectangular diffuse pipeline",
+                &self.filter_layout,
+                vec![],
+                "diffuse",
+            ),
+            EquirectangularPass::Specular => (
+                "equirectangular specular pipeline",
+                &self.filter_layout,
+                vec![],
+                "specular",
+            ),
+        };
+
+        ComputePipelineDescriptor {
+            label: Some(label.into()),
+            layout: vec![layout.clone()],
+            push_constant_ranges: vec![],
+            shader: EQUIRECTANGULAR_SHADER_HANDLE,
+            shader_defs,
+            entry_point: entry_point.into(),
+            zero_initialize_workgroup_memory: false,
+        }
+    }
+}
+
+/// A single dispatch of the conversion.
+struct EquirectangularDispatch {
+    pass: EquirectangularPass,
+    bind_group: BindGroup,
+    dynamic_offset: Option<u32>,
+    face_size: u32,
+}
+
+/// The conversions to run this frame, and the ones that already ran.
+#[derive(Resource, Default)]
+pub struct EquirectangularJobs {
+    /// The cubemaps of all [`EquirectangularEnvironmentMap`]s in the main world.
+    generated: Vec<GeneratedEquirectangularEnvironmentMap>,
+    pipelines: Option<[CachedComputePipelineId; 4]>,
+    dispatches: Vec<EquirectangularDispatch>,
+    uniforms: DynamicUniformBuffer<EquirectangularFilterUniform>,
+    /// The skyboxes that have been generated, which are skipped from then on, until their
+    /// panorama or one of their cubemaps is modified.
+    completed: HashSet<AssetId<Image>>,
+}
+
+/// Extracts the cubemaps of all [`EquirectangularEnvironmentMap`]s, whether they've been
+/// generated or not.
+///
+/// The cubemaps are generated again when their panorama or one of them is modified, as the GPU
+/// images are then prepared again.
+pub fn extract_equirectangular_environment_maps(
+    mut jobs: ResMut<EquirectangularJobs>,
+    generated_environment_maps: Extract<Query<&GeneratedEquirectangularEnvironmentMap>>,
+    mut image_events: Extract<EventReader<AssetEvent<Image>>>,
+) {
+    let jobs = &mut *jobs;
+    jobs.generated.clear();
+    jobs.generated
+        .extend(generated_environment_maps.iter().cloned());
+
+    for event in image_events.read() {
+        let AssetEvent::Modified { id } = event else {
+            continue;
+        };
+        for generated in &jobs.generated {
+            if generated.source == *id
+                || generated.skybox.id() == *id
+                || generated.diffuse_map.id() == *id
+                || generated.specular_map.id() == *id
+            {
+                jobs.completed.remove(&generated.skybox.id());
+            }
+        }
+    }
+}
+
+/// Creates the bind groups of the conversions of the [`GeneratedEquirectangularEnvironmentMap`]s
+/// that haven't been generated yet, once their images are ready.
+pub fn prepare_equirectangular_jobs(
+    mut jobs: ResMut<EquirectangularJobs>,
+    pipeline: Res<EquirectangularPipeline>,
+    mut pipelines: ResMut<SpecializedComputePipelines<EquirectangularPipeline>>,
+    pipeline_cache: Res<PipelineCache>,
+    images: Res<RenderAssets<GpuImage>>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+) {
+    let jobs = &mut *jobs;
+    jobs.dispatches.clear();
+    jobs.uniforms.clear();
+
+    let pipeline_ids = *jobs.pipelines.get_or_insert_with(|| {
+        [
+            EquirectangularPass::Convert,
+            EquirectangularPass::Downsample,
+            EquirectangularPass::Diffuse,
+            EquirectangularPass::Specular,
+        ]
+        .map(|pass| pipelines.specialize(&pipeline_cache, &pipeline, pass))
+    });
+    if pipeline_ids
+        .iter()
+        .any(|id| pipeline_cache.get_compute_pipeline(*id).is_none())
+    {
+        return;
+    }
+
+    // Forget the skyboxes that aren't in use anymore, in case their IDs are reused.
+    let EquirectangularJobs {
+        generated: generated_environment_maps,
+        completed,
+        dispatches,
+        uniforms,
+        ..
+    } = jobs;
+    completed.retain(|skybox| {
+        generated_environment_maps
+            .iter()
+            .any(|generated| generated.skybox.id() == *skybox)
+    });
+
+    // The filter passes are bound once all of their uniforms are written.
+    let mut filters = vec![];
+
+    for generated in generated_environment_maps.iter() {
+        if completed.contains(&generated.skybox.id()) {
+            continue;
+        }
+        let (Some(source), Some(skybox), Some(diffuse_map), Some(specular_map)) = (
+            images.get(generated.source),
+            images.get(&generated.skybox),
+            images.get(&generated.diffuse_map),
+            images.get(&generated.specular_map),
+        ) else {
+            continue;
+        };
+
+        dispatches.push(EquirectangularDispatch {
+            pass: EquirectangularPass::Convert,
+            bind_group: render_device.create_bind_group(
+                "equirectangular convert bind group",
+                &pipeline.convert_layout,
+                &BindGroupEntries::sequential((&source.texture_view, &mip_view(skybox, 0))),
+            ),
+            dynamic_offset: None,
+            face_size: mip_face_size(skybox, 0),
+        });
+
+        for mip_level in 1..skybox.mip_level_count {
+            dispatches.push(EquirectangularDispatch {
+                pass: EquirectangularPass::Downsample,
+                bind_group: render_device.create_bind_group(
+                    "equirectangular downsample bind group",
+                    &pipeline.downsample_layout,
+                    &BindGroupEntries::sequential((
+                        &mip_view(skybox, mip_level - 1),
+                        &mip_view(skybox, mip_level),
+                    )),
+                ),
+                dynamic_offset: None,
+                face_size: mip_face_size(skybox, mip_level),
+            });
+        }
+
+        let specular_mip_level_count = specular_map.mip_level_count;
+        let specular_mips = (0..specular_mip_level_count).map(|mip_level| {
+            let perceptual_roughness = if specular_mip_level_count > 1 {
+                mip_level as f32 / (specular_mip_level_count - 1) as f32
+            } else {
+                0.0
+            };
+            (
+                EquirectangularPass::Specular,
+                specular_map,
+                mip_level,
+                perceptual_roughness,
+            )
+        });
+
+        for (pass, output, mip_level, perceptual_roughness) in
+            [(EquirectangularPass::Diffuse, diffuse_map, 0, 0.0)]
+                .into_iter()
+                .chain(specular_mips)
+        {
+            let face_size = mip_face_size(output, mip_level);
+            let dynamic_offset = uniforms.push(&EquirectangularFilterUniform {
+                perceptual_roughness,
+                sample_count: generated.settings.sample_count.max(1),
+                radiance_face_size: skybox.size.width,
+                face_size,
+            });
+            filters.push((
+                pass,
+                skybox.texture_view.clone(),
+                mip_view(output, mip_level),
+                dynamic_offset,
+                face_size,
+            ));
+        }
+
+        completed.insert(generated.skybox.id());
+    }
+
+    uniforms.write_buffer(&render_device, &render_queue);
+    let Some(uniforms) = uniforms.binding() else {
+        return;
+    };
+
+    for (pass, radiance, output, dynamic_offset, face_size) in filters {
+        dispatches.push(EquirectangularDispatch {
+            pass,
+            bind_group: render_device.create_bind_group(
+                "equirectangular filter bind group",
+                &pipeline.filter_layout,
+                &BindGroupEntries::sequential((
+                    &radiance,
+                    &output,
+                    &pipeline.sampler,
+                    uniforms.clone(),
+                )),
+            ),
+            dynamic_offset: Some(dynamic_offset),
+            face_size,
+        });
+    }
+}
+
+/// A view of a single mip level of a cubemap, as a texture array.
+fn mip_view(image: &GpuImage, mip_level: u32) -> TextureView {
+    image.texture.create_view(&TextureViewDescriptor {
+        label: Some("equirectangular mip view"),
+        dimension: Some(TextureViewDimension::D2Array),
+        base_mip_level: mip_level,
+        mip_level_count: Some(1),
+        ..Default::default()
+    })
+}
+
+fn mip_face_size(image: &GpuImage, mip_level: u32) -> u32 {
+    (image.size.width >> mip_level).max(1)
+}
+
+/// Runs the conversions prepared by [`prepare_equirectangular_jobs`].
+struct EquirectangularNode;
+
+impl render_graph::Node for EquirectangularNode {
+    fn run(
+        &self,
+        _graph: &mut render_graph::RenderGraphContext,
+        render_context: &mut RenderContext,
+        world: &World,
+    ) -> Result<(), render_graph::NodeRunError> {
+        let jobs = world.resource::<EquirectangularJobs>();
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let (Some(pipeline_ids), false) = (jobs.pipelines, jobs.dispatches.is_empty()) else {
+            return Ok(());
+        };
+
+        let mut pass =
+            render_context
+                .command_encoder()
+                .begin_compute_pass(&ComputePassDescriptor {
+                    label: Some("equirectangular_environment_map"),
+                    timestamp_writes: None,
+                });
+
+        for dispatch in &jobs.dispatches {
+            let Some(pipeline) =
+                pipeline_cache.get_compute_pipeline(pipeline_ids[dispatch.pass as usize])
+            else {
+                continue;
+            };
+
+            pass.set_pipeline(pipeline);
+            match dispatch.dynamic_offset {
+                Some(offset) => pass.set_bind_group(0, &dispatch.bind_group, &[offset]),
+                None => pass.set_bind_group(0, &dispatch.bind_group, &[]),
+            }
+            let workgroups = dispatch.face_size.div_ceil(WORKGROUP_SIZE);
+            pass.dispatch_workgroups(workgroups, workgroups, 6);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,223 @@
+// Converts equirectangular panoramas into a skybox cubemap and the diffuse and specular maps of
+// an environment map light.
+//
+// This must match `bevy_image::equirectangular`, which does the same on the CPU.
+
+#import bevy_render::maths::{PI, PI_2}
+
+struct FilterSettings {
+    perceptual_roughness: f32,
+    sample_count: u32,
+    radiance_face_size: u32,
+    face_size: u32,
+}
+
+#ifdef CONVERT
+@group(0) @binding(0) var panorama: texture_2d<f32>;
+#else ifdef DOWNSAMPLE
+@group(0) @binding(0) var previous_mip: texture_2d_array<f32>;
+#else
+@group(0) @binding(0) var radiance: texture_cube<f32>;
+@group(0) @binding(2) var radiance_sampler: sampler;
+@group(0) @binding(3) var<uniform> settings: FilterSettings;
+#endif
+@group(0) @binding(1) var output: texture_storage_2d_array<rgba16float, write>;
+
+// Returns the direction, in cubemap space, through the point `uv` of the face `face`, where `uv`
+// goes from -1 to 1, left to right and top to bottom.
+fn face_direction(face: u32, uv: vec2<f32>) -> vec3<f32> {
+    var direction: vec3<f32>;
+    switch face {
+        case 0u: { direction = vec3(1.0, -uv.y, -uv.x); }
+        case 1u: { direction = vec3(-1.0, -uv.y, uv.x); }
+        case 2u: { direction = vec3(uv.x, 1.0, uv.y); }
+        case 3u: { direction = vec3(uv.x, -1.0, -uv.y); }
+        case 4u: { direction = vec3(uv.x, -uv.y, 1.0); }
+        default: { direction = vec3(-uv.x, -uv.y, -1.0); }
+    }
+    return normalize(direction);
+}
+
+// The direction through the center of the texel `id` of a cubemap with faces of `face_size`
+// pixels.
+fn texel_direction(id: vec3<u32>, face_size: u32) -> vec3<f32> {
+    let uv = (vec2<f32>(id.xy) + 0.5) / f32(face_size) * 2.0 - 1.0;
+    return face_direction(id.z, uv);
+}
+
+#ifdef CONVERT
+
+fn panorama_texel(texel: vec2<i32>, size: vec2<i32>) -> vec3<f32> {
+    // The panorama wraps around horizontally.
+    let x = ((texel.x % size.x) + size.x) % size.x;
+    let y = clamp(texel.y, 0, size.y - 1);
+    return textureLoad(panorama, vec2(x, y), 0).rgb;
+}
+
+// Samples the panorama in the world space `direction`, with bilinear filtering.
+//
+// The panorama is loaded texel by texel, as 32-bit float textures can't be filtered on all
+// platforms.
+fn sample_panorama(direction: vec3<f32>) -> vec3<f32> {
+    let size = vec2<i32>(textureDimensions(panorama));
+    let u = 0.5 + atan2(direction.x, -direction.z) / PI_2;
+    let v = acos(clamp(direction.y, -1.0, 1.0)) / PI;
+
+    let position = vec2(
+        u * f32(size.x) - 0.5,
+        clamp(v * f32(size.y) - 0.5, 0.0, f32(size.y - 1)),
+    );
+    let texel = vec2<i32>(floor(position));
+    let fraction = position - floor(position);
+
+    let top = mix(
+        panorama_texel(texel, size),
+        panorama_texel(texel + vec2(1, 0), size),
+        fraction.x
+    );
+    let bottom = mix(
+        panorama_texel(texel + vec2(0, 1), size),
+        panorama_texel(texel + vec2(1, 1), size),
+        fraction.x
+    );
+    return mix(top, bottom, fraction.y);
+}
+
+@compute @workgroup_size(8, 8, 1)
+fn convert(@builtin(global_invocation_id) id: vec3<u32>) {
+    let face_size = textureDimensions(output).x;
+    if (any(id.xy >= vec2(face_size))) {
+        return;
+    }
+
+    // Cubemaps are sampled with Z flipped.
+    let direction = texel_direction(id, face_size) * vec3(1.0, 1.0, -1.0);
+    textureStore(output, id.xy, id.z, vec4(sample_panorama(direction), 1.0));
+}
+
+#else ifdef DOWNSAMPLE
+
+@compute @workgroup_size(8, 8, 1)
+fn downsample(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (any(id.xy >= textureDimensions(output))) {
+        return;
+    }
+
+    let texel = id.xy * 2u;
+    let sum = textureLoad(previous_mip, texel, id.z, 0) +
+        textureLoad(previous_mip, texel + vec2(1u, 0u), id.z, 0) +
+        textureLoad(previous_mip, texel + vec2(0u, 1u), id.z, 0) +
+        textureLoad(previous_mip, texel + vec2(1u, 1u), id.z, 0);
+    textureStore(output, id.xy, id.z, vec4(sum.rgb * 0.25, 1.0));
+}
+
+#else
+
+// Maps the tangent space `sample` into the space around `normal`.
+fn tangent_to_world(normal: vec3<f32>, sample: vec3<f32>) -> vec3<f32> {
+    var up = vec3(1.0, 0.0, 0.0);
+    if (abs(normal.z) < 0.999) {
+        up = vec3(0.0, 0.0, 1.0);
+    }
+    let tangent = normalize(cross(up, normal));
+    let bitangent = cross(normal, tangent);
+    return tangent * sample.x + bitangent * sample.y + normal * sample.z;
+}
+
+// The `i`th of `n` points of the Hammersley sequence.
+fn hammersley(i: u32, n: u32) -> vec2<f32> {
+    return vec2(f32(i) / f32(n), f32(reverseBits(i)) * 2.3283064365386963e-10);
+}
+
+// The mip level of the radiance cubemap to sample at, for a sample that covers the solid angle
+// `1 / (sample_count * pdf)`.
+fn sample_mip_level(pdf: f32) -> f32 {
+    let radiance_face_size = f32(settings.radiance_face_size);
+    let sample_solid_angle = 1.0 / (f32(settings.sample_count) * pdf + 1.0e-4);
+    let texel_solid_angle = 4.0 * PI / (6.0 * radiance_face_size * radiance_face_size);
+    return 0.5 * log2(sample_solid_angle / texel_solid_angle) + 1.0;
+}
+
+// The lowest mip level of the radiance cubemap that has at least the resolution of the output.
+fn min_mip_level() -> f32 {
+    return max(log2(f32(settings.radiance_face_size) / f32(settings.face_size)), 0.0);
+}
+
+fn sample_radiance(direction: vec3<f32>, mip_level: f32) -> vec3<f32> {
+    return textureSampleLevel(radiance, radiance_sampler, direction, mip_level).rgb;
+}
+
+// The cosine weighted average of the radiance around each texel.
+@compute @workgroup_size(8, 8, 1)
+fn diffuse(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (any(id.xy >= vec2(settings.face_size))) {
+        return;
+    }
+
+    let normal = texel_direction(id, settings.face_size);
+    var sum = vec3(0.0);
+    for (var i = 0u; i < settings.sample_count; i += 1u) {
+        let xi = hammersley(i, settings.sample_count);
+        let phi = PI_2 * xi.x;
+        let cos_theta = sqrt(1.0 - xi.y);
+        let sin_theta = sqrt(xi.y);
+        let light = tangent_to_world(
+            normal,
+            vec3(sin_theta * cos(phi), sin_theta * sin(phi), cos_theta)
+        );
+        let pdf = cos_theta / PI;
+        sum += sample_radiance(light, max(sample_mip_level(pdf), min_mip_level()));
+    }
+
+    textureStore(output, id.xy, id.z, vec4(sum / f32(settings.sample_count), 1.0));
+}
+
+// The GGX filtered radiance around each texel, with the view direction equal to the normal.
+@compute @workgroup_size(8, 8, 1)
+fn specular(@builtin(global_invocation_id) id: vec3<u32>) {
+    if (any(id.xy >= vec2(settings.face_size))) {
+        return;
+    }
+
+    let normal = texel_direction(id, settings.face_size);
+    if (settings.perceptual_roughness == 0.0) {
+        textureStore(output, id.xy, id.z, vec4(sample_radiance(normal, min_mip_level()), 1.0));
+        return;
+    }
+
+    let alpha = settings.perceptual_roughness * settings.perceptual_roughness;
+    let alpha_squared = alpha * alpha;
+    var sum = vec3(0.0);
+    var weight = 0.0;
+    for (var i = 0u; i < settings.sample_count; i += 1u) {
+        let xi = hammersley(i, settings.sample_count);
+        let phi = PI_2 * xi.x;
+        let cos_theta = sqrt((1.0 - xi.y) / (1.0 + (alpha_squared - 1.0) * xi.y));
+        let sin_theta = sqrt(1.0 - cos_theta * cos_theta);
+        let half_vector = tangent_to_world(
+            normal,
+            vec3(sin_theta * cos(phi), sin_theta * sin(phi), cos_theta)
+        );
+
+        let n_dot_h = cos_theta;
+        let light = 2.0 * n_dot_h * half_vector - normal;
+        let n_dot_l = dot(normal, light);
+        if (n_dot_l <= 0.0) {
+            continue;
+        }
+
+        let d = n_dot_h * n_dot_h * (alpha_squared - 1.0) + 1.0;
+        let pdf = alpha_squared / (PI * d * d) / 4.0;
+        let mip_level = max(sample_mip_level(pdf), min_mip_level());
+        sum += sample_radiance(light, mip_level) * n_dot_l;
+        weight += n_dot_l;
+    }
+
+    var color = sample_radiance(normal, min_mip_level());
+    if (weight > 0.0) {
+        color = sum / weight;
+    }
+    textureStore(output, id.xy, id.z, vec4(color, 1.0));
+}
+
+#endif
@@ -41,12 +41,15 @@ use crate::{
     },
 };
 
-use self::irradiance_volume::IrradianceVolume;
+use self::{
+    equirectangular::EquirectangularEnvironmentMapPlugin, irradiance_volume::IrradianceVolume,
+};
 
 pub const LIGHT_PROBE_SHADER_HANDLE: Handle<Shader> =
     weak_handle!("e80a2ae6-1c5a-4d9a-a852-d66ff0e6bf7f");
 
 pub mod environment_map;
+pub mod equirectangular;
 pub mod irradiance_volume;
 
 /// The maximum number of each type of light probe that each view will consider.
@@ -365,7 +368,8 @@ impl Plugin for LightProbePlugin {
 
         app.register_type::<LightProbe>()
             .register_type::<EnvironmentMapLight>()
-            .register_type::<IrradianceVolume>();
+            .register_type::<IrradianceVolume>()
+            .add_plugins(EquirectangularEnvironmentMapPlugin);
     }
 
     fn finish(&self, app: &mut App) {
@@ -28,7 +28,7 @@ use bevy_ecs::{
         SystemParamItem,
     },
 };
-use bevy_platform_support::collections::HashMap;
+use bevy_platform_support::{collections::HashMap, hash::FixedHasher};
 use bevy_reflect::std_traits::ReflectDefault;
 use bevy_reflect::Reflect;
 use bevy_render::mesh::mark_3d_meshes_as_changed_if_their_assets_changed;
@@ -46,7 +46,11 @@ use bevy_render::{
 };
 use bevy_render::{mesh::allocator::MeshAllocator, sync_world::MainEntityHashMap};
 use bevy_render::{texture::FallbackImage, view::RenderVisibleEntities};
-use core::{hash::Hash, marker::PhantomData};
+use core::{
+    any::TypeId,
+    hash::{BuildHasher, Hash},
+    marker::PhantomData,
+};
 use tracing::error;
 
 /// Materials are used alongside [`MaterialPlugin`], [`Mesh3d`], and [`MeshMaterial3d`]
@@ -120,12 +124,18 @@ use tracing::error;
 pub trait Material: Asset + AsBindGroup + Clone + Sized {
     /// Returns this material's vertex shader. If [`ShaderRef::Default`] is returned, the default mesh vertex shader
     /// will be used.
+    ///
+    /// Custom shaders rendered by [`Multiview`](bevy_render::camera::Multiview) cameras must select the
+    /// view of the eye being rendered before reading `view`, as described there.
     fn vertex_shader() -> ShaderRef {
         ShaderRef::Default
     }
 
     /// Returns this material's fragment shader. If [`ShaderRef::Default`] is returned, the default mesh fragment shader
     /// will be used.
+    ///
+    /// Custom shaders rendered by [`Multiview`](bevy_render::camera::Multiview) cameras must select the
+    /// view of the eye being rendered before reading `view`, as described there.
     fn fragment_shader() -> ShaderRef {
         ShaderRef::Default
     }
@@ -325,9 +335,14 @@ where
                 )
                 .add_systems(
                     Render,
-                    prepare_material_bind_groups::<M>
-                        .in_set(RenderSet::PrepareBindGroups)
-                        .after(prepare_assets::<PreparedMaterial<M>>),
+                    (
+                        prepare_material_bind_groups::<M>
+                            .in_set(RenderSet::PrepareBindGroups)
+                            .after(prepare_assets::<PreparedMaterial<M>>),
+                        collect_material_render_statistics::<M>
+                            .in_set(RenderSet::PrepareBindGroups)
+                            .run_if(resource_exists::<RenderMaterialStatistics>),
+                    ),
                 );
 
             if self.shadows_enabled {
@@ -474,8 +489,28 @@ where
 
         descriptor.layout.insert(2, self.material_layout.clone());
 
+        let debug_view = key
+            .mesh_key
+            .intersection(MeshPipelineKey::DEBUG_VIEW_RESERVED_BITS);
+
         M::specialize(self, &mut descriptor, layout, key)?;
 
+        if let Some(ref mut fragment) = descriptor.fragment {
+            if debug_view == MeshPipelineKey::DEBUG_VIEW_SHADER_COMPLEXITY {
+                // Pipelines with more shader defs enabled run more of the shader.
+                let complexity = fragment.shader_defs.len() as u32;
+                fragment.shader_defs.push(ShaderDefVal::UInt(
+                    "DEBUG_VIEW_SHADER_COMPLEXITY".into(),
+                    complexity,
+                ));
+            } else if debug_view == MeshPipelineKey::DEBUG_VIEW_MATERIAL_TYPE {
+                fragment.shader_defs.push(ShaderDefVal::UInt(
+                    "DEBUG_VIEW_MATERIAL_TYPE".into(),
+                    FixedHasher.hash_one(TypeId::of::<M>()) as u32,
+                ));
+            }
+        }
+
         // If bindless mode is on, add a `BINDLESS` define.
         if self.bindless {
             descriptor.vertex.shader_defs.push("BINDLESS".into());
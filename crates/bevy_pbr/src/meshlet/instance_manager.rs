@@ -1,8 +1,8 @@
 use super::{meshlet_mesh_manager::MeshletMeshManager, MeshletMesh, MeshletMesh3d};
 use crate::{
-    Material, MeshFlags, MeshTransforms, MeshUniform, NotShadowCaster, NotShadowReceiver,
-    PreviousGlobalTransform, RenderMaterialBindings, RenderMaterialInstances,
-    RenderMeshMaterialIds,
+    Material, MaxMotionVelocity, MeshFlags, MeshTransforms, MeshUniform, NoMotionBlur,
+    NotShadowCaster, NotShadowReceiver, PreviousGlobalTransform, RenderMaterialBindings,
+    RenderMaterialInstances, RenderMeshMaterialIds,
 };
 use bevy_asset::{AssetEvent, AssetServer, Assets, UntypedAssetId};
 use bevy_ecs::{
@@ -94,6 +94,8 @@ impl InstanceManager {
         render_material_bindings: &RenderMaterialBindings,
         not_shadow_receiver: bool,
         not_shadow_caster: bool,
+        no_motion_blur: bool,
+        max_motion_velocity: Option<&MaxMotionVelocity>,
     ) {
         // Build a MeshUniform for the instance
         let transform = transform.affine();
@@ -106,6 +108,11 @@ impl InstanceManager {
         if transform.matrix3.determinant().is_sign_positive() {
             flags |= MeshFlags::SIGN_DETERMINANT_MODEL_3X3;
         }
+        if no_motion_blur {
+            flags |= MeshFlags::from_motion_vector_limit(0.0);
+        } else if let Some(max_motion_velocity) = max_motion_velocity {
+            flags |= MeshFlags::from_motion_vector_limit(max_motion_velocity.0);
+        }
         let transforms = MeshTransforms {
             world_from_local: (&transform).into(),
             previous_world_from_local: (&previous_transform).into(),
@@ -201,6 +208,8 @@ pub fn extract_meshlet_mesh_entities(
                     Option<&RenderLayers>,
                     Has<NotShadowReceiver>,
                     Has<NotShadowCaster>,
+                    Has<NoMotionBlur>,
+                    Option<&MaxMotionVelocity>,
                 )>,
                 Res<AssetServer>,
                 ResMut<Assets<MeshletMesh>>,
@@ -237,6 +246,8 @@ pub fn extract_meshlet_mesh_entities(
         render_layers,
         not_shadow_receiver,
         not_shadow_caster,
+        no_motion_blur,
+        max_motion_velocity,
     ) in &instances_query
     {
         // Skip instances with an unloaded MeshletMesh asset
@@ -262,6 +273,8 @@ pub fn extract_meshlet_mesh_entities(
             &render_material_bindings,
             not_shadow_receiver,
             not_shadow_caster,
+            no_motion_blur,
+            max_motion_velocity,
         );
     }
 }
@@ -48,7 +48,11 @@ pub fn prepare_material_meshlet_meshes_main_opaque_pass<M: Material>(
             Option<&Tonemapping>,
             Option<&DebandDither>,
             Option<&ShadowFilteringMethod>,
-            (Has<ScreenSpaceAmbientOcclusion>, Has<DistanceFog>),
+            (
+                Has<ScreenSpaceAmbientOcclusion>,
+                Has<ScreenSpaceGlobalIllumination>,
+                Has<DistanceFog>,
+            ),
             (
                 Has<NormalPrepass>,
                 Has<DepthPrepass>,
@@ -73,7 +77,7 @@ pub fn prepare_material_meshlet_meshes_main_opaque_pass<M: Material>(
         tonemapping,
         dither,
         shadow_filter_method,
-        (ssao, distance_fog),
+        (ssao, ssgi, distance_fog),
         (normal_prepass, depth_prepass, motion_vector_prepass, deferred_prepass),
         temporal_jitter,
         projection,
@@ -142,6 +146,9 @@ pub fn prepare_material_meshlet_meshes_main_opaque_pass<M: Material>(
         if ssao {
             view_key |= MeshPipelineKey::SCREEN_SPACE_AMBIENT_OCCLUSION;
         }
+        if ssgi {
+            view_key |= MeshPipelineKey::SCREEN_SPACE_GLOBAL_ILLUMINATION;
+        }
         if distance_fog {
             view_key |= MeshPipelineKey::DISTANCE_FOG;
         }
@@ -215,6 +222,7 @@ pub fn prepare_material_meshlet_meshes_main_opaque_pass<M: Material>(
                     targets: material_fragment.targets,
                 }),
                 zero_initialize_workgroup_memory: false,
+                multiview: None,
             };
 
             let material_id = instance_manager.get_material_id(material_id.untyped());
@@ -386,6 +394,7 @@ pub fn prepare_material_meshlet_meshes_prepass<M: Material>(
                     targets: material_fragment.targets,
                 }),
                 zero_initialize_workgroup_memory: false,
+                multiview: None,
             };
 
             let material_id = instance_manager.get_material_id(material_id.untyped());
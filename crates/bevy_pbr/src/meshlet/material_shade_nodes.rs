@@ -202,6 +202,8 @@ impl ViewNode for MeshletPrepassNode {
             // Use None in place of Deferred attachments
             None,
             None,
+            // Meshlets don't support outlines
+            None,
         ];
 
         let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
@@ -330,6 +332,8 @@ impl ViewNode for MeshletDeferredGBufferPrepassNode {
                 .deferred_lighting_pass_id
                 .as_ref()
                 .map(|deferred_lighting_pass_id| deferred_lighting_pass_id.get_attachment()),
+            // Meshlets don't support outlines
+            None,
         ];
 
         let mut render_pass = render_context.begin_tracked_render_pass(RenderPassDescriptor {
@@ -262,6 +262,7 @@ impl FromWorld for MeshletPipelines {
                         })],
                     }),
                     zero_initialize_workgroup_memory: false,
+                    multiview: None,
                 },
             ),
 
@@ -303,6 +304,7 @@ impl FromWorld for MeshletPipelines {
                         })],
                     }),
                     zero_initialize_workgroup_memory: false,
+                    multiview: None,
                 },
             ),
 
@@ -345,6 +347,7 @@ impl FromWorld for MeshletPipelines {
                         })],
                     }),
                     zero_initialize_workgroup_memory: false,
+                    multiview: None,
                 }),
 
             resolve_depth: pipeline_cache.queue_render_pipeline(RenderPipelineDescriptor {
@@ -371,6 +374,7 @@ impl FromWorld for MeshletPipelines {
                     targets: vec![],
                 }),
                 zero_initialize_workgroup_memory: false,
+                multiview: None,
             }),
 
             resolve_depth_shadow_view: pipeline_cache.queue_render_pipeline(
@@ -398,6 +402,7 @@ impl FromWorld for MeshletPipelines {
                         targets: vec![],
                     }),
                     zero_initialize_workgroup_memory: false,
+                    multiview: None,
                 },
             ),
 
@@ -426,6 +431,7 @@ impl FromWorld for MeshletPipelines {
                         targets: vec![],
                     }),
                     zero_initialize_workgroup_memory: false,
+                    multiview: None,
                 },
             ),
 
@@ -25,6 +25,8 @@
 #import bevy_pbr::{
     prepass_bindings::previous_view_uniforms,
     pbr_prepass_functions::calculate_motion_vector,
+    mesh_functions::limit_motion_vector,
+    mesh_types::{MESH_FLAGS_MOTION_VECTOR_LIMIT_BITS, MESH_FLAGS_MOTION_VECTOR_LIMIT_SHIFT},
 }
 #endif
 #endif
@@ -160,7 +162,10 @@ fn resolve_vertex_output(frag_coord: vec4<f32>) -> VertexOutput {
     let previous_world_position_1 = mesh_position_local_to_world(previous_world_from_local, vec4(vertex_1.position, 1.0));
     let previous_world_position_2 = mesh_position_local_to_world(previous_world_from_local, vec4(vertex_2.position, 1.0));
     let previous_world_position = mat3x4(previous_world_position_0, previous_world_position_1, previous_world_position_2) * partial_derivatives.barycentrics;
-    let motion_vector = calculate_motion_vector(world_position, previous_world_position);
+    let motion_vector = limit_motion_vector(
+        calculate_motion_vector(world_position, previous_world_position),
+        (instance_uniform.flags & MESH_FLAGS_MOTION_VECTOR_LIMIT_BITS) >> MESH_FLAGS_MOTION_VECTOR_LIMIT_SHIFT
+    );
 #endif
 #endif
 
@@ -13,12 +13,16 @@ use bevy_app::{App, Plugin, PreUpdate};
 use bevy_render::{
     alpha::AlphaMode,
     batching::gpu_preprocessing::GpuPreprocessingSupport,
+    camera::Multiview,
     mesh::{allocator::MeshAllocator, Mesh3d, MeshVertexBufferLayoutRef, RenderMesh},
     render_asset::prepare_assets,
     render_resource::binding_types::uniform_buffer,
     renderer::RenderAdapter,
     sync_world::RenderEntity,
-    view::{RenderVisibilityRanges, VISIBILITY_RANGES_STORAGE_BUFFER_COUNT},
+    view::{
+        MultiviewUniform, MultiviewUniformOffset, MultiviewUniforms, RenderVisibilityRanges,
+        VISIBILITY_RANGES_STORAGE_BUFFER_COUNT,
+    },
     ExtractSchedule, Render, RenderApp, RenderSet,
 };
 pub use prepass_bindings::*;
@@ -35,6 +39,7 @@ use bevy_ecs::{
     },
 };
 use bevy_math::{Affine3A, Vec4};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_render::{
     globals::{GlobalsBuffer, GlobalsUniform},
     prelude::{Camera, Mesh},
@@ -251,6 +256,31 @@ pub fn update_previous_view_data(
 #[derive(Component, PartialEq, Default)]
 pub struct PreviousGlobalTransform(pub Affine3A);
 
+/// Add this component to a [`Mesh3d`] to exclude it from motion blur.
+///
+/// The mesh writes zero velocity into the motion vector prepass, even when it
+/// or the camera moves, so that things like first-person hands or weapons
+/// attached to the camera stay sharp.
+///
+/// **Note:** Other effects that read the motion vectors, such as TAA, will
+/// treat the mesh as if it stood still on screen as well.
+#[derive(Debug, Component, Reflect, Default, Clone, Copy)]
+#[reflect(Component, Default, Debug)]
+pub struct NoMotionBlur;
+
+/// Add this component to a [`Mesh3d`] to limit how far it's smeared by motion
+/// blur.
+///
+/// The value is the maximum length of the mesh's motion vectors: the distance,
+/// as a fraction of the screen size, that a point on the mesh appears to move
+/// from one frame to the next. It's stored with a limited precision, and
+/// values above 1.0 have no effect.
+///
+/// [`NoMotionBlur`] overrides this component.
+#[derive(Debug, Component, Reflect, Clone, Copy)]
+#[reflect(Component, Debug)]
+pub struct MaxMotionVelocity(pub f32);
+
 #[cfg(not(feature = "meshlet"))]
 type PreviousMeshFilter = With<Mesh3d>;
 #[cfg(feature = "meshlet")]
@@ -280,6 +310,7 @@ pub fn update_mesh_previous_global_transforms(
 pub struct PrepassPipeline<M: Material> {
     pub view_layout_motion_vectors: BindGroupLayout,
     pub view_layout_no_motion_vectors: BindGroupLayout,
+    pub view_layout_multiview: BindGroupLayout,
     pub mesh_layouts: MeshLayouts,
     pub material_layout: BindGroupLayout,
     pub prepass_material_vertex_shader: Option<Handle<Shader>>,
@@ -358,6 +389,31 @@ impl<M: Material> FromWorld for PrepassPipeline<M> {
             ),
         );
 
+        let view_layout_multiview = render_device.create_bind_group_layout(
+            "prepass_view_layout_multiview",
+            &BindGroupLayoutEntries::with_indices(
+                ShaderStages::VERTEX_FRAGMENT,
+                (
+                    // View
+                    (0, uniform_buffer::<ViewUniform>(true)),
+                    // Globals
+                    (1, uniform_buffer::<GlobalsUniform>(false)),
+                    // VisibilityRanges
+                    (
+                        14,
+                        buffer_layout(
+                            visibility_ranges_buffer_binding_type,
+                            false,
+                            Some(Vec4::min_size()),
+                        )
+                        .visibility(ShaderStages::VERTEX),
+                    ),
+                    // Multiview
+                    (40, uniform_buffer::<MultiviewUniform>(true)),
+                ),
+            ),
+        );
+
         let mesh_pipeline = world.resource::<MeshPipeline>();
 
         let depth_clip_control_supported = render_device
@@ -367,6 +423,7 @@ impl<M: Material> FromWorld for PrepassPipeline<M> {
         PrepassPipeline {
             view_layout_motion_vectors,
             view_layout_no_motion_vectors,
+            view_layout_multiview,
             mesh_layouts: mesh_pipeline.mesh_layouts.clone(),
             prepass_material_vertex_shader: match M::prepass_vertex_shader() {
                 ShaderRef::Default => None,
@@ -409,7 +466,9 @@ where
         key: Self::Key,
         layout: &MeshVertexBufferLayoutRef,
     ) -> Result<RenderPipelineDescriptor, SpecializedMeshPipelineError> {
-        let mut bind_group_layouts = vec![if key
+        let mut bind_group_layouts = vec![if key.mesh_key.contains(MeshPipelineKey::MULTIVIEW) {
+            self.view_layout_multiview.clone()
+        } else if key
             .mesh_key
             .contains(MeshPipelineKey::MOTION_VECTOR_PREPASS)
         {
@@ -418,6 +477,10 @@ where
             self.view_layout_no_motion_vectors.clone()
         }];
         let mut shader_defs = Vec::new();
+
+        if key.mesh_key.contains(MeshPipelineKey::MULTIVIEW) {
+            shader_defs.push("MULTIVIEW".into());
+        }
         let mut vertex_attributes = Vec::new();
 
         // Let the shader code know that it's running in a prepass pipeline.
@@ -517,6 +580,10 @@ where
             shader_defs.push("DEFERRED_PREPASS".into());
         }
 
+        if key.mesh_key.contains(MeshPipelineKey::OUTLINE_ID_PREPASS) {
+            shader_defs.push("OUTLINE_ID_PREPASS".into());
+        }
+
         if key.mesh_key.contains(MeshPipelineKey::LIGHTMAPPED) {
             shader_defs.push("LIGHTMAP".into());
         }
@@ -566,7 +633,8 @@ where
         if key.mesh_key.intersects(
             MeshPipelineKey::NORMAL_PREPASS
                 | MeshPipelineKey::MOTION_VECTOR_PREPASS
-                | MeshPipelineKey::DEFERRED_PREPASS,
+                | MeshPipelineKey::DEFERRED_PREPASS
+                | MeshPipelineKey::OUTLINE_ID_PREPASS,
         ) {
             shader_defs.push("PREPASS_FRAGMENT".into());
         }
@@ -590,6 +658,7 @@ where
             key.mesh_key
                 .contains(MeshPipelineKey::MOTION_VECTOR_PREPASS),
             key.mesh_key.contains(MeshPipelineKey::DEFERRED_PREPASS),
+            key.mesh_key.contains(MeshPipelineKey::OUTLINE_ID_PREPASS),
         );
 
         if targets.iter().all(Option::is_none) {
@@ -683,6 +752,7 @@ where
             push_constant_ranges: vec![],
             label: Some("prepass_pipeline".into()),
             zero_initialize_workgroup_memory: false,
+            multiview: key.mesh_key.multiview(),
         };
 
         // This is a bit risky because it's possible to change something that would
@@ -756,6 +826,7 @@ pub fn prepare_previous_view_uniforms(
 pub struct PrepassViewBindGroup {
     pub motion_vectors: Option<BindGroup>,
     pub no_motion_vectors: Option<BindGroup>,
+    pub multiview: Option<BindGroup>,
 }
 
 pub fn prepare_prepass_view_bind_group<M: Material>(
@@ -765,6 +836,7 @@ pub fn prepare_prepass_view_bind_group<M: Material>(
     globals_buffer: Res<GlobalsBuffer>,
     previous_view_uniforms: Res<PreviousViewUniforms>,
     visibility_ranges: Res<RenderVisibilityRanges>,
+    multiview_uniforms: Res<MultiviewUniforms>,
     mut prepass_view_bind_group: ResMut<PrepassViewBindGroup>,
 ) {
     if let (Some(view_binding), Some(globals_binding), Some(visibility_ranges_buffer)) = (
@@ -782,6 +854,19 @@ pub fn prepare_prepass_view_bind_group<M: Material>(
             )),
         ));
 
+        if let Some(multiview_binding) = multiview_uniforms.uniforms.binding() {
+            prepass_view_bind_group.multiview = Some(render_device.create_bind_group(
+                "prepass_view_multiview_bind_group",
+                &prepass_pipeline.view_layout_multiview,
+                &BindGroupEntries::with_indices((
+                    (0, view_binding.clone()),
+                    (1, globals_binding.clone()),
+                    (14, visibility_ranges_buffer.as_entire_binding()),
+                    (40, multiview_binding),
+                )),
+            ));
+        }
+
         if let Some(previous_view_uniforms_binding) = previous_view_uniforms.uniforms.binding() {
             prepass_view_bind_group.motion_vectors = Some(render_device.create_bind_group(
                 "prepass_view_motion_vectors_bind_group",
@@ -829,11 +914,20 @@ pub fn check_prepass_views_need_specialization(
         Option<&DepthPrepass>,
         Option<&NormalPrepass>,
         Option<&MotionVectorPrepass>,
+        Option<&OutlineIdPrepass>,
+        Has<Multiview>,
     )>,
     ticks: SystemChangeTick,
 ) {
-    for (view_entity, msaa, depth_prepass, normal_prepass, motion_vector_prepass) in
-        views.iter_mut()
+    for (
+        view_entity,
+        msaa,
+        depth_prepass,
+        normal_prepass,
+        motion_vector_prepass,
+        outline_id_prepass,
+        multiview,
+    ) in views.iter_mut()
     {
         let mut view_key = MeshPipelineKey::from_msaa_samples(msaa.samples());
         if depth_prepass.is_some() {
@@ -842,9 +936,15 @@ pub fn check_prepass_views_need_specialization(
         if normal_prepass.is_some() {
             view_key |= MeshPipelineKey::NORMAL_PREPASS;
         }
-        if motion_vector_prepass.is_some() {
+        // Motion vectors aren't supported with multiview.
+        if multiview {
+            view_key |= MeshPipelineKey::MULTIVIEW;
+        } else if motion_vector_prepass.is_some() {
             view_key |= MeshPipelineKey::MOTION_VECTOR_PREPASS;
         }
+        if outline_id_prepass.is_some() {
+            view_key |= MeshPipelineKey::OUTLINE_ID_PREPASS;
+        }
 
         if let Some(current_key) = view_key_cache.get_mut(view_entity) {
             if *current_key != view_key {
@@ -1239,16 +1339,23 @@ impl<P: PhaseItem, const I: usize> RenderCommand<P> for SetPrepassViewBindGroup<
         Read<ViewUniformOffset>,
         Has<MotionVectorPrepass>,
         Option<Read<PreviousViewUniformOffset>>,
+        Option<Read<MultiviewUniformOffset>>,
     );
     type ItemQuery = ();
 
     #[inline]
     fn render<'w>(
         _item: &P,
-        (view_uniform_offset, has_motion_vector_prepass, previous_view_uniform_offset): (
+        (
+            view_uniform_offset,
+            has_motion_vector_prepass,
+            previous_view_uniform_offset,
+            multiview_uniform_offset,
+        ): (
             &'_ ViewUniformOffset,
             bool,
             Option<&'_ PreviousViewUniformOffset>,
+            Option<&'_ MultiviewUniformOffset>,
         ),
         _entity: Option<()>,
         prepass_view_bind_group: SystemParamItem<'w, '_, Self::Param>,
@@ -1256,6 +1363,18 @@ impl<P: PhaseItem, const I: usize> RenderCommand<P> for SetPrepassViewBindGroup<
     ) -> RenderCommandResult {
         let prepass_view_bind_group = prepass_view_bind_group.into_inner();
 
+        if let Some(multiview_uniform_offset) = multiview_uniform_offset {
+            let Some(multiview_bind_group) = prepass_view_bind_group.multiview.as_ref() else {
+                return RenderCommandResult::Skip;
+            };
+            pass.set_bind_group(
+                I,
+                multiview_bind_group,
+                &[view_uniform_offset.offset, multiview_uniform_offset.offset],
+            );
+            return RenderCommandResult::Success;
+        }
+
         match previous_view_uniform_offset {
             Some(previous_view_uniform_offset) if has_motion_vector_prepass => {
                 pass.set_bind_group(
@@ -60,6 +60,11 @@ fn morph_prev_vertex(vertex_in: Vertex) -> Vertex {
 fn vertex(vertex_no_morph: Vertex) -> VertexOutput {
     var out: VertexOutput;
 
+#ifdef MULTIVIEW
+    bevy_pbr::mesh_view_bindings::select_view(vertex_no_morph.view_index);
+    out.view_index = vertex_no_morph.view_index;
+#endif
+
 #ifdef MORPH_TARGETS
     var vertex = morph_vertex(vertex_no_morph);
 #else
@@ -160,6 +165,7 @@ fn vertex(vertex_no_morph: Vertex) -> VertexOutput {
         prev_model,
         vec4<f32>(prev_vertex.position, 1.0)
     );
+    out.motion_vector_limit = mesh_functions::get_motion_vector_limit(vertex_no_morph.instance_index);
 #endif // MOTION_VECTOR_PREPASS
 
 #ifdef VERTEX_OUTPUT_INSTANCE_INDEX
@@ -173,12 +179,20 @@ fn vertex(vertex_no_morph: Vertex) -> VertexOutput {
         vertex_no_morph.instance_index, mesh_world_from_local[3]);
 #endif  // VISIBILITY_RANGE_DITHER
 
+#ifdef OUTLINE_ID_PREPASS
+    out.outline_id = mesh_functions::get_outline_id(vertex_no_morph.instance_index);
+#endif  // OUTLINE_ID_PREPASS
+
     return out;
 }
 
 #ifdef PREPASS_FRAGMENT
 @fragment
 fn fragment(in: VertexOutput) -> FragmentOutput {
+#ifdef MULTIVIEW
+    bevy_pbr::mesh_view_bindings::select_view(in.view_index);
+#endif
+
     var out: FragmentOutput;
 
 #ifdef NORMAL_PREPASS
@@ -200,7 +214,10 @@ fn fragment(in: VertexOutput) -> FragmentOutput {
     // A difference between diagonally-opposite corners of clip space is in the
     // range -2,2, so this needs to be scaled by 0.5. And the V direction goes
     // down where clip space y goes up, so y needs to be flipped.
-    out.motion_vector = (clip_position - previous_clip_position) * vec2(0.5, -0.5);
+    out.motion_vector = mesh_functions::limit_motion_vector(
+        (clip_position - previous_clip_position) * vec2(0.5, -0.5),
+        in.motion_vector_limit
+    );
 #endif // MOTION_VECTOR_PREPASS
 
 #ifdef DEFERRED_PREPASS
@@ -212,6 +229,10 @@ fn fragment(in: VertexOutput) -> FragmentOutput {
     out.deferred_lighting_pass_id = 1u;
 #endif
 
+#ifdef OUTLINE_ID_PREPASS
+    out.outline_id = in.outline_id;
+#endif
+
     return out;
 }
 #endif // PREPASS_FRAGMENT
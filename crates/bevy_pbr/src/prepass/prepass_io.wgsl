@@ -33,6 +33,10 @@ struct Vertex {
 #ifdef MORPH_TARGETS
     @builtin(vertex_index) index: u32,
 #endif // MORPH_TARGETS
+
+#ifdef MULTIVIEW
+    @builtin(view_index) view_index: i32,
+#endif // MULTIVIEW
 }
 
 struct VertexOutput {
@@ -74,6 +78,21 @@ struct VertexOutput {
 #ifdef VISIBILITY_RANGE_DITHER
     @location(9) @interpolate(flat) visibility_range_dither: i32,
 #endif  // VISIBILITY_RANGE_DITHER
+
+#ifdef MOTION_VECTOR_PREPASS
+    // The packed limit on the length of the motion vectors of the mesh, or 0 for
+    // none. See `mesh_functions::limit_motion_vector`.
+    @location(10) @interpolate(flat) motion_vector_limit: u32,
+#endif
+
+#ifdef OUTLINE_ID_PREPASS
+    // The outline style slot of the mesh, or 0 if it isn't outlined.
+    @location(11) @interpolate(flat) outline_id: u32,
+#endif
+
+#ifdef MULTIVIEW
+    @location(12) @interpolate(flat) view_index: i32,
+#endif
 }
 
 #ifdef PREPASS_FRAGMENT
@@ -91,6 +110,10 @@ struct FragmentOutput {
     @location(3) deferred_lighting_pass_id: u32,
 #endif
 
+#ifdef OUTLINE_ID_PREPASS
+    @location(4) outline_id: u32,
+#endif
+
 #ifdef UNCLIPPED_DEPTH_ORTHO_EMULATION
     @builtin(frag_depth) frag_depth: f32,
 #endif // UNCLIPPED_DEPTH_ORTHO_EMULATION
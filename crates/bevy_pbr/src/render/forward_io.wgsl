@@ -27,6 +27,9 @@ struct Vertex {
 #ifdef MORPH_TARGETS
     @builtin(vertex_index) index: u32,
 #endif
+#ifdef MULTIVIEW
+    @builtin(view_index) view_index: i32,
+#endif
 };
 
 struct VertexOutput {
@@ -53,6 +56,9 @@ struct VertexOutput {
 #ifdef VISIBILITY_RANGE_DITHER
     @location(7) @interpolate(flat) visibility_range_dither: i32,
 #endif
+#ifdef MULTIVIEW
+    @location(8) @interpolate(flat) view_index: i32,
+#endif
 }
 
 struct FragmentOutput {
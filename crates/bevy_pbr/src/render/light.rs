@@ -61,6 +61,7 @@ pub struct ExtractedPointLight {
     pub soft_shadows_enabled: bool,
     /// whether this point light contributes diffuse light to lightmapped meshes
     pub affects_lightmapped_mesh_diffuse: bool,
+    pub contact_shadows: Option<ContactShadows>,
 }
 
 #[derive(Component, Debug)]
@@ -80,6 +81,7 @@ pub struct ExtractedDirectionalLight {
     pub frusta: EntityHashMap<Vec<Frustum>>,
     pub render_layers: RenderLayers,
     pub soft_shadow_size: Option<f32>,
+    pub contact_shadows: Option<ContactShadows>,
 }
 
 // NOTE: These must match the bit flags in bevy_pbr/src/render/mesh_view_types.wgsl!
@@ -90,6 +92,7 @@ bitflags::bitflags! {
         const SPOT_LIGHT_Y_NEGATIVE             = 1 << 1;
         const VOLUMETRIC                        = 1 << 2;
         const AFFECTS_LIGHTMAPPED_MESH_DIFFUSE  = 1 << 3;
+        const CONTACT_SHADOWS                   = 1 << 4;
         const NONE                              = 0;
         const UNINITIALIZED                     = 0xFFFF;
     }
@@ -115,6 +118,8 @@ pub struct GpuDirectionalLight {
     cascades_overlap_proportion: f32,
     depth_texture_base_index: u32,
     skip: u32,
+    contact_shadow_length: f32,
+    contact_shadow_thickness: f32,
 }
 
 // NOTE: These must match the bit flags in bevy_pbr/src/render/mesh_view_types.wgsl!
@@ -124,6 +129,7 @@ bitflags::bitflags! {
         const SHADOWS_ENABLED                   = 1 << 0;
         const VOLUMETRIC                        = 1 << 1;
         const AFFECTS_LIGHTMAPPED_MESH_DIFFUSE  = 1 << 2;
+        const CONTACT_SHADOWS                   = 1 << 3;
         const NONE                              = 0;
         const UNINITIALIZED                     = 0xFFFF;
     }
@@ -143,6 +149,10 @@ pub struct GpuLights {
     // offset from spot light's light index to spot light's shadow map index
     spot_light_shadowmap_offset: i32,
     ambient_light_affects_lightmapped_meshes: u32,
+    // xy are the world space center of the cloud shadow map
+    // z is 1 / the world space extent of the cloud shadow map, or 0 if there are no cloud shadows
+    // w is the world space height of the bottom of the cloud layer
+    cloud_shadow_params: Vec4,
 }
 
 // NOTE: When running bevy on Adreno GPU chipsets in WebGL, any value above 1 will result in a crash
@@ -224,6 +234,7 @@ pub fn extract_lights(
             &ViewVisibility,
             &CubemapFrusta,
             Option<&VolumetricLight>,
+            Option<&ContactShadows>,
         )>,
     >,
     spot_lights: Extract<
@@ -236,6 +247,7 @@ pub fn extract_lights(
             &ViewVisibility,
             &Frustum,
             Option<&VolumetricLight>,
+            Option<&ContactShadows>,
         )>,
     >,
     directional_lights: Extract<
@@ -252,6 +264,7 @@ pub fn extract_lights(
                 &ViewVisibility,
                 Option<&RenderLayers>,
                 Option<&VolumetricLight>,
+                Option<&ContactShadows>,
             ),
             Without<SpotLight>,
         >,
@@ -288,6 +301,7 @@ pub fn extract_lights(
             view_visibility,
             frusta,
             volumetric_light,
+            contact_shadows,
         )) = point_lights.get(entity)
         else {
             continue;
@@ -327,6 +341,7 @@ pub fn extract_lights(
             soft_shadows_enabled: point_light.soft_shadows_enabled,
             #[cfg(not(feature = "experimental_pbr_pcss"))]
             soft_shadows_enabled: false,
+            contact_shadows: contact_shadows.copied(),
         };
         point_lights_values.push((
             render_entity,
@@ -352,6 +367,7 @@ pub fn extract_lights(
             view_visibility,
             frustum,
             volumetric_light,
+            contact_shadows,
         )) = spot_lights.get(entity)
         {
             if !view_visibility.get() {
@@ -393,6 +409,7 @@ pub fn extract_lights(
                         soft_shadows_enabled: spot_light.soft_shadows_enabled,
                         #[cfg(not(feature = "experimental_pbr_pcss"))]
                         soft_shadows_enabled: false,
+                        contact_shadows: contact_shadows.copied(),
                     },
                     render_visible_entities,
                     *frustum,
@@ -416,6 +433,7 @@ pub fn extract_lights(
         view_visibility,
         maybe_layers,
         volumetric_light,
+        contact_shadows,
     ) in &directional_lights
     {
         if !view_visibility.get() {
@@ -481,6 +499,7 @@ pub fn extract_lights(
                     cascades: extracted_cascades,
                     frusta: extracted_frusta,
                     render_layers: maybe_layers.unwrap_or_default().clone(),
+                    contact_shadows: contact_shadows.copied(),
                 },
                 RenderCascadesVisibleEntities {
                     entities: cascade_visible_entities,
@@ -705,6 +724,127 @@ pub(crate) fn spot_light_clip_from_view(angle: f32, near_z: f32) -> Mat4 {
     Mat4::perspective_infinite_reverse_rh(angle * 2.0, 1.0, near_z)
 }
 
+/// Returns the GPU data of a point or spot light.
+///
+/// `flags` are the flags that depend on the other lights, such as whether the
+/// light has a shadow map.
+fn gpu_clusterable_object(
+    light: &ExtractedPointLight,
+    mut flags: PointLightFlags,
+) -> GpuClusterableObject {
+    let cube_face_projection = Mat4::perspective_infinite_reverse_rh(
+        core::f32::consts::FRAC_PI_2,
+        1.0,
+        light.shadow_map_near_z,
+    );
+
+    if light.affects_lightmapped_mesh_diffuse {
+        flags |= PointLightFlags::AFFECTS_LIGHTMAPPED_MESH_DIFFUSE;
+    }
+
+    if light.contact_shadows.is_some() {
+        flags |= PointLightFlags::CONTACT_SHADOWS;
+    }
+    let contact_shadows = light.contact_shadows.unwrap_or_default();
+
+    let (light_custom_data, spot_light_tan_angle) = match light.spot_light_angles {
+        Some((inner, outer)) => {
+            let light_direction = light.transform.forward();
+            if light_direction.y.is_sign_negative() {
+                flags |= PointLightFlags::SPOT_LIGHT_Y_NEGATIVE;
+            }
+
+            let cos_outer = ops::cos(outer);
+            let spot_scale = 1.0 / f32::max(ops::cos(inner) - cos_outer, 1e-4);
+            let spot_offset = -cos_outer * spot_scale;
+
+            (
+                // For spot lights: the direction (x,z), spot_scale and spot_offset
+                light_direction.xz().extend(spot_scale).extend(spot_offset),
+                ops::tan(outer),
+            )
+        }
+        None => {
+            (
+                // For point lights: the lower-right 2x2 values of the projection matrix [2][2] [2][3] [3][2] [3][3]
+                Vec4::new(
+                    cube_face_projection.z_axis.z,
+                    cube_face_projection.z_axis.w,
+                    cube_face_projection.w_axis.z,
+                    cube_face_projection.w_axis.w,
+                ),
+                // unused
+                0.0,
+            )
+        }
+    };
+
+    GpuClusterableObject {
+        light_custom_data,
+        // premultiply color by intensity
+        // we don't use the alpha at all, so no reason to multiply only [0..3]
+        color_inverse_square_range: (Vec4::from_slice(&light.color.to_f32_array())
+            * light.intensity)
+            .xyz()
+            .extend(1.0 / (light.range * light.range)),
+        position_radius: light.transform.translation().extend(light.radius),
+        flags: flags.bits(),
+        shadow_depth_bias: light.shadow_depth_bias,
+        shadow_normal_bias: light.shadow_normal_bias,
+        shadow_map_near_z: light.shadow_map_near_z,
+        spot_light_tan_angle,
+        contact_shadow_length: contact_shadows.length,
+        contact_shadow_thickness: contact_shadows.thickness,
+        soft_shadow_size: if light.soft_shadows_enabled {
+            light.radius
+        } else {
+            0.0
+        },
+    }
+}
+
+/// Returns the GPU data of a directional light, with its cascades filled in
+/// later.
+///
+/// `flags` are the flags that depend on the other lights, such as whether the
+/// light has shadow maps.
+fn gpu_directional_light(
+    light: &ExtractedDirectionalLight,
+    mut flags: DirectionalLightFlags,
+    num_cascades: usize,
+    depth_texture_base_index: usize,
+) -> GpuDirectionalLight {
+    if light.affects_lightmapped_mesh_diffuse {
+        flags |= DirectionalLightFlags::AFFECTS_LIGHTMAPPED_MESH_DIFFUSE;
+    }
+
+    if light.contact_shadows.is_some() {
+        flags |= DirectionalLightFlags::CONTACT_SHADOWS;
+    }
+    let contact_shadows = light.contact_shadows.unwrap_or_default();
+
+    GpuDirectionalLight {
+        // Set to true later when necessary.
+        skip: 0u32,
+        // Filled in later.
+        cascades: [GpuDirectionalCascade::default(); MAX_CASCADES_PER_LIGHT],
+        // premultiply color by illuminance
+        // we don't use the alpha at all, so no reason to multiply only [0..3]
+        color: Vec4::from_slice(&light.color.to_f32_array()) * light.illuminance,
+        // direction is negated to be ready for N.L
+        dir_to_light: light.transform.back().into(),
+        flags: flags.bits(),
+        soft_shadow_size: light.soft_shadow_size.unwrap_or_default(),
+        shadow_depth_bias: light.shadow_depth_bias,
+        shadow_normal_bias: light.shadow_normal_bias,
+        num_cascades: num_cascades as u32,
+        cascades_overlap_proportion: light.cascade_shadow_config.overlap_proportion,
+        depth_texture_base_index: depth_texture_base_index as u32,
+        contact_shadow_length: contact_shadows.length,
+        contact_shadow_thickness: contact_shadows.thickness,
+    }
+}
+
 pub fn prepare_lights(
     mut commands: Commands,
     mut texture_cache: ResMut<TextureCache>,
@@ -720,6 +860,7 @@ pub fn prepare_lights(
             Option<&RenderLayers>,
             Has<NoIndirectDrawing>,
             Option<&AmbientLight>,
+            Option<(&VolumetricClouds, &AtmosphereSettings)>,
         ),
         With<Camera3d>,
     >,
@@ -897,11 +1038,6 @@ pub fn prepare_lights(
             flags |= PointLightFlags::SHADOWS_ENABLED;
         }
 
-        let cube_face_projection = Mat4::perspective_infinite_reverse_rh(
-            core::f32::consts::FRAC_PI_2,
-            1.0,
-            light.shadow_map_near_z,
-        );
         if light.shadows_enabled
             && light.volumetric
             && (index < point_light_volumetric_enabled_count
@@ -911,64 +1047,7 @@ pub fn prepare_lights(
             flags |= PointLightFlags::VOLUMETRIC;
         }
 
-        if light.affects_lightmapped_mesh_diffuse {
-            flags |= PointLightFlags::AFFECTS_LIGHTMAPPED_MESH_DIFFUSE;
-        }
-
-        let (light_custom_data, spot_light_tan_angle) = match light.spot_light_angles {
-            Some((inner, outer)) => {
-                let light_direction = light.transform.forward();
-                if light_direction.y.is_sign_negative() {
-                    flags |= PointLightFlags::SPOT_LIGHT_Y_NEGATIVE;
-                }
-
-                let cos_outer = ops::cos(outer);
-                let spot_scale = 1.0 / f32::max(ops::cos(inner) - cos_outer, 1e-4);
-                let spot_offset = -cos_outer * spot_scale;
-
-                (
-                    // For spot lights: the direction (x,z), spot_scale and spot_offset
-                    light_direction.xz().extend(spot_scale).extend(spot_offset),
-                    ops::tan(outer),
-                )
-            }
-            None => {
-                (
-                    // For point lights: the lower-right 2x2 values of the projection matrix [2][2] [2][3] [3][2] [3][3]
-                    Vec4::new(
-                        cube_face_projection.z_axis.z,
-                        cube_face_projection.z_axis.w,
-                        cube_face_projection.w_axis.z,
-                        cube_face_projection.w_axis.w,
-                    ),
-                    // unused
-                    0.0,
-                )
-            }
-        };
-
-        gpu_point_lights.push(GpuClusterableObject {
-            light_custom_data,
-            // premultiply color by intensity
-            // we don't use the alpha at all, so no reason to multiply only [0..3]
-            color_inverse_square_range: (Vec4::from_slice(&light.color.to_f32_array())
-                * light.intensity)
-                .xyz()
-                .extend(1.0 / (light.range * light.range)),
-            position_radius: light.transform.translation().extend(light.radius),
-            flags: flags.bits(),
-            shadow_depth_bias: light.shadow_depth_bias,
-            shadow_normal_bias: light.shadow_normal_bias,
-            shadow_map_near_z: light.shadow_map_near_z,
-            spot_light_tan_angle,
-            pad_a: 0.0,
-            pad_b: 0.0,
-            soft_shadow_size: if light.soft_shadows_enabled {
-                light.radius
-            } else {
-                0.0
-            },
-        });
+        gpu_point_lights.push(gpu_clusterable_object(light, flags));
         global_light_meta.entity_to_index.insert(entity, index);
     }
 
@@ -993,33 +1072,13 @@ pub fn prepare_lights(
             flags |= DirectionalLightFlags::SHADOWS_ENABLED;
         }
 
-        if light.affects_lightmapped_mesh_diffuse {
-            flags |= DirectionalLightFlags::AFFECTS_LIGHTMAPPED_MESH_DIFFUSE;
-        }
-
         let num_cascades = light
             .cascade_shadow_config
             .bounds
             .len()
             .min(MAX_CASCADES_PER_LIGHT);
-        gpu_directional_lights[index] = GpuDirectionalLight {
-            // Set to true later when necessary.
-            skip: 0u32,
-            // Filled in later.
-            cascades: [GpuDirectionalCascade::default(); MAX_CASCADES_PER_LIGHT],
-            // premultiply color by illuminance
-            // we don't use the alpha at all, so no reason to multiply only [0..3]
-            color: Vec4::from_slice(&light.color.to_f32_array()) * light.illuminance,
-            // direction is negated to be ready for N.L
-            dir_to_light: light.transform.back().into(),
-            flags: flags.bits(),
-            soft_shadow_size: light.soft_shadow_size.unwrap_or_default(),
-            shadow_depth_bias: light.shadow_depth_bias,
-            shadow_normal_bias: light.shadow_normal_bias,
-            num_cascades: num_cascades as u32,
-            cascades_overlap_proportion: light.cascade_shadow_config.overlap_proportion,
-            depth_texture_base_index: num_directional_cascades_enabled as u32,
-        };
+        gpu_directional_lights[index] =
+            gpu_directional_light(light, flags, num_cascades, num_directional_cascades_enabled);
         if index < directional_shadow_enabled_count {
             num_directional_cascades_enabled += num_cascades;
         }
@@ -1140,6 +1199,7 @@ pub fn prepare_lights(
         maybe_layers,
         no_indirect_drawing,
         maybe_ambient_override,
+        maybe_volumetric_clouds,
     ) in sorted_cameras
         .0
         .iter()
@@ -1184,6 +1244,15 @@ pub fn prepare_lights(
                 - point_light_count as i32,
             ambient_light_affects_lightmapped_meshes: ambient_light.affects_lightmapped_meshes
                 as u32,
+            cloud_shadow_params: maybe_volumetric_clouds.map_or(
+                Vec4::ZERO,
+                |(volumetric_clouds, atmosphere_settings)| {
+                    volumetric_clouds.shadow_map_params(
+                        extracted_view.world_from_view.translation(),
+                        atmosphere_settings.scene_units_to_m,
+                    )
+                },
+            ),
         };
 
         // TODO: this should select lights based on relevance to the view instead of the first ones that show up in a query
@@ -2171,3 +2240,116 @@ impl Node for ShadowPassNode {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTACT_SHADOWS: ContactShadows = ContactShadows {
+        length: 0.5,
+        thickness: 0.02,
+    };
+
+    fn point_light(contact_shadows: Option<ContactShadows>) -> ExtractedPointLight {
+        ExtractedPointLight {
+            color: LinearRgba::WHITE,
+            intensity: 1000.0,
+            range: 20.0,
+            radius: 0.0,
+            transform: GlobalTransform::default(),
+            shadows_enabled: true,
+            shadow_depth_bias: 0.0,
+            shadow_normal_bias: 0.0,
+            shadow_map_near_z: 0.1,
+            spot_light_angles: None,
+            volumetric: false,
+            soft_shadows_enabled: false,
+            affects_lightmapped_mesh_diffuse: true,
+            contact_shadows,
+        }
+    }
+
+    fn directional_light(contact_shadows: Option<ContactShadows>) -> ExtractedDirectionalLight {
+        ExtractedDirectionalLight {
+            color: LinearRgba::WHITE,
+            illuminance: 1000.0,
+            transform: GlobalTransform::default(),
+            shadows_enabled: true,
+            volumetric: false,
+            affects_lightmapped_mesh_diffuse: true,
+            shadow_depth_bias: 0.0,
+            shadow_normal_bias: 0.0,
+            cascade_shadow_config: CascadeShadowConfig::default(),
+            cascades: EntityHashMap::default(),
+            frusta: EntityHashMap::default(),
+            render_layers: RenderLayers::default(),
+            soft_shadow_size: None,
+            contact_shadows,
+        }
+    }
+
+    #[test]
+    fn contact_shadow_flags_match_shader() {
+        let shader = include_str!("mesh_view_types.wgsl");
+        for (name, bit) in [
+            (
+                "POINT_LIGHT_FLAGS_CONTACT_SHADOWS_BIT",
+                PointLightFlags::CONTACT_SHADOWS.bits(),
+            ),
+            (
+                "DIRECTIONAL_LIGHT_FLAGS_CONTACT_SHADOWS_BIT",
+                DirectionalLightFlags::CONTACT_SHADOWS.bits(),
+            ),
+        ] {
+            let line = shader
+                .lines()
+                .find(|line| line.starts_with(&format!("const {name}:")))
+                .unwrap_or_else(|| panic!("{name} is missing from mesh_view_types.wgsl"));
+            assert!(line.ends_with(&format!("= {bit}u;")), "{line}");
+        }
+    }
+
+    #[test]
+    fn point_light_contact_shadows() {
+        for spot_light_angles in [None, Some((0.2, 0.4))] {
+            let mut light = point_light(Some(CONTACT_SHADOWS));
+            light.spot_light_angles = spot_light_angles;
+            let gpu_light = gpu_clusterable_object(&light, PointLightFlags::SHADOWS_ENABLED);
+            let flags = PointLightFlags::from_bits_retain(gpu_light.flags);
+            assert!(flags.contains(PointLightFlags::CONTACT_SHADOWS));
+            assert!(flags.contains(PointLightFlags::SHADOWS_ENABLED));
+            assert_eq!(gpu_light.contact_shadow_length, CONTACT_SHADOWS.length);
+            assert_eq!(
+                gpu_light.contact_shadow_thickness,
+                CONTACT_SHADOWS.thickness
+            );
+        }
+
+        let gpu_light = gpu_clusterable_object(&point_light(None), PointLightFlags::NONE);
+        assert!(!PointLightFlags::from_bits_retain(gpu_light.flags)
+            .contains(PointLightFlags::CONTACT_SHADOWS));
+    }
+
+    #[test]
+    fn directional_light_contact_shadows() {
+        let gpu_light = gpu_directional_light(
+            &directional_light(Some(CONTACT_SHADOWS)),
+            DirectionalLightFlags::SHADOWS_ENABLED,
+            4,
+            0,
+        );
+        let flags = DirectionalLightFlags::from_bits_retain(gpu_light.flags);
+        assert!(flags.contains(DirectionalLightFlags::CONTACT_SHADOWS));
+        assert!(flags.contains(DirectionalLightFlags::SHADOWS_ENABLED));
+        assert_eq!(gpu_light.contact_shadow_length, CONTACT_SHADOWS.length);
+        assert_eq!(
+            gpu_light.contact_shadow_thickness,
+            CONTACT_SHADOWS.thickness
+        );
+
+        let gpu_light =
+            gpu_directional_light(&directional_light(None), DirectionalLightFlags::NONE, 4, 0);
+        assert!(!DirectionalLightFlags::from_bits_retain(gpu_light.flags)
+            .contains(DirectionalLightFlags::CONTACT_SHADOWS));
+    }
+}
@@ -5,6 +5,7 @@ use bevy_core_pipeline::{
     core_3d::{AlphaMask3d, Opaque3d, Transmissive3d, Transparent3d, CORE_3D_DEPTH_FORMAT},
     deferred::{AlphaMask3dDeferred, Opaque3dDeferred},
     oit::{prepare_oit_buffers, OrderIndependentTransparencySettingsOffset},
+    outline::{Outline, OutlineStyles},
     prepass::MotionVectorPrepass,
 };
 use bevy_derive::{Deref, DerefMut};
@@ -25,7 +26,7 @@ use bevy_render::{
         },
         no_gpu_preprocessing, GetBatchData, GetFullBatchData, NoAutomaticBatching,
     },
-    camera::Camera,
+    camera::{Camera, Multiview},
     mesh::*,
     primitives::Aabb,
     render_asset::RenderAssets,
@@ -37,14 +38,14 @@ use bevy_render::{
     renderer::{RenderAdapter, RenderDevice, RenderQueue},
     texture::DefaultImageSampler,
     view::{
-        self, NoFrustumCulling, NoIndirectDrawing, RenderVisibilityRanges, ViewTarget,
-        ViewUniformOffset, ViewVisibility, VisibilityRange,
+        self, MultiviewUniformOffset, NoFrustumCulling, NoIndirectDrawing, RenderVisibilityRanges,
+        ViewTarget, ViewUniformOffset, ViewVisibility, VisibilityRange,
     },
     Extract,
 };
 use bevy_transform::components::GlobalTransform;
 use bevy_utils::{default, Parallel};
-use core::mem::size_of;
+use core::{mem::size_of, num::NonZeroU32};
 use material_bind_groups::MaterialBindingId;
 use render::skin::{self, SkinIndex};
 use tracing::{error, warn};
@@ -320,7 +321,10 @@ pub fn check_views_need_specialization(
         Option<&Tonemapping>,
         Option<&DebandDither>,
         Option<&ShadowFilteringMethod>,
-        Has<ScreenSpaceAmbientOcclusion>,
+        (
+            Has<ScreenSpaceAmbientOcclusion>,
+            Has<ScreenSpaceGlobalIllumination>,
+        ),
         (
             Has<NormalPrepass>,
             Has<DepthPrepass>,
@@ -335,7 +339,11 @@ pub fn check_views_need_specialization(
             Has<RenderViewLightProbes<EnvironmentMapLight>>,
             Has<RenderViewLightProbes<IrradianceVolume>>,
         ),
-        Has<OrderIndependentTransparencySettings>,
+        (
+            Has<OrderIndependentTransparencySettings>,
+            Option<&DebugView>,
+            Has<Multiview>,
+        ),
     )>,
     ticks: SystemChangeTick,
 ) {
@@ -346,33 +354,38 @@ pub fn check_views_need_specialization(
         tonemapping,
         dither,
         shadow_filter_method,
-        ssao,
+        (ssao, ssgi),
         (normal_prepass, depth_prepass, motion_vector_prepass, deferred_prepass),
         camera_3d,
         temporal_jitter,
         projection,
         distance_fog,
         (has_environment_maps, has_irradiance_volumes),
-        has_oit,
+        (has_oit, debug_view, multiview),
     ) in views.iter_mut()
     {
         let mut view_key = MeshPipelineKey::from_msaa_samples(msaa.samples())
             | MeshPipelineKey::from_hdr(view.hdr);
 
-        if normal_prepass {
-            view_key |= MeshPipelineKey::NORMAL_PREPASS;
-        }
+        // The main passes of multiview views don't read the layered prepass textures.
+        if multiview {
+            view_key |= MeshPipelineKey::MULTIVIEW;
+        } else {
+            if normal_prepass {
+                view_key |= MeshPipelineKey::NORMAL_PREPASS;
+            }
 
-        if depth_prepass {
-            view_key |= MeshPipelineKey::DEPTH_PREPASS;
-        }
+            if depth_prepass {
+                view_key |= MeshPipelineKey::DEPTH_PREPASS;
+            }
 
-        if motion_vector_prepass {
-            view_key |= MeshPipelineKey::MOTION_VECTOR_PREPASS;
-        }
+            if motion_vector_prepass {
+                view_key |= MeshPipelineKey::MOTION_VECTOR_PREPASS;
+            }
 
-        if deferred_prepass {
-            view_key |= MeshPipelineKey::DEFERRED_PREPASS;
+            if deferred_prepass {
+                view_key |= MeshPipelineKey::DEFERRED_PREPASS;
+            }
         }
 
         if temporal_jitter {
@@ -391,6 +404,10 @@ pub fn check_views_need_specialization(
             view_key |= MeshPipelineKey::OIT_ENABLED;
         }
 
+        if let Some(debug_view) = debug_view {
+            view_key |= debug_view_pipeline_key(*debug_view);
+        }
+
         if let Some(projection) = projection {
             view_key |= match projection {
                 Projection::Perspective(_) => MeshPipelineKey::VIEW_PROJECTION_PERSPECTIVE,
@@ -423,6 +440,9 @@ pub fn check_views_need_specialization(
         if ssao {
             view_key |= MeshPipelineKey::SCREEN_SPACE_AMBIENT_OCCLUSION;
         }
+        if ssgi {
+            view_key |= MeshPipelineKey::SCREEN_SPACE_GLOBAL_ILLUMINATION;
+        }
         if distance_fog {
             view_key |= MeshPipelineKey::DISTANCE_FOG;
         }
@@ -618,6 +638,17 @@ bitflags::bitflags! {
         ///
         /// This will be `u16::MAX` if this mesh has no LOD.
         const LOD_INDEX_MASK              = (1 << 16) - 1;
+        /// Bitmask for the 8-bit limit on the length of the mesh's motion
+        /// vectors.
+        ///
+        /// This is zero if the motion vectors aren't limited. It corresponds to
+        /// the [`NoMotionBlur`] and [`MaxMotionVelocity`] components.
+        const MOTION_VECTOR_LIMIT_MASK    = ((1 << 8) - 1) << 16;
+        /// Bitmask for the 4-bit outline style slot of the mesh.
+        ///
+        /// This is zero if the mesh isn't outlined. It corresponds to the
+        /// [`bevy_core_pipeline::outline::Outline`] component.
+        const OUTLINE_STYLE_MASK          = ((1 << 4) - 1) << 24;
         /// Disables frustum culling for this mesh.
         ///
         /// This corresponds to the
@@ -640,6 +671,9 @@ impl MeshFlags {
         no_frustum_culling: bool,
         not_shadow_receiver: bool,
         transmitted_receiver: bool,
+        no_motion_blur: bool,
+        max_motion_velocity: Option<&MaxMotionVelocity>,
+        outline_style: Option<u8>,
     ) -> MeshFlags {
         let mut mesh_flags = if not_shadow_receiver {
             MeshFlags::empty()
@@ -663,11 +697,43 @@ impl MeshFlags {
         mesh_flags |=
             MeshFlags::from_bits_retain((lod_index_bits as u32) << MeshFlags::LOD_INDEX_SHIFT);
 
+        let max_motion_velocity = if no_motion_blur {
+            Some(0.0)
+        } else {
+            max_motion_velocity.map(|max_motion_velocity| max_motion_velocity.0)
+        };
+        if let Some(max_motion_velocity) = max_motion_velocity {
+            mesh_flags |= MeshFlags::from_motion_vector_limit(max_motion_velocity);
+        }
+
+        if let Some(outline_style) = outline_style {
+            mesh_flags |= MeshFlags::from_bits_retain(
+                (outline_style as u32) << MeshFlags::OUTLINE_STYLE_SHIFT,
+            ) & MeshFlags::OUTLINE_STYLE_MASK;
+        }
+
         mesh_flags
     }
 
+    /// Packs a limit on the length of the motion vectors of a mesh, as a
+    /// fraction of the screen size, into the bits of
+    /// [`MeshFlags::MOTION_VECTOR_LIMIT_MASK`].
+    ///
+    /// The limit is stored as its square root, from 1 to 255, so that slow
+    /// speeds keep the most precision. Zero means no limit.
+    pub fn from_motion_vector_limit(max_length: f32) -> MeshFlags {
+        let bits = (max_length.clamp(0.0, 1.0).sqrt() * 254.0).round() as u32 + 1;
+        MeshFlags::from_bits_retain(bits << MeshFlags::MOTION_VECTOR_LIMIT_SHIFT)
+    }
+
     /// The first bit of the LOD index.
     pub const LOD_INDEX_SHIFT: u32 = 0;
+
+    /// The first bit of the motion vector limit.
+    pub const MOTION_VECTOR_LIMIT_SHIFT: u32 = 16;
+
+    /// The first bit of the outline style slot.
+    pub const OUTLINE_STYLE_SHIFT: u32 = 24;
 }
 
 bitflags::bitflags! {
@@ -1311,8 +1377,14 @@ pub fn extract_meshes_for_cpu_building(
             Has<NotShadowCaster>,
             Has<NoAutomaticBatching>,
             Has<VisibilityRange>,
+            (
+                Has<NoMotionBlur>,
+                Option<&MaxMotionVelocity>,
+                Option<&Outline>,
+            ),
         )>,
     >,
+    outline_styles: Extract<Res<OutlineStyles>>,
 ) {
     meshes_query.par_iter().for_each_init(
         || render_mesh_instance_queues.borrow_local_mut(),
@@ -1330,6 +1402,7 @@ pub fn extract_meshes_for_cpu_building(
             not_shadow_caster,
             no_automatic_batching,
             visibility_range,
+            (no_motion_blur, max_motion_velocity, outline),
         )| {
             if !view_visibility.get() {
                 return;
@@ -1346,6 +1419,9 @@ pub fn extract_meshes_for_cpu_building(
                 no_frustum_culling,
                 not_shadow_receiver,
                 transmitted_receiver,
+                no_motion_blur,
+                max_motion_velocity,
+                outline.and_then(|outline| outline_styles.slot(outline)),
             );
 
             let shared = RenderMeshInstanceShared::from_components(
@@ -1420,6 +1496,11 @@ pub fn extract_meshes_for_gpu_building(
                 Has<NotShadowCaster>,
                 Has<NoAutomaticBatching>,
                 Has<VisibilityRange>,
+                (
+                    Has<NoMotionBlur>,
+                    Option<&MaxMotionVelocity>,
+                    Option<&Outline>,
+                ),
             ),
             Or<(
                 Changed<ViewVisibility>,
@@ -1434,6 +1515,9 @@ pub fn extract_meshes_for_gpu_building(
                 Changed<NotShadowCaster>,
                 Changed<NoAutomaticBatching>,
                 Changed<VisibilityRange>,
+                Changed<NoMotionBlur>,
+                Changed<MaxMotionVelocity>,
+                Changed<Outline>,
             )>,
         >,
     >,
@@ -1441,6 +1525,7 @@ pub fn extract_meshes_for_gpu_building(
     mut removed_global_transforms_query: Extract<RemovedComponents<GlobalTransform>>,
     mut removed_meshes_query: Extract<RemovedComponents<Mesh3d>>,
     gpu_culling_query: Extract<Query<(), (With<Camera>, Without<NoIndirectDrawing>)>>,
+    outline_styles: Extract<Res<OutlineStyles>>,
 ) {
     let any_gpu_culling = !gpu_culling_query.is_empty();
 
@@ -1478,6 +1563,7 @@ pub fn extract_meshes_for_gpu_building(
             not_shadow_caster,
             no_automatic_batching,
             visibility_range,
+            (no_motion_blur, max_motion_velocity, outline),
         )| {
             if !view_visibility.get() {
                 queue.remove(entity.into(), any_gpu_culling);
@@ -1495,6 +1581,9 @@ pub fn extract_meshes_for_gpu_building(
                 no_frustum_culling,
                 not_shadow_receiver,
                 transmitted_receiver,
+                no_motion_blur,
+                max_motion_velocity,
+                outline.and_then(|outline| outline_styles.slot(outline)),
             );
 
             let shared = RenderMeshInstanceShared::from_components(
@@ -2010,7 +2099,10 @@ bitflags::bitflags! {
         const HAS_PREVIOUS_MORPH                = 1 << 19;
         const OIT_ENABLED                       = 1 << 20;
         const DISTANCE_FOG                      = 1 << 21;
-        const LAST_FLAG                         = Self::DISTANCE_FOG.bits();
+        const SCREEN_SPACE_GLOBAL_ILLUMINATION  = 1 << 22;
+        const OUTLINE_ID_PREPASS                = 1 << 23;
+        const MULTIVIEW                         = 1 << 24;
+        const LAST_FLAG                         = Self::MULTIVIEW.bits();
 
         // Bitfields
         const MSAA_RESERVED_BITS                = Self::MSAA_MASK_BITS << Self::MSAA_SHIFT_BITS;
@@ -2043,13 +2135,20 @@ bitflags::bitflags! {
         const SCREEN_SPACE_SPECULAR_TRANSMISSION_MEDIUM = 1 << Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_SHIFT_BITS;
         const SCREEN_SPACE_SPECULAR_TRANSMISSION_HIGH   = 2 << Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_SHIFT_BITS;
         const SCREEN_SPACE_SPECULAR_TRANSMISSION_ULTRA  = 3 << Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_SHIFT_BITS;
+        const DEBUG_VIEW_RESERVED_BITS          = Self::DEBUG_VIEW_MASK_BITS << Self::DEBUG_VIEW_SHIFT_BITS;
+        const DEBUG_VIEW_NONE                   = 0 << Self::DEBUG_VIEW_SHIFT_BITS;
+        const DEBUG_VIEW_OVERDRAW               = 1 << Self::DEBUG_VIEW_SHIFT_BITS;
+        const DEBUG_VIEW_SHADER_COMPLEXITY      = 2 << Self::DEBUG_VIEW_SHIFT_BITS;
+        const DEBUG_VIEW_MESH_ID                = 3 << Self::DEBUG_VIEW_SHIFT_BITS;
+        const DEBUG_VIEW_MATERIAL_TYPE          = 4 << Self::DEBUG_VIEW_SHIFT_BITS;
         const ALL_RESERVED_BITS =
             Self::BLEND_RESERVED_BITS.bits() |
             Self::MSAA_RESERVED_BITS.bits() |
             Self::TONEMAP_METHOD_RESERVED_BITS.bits() |
             Self::SHADOW_FILTER_METHOD_RESERVED_BITS.bits() |
             Self::VIEW_PROJECTION_RESERVED_BITS.bits() |
-            Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_RESERVED_BITS.bits();
+            Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_RESERVED_BITS.bits() |
+            Self::DEBUG_VIEW_RESERVED_BITS.bits();
     }
 }
 
@@ -2077,6 +2176,11 @@ impl MeshPipelineKey {
     const SCREEN_SPACE_SPECULAR_TRANSMISSION_SHIFT_BITS: u64 =
         Self::VIEW_PROJECTION_MASK_BITS.count_ones() as u64 + Self::VIEW_PROJECTION_SHIFT_BITS;
 
+    const DEBUG_VIEW_MASK_BITS: u64 = 0b111;
+    const DEBUG_VIEW_SHIFT_BITS: u64 = Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_MASK_BITS
+        .count_ones() as u64
+        + Self::SCREEN_SPACE_SPECULAR_TRANSMISSION_SHIFT_BITS;
+
     pub fn from_msaa_samples(msaa_samples: u32) -> Self {
         let msaa_bits =
             (msaa_samples.trailing_zeros() as u64 & Self::MSAA_MASK_BITS) << Self::MSAA_SHIFT_BITS;
@@ -2095,6 +2199,13 @@ impl MeshPipelineKey {
         1 << ((self.bits() >> Self::MSAA_SHIFT_BITS) & Self::MSAA_MASK_BITS)
     }
 
+    /// The number of views rendered at once by pipelines with this key, if they use multiview.
+    pub fn multiview(&self) -> Option<NonZeroU32> {
+        self.contains(MeshPipelineKey::MULTIVIEW)
+            .then_some(Multiview::VIEW_COUNT)
+            .and_then(NonZeroU32::new)
+    }
+
     pub fn from_primitive_topology(primitive_topology: PrimitiveTopology) -> Self {
         let primitive_topology_bits = ((primitive_topology as u64)
             & BaseMeshPipelineKey::PRIMITIVE_TOPOLOGY_MASK_BITS)
@@ -2277,9 +2388,13 @@ impl SpecializedMeshPipeline for MeshPipeline {
             shader_defs.push("SCREEN_SPACE_AMBIENT_OCCLUSION".into());
         }
 
+        if key.contains(MeshPipelineKey::SCREEN_SPACE_GLOBAL_ILLUMINATION) {
+            shader_defs.push("SCREEN_SPACE_GLOBAL_ILLUMINATION".into());
+        }
+
         let vertex_buffer_layout = layout.0.get_layout(&vertex_attributes)?;
 
-        let (label, blend, depth_write_enabled);
+        let (label, mut blend, mut depth_write_enabled);
         let pass = key.intersection(MeshPipelineKey::BLEND_RESERVED_BITS);
         let (mut is_opaque, mut alpha_to_coverage_enabled) = (false, false);
         if key.contains(MeshPipelineKey::OIT_ENABLED) && pass == MeshPipelineKey::BLEND_ALPHA {
@@ -2341,6 +2456,33 @@ impl SpecializedMeshPipeline for MeshPipeline {
             is_opaque = !key.contains(MeshPipelineKey::READS_VIEW_TRANSMISSION_TEXTURE);
         }
 
+        let mut depth_compare = CompareFunction::GreaterEqual;
+        let debug_view = key.intersection(MeshPipelineKey::DEBUG_VIEW_RESERVED_BITS);
+        if debug_view != MeshPipelineKey::DEBUG_VIEW_NONE {
+            shader_defs.push("DEBUG_VIEW".into());
+        }
+        if debug_view == MeshPipelineKey::DEBUG_VIEW_OVERDRAW {
+            shader_defs.push("DEBUG_VIEW_OVERDRAW".into());
+            // Every fragment of every pass adds to the pixel, whether or not it's
+            // occluded. Depth is still written so that the skybox only covers the
+            // background.
+            blend = Some(BlendState {
+                color: BlendComponent {
+                    src_factor: BlendFactor::One,
+                    dst_factor: BlendFactor::One,
+                    operation: BlendOperation::Add,
+                },
+                alpha: BlendComponent::OVER,
+            });
+            depth_compare = CompareFunction::Always;
+            depth_write_enabled = true;
+            alpha_to_coverage_enabled = false;
+        } else if debug_view == MeshPipelineKey::DEBUG_VIEW_MESH_ID {
+            shader_defs.push("DEBUG_VIEW_MESH_ID".into());
+        }
+        // The shader complexity and material type views are completed by
+        // `MaterialPipeline`, which knows the material.
+
         if key.contains(MeshPipelineKey::NORMAL_PREPASS) {
             shader_defs.push("NORMAL_PREPASS".into());
         }
@@ -2489,6 +2631,10 @@ impl SpecializedMeshPipeline for MeshPipeline {
             shader_defs.push("CLUSTERED_DECALS_ARE_USABLE".into());
         }
 
+        if key.contains(MeshPipelineKey::MULTIVIEW) {
+            shader_defs.push("MULTIVIEW".into());
+        }
+
         let format = if key.contains(MeshPipelineKey::HDR) {
             ViewTarget::TEXTURE_FORMAT_HDR
         } else {
@@ -2536,7 +2682,7 @@ impl SpecializedMeshPipeline for MeshPipeline {
             depth_stencil: Some(DepthStencilState {
                 format: CORE_3D_DEPTH_FORMAT,
                 depth_write_enabled,
-                depth_compare: CompareFunction::GreaterEqual,
+                depth_compare,
                 stencil: StencilState {
                     front: StencilFaceState::IGNORE,
                     back: StencilFaceState::IGNORE,
@@ -2556,6 +2702,7 @@ impl SpecializedMeshPipeline for MeshPipeline {
             },
             label: Some(label),
             zero_initialize_workgroup_memory: false,
+            multiview: key.multiview(),
         })
     }
 }
@@ -2733,6 +2880,7 @@ impl<P: PhaseItem, const I: usize> RenderCommand<P> for SetMeshViewBindGroup<I>
         Read<ViewEnvironmentMapUniformOffset>,
         Read<MeshViewBindGroup>,
         Option<Read<OrderIndependentTransparencySettingsOffset>>,
+        Option<Read<MultiviewUniformOffset>>,
     );
     type ItemQuery = ();
 
@@ -2748,6 +2896,7 @@ impl<P: PhaseItem, const I: usize> RenderCommand<P> for SetMeshViewBindGroup<I>
             view_environment_map,
             mesh_view_bind_group,
             maybe_oit_layers_count_offset,
+            maybe_multiview_offset,
         ): ROQueryItem<'w, Self::ViewQuery>,
         _entity: Option<()>,
         _: SystemParamItem<'w, '_, Self::Param>,
@@ -2764,6 +2913,9 @@ impl<P: PhaseItem, const I: usize> RenderCommand<P> for SetMeshViewBindGroup<I>
         if let Some(layers_count_offset) = maybe_oit_layers_count_offset {
             offsets.push(layers_count_offset.offset);
         }
+        if let Some(multiview_offset) = maybe_multiview_offset {
+            offsets.push(multiview_offset.offset);
+        }
         pass.set_bind_group(I, &mesh_view_bind_group.value, &offsets);
 
         RenderCommandResult::Success
@@ -35,6 +35,11 @@ fn morph_vertex(vertex_in: Vertex) -> Vertex {
 fn vertex(vertex_no_morph: Vertex) -> VertexOutput {
     var out: VertexOutput;
 
+#ifdef MULTIVIEW
+    bevy_pbr::mesh_view_bindings::select_view(vertex_no_morph.view_index);
+    out.view_index = vertex_no_morph.view_index;
+#endif
+
 #ifdef MORPH_TARGETS
     var vertex = morph_vertex(vertex_no_morph);
 #else
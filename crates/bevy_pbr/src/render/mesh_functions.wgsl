@@ -7,7 +7,13 @@
         VISIBILITY_RANGE_UNIFORM_BUFFER_SIZE
     },
     mesh_bindings::mesh,
-    mesh_types::MESH_FLAGS_SIGN_DETERMINANT_MODEL_3X3_BIT,
+    mesh_types::{
+        MESH_FLAGS_SIGN_DETERMINANT_MODEL_3X3_BIT,
+        MESH_FLAGS_MOTION_VECTOR_LIMIT_BITS,
+        MESH_FLAGS_MOTION_VECTOR_LIMIT_SHIFT,
+        MESH_FLAGS_OUTLINE_STYLE_BITS,
+        MESH_FLAGS_OUTLINE_STYLE_SHIFT,
+    },
     view_transformations::position_world_to_clip,
 }
 #import bevy_render::maths::{affine3_to_square, mat2x4_f32_to_mat3x3_unpack}
@@ -135,4 +141,34 @@ fn get_visibility_range_dither_level(instance_index: u32, world_position: vec4<f
 
 fn get_tag(instance_index: u32) -> u32 {
     return mesh[instance_index].tag;
-}
\ No newline at end of file
+}
+#ifndef MESHLET_MESH_MATERIAL_PASS
+// Returns the packed limit on the length of the motion vectors of the mesh, or 0 if
+// they aren't limited.
+fn get_motion_vector_limit(instance_index: u32) -> u32 {
+    return (mesh[instance_index].flags & MESH_FLAGS_MOTION_VECTOR_LIMIT_BITS) >>
+        MESH_FLAGS_MOTION_VECTOR_LIMIT_SHIFT;
+}
+
+// Returns the outline style slot of the mesh, or 0 if it isn't outlined.
+fn get_outline_id(instance_index: u32) -> u32 {
+    return (mesh[instance_index].flags & MESH_FLAGS_OUTLINE_STYLE_BITS) >>
+        MESH_FLAGS_OUTLINE_STYLE_SHIFT;
+}
+#endif  // MESHLET_MESH_MATERIAL_PASS
+
+// Shortens the motion vector to the packed limit returned by
+// `get_motion_vector_limit`. The limit is stored as its square root from 1 to 255,
+// where 1 makes the motion vector zero.
+fn limit_motion_vector(motion_vector: vec2<f32>, limit: u32) -> vec2<f32> {
+    if limit == 0u {
+        return motion_vector;
+    }
+    let max_length_sqrt = f32(limit - 1u) / 254.0;
+    let max_length = max_length_sqrt * max_length_sqrt;
+    let motion_vector_length = length(motion_vector);
+    if motion_vector_length <= max_length {
+        return motion_vector;
+    }
+    return motion_vector * (max_length / motion_vector_length);
+}
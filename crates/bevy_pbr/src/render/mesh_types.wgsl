@@ -40,6 +40,12 @@ struct MorphWeights {
 
 // [2^0, 2^16)
 const MESH_FLAGS_VISIBILITY_RANGE_INDEX_BITS: u32 = 65535u;
+// [2^16, 2^24)
+const MESH_FLAGS_MOTION_VECTOR_LIMIT_BITS: u32 = 16711680u;
+const MESH_FLAGS_MOTION_VECTOR_LIMIT_SHIFT: u32 = 16u;
+// [2^24, 2^28)
+const MESH_FLAGS_OUTLINE_STYLE_BITS: u32 = 251658240u;
+const MESH_FLAGS_OUTLINE_STYLE_SHIFT: u32 = 24u;
 // 2^28
 const MESH_FLAGS_NO_FRUSTUM_CULLING_BIT: u32 = 268435456u;
 // 2^29
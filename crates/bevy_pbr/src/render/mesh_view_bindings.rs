@@ -19,20 +19,22 @@ use bevy_ecs::{
 use bevy_image::BevyDefault as _;
 use bevy_math::Vec4;
 use bevy_render::{
+    camera::Multiview,
     globals::{GlobalsBuffer, GlobalsUniform},
     render_asset::RenderAssets,
     render_resource::{binding_types::*, *},
     renderer::{RenderAdapter, RenderDevice},
     texture::{FallbackImage, FallbackImageMsaa, FallbackImageZero, GpuImage},
     view::{
-        Msaa, RenderVisibilityRanges, ViewUniform, ViewUniforms,
-        VISIBILITY_RANGES_STORAGE_BUFFER_COUNT,
+        Msaa, MultiviewUniform, MultiviewUniforms, RenderVisibilityRanges, ViewUniform,
+        ViewUniforms, VISIBILITY_RANGES_STORAGE_BUFFER_COUNT,
     },
 };
 use core::{array, num::NonZero};
 use environment_map::EnvironmentMapLight;
 
 use crate::{
+    atmosphere::VolumetricCloudsTextures,
     decal::{
         self,
         clustered::{
@@ -47,8 +49,9 @@ use crate::{
     prepass, EnvironmentMapUniformBuffer, FogMeta, GlobalClusterableObjectMeta,
     GpuClusterableObjects, GpuFog, GpuLights, LightMeta, LightProbesBuffer, LightProbesUniform,
     MeshPipeline, MeshPipelineKey, RenderViewLightProbes, ScreenSpaceAmbientOcclusionResources,
-    ScreenSpaceReflectionsBuffer, ScreenSpaceReflectionsUniform, ShadowSamplers,
-    ViewClusterBindings, ViewShadowBindings, CLUSTERED_FORWARD_STORAGE_BUFFER_COUNT,
+    ScreenSpaceGlobalIlluminationResources, ScreenSpaceReflectionsBuffer,
+    ScreenSpaceReflectionsUniform, ShadowSamplers, ViewClusterBindings, ViewShadowBindings,
+    CLUSTERED_FORWARD_STORAGE_BUFFER_COUNT,
 };
 
 #[cfg(all(feature = "webgl", target_arch = "wasm32", not(feature = "webgpu")))]
@@ -80,6 +83,7 @@ bitflags::bitflags! {
         const MOTION_VECTOR_PREPASS       = 1 << 3;
         const DEFERRED_PREPASS            = 1 << 4;
         const OIT_ENABLED                 = 1 << 5;
+        const MULTIVIEW                   = 1 << 6;
     }
 }
 
@@ -92,7 +96,7 @@ impl MeshPipelineViewLayoutKey {
         use MeshPipelineViewLayoutKey as Key;
 
         format!(
-            "mesh_view_layout{}{}{}{}{}{}",
+            "mesh_view_layout{}{}{}{}{}{}{}",
             self.contains(Key::MULTISAMPLED)
                 .then_some("_multisampled")
                 .unwrap_or_default(),
@@ -111,6 +115,9 @@ impl MeshPipelineViewLayoutKey {
             self.contains(Key::OIT_ENABLED)
                 .then_some("_oit")
                 .unwrap_or_default(),
+            self.contains(Key::MULTIVIEW)
+                .then_some("_multiview")
+                .unwrap_or_default(),
         )
     }
 }
@@ -137,6 +144,9 @@ impl From<MeshPipelineKey> for MeshPipelineViewLayoutKey {
         if value.contains(MeshPipelineKey::OIT_ENABLED) {
             result |= MeshPipelineViewLayoutKey::OIT_ENABLED;
         }
+        if value.contains(MeshPipelineKey::MULTIVIEW) {
+            result |= MeshPipelineViewLayoutKey::MULTIVIEW;
+        }
 
         result
     }
@@ -378,6 +388,35 @@ fn layout_entries(
         (33, sampler(SamplerBindingType::Filtering)),
     ));
 
+    // Cloud shadow map
+    #[cfg(any(
+        not(feature = "webgl"),
+        not(target_arch = "wasm32"),
+        feature = "webgpu"
+    ))]
+    {
+        entries = entries.extend_with_indices((
+            (
+                37,
+                texture_2d(TextureSampleType::Float { filterable: true }),
+            ),
+            (38, sampler(SamplerBindingType::Filtering)),
+        ));
+    }
+
+    // Screen space global illumination texture
+    #[cfg(any(
+        not(feature = "webgl"),
+        not(target_arch = "wasm32"),
+        feature = "webgpu"
+    ))]
+    {
+        entries = entries.extend_with_indices(((
+            39,
+            texture_2d(TextureSampleType::Float { filterable: false }),
+        ),));
+    }
+
     // OIT
     if layout_key.contains(MeshPipelineViewLayoutKey::OIT_ENABLED) {
         // Check if the GPU supports writable storage buffers in the fragment shader
@@ -403,6 +442,14 @@ fn layout_entries(
         }
     }
 
+    // Multiview
+    if layout_key.contains(MeshPipelineViewLayoutKey::MULTIVIEW) {
+        entries = entries.extend_with_indices(((
+            40,
+            uniform_buffer::<MultiviewUniform>(true).visibility(ShaderStages::VERTEX_FRAGMENT),
+        ),));
+    }
+
     entries.to_vec()
 }
 
@@ -513,7 +560,11 @@ pub fn prepare_mesh_view_bind_groups(
     shadow_samplers: Res<ShadowSamplers>,
     (light_meta, global_light_meta): (Res<LightMeta>, Res<GlobalClusterableObjectMeta>),
     fog_meta: Res<FogMeta>,
-    (view_uniforms, environment_map_uniform): (Res<ViewUniforms>, Res<EnvironmentMapUniformBuffer>),
+    (view_uniforms, multiview_uniforms, environment_map_uniform): (
+        Res<ViewUniforms>,
+        Res<MultiviewUniforms>,
+        Res<EnvironmentMapUniformBuffer>,
+    ),
     views: Query<(
         Entity,
         &ViewShadowBindings,
@@ -526,6 +577,9 @@ pub fn prepare_mesh_view_bind_groups(
         Option<&RenderViewLightProbes<EnvironmentMapLight>>,
         Option<&RenderViewLightProbes<IrradianceVolume>>,
         Has<OrderIndependentTransparencySettings>,
+        Option<&VolumetricCloudsTextures>,
+        Option<&ScreenSpaceGlobalIlluminationResources>,
+        Has<Multiview>,
     )>,
     (images, mut fallback_images, fallback_image, fallback_image_zero): (
         Res<RenderAssets<GpuImage>>,
@@ -574,6 +628,9 @@ pub fn prepare_mesh_view_bind_groups(
             render_view_environment_maps,
             render_view_irradiance_volumes,
             has_oit,
+            volumetric_clouds_textures,
+            ssgi_resources,
+            multiview,
         ) in &views
         {
             let fallback_ssao = fallback_images
@@ -584,11 +641,17 @@ pub fn prepare_mesh_view_bind_groups(
                 .map(|t| &t.screen_space_ambient_occlusion_texture.default_view)
                 .unwrap_or(&fallback_ssao);
 
+            // The main passes of multiview views don't read the layered prepass textures.
+            let prepass_textures = prepass_textures.filter(|_| !multiview);
+
             let mut layout_key = MeshPipelineViewLayoutKey::from(*msaa)
                 | MeshPipelineViewLayoutKey::from(prepass_textures);
             if has_oit {
                 layout_key |= MeshPipelineViewLayoutKey::OIT_ENABLED;
             }
+            if multiview {
+                layout_key |= MeshPipelineViewLayoutKey::MULTIVIEW;
+            }
 
             let layout = &mesh_pipeline.get_view_layout(layout_key);
 
@@ -744,6 +807,36 @@ pub fn prepare_mesh_view_bind_groups(
             entries =
                 entries.extend_with_indices(((32, transmission_view), (33, transmission_sampler)));
 
+            #[cfg(any(
+                not(feature = "webgl"),
+                not(target_arch = "wasm32"),
+                feature = "webgpu"
+            ))]
+            {
+                let (cloud_shadow_map_view, cloud_shadow_map_sampler) =
+                    match volumetric_clouds_textures {
+                        Some(textures) => (
+                            &textures.shadow_map.default_view,
+                            &textures.shadow_map_sampler,
+                        ),
+                        None => (
+                            &fallback_image_zero.texture_view,
+                            &fallback_image_zero.sampler,
+                        ),
+                    };
+                entries = entries.extend_with_indices((
+                    (37, cloud_shadow_map_view),
+                    (38, cloud_shadow_map_sampler),
+                ));
+
+                let ssgi_view = ssgi_resources
+                    .map(|t| &t.screen_space_global_illumination_texture().default_view)
+                    .unwrap_or(&fallback_image_zero.texture_view);
+                entries = entries.extend_with_indices(((39, ssgi_view),));
+            }
+            #[cfg(all(feature = "webgl", target_arch = "wasm32", not(feature = "webgpu")))]
+            let _ = (volumetric_clouds_textures, ssgi_resources);
+
             if has_oit {
                 if let (
                     Some(oit_layers_binding),
@@ -762,6 +855,12 @@ pub fn prepare_mesh_view_bind_groups(
                 }
             }
 
+            if multiview {
+                if let Some(multiview_binding) = multiview_uniforms.uniforms.binding() {
+                    entries = entries.extend_with_indices(((40, multiview_binding),));
+                }
+            }
+
             commands.entity(entity).insert(MeshViewBindGroup {
                 value: render_device.create_bind_group("mesh_view_bind_group", layout, &entries),
             });
@@ -2,11 +2,17 @@
 
 #import bevy_pbr::mesh_view_types as types
 #import bevy_render::{
-    view::View,
+    view::{View, Multiview},
     globals::Globals,
 }
 
+#ifdef MULTIVIEW
+// The view of the eye being rendered, set by `select_view`.
+var<private> view: View;
+@group(0) @binding(40) var<uniform> multiview: Multiview;
+#else   // MULTIVIEW
 @group(0) @binding(0) var<uniform> view: View;
+#endif  // MULTIVIEW
 @group(0) @binding(1) var<uniform> lights: types::Lights;
 #ifdef NO_CUBE_ARRAY_TEXTURES_SUPPORT
 @group(0) @binding(2) var point_shadow_textures: texture_depth_cube;
@@ -112,8 +118,22 @@ const VISIBILITY_RANGE_UNIFORM_BUFFER_SIZE: u32 = 64u;
 @group(0) @binding(32) var view_transmission_texture: texture_2d<f32>;
 @group(0) @binding(33) var view_transmission_sampler: sampler;
 
+#ifndef WEBGL2
+@group(0) @binding(37) var cloud_shadow_map: texture_2d<f32>;
+@group(0) @binding(38) var cloud_shadow_map_sampler: sampler;
+@group(0) @binding(39) var screen_space_global_illumination_texture: texture_2d<f32>;
+#endif // WEBGL2
+
 #ifdef OIT_ENABLED
 @group(0) @binding(34) var<storage, read_write> oit_layers: array<vec2<u32>>;
 @group(0) @binding(35) var<storage, read_write> oit_layer_ids: array<atomic<i32>>;
 @group(0) @binding(36) var<uniform> oit_settings: types::OrderIndependentTransparencySettings;
 #endif // OIT_ENABLED
+
+#ifdef MULTIVIEW
+// Selects the view of the eye being rendered. Every entry point of a multiview pipeline must call
+// this before anything reads `view`.
+fn select_view(view_index: i32) {
+    view = multiview.views[view_index];
+}
+#endif  // MULTIVIEW
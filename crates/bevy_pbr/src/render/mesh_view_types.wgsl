@@ -13,14 +13,15 @@ struct ClusterableObject {
     spot_light_tan_angle: f32,
     soft_shadow_size: f32,
     shadow_map_near_z: f32,
-    texture_index: u32,
-    pad: f32,
+    contact_shadow_length: f32,
+    contact_shadow_thickness: f32,
 };
 
 const POINT_LIGHT_FLAGS_SHADOWS_ENABLED_BIT: u32                    = 1u;
 const POINT_LIGHT_FLAGS_SPOT_LIGHT_Y_NEGATIVE: u32                  = 2u;
 const POINT_LIGHT_FLAGS_VOLUMETRIC_BIT: u32                         = 4u;
 const POINT_LIGHT_FLAGS_AFFECTS_LIGHTMAPPED_MESH_DIFFUSE_BIT: u32   = 8u;
+const POINT_LIGHT_FLAGS_CONTACT_SHADOWS_BIT: u32                    = 16u;
 
 struct DirectionalCascade {
     clip_from_world: mat4x4<f32>,
@@ -41,11 +42,14 @@ struct DirectionalLight {
     cascades_overlap_proportion: f32,
     depth_texture_base_index: u32,
     skip: u32,
+    contact_shadow_length: f32,
+    contact_shadow_thickness: f32,
 };
 
 const DIRECTIONAL_LIGHT_FLAGS_SHADOWS_ENABLED_BIT: u32                  = 1u;
 const DIRECTIONAL_LIGHT_FLAGS_VOLUMETRIC_BIT: u32                       = 2u;
 const DIRECTIONAL_LIGHT_FLAGS_AFFECTS_LIGHTMAPPED_MESH_DIFFUSE_BIT: u32 = 4u;
+const DIRECTIONAL_LIGHT_FLAGS_CONTACT_SHADOWS_BIT: u32                  = 8u;
 
 struct Lights {
     // NOTE: this array size must be kept in sync with the constants defined in bevy_pbr/src/render/light.rs
@@ -68,6 +72,10 @@ struct Lights {
     spot_light_shadowmap_offset: i32,
     environment_map_smallest_specular_mip_level: u32,
     environment_map_intensity: f32,
+    // xy: world space center of the cloud shadow map
+    // z: 1 / world space extent of the cloud shadow map, or zero if there are no cloud shadows
+    // w: world space height of the bottom of the cloud layer
+    cloud_shadow_params: vec4<f32>,
 };
 
 struct Fog {
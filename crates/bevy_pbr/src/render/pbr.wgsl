@@ -27,6 +27,10 @@
 #import bevy_core_pipeline::oit::oit_draw
 #endif // OIT_ENABLED
 
+#ifdef DEBUG_VIEW
+#import bevy_pbr::debug_view::debug_view_color
+#endif // DEBUG_VIEW
+
 #ifdef FORWARD_DECAL
 #import bevy_pbr::decal::forward::get_forward_decal_info
 #endif
@@ -47,6 +51,10 @@ fn fragment(
 
     var in = vertex_output;
 
+#ifdef MULTIVIEW
+    bevy_pbr::mesh_view_bindings::select_view(in.view_index);
+#endif
+
     // If we're in the crossfade section of a visibility range, conditionally
     // discard the fragment according to the visibility pattern.
 #ifdef VISIBILITY_RANGE_DITHER
@@ -88,6 +96,10 @@ fn fragment(
     // apply in-shader post processing (fog, alpha-premultiply, and also tonemapping, debanding if the camera is non-hdr)
     // note this does not include fullscreen postprocessing effects like bloom.
     out.color = main_pass_post_lighting_processing(pbr_input, out.color);
+
+#ifdef DEBUG_VIEW
+    out.color = debug_view_color(in.instance_index, out.color);
+#endif // DEBUG_VIEW
 #endif
 
 #ifdef OIT_ENABLED
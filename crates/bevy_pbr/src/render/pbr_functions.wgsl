@@ -421,6 +421,10 @@ fn apply_pbr_lighting(
                 && (view_bindings::clusterable_objects.data[light_id].flags & mesh_view_types::POINT_LIGHT_FLAGS_SHADOWS_ENABLED_BIT) != 0u) {
             shadow = shadows::fetch_point_shadow(light_id, in.world_position, in.world_normal);
         }
+        if ((in.flags & MESH_FLAGS_SHADOW_RECEIVER_BIT) != 0u
+                && (view_bindings::clusterable_objects.data[light_id].flags & mesh_view_types::POINT_LIGHT_FLAGS_CONTACT_SHADOWS_BIT) != 0u) {
+            shadow = min(shadow, shadows::fetch_point_contact_shadow(light_id, in.world_position, in.frag_coord.xy));
+        }
 
         let light_contrib = lighting::point_light(light_id, &lighting_input, enable_diffuse);
         direct_light += light_contrib * shadow;
@@ -474,6 +478,11 @@ fn apply_pbr_lighting(
                 view_bindings::clusterable_objects.data[light_id].shadow_map_near_z,
             );
         }
+        if ((in.flags & MESH_FLAGS_SHADOW_RECEIVER_BIT) != 0u
+                && (view_bindings::clusterable_objects.data[light_id].flags &
+                    mesh_view_types::POINT_LIGHT_FLAGS_CONTACT_SHADOWS_BIT) != 0u) {
+            shadow = min(shadow, shadows::fetch_point_contact_shadow(light_id, in.world_position, in.frag_coord.xy));
+        }
 
         let light_contrib = lighting::spot_light(light_id, &lighting_input, enable_diffuse);
         direct_light += light_contrib * shadow;
@@ -531,6 +540,13 @@ fn apply_pbr_lighting(
                 && (view_bindings::lights.directional_lights[i].flags & mesh_view_types::DIRECTIONAL_LIGHT_FLAGS_SHADOWS_ENABLED_BIT) != 0u) {
             shadow = shadows::fetch_directional_shadow(i, in.world_position, in.world_normal, view_z);
         }
+        if ((in.flags & MESH_FLAGS_SHADOW_RECEIVER_BIT) != 0u
+                && (view_bindings::lights.directional_lights[i].flags & mesh_view_types::DIRECTIONAL_LIGHT_FLAGS_CONTACT_SHADOWS_BIT) != 0u) {
+            shadow = min(shadow, shadows::fetch_directional_contact_shadow(i, in.world_position, in.frag_coord.xy));
+        }
+        if (i == 0u && (in.flags & MESH_FLAGS_SHADOW_RECEIVER_BIT) != 0u) {
+            shadow *= shadows::fetch_cloud_shadow(in.world_position);
+        }
 
         var light_contrib = lighting::directional_light(i, &lighting_input, enable_diffuse);
 
@@ -647,6 +663,16 @@ fn apply_pbr_lighting(
     // Ambient light (indirect)
     indirect_light += ambient::ambient_light(in.world_position, in.N, in.V, NdotV, diffuse_color, F0, perceptual_roughness, diffuse_occlusion);
 
+#ifdef SCREEN_SPACE_GLOBAL_ILLUMINATION
+    // Screen space global illumination (indirect)
+    let ssgi = textureLoad(
+        view_bindings::screen_space_global_illumination_texture,
+        vec2<i32>(in.frag_coord.xy),
+        0
+    ).rgb;
+    indirect_light += ssgi * diffuse_color * diffuse_occlusion;
+#endif  // SCREEN_SPACE_GLOBAL_ILLUMINATION
+
     // we'll use the specular component of the transmitted environment
     // light in the call to `specular_transmissive_light()` below
     var specular_transmitted_environment_light = vec3<f32>(0.0);
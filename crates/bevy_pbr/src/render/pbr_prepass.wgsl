@@ -7,6 +7,7 @@
     pbr_functions::SampleBias,
     prepass_io,
     mesh_bindings::mesh,
+    mesh_functions,
     mesh_view_bindings::view,
 }
 
@@ -29,6 +30,10 @@ fn fragment(
     let is_front = true;
 #else   // MESHLET_MESH_MATERIAL_PASS
 
+#ifdef MULTIVIEW
+    bevy_pbr::mesh_view_bindings::select_view(in.view_index);
+#endif
+
 #ifdef BINDLESS
     let slot = mesh[in.instance_index].material_and_lightmap_bind_group_slot & 0xffffu;
     let flags = pbr_bindings::material[slot].flags;
@@ -131,10 +136,17 @@ fn fragment(
 #ifdef MESHLET_MESH_MATERIAL_PASS
     out.motion_vector = in.motion_vector;
 #else
-    out.motion_vector = pbr_prepass_functions::calculate_motion_vector(in.world_position, in.previous_world_position);
+    out.motion_vector = mesh_functions::limit_motion_vector(
+        pbr_prepass_functions::calculate_motion_vector(in.world_position, in.previous_world_position),
+        in.motion_vector_limit
+    );
 #endif
 #endif
 
+#ifdef OUTLINE_ID_PREPASS
+    out.outline_id = in.outline_id;
+#endif
+
     return out;
 }
 #else
@@ -3,10 +3,13 @@
 #import bevy_pbr::{
     mesh_view_types::POINT_LIGHT_FLAGS_SPOT_LIGHT_Y_NEGATIVE,
     mesh_view_bindings as view_bindings,
+    prepass_utils,
     shadow_sampling::{
         SPOT_SHADOW_TEXEL_SIZE, sample_shadow_cubemap, sample_shadow_cubemap_pcss,
         sample_shadow_map, sample_shadow_map_pcss,
-    }
+    },
+    utils::interleaved_gradient_noise,
+    view_transformations::{depth_ndc_to_view_z, ndc_to_uv, position_world_to_ndc},
 }
 
 #import bevy_render::{
@@ -16,6 +19,9 @@
 
 const flip_z: vec3<f32> = vec3<f32>(1.0, 1.0, -1.0);
 
+// The number of steps taken along the ray when computing contact shadows.
+const CONTACT_SHADOW_STEPS: u32 = 16u;
+
 fn fetch_point_shadow(light_id: u32, frag_position: vec4<f32>, surface_normal: vec3<f32>) -> f32 {
     let light = &view_bindings::clusterable_objects.data[light_id];
 
@@ -239,3 +245,115 @@ fn cascade_debug_visualization(
         (1.0 - overlay_alpha) * output_color.rgb + overlay_alpha * cascade_color
     );
 }
+
+// Ray marches the depth prepass from `world_position` toward the light to find
+// small-scale occluders that the shadow maps are too coarse to capture.
+//
+// Returns 0.0 if the ray is blocked by something on screen and 1.0 otherwise.
+// The result is meant to be combined with the shadow map result using `min`.
+fn fetch_contact_shadow(
+    world_position: vec3<f32>,
+    direction_to_light: vec3<f32>,
+    ray_length: f32,
+    thickness: f32,
+    frag_coord: vec2<f32>,
+) -> f32 {
+#ifdef DEPTH_PREPASS
+#ifndef WEBGL2
+    let step_vector = direction_to_light * (ray_length / f32(CONTACT_SHADOW_STEPS));
+
+    // Offset the start of the ray by a random fraction of a step, trading
+    // banding for noise that TAA can clean up.
+    let jitter = interleaved_gradient_noise(frag_coord, view_bindings::globals.frame_count);
+    var ray_position = world_position + step_vector * jitter;
+
+    for (var i: u32 = 0u; i < CONTACT_SHADOW_STEPS; i = i + 1u) {
+        ray_position += step_vector;
+
+        let ray_ndc = position_world_to_ndc(ray_position);
+        let ray_uv = ndc_to_uv(ray_ndc.xy);
+        if (any(ray_uv < vec2(0.0)) || any(ray_uv > vec2(1.0))) {
+            break;
+        }
+
+        let sample_coord = view_bindings::view.viewport.xy + ray_uv * view_bindings::view.viewport.zw;
+        let scene_depth = prepass_utils::prepass_depth(vec4(sample_coord, 0.0, 0.0), 0u);
+
+        // Compare linear depths. View space Z is negative in front of the
+        // camera, so a positive delta means that the scene is in front of the
+        // ray.
+        let depth_delta = depth_ndc_to_view_z(scene_depth) - depth_ndc_to_view_z(ray_ndc.z);
+        if (depth_delta > 0.0 && depth_delta < thickness) {
+            // Fade out near the edges of the screen, where occluders may
+            // suddenly appear or disappear.
+            let edge_distance = min(ray_uv, vec2(1.0) - ray_uv);
+            return 1.0 - saturate(min(edge_distance.x, edge_distance.y) * 10.0);
+        }
+    }
+#endif  // WEBGL2
+#endif  // DEPTH_PREPASS
+
+    return 1.0;
+}
+
+// Computes contact shadows for a point light or spot light.
+fn fetch_point_contact_shadow(light_id: u32, frag_position: vec4<f32>, frag_coord: vec2<f32>) -> f32 {
+    let light = &view_bindings::clusterable_objects.data[light_id];
+
+    // Don't march past the light itself.
+    let surface_to_light = (*light).position_radius.xyz - frag_position.xyz;
+    let distance_to_light = length(surface_to_light);
+    return fetch_contact_shadow(
+        frag_position.xyz,
+        surface_to_light / distance_to_light,
+        min((*light).contact_shadow_length, distance_to_light),
+        (*light).contact_shadow_thickness,
+        frag_coord,
+    );
+}
+
+// Computes contact shadows for a directional light.
+fn fetch_directional_contact_shadow(light_id: u32, frag_position: vec4<f32>, frag_coord: vec2<f32>) -> f32 {
+    let light = &view_bindings::lights.directional_lights[light_id];
+    return fetch_contact_shadow(
+        frag_position.xyz,
+        (*light).direction_to_light,
+        (*light).contact_shadow_length,
+        (*light).contact_shadow_thickness,
+        frag_coord,
+    );
+}
+
+// Returns how much light from the first directional light passes through the
+// volumetric cloud layer above a point, from the cloud shadow map.
+fn fetch_cloud_shadow(frag_position: vec4<f32>) -> f32 {
+#ifdef WEBGL2
+    return 1.0;
+#else   // WEBGL2
+    let params = view_bindings::lights.cloud_shadow_params;
+    let direction_to_light = view_bindings::lights.directional_lights[0].direction_to_light;
+    if params.z == 0.0 || direction_to_light.y <= 0.0 {
+        return 1.0;
+    }
+
+    // Project the point along the light direction onto the bottom of the cloud
+    // layer, which is where the shadow map was rendered from.
+    let t = (params.w - frag_position.y) / direction_to_light.y;
+    if t < 0.0 {
+        return 1.0;
+    }
+    let position_xz = frag_position.xz + direction_to_light.xz * t;
+    let uv = (position_xz - params.xy) * params.z + 0.5;
+
+    let opacity = textureSampleLevel(
+        view_bindings::cloud_shadow_map,
+        view_bindings::cloud_shadow_map_sampler,
+        uv,
+        0.0
+    ).a;
+
+    // Fade out towards the edges of the shadow map
+    let edge_distance = min(min(uv.x, 1.0 - uv.x), min(uv.y, 1.0 - uv.y));
+    return 1.0 - opacity * saturate(edge_distance * 10.0);
+#endif  // WEBGL2
+}
@@ -0,0 +1,15 @@
+// Copies the lit color of the opaque pass so that the next frame's rays can
+// gather it.
+
+@group(0) @binding(0) var color: texture_2d<f32>;
+@group(0) @binding(1) var previous_color: texture_storage_2d<rgba16float, write>;
+
+@compute
+@workgroup_size(8, 8, 1)
+fn capture_color(@builtin(global_invocation_id) global_id: vec3<u32>) {
+    if any(global_id.xy >= textureDimensions(previous_color)) {
+        return;
+    }
+    let pixel = vec2<i32>(global_id.xy);
+    textureStore(previous_color, pixel, vec4(textureLoad(color, pixel, 0).rgb, 1.0));
+}
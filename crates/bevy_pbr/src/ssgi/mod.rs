@@ -0,0 +1,1087 @@
+//! Screen space global illumination, traced through a hierarchical Z-buffer.
+
+use crate::NodePbr;
+use bevy_app::{App, Plugin};
+use bevy_asset::{load_internal_asset, weak_handle, Handle};
+use bevy_core_pipeline::{
+    core_3d::graph::{Core3d, Node3d},
+    experimental::mip_generation::{
+        DepthPyramidDummyTexture, DownsampleDepthPipelines, ViewDepthPyramid,
+    },
+    prelude::Camera3d,
+    prepass::{DepthPrepass, MotionVectorPrepass, NormalPrepass, ViewPrepassTextures},
+};
+use bevy_derive::{Deref, DerefMut};
+use bevy_ecs::{
+    prelude::{require, Component, Entity},
+    query::{QueryItem, With, Without},
+    reflect::ReflectComponent,
+    resource::Resource,
+    schedule::IntoSystemConfigs,
+    system::{Commands, Query, Res, ResMut},
+    world::{FromWorld, World},
+};
+use bevy_math::UVec2;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    camera::ExtractedCamera,
+    globals::{GlobalsBuffer, GlobalsUniform},
+    prelude::Camera,
+    render_graph::{NodeRunError, RenderGraphApp, RenderGraphContext, ViewNode, ViewNodeRunner},
+    render_resource::{
+        binding_types::{
+            sampler, texture_2d, texture_depth_2d, texture_storage_2d, uniform_buffer,
+        },
+        *,
+    },
+    renderer::{RenderAdapter, RenderContext, RenderDevice, RenderQueue},
+    sync_component::SyncComponentPlugin,
+    sync_world::RenderEntity,
+    texture::{CachedTexture, TextureCache},
+    view::{Msaa, ViewTarget, ViewUniform, ViewUniformOffset, ViewUniforms},
+    Extract, ExtractSchedule, Render, RenderApp, RenderSet,
+};
+use tracing::{error, warn};
+
+const SSGI_SHADER_HANDLE: Handle<Shader> = weak_handle!("2af3c60d-1faa-4c8b-9681-86e3adac8d28");
+const SPATIAL_DENOISE_SHADER_HANDLE: Handle<Shader> =
+    weak_handle!("191eb50c-352d-439c-80ee-b1aa88e09c7e");
+const TEMPORAL_DENOISE_SHADER_HANDLE: Handle<Shader> =
+    weak_handle!("15d78ddb-0b66-49d8-8835-ca46a8b5ae90");
+const CAPTURE_COLOR_SHADER_HANDLE: Handle<Shader> =
+    weak_handle!("6c4c948f-1c87-4111-af75-2318fa552a60");
+const SSGI_UTILS_SHADER_HANDLE: Handle<Shader> =
+    weak_handle!("65857499-74e3-42a9-aa60-12213704d6a0");
+
+/// Plugin for screen space global illumination.
+pub struct ScreenSpaceGlobalIlluminationPlugin;
+
+impl Plugin for ScreenSpaceGlobalIlluminationPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(app, SSGI_SHADER_HANDLE, "ssgi.wgsl", Shader::from_wgsl);
+        load_internal_asset!(
+            app,
+            SPATIAL_DENOISE_SHADER_HANDLE,
+            "spatial_denoise.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            TEMPORAL_DENOISE_SHADER_HANDLE,
+            "temporal_denoise.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            CAPTURE_COLOR_SHADER_HANDLE,
+            "capture_color.wgsl",
+            Shader::from_wgsl
+        );
+        load_internal_asset!(
+            app,
+            SSGI_UTILS_SHADER_HANDLE,
+            "ssgi_utils.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<ScreenSpaceGlobalIllumination>();
+
+        app.add_plugins(SyncComponentPlugin::<ScreenSpaceGlobalIllumination>::default());
+    }
+
+    fn finish(&self, app: &mut App) {
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else {
+            return;
+        };
+
+        if !render_app
+            .world()
+            .resource::<RenderAdapter>()
+            .get_downlevel_capabilities()
+            .flags
+            .contains(DownlevelFlags::COMPUTE_SHADERS)
+        {
+            warn!("ScreenSpaceGlobalIlluminationPlugin not loaded. GPU lacks support for compute shaders.");
+            return;
+        }
+
+        // The depth pyramid is built with a single pass that writes every mip
+        // level at once.
+        if render_app
+            .world()
+            .resource::<RenderDevice>()
+            .limits()
+            .max_storage_textures_per_shader_stage
+            < 12
+        {
+            warn!("ScreenSpaceGlobalIlluminationPlugin not loaded. GPU lacks support: Limits::max_storage_textures_per_shader_stage is less than 12.");
+            return;
+        }
+
+        render_app
+            .init_resource::<SsgiPipelines>()
+            .init_resource::<ScreenSpaceGlobalIlluminationBuffer>()
+            .add_systems(ExtractSchedule, extract_ssgi_settings)
+            .add_systems(
+                Render,
+                (
+                    (
+                        remove_ssgi_resources,
+                        prepare_ssgi_textures,
+                        prepare_ssgi_uniforms,
+                    )
+                        .chain()
+                        .in_set(RenderSet::PrepareResources),
+                    prepare_ssgi_bind_groups.in_set(RenderSet::PrepareBindGroups),
+                ),
+            )
+            .add_render_graph_node::<ViewNodeRunner<SsgiNode>>(
+                Core3d,
+                NodePbr::ScreenSpaceGlobalIllumination,
+            )
+            .add_render_graph_edges(
+                Core3d,
+                (
+                    // END_PRE_PASSES -> SCREEN_SPACE_GLOBAL_ILLUMINATION -> MAIN_PASS
+                    Node3d::EndPrepasses,
+                    NodePbr::ScreenSpaceGlobalIllumination,
+                    Node3d::StartMainPass,
+                ),
+            )
+            .add_render_graph_node::<ViewNodeRunner<SsgiCaptureColorNode>>(
+                Core3d,
+                NodePbr::ScreenSpaceGlobalIlluminationCaptureColor,
+            )
+            .add_render_graph_edges(
+                Core3d,
+                (
+                    // Capture the color before transparent objects, which
+                    // aren't in the depth buffer, are drawn over it.
+                    Node3d::MainOpaquePass,
+                    NodePbr::ScreenSpaceGlobalIlluminationCaptureColor,
+                    Node3d::MainTransmissivePass,
+                ),
+            );
+    }
+}
+
+/// Component to apply screen space global illumination to a 3d camera.
+///
+/// Screen space global illumination (SSGI) adds a bounce of _indirect_ diffuse
+/// light from the surfaces that are visible on-screen. Rays are traced from
+/// every pixel through a hierarchical depth buffer, and whatever they hit
+/// contributes the lit color it had in the previous frame. Since that color
+/// already includes the previous frame's SSGI, further bounces build up over
+/// time.
+///
+/// The result is noisy, so it's filtered spatially and then accumulated over
+/// several frames using motion vectors. It's applied on top of any other
+/// source of indirect light, and works with both forward and deferred
+/// rendering.
+///
+/// # Usage Notes
+///
+/// Requires that you add [`ScreenSpaceGlobalIlluminationPlugin`] to your app,
+/// and that [`Msaa`] is off.
+///
+/// Since the lighting is gathered from the final color of the opaque pass,
+/// this is best used with an HDR camera.
+///
+/// As with all screen-space techniques, light can only come from surfaces on
+/// screen, so light bouncing off objects will disappear as they leave the
+/// view or become occluded.
+///
+/// SSGI is not supported on `WebGL2`.
+#[derive(Component, Reflect, PartialEq, Clone, Debug)]
+#[reflect(Component, Debug, Default, PartialEq)]
+#[require(DepthPrepass, NormalPrepass, MotionVectorPrepass)]
+#[doc(alias = "Ssgi")]
+pub struct ScreenSpaceGlobalIllumination {
+    /// The number of rays traced from each pixel per frame.
+    ///
+    /// Higher values reduce noise, at a proportional cost in GPU time.
+    pub samples: u32,
+    /// The maximum number of steps each ray takes through the depth pyramid.
+    pub max_steps: u32,
+    /// The maximum distance, in world units, that light is gathered from.
+    pub max_distance: f32,
+    /// A constant estimated thickness of objects.
+    ///
+    /// A ray that passes behind a surface by more than this distance is
+    /// assumed to have gone behind the object rather than hit it.
+    pub thickness: f32,
+    /// A multiplier for the gathered light.
+    pub intensity: f32,
+    /// How much of the previous frame's result is kept each frame.
+    ///
+    /// Higher values give smoother lighting, but it takes longer to respond
+    /// to changes.
+    pub temporal_blend: f32,
+}
+
+impl Default for ScreenSpaceGlobalIllumination {
+    fn default() -> Self {
+        Self {
+            samples: 1,
+            max_steps: 48,
+            max_distance: 10.0,
+            thickness: 0.5,
+            intensity: 1.0,
+            temporal_blend: 0.9,
+        }
+    }
+}
+
+/// A version of [`ScreenSpaceGlobalIllumination`] for upload to the GPU.
+#[derive(Clone, Copy, ShaderType)]
+struct ScreenSpaceGlobalIlluminationUniform {
+    max_distance: f32,
+    thickness: f32,
+    intensity: f32,
+    /// Zero if there's no history to blend with.
+    temporal_blend: f32,
+    samples: u32,
+    max_steps: u32,
+    depth_pyramid_mip_count: u32,
+}
+
+/// A GPU buffer that stores the screen space global illumination settings for
+/// each view.
+#[derive(Resource, Default, Deref, DerefMut)]
+struct ScreenSpaceGlobalIlluminationBuffer(
+    DynamicUniformBuffer<ScreenSpaceGlobalIlluminationUniform>,
+);
+
+/// A component that stores the offset within the
+/// [`ScreenSpaceGlobalIlluminationBuffer`] for each view.
+#[derive(Component, Default, Deref, DerefMut)]
+struct ViewScreenSpaceGlobalIlluminationUniformOffset(u32);
+
+impl ScreenSpaceGlobalIlluminationUniform {
+    fn new(
+        settings: &ScreenSpaceGlobalIllumination,
+        depth_pyramid_mip_count: u32,
+        has_history: bool,
+    ) -> Self {
+        Self {
+            max_distance: settings.max_distance,
+            thickness: settings.thickness,
+            intensity: settings.intensity,
+            temporal_blend: if has_history {
+                settings.temporal_blend.clamp(0.0, 0.99)
+            } else {
+                0.0
+            },
+            samples: settings.samples.max(1),
+            max_steps: settings.max_steps,
+            depth_pyramid_mip_count,
+        }
+    }
+}
+
+/// Per-view state carried from one frame to the next.
+#[derive(Component)]
+struct SsgiHistory {
+    size: UVec2,
+    frame: u32,
+}
+
+impl SsgiHistory {
+    /// Returns the index of the history texture that this frame writes to.
+    fn current(&self) -> usize {
+        self.frame as usize % 2
+    }
+
+    /// Returns `true` if the last frame's result can be blended with at
+    /// `size`.
+    ///
+    /// The history textures are reallocated when the view is resized, so
+    /// there's nothing to blend with then.
+    fn is_valid(&self, size: UVec2) -> bool {
+        self.size == size
+    }
+
+    /// Swaps the history textures for the next frame.
+    fn advance(&mut self, size: UVec2) {
+        self.size = size;
+        self.frame = self.frame.wrapping_add(1);
+    }
+}
+
+#[derive(Default)]
+struct SsgiNode {}
+
+impl ViewNode for SsgiNode {
+    type ViewQuery = (
+        &'static ExtractedCamera,
+        &'static ScreenSpaceGlobalIlluminationResources,
+        &'static SsgiBindGroups,
+        &'static ViewUniformOffset,
+        &'static ViewScreenSpaceGlobalIlluminationUniformOffset,
+    );
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (camera, ssgi_resources, bind_groups, view_uniform_offset, ssgi_uniform_offset): QueryItem<
+            Self::ViewQuery,
+        >,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let pipelines = world.resource::<SsgiPipelines>();
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let Some((first_downsample_depth_pipeline_id, second_downsample_depth_pipeline_id)) = world
+            .get_resource::<DownsampleDepthPipelines>()
+            .and_then(DownsampleDepthPipelines::nearest_pipeline_ids)
+        else {
+            return Ok(());
+        };
+        let (
+            Some(camera_size),
+            Some(first_downsample_depth_pipeline),
+            Some(second_downsample_depth_pipeline),
+            Some(ssgi_pipeline),
+            Some(spatial_denoise_pipeline),
+            Some(temporal_denoise_pipeline),
+        ) = (
+            camera.physical_viewport_size,
+            pipeline_cache.get_compute_pipeline(first_downsample_depth_pipeline_id),
+            pipeline_cache.get_compute_pipeline(second_downsample_depth_pipeline_id),
+            pipeline_cache.get_compute_pipeline(pipelines.ssgi_pipeline),
+            pipeline_cache.get_compute_pipeline(pipelines.spatial_denoise_pipeline),
+            pipeline_cache.get_compute_pipeline(pipelines.temporal_denoise_pipeline),
+        )
+        else {
+            return Ok(());
+        };
+
+        render_context.command_encoder().push_debug_group("ssgi");
+
+        ssgi_resources.depth_pyramid.downsample_depth(
+            "ssgi_downsample_depth_pass",
+            render_context,
+            camera_size,
+            &bind_groups.downsample_depth_bind_group,
+            first_downsample_depth_pipeline,
+            second_downsample_depth_pipeline,
+        );
+
+        {
+            let mut ssgi_pass =
+                render_context
+                    .command_encoder()
+                    .begin_compute_pass(&ComputePassDescriptor {
+                        label: Some("ssgi_ssgi_pass"),
+                        timestamp_writes: None,
+                    });
+            ssgi_pass.set_pipeline(ssgi_pipeline);
+            ssgi_pass.set_bind_group(0, &bind_groups.ssgi_bind_group, &[**ssgi_uniform_offset]);
+            ssgi_pass.set_bind_group(
+                1,
+                &bind_groups.common_bind_group,
+                &[view_uniform_offset.offset],
+            );
+            ssgi_pass.dispatch_workgroups(camera_size.x.div_ceil(8), camera_size.y.div_ceil(8), 1);
+        }
+
+        {
+            let mut spatial_denoise_pass =
+                render_context
+                    .command_encoder()
+                    .begin_compute_pass(&ComputePassDescriptor {
+                        label: Some("ssgi_spatial_denoise_pass"),
+                        timestamp_writes: None,
+                    });
+            spatial_denoise_pass.set_pipeline(spatial_denoise_pipeline);
+            spatial_denoise_pass.set_bind_group(0, &bind_groups.spatial_denoise_bind_group, &[]);
+            spatial_denoise_pass.set_bind_group(
+                1,
+                &bind_groups.common_bind_group,
+                &[view_uniform_offset.offset],
+            );
+            spatial_denoise_pass.dispatch_workgroups(
+                camera_size.x.div_ceil(8),
+                camera_size.y.div_ceil(8),
+                1,
+            );
+        }
+
+        {
+            let mut temporal_denoise_pass =
+                render_context
+                    .command_encoder()
+                    .begin_compute_pass(&ComputePassDescriptor {
+                        label: Some("ssgi_temporal_denoise_pass"),
+                        timestamp_writes: None,
+                    });
+            temporal_denoise_pass.set_pipeline(temporal_denoise_pipeline);
+            temporal_denoise_pass.set_bind_group(
+                0,
+                &bind_groups.temporal_denoise_bind_group,
+                &[**ssgi_uniform_offset],
+            );
+            temporal_denoise_pass.set_bind_group(
+                1,
+                &bind_groups.common_bind_group,
+                &[view_uniform_offset.offset],
+            );
+            temporal_denoise_pass.dispatch_workgroups(
+                camera_size.x.div_ceil(8),
+                camera_size.y.div_ceil(8),
+                1,
+            );
+        }
+
+        render_context.command_encoder().pop_debug_group();
+        Ok(())
+    }
+}
+
+/// Copies the output of the opaque pass, for the next frame's rays to gather
+/// light from.
+#[derive(Default)]
+struct SsgiCaptureColorNode {}
+
+impl ViewNode for SsgiCaptureColorNode {
+    type ViewQuery = (
+        &'static ExtractedCamera,
+        &'static ViewTarget,
+        &'static ScreenSpaceGlobalIlluminationResources,
+    );
+
+    fn run(
+        &self,
+        _graph: &mut RenderGraphContext,
+        render_context: &mut RenderContext,
+        (camera, view_target, ssgi_resources): QueryItem<Self::ViewQuery>,
+        world: &World,
+    ) -> Result<(), NodeRunError> {
+        let pipelines = world.resource::<SsgiPipelines>();
+        let pipeline_cache = world.resource::<PipelineCache>();
+        let (Some(camera_size), Some(capture_color_pipeline)) = (
+            camera.physical_viewport_size,
+            pipeline_cache.get_compute_pipeline(pipelines.capture_color_pipeline),
+        ) else {
+            return Ok(());
+        };
+
+        // The main texture changes from frame to frame, so the bind group has
+        // to be created here.
+        let capture_color_bind_group = render_context.render_device().create_bind_group(
+            "ssgi_capture_color_bind_group",
+            &pipelines.capture_color_bind_group_layout,
+            &BindGroupEntries::sequential((
+                view_target.main_texture_view(),
+                &ssgi_resources.previous_color_texture.default_view,
+            )),
+        );
+
+        let mut capture_color_pass =
+            render_context
+                .command_encoder()
+                .begin_compute_pass(&ComputePassDescriptor {
+                    label: Some("ssgi_capture_color_pass"),
+                    timestamp_writes: None,
+                });
+        capture_color_pass.set_pipeline(capture_color_pipeline);
+        capture_color_pass.set_bind_group(0, &capture_color_bind_group, &[]);
+        capture_color_pass.dispatch_workgroups(
+            camera_size.x.div_ceil(8),
+            camera_size.y.div_ceil(8),
+            1,
+        );
+
+        Ok(())
+    }
+}
+
+#[derive(Resource)]
+struct SsgiPipelines {
+    ssgi_pipeline: CachedComputePipelineId,
+    spatial_denoise_pipeline: CachedComputePipelineId,
+    temporal_denoise_pipeline: CachedComputePipelineId,
+    capture_color_pipeline: CachedComputePipelineId,
+
+    common_bind_group_layout: BindGroupLayout,
+    ssgi_bind_group_layout: BindGroupLayout,
+    spatial_denoise_bind_group_layout: BindGroupLayout,
+    temporal_denoise_bind_group_layout: BindGroupLayout,
+    capture_color_bind_group_layout: BindGroupLayout,
+
+    point_clamp_sampler: Sampler,
+    linear_clamp_sampler: Sampler,
+}
+
+impl FromWorld for SsgiPipelines {
+    fn from_world(world: &mut World) -> Self {
+        let render_device = world.resource::<RenderDevice>();
+        let pipeline_cache = world.resource::<PipelineCache>();
+
+        let point_clamp_sampler = render_device.create_sampler(&SamplerDescriptor {
+            min_filter: FilterMode::Nearest,
+            mag_filter: FilterMode::Nearest,
+            mipmap_filter: FilterMode::Nearest,
+            address_mode_u: AddressMode::ClampToEdge,
+            address_mode_v: AddressMode::ClampToEdge,
+            ..Default::default()
+        });
+        let linear_clamp_sampler = render_device.create_sampler(&SamplerDescriptor {
+            min_filter: FilterMode::Linear,
+            mag_filter: FilterMode::Linear,
+            mipmap_filter: FilterMode::Nearest,
+            address_mode_u: AddressMode::ClampToEdge,
+            address_mode_v: AddressMode::ClampToEdge,
+            ..Default::default()
+        });
+
+        let common_bind_group_layout = render_device.create_bind_group_layout(
+            "ssgi_common_bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::COMPUTE,
+                (
+                    sampler(SamplerBindingType::NonFiltering),
+                    sampler(SamplerBindingType::Filtering),
+                    uniform_buffer::<ViewUniform>(true),
+                ),
+            ),
+        );
+
+        let ssgi_bind_group_layout = render_device.create_bind_group_layout(
+            "ssgi_ssgi_bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::COMPUTE,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: false }),
+                    texture_depth_2d(),
+                    texture_2d(TextureSampleType::Float { filterable: false }),
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    texture_2d(TextureSampleType::Float { filterable: false }),
+                    texture_storage_2d(TextureFormat::Rgba16Float, StorageTextureAccess::WriteOnly),
+                    uniform_buffer::<ScreenSpaceGlobalIlluminationUniform>(true),
+                    uniform_buffer::<GlobalsUniform>(false),
+                ),
+            ),
+        );
+
+        let spatial_denoise_bind_group_layout = render_device.create_bind_group_layout(
+            "ssgi_spatial_denoise_bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::COMPUTE,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: false }),
+                    texture_depth_2d(),
+                    texture_2d(TextureSampleType::Float { filterable: false }),
+                    texture_storage_2d(TextureFormat::Rgba16Float, StorageTextureAccess::WriteOnly),
+                ),
+            ),
+        );
+
+        let temporal_denoise_bind_group_layout = render_device.create_bind_group_layout(
+            "ssgi_temporal_denoise_bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::COMPUTE,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: false }),
+                    texture_2d(TextureSampleType::Float { filterable: true }),
+                    texture_2d(TextureSampleType::Float { filterable: false }),
+                    texture_storage_2d(TextureFormat::Rgba16Float, StorageTextureAccess::WriteOnly),
+                    uniform_buffer::<ScreenSpaceGlobalIlluminationUniform>(true),
+                ),
+            ),
+        );
+
+        let capture_color_bind_group_layout = render_device.create_bind_group_layout(
+            "ssgi_capture_color_bind_group_layout",
+            &BindGroupLayoutEntries::sequential(
+                ShaderStages::COMPUTE,
+                (
+                    texture_2d(TextureSampleType::Float { filterable: false }),
+                    texture_storage_2d(TextureFormat::Rgba16Float, StorageTextureAccess::WriteOnly),
+                ),
+            ),
+        );
+
+        let ssgi_pipeline = pipeline_cache.queue_compute_pipeline(ComputePipelineDescriptor {
+            label: Some("ssgi_ssgi_pipeline".into()),
+            layout: vec![
+                ssgi_bind_group_layout.clone(),
+                common_bind_group_layout.clone(),
+            ],
+            push_constant_ranges: vec![],
+            shader: SSGI_SHADER_HANDLE,
+            shader_defs: Vec::new(),
+            entry_point: "ssgi".into(),
+            zero_initialize_workgroup_memory: false,
+        });
+
+        let spatial_denoise_pipeline =
+            pipeline_cache.queue_compute_pipeline(ComputePipelineDescriptor {
+                label: Some("ssgi_spatial_denoise_pipeline".into()),
+                layout: vec![
+                    spatial_denoise_bind_group_layout.clone(),
+                    common_bind_group_layout.clone(),
+                ],
+                push_constant_ranges: vec![],
+                shader: SPATIAL_DENOISE_SHADER_HANDLE,
+                shader_defs: Vec::new(),
+                entry_point: "spatial_denoise".into(),
+                zero_initialize_workgroup_memory: false,
+            });
+
+        let temporal_denoise_pipeline =
+            pipeline_cache.queue_compute_pipeline(ComputePipelineDescriptor {
+                label: Some("ssgi_temporal_denoise_pipeline".into()),
+                layout: vec![
+                    temporal_denoise_bind_group_layout.clone(),
+                    common_bind_group_layout.clone(),
+                ],
+                push_constant_ranges: vec![],
+                shader: TEMPORAL_DENOISE_SHADER_HANDLE,
+                shader_defs: Vec::new(),
+                entry_point: "temporal_denoise".into(),
+                zero_initialize_workgroup_memory: false,
+            });
+
+        let capture_color_pipeline =
+            pipeline_cache.queue_compute_pipeline(ComputePipelineDescriptor {
+                label: Some("ssgi_capture_color_pipeline".into()),
+                layout: vec![capture_color_bind_group_layout.clone()],
+                push_constant_ranges: vec![],
+                shader: CAPTURE_COLOR_SHADER_HANDLE,
+                shader_defs: Vec::new(),
+                entry_point: "capture_color".into(),
+                zero_initialize_workgroup_memory: false,
+            });
+
+        Self {
+            ssgi_pipeline,
+            spatial_denoise_pipeline,
+            temporal_denoise_pipeline,
+            capture_color_pipeline,
+
+            common_bind_group_layout,
+            ssgi_bind_group_layout,
+            spatial_denoise_bind_group_layout,
+            temporal_denoise_bind_group_layout,
+            capture_color_bind_group_layout,
+
+            point_clamp_sampler,
+            linear_clamp_sampler,
+        }
+    }
+}
+
+fn extract_ssgi_settings(
+    mut commands: Commands,
+    cameras: Extract<
+        Query<
+            (RenderEntity, &Camera, &ScreenSpaceGlobalIllumination, &Msaa),
+            (
+                With<Camera3d>,
+                With<DepthPrepass>,
+                With<NormalPrepass>,
+                With<MotionVectorPrepass>,
+            ),
+        >,
+    >,
+) {
+    for (entity, camera, ssgi_settings, msaa) in &cameras {
+        let mut entity_commands = commands
+            .get_entity(entity)
+            .expect("SSGI entity wasn't synced.");
+        if *msaa != Msaa::Off {
+            error!(
+                "SSGI is being used which requires Msaa::Off, but Msaa is currently set to Msaa::{:?}",
+                *msaa
+            );
+            entity_commands.remove::<ScreenSpaceGlobalIllumination>();
+        } else if camera.is_active {
+            entity_commands.insert(ssgi_settings.clone());
+        } else {
+            entity_commands.remove::<ScreenSpaceGlobalIllumination>();
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct ScreenSpaceGlobalIlluminationResources {
+    depth_pyramid: ViewDepthPyramid,
+    noisy_texture: CachedTexture,    // Traced, one bounce of light
+    denoised_texture: CachedTexture, // Spatially denoised
+    history: [CachedTexture; 2],     // Temporally denoised, this frame and the last
+    current: usize,
+    previous_color_texture: CachedTexture,
+}
+
+impl ScreenSpaceGlobalIlluminationResources {
+    /// The denoised indirect diffuse light for this frame, which is applied
+    /// in the main pass.
+    #[inline]
+    pub fn screen_space_global_illumination_texture(&self) -> &CachedTexture {
+        &self.history[self.current]
+    }
+
+    #[inline]
+    fn previous_history(&self) -> &CachedTexture {
+        &self.history[1 - self.current]
+    }
+}
+
+/// Frees the textures and history of views that no longer have
+/// [`ScreenSpaceGlobalIllumination`], so that the history isn't blended with
+/// if it's enabled again.
+fn remove_ssgi_resources(
+    mut commands: Commands,
+    views: Query<Entity, (With<SsgiHistory>, Without<ScreenSpaceGlobalIllumination>)>,
+) {
+    for entity in &views {
+        commands
+            .entity(entity)
+            .remove::<(ScreenSpaceGlobalIlluminationResources, SsgiHistory)>();
+    }
+}
+
+fn prepare_ssgi_textures(
+    mut commands: Commands,
+    mut texture_cache: ResMut<TextureCache>,
+    render_device: Res<RenderDevice>,
+    depth_pyramid_dummy_texture: Res<DepthPyramidDummyTexture>,
+    views: Query<
+        (Entity, &ExtractedCamera, Option<&SsgiHistory>),
+        With<ScreenSpaceGlobalIllumination>,
+    >,
+) {
+    for (entity, camera, history) in &views {
+        let Some(physical_viewport_size) = camera.physical_viewport_size else {
+            continue;
+        };
+        let descriptor = |label| TextureDescriptor {
+            label: Some(label),
+            size: Extent3d {
+                width: physical_viewport_size.x,
+                height: physical_viewport_size.y,
+                depth_or_array_layers: 1,
+            },
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Rgba16Float,
+            usage: TextureUsages::STORAGE_BINDING | TextureUsages::TEXTURE_BINDING,
+            view_formats: &[],
+        };
+
+        let depth_pyramid = ViewDepthPyramid::new(
+            &render_device,
+            &mut texture_cache,
+            &depth_pyramid_dummy_texture,
+            physical_viewport_size,
+            "ssgi_depth_pyramid_texture",
+            "ssgi_depth_pyramid_texture_view",
+        );
+
+        commands
+            .entity(entity)
+            .insert(ScreenSpaceGlobalIlluminationResources {
+                depth_pyramid,
+                noisy_texture: texture_cache.get(&render_device, descriptor("ssgi_noisy_texture")),
+                denoised_texture: texture_cache
+                    .get(&render_device, descriptor("ssgi_denoised_texture")),
+                history: [
+                    texture_cache.get(&render_device, descriptor("ssgi_history_1")),
+                    texture_cache.get(&render_device, descriptor("ssgi_history_2")),
+                ],
+                current: history.map_or(0, SsgiHistory::current),
+                previous_color_texture: texture_cache
+                    .get(&render_device, descriptor("ssgi_previous_color_texture")),
+            });
+    }
+}
+
+fn prepare_ssgi_uniforms(
+    mut commands: Commands,
+    mut ssgi_buffer: ResMut<ScreenSpaceGlobalIlluminationBuffer>,
+    render_device: Res<RenderDevice>,
+    render_queue: Res<RenderQueue>,
+    mut views: Query<(
+        Entity,
+        &ExtractedCamera,
+        &ScreenSpaceGlobalIllumination,
+        &ScreenSpaceGlobalIlluminationResources,
+        Option<&mut SsgiHistory>,
+    )>,
+) {
+    let views_count = views.iter().len();
+    let Some(mut writer) = ssgi_buffer.get_writer(views_count, &render_device, &render_queue)
+    else {
+        return;
+    };
+
+    for (entity, camera, ssgi_settings, ssgi_resources, history) in &mut views {
+        let Some(size) = camera.physical_viewport_size else {
+            continue;
+        };
+
+        let has_history = history
+            .as_ref()
+            .is_some_and(|history| history.is_valid(size));
+        let uniform = ScreenSpaceGlobalIlluminationUniform::new(
+            ssgi_settings,
+            ssgi_resources.depth_pyramid.mip_count,
+            has_history,
+        );
+
+        commands
+            .entity(entity)
+            .insert(ViewScreenSpaceGlobalIlluminationUniformOffset(
+                writer.write(&uniform),
+            ));
+
+        match history {
+            Some(mut history) => history.advance(size),
+            None => {
+                commands
+                    .entity(entity)
+                    .insert(SsgiHistory { size, frame: 1 });
+            }
+        }
+    }
+}
+
+#[derive(Component)]
+struct SsgiBindGroups {
+    common_bind_group: BindGroup,
+    downsample_depth_bind_group: BindGroup,
+    ssgi_bind_group: BindGroup,
+    spatial_denoise_bind_group: BindGroup,
+    temporal_denoise_bind_group: BindGroup,
+}
+
+fn prepare_ssgi_bind_groups(
+    mut commands: Commands,
+    render_device: Res<RenderDevice>,
+    pipelines: Res<SsgiPipelines>,
+    downsample_depth_pipelines: Option<Res<DownsampleDepthPipelines>>,
+    view_uniforms: Res<ViewUniforms>,
+    global_uniforms: Res<GlobalsBuffer>,
+    ssgi_buffer: Res<ScreenSpaceGlobalIlluminationBuffer>,
+    views: Query<(
+        Entity,
+        &ScreenSpaceGlobalIlluminationResources,
+        &ViewPrepassTextures,
+    )>,
+) {
+    let (
+        Some(downsample_depth_pipelines),
+        Some(view_uniforms),
+        Some(globals_uniforms),
+        Some(ssgi_uniforms),
+    ) = (
+        downsample_depth_pipelines,
+        view_uniforms.uniforms.binding(),
+        global_uniforms.buffer.binding(),
+        ssgi_buffer.binding(),
+    )
+    else {
+        return;
+    };
+
+    for (entity, ssgi_resources, prepass_textures) in &views {
+        let (Some(depth_view), Some(normal_view), Some(motion_vectors_view)) = (
+            prepass_textures.depth_view(),
+            prepass_textures.normal_view(),
+            prepass_textures.motion_vectors_view(),
+        ) else {
+            continue;
+        };
+
+        let common_bind_group = render_device.create_bind_group(
+            "ssgi_common_bind_group",
+            &pipelines.common_bind_group_layout,
+            &BindGroupEntries::sequential((
+                &pipelines.point_clamp_sampler,
+                &pipelines.linear_clamp_sampler,
+                view_uniforms.clone(),
+            )),
+        );
+
+        let downsample_depth_bind_group = ssgi_resources.depth_pyramid.create_bind_group(
+            &render_device,
+            "ssgi_downsample_depth_bind_group",
+            downsample_depth_pipelines.bind_group_layout(),
+            depth_view,
+            downsample_depth_pipelines.sampler(),
+        );
+
+        let ssgi_bind_group = render_device.create_bind_group(
+            "ssgi_ssgi_bind_group",
+            &pipelines.ssgi_bind_group_layout,
+            &BindGroupEntries::sequential((
+                &ssgi_resources.depth_pyramid.all_mips,
+                depth_view,
+                normal_view,
+                &ssgi_resources.previous_color_texture.default_view,
+                motion_vectors_view,
+                &ssgi_resources.noisy_texture.default_view,
+                ssgi_uniforms.clone(),
+                globals_uniforms.clone(),
+            )),
+        );
+
+        let spatial_denoise_bind_group = render_device.create_bind_group(
+            "ssgi_spatial_denoise_bind_group",
+            &pipelines.spatial_denoise_bind_group_layout,
+            &BindGroupEntries::sequential((
+                &ssgi_resources.noisy_texture.default_view,
+                depth_view,
+                normal_view,
+                &ssgi_resources.denoised_texture.default_view,
+            )),
+        );
+
+        let temporal_denoise_bind_group = render_device.create_bind_group(
+            "ssgi_temporal_denoise_bind_group",
+            &pipelines.temporal_denoise_bind_group_layout,
+            &BindGroupEntries::sequential((
+                &ssgi_resources.denoised_texture.default_view,
+                &ssgi_resources.previous_history().default_view,
+                motion_vectors_view,
+                &ssgi_resources
+                    .screen_space_global_illumination_texture()
+                    .default_view,
+                ssgi_uniforms.clone(),
+            )),
+        );
+
+        commands.entity(entity).insert(SsgiBindGroups {
+            common_bind_group,
+            downsample_depth_bind_group,
+            ssgi_bind_group,
+            spatial_denoise_bind_group,
+            temporal_denoise_bind_group,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        extract_ssgi_settings, remove_ssgi_resources, ScreenSpaceGlobalIllumination,
+        ScreenSpaceGlobalIlluminationUniform, SsgiHistory,
+    };
+    use bevy_core_pipeline::prelude::Camera3d;
+    use bevy_ecs::{system::RunSystemOnce, world::World};
+    use bevy_math::UVec2;
+    use bevy_render::{camera::Camera, sync_world::RenderEntity, view::Msaa, MainWorld};
+
+    #[test]
+    fn uniform_follows_settings() {
+        let settings = ScreenSpaceGlobalIllumination {
+            samples: 0,
+            max_steps: 32,
+            max_distance: 5.0,
+            thickness: 0.25,
+            intensity: 2.0,
+            temporal_blend: 1.0,
+        };
+
+        let uniform = ScreenSpaceGlobalIlluminationUniform::new(&settings, 7, true);
+        assert_eq!(uniform.max_distance, 5.0);
+        assert_eq!(uniform.thickness, 0.25);
+        assert_eq!(uniform.intensity, 2.0);
+        assert_eq!(uniform.max_steps, 32);
+        assert_eq!(uniform.depth_pyramid_mip_count, 7);
+        // At least one ray is traced, and some of each frame is always kept.
+        assert_eq!(uniform.samples, 1);
+        assert_eq!(uniform.temporal_blend, 0.99);
+
+        // Without history there's nothing to blend with.
+        let uniform = ScreenSpaceGlobalIlluminationUniform::new(&settings, 7, false);
+        assert_eq!(uniform.temporal_blend, 0.0);
+    }
+
+    #[test]
+    fn history_alternates_and_resets_on_resize() {
+        let size = UVec2::new(1280, 720);
+        let mut history = SsgiHistory { size, frame: 1 };
+        assert!(history.is_valid(size));
+        assert_eq!(history.current(), 1);
+
+        history.advance(size);
+        assert_eq!(history.current(), 0);
+        history.advance(size);
+        assert_eq!(history.current(), 1);
+
+        assert!(!history.is_valid(UVec2::new(640, 360)));
+        history.advance(UVec2::new(640, 360));
+        assert!(history.is_valid(UVec2::new(640, 360)));
+
+        history.frame = u32::MAX;
+        history.advance(size);
+        assert_eq!(history.frame, 0);
+    }
+
+    #[test]
+    fn history_is_removed_with_settings() {
+        let mut world = World::new();
+        let size = UVec2::new(64, 64);
+        let enabled = world
+            .spawn((
+                ScreenSpaceGlobalIllumination::default(),
+                SsgiHistory { size, frame: 3 },
+            ))
+            .id();
+        let disabled = world.spawn(SsgiHistory { size, frame: 3 }).id();
+
+        world.run_system_once(remove_ssgi_resources).unwrap();
+        assert!(world.get::<SsgiHistory>(enabled).is_some());
+        assert!(world.get::<SsgiHistory>(disabled).is_none());
+    }
+
+    #[test]
+    fn extract_active_cameras_without_msaa() {
+        let mut render_world = World::new();
+        let mut render_entity = || render_world.spawn_empty().id();
+        let (active, inactive, multisampled) = (render_entity(), render_entity(), render_entity());
+        for entity in [inactive, multisampled] {
+            render_world
+                .entity_mut(entity)
+                .insert(ScreenSpaceGlobalIllumination::default());
+        }
+
+        let mut main_world = MainWorld::default();
+        let settings = ScreenSpaceGlobalIllumination {
+            intensity: 3.0,
+            ..Default::default()
+        };
+        for (render_entity, is_active, msaa) in [
+            (multisampled, true, Msaa::Sample4),
+            (active, true, Msaa::Off),
+            (inactive, false, Msaa::Off),
+        ] {
+            main_world.spawn((
+                Camera3d::default(),
+                Camera {
+                    is_active,
+                    ..Default::default()
+                },
+                settings.clone(),
+                msaa,
+                RenderEntity::from(render_entity),
+            ));
+        }
+        render_world.insert_resource(main_world);
+
+        render_world.run_system_once(extract_ssgi_settings).unwrap();
+        assert_eq!(
+            render_world.get::<ScreenSpaceGlobalIllumination>(active),
+            Some(&settings)
+        );
+        assert!(render_world
+            .get::<ScreenSpaceGlobalIllumination>(inactive)
+            .is_none());
+        assert!(render_world
+            .get::<ScreenSpaceGlobalIllumination>(multisampled)
+            .is_none());
+    }
+}
@@ -0,0 +1,63 @@
+// 5x5 joint bilateral filter, which blurs the noisy global illumination while
+// keeping it from bleeding across depth and normal discontinuities.
+
+#import bevy_render::view::View
+#import bevy_pbr::ssgi_utils::{depth_ndc_to_view_z, load_normal_view_space}
+
+@group(0) @binding(0) var global_illumination_noisy: texture_2d<f32>;
+@group(0) @binding(1) var depth: texture_depth_2d;
+@group(0) @binding(2) var normals: texture_2d<f32>;
+@group(0) @binding(3) var global_illumination: texture_storage_2d<rgba16float, write>;
+@group(1) @binding(0) var point_clamp_sampler: sampler;
+@group(1) @binding(1) var linear_clamp_sampler: sampler;
+@group(1) @binding(2) var<uniform> view: View;
+
+const RADIUS: i32 = 2;
+// Relative difference in view space depth at which a neighbor's weight falls
+// to 1/e.
+const DEPTH_SIGMA: f32 = 0.02;
+const NORMAL_POWER: f32 = 16.0;
+const SPATIAL_SIGMA: f32 = 1.5;
+
+@compute
+@workgroup_size(8, 8, 1)
+fn spatial_denoise(@builtin(global_invocation_id) global_id: vec3<u32>) {
+    let size = vec2<i32>(textureDimensions(global_illumination));
+    let pixel = vec2<i32>(global_id.xy);
+    if any(pixel >= size) {
+        return;
+    }
+
+    let center_depth = textureLoad(depth, pixel, 0);
+    if center_depth == 0.0 {
+        textureStore(global_illumination, pixel, vec4(0.0));
+        return;
+    }
+    let center_z = depth_ndc_to_view_z(center_depth, view.view_from_clip);
+    let center_normal = load_normal_view_space(normals, pixel, view.view_from_world);
+
+    var sum = vec3(0.0);
+    var weight_sum = 0.0;
+    for (var y = -RADIUS; y <= RADIUS; y += 1) {
+        for (var x = -RADIUS; x <= RADIUS; x += 1) {
+            let sample_pixel = clamp(pixel + vec2(x, y), vec2(0), size - 1);
+            let sample_depth = textureLoad(depth, sample_pixel, 0);
+            if sample_depth == 0.0 {
+                continue;
+            }
+
+            let sample_z = depth_ndc_to_view_z(sample_depth, view.view_from_clip);
+            let sample_normal = load_normal_view_space(normals, sample_pixel, view.view_from_world);
+
+            let depth_weight = exp(-abs(sample_z - center_z) / (DEPTH_SIGMA * abs(center_z) + 1e-4));
+            let normal_weight = pow(saturate(dot(sample_normal, center_normal)), NORMAL_POWER);
+            let spatial_weight = exp(-f32(x * x + y * y) / (2.0 * SPATIAL_SIGMA * SPATIAL_SIGMA));
+            let weight = depth_weight * normal_weight * spatial_weight;
+
+            sum += textureLoad(global_illumination_noisy, sample_pixel, 0).rgb * weight;
+            weight_sum += weight;
+        }
+    }
+
+    textureStore(global_illumination, pixel, vec4(sum / max(weight_sum, 1e-4), 1.0));
+}
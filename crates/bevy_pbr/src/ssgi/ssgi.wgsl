@@ -0,0 +1,230 @@
+// Screen space global illumination (SSGI)
+//
+// Each pixel traces cosine-distributed rays through a hierarchical Z-buffer
+// that holds the nearest depth beneath each texel, and gathers the previous
+// frame's lit color wherever a ray hits a surface on screen.
+//
+// The hierarchical trace is based on "Hi-Z Screen-Space Cone-Traced
+// Reflections" by Yasin Uludag, in GPU Pro 5.
+
+#import bevy_render::{
+    view::View,
+    globals::Globals,
+    maths::PI_2,
+}
+#import bevy_pbr::ssgi_utils::{
+    ScreenSpaceGlobalIllumination,
+    uv_to_ndc,
+    ndc_to_uv,
+    position_ndc_to_view,
+    depth_ndc_to_view_z,
+    load_normal_view_space,
+}
+
+@group(0) @binding(0) var depth_pyramid: texture_2d<f32>;
+@group(0) @binding(1) var depth: texture_depth_2d;
+@group(0) @binding(2) var normals: texture_2d<f32>;
+@group(0) @binding(3) var previous_color: texture_2d<f32>;
+@group(0) @binding(4) var motion_vectors: texture_2d<f32>;
+@group(0) @binding(5) var global_illumination: texture_storage_2d<rgba16float, write>;
+@group(0) @binding(6) var<uniform> settings: ScreenSpaceGlobalIllumination;
+@group(0) @binding(7) var<uniform> globals: Globals;
+@group(1) @binding(0) var point_clamp_sampler: sampler;
+@group(1) @binding(1) var linear_clamp_sampler: sampler;
+@group(1) @binding(2) var<uniform> view: View;
+
+// Rays are kept this far in front of the camera, in clip space W, so that
+// they never wrap around behind it when projected.
+const MIN_CLIP_W: f32 = 1e-3;
+
+// How far past a cell boundary, in units of the finest pyramid texel, the ray
+// is advanced so that it lands in the next cell.
+const CELL_CROSSING_EPSILON: f32 = 0.01;
+
+// The fraction of the screen, at each edge, over which hits fade out.
+const SCREEN_EDGE_FADE: f32 = 0.05;
+
+// PCG3D, from "Hash Functions for GPU Rendering" (Jarzynski and Olano)
+fn pcg3d(seed: vec3<u32>) -> vec3<u32> {
+    var v = seed * 1664525u + 1013904223u;
+    v.x += v.y * v.z;
+    v.y += v.z * v.x;
+    v.z += v.x * v.y;
+    v ^= v >> vec3(16u);
+    v.x += v.y * v.z;
+    v.y += v.z * v.x;
+    v.z += v.x * v.y;
+    return v;
+}
+
+// Returns three uniformly distributed random numbers in [0, 1), different for
+// each pixel, sample and frame.
+fn sample_noise(pixel: vec2<i32>, sample_index: u32) -> vec3<f32> {
+    let seed = vec3(vec2<u32>(pixel), globals.frame_count * settings.samples + sample_index);
+    return vec3<f32>(pcg3d(seed) >> vec3(8u)) / 16777216.0;
+}
+
+// Adding a uniformly distributed unit vector to the normal and renormalizing
+// gives a cosine-weighted direction in the hemisphere around the normal.
+fn cosine_sample_hemisphere(normal: vec3<f32>, noise: vec2<f32>) -> vec3<f32> {
+    let phi = PI_2 * noise.x;
+    let z = noise.y * 2.0 - 1.0;
+    let r = sqrt(max(1.0 - z * z, 0.0));
+    let direction = normal + vec3(r * cos(phi), r * sin(phi), z);
+    if dot(direction, direction) < 1e-6 {
+        return normal;
+    }
+    return normalize(direction);
+}
+
+// Returns true if a point on the ray, in UV and NDC depth, lies behind the
+// full resolution depth buffer by no more than the thickness.
+fn is_hit(position: vec3<f32>) -> bool {
+    let pixel = vec2<i32>(position.xy * vec2<f32>(textureDimensions(global_illumination)));
+    let scene_depth = textureLoad(depth, pixel, 0);
+    if scene_depth == 0.0 {
+        return false;
+    }
+    let distance_behind = depth_ndc_to_view_z(scene_depth, view.view_from_clip) -
+        depth_ndc_to_view_z(position.z, view.view_from_clip);
+    return distance_behind >= 0.0 && distance_behind <= settings.thickness;
+}
+
+// Marches a ray, given in view space, through the depth pyramid. Returns the
+// UV of the first hit in xy and 1.0 in z, or zero in z if nothing was hit.
+fn trace_ray(origin: vec3<f32>, direction: vec3<f32>, jitter: f32) -> vec3<f32> {
+    // Project the ray into screen space, as UV and NDC depth, both of which
+    // vary linearly along it.
+    var ray_length = settings.max_distance;
+    let start_clip = view.clip_from_view * vec4(origin, 1.0);
+    var end_clip = view.clip_from_view * vec4(origin + direction * ray_length, 1.0);
+    if end_clip.w < MIN_CLIP_W {
+        ray_length *= (start_clip.w - MIN_CLIP_W) / (start_clip.w - end_clip.w);
+        end_clip = view.clip_from_view * vec4(origin + direction * ray_length, 1.0);
+    }
+    let start = vec3(ndc_to_uv(start_clip.xy / start_clip.w), start_clip.z / start_clip.w);
+    let end = vec3(ndc_to_uv(end_clip.xy / end_clip.w), end_clip.z / end_clip.w);
+    let delta = end - start;
+    let inv_delta = 1.0 / select(delta, vec3(1e-10), abs(delta) < vec3(1e-10));
+
+    // Clip the ray to the screen and the depth range.
+    let t_exit = select(-start, vec3(1.0) - start, delta > vec3(0.0)) * inv_delta;
+    let t_max = min(1.0, min(t_exit.x, min(t_exit.y, t_exit.z)));
+
+    // Start a little over one texel away from the origin so that the ray
+    // doesn't immediately hit the surface it was cast from.
+    let delta_texels = delta.xy * vec2<f32>(textureDimensions(depth_pyramid, 0));
+    let t_per_texel = 1.0 / max(max(abs(delta_texels.x), abs(delta_texels.y)), 1e-5);
+    var t = t_per_texel * (1.0 + jitter);
+
+    let max_level = i32(settings.depth_pyramid_mip_count) - 1;
+    var level = 0;
+
+    for (var step_index = 0u; step_index < settings.max_steps; step_index += 1u) {
+        if t >= t_max {
+            break;
+        }
+
+        let position = start + delta * t;
+        let level_size = vec2<f32>(textureDimensions(depth_pyramid, level));
+        let cell = floor(position.xy * level_size);
+        let nearest_depth = textureLoad(depth_pyramid, vec2<i32>(cell), level).r;
+
+        let cell_boundary = (cell + select(vec2(0.0), vec2(1.0), delta.xy > vec2(0.0))) / level_size;
+        let t_cell_xy = (cell_boundary - start.xy) * inv_delta.xy;
+        let t_cell = min(t_cell_xy.x, t_cell_xy.y) + CELL_CROSSING_EPSILON * t_per_texel;
+
+        if position.z > nearest_depth {
+            // The ray is in front of everything in this cell. If it leaves the
+            // cell before reaching the nearest depth, skip the cell and take
+            // bigger steps; otherwise move to that depth and look closer.
+            var t_plane = t_cell;
+            if delta.z < 0.0 {
+                t_plane = t + (nearest_depth - position.z) * inv_delta.z;
+            }
+            if t_plane >= t_cell {
+                t = t_cell;
+                level = min(level + 1, max_level);
+            } else if level > 0 {
+                t = t_plane;
+                level -= 1;
+            } else if is_hit(start + delta * t_plane) {
+                return vec3(start.xy + delta.xy * t_plane, 1.0);
+            } else {
+                t = t_cell;
+            }
+        } else if level > 0 {
+            level -= 1;
+        } else if is_hit(position) {
+            return vec3(position.xy, 1.0);
+        } else {
+            // The ray passed behind a surface thinner than the thickness, or
+            // in front of the full resolution depth, so carry on past it.
+            t = t_cell;
+        }
+    }
+
+    return vec3(0.0);
+}
+
+// Returns the light that the previous frame shaded at a hit, reprojected with
+// the hit's motion vector.
+fn sample_hit_radiance(hit_uv: vec2<f32>, direction: vec3<f32>) -> vec3<f32> {
+    let hit_pixel = vec2<i32>(hit_uv * vec2<f32>(textureDimensions(global_illumination)));
+
+    // Back faces aren't visible on screen, so the color there belongs to some
+    // other surface.
+    let hit_normal = load_normal_view_space(normals, hit_pixel, view.view_from_world);
+    if dot(hit_normal, direction) > 0.0 {
+        return vec3(0.0);
+    }
+
+    let previous_uv = hit_uv - textureLoad(motion_vectors, hit_pixel, 0).xy;
+    if any(previous_uv < vec2(0.0)) || any(previous_uv > vec2(1.0)) {
+        return vec3(0.0);
+    }
+
+    let edge_distance = min(min(previous_uv.x, previous_uv.y), min(1.0 - previous_uv.x, 1.0 - previous_uv.y));
+    let fade = saturate(edge_distance / SCREEN_EDGE_FADE);
+
+    // The previous frame's color is pre-exposed.
+    let color = textureSampleLevel(previous_color, linear_clamp_sampler, previous_uv, 0.0).rgb;
+    return color * fade / view.exposure;
+}
+
+@compute
+@workgroup_size(8, 8, 1)
+fn ssgi(@builtin(global_invocation_id) global_id: vec3<u32>) {
+    let size = textureDimensions(global_illumination);
+    if any(global_id.xy >= size) {
+        return;
+    }
+    let pixel = vec2<i32>(global_id.xy);
+
+    let ndc_depth = textureLoad(depth, pixel, 0);
+    if ndc_depth == 0.0 {
+        textureStore(global_illumination, pixel, vec4(0.0));
+        return;
+    }
+
+    let uv = (vec2<f32>(pixel) + 0.5) / vec2<f32>(size);
+    let position = position_ndc_to_view(vec3(uv_to_ndc(uv), ndc_depth), view.view_from_clip);
+    let normal = load_normal_view_space(normals, pixel, view.view_from_world);
+
+    // Push the origin off the surface a little, further away from the camera
+    // where depth precision is lower.
+    let origin = position + normal * max(abs(position.z) * 0.002, 0.005);
+
+    var radiance = vec3(0.0);
+    for (var sample_index = 0u; sample_index < settings.samples; sample_index += 1u) {
+        let noise = sample_noise(pixel, sample_index);
+        let direction = cosine_sample_hemisphere(normal, noise.xy);
+        let hit = trace_ray(origin, direction, noise.z);
+        if hit.z > 0.0 {
+            radiance += sample_hit_radiance(hit.xy, direction);
+        }
+    }
+    radiance *= settings.intensity / f32(max(settings.samples, 1u));
+
+    textureStore(global_illumination, pixel, vec4(radiance, 1.0));
+}
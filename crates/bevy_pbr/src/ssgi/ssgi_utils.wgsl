@@ -0,0 +1,42 @@
+#define_import_path bevy_pbr::ssgi_utils
+
+struct ScreenSpaceGlobalIllumination {
+    max_distance: f32,
+    thickness: f32,
+    intensity: f32,
+    // Zero if there's no history to blend with
+    temporal_blend: f32,
+    samples: u32,
+    max_steps: u32,
+    depth_pyramid_mip_count: u32,
+}
+
+fn uv_to_ndc(uv: vec2<f32>) -> vec2<f32> {
+    return uv * vec2(2.0, -2.0) + vec2(-1.0, 1.0);
+}
+
+fn ndc_to_uv(ndc: vec2<f32>) -> vec2<f32> {
+    return ndc * vec2(0.5, -0.5) + vec2(0.5);
+}
+
+fn position_ndc_to_view(ndc: vec3<f32>, view_from_clip: mat4x4<f32>) -> vec3<f32> {
+    let view_position = view_from_clip * vec4(ndc, 1.0);
+    return view_position.xyz / view_position.w;
+}
+
+// The view space depth doesn't depend on the screen position for either
+// perspective or orthographic projections, so only the NDC depth is needed.
+fn depth_ndc_to_view_z(ndc_depth: f32, view_from_clip: mat4x4<f32>) -> f32 {
+    let view_position = view_from_clip * vec4(0.0, 0.0, ndc_depth, 1.0);
+    return view_position.z / view_position.w;
+}
+
+fn load_normal_view_space(normals: texture_2d<f32>, pixel: vec2<i32>, view_from_world: mat4x4<f32>) -> vec3<f32> {
+    let world_normal = textureLoad(normals, pixel, 0).xyz * 2.0 - 1.0;
+    let view_from_world_3x3 = mat3x3<f32>(
+        view_from_world[0].xyz,
+        view_from_world[1].xyz,
+        view_from_world[2].xyz,
+    );
+    return normalize(view_from_world_3x3 * world_normal);
+}
@@ -0,0 +1,48 @@
+// Accumulates the spatially denoised global illumination over time. The
+// history is reprojected with motion vectors and clamped to the neighborhood
+// of the current frame's result to reject stale lighting after disocclusion.
+
+#import bevy_render::view::View
+#import bevy_pbr::ssgi_utils::ScreenSpaceGlobalIllumination
+
+@group(0) @binding(0) var global_illumination_denoised: texture_2d<f32>;
+@group(0) @binding(1) var history: texture_2d<f32>;
+@group(0) @binding(2) var motion_vectors: texture_2d<f32>;
+@group(0) @binding(3) var global_illumination: texture_storage_2d<rgba16float, write>;
+@group(0) @binding(4) var<uniform> settings: ScreenSpaceGlobalIllumination;
+@group(1) @binding(0) var point_clamp_sampler: sampler;
+@group(1) @binding(1) var linear_clamp_sampler: sampler;
+@group(1) @binding(2) var<uniform> view: View;
+
+@compute
+@workgroup_size(8, 8, 1)
+fn temporal_denoise(@builtin(global_invocation_id) global_id: vec3<u32>) {
+    let size = vec2<i32>(textureDimensions(global_illumination));
+    let pixel = vec2<i32>(global_id.xy);
+    if any(pixel >= size) {
+        return;
+    }
+
+    let current = textureLoad(global_illumination_denoised, pixel, 0).rgb;
+    var result = current;
+
+    let uv = (vec2<f32>(pixel) + 0.5) / vec2<f32>(size);
+    let history_uv = uv - textureLoad(motion_vectors, pixel, 0).xy;
+    if settings.temporal_blend > 0.0 && all(history_uv >= vec2(0.0)) && all(history_uv <= vec2(1.0)) {
+        var neighborhood_min = current;
+        var neighborhood_max = current;
+        for (var y = -1; y <= 1; y += 1) {
+            for (var x = -1; x <= 1; x += 1) {
+                let sample_pixel = clamp(pixel + vec2(x, y), vec2(0), size - 1);
+                let neighbor = textureLoad(global_illumination_denoised, sample_pixel, 0).rgb;
+                neighborhood_min = min(neighborhood_min, neighbor);
+                neighborhood_max = max(neighborhood_max, neighbor);
+            }
+        }
+
+        let history_color = textureSampleLevel(history, linear_clamp_sampler, history_uv, 0.0).rgb;
+        result = mix(current, clamp(history_color, neighborhood_min, neighborhood_max), settings.temporal_blend);
+    }
+
+    textureStore(global_illumination, pixel, vec4(result, 1.0));
+}
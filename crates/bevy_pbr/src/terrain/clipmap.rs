@@ -0,0 +1,242 @@
+//! Grid meshes and camera-relative placement of the terrain clipmap.
+//!
+//! Level 0 is a full square grid of `resolution` × `resolution` cells. Every
+//! level above it is a ring of the same outer size, with cells twice as large
+//! as the level inside it, and a hole that the inner level fits in.
+//!
+//! Each level snaps its center to a grid twice as coarse as its own cells, so
+//! the inner level is either centered in the hole or offset by one cell along
+//! each axis. The hole is one cell wider than the inner level, and two trim
+//! strips, one along each axis, fill the side of the hole that the inner level
+//! leaves uncovered.
+
+use core::ops::Range;
+
+use bevy_asset::RenderAssetUsages;
+use bevy_math::{Vec2, Vec3};
+use bevy_render::{
+    mesh::{Indices, Mesh, PrimitiveTopology},
+    primitives::Aabb,
+};
+
+/// The smallest supported clipmap resolution.
+const MIN_RESOLUTION: u32 = 8;
+
+/// One mesh of the clipmap.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum ClipmapPiece {
+    /// The full grid of level 0, or the ring of any other level.
+    Level(u32),
+    /// The strip, one cell wide along X, that fills the hole of a ring on the
+    /// side that the inner level doesn't cover.
+    TrimX(u32),
+    /// The strip, one cell wide along Z, that fills the rest of the hole.
+    TrimZ(u32),
+}
+
+impl ClipmapPiece {
+    /// Returns all the pieces of a clipmap with the given number of levels.
+    pub(super) fn all(levels: u32) -> impl Iterator<Item = ClipmapPiece> {
+        (0..levels.max(1)).flat_map(|level| {
+            let trims = (level > 0)
+                .then_some([ClipmapPiece::TrimX(level), ClipmapPiece::TrimZ(level)])
+                .into_iter()
+                .flatten();
+            core::iter::once(ClipmapPiece::Level(level)).chain(trims)
+        })
+    }
+
+    /// Returns the level that this piece belongs to.
+    pub(super) fn level(self) -> u32 {
+        match self {
+            ClipmapPiece::Level(level)
+            | ClipmapPiece::TrimX(level)
+            | ClipmapPiece::TrimZ(level) => level,
+        }
+    }
+
+    /// Returns the rectangles of cells, in units of the piece's cell size and
+    /// relative to its translation, that make up this piece.
+    pub(super) fn patches(self, resolution: u32) -> Vec<(Range<i32>, Range<i32>)> {
+        let half = resolution as i32 / 2;
+        let quarter = resolution as i32 / 4;
+        match self {
+            ClipmapPiece::Level(0) => vec![(-half..half, -half..half)],
+            ClipmapPiece::Level(_) => vec![
+                (-half..half, -half..-quarter),
+                (-half..half, quarter + 1..half),
+                (-half..-quarter, -quarter..quarter + 1),
+                (quarter + 1..half, -quarter..quarter + 1),
+            ],
+            ClipmapPiece::TrimX(_) => vec![(0..1, 0..half + 1)],
+            ClipmapPiece::TrimZ(_) => vec![(0..half, 0..1)],
+        }
+    }
+
+    /// Returns the translation of this piece, in terrain space, for a camera
+    /// at the given terrain space position.
+    pub(super) fn translation(self, camera: Vec2, resolution: u32, spacing: f32) -> Vec2 {
+        let level = self.level();
+        let cell_size = level_cell_size(level, spacing);
+        let center = level_center(camera, cell_size);
+        if level == 0 {
+            return center;
+        }
+
+        // The offset of the inner level from this one, in cells, is either 0
+        // or 1 along each axis.
+        let inner_center = level_center(camera, cell_size * 0.5);
+        let offset = ((inner_center - center) / cell_size).round();
+        let quarter = (resolution / 4) as f32;
+        let uncovered_side = |offset: f32| if offset == 0.0 { quarter } else { -quarter };
+
+        let cells = match self {
+            ClipmapPiece::Level(_) => Vec2::ZERO,
+            ClipmapPiece::TrimX(_) => Vec2::new(uncovered_side(offset.x), -quarter),
+            ClipmapPiece::TrimZ(_) => Vec2::new(offset.x - quarter, uncovered_side(offset.y)),
+        };
+        center + cells * cell_size
+    }
+
+    /// Builds the mesh for this piece.
+    ///
+    /// Alongside positions and upward normals, the mesh stores the morph
+    /// offset of each vertex in `UV_0` and the cell size in `UV_1`, which the
+    /// terrain shader uses to stitch levels together and filter normals.
+    pub(super) fn mesh(self, resolution: u32, spacing: f32) -> Mesh {
+        let cell_size = level_cell_size(self.level(), spacing);
+        // Only the outer edge of a level meets a coarser level.
+        let outer_edge = matches!(self, ClipmapPiece::Level(_)).then_some(resolution as i32 / 2);
+
+        let mut positions = vec![];
+        let mut morph_offsets = vec![];
+        let mut indices = vec![];
+        for (x, z) in self.patches(resolution) {
+            let first_vertex = positions.len() as u32;
+            let columns = x.len() as u32 + 1;
+
+            for j in z.start..=z.end {
+                for i in x.start..=x.end {
+                    positions.push([i as f32 * cell_size, 0.0, j as f32 * cell_size]);
+                    morph_offsets.push(match outer_edge {
+                        Some(edge) if i.abs() == edge && j.rem_euclid(2) == 1 => [0.0, cell_size],
+                        Some(edge) if j.abs() == edge && i.rem_euclid(2) == 1 => [cell_size, 0.0],
+                        _ => [0.0, 0.0],
+                    });
+                }
+            }
+
+            for row in 0..z.len() as u32 {
+                for column in 0..x.len() as u32 {
+                    let a = first_vertex + row * columns + column;
+                    let b = a + 1;
+                    let c = a + columns;
+                    let d = c + 1;
+                    indices.extend([a, c, b, b, c, d]);
+                }
+            }
+        }
+
+        let vertex_count = positions.len();
+        Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::RENDER_WORLD,
+        )
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, vec![[0.0, 1.0, 0.0]; vertex_count])
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, morph_offsets)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_1, vec![[cell_size, 0.0]; vertex_count])
+        .with_inserted_indices(Indices::U32(indices))
+    }
+
+    /// Returns the bounding box of this piece once displaced by heights in
+    /// the given range.
+    pub(super) fn aabb(self, resolution: u32, spacing: f32, heights: Range<f32>) -> Aabb {
+        let cell_size = level_cell_size(self.level(), spacing);
+        let (min, max) = self.patches(resolution).into_iter().fold(
+            (Vec2::MAX, Vec2::MIN),
+            |(min, max), (x, z)| {
+                (
+                    min.min(Vec2::new(x.start as f32, z.start as f32)),
+                    max.max(Vec2::new(x.end as f32, z.end as f32)),
+                )
+            },
+        );
+        Aabb::from_min_max(
+            Vec3::new(min.x * cell_size, heights.start, min.y * cell_size),
+            Vec3::new(max.x * cell_size, heights.end, max.y * cell_size),
+        )
+    }
+}
+
+/// Rounds a requested resolution to one that the clipmap supports: a multiple
+/// of 4, so that every level lines up with the cells of the next, and no
+/// smaller than 8.
+pub(super) fn clipmap_resolution(requested: u32) -> u32 {
+    requested.max(MIN_RESOLUTION).div_ceil(4) * 4
+}
+
+fn level_cell_size(level: u32, spacing: f32) -> f32 {
+    spacing * (1u32 << level.min(31)) as f32
+}
+
+/// Snaps a level to the grid twice as coarse as its cells.
+fn level_center(camera: Vec2, cell_size: f32) -> Vec2 {
+    (camera / (2.0 * cell_size)).floor() * (2.0 * cell_size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{clipmap_resolution, ClipmapPiece};
+    use bevy_math::{IVec2, Vec2};
+
+    #[test]
+    fn resolution_is_rounded_to_multiple_of_four() {
+        assert_eq!(clipmap_resolution(0), 8);
+        assert_eq!(clipmap_resolution(8), 8);
+        assert_eq!(clipmap_resolution(13), 16);
+        assert_eq!(clipmap_resolution(64), 64);
+    }
+
+    #[test]
+    fn pieces_tile_without_gaps_or_overlaps() {
+        const RESOLUTION: u32 = 8;
+        const LEVELS: u32 = 4;
+
+        for camera in [
+            Vec2::ZERO,
+            Vec2::new(0.5, 0.5),
+            Vec2::new(1.5, -2.5),
+            Vec2::new(-7.25, 13.75),
+            Vec2::new(31.0, -17.0),
+        ] {
+            // Count how many times each cell of level 0 is covered.
+            let outermost = ClipmapPiece::Level(LEVELS - 1).translation(camera, RESOLUTION, 1.0);
+            let extent = (RESOLUTION << (LEVELS - 1)) as i32;
+            let origin = outermost.as_ivec2() - IVec2::splat(extent / 2);
+            let mut coverage = vec![0u32; (extent * extent) as usize];
+
+            for piece in ClipmapPiece::all(LEVELS) {
+                let scale = 1 << piece.level();
+                let translation = piece.translation(camera, RESOLUTION, 1.0).as_ivec2();
+                for (x, z) in piece.patches(RESOLUTION) {
+                    for cell_z in z.start * scale..z.end * scale {
+                        for cell_x in x.start * scale..x.end * scale {
+                            let cell = translation + IVec2::new(cell_x, cell_z) - origin;
+                            assert!(
+                                cell.cmpge(IVec2::ZERO).all()
+                                    && cell.cmplt(IVec2::splat(extent)).all()
+                            );
+                            coverage[(cell.y * extent + cell.x) as usize] += 1;
+                        }
+                    }
+                }
+            }
+
+            assert!(
+                coverage.iter().all(|&count| count == 1),
+                "clipmap doesn't tile for camera at {camera}"
+            );
+        }
+    }
+}
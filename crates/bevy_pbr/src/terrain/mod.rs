@@ -0,0 +1,773 @@
+//! Heightmap terrain, rendered with geometry clipmaps.
+//!
+//! A [`Terrain`] is drawn as a set of flat grid meshes, one per level of
+//! detail, that follow the camera. The terrain shader displaces the grids by
+//! the heightmap and blends up to [`MAX_TERRAIN_LAYERS`] splat layers, so the
+//! cost of a terrain doesn't depend on the size of its heightmap.
+
+mod clipmap;
+
+use bevy_app::{App, Plugin, PostUpdate};
+use bevy_asset::{load_internal_asset, weak_handle, Asset, AssetEvent, Assets, Handle};
+use bevy_color::{Color, ColorToComponents};
+use bevy_core_pipeline::{core_3d::Camera3d, outline::Outline};
+use bevy_ecs::{
+    change_detection::{DetectChanges, DetectChangesMut},
+    component::{require, Component},
+    entity::Entity,
+    event::EventReader,
+    hierarchy::ChildOf,
+    observer::Trigger,
+    query::With,
+    reflect::ReflectComponent,
+    removal_detection::RemovedComponents,
+    schedule::IntoSystemConfigs,
+    system::{Commands, Query, Res, ResMut},
+    world::{OnRemove, Ref},
+};
+use bevy_image::Image;
+use bevy_math::{IVec2, Mat4, Vec2, Vec3Swizzles, Vec4};
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
+use bevy_render::{
+    camera::Camera,
+    mesh::{Mesh, Mesh3d},
+    render_asset::RenderAssets,
+    render_resource::{AsBindGroup, AsBindGroupShaderType, Shader, ShaderRef, ShaderType},
+    texture::GpuImage,
+    view::{NoFrustumCulling, Visibility},
+};
+use bevy_transform::{
+    components::{GlobalTransform, Transform},
+    TransformSystem,
+};
+
+use crate::{Material, MaterialPlugin, MeshMaterial3d, OpaqueRendererMethod};
+
+use self::clipmap::{clipmap_resolution, ClipmapPiece};
+
+const TERRAIN_SHADER_HANDLE: Handle<Shader> = weak_handle!("89cfa224-d65b-47dd-80c7-6794e98e84e5");
+
+/// The maximum number of splat layers that a [`Terrain`] can blend.
+///
+/// The weight of each layer is read from one channel of the splat map.
+pub const MAX_TERRAIN_LAYERS: usize = 4;
+
+/// Adds support for [`Terrain`].
+pub struct TerrainPlugin;
+
+impl Plugin for TerrainPlugin {
+    fn build(&self, app: &mut App) {
+        load_internal_asset!(
+            app,
+            TERRAIN_SHADER_HANDLE,
+            "terrain.wgsl",
+            Shader::from_wgsl
+        );
+
+        app.register_type::<Terrain>()
+            .add_plugins(MaterialPlugin::<TerrainMaterial>::default())
+            .add_systems(
+                PostUpdate,
+                (
+                    (
+                        build_terrain_clipmaps,
+                        update_terrain_outlines,
+                        center_terrain_clipmaps,
+                    )
+                        .chain()
+                        .before(TransformSystem::TransformPropagate),
+                    update_terrain_materials.after(TransformSystem::TransformPropagate),
+                ),
+            )
+            .add_observer(despawn_terrain_clipmap);
+    }
+}
+
+/// A heightmap terrain.
+///
+/// The terrain covers a rectangle of [`Terrain::size`] in the XZ plane of the
+/// entity, centered on its origin, and rises along its Y axis. It's rendered
+/// as a geometry clipmap: nested grid rings, each with cells twice as large
+/// as the ring inside it, centered on the active 3D camera with the lowest
+/// order. Far away parts of the terrain are drawn with fewer vertices, and the
+/// grids stay the same no matter how large the heightmap is.
+///
+/// The terrain casts and receives shadows and writes to the depth, normal
+/// and motion vector prepasses. It's always rendered forward, even with a
+/// deferred prepass.
+///
+/// The clipmap meshes are spawned as children of the terrain entity once the
+/// heightmap is loaded, and despawned when the component is removed. An
+/// [`Outline`] on the terrain entity is copied to its clipmap meshes.
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+#[require(Transform, Visibility)]
+pub struct Terrain {
+    /// The heights of the terrain.
+    ///
+    /// Heights are read from the red channel and scaled by
+    /// [`Terrain::height_scale`]. Since the heightmap is filtered by hand,
+    /// any format with a float sample type works, including `R32Float`. The
+    /// first row of texels is at the terrain's -Z edge.
+    ///
+    /// Keep the image's data in the main world, with
+    /// [`RenderAssetUsages::MAIN_WORLD`](bevy_asset::RenderAssetUsages::MAIN_WORLD),
+    /// for [`Terrain::height_at`] and for the terrain to be frustum culled.
+    pub heightmap: Handle<Image>,
+
+    /// The size of the terrain along its X and Z axes.
+    ///
+    /// Defaults to 256 × 256.
+    pub size: Vec2,
+
+    /// The height of the terrain where the heightmap is 1.0.
+    ///
+    /// Defaults to 32.
+    pub height_scale: f32,
+
+    /// The weights of the [`Terrain::layers`], one per channel, stretched
+    /// over the whole terrain.
+    ///
+    /// Weights are normalized before blending. Without a splat map, only the
+    /// first layer is drawn.
+    pub splat_map: Option<Handle<Image>>,
+
+    /// The surface layers that the splat map blends between.
+    ///
+    /// Layers after the first [`MAX_TERRAIN_LAYERS`] are ignored.
+    pub layers: Vec<TerrainLayer>,
+
+    /// A 2D array texture with the base color of each layer in the layer of
+    /// the same index.
+    ///
+    /// This is multiplied by [`TerrainLayer::base_color`].
+    pub layer_base_color_texture: Option<Handle<Image>>,
+
+    /// A 2D array texture with the tangent space normal map of each layer in
+    /// the layer of the same index.
+    ///
+    /// The normal maps' U axis points along the terrain's X axis.
+    pub layer_normal_map_texture: Option<Handle<Image>>,
+
+    /// The number of levels of detail in the clipmap.
+    ///
+    /// Each level covers twice the distance of the one before it. Defaults
+    /// to 6.
+    pub clipmap_levels: u32,
+
+    /// The number of grid cells along each side of a clipmap level.
+    ///
+    /// This is rounded up to a multiple of 4, and no less than 8. Defaults to
+    /// 64.
+    pub clipmap_resolution: u32,
+
+    /// The size of the grid cells of the finest clipmap level.
+    ///
+    /// For the most detail, match this to the size of a heightmap texel.
+    /// Defaults to 1.
+    pub clipmap_spacing: f32,
+}
+
+impl Default for Terrain {
+    fn default() -> Self {
+        Self {
+            heightmap: Handle::default(),
+            size: Vec2::splat(256.0),
+            height_scale: 32.0,
+            splat_map: None,
+            layers: vec![TerrainLayer::default()],
+            layer_base_color_texture: None,
+            layer_normal_map_texture: None,
+            clipmap_levels: 6,
+            clipmap_resolution: 64,
+            clipmap_spacing: 1.0,
+        }
+    }
+}
+
+impl Terrain {
+    /// Returns the height of the terrain at the given position in the XZ
+    /// plane of the terrain entity.
+    ///
+    /// This filters the heightmap in the same way as the terrain shader, so
+    /// the result matches the rendered surface up to the clipmap's level of
+    /// detail. Returns [`None`] if the position is outside of the terrain or
+    /// if the heightmap isn't loaded or has no data in the main world.
+    ///
+    /// To sample the terrain at a world space position, transform it into
+    /// the terrain's space first:
+    ///
+    /// ```
+    /// # use bevy_asset::Assets;
+    /// # use bevy_image::Image;
+    /// # use bevy_math::{Vec3, Vec3Swizzles};
+    /// # use bevy_pbr::Terrain;
+    /// # use bevy_transform::components::GlobalTransform;
+    /// fn ground_height(
+    ///     terrain: &Terrain,
+    ///     transform: &GlobalTransform,
+    ///     images: &Assets<Image>,
+    ///     position: Vec3,
+    /// ) -> Option<Vec3> {
+    ///     let local = transform.affine().inverse().transform_point3(position);
+    ///     let height = terrain.height_at(images, local.xz())?;
+    ///     Some(transform.transform_point(local.with_y(height)))
+    /// }
+    /// ```
+    pub fn height_at(&self, images: &Assets<Image>, position: Vec2) -> Option<f32> {
+        let heightmap = images.get(&self.heightmap)?;
+        let uv = position / self.size + 0.5;
+        if uv.cmplt(Vec2::ZERO).any() || uv.cmpgt(Vec2::ONE).any() {
+            return None;
+        }
+
+        let size = heightmap.size().as_ivec2();
+        let texel = uv * size.as_vec2() - 0.5;
+        let base = texel.floor();
+        let t = texel - base;
+
+        let p0 = base.as_ivec2().clamp(IVec2::ZERO, size - 1);
+        let p1 = (base.as_ivec2() + 1).clamp(IVec2::ZERO, size - 1);
+        let load = |x: i32, y: i32| heightmap_texel(heightmap, x as u32, y as u32);
+        let h00 = load(p0.x, p0.y)?;
+        let h10 = load(p1.x, p0.y)?;
+        let h01 = load(p0.x, p1.y)?;
+        let h11 = load(p1.x, p1.y)?;
+
+        let h0 = h00 + (h10 - h00) * t.x;
+        let h1 = h01 + (h11 - h01) * t.x;
+        Some((h0 + (h1 - h0) * t.y) * self.height_scale)
+    }
+}
+
+/// A surface layer of a [`Terrain`], with a subset of the parameters of
+/// [`StandardMaterial`](crate::StandardMaterial).
+#[derive(Clone, Debug, Reflect)]
+#[reflect(Default, Debug)]
+pub struct TerrainLayer {
+    /// The color of the layer before lighting.
+    ///
+    /// See [`StandardMaterial::base_color`](crate::StandardMaterial::base_color).
+    /// Defaults to [`Color::WHITE`].
+    pub base_color: Color,
+
+    /// The roughness of the layer.
+    ///
+    /// See [`StandardMaterial::perceptual_roughness`](crate::StandardMaterial::perceptual_roughness).
+    /// Defaults to 0.5.
+    pub perceptual_roughness: f32,
+
+    /// How metallic the layer is.
+    ///
+    /// See [`StandardMaterial::metallic`](crate::StandardMaterial::metallic).
+    /// Defaults to 0.0.
+    pub metallic: f32,
+
+    /// The specular intensity of the layer.
+    ///
+    /// See [`StandardMaterial::reflectance`](crate::StandardMaterial::reflectance).
+    /// Defaults to 0.5.
+    pub reflectance: f32,
+
+    /// How many times the layer's textures repeat per unit of distance along
+    /// the terrain.
+    ///
+    /// Defaults to 0.25.
+    pub uv_scale: f32,
+}
+
+impl Default for TerrainLayer {
+    fn default() -> Self {
+        Self {
+            base_color: Color::WHITE,
+            perceptual_roughness: 0.5,
+            metallic: 0.0,
+            reflectance: 0.5,
+            uv_scale: 0.25,
+        }
+    }
+}
+
+/// The material of the clipmap meshes of a [`Terrain`].
+///
+/// [`TerrainPlugin`] creates one of these for each terrain and keeps it up to
+/// date with the [`Terrain`] component and the terrain's transform.
+#[derive(Asset, AsBindGroup, Reflect, Debug, Clone)]
+#[uniform(0, TerrainMaterialUniform)]
+#[reflect(Debug)]
+pub struct TerrainMaterial {
+    #[texture(1, sample_type = "float", filterable = false)]
+    heightmap: Handle<Image>,
+    #[texture(2)]
+    #[sampler(3)]
+    splat_map: Option<Handle<Image>>,
+    #[texture(4, dimension = "2d_array")]
+    #[sampler(5)]
+    base_color_texture: Option<Handle<Image>>,
+    #[texture(6, dimension = "2d_array")]
+    #[sampler(7)]
+    normal_map_texture: Option<Handle<Image>>,
+    layers: Vec<TerrainLayer>,
+    world_from_terrain: Mat4,
+    /// The transform of the terrain during the previous frame, for motion
+    /// vectors.
+    previous_world_from_terrain: Mat4,
+    size: Vec2,
+    height_scale: f32,
+}
+
+impl TerrainMaterial {
+    fn new(terrain: &Terrain, transform: &GlobalTransform) -> Self {
+        let world_from_terrain = transform.compute_matrix();
+        Self {
+            heightmap: terrain.heightmap.clone(),
+            splat_map: terrain.splat_map.clone(),
+            base_color_texture: terrain.layer_base_color_texture.clone(),
+            normal_map_texture: terrain.layer_normal_map_texture.clone(),
+            layers: terrain.layers.clone(),
+            world_from_terrain,
+            previous_world_from_terrain: world_from_terrain,
+            size: terrain.size,
+            height_scale: terrain.height_scale,
+        }
+    }
+}
+
+impl Material for TerrainMaterial {
+    fn vertex_shader() -> ShaderRef {
+        TERRAIN_SHADER_HANDLE.into()
+    }
+
+    fn fragment_shader() -> ShaderRef {
+        TERRAIN_SHADER_HANDLE.into()
+    }
+
+    fn prepass_vertex_shader() -> ShaderRef {
+        TERRAIN_SHADER_HANDLE.into()
+    }
+
+    fn opaque_render_method(&self) -> OpaqueRendererMethod {
+        OpaqueRendererMethod::Forward
+    }
+}
+
+// NOTE: These must match the bit flags in bevy_pbr/src/terrain/terrain.wgsl!
+const TERRAIN_FLAGS_SPLAT_MAP: u32 = 1;
+const TERRAIN_FLAGS_BASE_COLOR_TEXTURE: u32 = 2;
+const TERRAIN_FLAGS_NORMAL_MAP_TEXTURE: u32 = 4;
+
+/// The GPU representation of a [`TerrainLayer`].
+#[derive(Clone, Copy, Default, ShaderType)]
+pub struct TerrainLayerUniform {
+    pub base_color: Vec4,
+    pub perceptual_roughness: f32,
+    pub metallic: f32,
+    pub reflectance: f32,
+    pub uv_scale: f32,
+}
+
+/// The GPU representation of the uniform data of a [`TerrainMaterial`].
+#[derive(Clone, Default, ShaderType)]
+pub struct TerrainMaterialUniform {
+    pub layers: [TerrainLayerUniform; MAX_TERRAIN_LAYERS],
+    pub terrain_from_world: Mat4,
+    pub previous_world_from_terrain: Mat4,
+    pub size: Vec2,
+    pub height_scale: f32,
+    pub layer_count: u32,
+    pub flags: u32,
+}
+
+impl AsBindGroupShaderType<TerrainMaterialUniform> for TerrainMaterial {
+    fn as_bind_group_shader_type(
+        &self,
+        _images: &RenderAssets<GpuImage>,
+    ) -> TerrainMaterialUniform {
+        let mut layers = [TerrainLayerUniform::default(); MAX_TERRAIN_LAYERS];
+        for (uniform, layer) in layers.iter_mut().zip(&self.layers) {
+            *uniform = TerrainLayerUniform {
+                base_color: layer.base_color.to_linear().to_vec4(),
+                perceptual_roughness: layer.perceptual_roughness,
+                metallic: layer.metallic,
+                reflectance: layer.reflectance,
+                uv_scale: layer.uv_scale,
+            };
+        }
+
+        let mut flags = 0;
+        if self.splat_map.is_some() {
+            flags |= TERRAIN_FLAGS_SPLAT_MAP;
+        }
+        if self.base_color_texture.is_some() {
+            flags |= TERRAIN_FLAGS_BASE_COLOR_TEXTURE;
+        }
+        if self.normal_map_texture.is_some() {
+            flags |= TERRAIN_FLAGS_NORMAL_MAP_TEXTURE;
+        }
+
+        TerrainMaterialUniform {
+            layers,
+            terrain_from_world: self.world_from_terrain.inverse(),
+            previous_world_from_terrain: self.previous_world_from_terrain,
+            size: self.size,
+            height_scale: self.height_scale,
+            layer_count: self.layers.len().min(MAX_TERRAIN_LAYERS) as u32,
+            flags,
+        }
+    }
+}
+
+/// The clipmap meshes and material spawned for a [`Terrain`].
+#[derive(Component)]
+struct TerrainClipmap {
+    material: Handle<TerrainMaterial>,
+    pieces: Vec<Entity>,
+}
+
+/// A clipmap mesh of the [`Terrain`] that this entity is a child of.
+#[derive(Component)]
+struct TerrainClipmapPiece(ClipmapPiece);
+
+/// Spawns the clipmap meshes of terrains that were added or changed, or whose
+/// heightmaps were loaded or modified.
+fn build_terrain_clipmaps(
+    mut commands: Commands,
+    mut terrains: Query<(
+        Entity,
+        Ref<Terrain>,
+        &GlobalTransform,
+        Option<&Outline>,
+        Option<&mut TerrainClipmap>,
+    )>,
+    mut image_events: EventReader<AssetEvent<Image>>,
+    images: Res<Assets<Image>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<TerrainMaterial>>,
+) {
+    let changed_images: Vec<_> = image_events
+        .read()
+        .filter_map(|event| match *event {
+            AssetEvent::Added { id } | AssetEvent::Modified { id } => Some(id),
+            _ => None,
+        })
+        .collect();
+
+    for (entity, terrain, transform, outline, mut clipmap) in &mut terrains {
+        let heightmap_changed = changed_images.contains(&terrain.heightmap.id());
+        if clipmap.is_some() && !terrain.is_changed() && !heightmap_changed {
+            continue;
+        }
+        let Some(heightmap) = images.get(&terrain.heightmap) else {
+            continue;
+        };
+
+        let material = TerrainMaterial::new(&terrain, transform);
+        let material = match &mut clipmap {
+            Some(clipmap) => {
+                for piece in clipmap.pieces.drain(..) {
+                    commands.entity(piece).despawn();
+                }
+                if let Some(existing) = materials.get_mut(&clipmap.material) {
+                    *existing = material;
+                }
+                clipmap.material.clone()
+            }
+            None => materials.add(material),
+        };
+
+        let resolution = clipmap_resolution(terrain.clipmap_resolution);
+        let heights = heightmap_range(heightmap).map(|heights| {
+            heights.start * terrain.height_scale..heights.end * terrain.height_scale
+        });
+
+        let pieces = ClipmapPiece::all(terrain.clipmap_levels)
+            .map(|piece| {
+                let mut piece_entity = commands.spawn((
+                    TerrainClipmapPiece(piece),
+                    Mesh3d(meshes.add(piece.mesh(resolution, terrain.clipmap_spacing))),
+                    MeshMaterial3d(material.clone()),
+                    ChildOf(entity),
+                ));
+                match &heights {
+                    Some(heights) => piece_entity.insert(piece.aabb(
+                        resolution,
+                        terrain.clipmap_spacing,
+                        heights.clone(),
+                    )),
+                    None => piece_entity.insert(NoFrustumCulling),
+                };
+                if let Some(outline) = outline {
+                    piece_entity.insert(*outline);
+                }
+                piece_entity.id()
+            })
+            .collect();
+
+        match clipmap {
+            Some(mut clipmap) => clipmap.pieces = pieces,
+            None => {
+                commands
+                    .entity(entity)
+                    .insert(TerrainClipmap { material, pieces });
+            }
+        }
+    }
+}
+
+/// Copies the [`Outline`] of terrains to their clipmap meshes when it's
+/// changed, and removes it from them when it's removed from the terrain.
+fn update_terrain_outlines(
+    mut commands: Commands,
+    terrains: Query<(Option<Ref<Outline>>, &TerrainClipmap)>,
+    mut removed_outlines: RemovedComponents<Outline>,
+) {
+    for entity in removed_outlines.read() {
+        let Ok((None, clipmap)) = terrains.get(entity) else {
+            continue;
+        };
+        for &piece in &clipmap.pieces {
+            if let Some(mut piece) = commands.get_entity(piece) {
+                piece.try_remove::<Outline>();
+            }
+        }
+    }
+
+    for (outline, clipmap) in &terrains {
+        let Some(outline) = outline.filter(DetectChanges::is_changed) else {
+            continue;
+        };
+        for &piece in &clipmap.pieces {
+            if let Some(mut piece) = commands.get_entity(piece) {
+                piece.try_insert(*outline);
+            }
+        }
+    }
+}
+
+/// Despawns the clipmap meshes of a terrain when its [`Terrain`] is removed.
+fn despawn_terrain_clipmap(
+    trigger: Trigger<OnRemove, Terrain>,
+    clipmaps: Query<&TerrainClipmap>,
+    mut commands: Commands,
+) {
+    let Ok(clipmap) = clipmaps.get(trigger.target()) else {
+        return;
+    };
+    for &piece in &clipmap.pieces {
+        if let Some(mut piece) = commands.get_entity(piece) {
+            piece.despawn();
+        }
+    }
+    if let Some(mut terrain) = commands.get_entity(trigger.target()) {
+        terrain.try_remove::<TerrainClipmap>();
+    }
+}
+
+/// Moves the clipmap meshes of every terrain to follow the camera.
+fn center_terrain_clipmaps(
+    cameras: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+    terrains: Query<(&Terrain, &GlobalTransform, &TerrainClipmap)>,
+    mut pieces: Query<(&TerrainClipmapPiece, &mut Transform)>,
+) {
+    let Some((_, camera_transform)) = cameras
+        .iter()
+        .filter(|(camera, _)| camera.is_active)
+        .min_by_key(|(camera, _)| camera.order)
+    else {
+        return;
+    };
+
+    for (terrain, terrain_transform, clipmap) in &terrains {
+        let camera = terrain_transform
+            .affine()
+            .inverse()
+            .transform_point3(camera_transform.translation())
+            .xz();
+        let resolution = clipmap_resolution(terrain.clipmap_resolution);
+
+        let mut iter = pieces.iter_many_mut(&clipmap.pieces);
+        while let Some((TerrainClipmapPiece(piece), mut transform)) = iter.fetch_next() {
+            let translation = piece.translation(camera, resolution, terrain.clipmap_spacing);
+            transform.set_if_neq(Transform::from_xyz(translation.x, 0.0, translation.y));
+        }
+    }
+}
+
+/// Keeps the current and previous transforms in the materials of terrains up
+/// to date.
+///
+/// Materials are only modified while their terrain moves, and on the frame
+/// after it stops, when the previous transform catches up.
+fn update_terrain_materials(
+    terrains: Query<(&GlobalTransform, &TerrainClipmap)>,
+    mut materials: ResMut<Assets<TerrainMaterial>>,
+) {
+    for (transform, clipmap) in &terrains {
+        let world_from_terrain = transform.compute_matrix();
+        if let Some(material) = materials.get(&clipmap.material) {
+            if material.world_from_terrain == world_from_terrain
+                && material.previous_world_from_terrain == world_from_terrain
+            {
+                continue;
+            }
+        }
+        if let Some(material) = materials.get_mut(&clipmap.material) {
+            material.previous_world_from_terrain = material.world_from_terrain;
+            material.world_from_terrain = world_from_terrain;
+        }
+    }
+}
+
+/// Reads a height from a heightmap, in the same units as the shader.
+fn heightmap_texel(heightmap: &Image, x: u32, y: u32) -> Option<f32> {
+    heightmap
+        .get_color_at(x, y)
+        .ok()
+        .map(|color| color.to_linear().red)
+}
+
+/// Returns the range of the heights in a heightmap, or [`None`] if its data
+/// isn't available in the main world.
+fn heightmap_range(heightmap: &Image) -> Option<core::ops::Range<f32>> {
+    heightmap.data.as_ref()?;
+    let size = heightmap.size();
+    let mut range = f32::MAX..f32::MIN;
+    for y in 0..size.y {
+        for x in 0..size.x {
+            let height = heightmap_texel(heightmap, x, y)?;
+            range.start = range.start.min(height);
+            range.end = range.end.max(height);
+        }
+    }
+    (range.start <= range.end).then_some(range)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        build_terrain_clipmaps, despawn_terrain_clipmap, update_terrain_outlines, Terrain,
+        TerrainClipmap, TerrainClipmapPiece, TerrainMaterial,
+    };
+    use bevy_asset::{AssetEvent, Assets, RenderAssetUsages};
+    use bevy_core_pipeline::outline::Outline;
+    use bevy_ecs::{
+        event::Events,
+        query::With,
+        schedule::{IntoSystemConfigs, Schedule},
+        system::RunSystemOnce,
+        world::World,
+    };
+    use bevy_image::Image;
+    use bevy_math::Vec2;
+    use bevy_render::{
+        mesh::Mesh,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+    };
+    use bevy_transform::components::GlobalTransform;
+
+    fn heightmap(heights: &[f32; 4]) -> Image {
+        Image::new(
+            Extent3d {
+                width: 2,
+                height: 2,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            heights
+                .iter()
+                .flat_map(|height| height.to_le_bytes())
+                .collect(),
+            TextureFormat::R32Float,
+            RenderAssetUsages::MAIN_WORLD,
+        )
+    }
+
+    #[test]
+    fn height_at_filters_heightmap() {
+        let mut images = Assets::<Image>::default();
+        let terrain = Terrain {
+            heightmap: images.add(heightmap(&[0.0, 1.0, 2.0, 3.0])),
+            size: Vec2::splat(2.0),
+            height_scale: 10.0,
+            ..Terrain::default()
+        };
+
+        // Texel centers.
+        assert_eq!(terrain.height_at(&images, Vec2::new(-0.5, -0.5)), Some(0.0));
+        assert_eq!(terrain.height_at(&images, Vec2::new(0.5, -0.5)), Some(10.0));
+        assert_eq!(terrain.height_at(&images, Vec2::new(-0.5, 0.5)), Some(20.0));
+        // Between all four texels.
+        assert_eq!(terrain.height_at(&images, Vec2::ZERO), Some(15.0));
+        // Edges are clamped.
+        assert_eq!(terrain.height_at(&images, Vec2::new(1.0, 1.0)), Some(30.0));
+        // Outside of the terrain.
+        assert_eq!(terrain.height_at(&images, Vec2::new(1.5, 0.0)), None);
+    }
+
+    #[test]
+    fn removing_terrain_despawns_clipmap() {
+        let mut world = World::new();
+        world.init_resource::<Assets<Mesh>>();
+        world.init_resource::<Assets<TerrainMaterial>>();
+        world.init_resource::<Events<AssetEvent<Image>>>();
+        let mut images = Assets::<Image>::default();
+        let terrain = Terrain {
+            heightmap: images.add(heightmap(&[0.0; 4])),
+            clipmap_levels: 2,
+            ..Terrain::default()
+        };
+        world.insert_resource(images);
+        world.add_observer(despawn_terrain_clipmap);
+
+        let terrain = world.spawn((terrain, GlobalTransform::default())).id();
+        world.run_system_once(build_terrain_clipmaps).unwrap();
+        let mut pieces = world.query_filtered::<(), With<TerrainClipmapPiece>>();
+        assert_ne!(pieces.iter(&world).count(), 0);
+
+        world.entity_mut(terrain).remove::<Terrain>();
+        world.flush();
+        assert_eq!(pieces.iter(&world).count(), 0);
+        assert!(!world.entity(terrain).contains::<TerrainClipmap>());
+    }
+
+    #[test]
+    fn terrain_outline_is_copied_to_clipmap() {
+        let mut world = World::new();
+        world.init_resource::<Assets<Mesh>>();
+        world.init_resource::<Assets<TerrainMaterial>>();
+        world.init_resource::<Events<AssetEvent<Image>>>();
+        let mut images = Assets::<Image>::default();
+        let terrain = Terrain {
+            heightmap: images.add(heightmap(&[0.0; 4])),
+            clipmap_levels: 2,
+            ..Terrain::default()
+        };
+        world.insert_resource(images);
+        let mut schedule = Schedule::default();
+        schedule.add_systems((build_terrain_clipmaps, update_terrain_outlines).chain());
+
+        let outline = Outline {
+            width: 4.0,
+            ..Outline::default()
+        };
+        let terrain = world
+            .spawn((terrain, outline, GlobalTransform::default()))
+            .id();
+        schedule.run(&mut world);
+        let mut outlines = world.query_filtered::<Option<&Outline>, With<TerrainClipmapPiece>>();
+        assert_ne!(outlines.iter(&world).count(), 0);
+        assert!(outlines.iter(&world).all(|piece| piece == Some(&outline)));
+
+        let outline = Outline {
+            width: 1.0,
+            ..outline
+        };
+        world.entity_mut(terrain).insert(outline);
+        schedule.run(&mut world);
+        assert!(outlines.iter(&world).all(|piece| piece == Some(&outline)));
+
+        world.entity_mut(terrain).remove::<Outline>();
+        schedule.run(&mut world);
+        assert!(outlines.iter(&world).all(|piece| piece.is_none()));
+    }
+}
@@ -0,0 +1,260 @@
+// Heightmap terrain rendered with geometry clipmaps
+//
+// The clipmap is a set of flat grid rings, each twice as coarse as the one
+// inside it, that follow the camera. The vertex shader displaces every grid
+// vertex by the heightmap, and the fragment shader blends up to four splat
+// layers by the weights in the splat map.
+//
+// Based on "Geometry Clipmaps: Terrain Rendering Using Nested Regular Grids"
+// by Frank Losasso and Hugues Hoppe.
+
+#import bevy_pbr::{
+    mesh_functions,
+    view_transformations::position_world_to_clip,
+}
+
+#ifdef PREPASS_PIPELINE
+#import bevy_pbr::prepass_io::{Vertex, VertexOutput}
+#else
+#import bevy_pbr::{
+    forward_io::{Vertex, VertexOutput, FragmentOutput},
+    pbr_fragment::pbr_input_from_vertex_output,
+    pbr_functions::{apply_pbr_lighting, main_pass_post_lighting_processing, calculate_tbn_mikktspace},
+}
+#ifdef SCREEN_SPACE_AMBIENT_OCCLUSION
+#import bevy_pbr::{
+    mesh_view_bindings::screen_space_ambient_occlusion_texture,
+    ssao_utils::ssao_multibounce,
+}
+#endif
+#ifdef DEBUG_VIEW
+#import bevy_pbr::debug_view::debug_view_color
+#endif
+#endif
+
+const MAX_TERRAIN_LAYERS: u32 = 4u;
+
+const TERRAIN_FLAGS_SPLAT_MAP_BIT: u32 = 1u;
+const TERRAIN_FLAGS_BASE_COLOR_TEXTURE_BIT: u32 = 2u;
+const TERRAIN_FLAGS_NORMAL_MAP_TEXTURE_BIT: u32 = 4u;
+
+struct TerrainLayer {
+    base_color: vec4<f32>,
+    perceptual_roughness: f32,
+    metallic: f32,
+    reflectance: f32,
+    uv_scale: f32,
+}
+
+struct TerrainMaterial {
+    layers: array<TerrainLayer, MAX_TERRAIN_LAYERS>,
+    terrain_from_world: mat4x4<f32>,
+    previous_world_from_terrain: mat4x4<f32>,
+    size: vec2<f32>,
+    height_scale: f32,
+    layer_count: u32,
+    flags: u32,
+}
+
+@group(2) @binding(0) var<uniform> material: TerrainMaterial;
+@group(2) @binding(1) var heightmap: texture_2d<f32>;
+@group(2) @binding(2) var splat_map: texture_2d<f32>;
+@group(2) @binding(3) var splat_map_sampler: sampler;
+@group(2) @binding(4) var base_color_texture: texture_2d_array<f32>;
+@group(2) @binding(5) var base_color_sampler: sampler;
+@group(2) @binding(6) var normal_map_texture: texture_2d_array<f32>;
+@group(2) @binding(7) var normal_map_sampler: sampler;
+
+// Returns the height of the terrain at a position in terrain space.
+//
+// The heightmap is filtered by hand because float heightmaps aren't
+// filterable on every platform. This must match `Terrain::height_at`.
+fn terrain_height(position: vec2<f32>) -> f32 {
+    let size = vec2<i32>(textureDimensions(heightmap));
+    let texel = (position / material.size + 0.5) * vec2<f32>(size) - 0.5;
+    let base = floor(texel);
+    let t = texel - base;
+
+    let p0 = clamp(vec2<i32>(base), vec2(0), size - 1);
+    let p1 = clamp(vec2<i32>(base) + 1, vec2(0), size - 1);
+    let h00 = textureLoad(heightmap, p0, 0).r;
+    let h10 = textureLoad(heightmap, vec2(p1.x, p0.y), 0).r;
+    let h01 = textureLoad(heightmap, vec2(p0.x, p1.y), 0).r;
+    let h11 = textureLoad(heightmap, p1, 0).r;
+
+    return mix(mix(h00, h10, t.x), mix(h01, h11, t.x), t.y) * material.height_scale;
+}
+
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+
+#ifdef MULTIVIEW
+    bevy_pbr::mesh_view_bindings::select_view(vertex.view_index);
+    out.view_index = vertex.view_index;
+#endif
+
+    let world_from_local = mesh_functions::get_world_from_local(vertex.instance_index);
+
+    // Clipmap meshes are only ever translated relative to the terrain, so
+    // local offsets are terrain space offsets.
+    let grid_position = (material.terrain_from_world *
+        world_from_local * vec4(vertex.position.x, 0.0, vertex.position.z, 1.0)).xz;
+
+    // Collapse the grid outside the heightmap onto its edges.
+    let half_size = material.size * 0.5;
+    let terrain_position = clamp(grid_position, -half_size, half_size);
+
+    // Vertices on the outer edge of a ring that lie between two vertices of
+    // the next coarser ring take the average of their heights, so that the
+    // rings meet without cracks. `uv` holds the offset to those neighbors.
+    let morph_offset = vertex.uv;
+    var height = terrain_height(terrain_position);
+    if any(morph_offset != vec2(0.0)) {
+        height = 0.5 * (terrain_height(terrain_position - morph_offset) +
+            terrain_height(terrain_position + morph_offset));
+    }
+
+    let local_position = vec3(
+        vertex.position.x + terrain_position.x - grid_position.x,
+        height,
+        vertex.position.z + terrain_position.y - grid_position.y,
+    );
+
+    out.world_position = mesh_functions::mesh_position_local_to_world(world_from_local, vec4(local_position, 1.0));
+    out.position = position_world_to_clip(out.world_position.xyz);
+
+#ifdef UNCLIPPED_DEPTH_ORTHO_EMULATION
+    out.unclipped_depth = out.position.z;
+    out.position.z = min(out.position.z, 1.0); // Clamp depth to avoid clipping
+#endif // UNCLIPPED_DEPTH_ORTHO_EMULATION
+
+    // The UV is the heightmap and splat map coordinate.
+    out.uv = terrain_position / material.size + 0.5;
+#ifdef VERTEX_UVS_B
+    out.uv_b = vertex.uv_b;
+#endif
+
+#ifdef PREPASS_PIPELINE
+#ifdef NORMAL_PREPASS_OR_DEFERRED_PREPASS
+    out.world_normal = terrain_normal(terrain_position, vertex.uv_b.x, vertex.instance_index);
+#endif
+#else
+    out.world_normal = terrain_normal(terrain_position, vertex.uv_b.x, vertex.instance_index);
+#endif
+
+#ifdef MOTION_VECTOR_PREPASS
+    // The clipmap meshes move with the camera, but the surface only moves
+    // with the terrain, so the previous position is that of the same point of
+    // the terrain under its previous transform.
+    out.previous_world_position = material.previous_world_from_terrain *
+        vec4(terrain_position.x, height, terrain_position.y, 1.0);
+#endif // MOTION_VECTOR_PREPASS
+
+#ifdef OUTLINE_ID_PREPASS
+    out.outline_id = mesh_functions::get_outline_id(vertex.instance_index);
+#endif // OUTLINE_ID_PREPASS
+
+#ifdef VERTEX_OUTPUT_INSTANCE_INDEX
+    out.instance_index = vertex.instance_index;
+#endif
+
+    return out;
+}
+
+// Returns the world space normal of the terrain from central differences
+// spaced by the ring's grid spacing, or by one heightmap texel if that is
+// larger, so that coarse rings don't alias.
+fn terrain_normal(position: vec2<f32>, grid_spacing: f32, instance_index: u32) -> vec3<f32> {
+    let texel_size = material.size / vec2<f32>(textureDimensions(heightmap));
+    let step = max(vec2(grid_spacing), texel_size);
+    let dx = terrain_height(position + vec2(step.x, 0.0)) - terrain_height(position - vec2(step.x, 0.0));
+    let dz = terrain_height(position + vec2(0.0, step.y)) - terrain_height(position - vec2(0.0, step.y));
+    let local_normal = normalize(vec3(-dx * step.y, 2.0 * step.x * step.y, -dz * step.x));
+    return mesh_functions::mesh_normal_local_to_world(local_normal, instance_index);
+}
+
+#ifndef PREPASS_PIPELINE
+@fragment
+fn fragment(
+    in: VertexOutput,
+    @builtin(front_facing) is_front: bool,
+) -> FragmentOutput {
+#ifdef MULTIVIEW
+    bevy_pbr::mesh_view_bindings::select_view(in.view_index);
+#endif
+
+    var pbr_input = pbr_input_from_vertex_output(in, is_front, false);
+
+    // Normalize the splat weights. Without a splat map, only the first layer
+    // is drawn.
+    var weights = vec4(1.0, 0.0, 0.0, 0.0);
+    if (material.flags & TERRAIN_FLAGS_SPLAT_MAP_BIT) != 0u {
+        weights = textureSample(splat_map, splat_map_sampler, in.uv);
+    }
+    for (var i = material.layer_count; i < MAX_TERRAIN_LAYERS; i += 1u) {
+        weights[i] = 0.0;
+    }
+    weights /= max(dot(weights, vec4(1.0)), 1e-4);
+
+    let terrain_position = (in.uv - 0.5) * material.size;
+
+    var base_color = vec4(0.0);
+    var perceptual_roughness = 0.0;
+    var metallic = 0.0;
+    var reflectance = 0.0;
+    var Nt = vec3(0.0);
+    for (var i = 0u; i < material.layer_count; i += 1u) {
+        let weight = weights[i];
+        let layer = material.layers[i];
+        let uv = terrain_position * layer.uv_scale;
+
+        var layer_color = layer.base_color;
+        if (material.flags & TERRAIN_FLAGS_BASE_COLOR_TEXTURE_BIT) != 0u {
+            layer_color *= textureSample(base_color_texture, base_color_sampler, uv, i);
+        }
+        var layer_Nt = vec3(0.0, 0.0, 1.0);
+        if (material.flags & TERRAIN_FLAGS_NORMAL_MAP_TEXTURE_BIT) != 0u {
+            layer_Nt = textureSample(normal_map_texture, normal_map_sampler, uv, i).rgb * 2.0 - 1.0;
+        }
+
+        base_color += layer_color * weight;
+        perceptual_roughness += layer.perceptual_roughness * weight;
+        metallic += layer.metallic * weight;
+        reflectance += layer.reflectance * weight;
+        Nt += layer_Nt * weight;
+    }
+
+    pbr_input.material.base_color = base_color;
+    pbr_input.material.perceptual_roughness = perceptual_roughness;
+    pbr_input.material.metallic = metallic;
+    pbr_input.material.reflectance = vec3(reflectance);
+
+#ifdef SCREEN_SPACE_AMBIENT_OCCLUSION
+    let ssao = textureLoad(screen_space_ambient_occlusion_texture, vec2<i32>(in.position.xy), 0i).r;
+    let NdotV = max(dot(pbr_input.N, pbr_input.V), 0.0001);
+    let roughness = perceptual_roughness * perceptual_roughness;
+    pbr_input.diffuse_occlusion = vec3(ssao_multibounce(ssao, base_color.rgb));
+    // Lagarde and Rousiers 2014, "Moving Frostbite to Physically Based Rendering"
+    pbr_input.specular_occlusion = saturate(pow(NdotV + ssao, exp2(-16.0 * roughness - 1.0)) - 1.0 + ssao);
+#endif
+
+#ifndef LOAD_PREPASS_NORMALS
+    if (material.flags & TERRAIN_FLAGS_NORMAL_MAP_TEXTURE_BIT) != 0u {
+        // Layer textures are mapped along terrain space X and Z.
+        let world_from_local = mesh_functions::get_world_from_local(in.instance_index);
+        let world_tangent = vec4((world_from_local * vec4(1.0, 0.0, 0.0, 0.0)).xyz, 1.0);
+        let TBN = calculate_tbn_mikktspace(pbr_input.world_normal, world_tangent);
+        pbr_input.N = normalize(Nt.x * TBN[0] + Nt.y * TBN[1] + Nt.z * TBN[2]);
+    }
+#endif
+
+    var out: FragmentOutput;
+    out.color = apply_pbr_lighting(pbr_input);
+    out.color = main_pass_post_lighting_processing(pbr_input, out.color);
+#ifdef DEBUG_VIEW
+    out.color = debug_view_color(in.instance_index, out.color);
+#endif
+    return out;
+}
+#endif
@@ -604,6 +604,7 @@ impl SpecializedRenderPipeline for VolumetricFogPipeline {
                 })],
             }),
             zero_initialize_workgroup_memory: false,
+            multiview: None,
         }
     }
 }
@@ -21,6 +21,23 @@
 //! 2. they allow events to bubble up the entity hierarchy,
 //! 3. and they allow events of different types to be called in a specific order.
 //!
+//! Like any event that propagates automatically, pointer events also have a capture phase, which
+//! lets an ancestor handle an event before its descendants, and stop it from reaching them:
+//!
+//! ```rust
+//! # use bevy_ecs::prelude::*;
+//! # use bevy_picking::prelude::*;
+//! # let mut world = World::default();
+//! world.spawn_empty()
+//!     .observe_capture(|mut trigger: Trigger<Pointer<Click>>| {
+//!         // This panel is disabled, so its children don't receive clicks.
+//!         trigger.propagate(false);
+//!     });
+//! ```
+//!
+//! See [`PropagationPhase`](bevy_ecs::observer::PropagationPhase) for the order in which observers
+//! run while an event propagates.
+//!
 //! The order in which interaction events are received is extremely important, and you can read more
 //! about it on the docs for the dispatcher system: [`pointer_events`]. This system runs in
 //! [`PreUpdate`](bevy_app::PreUpdate) in [`PickSet::Hover`](crate::PickSet::Hover). All pointer-event
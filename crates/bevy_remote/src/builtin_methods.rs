@@ -11,8 +11,9 @@ use bevy_ecs::{
     query::QueryBuilder,
     reflect::{AppTypeRegistry, ReflectComponent, ReflectResource},
     removal_detection::RemovedComponentEntity,
+    schedule::{InternedScheduleLabel, NodeId, Schedules, Stepping},
     system::{In, Local},
-    world::{EntityRef, EntityWorldMut, FilteredEntityRef, World},
+    world::{ComponentChangeHistory, EntityRef, EntityWorldMut, FilteredEntityRef, World},
 };
 use bevy_platform_support::collections::HashMap;
 use bevy_reflect::{
@@ -62,6 +63,33 @@ pub const BRP_LIST_AND_WATCH_METHOD: &str = "bevy/list+watch";
 /// The method path for a `bevy/registry/schema` request.
 pub const BRP_REGISTRY_SCHEMA_METHOD: &str = "bevy/registry/schema";
 
+/// The method path for a `bevy/stepping/enable` request.
+pub const BRP_STEPPING_ENABLE_METHOD: &str = "bevy/stepping/enable";
+
+/// The method path for a `bevy/stepping/disable` request.
+pub const BRP_STEPPING_DISABLE_METHOD: &str = "bevy/stepping/disable";
+
+/// The method path for a `bevy/stepping/step_system` request.
+pub const BRP_STEPPING_STEP_SYSTEM_METHOD: &str = "bevy/stepping/step_system";
+
+/// The method path for a `bevy/stepping/continue` request.
+pub const BRP_STEPPING_CONTINUE_METHOD: &str = "bevy/stepping/continue";
+
+/// The method path for a `bevy/stepping/set_breakpoint` request.
+pub const BRP_STEPPING_SET_BREAKPOINT_METHOD: &str = "bevy/stepping/set_breakpoint";
+
+/// The method path for a `bevy/stepping/clear_breakpoint` request.
+pub const BRP_STEPPING_CLEAR_BREAKPOINT_METHOD: &str = "bevy/stepping/clear_breakpoint";
+
+/// The method path for a `bevy/stepping/cursor` request.
+pub const BRP_STEPPING_CURSOR_METHOD: &str = "bevy/stepping/cursor";
+
+/// The method path for a `bevy/storage` request.
+pub const BRP_STORAGE_METHOD: &str = "bevy/storage";
+
+/// The method path for a `bevy/component_history` request.
+pub const BRP_COMPONENT_HISTORY_METHOD: &str = "bevy/component_history";
+
 /// `bevy/get`: Retrieves one or more components from the entity with the given
 /// ID.
 ///
@@ -222,6 +250,51 @@ pub struct BrpMutateParams {
     pub value: Value,
 }
 
+/// `bevy/stepping/enable`: Enables [`Stepping`], optionally adding a schedule to the schedules
+/// that are stepped.
+///
+/// The server responds with a null.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct BrpSteppingEnableParams {
+    /// The name of the schedule to step, e.g. `Update`.
+    ///
+    /// If this is `None`, only the schedules that were already added are stepped.
+    #[serde(default)]
+    pub schedule: Option<String>,
+}
+
+/// `bevy/stepping/set_breakpoint` and `bevy/stepping/clear_breakpoint`: Sets or clears a
+/// [`Stepping`] breakpoint on the systems with the given name.
+///
+/// The server responds with a null.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpSteppingBreakpointParams {
+    /// The name of the schedule containing the system, e.g. `Update`.
+    pub schedule: String,
+
+    /// The name of the system.
+    ///
+    /// This can be either the full name of the system, e.g. `my_game::movement::apply_velocity`,
+    /// or only its last segment, e.g. `apply_velocity`. All systems in the schedule with that
+    /// name are affected.
+    pub system: String,
+}
+
+/// `bevy/component_history`: Retrieves the recorded changes of a component of an entity, whose
+/// changes are tracked with [`World::track_component_changes`].
+///
+/// The server responds with a [`BrpComponentHistoryResponse`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpComponentHistoryParams {
+    /// The ID of the entity.
+    pub entity: Entity,
+
+    /// The [full path] of the component type.
+    ///
+    /// [full path]: bevy_reflect::TypePath::type_path
+    pub component: String,
+}
+
 /// Describes the data that is to be fetched in a query.
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
 pub struct BrpQuery {
@@ -361,6 +434,121 @@ pub struct BrpListWatchingResponse {
 /// The response to a `bevy/query` request.
 pub type BrpQueryResponse = Vec<BrpQueryRow>;
 
+/// The response to a `bevy/stepping/cursor` request.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpSteppingCursorResponse {
+    /// Whether stepping is enabled.
+    pub enabled: bool,
+
+    /// The name of the schedule containing the next system to run, if any.
+    pub schedule: Option<String>,
+
+    /// The name of the next system to run, if any.
+    pub system: Option<String>,
+}
+
+/// The response to a `bevy/storage` request.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpStorageResponse {
+    /// The archetypes of the world.
+    pub archetypes: Vec<BrpArchetypeStorage>,
+
+    /// The tables of the world.
+    pub tables: Vec<BrpTableStorage>,
+
+    /// The sparse sets of the world.
+    pub sparse_sets: Vec<BrpSparseSetStorage>,
+
+    /// The number of times an entity has moved between archetypes since the app started.
+    pub entity_moves: u64,
+}
+
+/// An archetype in a [`BrpStorageResponse`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpArchetypeStorage {
+    /// The index of the archetype.
+    pub id: usize,
+
+    /// The index of the table storing the archetype's table components.
+    pub table: usize,
+
+    /// The number of entities in the archetype.
+    pub entity_count: usize,
+
+    /// The fully-qualified type names of the archetype's components.
+    pub components: Vec<String>,
+}
+
+/// A table in a [`BrpStorageResponse`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpTableStorage {
+    /// The index of the table.
+    pub id: usize,
+
+    /// The number of entities in the table.
+    pub entity_count: usize,
+
+    /// The number of entities the table can store without reallocating.
+    pub capacity: usize,
+
+    /// The number of archetypes sharing the table.
+    pub archetype_count: usize,
+
+    /// The columns of the table.
+    pub columns: Vec<BrpColumnStorage>,
+}
+
+/// A column of a [`BrpTableStorage`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpColumnStorage {
+    /// The fully-qualified type name of the component stored in the column.
+    pub component: String,
+
+    /// The size of a single component value, in bytes.
+    pub item_size: usize,
+
+    /// The bytes used by the component values stored in the column.
+    pub bytes: usize,
+
+    /// The bytes allocated for the column, including unused capacity and change detection ticks.
+    pub allocated_bytes: usize,
+}
+
+/// A sparse set in a [`BrpStorageResponse`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpSparseSetStorage {
+    /// The fully-qualified type name of the component stored in the sparse set.
+    pub component: String,
+
+    /// The number of entities with the component.
+    pub entity_count: usize,
+
+    /// The size of a single component value, in bytes.
+    pub item_size: usize,
+
+    /// The bytes used by the component values stored in the sparse set.
+    pub bytes: usize,
+}
+
+/// The response to a `bevy/component_history` request, from the oldest change to the newest.
+pub type BrpComponentHistoryResponse = Vec<BrpComponentChange>;
+
+/// A change in a [`BrpComponentHistoryResponse`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct BrpComponentChange {
+    /// The change tick at which the component was changed.
+    pub tick: u32,
+
+    /// Whether the component was inserted rather than mutated in place.
+    pub added: bool,
+
+    /// The name of the system that made the change, if any.
+    pub system: Option<String>,
+
+    /// The source location of the change, if the `track_location` feature is enabled.
+    pub caller: Option<String>,
+}
+
 /// One query match result: a single entity paired with the requested components.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct BrpQueryRow {
@@ -927,6 +1115,232 @@ pub fn process_remote_list_watching_request(
     }
 }
 
+/// Handles a `bevy/stepping/enable` request coming from a client.
+pub fn process_remote_stepping_enable_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    require_stepping_feature()?;
+    let BrpSteppingEnableParams { schedule } = match params {
+        None => Default::default(),
+        Some(params) => parse(params)?,
+    };
+
+    let label = schedule
+        .map(|schedule| get_schedule_label(world, &schedule))
+        .transpose()?;
+
+    let mut stepping = world.get_resource_or_init::<Stepping>();
+    if let Some(label) = label {
+        stepping.add_schedule(label);
+    }
+    stepping.enable();
+
+    Ok(Value::Null)
+}
+
+/// Handles a `bevy/stepping/disable` request coming from a client.
+pub fn process_remote_stepping_disable_request(
+    In(_): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    require_stepping_feature()?;
+    if let Some(mut stepping) = world.get_resource_mut::<Stepping>() {
+        stepping.disable();
+    }
+
+    Ok(Value::Null)
+}
+
+/// Handles a `bevy/stepping/step_system` request coming from a client.
+///
+/// Only the single system at the [`Stepping`] cursor runs during the next frame; use
+/// `bevy/stepping/continue` to run the rest of the frame.
+pub fn process_remote_stepping_step_system_request(
+    In(_): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    require_stepping_feature()?;
+    world.get_resource_or_init::<Stepping>().step_frame();
+
+    Ok(Value::Null)
+}
+
+/// Handles a `bevy/stepping/continue` request coming from a client.
+///
+/// The stepped schedules run from the [`Stepping`] cursor until the next breakpoint or the end of
+/// the frame during the next frame.
+pub fn process_remote_stepping_continue_request(
+    In(_): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    require_stepping_feature()?;
+    world.get_resource_or_init::<Stepping>().continue_frame();
+
+    Ok(Value::Null)
+}
+
+/// Handles a `bevy/stepping/set_breakpoint` request coming from a client.
+pub fn process_remote_stepping_set_breakpoint_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    require_stepping_feature()?;
+    let BrpSteppingBreakpointParams { schedule, system } = parse_some(params)?;
+
+    let label = get_schedule_label(world, &schedule)?;
+    let nodes = get_system_nodes(world, label, &system)?;
+
+    let mut stepping = world.get_resource_or_init::<Stepping>();
+    for node in nodes {
+        stepping.set_breakpoint_node(label, node);
+    }
+
+    Ok(Value::Null)
+}
+
+/// Handles a `bevy/stepping/clear_breakpoint` request coming from a client.
+pub fn process_remote_stepping_clear_breakpoint_request(
+    In(params): In<Option<Value>>,
+    world: &mut World,
+) -> BrpResult {
+    require_stepping_feature()?;
+    let BrpSteppingBreakpointParams { schedule, system } = parse_some(params)?;
+
+    let label = get_schedule_label(world, &schedule)?;
+    let nodes = get_system_nodes(world, label, &system)?;
+
+    let mut stepping = world.get_resource_or_init::<Stepping>();
+    for node in nodes {
+        stepping.clear_breakpoint_node(label, node);
+    }
+
+    Ok(Value::Null)
+}
+
+/// Handles a `bevy/stepping/cursor` request coming from a client.
+pub fn process_remote_stepping_cursor_request(
+    In(_): In<Option<Value>>,
+    world: &World,
+) -> BrpResult {
+    require_stepping_feature()?;
+    let mut response = BrpSteppingCursorResponse::default();
+
+    if let Some(stepping) = world.get_resource::<Stepping>() {
+        response.enabled = stepping.is_enabled();
+        if let Some((label, node)) = stepping.cursor() {
+            response.schedule = Some(format!("{label:?}"));
+            response.system = world
+                .get_resource::<Schedules>()
+                .and_then(|schedules| schedules.get(label))
+                .and_then(|schedule| schedule.systems().ok())
+                .and_then(|mut systems| systems.find(|(id, _)| *id == node))
+                .map(|(_, system)| system.name().into_owned());
+        }
+    }
+
+    serde_json::to_value(response).map_err(BrpError::internal)
+}
+
+/// Handles a `bevy/storage` request coming from a client.
+pub fn process_remote_storage_request(In(_): In<Option<Value>>, world: &World) -> BrpResult {
+    let report = world.storage_report();
+    let component_name = |id: ComponentId| {
+        world
+            .components()
+            .get_name(id)
+            .map(ToOwned::to_owned)
+            .unwrap_or_else(|| format!("{id:?}"))
+    };
+
+    let response = BrpStorageResponse {
+        archetypes: report
+            .archetypes
+            .iter()
+            .map(|archetype| BrpArchetypeStorage {
+                id: archetype.id.index(),
+                table: archetype.table.as_usize(),
+                entity_count: archetype.entity_count,
+                components: archetype
+                    .components
+                    .iter()
+                    .map(|&id| component_name(id))
+                    .collect(),
+            })
+            .collect(),
+        tables: report
+            .tables
+            .iter()
+            .map(|table| BrpTableStorage {
+                id: table.id.as_usize(),
+                entity_count: table.entity_count,
+                capacity: table.capacity,
+                archetype_count: table.archetype_count,
+                columns: table
+                    .columns
+                    .iter()
+                    .map(|column| BrpColumnStorage {
+                        component: component_name(column.component),
+                        item_size: column.item_size,
+                        bytes: column.bytes,
+                        allocated_bytes: column.allocated_bytes,
+                    })
+                    .collect(),
+            })
+            .collect(),
+        sparse_sets: report
+            .sparse_sets
+            .iter()
+            .map(|sparse_set| BrpSparseSetStorage {
+                component: component_name(sparse_set.component),
+                entity_count: sparse_set.entity_count,
+                item_size: sparse_set.item_size,
+                bytes: sparse_set.bytes,
+            })
+            .collect(),
+        entity_moves: report.entity_moves,
+    };
+
+    serde_json::to_value(response).map_err(BrpError::internal)
+}
+
+/// Handles a `bevy/component_history` request coming from a client.
+pub fn process_remote_component_history_request(
+    In(params): In<Option<Value>>,
+    world: &World,
+) -> BrpResult {
+    let BrpComponentHistoryParams { entity, component } = parse_some(params)?;
+
+    let app_type_registry = world.resource::<AppTypeRegistry>();
+    let type_registry = app_type_registry.read();
+    let registration = get_component_type_registration(&type_registry, &component)
+        .map_err(BrpError::component_error)?;
+    let component_id = world
+        .components()
+        .get_id(registration.type_id())
+        .filter(|&id| {
+            world
+                .get_resource::<ComponentChangeHistory>()
+                .is_some_and(|history| history.is_tracked(id))
+        })
+        .ok_or_else(|| {
+            BrpError::component_error(format!("Changes of `{component}` are not tracked"))
+        })?;
+    get_entity(world, entity)?;
+
+    let response: BrpComponentHistoryResponse = world
+        .component_changes_by_id(entity, component_id)
+        .map(|change| BrpComponentChange {
+            tick: change.tick.get(),
+            added: change.added,
+            system: change.system.as_ref().map(ToString::to_string),
+            caller: change.caller.into_option().map(ToString::to_string),
+        })
+        .collect();
+
+    serde_json::to_value(response).map_err(BrpError::internal)
+}
+
 /// Handles a `bevy/registry/schema` request (list all registry types in form of schema) coming from a client.
 pub fn export_registry_types(In(params): In<Option<Value>>, world: &World) -> BrpResult {
     let filter: BrpJsonSchemaQueryFilter = match params {
@@ -1310,6 +1724,61 @@ impl SchemaJsonReference for &NamedField {
     }
 }
 
+/// Finds the label of the schedule whose name is `name`, returning an error if there is no such
+/// schedule.
+fn get_schedule_label(world: &World, name: &str) -> Result<InternedScheduleLabel, BrpError> {
+    world
+        .get_resource::<Schedules>()
+        .and_then(|schedules| {
+            schedules
+                .iter()
+                .map(|(_, schedule)| schedule.label())
+                .find(|label| format!("{label:?}") == name)
+        })
+        .ok_or_else(|| BrpError::schedule_not_found(name))
+}
+
+/// Returns an error if bevy was compiled without the `bevy_debug_stepping` feature, without which
+/// schedules ignore [`Stepping`].
+fn require_stepping_feature() -> Result<(), BrpError> {
+    if cfg!(feature = "bevy_debug_stepping") {
+        Ok(())
+    } else {
+        Err(BrpError::internal(
+            "Stepping is unavailable; bevy was compiled without the bevy_debug_stepping feature",
+        ))
+    }
+}
+
+/// Finds the systems named `name` in the schedule with the given `label`, returning an error if
+/// there are none.
+///
+/// `name` matches both the full name of a system and the last segment of its name.
+fn get_system_nodes(
+    world: &World,
+    label: InternedScheduleLabel,
+    name: &str,
+) -> Result<Vec<NodeId>, BrpError> {
+    let schedule = world
+        .get_resource::<Schedules>()
+        .and_then(|schedules| schedules.get(label))
+        .ok_or_else(|| BrpError::schedule_not_found(&format!("{label:?}")))?;
+    let nodes: Vec<_> = schedule
+        .systems()
+        .map_err(BrpError::internal)?
+        .filter(|(_, system)| {
+            let system_name = system.name();
+            system_name == name || system_name.rsplit("::").next() == Some(name)
+        })
+        .map(|(node, _)| node)
+        .collect();
+
+    if nodes.is_empty() {
+        return Err(BrpError::system_not_found(name, &format!("{label:?}")));
+    }
+    Ok(nodes)
+}
+
 /// Immutably retrieves an entity from the [`World`], returning an error if the
 /// entity isn't present.
 fn get_entity(world: &World, entity: Entity) -> Result<EntityRef<'_>, BrpError> {
@@ -1512,8 +1981,8 @@ mod tests {
         );
     }
     use super::*;
-    use bevy_ecs::{component::Component, resource::Resource};
-    use bevy_reflect::Reflect;
+    use bevy_ecs::{component::Component, resource::Resource, schedule::Schedule, system::Query};
+    use bevy_reflect::{Reflect, TypePath};
 
     #[test]
     fn serialization_tests() {
@@ -1537,6 +2006,210 @@ mod tests {
         });
     }
 
+    #[test]
+    #[cfg(not(feature = "bevy_debug_stepping"))]
+    fn stepping_requires_feature() {
+        let mut world = World::new();
+        assert!(process_remote_stepping_enable_request(In(None), &mut world).is_err());
+        assert!(process_remote_stepping_step_system_request(In(None), &mut world).is_err());
+        assert!(process_remote_stepping_continue_request(In(None), &mut world).is_err());
+        assert!(process_remote_stepping_cursor_request(In(None), &world).is_err());
+        assert!(!world.contains_resource::<Stepping>());
+    }
+
+    #[test]
+    #[cfg(feature = "bevy_debug_stepping")]
+    fn stepping_step_and_continue() {
+        use bevy_ecs::{
+            schedule::{IntoSystemConfigs, ScheduleLabel},
+            system::{ResMut, RunSystemOnce},
+        };
+
+        #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+        struct TestSchedule;
+
+        #[derive(Resource, Default)]
+        struct Ran(Vec<&'static str>);
+
+        fn first(mut ran: ResMut<Ran>) {
+            ran.0.push("first");
+        }
+        fn second(mut ran: ResMut<Ran>) {
+            ran.0.push("second");
+        }
+        fn third(mut ran: ResMut<Ran>) {
+            ran.0.push("third");
+        }
+
+        let mut world = World::new();
+        world.init_resource::<Ran>();
+        let mut schedule = Schedule::new(TestSchedule);
+        schedule.add_systems((first, second, third).chain());
+        world.add_schedule(schedule);
+        world.run_schedule(TestSchedule);
+
+        let run_frame = |world: &mut World| {
+            world.resource_mut::<Ran>().0.clear();
+            world.run_system_once(Stepping::begin_frame).unwrap();
+            world.run_schedule(TestSchedule);
+            core::mem::take(&mut world.resource_mut::<Ran>().0)
+        };
+
+        process_remote_stepping_enable_request(
+            In(Some(json!({ "schedule": "TestSchedule" }))),
+            &mut world,
+        )
+        .unwrap();
+        assert_eq!(run_frame(&mut world), Vec::<&str>::new());
+
+        process_remote_stepping_step_system_request(In(None), &mut world).unwrap();
+        assert_eq!(run_frame(&mut world), ["first"]);
+        assert_eq!(
+            process_remote_stepping_cursor_request(In(None), &world).unwrap()["system"],
+            json!(core::any::type_name_of_val(&second))
+        );
+
+        process_remote_stepping_continue_request(In(None), &mut world).unwrap();
+        assert_eq!(run_frame(&mut world), ["second", "third"]);
+
+        process_remote_stepping_set_breakpoint_request(
+            In(Some(
+                json!({ "schedule": "TestSchedule", "system": "third" }),
+            )),
+            &mut world,
+        )
+        .unwrap();
+        process_remote_stepping_continue_request(In(None), &mut world).unwrap();
+        assert_eq!(run_frame(&mut world), ["first", "second"]);
+
+        process_remote_stepping_disable_request(In(None), &mut world).unwrap();
+        assert_eq!(run_frame(&mut world), ["first", "second", "third"]);
+    }
+
+    #[test]
+    #[cfg(feature = "bevy_debug_stepping")]
+    fn stepping_breakpoints_by_name() {
+        use bevy_ecs::schedule::ScheduleLabel;
+
+        #[derive(ScheduleLabel, Clone, Debug, PartialEq, Eq, Hash)]
+        struct TestSchedule;
+
+        fn stepped_system() {}
+
+        let mut world = World::new();
+        let mut schedule = Schedule::new(TestSchedule);
+        schedule.add_systems(stepped_system);
+        world.add_schedule(schedule);
+        world.run_schedule(TestSchedule);
+
+        let params =
+            |schedule: &str, system: &str| Some(json!({ "schedule": schedule, "system": system }));
+        assert!(process_remote_stepping_set_breakpoint_request(
+            In(params("TestSchedule", "stepped_system")),
+            &mut world
+        )
+        .is_ok());
+        assert!(process_remote_stepping_clear_breakpoint_request(
+            In(params("TestSchedule", "stepped_system")),
+            &mut world
+        )
+        .is_ok());
+
+        let error = process_remote_stepping_set_breakpoint_request(
+            In(params("TestSchedule", "missing_system")),
+            &mut world,
+        )
+        .unwrap_err();
+        assert_eq!(error.code, error_codes::SYSTEM_NOT_FOUND);
+        let error = process_remote_stepping_set_breakpoint_request(
+            In(params("MissingSchedule", "stepped_system")),
+            &mut world,
+        )
+        .unwrap_err();
+        assert_eq!(error.code, error_codes::SCHEDULE_NOT_FOUND);
+
+        let cursor = process_remote_stepping_cursor_request(In(None), &world).unwrap();
+        assert_eq!(
+            cursor,
+            json!({ "enabled": false, "schedule": null, "system": null })
+        );
+    }
+
+    #[test]
+    fn storage_report_uses_component_names() {
+        #[derive(Component)]
+        struct Health(#[expect(dead_code, reason = "Only gives the component a size.")] u32);
+
+        #[derive(Component)]
+        #[component(storage = "SparseSet")]
+        struct Marker;
+
+        let mut world = World::new();
+        let entity = world.spawn(Health(10)).id();
+        world.entity_mut(entity).insert(Marker);
+
+        let value = process_remote_storage_request(In(None), &world).unwrap();
+        let response: BrpStorageResponse = serde_json::from_value(value).unwrap();
+        let health = core::any::type_name::<Health>();
+        let marker = core::any::type_name::<Marker>();
+
+        let archetype = response
+            .archetypes
+            .iter()
+            .find(|archetype| archetype.entity_count == 1)
+            .unwrap();
+        assert_eq!(archetype.components, [health, marker]);
+        let table = &response.tables[archetype.table];
+        assert_eq!(table.columns[0].component, health);
+        assert_eq!(table.columns[0].bytes, size_of::<Health>());
+        assert!(response
+            .sparse_sets
+            .iter()
+            .any(|sparse_set| sparse_set.component == marker && sparse_set.entity_count == 1));
+        assert_eq!(response.entity_moves, 1);
+    }
+
+    #[test]
+    fn component_history() {
+        #[derive(Component, Reflect)]
+        struct Health(u32);
+
+        fn heal(mut query: Query<&mut Health>) {
+            for mut health in &mut query {
+                health.0 += 1;
+            }
+        }
+
+        let mut world = World::new();
+        let registry = AppTypeRegistry::default();
+        registry.write().register::<Health>();
+        world.insert_resource(registry);
+        let entity = world.spawn(Health(0)).id();
+        let params = |entity: Entity| {
+            Some(json!({ "entity": entity, "component": <Health as TypePath>::type_path() }))
+        };
+
+        let error = process_remote_component_history_request(In(params(entity)), &world);
+        assert_eq!(error.unwrap_err().code, error_codes::COMPONENT_ERROR);
+
+        world.track_component_changes::<Health>();
+        let mut schedule = Schedule::default();
+        schedule.add_systems(heal);
+        schedule.run(&mut world);
+
+        let value = process_remote_component_history_request(In(params(entity)), &world).unwrap();
+        let response: BrpComponentHistoryResponse = serde_json::from_value(value).unwrap();
+        assert_eq!(response.len(), 2);
+        assert!(response[0].added);
+        assert_eq!(response[0].system, None);
+        assert!(!response[1].added);
+        assert!(response[1].system.as_ref().unwrap().ends_with("heal"));
+
+        world.despawn(entity);
+        let error = process_remote_component_history_request(In(params(entity)), &world);
+        assert_eq!(error.unwrap_err().code, error_codes::ENTITY_NOT_FOUND);
+    }
+
     #[test]
     fn reflect_export_struct() {
         #[derive(Reflect, Resource, Default, Deserialize, Serialize)]
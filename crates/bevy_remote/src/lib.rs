@@ -274,6 +274,121 @@
 //! - `removed`: An array of fully-qualified type names of components removed from the entity
 //!   in the last tick.
 //!
+//! ### bevy/stepping/enable
+//!
+//! Enable [`Stepping`], pausing the stepped schedules at the start of the next frame. This, and
+//! every other `bevy/stepping` method, requires the `bevy_debug_stepping` feature, and returns an
+//! error without it.
+//!
+//! `params` (optional):
+//! - `schedule`: The name of a schedule to add to the stepped schedules, e.g. `Update`.
+//!
+//! `result`: null.
+//!
+//! ### bevy/stepping/disable
+//!
+//! Disable [`Stepping`], running all systems normally again from the next frame on.
+//!
+//! `params`: None.
+//!
+//! `result`: null.
+//!
+//! ### `bevy/stepping/step_system`
+//!
+//! Run only the next system in the stepped schedules during the next frame, then stop again.
+//! To run the rest of the frame, use `bevy/stepping/continue`.
+//!
+//! `params`: None.
+//!
+//! `result`: null.
+//!
+//! ### bevy/stepping/continue
+//!
+//! Run the remaining systems of the stepped schedules during the next frame, stopping at the
+//! next breakpoint or the end of the frame.
+//!
+//! `params`: None.
+//!
+//! `result`: null.
+//!
+//! ### `bevy/stepping/set_breakpoint`
+//!
+//! Set a breakpoint on a system, so that `bevy/stepping/continue` stops before it.
+//!
+//! `params`:
+//! - `schedule`: The name of the schedule containing the system, e.g. `Update`.
+//! - `system`: The full name of the system, or the last segment of its name. All systems in the
+//!   schedule with that name are affected.
+//!
+//! `result`: null.
+//!
+//! ### `bevy/stepping/clear_breakpoint`
+//!
+//! Clear a breakpoint set with `bevy/stepping/set_breakpoint`.
+//!
+//! `params`:
+//! - `schedule`: The name of the schedule containing the system, e.g. `Update`.
+//! - `system`: The full name of the system, or the last segment of its name.
+//!
+//! `result`: null.
+//!
+//! ### bevy/stepping/cursor
+//!
+//! Report the position of the [`Stepping`] cursor, i.e. the next system to run.
+//!
+//! `params`: None.
+//!
+//! `result`:
+//! - `enabled`: Whether stepping is enabled.
+//! - `schedule`: The name of the schedule containing the next system to run, or null.
+//! - `system`: The name of the next system to run, or null.
+//!
+//! [`Stepping`]: bevy_ecs::schedule::Stepping
+//!
+//! ### bevy/storage
+//!
+//! Report how entities are stored in archetypes, tables and sparse sets, and how much memory their
+//! components use. See [`World::storage_report`] for details.
+//!
+//! `params`: None.
+//!
+//! `result`:
+//! - `archetypes`: An array of objects, one per archetype, with the fields:
+//!   - `id`: The index of the archetype.
+//!   - `table`: The index of the table storing the archetype's table components.
+//!   - `entity_count`: The number of entities in the archetype.
+//!   - `components`: The fully-qualified type names of the archetype's components.
+//! - `tables`: An array of objects, one per table, with the fields:
+//!   - `id`: The index of the table.
+//!   - `entity_count`: The number of entities in the table.
+//!   - `capacity`: The number of entities the table can store without reallocating.
+//!   - `archetype_count`: The number of archetypes sharing the table.
+//!   - `columns`: An array of objects with the fields `component`, `item_size`, `bytes` and
+//!     `allocated_bytes`.
+//! - `sparse_sets`: An array of objects, one per sparse set component, with the fields
+//!   `component`, `entity_count`, `item_size` and `bytes`.
+//! - `entity_moves`: The number of times an entity has moved between archetypes. Comparing this
+//!   between requests measures archetype churn.
+//!
+//! [`World::storage_report`]: bevy_ecs::world::World::storage_report
+//!
+//! ### `bevy/component_history`
+//!
+//! Retrieve the last recorded changes of a component of an entity. Only components whose changes
+//! are tracked with [`World::track_component_changes`] have a history.
+//!
+//! `params`:
+//! - `entity`: The ID of the entity.
+//! - `component`: The [fully-qualified type name] of the component.
+//!
+//! `result`: An array of objects, from the oldest change to the newest, with the fields:
+//! - `tick`: The change tick at which the component was changed.
+//! - `added`: Whether the component was inserted rather than mutated in place.
+//! - `system`: The name of the system that made the change, or null.
+//! - `caller`: The source location of the change, or null if the `track_location` feature is
+//!   disabled.
+//!
+//! [`World::track_component_changes`]: bevy_ecs::world::World::track_component_changes
 //!
 //! ## Custom methods
 //!
@@ -440,6 +555,42 @@ impl Default for RemotePlugin {
                 builtin_methods::BRP_LIST_AND_WATCH_METHOD,
                 builtin_methods::process_remote_list_watching_request,
             )
+            .with_method(
+                builtin_methods::BRP_STEPPING_ENABLE_METHOD,
+                builtin_methods::process_remote_stepping_enable_request,
+            )
+            .with_method(
+                builtin_methods::BRP_STEPPING_DISABLE_METHOD,
+                builtin_methods::process_remote_stepping_disable_request,
+            )
+            .with_method(
+                builtin_methods::BRP_STEPPING_STEP_SYSTEM_METHOD,
+                builtin_methods::process_remote_stepping_step_system_request,
+            )
+            .with_method(
+                builtin_methods::BRP_STEPPING_CONTINUE_METHOD,
+                builtin_methods::process_remote_stepping_continue_request,
+            )
+            .with_method(
+                builtin_methods::BRP_STEPPING_SET_BREAKPOINT_METHOD,
+                builtin_methods::process_remote_stepping_set_breakpoint_request,
+            )
+            .with_method(
+                builtin_methods::BRP_STEPPING_CLEAR_BREAKPOINT_METHOD,
+                builtin_methods::process_remote_stepping_clear_breakpoint_request,
+            )
+            .with_method(
+                builtin_methods::BRP_STEPPING_CURSOR_METHOD,
+                builtin_methods::process_remote_stepping_cursor_request,
+            )
+            .with_method(
+                builtin_methods::BRP_STORAGE_METHOD,
+                builtin_methods::process_remote_storage_request,
+            )
+            .with_method(
+                builtin_methods::BRP_COMPONENT_HISTORY_METHOD,
+                builtin_methods::process_remote_component_history_request,
+            )
     }
 }
 
@@ -737,6 +888,26 @@ impl BrpError {
             data: None,
         }
     }
+
+    /// Schedule wasn't found.
+    #[must_use]
+    pub fn schedule_not_found(schedule: &str) -> Self {
+        Self {
+            code: error_codes::SCHEDULE_NOT_FOUND,
+            message: format!("Schedule `{schedule}` not found"),
+            data: None,
+        }
+    }
+
+    /// System wasn't found in a schedule.
+    #[must_use]
+    pub fn system_not_found(system: &str, schedule: &str) -> Self {
+        Self {
+            code: error_codes::SYSTEM_NOT_FOUND,
+            message: format!("System `{system}` not found in Schedule `{schedule}`"),
+            data: None,
+        }
+    }
 }
 
 /// Error codes used by BRP.
@@ -772,6 +943,12 @@ pub mod error_codes {
 
     /// Cannot reparent an entity to itself.
     pub const SELF_REPARENT: i16 = -23404;
+
+    /// Schedule not found.
+    pub const SCHEDULE_NOT_FOUND: i16 = -23405;
+
+    /// Could not find system in schedule.
+    pub const SYSTEM_NOT_FOUND: i16 = -23406;
 }
 
 /// The result of a request.
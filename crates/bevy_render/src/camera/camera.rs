@@ -5,11 +5,13 @@
 use super::{ClearColorConfig, Projection};
 use crate::{
     batching::gpu_preprocessing::{GpuPreprocessingMode, GpuPreprocessingSupport},
-    camera::{CameraProjection, ManualTextureViewHandle, ManualTextureViews},
+    camera::{CameraProjection, ManualTextureViewHandle, ManualTextureViews, Multiview},
     primitives::Frustum,
     render_asset::RenderAssets,
     render_graph::{InternedRenderSubGraph, RenderSubGraph},
     render_resource::TextureView,
+    renderer::RenderDevice,
+    settings::WgpuFeatures,
     sync_world::{RenderEntity, SyncToRenderWorld},
     texture::GpuImage,
     view::{
@@ -38,6 +40,7 @@ use bevy_platform_support::collections::{HashMap, HashSet};
 use bevy_reflect::prelude::*;
 use bevy_render_macros::ExtractComponent;
 use bevy_transform::components::{GlobalTransform, Transform};
+use bevy_utils::once;
 use bevy_window::{
     NormalizedWindowRef, PrimaryWindow, Window, WindowCreated, WindowRef, WindowResized,
     WindowScaleFactorChanged,
@@ -1059,10 +1062,12 @@ pub fn extract_cameras(
             Option<&RenderLayers>,
             Option<&Projection>,
             Has<NoIndirectDrawing>,
+            Option<&Multiview>,
         )>,
     >,
     primary_window: Extract<Query<Entity, With<PrimaryWindow>>>,
     gpu_preprocessing_support: Res<GpuPreprocessingSupport>,
+    render_device: Res<RenderDevice>,
     mapper: Extract<Query<&RenderEntity>>,
 ) {
     let primary_window = primary_window.iter().next();
@@ -1080,6 +1085,7 @@ pub fn extract_cameras(
         render_layers,
         projection,
         no_indirect_drawing,
+        multiview,
     ) in query.iter()
     {
         if !camera.is_active {
@@ -1092,6 +1098,7 @@ pub fn extract_cameras(
                 Projection,
                 NoIndirectDrawing,
                 ViewUniformOffset,
+                Multiview,
             )>();
             continue;
         }
@@ -1135,10 +1142,30 @@ pub fn extract_cameras(
                     .collect(),
             };
 
+            let target = camera.target.normalize(primary_window);
+
+            let multiview = multiview.filter(|_| {
+                if !render_device.features().contains(WgpuFeatures::MULTIVIEW) {
+                    once!(warn!(
+                        "Multiview cameras are not supported by this device and are rendered \
+                        as regular cameras."
+                    ));
+                    return false;
+                }
+                if matches!(target, Some(NormalizedRenderTarget::Window(_))) {
+                    once!(warn!(
+                        "Multiview cameras can't render to a window and are rendered as \
+                        regular cameras."
+                    ));
+                    return false;
+                }
+                true
+            });
+
             let mut commands = commands.entity(render_entity);
             commands.insert((
                 ExtractedCamera {
-                    target: camera.target.normalize(primary_window),
+                    target,
                     viewport: camera.viewport.clone(),
                     physical_viewport_size: Some(viewport_size),
                     physical_target_size: Some(target_size),
@@ -1184,6 +1211,12 @@ pub fn extract_cameras(
                 commands.insert(perspective.clone());
             }
 
+            if let Some(multiview) = multiview {
+                commands.insert(multiview.clone());
+            } else {
+                commands.remove::<Multiview>();
+            }
+
             if no_indirect_drawing
                 || !matches!(
                     gpu_preprocessing_support.max_supported_mode,
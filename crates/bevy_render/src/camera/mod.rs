@@ -2,19 +2,24 @@ mod camera;
 mod camera_driver_node;
 mod clear_color;
 mod manual_texture_view;
+mod multiview;
 mod projection;
 
 pub use camera::*;
 pub use camera_driver_node::*;
 pub use clear_color::*;
 pub use manual_texture_view::*;
+pub use multiview::*;
 pub use projection::*;
 
 use crate::{
-    extract_component::ExtractComponentPlugin, extract_resource::ExtractResourcePlugin,
-    render_graph::RenderGraph, ExtractSchedule, Render, RenderApp, RenderSet,
+    extract_component::ExtractComponentPlugin,
+    extract_resource::ExtractResourcePlugin,
+    render_graph::RenderGraph,
+    view::{update_frusta, VisibilitySystems},
+    ExtractSchedule, Render, RenderApp, RenderSet,
 };
-use bevy_app::{App, Plugin};
+use bevy_app::{App, Plugin, PostUpdate};
 use bevy_ecs::schedule::IntoSystemConfigs;
 
 #[derive(Default)]
@@ -29,6 +34,7 @@ impl Plugin for CameraPlugin {
             .register_type::<Exposure>()
             .register_type::<TemporalJitter>()
             .register_type::<MipBias>()
+            .register_type::<Multiview>()
             .init_resource::<ManualTextureViews>()
             .init_resource::<ClearColor>()
             .add_plugins((
@@ -36,7 +42,13 @@ impl Plugin for CameraPlugin {
                 ExtractResourcePlugin::<ManualTextureViews>::default(),
                 ExtractResourcePlugin::<ClearColor>::default(),
                 ExtractComponentPlugin::<CameraMainTextureUsages>::default(),
-            ));
+            ))
+            .add_systems(
+                PostUpdate,
+                update_multiview_frusta
+                    .in_set(VisibilitySystems::UpdateFrusta)
+                    .after(update_frusta),
+            );
 
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app
@@ -0,0 +1,195 @@
+use super::{CameraProjection, Projection};
+use crate::primitives::Frustum;
+use bevy_ecs::{
+    component::Component,
+    query::{Changed, Or},
+    reflect::ReflectComponent,
+    system::Query,
+};
+use bevy_math::{FloatOrd, Mat4, Vec3, Vec3A};
+use bevy_reflect::prelude::*;
+use bevy_transform::components::{GlobalTransform, Transform};
+use core::array;
+
+/// Renders a 3D camera from two eyes at once, to the two layers of a texture array, as stereo
+/// displays such as XR headsets expect.
+///
+/// Each render pass of the camera draws both eyes with a single set of draw calls, using
+/// multiview, which requires [`WgpuFeatures::MULTIVIEW`]. The component is ignored, with a
+/// warning, on devices without it.
+///
+/// The [`RenderTarget`](super::RenderTarget) of the camera must be an
+/// [`Image`](bevy_image::Image) or a [`ManualTextureView`](super::ManualTextureView) with two
+/// array layers, as windows can't be rendered to with multiview. The left eye is rendered to the
+/// first layer and the right eye to the second.
+///
+/// Visibility is only checked once for both eyes, against a [`Frustum`] enclosing the frusta of
+/// both of them. For the same reason, `bevy_pbr` clusters the lights of multiview cameras into a
+/// single cluster unless the camera has its own `ClusterConfig`.
+///
+/// Meshes, gizmos, the skybox, the depth and normal prepasses, tonemapping and the final blit to
+/// the target are rendered per eye. Features that read the textures of the view in a separate pass
+/// aren't supported yet: deferred rendering, motion vectors, transmission, occlusion culling and
+/// screen space effects. Post-processing passes other than tonemapping, such as bloom, depth of
+/// field, anti-aliasing and outlines, are skipped with a warning.
+///
+/// Shaders of multiview pipelines are compiled with the `MULTIVIEW` shader def, and `view` is only
+/// valid once the entry point has selected the view of the eye being rendered. Custom material
+/// shaders that read `view` must do the same, with the `view_index` of the mesh vertex input and
+/// output:
+///
+/// ```wgsl
+/// #ifdef MULTIVIEW
+///     bevy_pbr::mesh_view_bindings::select_view(mesh.view_index);
+/// #endif
+/// ```
+///
+/// [`WgpuFeatures::MULTIVIEW`]: crate::settings::WgpuFeatures::MULTIVIEW
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default, Debug)]
+pub struct Multiview {
+    /// The left and the right eye, in that order.
+    pub eyes: [MultiviewEye; 2],
+}
+
+impl Multiview {
+    /// The number of views of a multiview camera, and of array layers of its target.
+    pub const VIEW_COUNT: u32 = 2;
+
+    /// Creates a [`Multiview`] with eyes `interpupillary_distance` apart along the X axis of the
+    /// camera, sharing its projection.
+    pub fn from_interpupillary_distance(interpupillary_distance: f32) -> Self {
+        let eye = |x| MultiviewEye {
+            transform: Transform::from_xyz(x, 0.0, 0.0),
+            clip_from_view: None,
+        };
+
+        Self {
+            eyes: [
+                eye(-interpupillary_distance * 0.5),
+                eye(interpupillary_distance * 0.5),
+            ],
+        }
+    }
+}
+
+impl Default for Multiview {
+    /// Eyes 64 millimeters apart, about the average interpupillary distance of adults.
+    fn default() -> Self {
+        Self::from_interpupillary_distance(0.064)
+    }
+}
+
+/// One eye of a [`Multiview`] camera.
+#[derive(Clone, Debug, Default, Reflect)]
+#[reflect(Default, Debug)]
+pub struct MultiviewEye {
+    /// The transform of the eye, relative to the camera.
+    pub transform: Transform,
+    /// The projection of the eye, or [`None`] to use the [`Projection`] of the camera.
+    ///
+    /// XR runtimes usually report a separate, asymmetric projection for each eye.
+    pub clip_from_view: Option<Mat4>,
+}
+
+impl MultiviewEye {
+    /// The world transform of the eye of a camera at `camera_transform`.
+    pub fn world_from_view(&self, camera_transform: &GlobalTransform) -> GlobalTransform {
+        camera_transform.mul_transform(self.transform)
+    }
+
+    /// The frustum of the eye of a camera at `camera_transform`.
+    pub fn compute_frustum(
+        &self,
+        camera_transform: &GlobalTransform,
+        projection: &Projection,
+    ) -> Frustum {
+        let world_from_view = self.world_from_view(camera_transform);
+        match self.clip_from_view {
+            Some(clip_from_view) => Frustum::from_clip_from_world_custom_far(
+                &(clip_from_view * world_from_view.compute_matrix().inverse()),
+                &world_from_view.translation(),
+                &world_from_view.back(),
+                projection.far(),
+            ),
+            None => projection.compute_frustum(&world_from_view),
+        }
+    }
+}
+
+/// Replaces the [`Frustum`] of [`Multiview`] cameras with one that encloses the frusta of both
+/// eyes.
+///
+/// This system runs after [`update_frusta`](crate::view::update_frusta) in
+/// [`VisibilitySystems::UpdateFrusta`](crate::view::VisibilitySystems::UpdateFrusta).
+pub fn update_multiview_frusta(
+    mut views: Query<
+        (&GlobalTransform, &Projection, &Multiview, &mut Frustum),
+        Or<(
+            Changed<GlobalTransform>,
+            Changed<Projection>,
+            Changed<Multiview>,
+        )>,
+    >,
+) {
+    for (transform, projection, multiview, mut frustum) in &mut views {
+        let eye_frusta = multiview
+            .eyes
+            .each_ref()
+            .map(|eye| eye.compute_frustum(transform, projection));
+        *frustum = enclosing_frustum(&eye_frusta, transform.translation());
+    }
+}
+
+/// Picks, for each side, the half space of the frustum that contains `origin` the deepest.
+///
+/// For frusta that face the same way, such as the eyes of a stereo camera, the half spaces of a
+/// side are parallel, so this is the outermost one.
+fn enclosing_frustum(frusta: &[Frustum], origin: Vec3) -> Frustum {
+    let origin = Vec3A::from(origin).extend(1.0);
+    Frustum {
+        half_spaces: array::from_fn(|side| {
+            frusta
+                .iter()
+                .map(|frustum| frustum.half_spaces[side])
+                .max_by_key(|half_space| FloatOrd(half_space.normal_d().dot(origin)))
+                .unwrap_or_default()
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::camera::PerspectiveProjection;
+
+    #[test]
+    fn enclosing_frustum_contains_both_eyes() {
+        let projection = Projection::Perspective(PerspectiveProjection::default());
+        let camera_transform = GlobalTransform::default();
+        let multiview = Multiview::from_interpupillary_distance(1.0);
+
+        let eye_frusta = multiview
+            .eyes
+            .each_ref()
+            .map(|eye| eye.compute_frustum(&camera_transform, &projection));
+        let frustum = enclosing_frustum(&eye_frusta, camera_transform.translation());
+
+        let contains = |frustum: &Frustum, point: Vec3A| {
+            frustum
+                .half_spaces
+                .iter()
+                .all(|half_space| half_space.normal_d().dot(point.extend(1.0)) > 0.0)
+        };
+
+        // Points at the outer edges of the view of each eye, which the other eye can't see.
+        let left = Vec3A::new(-1.2, 0.0, -2.0);
+        let right = Vec3A::new(1.2, 0.0, -2.0);
+        assert!(contains(&eye_frusta[0], left) && !contains(&eye_frusta[1], left));
+        assert!(contains(&eye_frusta[1], right) && !contains(&eye_frusta[0], right));
+        assert!(contains(&frustum, left) && contains(&frustum, right));
+
+        // A point that neither eye can see.
+        assert!(!contains(&frustum, Vec3A::new(0.0, 0.0, 2.0)));
+    }
+}
@@ -7,7 +7,7 @@ use crate::{
 };
 use alloc::borrow::Cow;
 use bevy_asset::Handle;
-use core::ops::Deref;
+use core::{num::NonZeroU32, ops::Deref};
 use wgpu::{
     ColorTargetState, DepthStencilState, MultisampleState, PrimitiveState, PushConstantRange,
 };
@@ -110,6 +110,11 @@ pub struct RenderPipelineDescriptor {
     /// Whether to zero-initialize workgroup memory by default. If you're not sure, set this to true.
     /// If this is false, reading from workgroup variables before writing to them will result in garbage values.
     pub zero_initialize_workgroup_memory: bool,
+    /// The number of array layers the render attachments of the pipeline have, if the pipeline
+    /// renders to every layer at once with multiview. Requires [`WgpuFeatures::MULTIVIEW`].
+    ///
+    /// [`WgpuFeatures::MULTIVIEW`]: crate::settings::WgpuFeatures::MULTIVIEW
+    pub multiview: Option<NonZeroU32>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -70,6 +70,9 @@ impl Plugin for ImagePlugin {
             app.init_asset_loader::<HdrTextureLoader>();
         }
 
+        app.init_asset_loader::<bevy_image::CubeLutLoader>()
+            .init_asset_loader::<bevy_image::EnvironmentMapLoader>();
+
         app.add_plugins(RenderAssetPlugin::<GpuImage>::default())
             .register_type::<Image>()
             .init_asset::<Image>()
@@ -97,6 +100,33 @@ impl Plugin for ImagePlugin {
             >>("png");
         }
 
+        // Converts equirectangular panoramas into environment maps, for assets whose meta file
+        // selects it.
+        #[cfg(any(feature = "hdr", feature = "exr"))]
+        if let Some(processor) = app
+            .world()
+            .get_resource::<bevy_asset::processor::AssetProcessor>()
+        {
+            #[cfg(feature = "hdr")]
+            processor.register_processor::<bevy_asset::processor::LoadTransformAndSave<
+                HdrTextureLoader,
+                bevy_image::EquirectangularToCubemapTransformer,
+                bevy_image::EnvironmentMapSaver,
+            >>(bevy_asset::processor::LoadTransformAndSave::new(
+                bevy_image::EquirectangularToCubemapTransformer,
+                bevy_image::EnvironmentMapSaver,
+            ));
+            #[cfg(feature = "exr")]
+            processor.register_processor::<bevy_asset::processor::LoadTransformAndSave<
+                bevy_image::ExrTextureLoader,
+                bevy_image::EquirectangularToCubemapTransformer,
+                bevy_image::EnvironmentMapSaver,
+            >>(bevy_asset::processor::LoadTransformAndSave::new(
+                bevy_image::EquirectangularToCubemapTransformer,
+                bevy_image::EnvironmentMapSaver,
+            ));
+        }
+
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app.init_resource::<TextureCache>().add_systems(
                 Render,
@@ -8,7 +8,7 @@ pub use window::*;
 use crate::{
     camera::{
         CameraMainTextureUsages, ClearColor, ClearColorConfig, Exposure, ExtractedCamera,
-        ManualTextureViews, MipBias, NormalizedRenderTarget, TemporalJitter,
+        ManualTextureViews, MipBias, Multiview, NormalizedRenderTarget, TemporalJitter,
     },
     experimental::occlusion_culling::OcclusionCulling,
     extract_component::ExtractComponentPlugin,
@@ -146,6 +146,7 @@ impl Plugin for ViewPlugin {
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app
                 .init_resource::<ViewUniforms>()
+                .init_resource::<MultiviewUniforms>()
                 .init_resource::<ViewTargetAttachments>();
         }
     }
@@ -594,6 +595,30 @@ pub struct ViewUniformOffset {
     pub offset: u32,
 }
 
+/// The [`ViewUniform`]s of the eyes of a [`Multiview`] view, indexed by view index.
+#[derive(Clone, ShaderType)]
+pub struct MultiviewUniform {
+    pub views: [ViewUniform; Multiview::VIEW_COUNT as usize],
+}
+
+#[derive(Resource)]
+pub struct MultiviewUniforms {
+    pub uniforms: DynamicUniformBuffer<MultiviewUniform>,
+}
+
+impl Default for MultiviewUniforms {
+    fn default() -> Self {
+        let mut uniforms = DynamicUniformBuffer::default();
+        uniforms.set_label(Some("multiview_uniforms_buffer"));
+        Self { uniforms }
+    }
+}
+
+#[derive(Component)]
+pub struct MultiviewUniformOffset {
+    pub offset: u32,
+}
+
 #[derive(Component)]
 pub struct ViewTarget {
     main_textures: MainTargetTextures,
@@ -881,6 +906,7 @@ pub fn prepare_view_uniforms(
     render_device: Res<RenderDevice>,
     render_queue: Res<RenderQueue>,
     mut view_uniforms: ResMut<ViewUniforms>,
+    mut multiview_uniforms: ResMut<MultiviewUniforms>,
     views: Query<(
         Entity,
         Option<&ExtractedCamera>,
@@ -888,6 +914,7 @@ pub fn prepare_view_uniforms(
         Option<&Frustum>,
         Option<&TemporalJitter>,
         Option<&MipBias>,
+        Option<&Multiview>,
     )>,
 ) {
     let view_iter = views.iter();
@@ -899,42 +926,52 @@ pub fn prepare_view_uniforms(
     else {
         return;
     };
-    for (entity, extracted_camera, extracted_view, frustum, temporal_jitter, mip_bias) in &views {
+    let multiview_count = views
+        .iter()
+        .filter(|(.., multiview)| multiview.is_some())
+        .count();
+    let mut multiview_writer =
+        multiview_uniforms
+            .uniforms
+            .get_writer(multiview_count, &render_device, &render_queue);
+    for (entity, extracted_camera, extracted_view, frustum, temporal_jitter, mip_bias, multiview) in
+        &views
+    {
         let viewport = extracted_view.viewport.as_vec4();
-        let unjittered_projection = extracted_view.clip_from_view;
-        let mut clip_from_view = unjittered_projection;
-
-        if let Some(temporal_jitter) = temporal_jitter {
-            temporal_jitter.jitter_projection(&mut clip_from_view, viewport.zw());
-        }
-
-        let view_from_clip = clip_from_view.inverse();
-        let world_from_view = extracted_view.world_from_view.compute_matrix();
-        let view_from_world = world_from_view.inverse();
-
-        let clip_from_world = if temporal_jitter.is_some() {
-            clip_from_view * view_from_world
-        } else {
-            extracted_view
-                .clip_from_world
-                .unwrap_or_else(|| clip_from_view * view_from_world)
-        };
 
         // Map Frustum type to shader array<vec4<f32>, 6>
         let frustum = frustum
             .map(|frustum| frustum.half_spaces.map(|h| h.normal_d()))
             .unwrap_or([Vec4::ZERO; 6]);
 
-        let view_uniforms = ViewUniformOffset {
-            offset: writer.write(&ViewUniform {
+        let view_uniform = |world_from_view: &GlobalTransform,
+                            unjittered_projection: Mat4,
+                            clip_from_world: Option<Mat4>| {
+            let mut clip_from_view = unjittered_projection;
+
+            if let Some(temporal_jitter) = temporal_jitter {
+                temporal_jitter.jitter_projection(&mut clip_from_view, viewport.zw());
+            }
+
+            let view_from_clip = clip_from_view.inverse();
+            let world_from_view_matrix = world_from_view.compute_matrix();
+            let view_from_world = world_from_view_matrix.inverse();
+
+            let clip_from_world = if temporal_jitter.is_some() {
+                clip_from_view * view_from_world
+            } else {
+                clip_from_world.unwrap_or_else(|| clip_from_view * view_from_world)
+            };
+
+            ViewUniform {
                 clip_from_world,
                 unjittered_clip_from_world: unjittered_projection * view_from_world,
-                world_from_clip: world_from_view * view_from_clip,
-                world_from_view,
+                world_from_clip: world_from_view_matrix * view_from_clip,
+                world_from_view: world_from_view_matrix,
                 view_from_world,
                 clip_from_view,
                 view_from_clip,
-                world_position: extracted_view.world_from_view.translation(),
+                world_position: world_from_view.translation(),
                 exposure: extracted_camera
                     .map(|c| c.exposure)
                     .unwrap_or_else(|| Exposure::default().exposure()),
@@ -942,10 +979,35 @@ pub fn prepare_view_uniforms(
                 frustum,
                 color_grading: extracted_view.color_grading.clone().into(),
                 mip_bias: mip_bias.unwrap_or(&MipBias(0.0)).0,
-            }),
+            }
+        };
+
+        let view_uniforms = ViewUniformOffset {
+            offset: writer.write(&view_uniform(
+                &extracted_view.world_from_view,
+                extracted_view.clip_from_view,
+                extracted_view.clip_from_world,
+            )),
         };
 
         commands.entity(entity).insert(view_uniforms);
+
+        // Each eye shares the frustum of the view, which encloses both of them.
+        if let (Some(multiview), Some(multiview_writer)) = (multiview, multiview_writer.as_mut()) {
+            let views = multiview.eyes.each_ref().map(|eye| {
+                view_uniform(
+                    &eye.world_from_view(&extracted_view.world_from_view),
+                    eye.clip_from_view.unwrap_or(extracted_view.clip_from_view),
+                    None,
+                )
+            });
+
+            commands.entity(entity).insert(MultiviewUniformOffset {
+                offset: multiview_writer.write(&MultiviewUniform { views }),
+            });
+        } else {
+            commands.entity(entity).remove::<MultiviewUniformOffset>();
+        }
     }
 }
 
@@ -1006,11 +1068,12 @@ pub fn prepare_view_targets(
         &ExtractedView,
         &CameraMainTextureUsages,
         &Msaa,
+        Has<Multiview>,
     )>,
     view_target_attachments: Res<ViewTargetAttachments>,
 ) {
     let mut textures = <HashMap<_, _>>::default();
-    for (entity, camera, view, texture_usage, msaa) in cameras.iter() {
+    for (entity, camera, view, texture_usage, msaa, multiview) in cameras.iter() {
         let (Some(target_size), Some(target)) = (camera.physical_target_size, &camera.target)
         else {
             continue;
@@ -1020,10 +1083,11 @@ pub fn prepare_view_targets(
             continue;
         };
 
+        // Multiview cameras render each eye to its own layer.
         let size = Extent3d {
             width: target_size.x,
             height: target_size.y,
-            depth_or_array_layers: 1,
+            depth_or_array_layers: if multiview { Multiview::VIEW_COUNT } else { 1 },
         };
 
         let main_texture_format = if view.hdr {
@@ -1039,7 +1103,7 @@ pub fn prepare_view_targets(
         };
 
         let (a, b, sampled, main_texture) = textures
-            .entry((camera.target.clone(), view.hdr, msaa))
+            .entry((camera.target.clone(), view.hdr, msaa, multiview))
             .or_insert_with(|| {
                 let descriptor = TextureDescriptor {
                     label: None,
@@ -61,3 +61,8 @@ struct View {
     color_grading: ColorGrading,
     mip_bias: f32,
 };
+
+// The views of the eyes of a multiview view, indexed by view index.
+struct Multiview {
+    views: array<View, 2>,
+};
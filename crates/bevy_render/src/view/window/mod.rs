@@ -6,6 +6,7 @@ use crate::{
 use bevy_app::{App, Plugin};
 use bevy_ecs::{entity::hash_map::EntityHashMap, prelude::*};
 use bevy_platform_support::collections::HashSet;
+use bevy_reflect::{std_traits::ReflectDefault, Reflect};
 use bevy_utils::default;
 use bevy_window::{
     CompositeAlphaMode, PresentMode, PrimaryWindow, RawHandleWrapper, Window, WindowClosing,
@@ -27,7 +28,8 @@ pub struct WindowRenderPlugin;
 
 impl Plugin for WindowRenderPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(ScreenshotPlugin);
+        app.register_type::<HdrOutput>()
+            .add_plugins(ScreenshotPlugin);
 
         if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
             render_app
@@ -68,6 +70,12 @@ pub struct ExtractedWindow {
     pub size_changed: bool,
     pub present_mode_changed: bool,
     pub alpha_mode: CompositeAlphaMode,
+    /// The HDR output requested for this window, if any.
+    ///
+    /// The window is only presented in HDR if its surface supports the
+    /// requested encoding; see [`ExtractedWindow::hdr_encoding`].
+    pub hdr_output: Option<HdrOutput>,
+    pub hdr_output_changed: bool,
 }
 
 impl ExtractedWindow {
@@ -81,6 +89,101 @@ impl ExtractedWindow {
         ));
         self.swap_chain_texture = Some(SurfaceTexture::from(frame));
     }
+
+    /// Returns the encoding of the swap chain texture if the window is
+    /// presented in HDR, or `None` if it is presented in SDR.
+    pub fn hdr_encoding(&self) -> Option<HdrEncoding> {
+        self.hdr_output?;
+        HdrEncoding::from_texture_format(self.swap_chain_texture_format?)
+    }
+}
+
+/// Add this component to a [`Window`] to present it on HDR displays with
+/// highlights brighter than SDR white.
+///
+/// The window surface is configured with a format of the requested
+/// [`HdrEncoding`], or of the other one if the surface doesn't support it. If
+/// the surface supports neither, for example because the display or platform
+/// has no HDR support, the window falls back to SDR.
+///
+/// Cameras that render to the window map their output to the display after
+/// tonemapping. A value of 1.0 is shown at [`HdrOutput::paper_white`], so SDR
+/// content, including UI and text, is as bright as a white page, and brighter
+/// values roll off smoothly towards [`HdrOutput::peak_luminance`]. Only
+/// cameras with [`Camera::hdr`](crate::camera::Camera::hdr) enabled and
+/// tonemapping disabled keep values above 1.0; the tonemapping methods all
+/// limit the scene to paper white.
+///
+/// # Color spaces
+///
+/// Cameras are assumed to output linear Rec. 709 (sRGB primaries). For
+/// [`HdrEncoding::Pq`], colors are converted to Rec. 2020 primaries and
+/// encoded with the SMPTE ST 2084 transfer function, as in HDR10. For
+/// [`HdrEncoding::ScRgb`], colors keep their Rec. 709 primaries and stay
+/// linear, with 1.0 at 80 nits.
+///
+/// wgpu can't choose the color space of a surface, so the swap chain is
+/// assumed to be in the color space of the encoding that its format belongs
+/// to. A platform that presents the format in another color space, such as
+/// SDR sRGB, shows washed out or overly bright colors. In that case, request
+/// the other encoding, or remove this component.
+#[derive(Component, Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(Component, Default, Debug, PartialEq)]
+pub struct HdrOutput {
+    /// The preferred encoding of the swap chain.
+    pub encoding: HdrEncoding,
+    /// The luminance, in nits, that a value of 1.0 is shown at.
+    ///
+    /// Defaults to 203 nits, the reference white of ITU-R BT.2408.
+    pub paper_white: f32,
+    /// The luminance, in nits, of the brightest highlights.
+    ///
+    /// This should match the peak brightness of the display. Defaults to 1000
+    /// nits.
+    pub peak_luminance: f32,
+}
+
+impl Default for HdrOutput {
+    fn default() -> Self {
+        Self {
+            encoding: HdrEncoding::default(),
+            paper_white: 203.0,
+            peak_luminance: 1000.0,
+        }
+    }
+}
+
+/// How colors are encoded in an HDR swap chain.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Reflect)]
+#[reflect(Default, Debug, PartialEq, Hash)]
+pub enum HdrEncoding {
+    /// HDR10: Rec. 2020 primaries with the SMPTE ST 2084 perceptual quantizer
+    /// transfer function, in an [`TextureFormat::Rgb10a2Unorm`] swap chain.
+    #[default]
+    Pq,
+    /// Linear Rec. 709 primaries where 1.0 is 80 nits, in an
+    /// [`TextureFormat::Rgba16Float`] swap chain.
+    ScRgb,
+}
+
+impl HdrEncoding {
+    /// Returns the swap chain format of this encoding.
+    pub fn texture_format(self) -> TextureFormat {
+        match self {
+            HdrEncoding::Pq => TextureFormat::Rgb10a2Unorm,
+            HdrEncoding::ScRgb => TextureFormat::Rgba16Float,
+        }
+    }
+
+    /// Returns the encoding of a swap chain with the given format, if it is
+    /// an HDR format.
+    pub fn from_texture_format(format: TextureFormat) -> Option<Self> {
+        match format {
+            TextureFormat::Rgb10a2Unorm => Some(HdrEncoding::Pq),
+            TextureFormat::Rgba16Float => Some(HdrEncoding::ScRgb),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Default, Resource)]
@@ -106,11 +209,19 @@ impl DerefMut for ExtractedWindows {
 fn extract_windows(
     mut extracted_windows: ResMut<ExtractedWindows>,
     mut closing: Extract<EventReader<WindowClosing>>,
-    windows: Extract<Query<(Entity, &Window, &RawHandleWrapper, Option<&PrimaryWindow>)>>,
+    windows: Extract<
+        Query<(
+            Entity,
+            &Window,
+            &RawHandleWrapper,
+            Option<&PrimaryWindow>,
+            Option<&HdrOutput>,
+        )>,
+    >,
     mut removed: Extract<RemovedComponents<RawHandleWrapper>>,
     mut window_surfaces: ResMut<WindowSurfaces>,
 ) {
-    for (entity, window, handle, primary) in windows.iter() {
+    for (entity, window, handle, primary, hdr_output) in windows.iter() {
         if primary.is_some() {
             extracted_windows.primary = Some(entity);
         }
@@ -133,6 +244,8 @@ fn extract_windows(
             swap_chain_texture_format: None,
             present_mode_changed: false,
             alpha_mode: window.composite_alpha_mode,
+            hdr_output: hdr_output.copied(),
+            hdr_output_changed: false,
         });
 
         // NOTE: Drop the swap chain frame here
@@ -141,6 +254,13 @@ fn extract_windows(
             || new_height != extracted_window.physical_height;
         extracted_window.present_mode_changed =
             window.present_mode != extracted_window.present_mode;
+        // Only the encoding affects the surface, the luminances are applied
+        // when mapping cameras to the display.
+        extracted_window.hdr_output_changed = hdr_output.map(|hdr_output| hdr_output.encoding)
+            != extracted_window
+                .hdr_output
+                .map(|hdr_output| hdr_output.encoding);
+        extracted_window.hdr_output = hdr_output.copied();
 
         if extracted_window.size_changed {
             debug!(
@@ -287,6 +407,7 @@ pub fn need_surface_configuration(
         if !window_surfaces.configured_windows.contains(&window.entity)
             || window.size_changed
             || window.present_mode_changed
+            || window.hdr_output_changed
         {
             return true;
         }
@@ -331,20 +452,7 @@ pub fn create_surfaces(
                         .expect("Failed to create wgpu surface")
                 };
                 let caps = surface.get_capabilities(&render_adapter);
-                let formats = caps.formats;
-                // For future HDR output support, we'll need to request a format that supports HDR,
-                // but as of wgpu 0.15 that is not yet supported.
-                // Prefer sRGB formats for surfaces, but fall back to first available format if no sRGB formats are available.
-                let mut format = *formats.first().expect("No supported formats for surface");
-                for available_format in formats {
-                    // Rgba8UnormSrgb and Bgra8UnormSrgb and the only sRGB formats wgpu exposes that we can use for surfaces.
-                    if available_format == TextureFormat::Rgba8UnormSrgb
-                        || available_format == TextureFormat::Bgra8UnormSrgb
-                    {
-                        format = available_format;
-                        break;
-                    }
-                }
+                let format = select_surface_format(&caps.formats, window.hdr_output.as_ref());
 
                 let configuration = SurfaceConfiguration {
                     format,
@@ -374,7 +482,7 @@ pub fn create_surfaces(
                         }
                         CompositeAlphaMode::Inherit => wgpu::CompositeAlphaMode::Inherit,
                     },
-                    view_formats: if !format.is_srgb() {
+                    view_formats: if format.add_srgb_suffix() != format {
                         vec![format.add_srgb_suffix()]
                     } else {
                         vec![]
@@ -389,6 +497,18 @@ pub fn create_surfaces(
                 }
             });
 
+        if window.hdr_output_changed {
+            let caps = data.surface.get_capabilities(&render_adapter);
+            let format = select_surface_format(&caps.formats, window.hdr_output.as_ref());
+            data.configuration.format = format;
+            data.configuration.view_formats = if format.add_srgb_suffix() != format {
+                vec![format.add_srgb_suffix()]
+            } else {
+                vec![]
+            };
+            render_device.configure_surface(&data.surface, &data.configuration);
+        }
+
         if window.size_changed || window.present_mode_changed {
             data.configuration.width = window.physical_width;
             data.configuration.height = window.physical_height;
@@ -406,3 +526,82 @@ pub fn create_surfaces(
         window_surfaces.configured_windows.insert(window.entity);
     }
 }
+
+/// Picks the format of a window surface from the formats that it supports.
+///
+/// With [`HdrOutput`], the format of the requested encoding is preferred,
+/// followed by that of the other encoding. Otherwise, and if the surface
+/// supports neither, sRGB formats are preferred, falling back to the first
+/// available format.
+fn select_surface_format(
+    formats: &[TextureFormat],
+    hdr_output: Option<&HdrOutput>,
+) -> TextureFormat {
+    if let Some(hdr_output) = hdr_output {
+        let fallback = match hdr_output.encoding {
+            HdrEncoding::Pq => HdrEncoding::ScRgb,
+            HdrEncoding::ScRgb => HdrEncoding::Pq,
+        };
+        for encoding in [hdr_output.encoding, fallback] {
+            if formats.contains(&encoding.texture_format()) {
+                return encoding.texture_format();
+            }
+        }
+        warn!("HDR output was requested, but the window surface doesn't support HDR formats");
+    }
+
+    // Rgba8UnormSrgb and Bgra8UnormSrgb and the only sRGB formats wgpu exposes that we can use for surfaces.
+    formats
+        .iter()
+        .copied()
+        .find(|format| {
+            *format == TextureFormat::Rgba8UnormSrgb || *format == TextureFormat::Bgra8UnormSrgb
+        })
+        .unwrap_or_else(|| *formats.first().expect("No supported formats for surface"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{select_surface_format, HdrEncoding, HdrOutput};
+    use wgpu::TextureFormat;
+
+    #[test]
+    fn surface_format_falls_back_from_hdr() {
+        let sdr = [TextureFormat::Bgra8Unorm, TextureFormat::Bgra8UnormSrgb];
+        let hdr = [
+            TextureFormat::Bgra8UnormSrgb,
+            TextureFormat::Rgba16Float,
+            TextureFormat::Rgb10a2Unorm,
+        ];
+        let pq = HdrOutput::default();
+        let scrgb = HdrOutput {
+            encoding: HdrEncoding::ScRgb,
+            ..HdrOutput::default()
+        };
+
+        assert_eq!(
+            select_surface_format(&sdr, None),
+            TextureFormat::Bgra8UnormSrgb
+        );
+        assert_eq!(
+            select_surface_format(&hdr, None),
+            TextureFormat::Bgra8UnormSrgb
+        );
+        assert_eq!(
+            select_surface_format(&sdr, Some(&pq)),
+            TextureFormat::Bgra8UnormSrgb
+        );
+        assert_eq!(
+            select_surface_format(&hdr, Some(&pq)),
+            TextureFormat::Rgb10a2Unorm
+        );
+        assert_eq!(
+            select_surface_format(&hdr, Some(&scrgb)),
+            TextureFormat::Rgba16Float
+        );
+        assert_eq!(
+            select_surface_format(&hdr[..2], Some(&pq)),
+            TextureFormat::Rgba16Float
+        );
+    }
+}
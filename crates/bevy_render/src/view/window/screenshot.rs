@@ -489,6 +489,7 @@ impl SpecializedRenderPipeline for ScreenshotToScreenPipeline {
             }),
             push_constant_ranges: Vec::new(),
             zero_initialize_workgroup_memory: false,
+            multiview: None,
         }
     }
 }
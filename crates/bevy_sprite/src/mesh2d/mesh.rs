@@ -734,6 +734,7 @@ impl SpecializedMeshPipeline for Mesh2dPipeline {
             },
             label: Some(label.into()),
             zero_initialize_workgroup_memory: false,
+            multiview: None,
         })
     }
 }
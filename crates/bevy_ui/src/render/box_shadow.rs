@@ -208,6 +208,7 @@ impl SpecializedRenderPipeline for BoxShadowPipeline {
             },
             label: Some("box_shadow_pipeline".into()),
             zero_initialize_workgroup_memory: false,
+            multiview: None,
         }
     }
 }
@@ -10,10 +10,15 @@
 //! | `C`                | Toggle Compensation Curve              |
 //! | `M`                | Toggle Metering Mask                   |
 //! | `V`                | Visualize Metering Mask                |
+//! | `E`                | Cycle Metering Mode                    |
+//! | `L`                | Toggle Exposure Lock                   |
 
 use bevy::{
     core_pipeline::{
-        auto_exposure::{AutoExposure, AutoExposureCompensationCurve, AutoExposurePlugin},
+        auto_exposure::{
+            AutoExposure, AutoExposureCompensationCurve, AutoExposureControl, AutoExposureMetering,
+            AutoExposurePlugin, AutoExposureReadback,
+        },
         Skybox,
     },
     math::{cubic_splines::LinearSpline, primitives::Plane3d, vec2},
@@ -49,6 +54,8 @@ fn setup(
             metering_mask: metering_mask.clone(),
             ..default()
         },
+        // Reads the metering results back, to display them.
+        AutoExposureReadback::default(),
         Skybox {
             image: asset_server.load("environment_maps/pisa_specular_rgb9e5_zstd.ktx2"),
             brightness: light_consts::lux::DIRECT_SUNLIGHT,
@@ -129,7 +136,7 @@ fn setup(
 
     let text_font = TextFont::default();
 
-    commands.spawn((Text::new("Left / Right - Rotate Camera\nC - Toggle Compensation Curve\nM - Toggle Metering Mask\nV - Visualize Metering Mask"),
+    commands.spawn((Text::new("Left / Right - Rotate Camera\nC - Toggle Compensation Curve\nM - Toggle Metering Mask\nV - Visualize Metering Mask\nE - Cycle Metering Mode\nL - Toggle Exposure Lock"),
             text_font.clone(), Node {
             position_type: PositionType::Absolute,
             top: Val::Px(12.0),
@@ -161,14 +168,14 @@ struct ExampleResources {
 }
 
 fn example_control_system(
-    camera: Single<(&mut Transform, &mut AutoExposure), With<Camera3d>>,
+    camera: Single<(&mut Transform, &mut AutoExposure, &AutoExposureReadback), With<Camera3d>>,
     mut display: Single<&mut Text, With<ExampleDisplay>>,
     mut mask_image: Single<&mut Node, With<ImageNode>>,
     time: Res<Time>,
     input: Res<ButtonInput<KeyCode>>,
     resources: Res<ExampleResources>,
 ) {
-    let (mut camera_transform, mut auto_exposure) = camera.into_inner();
+    let (mut camera_transform, mut auto_exposure, readback) = camera.into_inner();
 
     let rotation = if input.pressed(KeyCode::ArrowLeft) {
         time.delta_secs()
@@ -198,6 +205,24 @@ fn example_control_system(
             };
     }
 
+    if input.just_pressed(KeyCode::KeyE) {
+        auto_exposure.metering = match auto_exposure.metering {
+            AutoExposureMetering::Average => AutoExposureMetering::CenterWeighted { radius: 0.5 },
+            AutoExposureMetering::CenterWeighted { .. } => {
+                AutoExposureMetering::Spot { radius: 0.1 }
+            }
+            AutoExposureMetering::Spot { .. } => AutoExposureMetering::Matrix,
+            AutoExposureMetering::Matrix => AutoExposureMetering::Average,
+        };
+    }
+
+    if input.just_pressed(KeyCode::KeyL) {
+        auto_exposure.control = match auto_exposure.control {
+            AutoExposureControl::Automatic => AutoExposureControl::Locked,
+            _ => AutoExposureControl::Automatic,
+        };
+    }
+
     mask_image.display = if input.pressed(KeyCode::KeyV) {
         Display::Flex
     } else {
@@ -205,7 +230,7 @@ fn example_control_system(
     };
 
     display.0 = format!(
-        "Compensation Curve: {}\nMetering Mask: {}",
+        "Compensation Curve: {}\nMetering Mask: {}\nMetering Mode: {:?}\nControl: {:?}\nTarget Exposure: {:.2}\nExposure: {:.2}",
         if auto_exposure.compensation_curve == resources.basic_compensation_curve {
             "Enabled"
         } else {
@@ -216,5 +241,9 @@ fn example_control_system(
         } else {
             "Disabled"
         },
+        auto_exposure.metering,
+        auto_exposure.control,
+        readback.target_exposure,
+        readback.exposure,
     );
 }
@@ -129,14 +129,15 @@ fn adjust_focus(input: Res<ButtonInput<KeyCode>>, mut app_settings: ResMut<AppSe
         (app_settings.aperture_f_stops + f_stop_delta).max(MIN_APERTURE_F_STOPS);
 }
 
-/// Changes the depth of field mode (Gaussian, bokeh, off) per user inputs.
+/// Changes the depth of field mode (Gaussian, bokeh, aperture, off) per user inputs.
 fn change_mode(input: Res<ButtonInput<KeyCode>>, mut app_settings: ResMut<AppSettings>) {
     if !input.just_pressed(KeyCode::Space) {
         return;
     }
 
     app_settings.mode = match app_settings.mode {
-        Some(DepthOfFieldMode::Bokeh) => Some(DepthOfFieldMode::Gaussian),
+        Some(DepthOfFieldMode::Bokeh) => Some(DepthOfFieldMode::Aperture),
+        Some(DepthOfFieldMode::Aperture) => Some(DepthOfFieldMode::Gaussian),
         Some(DepthOfFieldMode::Gaussian) => None,
         None => Some(DepthOfFieldMode::Bokeh),
     }
@@ -256,6 +257,7 @@ Mode: {} (Press Space to change)",
             dof::calculate_focal_length(sensor_height, fov) * 1000.0,
             match mode {
                 DepthOfFieldMode::Bokeh => "Bokeh",
+                DepthOfFieldMode::Aperture => "Aperture",
                 DepthOfFieldMode::Gaussian => "Gaussian",
             }
         )
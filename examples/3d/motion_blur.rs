@@ -26,8 +26,7 @@ fn setup_camera(mut commands: Commands) {
         MotionBlur {
             shutter_angle: 1.0,
             samples: 2,
-            #[cfg(all(feature = "webgl2", target_arch = "wasm32", not(feature = "webgpu")))]
-            _webgl2_padding: Default::default(),
+            ..default()
         },
         // MSAA and Motion Blur together are not compatible on WebGL
         #[cfg(all(feature = "webgl2", target_arch = "wasm32", not(feature = "webgpu")))]
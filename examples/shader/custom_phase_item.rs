@@ -343,6 +343,7 @@ impl SpecializedRenderPipeline for CustomPhasePipeline {
                 alpha_to_coverage_enabled: false,
             },
             zero_initialize_workgroup_memory: false,
+            multiview: None,
         }
     }
 }
@@ -5,6 +5,8 @@
 //! To adapt this example for 2D, replace all instances of 3D structures (such as `Core3D`, etc.) with their corresponding 2D counterparts.
 //!
 //! This is a fairly low level example and assumes some familiarity with rendering concepts and wgpu.
+//! Simple full-screen effects like this one can instead implement `PostProcessEffect` and be added
+//! with a `PostProcessEffectPlugin`, which sets up the pipeline, the node and the graph edges.
 
 use bevy::{
     core_pipeline::{
@@ -287,6 +289,7 @@ impl FromWorld for PostProcessPipeline {
                 multisample: MultisampleState::default(),
                 push_constant_ranges: vec![],
                 zero_initialize_workgroup_memory: false,
+                multiview: None,
             });
 
         Self {
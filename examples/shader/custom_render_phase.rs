@@ -230,6 +230,7 @@ impl SpecializedMeshPipeline for StencilPipeline {
             // but it's not always possible
             multisample: MultisampleState::default(),
             zero_initialize_workgroup_memory: false,
+            multiview: None,
         })
     }
 }
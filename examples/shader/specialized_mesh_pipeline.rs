@@ -265,6 +265,7 @@ impl SpecializedMeshPipeline for CustomMeshPipeline {
                 ..MultisampleState::default()
             },
             zero_initialize_workgroup_memory: false,
+            multiview: None,
         })
     }
 }